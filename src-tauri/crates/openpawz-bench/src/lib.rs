@@ -39,6 +39,8 @@ pub fn make_message(
         tool_call_id: None,
         name: None,
         created_at: now(),
+        incomplete: false,
+        reasoning: None,
     }
 }
 
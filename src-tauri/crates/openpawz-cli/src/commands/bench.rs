@@ -113,6 +113,8 @@ fn run_quick(
                 tool_call_id: None,
                 name: None,
                 created_at: ts.clone(),
+                incomplete: false,
+                reasoning: None,
             };
             store.add_message(&msg).map_err(e)?;
         }
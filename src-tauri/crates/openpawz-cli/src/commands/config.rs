@@ -8,6 +8,7 @@ const ALLOWED_CONFIG_KEYS: &[&str] = &[
     "default_provider",
     "daily_budget_usd",
     "max_tool_rounds",
+    "max_tool_iterations",
     "max_tokens",
     "temperature",
     "system_prompt",
@@ -1,6 +1,7 @@
 use crate::OutputFormat;
 use clap::Subcommand;
 use openpawz_core::engine::sessions::SessionStore;
+use openpawz_core::engine::types::truncate_utf8;
 
 #[derive(Subcommand)]
 pub enum MemoryAction {
@@ -92,7 +93,7 @@ pub async fn run(
                         for m in &memories {
                             println!(
                                 "[{}] ({}, imp:{}) {}",
-                                &m.id[..8.min(m.id.len())],
+                                truncate_utf8(&m.id, 8),
                                 m.category,
                                 m.importance,
                                 truncate(&m.content, 100)
@@ -127,7 +128,7 @@ pub async fn run(
                         for m in &results {
                             println!(
                                 "[{}] ({}, imp:{}) {}",
-                                &m.id[..8.min(m.id.len())],
+                                truncate_utf8(&m.id, 8),
                                 m.category,
                                 m.importance,
                                 truncate(&m.content, 100)
@@ -155,7 +156,7 @@ pub async fn run(
                 }
                 OutputFormat::Quiet => println!("{}", id),
                 OutputFormat::Human => {
-                    println!("Stored memory {} ({})", &id[..8], category);
+                    println!("Stored memory {} ({})", truncate_utf8(&id, 8), category);
                 }
             }
             Ok(())
@@ -8,6 +8,7 @@ pub mod memory;
 pub mod metrics;
 pub mod project;
 pub mod providers;
+pub mod run;
 pub mod session;
 pub mod setup;
 pub mod status;
@@ -0,0 +1,52 @@
+use crate::OutputFormat;
+use openpawz_core::engine::cancel::CancelToken;
+use openpawz_core::engine::headless::{self, HeadlessOptions};
+use openpawz_core::engine::sessions::SessionStore;
+
+/// Run a single prompt against an agent and print the final answer.
+pub async fn run(
+    store: &SessionStore,
+    agent: &str,
+    prompt: &str,
+    model: Option<String>,
+    max_rounds: Option<u32>,
+    save: bool,
+    format: &OutputFormat,
+) -> Result<(), String> {
+    // Ctrl-C cancels the run cleanly instead of killing mid-write
+    let cancel = CancelToken::new();
+    let on_interrupt = cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            on_interrupt.cancel();
+        }
+    });
+
+    let opts = HeadlessOptions {
+        model,
+        max_rounds,
+        persist_session: save,
+        cancel: Some(cancel),
+    };
+    let result = headless::run_once_with(store, agent, prompt, &opts)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?
+            );
+        }
+        OutputFormat::Human => {
+            println!("{}", result.output);
+            eprintln!(
+                "\x1b[38;5;240m— {} · {} round(s) · {} tool call(s) · ${:.4}\x1b[0m",
+                result.model, result.rounds, result.tool_calls, result.cost_usd
+            );
+        }
+        OutputFormat::Quiet => println!("{}", result.output),
+    }
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use crate::OutputFormat;
 use clap::Subcommand;
 use openpawz_core::engine::sessions::SessionStore;
-use openpawz_core::engine::types::Task;
+use openpawz_core::engine::types::{truncate_utf8, Task};
 
 #[derive(Subcommand)]
 pub enum TaskAction {
@@ -188,7 +188,9 @@ pub fn run(store: &SessionStore, action: TaskAction, format: &OutputFormat) -> R
                     );
                 }
                 OutputFormat::Quiet => println!("{}", id),
-                OutputFormat::Human => println!("Created task '{}' ({})", title, &id[..8]),
+                OutputFormat::Human => {
+                    println!("Created task '{}' ({})", title, truncate_utf8(&id, 8))
+                }
             }
             Ok(())
         }
@@ -252,7 +254,7 @@ pub fn run(store: &SessionStore, action: TaskAction, format: &OutputFormat) -> R
                         for t in &tasks {
                             println!(
                                 "[{}] {} (cron: {})",
-                                &t.id[..8.min(t.id.len())],
+                                truncate_utf8(&t.id, 8),
                                 t.title,
                                 t.cron_schedule.as_deref().unwrap_or("-"),
                             );
@@ -107,6 +107,23 @@ enum Commands {
         #[command(subcommand)]
         action: commands::providers::ProvidersAction,
     },
+    /// Run a single prompt against an agent (headless) and print the answer
+    Run {
+        /// The prompt to send
+        prompt: String,
+        /// Agent ID
+        #[arg(long, short, default_value = "default")]
+        agent: String,
+        /// Model override (default: engine default model)
+        #[arg(long)]
+        model: Option<String>,
+        /// Maximum tool rounds (default: engine max_tool_rounds)
+        #[arg(long)]
+        max_rounds: Option<u32>,
+        /// Save the conversation as a session
+        #[arg(long)]
+        save: bool,
+    },
     /// Engine status and diagnostics
     Status,
     /// Comprehensive health check
@@ -158,6 +175,24 @@ async fn main() {
         Commands::Engram { action } => commands::engram::run(&store, action, &cli.output),
         Commands::Metrics { action } => commands::metrics::run(&store, action, &cli.output),
         Commands::Providers { action } => commands::providers::run(action, &cli.output),
+        Commands::Run {
+            prompt,
+            agent,
+            model,
+            max_rounds,
+            save,
+        } => {
+            commands::run::run(
+                &store,
+                &agent,
+                &prompt,
+                model,
+                max_rounds,
+                save,
+                &cli.output,
+            )
+            .await
+        }
         Commands::Status => commands::status::run(&store, &cli.output),
         Commands::Doctor => commands::doctor::run(&store, &cli.output),
         Commands::Bench { action } => commands::bench::run(&store, action, &cli.output),
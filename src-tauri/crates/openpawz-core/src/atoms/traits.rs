@@ -4,6 +4,7 @@
 
 use crate::atoms::types::{Message, ProviderKind, StreamChunk, ToolDefinition};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // ── Error type ─────────────────────────────────────────────────────────────
 
@@ -42,6 +43,53 @@ impl std::fmt::Display for ProviderError {
     }
 }
 
+/// Why a provider call failed, in terms the settings UI can act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderFailure {
+    /// The key was rejected (401 / 403).
+    InvalidKey,
+    /// The provider couldn't be reached (DNS, refused, timeout, TLS).
+    Connectivity,
+    /// Wrong base URL or model name (404).
+    NotFound,
+    /// The provider answered with some other error.
+    Upstream,
+}
+
+impl ProviderFailure {
+    /// Short, actionable message for settings.
+    pub fn hint(self) -> &'static str {
+        match self {
+            ProviderFailure::InvalidKey => "Invalid API key — check the key and its permissions",
+            ProviderFailure::Connectivity => {
+                "Can't reach the provider — check your network, proxy or base URL host"
+            }
+            ProviderFailure::NotFound => "Not found — check the base URL and model name",
+            ProviderFailure::Upstream => "The provider returned an error — try again later",
+        }
+    }
+}
+
+impl ProviderError {
+    /// Classify this error for the user.
+    pub fn failure(&self) -> ProviderFailure {
+        match self {
+            ProviderError::Auth(_) => ProviderFailure::InvalidKey,
+            ProviderError::Transport(_) => ProviderFailure::Connectivity,
+            ProviderError::ModelNotFound(_) => ProviderFailure::NotFound,
+            ProviderError::Api { status, .. } => match status {
+                401 | 403 => ProviderFailure::InvalidKey,
+                404 => ProviderFailure::NotFound,
+                _ => ProviderFailure::Upstream,
+            },
+            ProviderError::RateLimited { .. } | ProviderError::Unsupported(_) => {
+                ProviderFailure::Upstream
+            }
+        }
+    }
+}
+
 impl From<ProviderError> for String {
     fn from(e: ProviderError) -> Self {
         e.to_string()
@@ -15,6 +15,13 @@ pub struct ProviderConfig {
     pub api_key: String,
     pub base_url: Option<String>,
     pub default_model: Option<String>,
+    /// Azure OpenAI `api-version` query parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    /// Azure OpenAI model → deployment name. Unmapped models are assumed to
+    /// be deployed under their own name.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub deployments: std::collections::HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -22,6 +29,8 @@ pub struct ProviderConfig {
 pub enum ProviderKind {
     OpenAI,
     Anthropic,
+    /// Google Gemini (`generateContent` API)
+    #[serde(alias = "gemini")]
     Google,
     Ollama,
     OpenRouter,
@@ -34,6 +43,10 @@ pub enum ProviderKind {
     /// (OpenAI-compatible with `api-key` header + `api-version` query param).
     #[serde(alias = "azure_foundry")]
     AzureFoundry,
+    /// Azure OpenAI Service — deployment-based URLs, `api-version` query
+    /// param and `api-key` header.
+    #[serde(alias = "azure_openai")]
+    AzureOpenAI,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,6 +178,9 @@ pub enum EngineEvent {
         /// Estimated context token count at this point
         #[serde(skip_serializing_if = "Option::is_none")]
         context_tokens: Option<u32>,
+        /// Set when a configured HIL approval rule requires this prompt
+        #[serde(skip_serializing_if = "Option::is_none")]
+        approval_reason: Option<String>,
     },
     /// A tool finished executing
     #[serde(rename = "tool_result")]
@@ -179,6 +195,27 @@ pub enum EngineEvent {
         #[serde(skip_serializing_if = "Option::is_none")]
         duration_ms: Option<u64>,
     },
+    /// A tool started running (after any approval). Summaries are redacted
+    /// and size-capped for display — see `engine::tool_progress`.
+    #[serde(rename = "tool_start")]
+    ToolStart {
+        session_id: String,
+        run_id: String,
+        tool_call_id: String,
+        tool_name: String,
+        args_summary: String,
+    },
+    /// A tool started by `ToolStart` finished
+    #[serde(rename = "tool_end")]
+    ToolEnd {
+        session_id: String,
+        run_id: String,
+        tool_call_id: String,
+        tool_name: String,
+        result_summary: String,
+        success: bool,
+        duration_ms: u64,
+    },
     /// The full assistant turn is complete
     #[serde(rename = "complete")]
     Complete {
@@ -198,6 +235,9 @@ pub enum EngineEvent {
         /// Max rounds configured for this agent
         #[serde(skip_serializing_if = "Option::is_none")]
         max_rounds: Option<u32>,
+        /// Why the model stopped on the final round
+        #[serde(skip_serializing_if = "Option::is_none")]
+        finish_reason: Option<FinishReason>,
     },
     /// A thinking/reasoning delta from extended-thinking models
     #[serde(rename = "thinking_delta")]
@@ -214,6 +254,14 @@ pub enum EngineEvent {
         tool_name: String,
         tool_call_id: String,
     },
+    /// Every run slot is busy; this run starts when one frees up
+    #[serde(rename = "run_queued")]
+    RunQueued {
+        session_id: String,
+        run_id: String,
+        active_runs: usize,
+        max_runs: usize,
+    },
     /// An error occurred during the run
     #[serde(rename = "error")]
     Error {
@@ -421,6 +469,18 @@ pub struct Session {
     pub message_count: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub agent_id: Option<String>,
+    /// Session this one was forked from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_session_id: Option<String>,
+    /// Message in the parent the fork was taken at (the last one copied).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forked_from_message_id: Option<String>,
+    /// Hidden from the default session list (see engine::session_archive).
+    #[serde(default)]
+    pub archived: bool,
+    /// One-line summary stored when the session was archived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -433,6 +493,14 @@ pub struct StoredMessage {
     pub tool_call_id: Option<String>,
     pub name: Option<String>,
     pub created_at: String,
+    /// An assistant reply saved while streaming whose run never finished
+    /// (cancelled, crashed or disconnected) — the text may be cut short.
+    #[serde(default)]
+    pub incomplete: bool,
+    /// Reasoning a thinking model produced before this reply, kept apart
+    /// from `content` so it is never re-sent as history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -462,6 +530,14 @@ pub struct ChatRequest {
     /// panel. These are merged with the hardcoded `auto_approved_tools` list.
     #[serde(default)]
     pub user_approved_tools: Vec<String>,
+    /// Edit-and-resend: replace this earlier user message with `message` and
+    /// drop the turns after it, instead of appending a new user message.
+    #[serde(default)]
+    pub edit_message_id: Option<String>,
+    /// Regenerate: answer the session's last user message again, replacing
+    /// the previous reply. `message` is ignored.
+    #[serde(default)]
+    pub regenerate: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -482,6 +558,22 @@ pub struct ChatResponse {
     pub session_id: String,
 }
 
+/// Why the model stopped generating, normalized across providers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinishReason {
+    /// Natural end of the turn (or a stop sequence)
+    Stop,
+    /// Hit the output token limit mid-response
+    Length,
+    /// Stopped to call tools
+    ToolCalls,
+    /// Blocked by the provider's safety / content filter
+    ContentFilter,
+    /// Anything the provider reports that we don't recognize
+    Other,
+}
+
 #[derive(Debug, Clone)]
 pub struct StreamChunk {
     pub delta_text: Option<String>,
@@ -621,6 +713,12 @@ pub(crate) fn default_max_trade() -> f64 {
 pub(crate) fn default_max_daily() -> f64 {
     500.0
 }
+pub(crate) fn default_memory_chunk_chars() -> usize {
+    1200
+}
+pub(crate) fn default_memory_chunk_overlap() -> usize {
+    200
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingPolicy {
@@ -644,6 +742,48 @@ pub struct TradingPolicy {
     pub max_transfer_usd: f64,
 }
 
+/// What happens to a chat run started while every run slot is taken
+/// (see engine::run_limiter).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunLimitPolicy {
+    /// Wait until a slot frees up.
+    #[default]
+    Queue,
+    /// Fail immediately.
+    Reject,
+}
+
+/// One knob for how much an agent may do without asking (see
+/// engine::autonomy for what each level derives).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutonomyLevel {
+    /// Ask before any tool that changes something.
+    Manual,
+    /// The built-in tier policy: local changes run, external ones ask.
+    #[default]
+    Assisted,
+    /// Run everything; only financial tools at or above a threshold ask.
+    Autonomous,
+}
+
+/// An agent's autonomy level plus individual overrides of what it derives.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentAutonomy {
+    #[serde(default)]
+    pub level: AutonomyLevel,
+    /// Per-tool rules checked before the level's defaults.
+    #[serde(default)]
+    pub rules: Vec<crate::engine::approval_rules::ApprovalRule>,
+    /// Replaces the level's cap on tool calls per run.
+    #[serde(default)]
+    pub max_tool_iterations: Option<u32>,
+    /// USD amount at which an autonomous agent's financial tools ask first.
+    #[serde(default)]
+    pub financial_threshold_usd: Option<f64>,
+}
+
 /// Which embedding backend to use.
 ///
 /// - `"auto"` — try Ollama first, then fall back to the user's chat provider
@@ -672,6 +812,10 @@ pub struct MemoryConfig {
     pub embedding_base_url: String,
     /// Embedding model name (e.g., "nomic-embed-text", "text-embedding-3-small")
     pub embedding_model: String,
+    /// API key for an OpenAI-compatible embedding endpoint. When set, embeddings
+    /// go to `embedding_base_url` with this key instead of the chat provider.
+    #[serde(default)]
+    pub embedding_api_key: String,
     /// Embedding dimensions (e.g., 768 for nomic-embed-text, 384 for all-minilm)
     pub embedding_dims: usize,
     /// Whether to auto-recall relevant memories before each turn
@@ -682,6 +826,13 @@ pub struct MemoryConfig {
     pub recall_limit: usize,
     /// Minimum similarity score for auto-recall (0.0–1.0)
     pub recall_threshold: f64,
+    /// Memories longer than this many characters are also embedded as
+    /// separate chunks, so a fact deep inside a pasted doc can be found.
+    #[serde(default = "default_memory_chunk_chars")]
+    pub chunk_chars: usize,
+    /// Characters shared by consecutive chunks.
+    #[serde(default = "default_memory_chunk_overlap")]
+    pub chunk_overlap: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -713,6 +864,18 @@ pub struct ModelRouting {
     pub auto_tier: bool,
 }
 
+/// User correction for a model's capabilities (`EngineConfig::model_capabilities`).
+/// Unset fields keep the built-in registry value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCapabilityOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_tools: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_vision: Option<bool>,
+}
+
 pub(crate) fn default_user_timezone() -> String {
     "America/Chicago".to_string()
 }
@@ -722,9 +885,15 @@ pub(crate) fn default_daily_budget_usd() -> f64 {
 pub(crate) fn default_max_concurrent_runs() -> u32 {
     4
 }
+pub(crate) fn default_show_reasoning() -> bool {
+    true
+}
 pub(crate) fn default_context_window_tokens() -> usize {
     32_000
 }
+pub(crate) fn default_max_tool_iterations() -> u32 {
+    100
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngineConfig {
@@ -733,6 +902,11 @@ pub struct EngineConfig {
     pub default_model: Option<String>,
     pub default_system_prompt: Option<String>,
     pub max_tool_rounds: u32,
+    /// Total tool calls allowed in a single run (across all rounds). When
+    /// exceeded the model is told to conclude; if it keeps calling tools the
+    /// run is stopped. Set to 0 to disable.
+    #[serde(default = "default_max_tool_iterations")]
+    pub max_tool_iterations: u32,
     pub tool_timeout_secs: u64,
     /// IANA timezone for local time display (e.g. "America/Chicago")
     #[serde(default = "default_user_timezone")]
@@ -740,13 +914,27 @@ pub struct EngineConfig {
     /// Model routing for multi-agent orchestration
     #[serde(default)]
     pub model_routing: ModelRouting,
-    /// Maximum simultaneous agent runs (chat + cron + manual). Chat always gets priority.
+    /// Maximum simultaneous agent runs (chat + cron + manual).
     #[serde(default = "default_max_concurrent_runs")]
     pub max_concurrent_runs: u32,
+    /// Whether a chat run past `max_concurrent_runs` waits or is rejected.
+    #[serde(default)]
+    pub run_limit_policy: RunLimitPolicy,
     /// Daily budget in USD.  When estimated spend exceeds this, new API calls
     /// are blocked and an error is returned.  Set to 0 to disable.
     #[serde(default = "default_daily_budget_usd")]
     pub daily_budget_usd: f64,
+    /// Per-agent daily budgets in USD, keyed by agent id (e.g. a trading
+    /// agent capped lower than the chat agent). Enforced alongside the global
+    /// budget — whichever is hit first stops the run. Missing or 0 = no cap.
+    #[serde(default)]
+    pub agent_budgets_usd: std::collections::HashMap<String, f64>,
+    /// IANA timezone the daily budgets reset in. None = `user_timezone`.
+    #[serde(default)]
+    pub budget_timezone: Option<String>,
+    /// Local hour (0–23) at which the daily budgets reset. Default midnight.
+    #[serde(default)]
+    pub budget_reset_hour: u32,
     /// Context window size in tokens.  Controls how much conversation history
     /// the agent sees.  Higher = better topic tracking but more cost.
     /// Default 32K.  Models support 128K-1M, so this is conservative.
@@ -756,6 +944,22 @@ pub struct EngineConfig {
     /// If empty, auto-detected via IP geolocation.
     #[serde(default)]
     pub weather_location: Option<String>,
+    /// Capability overrides keyed by model id or id prefix
+    /// (e.g. {"llama3.2": {"supports_tools": true}}).
+    #[serde(default)]
+    pub model_capabilities: std::collections::HashMap<String, ModelCapabilityOverride>,
+    /// User-editable layout for the base system prompt, with `{variable}`
+    /// placeholders (see engine::prompt_template). None = built-in layout.
+    #[serde(default)]
+    pub system_prompt_template: Option<String>,
+    /// Stream and show the reasoning of thinking models in chat. Reasoning is
+    /// stored either way; it is never re-sent to the model as history.
+    #[serde(default = "default_show_reasoning")]
+    pub show_reasoning: bool,
+    /// Language for user-facing engine messages (e.g. "es", "pt-BR").
+    /// None = English. Model-facing text is always English.
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -863,6 +1067,32 @@ pub struct AgentMessage {
     pub created_at: String,
 }
 
+// ── Agent Definitions ──────────────────────────────────────────────────────
+
+/// The stored definition of an agent: who it is and how it runs. Read by
+/// prompt assembly (persona, skills) and model routing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentDefinition {
+    pub id: String,
+    pub name: String,
+    /// Persona / system prompt. None = the engine's default system prompt.
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// Default model. None = the engine's default model.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Skill ids enabled for this agent. Empty = the globally enabled skills.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// How much the agent may do without asking.
+    #[serde(default)]
+    pub autonomy: AgentAutonomy,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
 // ── Agent Squads ───────────────────────────────────────────────────────────
 
 /// A named group of agents that can be assigned goals collectively.
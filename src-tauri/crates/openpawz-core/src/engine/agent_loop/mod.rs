@@ -0,0 +1,1529 @@
+// Paw Agent Engine — Agentic Loop
+// The core orchestration loop: send to model → tool calls → execute → repeat.
+// The desktop app and the headless runner both drive their turns through
+// `run_turn`; everything that depends on where the loop runs (event delivery,
+// tool execution, approval prompts, spend tracking) goes through `TurnHost`.
+
+pub mod helpers;
+
+use crate::atoms::engram_types::{CheckpointMessage, WorkingMemorySnapshot};
+use crate::atoms::error::EngineResult;
+use crate::engine::approval_rules::{self, ApprovalDecision};
+use crate::engine::autonomy::AutonomyPolicy;
+use crate::engine::cancel::{run_cancellable, CancelToken};
+use crate::engine::constrained::{self, ConstraintLevel};
+use crate::engine::dex_allowance;
+use crate::engine::emergency;
+use crate::engine::engram::context_continuity::{capture_checkpoint, CaptureCheckpointRequest};
+use crate::engine::partial_reply::PartialReply;
+use crate::engine::providers::AnyProvider;
+use crate::engine::run_trace::RunTrace;
+use crate::engine::sessions::SessionStore;
+use crate::engine::tool_guard::{IterationVerdict, ToolIterationGuard};
+use crate::engine::tool_metadata::{self, ToolTier};
+use crate::engine::tool_progress;
+use crate::engine::types::*;
+use async_trait::async_trait;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Tool result recorded for calls skipped or interrupted by cancellation.
+pub const CANCELLED_TOOL_OUTPUT: &str = "Tool execution cancelled by user.";
+
+/// Result of a denied tool call, as the model and the UI see it.
+const DENIED_TOOL_OUTPUT: &str = "Tool execution denied by user.";
+
+/// Circuit breaker: after this many consecutive failures of the same tool,
+/// inject a system nudge.
+const MAX_CONSECUTIVE_TOOL_FAILS: u32 = 3;
+/// After this many, block further execution of that tool entirely.
+const HARD_STOP_TOOL_FAILS: u32 = 5;
+
+/// If the same tool-call signature appears this many rounds in a row, the
+/// model is stuck in a tool-calling loop.
+const MAX_REPEATED_SIGNATURES: usize = 3;
+
+// ── Host ───────────────────────────────────────────────────────────────
+
+/// What the loop needs from the environment it runs in.
+///
+/// The desktop app implements this over its Tauri handle and engine state;
+/// the headless runner over a bare `SessionStore`. Hooks with a default do
+/// nothing (or decline) unless the host has something to add.
+#[async_trait]
+pub trait TurnHost: Send + Sync {
+    /// The database, for approvals, audit, traces and checkpoints.
+    fn store(&self) -> Option<&SessionStore>;
+
+    /// Deliver an engine event (tool progress, completion, errors).
+    fn emit(&self, event: EngineEvent);
+
+    /// Deliver streamed reply text. Hosts may batch it; pending text must be
+    /// delivered by [`TurnHost::completed`] at the latest.
+    fn stream_text(&self, text: &str);
+
+    /// A newer user message is waiting — wrap the turn up early.
+    fn yield_requested(&self) -> bool {
+        false
+    }
+
+    /// Message explaining why the next model call would exceed a spend
+    /// limit, or `None` to go ahead.
+    fn budget_exceeded(&self) -> Option<String> {
+        None
+    }
+
+    /// Called after every model call with that round's token usage.
+    fn record_usage(&self, _round: u32, _usage: &RoundUsage) {}
+
+    /// Correction to send when the first reply ignores the user's message.
+    fn grounding_check(&self, _messages: &[Message], _reply: &str) -> Option<String> {
+        None
+    }
+
+    /// USD price of one unit of the token a trade tool spends.
+    async fn quote_unit_price_usd(&self, _tool_name: &str, _args: &str) -> Option<f64> {
+        None
+    }
+
+    /// Whether the trading policy lets a dangerous trade tool run unprompted.
+    fn trading_auto_approve(&self, _tool_name: &str, _args: &str) -> bool {
+        false
+    }
+
+    /// Ask the user to approve a gated tool call. Returns the decision and
+    /// who made it (`"user"`, `"timeout"`, `"cancelled"`, …).
+    async fn request_approval(
+        &self,
+        request: ApprovalRequest<'_>,
+        cancel: &CancelToken,
+    ) -> (bool, &'static str);
+
+    /// Run an `execute_plan` call as a DAG. `None` when the host has no
+    /// planner; the call then runs like any other tool.
+    async fn execute_plan(
+        &self,
+        _call: &ToolCall,
+        _tools: &[ToolDefinition],
+    ) -> Option<PlanOutcome> {
+        None
+    }
+
+    /// Execute one approved tool call.
+    async fn execute_tool(&self, call: &ToolCall) -> ToolResult;
+
+    /// Called after each executed tool call.
+    fn tool_finished(&self, _call: &ToolCall, _result: &ToolResult, _duration_ms: u64) {}
+
+    /// Pick up tools loaded mid-turn (e.g. by `request_tools`).
+    fn refresh_tools(&self, _tools: &mut Vec<ToolDefinition>) {}
+
+    /// Called once when the model produced its final answer.
+    fn completed(&self, _stats: &TurnStats) {}
+}
+
+/// A gated tool call awaiting the user's decision.
+pub struct ApprovalRequest<'a> {
+    pub call: &'a ToolCall,
+    /// "safe", "reversible", "external" or "dangerous".
+    pub tier: &'static str,
+    /// The round the call was made in, 1-based.
+    pub round: u32,
+    /// Why an approval rule asked for the prompt, if one did.
+    pub reason: Option<String>,
+}
+
+/// Outcome of a plan handed to [`TurnHost::execute_plan`].
+pub struct PlanOutcome {
+    /// Tool result fed back to the model.
+    pub output: String,
+    /// Nodes executed (0 when the plan was rejected).
+    pub nodes: u32,
+}
+
+/// Token usage of one model call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+}
+
+// ── Turn ───────────────────────────────────────────────────────────────
+
+/// Settings for one turn.
+pub struct TurnParams<'a> {
+    pub model: &'a str,
+    pub session_id: &'a str,
+    pub run_id: &'a str,
+    pub agent_id: &'a str,
+    pub max_rounds: u32,
+    pub temperature: Option<f64>,
+    pub thinking_level: Option<&'a str>,
+    /// Run every tool without asking (agent policy).
+    pub auto_approve_all: bool,
+    /// Tools the user chose to "Always Allow".
+    pub user_approved_tools: &'a [String],
+    /// `max_tool_iterations` from the engine config (0 = unlimited).
+    pub max_tool_iterations: u32,
+    /// Stream reasoning to the UI (it is kept either way).
+    pub show_reasoning: bool,
+    /// History size, in estimated tokens, above which it is truncated mid-turn.
+    pub context_window_tokens: usize,
+}
+
+/// How a turn ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnStatus {
+    /// The model gave a final answer.
+    Completed,
+    /// A newer user message took over.
+    Yielded,
+    /// The user pressed Stop.
+    Cancelled,
+    /// `max_rounds` ran out before a final answer.
+    MaxRounds,
+    /// The model kept repeating the same tool calls.
+    ToolLoop,
+    /// The model kept calling tools past `max_tool_iterations`.
+    ToolLimit,
+}
+
+/// Counters for one turn.
+#[derive(Debug, Clone, Default)]
+pub struct TurnStats {
+    pub rounds: u32,
+    pub tool_calls: u32,
+    /// Input tokens of the last round (= the context actually sent).
+    pub input_tokens: u64,
+    /// Output tokens summed over all rounds.
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub tool_duration_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// The reply text (empty when cancelled) and how the turn ended.
+#[derive(Debug, Clone)]
+pub struct TurnOutcome {
+    pub text: String,
+    pub status: TurnStatus,
+    pub stats: TurnStats,
+}
+
+/// Run a complete agent turn: send messages to the model, execute tool calls,
+/// and repeat until the model produces a final text response or a limit hits.
+///
+/// Spend limits and provider errors are returned as `Err`; every other way the
+/// turn can stop is a [`TurnOutcome`] whose text is what the user should see.
+pub async fn run_turn(
+    host: &dyn TurnHost,
+    provider: &AnyProvider,
+    messages: &mut Vec<Message>,
+    tools: &mut Vec<ToolDefinition>,
+    params: &TurnParams<'_>,
+    cancel: Option<&CancelToken>,
+    partial_reply: Option<&PartialReply>,
+) -> EngineResult<TurnOutcome> {
+    // Emergency stop: no new runs while halted, and a later stop cancels
+    // this one the same way the Stop button does.
+    emergency::ensure_armed()?;
+    let run_token = cancel.cloned().unwrap_or_default();
+    let _halt_watch = emergency::watch(&run_token);
+
+    let mut turn = Turn {
+        host,
+        params,
+        cancel: run_token,
+        trace: RunTrace::new(params.session_id, params.run_id),
+        stats: TurnStats::default(),
+        started: Instant::now(),
+    };
+    turn.run(provider, messages, tools, partial_reply).await
+}
+
+/// State of one running turn.
+struct Turn<'a> {
+    host: &'a dyn TurnHost,
+    params: &'a TurnParams<'a>,
+    cancel: CancelToken,
+    /// Ordered LLM/tool spans for this run, persisted per session on exit.
+    trace: RunTrace,
+    stats: TurnStats,
+    started: Instant,
+}
+
+impl Turn<'_> {
+    async fn run(
+        &mut self,
+        provider: &AnyProvider,
+        messages: &mut Vec<Message>,
+        tools: &mut Vec<ToolDefinition>,
+        partial_reply: Option<&PartialReply>,
+    ) -> EngineResult<TurnOutcome> {
+        let host = self.host;
+        let params = self.params;
+        let (model, max_rounds) = (params.model, params.max_rounds);
+        let store = host.store();
+        let partial_reply = partial_reply.zip(store);
+
+        let mut round = 0;
+        let mut continued_text = String::new(); // Text from rounds cut off by the output limit
+        let mut length_continuations: u32 = 0;
+
+        // Circuit breaker: consecutive failures per tool name.
+        let mut tool_fail_counter: HashMap<String, u32> = HashMap::new();
+
+        // Repetition detector: the tool-call "signature" (hashed tool names
+        // + args) of each round. The same signature MAX_REPEATED_SIGNATURES
+        // times in a row means the model is stuck in a tool-calling loop
+        // (common after model/context changes mid-conversation).
+        let mut round_signatures: Vec<u64> = Vec::new();
+
+        // The agent's autonomy level: derives approval gating (applied after the
+        // global HIL rules) and the tool-call cap below.
+        let autonomy = store
+            .and_then(|s| s.get_agent_definition(params.agent_id).ok().flatten())
+            .map(|agent| AutonomyPolicy::derive(&agent.autonomy))
+            .unwrap_or_default();
+
+        // Tool-iteration guard: caps total tool calls per run (max_tool_iterations),
+        // catching loops the signature detector misses (varying args).
+        let mut tool_guard =
+            ToolIterationGuard::new(autonomy.max_tool_iterations(params.max_tool_iterations));
+
+        // User-configured HIL rules (require / auto / USD threshold per tool).
+        // Loaded once per turn; they take precedence over the tier defaults.
+        let hil_rules = store.map(approval_rules::load_rules).unwrap_or_default();
+
+        loop {
+            round += 1;
+            self.stats.rounds = round;
+
+            // ── Yield check: if a new user message was queued, wrap up gracefully ─
+            // VS Code pattern: when yield is requested, the agent stops its loop
+            // and returns whatever it has so far.  The queued message will be
+            // processed next by the request queue handler.
+            if host.yield_requested() {
+                warn!(
+                    "[engine] Yield requested — wrapping up agent turn at round {}",
+                    round
+                );
+                let text = "I was wrapping up to handle your new message. \
+                    My previous work may be incomplete."
+                    .to_string();
+                self.emit_complete(&text, round);
+                return Ok(self.finish(TurnStatus::Yielded, text, round, true, ""));
+            }
+
+            // ── Cancellation check: user pressed Stop (engine_cancel_run) ──
+            // Every tool call already in `messages` has a result, so the partial
+            // turn can be persisted as-is by the caller.
+            if self.cancel.is_cancelled() {
+                warn!("[engine] Run cancelled by user at round {}", round);
+                return Ok(self.finish_cancelled(round));
+            }
+
+            if round > max_rounds {
+                warn!(
+                    "[engine] Max tool rounds ({}) reached, stopping",
+                    max_rounds
+                );
+                let text = format!(
+                    "I completed {} tool-call rounds but ran out of steps before I could \
+                    write a final summary.  You can continue the conversation or increase \
+                    the max tool rounds in Settings → Engine (currently {}).",
+                    max_rounds, max_rounds
+                );
+                // Emit the fallback text so the frontend shows *something*
+                self.emit_complete(&text, round);
+                return Ok(self.finish(TurnStatus::MaxRounds, text, round, false, ""));
+            }
+
+            info!(
+                "[engine] Agent round {}/{} session={} run={}",
+                round, max_rounds, params.session_id, params.run_id
+            );
+
+            // ── Budget check: stop before making the API call if over the
+            // global or this agent's daily limit
+            if let Some(msg) = host.budget_exceeded() {
+                warn!("[engine] {}", msg);
+                host.emit(EngineEvent::Error {
+                    session_id: params.session_id.to_string(),
+                    run_id: params.run_id.to_string(),
+                    message: msg.clone(),
+                });
+                self.finish_trace(round, "budget_exceeded", false, &msg);
+                return Err(msg.into());
+            }
+
+            // ── 1. Call the AI model ──────────────────────────────────────
+            if let Some((reply, _)) = &partial_reply {
+                reply.begin_round();
+            }
+            let llm_start = Instant::now();
+            let streamed = run_cancellable(
+                Some(&self.cancel),
+                provider.chat_stream(
+                    messages,
+                    tools,
+                    model,
+                    params.temperature,
+                    params.thinking_level,
+                ),
+            )
+            .await;
+            let Some(streamed) = streamed else {
+                warn!("[engine] Run cancelled by user during model call");
+                let llm_ms = llm_start.elapsed().as_millis() as u64;
+                self.trace
+                    .llm(round, model, 0, 0, 0, llm_ms, Some("cancelled"));
+                return Ok(self.finish_cancelled(round));
+            };
+            let chunks = match streamed {
+                Ok(chunks) => chunks,
+                Err(e) => {
+                    let err = e.to_string();
+                    let llm_ms = llm_start.elapsed().as_millis() as u64;
+                    self.trace.llm(round, model, 0, 0, 0, llm_ms, Some(&err));
+                    self.finish_trace(round, "error", false, &err);
+                    return Err(e);
+                }
+            };
+            let llm_ms = llm_start.elapsed().as_millis() as u64;
+
+            // ── 2. Assemble the response from chunks ──────────────────────
+            for chunk in &chunks {
+                if let Some(dt) = &chunk.delta_text {
+                    if let Some((reply, store)) = &partial_reply {
+                        reply.push(store, dt);
+                    }
+                    host.stream_text(dt);
+                }
+                // Reasoning is kept apart from the reply, and streamed to the
+                // frontend unless hidden
+                if let Some(tt) = &chunk.thinking_text {
+                    if let Some((reply, _)) = &partial_reply {
+                        reply.push_reasoning(tt);
+                    }
+                    if params.show_reasoning {
+                        host.emit(EngineEvent::ThinkingDelta {
+                            session_id: params.session_id.to_string(),
+                            run_id: params.run_id.to_string(),
+                            text: tt.clone(),
+                        });
+                    }
+                }
+            }
+            // The round's text is complete — save it before tools run
+            if let Some((reply, store)) = &partial_reply {
+                reply.flush(store);
+            }
+            let response = assemble(&chunks);
+            let text_accum = response.text;
+            let finish_reason = response.finish_reason;
+
+            // Input tokens reflect the full context sent each round, so only
+            // the LAST round's input counts; output tokens are incremental.
+            if let Some(input) = response.input_tokens {
+                self.stats.input_tokens = input;
+            }
+            let usage = RoundUsage {
+                input_tokens: self.stats.input_tokens,
+                output_tokens: response.output_tokens,
+                cache_read_tokens: response.cache_read_tokens,
+                cache_creation_tokens: response.cache_creation_tokens,
+            };
+            self.stats.output_tokens += usage.output_tokens;
+            self.stats.cache_read_tokens += usage.cache_read_tokens;
+            self.stats.cache_creation_tokens += usage.cache_creation_tokens;
+
+            self.trace.llm(
+                round,
+                model,
+                usage.input_tokens,
+                usage.output_tokens,
+                response.tool_calls.len(),
+                llm_ms,
+                None,
+            );
+            // Record this round's token usage against the daily budget
+            host.record_usage(round, &usage);
+
+            // ── 3. If no tool calls, we're done ──────────────────────────
+            if response.tool_calls.is_empty() {
+                if finish_reason == Some(FinishReason::ToolCalls) {
+                    warn!(
+                        "[engine] Model stopped for tool calls at round {} but none were parsed",
+                        round
+                    );
+                }
+
+                // Cut off by the output token limit → ask the model to keep going
+                if finish_reason == Some(FinishReason::Length)
+                    && helpers::handle_length_cutoff(
+                        &text_accum,
+                        messages,
+                        &mut length_continuations,
+                        round,
+                        max_rounds,
+                    )
+                {
+                    continued_text.push_str(&text_accum);
+                    continue;
+                }
+                let mut final_text = format!("{}{}", continued_text, text_accum);
+
+                // Retry on malformed tool calls (Gemini JSON issues)
+                // Skip retry when constrained decoding is active — the parse failure
+                // indicates a deeper issue, not a model formatting mistake.
+                let constrained_active = constrained::detect_constraints(provider.kind(), model)
+                    .level
+                    != ConstraintLevel::None;
+                if !constrained_active
+                    && helpers::handle_malformed_tool_call(&final_text, messages, round, max_rounds)
+                {
+                    continue;
+                }
+
+                // Blocked by the provider's safety filter — tell the user instead of
+                // retrying or returning a silently truncated answer.
+                let filtered = finish_reason == Some(FinishReason::ContentFilter);
+                if filtered {
+                    warn!(
+                        "[engine] Response stopped by content filter at round {} ({} chars)",
+                        round,
+                        final_text.len()
+                    );
+                    let notice = helpers::content_filter_notice(&final_text);
+                    host.stream_text(&notice);
+                    final_text.push_str(&notice);
+                }
+
+                // Retry on empty response (nudge with user recap)
+                if helpers::handle_empty_response(&final_text, messages, round, max_rounds) {
+                    continue;
+                }
+
+                // Persistent empty → fallback message
+                if final_text.is_empty() {
+                    warn!(
+                        "[engine] Model returned empty response (0 chars, 0 tool calls) at round {}",
+                        round
+                    );
+                    final_text = helpers::empty_response_fallback();
+                }
+
+                // ── Grounding check: verify response addresses user's message ──
+                // Run only once (round > 1 means we already retried) and only when
+                // the model produced substantive text (not a fallback).
+                if round == 1 && !filtered {
+                    if let Some(correction) = host.grounding_check(messages, &final_text) {
+                        // Push the ungrounded response as assistant message so the
+                        // model sees what it said, then inject the correction.
+                        messages.push(text_message(Role::Assistant, text_accum));
+                        messages.push(text_message(Role::System, correction));
+                        info!(
+                            "[engine] Grounding check failed at round {} — retrying with correction",
+                            round
+                        );
+                        continue;
+                    }
+                }
+
+                // Add assistant message to history
+                messages.push(text_message(Role::Assistant, text_accum));
+
+                // Emit completion event
+                let stats = &self.stats;
+                let token_usage = if stats.input_tokens > 0 || stats.output_tokens > 0 {
+                    Some(TokenUsage {
+                        input_tokens: stats.input_tokens,
+                        output_tokens: stats.output_tokens,
+                        total_tokens: stats.input_tokens + stats.output_tokens,
+                        cache_creation_tokens: usage.cache_creation_tokens,
+                        cache_read_tokens: usage.cache_read_tokens,
+                    })
+                } else {
+                    None
+                };
+                host.emit(EngineEvent::Complete {
+                    session_id: params.session_id.to_string(),
+                    run_id: params.run_id.to_string(),
+                    text: final_text.clone(),
+                    tool_calls_count: 0,
+                    usage: token_usage,
+                    model: response.model,
+                    total_rounds: Some(round),
+                    max_rounds: Some(max_rounds),
+                    finish_reason,
+                });
+
+                self.stats.duration_ms = self.started.elapsed().as_millis() as u64;
+                host.completed(&self.stats);
+                return Ok(self.finish(TurnStatus::Completed, final_text, round, true, ""));
+            }
+
+            // ── 4. Process tool calls ─────────────────────────────────────
+            let tool_calls = response.tool_calls;
+
+            // Add assistant message with tool calls to history
+            messages.push(Message {
+                role: Role::Assistant,
+                content: MessageContent::Text(text_accum),
+                tool_calls: Some(tool_calls.clone()),
+                tool_call_id: None,
+                name: None,
+            });
+
+            // ── Repetition detector: break tool-calling loops ──────────────
+            round_signatures.push(call_signature(&tool_calls));
+            if is_repeating(&round_signatures) {
+                // Check whether we (or detect_response_loop) already
+                // injected a loop/redirect message. If so, the model
+                // ignored the first nudge — hard-break to prevent
+                // unbounded redirect stacking.
+                let already_redirected = messages.iter().any(|m| {
+                    m.role == Role::System && {
+                        let t = m.content.as_text_ref();
+                        t.contains("stuck in a tool-calling loop")
+                            || t.contains("stuck in a response loop")
+                            || t.contains("stuck repeating yourself")
+                            || t.contains("TOPIC CHANGE")
+                            || t.contains("stuck asking clarifying questions")
+                    }
+                });
+                // Remove the assistant message we just pushed (it has the repeated tools)
+                messages.pop();
+                if already_redirected {
+                    warn!("[engine] Model ignored tool-loop redirect — hard-breaking agent turn");
+                    let text = "I was stuck calling the same tools repeatedly and couldn't make \
+                        progress. Please try rephrasing your request or switching context."
+                        .to_string();
+                    return Ok(self.finish(TurnStatus::ToolLoop, text, round, false, ""));
+                }
+
+                warn!(
+                    "[engine] Tool-call loop detected: same tool signature repeated {} times — injecting redirect",
+                    MAX_REPEATED_SIGNATURES
+                );
+                messages.push(text_message(
+                    Role::System,
+                    "[SYSTEM] You are stuck in a tool-calling loop — you have called the \
+                    same tools with the same arguments multiple times in a row. STOP calling \
+                    tools and provide a direct text response to the user summarizing what you \
+                    have accomplished and any issues encountered. Do NOT make any more tool calls."
+                        .to_string(),
+                ));
+                continue; // Go back to model call — it should now produce text
+            }
+
+            // ── Tool-iteration guard: nudge once, then stop ────────────────
+            match tool_guard.record(tool_calls.len()) {
+                IterationVerdict::Continue => {}
+                IterationVerdict::Nudge => {
+                    warn!(
+                        "[engine] Tool-call limit reached ({} calls) — asking model to conclude",
+                        tool_guard.count()
+                    );
+                    // Drop the over-limit calls unexecuted and ask for a final answer
+                    messages.pop();
+                    messages.push(text_message(Role::System, tool_guard.nudge_message()));
+                    continue;
+                }
+                IterationVerdict::Stop => {
+                    warn!("[engine] Model kept calling tools after limit nudge — stopping run");
+                    messages.pop();
+                    let text = tool_guard.stop_message();
+                    self.emit_complete(&text, round);
+                    return Ok(self.finish(TurnStatus::ToolLimit, text, round, false, ""));
+                }
+            }
+
+            // ── 5. Execute each tool call (with HIL approval) ──────────────
+            //
+            // Tool tiers (VS Code-inspired, adapted for Pawz multi-capability scope):
+            //
+            //  T1 — SAFE: Read-only, zero side effects → always auto-approve
+            //  T2 — REVERSIBLE: Local writes that can be undone (files, memory, tasks) → auto-approve
+            //  T3 — EXTERNAL: Irreversible outbound actions (send email, post to Slack,
+            //        create Google docs) → require approval, offer "Always Allow"
+            //  T4 — DANGEROUS: Shell exec, financial trades, destructive ops → always prompt
+            //
+            let tc_count = tool_calls.len();
+
+            // ── Plan interception: if the model called execute_plan, hand off
+            // to the host's DAG executor instead of tool-by-tool execution ──
+            if tc_count == 1 && tool_calls[0].function.name == "execute_plan" {
+                let tc = &tool_calls[0];
+                if let Some(plan) = host.execute_plan(tc, tools).await {
+                    self.stats.tool_calls += plan.nodes;
+                    messages.push(tool_message(tc, plan.output));
+                    // Continue the loop — model will synthesize results into a response
+                    continue;
+                }
+            }
+
+            for tc in &tool_calls {
+                // Cancelled mid-round: answer the remaining calls without running them
+                if self.cancel.is_cancelled() {
+                    messages.push(tool_message(tc, CANCELLED_TOOL_OUTPUT.into()));
+                    continue;
+                }
+                info!("[engine] Tool call: {} id={}", tc.function.name, tc.id);
+
+                // ─── Tool classification via centralized registry ───
+                let tool_name = tc.function.name.as_str();
+                let tool_tier = tool_metadata::call_tier(tool_name, &tc.function.arguments);
+                let auto_approved = matches!(tool_tier, ToolTier::Safe | ToolTier::Reversible);
+
+                // Trading write tools check the policy-based approval function
+                let is_trading_dangerous = tool_tier == ToolTier::Dangerous
+                    && (tool_name.starts_with("sol_")
+                        || tool_name.starts_with("dex_")
+                        || tool_name.starts_with("coinbase_"));
+
+                // ── Circuit breaker: block tools that already hit HARD_STOP ──
+                if let Some(count) = tool_fail_counter.get(&tc.function.name) {
+                    if *count >= HARD_STOP_TOOL_FAILS {
+                        warn!(
+                            "[engine] Circuit breaker: blocking '{}' (already failed {} times)",
+                            tc.function.name, count
+                        );
+                        messages.push(tool_message(
+                            tc,
+                            format!(
+                                "Error: Tool '{}' is blocked after {} consecutive failures. Use a different tool or tell the user.",
+                                tc.function.name, count
+                            ),
+                        ));
+                        continue;
+                    }
+                }
+
+                // ── DEX allowance: reject oversized trades before any approval prompt ──
+                if dex_allowance::ALLOWANCE_TOOLS.contains(&tool_name) {
+                    let verdict = store
+                        .map(|store| {
+                            let args: serde_json::Value =
+                                serde_json::from_str(&tc.function.arguments).unwrap_or_default();
+                            dex_allowance::enforce(store, tool_name, &args)
+                        })
+                        .unwrap_or(Ok(()));
+                    if let Err(reason) = verdict {
+                        warn!("[engine] {} rejected: {}", tool_name, reason);
+                        self.record_approval(tc, false, "allowance");
+                        self.emit_tool_result(tc, &reason, false, None);
+                        self.trace.tool(
+                            round,
+                            tool_name,
+                            &tc.function.arguments,
+                            &reason,
+                            false,
+                            0,
+                        );
+                        messages.push(tool_message(tc, format!("Error: {}", reason)));
+                        continue;
+                    }
+                }
+
+                // Threshold rules compare the trade's USD value, so quote the
+                // spent token first; without a quote they ask for approval.
+                let unit_price_usd =
+                    if hil_rules.needs_quote(tool_name) || autonomy.needs_quote(tool_name) {
+                        host.quote_unit_price_usd(tool_name, &tc.function.arguments)
+                            .await
+                    } else {
+                        None
+                    };
+                let mut rule_decision =
+                    hil_rules.evaluate(tool_name, &tc.function.arguments, unit_price_usd);
+                let mut rule_source = "rule";
+                if rule_decision == ApprovalDecision::NoRule {
+                    rule_decision =
+                        autonomy.evaluate(tool_name, &tc.function.arguments, unit_price_usd);
+                    rule_source = "autonomy";
+                }
+                let rule_reason = match &rule_decision {
+                    ApprovalDecision::Require(reason) | ApprovalDecision::AutoApprove(reason) => {
+                        info!("[engine] {} → {}", tool_name, reason);
+                        Some(reason.clone())
+                    }
+                    ApprovalDecision::NoRule => None,
+                };
+
+                let user_approved = params
+                    .user_approved_tools
+                    .iter()
+                    .any(|t| t == &tc.function.name);
+                let skip_hil = match rule_decision {
+                    ApprovalDecision::Require(_) => false,
+                    ApprovalDecision::AutoApprove(_) => true,
+                    ApprovalDecision::NoRule => {
+                        params.auto_approve_all
+                            || auto_approved
+                            || user_approved
+                            || (is_trading_dangerous
+                                && host.trading_auto_approve(tool_name, &tc.function.arguments))
+                    }
+                };
+
+                // Who made the call, for the approvals audit log. Safe tools that
+                // auto-run without any gating are not logged (`None`).
+                let (approved, decider) = if skip_hil {
+                    let decider = if matches!(rule_decision, ApprovalDecision::AutoApprove(_)) {
+                        Some(rule_source)
+                    } else if auto_approved {
+                        None
+                    } else if params.auto_approve_all {
+                        Some("agent_policy")
+                    } else if user_approved {
+                        Some("user_allowlist")
+                    } else {
+                        Some("trading_policy")
+                    };
+                    // Distinguish agent-level auto-approve from safe-tool auto-approve in logs
+                    if params.auto_approve_all && !auto_approved {
+                        info!(
+                            "[engine] Tool auto-approved (agent policy): {}",
+                            tc.function.name
+                        );
+                        // Emit audit event so frontend can track agent-policy approvals
+                        host.emit(EngineEvent::ToolAutoApproved {
+                            session_id: params.session_id.to_string(),
+                            run_id: params.run_id.to_string(),
+                            tool_name: tc.function.name.clone(),
+                            tool_call_id: tc.id.clone(),
+                        });
+                    } else {
+                        info!("[engine] Auto-approved safe tool: {}", tc.function.name);
+                    }
+                    (true, decider)
+                } else {
+                    info!("[engine] Tool requires user approval: {}", tc.function.name);
+                    let request = ApprovalRequest {
+                        call: tc,
+                        tier: tier_label(tool_tier),
+                        round,
+                        reason: rule_reason,
+                    };
+                    let (approved, decider) = host.request_approval(request, &self.cancel).await;
+                    (approved, Some(decider))
+                };
+                let approval_id = decider.and_then(|d| self.record_approval(tc, approved, d));
+
+                if !approved {
+                    info!(
+                        "[engine] Tool DENIED by user: {} id={}",
+                        tc.function.name, tc.id
+                    );
+                    if let Some(store) = store {
+                        crate::engine::audit::log_tool_denied(
+                            store,
+                            params.agent_id,
+                            params.session_id,
+                            &tc.function.name,
+                            &tc.id,
+                        );
+                    }
+                    self.emit_tool_result(tc, DENIED_TOOL_OUTPUT, false, None);
+                    self.trace.tool(
+                        round,
+                        &tc.function.name,
+                        &tc.function.arguments,
+                        DENIED_TOOL_OUTPUT,
+                        false,
+                        0,
+                    );
+                    // Add denial to message history so the model knows
+                    messages.push(tool_message(tc, DENIED_TOOL_OUTPUT.into()));
+                    continue;
+                }
+
+                // Execute the tool
+                let tool_start = Instant::now();
+                let result = tool_progress::run_with_progress(
+                    |event| host.emit(event),
+                    params.session_id,
+                    params.run_id,
+                    tc,
+                    async {
+                        run_cancellable(Some(&self.cancel), host.execute_tool(tc))
+                            .await
+                            .unwrap_or_else(|| ToolResult {
+                                tool_call_id: tc.id.clone(),
+                                output: CANCELLED_TOOL_OUTPUT.into(),
+                                success: false,
+                            })
+                    },
+                )
+                .await;
+                let tool_ms = tool_start.elapsed().as_millis() as u64;
+                self.stats.tool_duration_ms += tool_ms;
+                self.stats.tool_calls += 1;
+                host.tool_finished(tc, &result, tool_ms);
+                self.trace.tool(
+                    round,
+                    &tc.function.name,
+                    &tc.function.arguments,
+                    &result.output,
+                    result.success,
+                    tool_ms,
+                );
+
+                info!(
+                    "[engine] Tool result: {} success={} output_len={}",
+                    tc.function.name,
+                    result.success,
+                    result.output.len()
+                );
+
+                // Audit: log tool execution result
+                if let Some(store) = store {
+                    if let Some(id) = approval_id {
+                        if let Err(e) =
+                            store.record_approval_outcome(id, result.success, &result.output)
+                        {
+                            warn!("[engine] Failed to record approval outcome: {}", e);
+                        }
+                    }
+                    crate::engine::audit::log_tool_call(
+                        store,
+                        params.agent_id,
+                        params.session_id,
+                        &tc.function.name,
+                        &tc.id,
+                        &tc.function.arguments,
+                        result.success,
+                        &result.output,
+                    );
+                }
+
+                self.emit_tool_result(tc, &result.output, result.success, Some(tool_ms));
+                messages.push(tool_message(tc, result.output.clone()));
+
+                // ── Circuit breaker: track consecutive failures per tool ──
+                if result.success {
+                    tool_fail_counter.remove(&tc.function.name);
+                } else {
+                    let count = tool_fail_counter
+                        .entry(tc.function.name.clone())
+                        .or_insert(0);
+                    *count += 1;
+                    if let Some(nudge) = circuit_breaker_nudge(&tc.function.name, *count) {
+                        messages.push(text_message(Role::System, nudge));
+                    }
+                }
+            }
+
+            // ── 6. Pick up tools loaded this round (tool RAG) ──────────────
+            host.refresh_tools(tools);
+
+            // ── 7. Mid-loop context truncation ─────────────────────────────
+            // §24 Checkpoint: snapshot conversation state before truncation destroys messages
+            if let Some(store) = store {
+                checkpoint(store, params.agent_id, params.session_id, messages);
+            }
+            helpers::truncate_mid_loop(messages, params.context_window_tokens);
+
+            // ── 8. Loop: send tool results back to model ──────────────────
+            info!(
+                "[engine] {} tool calls executed, feeding results back to model",
+                tc_count
+            );
+
+            // NOTE: Do NOT emit Complete here — only emit Complete when the model
+            // produces a final text response (no more tool calls). Intermediate
+            // Complete events were causing premature stream resolution on the frontend.
+        }
+    }
+
+    /// Emit completion for a turn that ended without a model answer.
+    fn emit_complete(&self, text: &str, round: u32) {
+        self.host.emit(EngineEvent::Complete {
+            session_id: self.params.session_id.to_string(),
+            run_id: self.params.run_id.to_string(),
+            text: text.to_string(),
+            tool_calls_count: 0,
+            usage: None,
+            model: None,
+            total_rounds: Some(round),
+            max_rounds: Some(self.params.max_rounds),
+            finish_reason: None,
+        });
+    }
+
+    fn emit_tool_result(&self, tc: &ToolCall, output: &str, success: bool, ms: Option<u64>) {
+        self.host.emit(EngineEvent::ToolResultEvent {
+            session_id: self.params.session_id.to_string(),
+            run_id: self.params.run_id.to_string(),
+            tool_call_id: tc.id.clone(),
+            output: output.to_string(),
+            success,
+            duration_ms: ms,
+        });
+    }
+
+    /// Emit completion for a cancelled run. Its text is empty so callers
+    /// skip memory capture for the partial turn.
+    fn finish_cancelled(&mut self, round: u32) -> TurnOutcome {
+        self.emit_complete("", round);
+        self.finish(TurnStatus::Cancelled, String::new(), round, false, "")
+    }
+
+    /// Close the trace and build the outcome.
+    fn finish(
+        &mut self,
+        status: TurnStatus,
+        text: String,
+        round: u32,
+        success: bool,
+        detail: &str,
+    ) -> TurnOutcome {
+        self.finish_trace(round, status.label(), success, detail);
+        self.stats.duration_ms = self.started.elapsed().as_millis() as u64;
+        TurnOutcome {
+            text,
+            status,
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Record the run's outcome span and persist its trace.
+    fn finish_trace(&mut self, round: u32, status: &str, success: bool, detail: &str) {
+        self.trace.outcome(round, status, success, detail);
+        if let Some(store) = self.host.store() {
+            self.trace.persist(store);
+        }
+    }
+
+    /// Log a gated tool decision to the approvals audit trail. Returns the row
+    /// id so the outcome can be attached once the tool has run.
+    fn record_approval(&self, tc: &ToolCall, approved: bool, decider: &str) -> Option<i64> {
+        self.host
+            .store()?
+            .record_approval_decision(
+                self.params.session_id,
+                &tc.id,
+                &tc.function.name,
+                &tc.function.arguments,
+                approved,
+                decider,
+            )
+            .map_err(|e| warn!("[engine] Failed to record approval: {}", e))
+            .ok()
+    }
+}
+
+impl TurnStatus {
+    /// Outcome name used in run traces.
+    fn label(self) -> &'static str {
+        match self {
+            TurnStatus::Completed => "completed",
+            TurnStatus::Yielded => "yielded",
+            TurnStatus::Cancelled => "cancelled",
+            TurnStatus::MaxRounds => "max_rounds",
+            TurnStatus::ToolLoop => "tool_loop",
+            TurnStatus::ToolLimit => "tool_limit",
+        }
+    }
+}
+
+// ── Response assembly ──────────────────────────────────────────────────
+
+/// One model response merged from its streamed chunks.
+struct Response {
+    text: String,
+    tool_calls: Vec<ToolCall>,
+    finish_reason: Option<FinishReason>,
+    /// The model name the API confirmed, if it reported one.
+    model: Option<String>,
+    /// Input tokens of the last usage report, if any.
+    input_tokens: Option<u64>,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+}
+
+/// A tool call being streamed: (id, name, arguments, thought_signature,
+/// thought_parts).
+type PartialCall = (String, String, String, Option<String>, Vec<ThoughtPart>);
+
+fn assemble(chunks: &[StreamChunk]) -> Response {
+    let mut text = String::new();
+    let mut tool_call_map: HashMap<usize, PartialCall> = HashMap::new();
+    let mut has_tool_calls = false;
+    let mut finish_reason: Option<FinishReason> = None;
+    let mut input_tokens = None;
+    let (mut output_tokens, mut cache_read_tokens, mut cache_creation_tokens) = (0, 0, 0);
+
+    for chunk in chunks {
+        if let Some(dt) = &chunk.delta_text {
+            text.push_str(dt);
+        }
+
+        for tc_delta in &chunk.tool_calls {
+            has_tool_calls = true;
+            let entry = tool_call_map.entry(tc_delta.index).or_default();
+            if let Some(id) = &tc_delta.id {
+                entry.0.push_str(id);
+            }
+            if let Some(name) = &tc_delta.function_name {
+                entry.1.push_str(name);
+            }
+            if let Some(args_delta) = &tc_delta.arguments_delta {
+                entry.2.push_str(args_delta);
+            }
+            if tc_delta.thought_signature.is_some() {
+                entry.3 = tc_delta.thought_signature.clone();
+            }
+        }
+
+        // Thought parts ride along with the first tool call of their chunk
+        if !chunk.thought_parts.is_empty() {
+            let first_idx = chunk.tool_calls.first().map(|tc| tc.index).unwrap_or(0);
+            let entry = tool_call_map.entry(first_idx).or_default();
+            entry.4.extend(chunk.thought_parts.clone());
+        }
+
+        if let Some(reason) = &chunk.finish_reason {
+            // Normalize across providers: OpenAI finish_reason, Anthropic
+            // stop_reason, Google finishReason.
+            finish_reason = Some(FinishReason::merge(
+                finish_reason,
+                FinishReason::from_provider(reason),
+            ));
+        }
+
+        if let Some(usage) = &chunk.usage {
+            input_tokens = Some(usage.input_tokens);
+            output_tokens += usage.output_tokens;
+            cache_read_tokens += usage.cache_read_tokens;
+            cache_creation_tokens += usage.cache_creation_tokens;
+        }
+    }
+
+    // Thought parts alone don't make a tool call
+    if !has_tool_calls {
+        tool_call_map.clear();
+    }
+    let mut sorted_indices: Vec<usize> = tool_call_map.keys().cloned().collect();
+    sorted_indices.sort();
+    let tool_calls = sorted_indices
+        .into_iter()
+        .filter_map(|idx| tool_call_map.remove(&idx))
+        .map(|(id, name, arguments, thought_signature, thought_parts)| {
+            // Generate an ID if the provider didn't supply one, or if the
+            // accumulated ID is suspiciously short (SSE chunk corruption).
+            let id = if id.is_empty() || (id.len() < 8 && !id.starts_with("call_")) {
+                if !id.is_empty() {
+                    warn!(
+                        "[engine] Replacing suspicious tool_call id '{}' (len={}) with generated UUID",
+                        id,
+                        id.len()
+                    );
+                }
+                format!("call_{}", uuid::Uuid::new_v4())
+            } else {
+                id
+            };
+            ToolCall {
+                id,
+                call_type: "function".into(),
+                function: FunctionCall { name, arguments },
+                thought_signature,
+                thought_parts,
+            }
+        })
+        .collect();
+
+    Response {
+        text,
+        tool_calls,
+        finish_reason,
+        model: chunks.iter().find_map(|c| c.model.clone()),
+        input_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_creation_tokens,
+    }
+}
+
+// ── Loop guards ────────────────────────────────────────────────────────
+
+/// Fingerprint of a round's tool calls: the sorted tool names + full args,
+/// hashed to avoid UTF-8 boundary issues and keep memory flat.
+fn call_signature(tool_calls: &[ToolCall]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut parts: Vec<(&str, &str)> = tool_calls
+        .iter()
+        .map(|tc| (tc.function.name.as_str(), tc.function.arguments.as_str()))
+        .collect();
+    parts.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for (name, args) in &parts {
+        name.hash(&mut hasher);
+        args.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// True when the last MAX_REPEATED_SIGNATURES rounds made identical calls.
+fn is_repeating(signatures: &[u64]) -> bool {
+    signatures.len() >= MAX_REPEATED_SIGNATURES
+        && signatures[signatures.len() - MAX_REPEATED_SIGNATURES..]
+            .windows(2)
+            .all(|w| w[0] == w[1])
+}
+
+/// System message for a tool that has now failed `count` times in a row.
+fn circuit_breaker_nudge(tool: &str, count: u32) -> Option<String> {
+    if count >= HARD_STOP_TOOL_FAILS {
+        warn!(
+            "[engine] Circuit breaker HARD STOP: tool '{}' failed {} consecutive times. Blocking further calls.",
+            tool, count
+        );
+        Some(format!(
+            "[SYSTEM] HARD STOP: The tool '{}' has failed {} times in a row and is now BLOCKED. \
+            Do NOT call '{}' again — it will not work. \
+            Instead, tell the user what happened and suggest they check their \
+            skill configuration or try a different approach. Provide a text summary now.",
+            tool, count, tool
+        ))
+    } else if count >= MAX_CONSECUTIVE_TOOL_FAILS {
+        warn!(
+            "[engine] Circuit breaker: tool '{}' failed {} consecutive times. Injecting stop-retry nudge.",
+            tool, count
+        );
+        Some(format!(
+            "[SYSTEM] The tool '{}' has failed {} times in a row. \
+            Stop calling '{}' with the same arguments — try a DIFFERENT tool or approach instead. \
+            Use `request_tools` to discover alternative tools that might work better. \
+            For example, if google_api failed, try dedicated tools like google_docs_create, \
+            google_drive_upload, or google_drive_share instead.",
+            tool, count, tool
+        ))
+    } else {
+        None
+    }
+}
+
+/// Tier label sent to the frontend for UI hints.
+fn tier_label(tier: ToolTier) -> &'static str {
+    match tier {
+        ToolTier::Safe => "safe",
+        ToolTier::Reversible => "reversible",
+        ToolTier::External => "external",
+        ToolTier::Dangerous => "dangerous",
+    }
+}
+
+// ── Messages ───────────────────────────────────────────────────────────
+
+fn text_message(role: Role, text: String) -> Message {
+    Message {
+        role,
+        content: MessageContent::Text(text),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+    }
+}
+
+fn tool_message(tc: &ToolCall, output: String) -> Message {
+    Message {
+        role: Role::Tool,
+        content: MessageContent::Text(output),
+        tool_calls: None,
+        tool_call_id: Some(tc.id.clone()),
+        name: Some(tc.function.name.clone()),
+    }
+}
+
+/// Snapshot the conversation before mid-loop truncation drops messages.
+fn checkpoint(store: &SessionStore, agent_id: &str, session_id: &str, messages: &[Message]) {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let checkpoint_msgs: Vec<CheckpointMessage> = messages
+        .iter()
+        .map(|m| CheckpointMessage {
+            role: format!("{:?}", m.role).to_lowercase(),
+            content: match &m.content {
+                MessageContent::Text(t) => t.clone(),
+                MessageContent::Blocks(blocks) => blocks
+                    .iter()
+                    .filter_map(|b| match b {
+                        ContentBlock::Text { text } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            },
+            timestamp: now.clone(),
+        })
+        .collect();
+    let empty_wm = WorkingMemorySnapshot {
+        agent_id: agent_id.to_string(),
+        slots: vec![],
+        momentum_embeddings: vec![],
+        saved_at: now,
+    };
+    let empty_hashes = HashMap::new();
+    let req = CaptureCheckpointRequest {
+        agent_id,
+        session_id,
+        messages: &checkpoint_msgs,
+        working_memory: &empty_wm,
+        file_hashes: &empty_hashes,
+        tasks: &[],
+        key_decisions: &[],
+    };
+    if let Err(e) = capture_checkpoint(store, &req) {
+        warn!(
+            "[engine] Failed to capture pre-truncation checkpoint: {}",
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atoms::traits::{AiProvider, ProviderError};
+    use parking_lot::Mutex;
+
+    /// Replays a fixed list of responses, one per round.
+    struct ScriptedProvider(Mutex<Vec<Vec<StreamChunk>>>);
+
+    #[async_trait]
+    impl AiProvider for ScriptedProvider {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Custom
+        }
+        async fn chat_stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            let mut rounds = self.0.lock();
+            Ok(if rounds.is_empty() {
+                vec![]
+            } else {
+                rounds.remove(0)
+            })
+        }
+    }
+
+    /// Records executed tools; optionally presses Stop from inside one.
+    #[derive(Default)]
+    struct TestHost {
+        executed: Mutex<Vec<String>>,
+        stop_during: Option<(String, CancelToken)>,
+    }
+
+    #[async_trait]
+    impl TurnHost for TestHost {
+        fn store(&self) -> Option<&SessionStore> {
+            None
+        }
+        fn emit(&self, _event: EngineEvent) {}
+        fn stream_text(&self, _text: &str) {}
+        async fn request_approval(
+            &self,
+            _request: ApprovalRequest<'_>,
+            _cancel: &CancelToken,
+        ) -> (bool, &'static str) {
+            (false, "test")
+        }
+        async fn execute_tool(&self, call: &ToolCall) -> ToolResult {
+            self.executed.lock().push(call.id.clone());
+            if let Some((id, token)) = &self.stop_during {
+                if *id == call.id {
+                    token.cancel();
+                }
+            }
+            ToolResult {
+                tool_call_id: call.id.clone(),
+                output: "ok".into(),
+                success: true,
+            }
+        }
+    }
+
+    fn provider(rounds: Vec<Vec<StreamChunk>>) -> AnyProvider {
+        AnyProvider::from_provider(Box::new(ScriptedProvider(Mutex::new(rounds))))
+    }
+
+    fn chunk(text: Option<&str>, tool_calls: Vec<ToolCallDelta>) -> StreamChunk {
+        let finish = if tool_calls.is_empty() {
+            "stop"
+        } else {
+            "tool_calls"
+        };
+        StreamChunk {
+            finish_reason: Some(finish.into()),
+            delta_text: text.map(Into::into),
+            tool_calls,
+            usage: None,
+            model: None,
+            thought_parts: vec![],
+            thinking_text: None,
+        }
+    }
+
+    /// One round calling the safe `fetch` tool once per id.
+    fn fetch_round(ids: &[&str]) -> Vec<StreamChunk> {
+        let calls = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ToolCallDelta {
+                index: i,
+                id: Some(id.to_string()),
+                function_name: Some("fetch".into()),
+                arguments_delta: Some(format!(r#"{{"url":"https://example.com/{}"}}"#, id)),
+                thought_signature: None,
+            })
+            .collect();
+        vec![chunk(None, calls)]
+    }
+
+    fn params(max_tool_iterations: u32) -> TurnParams<'static> {
+        TurnParams {
+            model: "test-model",
+            session_id: "s1",
+            run_id: "r1",
+            agent_id: "default",
+            max_rounds: 10,
+            temperature: None,
+            thinking_level: None,
+            auto_approve_all: false,
+            user_approved_tools: &[],
+            max_tool_iterations,
+            show_reasoning: false,
+            context_window_tokens: 32_000,
+        }
+    }
+
+    fn tool_output<'m>(messages: &'m [Message], id: &str) -> Option<&'m str> {
+        messages.iter().find_map(|m| match &m.content {
+            MessageContent::Text(t) if m.tool_call_id.as_deref() == Some(id) => Some(t.as_str()),
+            _ => None,
+        })
+    }
+
+    #[tokio::test]
+    async fn stop_mid_round_answers_remaining_calls_as_cancelled() {
+        let cancel = CancelToken::new();
+        let host = TestHost {
+            stop_during: Some(("call_a".into(), cancel.clone())),
+            ..Default::default()
+        };
+        let provider = provider(vec![
+            fetch_round(&["call_a", "call_b"]),
+            vec![chunk(Some("never reached"), vec![])],
+        ]);
+        let mut messages = vec![text_message(Role::User, "fetch both".into())];
+        let mut tools = vec![];
+
+        let outcome = run_turn(
+            &host,
+            &provider,
+            &mut messages,
+            &mut tools,
+            &params(0),
+            Some(&cancel),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome.status, TurnStatus::Cancelled);
+        assert!(outcome.text.is_empty());
+        assert_eq!(*host.executed.lock(), vec!["call_a".to_string()]);
+        // Every call of the round still gets a result, so the history stays valid
+        assert_eq!(
+            tool_output(&messages, "call_b"),
+            Some(CANCELLED_TOOL_OUTPUT)
+        );
+        assert!(tool_output(&messages, "call_a").is_some());
+    }
+
+    #[tokio::test]
+    async fn tool_limit_nudges_once_then_stops() {
+        let host = TestHost::default();
+        let provider = provider(vec![
+            fetch_round(&["call_1"]),
+            fetch_round(&["call_2"]),
+            fetch_round(&["call_3"]),
+            fetch_round(&["call_4"]),
+        ]);
+        let mut messages = vec![text_message(Role::User, "keep fetching".into())];
+        let mut tools = vec![];
+
+        let outcome = run_turn(
+            &host,
+            &provider,
+            &mut messages,
+            &mut tools,
+            &params(2),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let guard = ToolIterationGuard::new(2);
+        assert_eq!(outcome.status, TurnStatus::ToolLimit);
+        assert_eq!(outcome.text, guard.stop_message());
+        assert_eq!(outcome.stats.tool_calls, 2);
+        assert_eq!(*host.executed.lock(), vec!["call_1", "call_2"]);
+        let nudged = messages.iter().any(|m| {
+            m.role == Role::System
+                && matches!(&m.content, MessageContent::Text(t) if *t == guard.nudge_message())
+        });
+        assert!(nudged);
+        // Over-limit calls never reach the history unanswered
+        assert!(tool_output(&messages, "call_3").is_none());
+        assert!(tool_output(&messages, "call_4").is_none());
+    }
+
+    #[tokio::test]
+    async fn answer_after_tool_limit_nudge_completes() {
+        let host = TestHost::default();
+        let provider = provider(vec![
+            fetch_round(&["call_1", "call_2"]),
+            fetch_round(&["call_3"]),
+            vec![chunk(Some("Here is what I found."), vec![])],
+        ]);
+        let mut messages = vec![text_message(Role::User, "keep fetching".into())];
+        let mut tools = vec![];
+
+        let outcome = run_turn(
+            &host,
+            &provider,
+            &mut messages,
+            &mut tools,
+            &params(2),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome.status, TurnStatus::Completed);
+        assert_eq!(outcome.text, "Here is what I found.");
+        assert_eq!(*host.executed.lock(), vec!["call_1", "call_2"]);
+    }
+}
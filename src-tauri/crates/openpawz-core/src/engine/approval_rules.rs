@@ -0,0 +1,297 @@
+// Paw Agent Engine — Configurable HIL approval rules
+//
+// User-defined rules that decide, per tool call, whether the human-in-the-loop
+// approval prompt is shown. Rules are evaluated in order; the first rule whose
+// `tool` pattern matches decides. When no rule matches, the built-in tier
+// policy (safe tools auto-run, trading policy, agent auto-approve, …) applies.
+//
+//   { "tool": "exec",        "action": "require" }                 always ask
+//   { "tool": "web_search",  "action": "auto" }                    never ask
+//   { "tool": "dex_swap",    "action": "threshold",
+//     "threshold_usd": 50 }                                        ask at ≥ $50
+//
+// Threshold rules read the token amount from `amount_field` (default
+// "amount"), accepting numbers or numeric strings, and value it with the
+// caller's USD quote for one unit of the token being spent. A missing amount
+// or a missing quote requires approval — never guess in the permissive
+// direction.
+//
+// Stored as JSON under the `hil_approval_rules` config key.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use serde::{Deserialize, Serialize};
+
+/// engine_config key holding the serialized [`ApprovalRules`].
+pub const APPROVAL_RULES_KEY: &str = "hil_approval_rules";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Always show the approval prompt.
+    Require,
+    /// Run without prompting.
+    Auto,
+    /// Auto-run below `threshold_usd`, prompt at or above it.
+    Threshold,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    /// Tool name, or a `prefix*` pattern (e.g. `dex_*`).
+    pub tool: String,
+    pub action: RuleAction,
+    /// USD amount at which a `threshold` rule starts requiring approval.
+    #[serde(default)]
+    pub threshold_usd: Option<f64>,
+    /// Argument holding the token amount (default `amount`).
+    #[serde(default)]
+    pub amount_field: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRules {
+    #[serde(default)]
+    pub rules: Vec<ApprovalRule>,
+}
+
+/// Outcome of evaluating the rules for one tool call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApprovalDecision {
+    /// Prompt the user; the reason is shown in the approval request.
+    Require(String),
+    /// Skip the prompt.
+    AutoApprove(String),
+    /// No rule matched — fall back to the built-in policy.
+    NoRule,
+}
+
+fn pattern_matches(pattern: &str, tool: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool.starts_with(prefix),
+        None => pattern == tool,
+    }
+}
+
+fn extract_amount(args: &serde_json::Value, field: &str) -> Option<f64> {
+    let value = &args[field];
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .filter(|v: &f64| v.is_finite())
+}
+
+impl ApprovalRules {
+    fn rule_for(&self, tool: &str) -> Option<&ApprovalRule> {
+        self.rules.iter().find(|r| pattern_matches(&r.tool, tool))
+    }
+
+    /// Whether deciding on `tool` needs a USD quote (its rule is a threshold).
+    pub fn needs_quote(&self, tool: &str) -> bool {
+        self.rule_for(tool)
+            .is_some_and(|r| r.action == RuleAction::Threshold)
+    }
+
+    /// Decide whether `tool` called with `args_json` needs approval.
+    /// `unit_price_usd` is the USD price of one unit of the token the call
+    /// spends, when the caller could quote it.
+    pub fn evaluate(
+        &self,
+        tool: &str,
+        args_json: &str,
+        unit_price_usd: Option<f64>,
+    ) -> ApprovalDecision {
+        let Some(rule) = self.rule_for(tool) else {
+            return ApprovalDecision::NoRule;
+        };
+        match rule.action {
+            RuleAction::Require => ApprovalDecision::Require(format!(
+                "Approval rule: '{}' always requires approval",
+                rule.tool
+            )),
+            RuleAction::Auto => ApprovalDecision::AutoApprove(format!(
+                "Approval rule: '{}' is auto-approved",
+                rule.tool
+            )),
+            RuleAction::Threshold => {
+                let field = rule.amount_field.as_deref().unwrap_or("amount");
+                let Some(threshold) = rule.threshold_usd else {
+                    return ApprovalDecision::Require(format!(
+                        "Approval rule: '{}' has no threshold configured",
+                        rule.tool
+                    ));
+                };
+                let args: serde_json::Value = serde_json::from_str(args_json).unwrap_or_default();
+                let Some(amount) = extract_amount(&args, field) else {
+                    return ApprovalDecision::Require(format!(
+                        "Approval rule: could not read '{}' to compare with the ${:.2} threshold",
+                        field, threshold
+                    ));
+                };
+                let price = unit_price_usd.filter(|p| p.is_finite() && *p > 0.0);
+                match price.map(|p| amount * p) {
+                    Some(usd) if usd < threshold => ApprovalDecision::AutoApprove(format!(
+                        "Approval rule: ${:.2} is under the ${:.2} threshold",
+                        usd, threshold
+                    )),
+                    Some(usd) => ApprovalDecision::Require(format!(
+                        "Approval rule: ${:.2} meets the ${:.2} approval threshold",
+                        usd, threshold
+                    )),
+                    None => ApprovalDecision::Require(format!(
+                        "Approval rule: no USD price for this call to compare with the ${:.2} threshold",
+                        threshold
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Reject rules that could never be applied correctly.
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.tool.trim().is_empty() || rule.tool == "*" {
+                return Err("Each rule needs a tool name or a 'prefix*' pattern".into());
+            }
+            if rule.action == RuleAction::Threshold {
+                match rule.threshold_usd {
+                    Some(t) if t.is_finite() && t >= 0.0 => {}
+                    _ => {
+                        return Err(format!(
+                            "Rule for '{}' needs a non-negative threshold_usd",
+                            rule.tool
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Load the configured rules (empty when unset or unreadable).
+pub fn load_rules(store: &SessionStore) -> ApprovalRules {
+    store
+        .get_config(APPROVAL_RULES_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Validate and persist the rules.
+pub fn save_rules(store: &SessionStore, rules: &ApprovalRules) -> EngineResult<()> {
+    rules
+        .validate()
+        .map_err(crate::atoms::error::EngineError::Config)?;
+    store.set_config(APPROVAL_RULES_KEY, &serde_json::to_string(rules)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn swap_rules() -> ApprovalRules {
+        ApprovalRules {
+            rules: vec![
+                ApprovalRule {
+                    tool: "dex_swap".into(),
+                    action: RuleAction::Threshold,
+                    threshold_usd: Some(50.0),
+                    amount_field: None,
+                },
+                ApprovalRule {
+                    tool: "exec".into(),
+                    action: RuleAction::Require,
+                    threshold_usd: None,
+                    amount_field: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn swap_above_threshold_requires_approval() {
+        // 0.05 ETH at $2,400 = $120
+        let decision = swap_rules().evaluate(
+            "dex_swap",
+            r#"{"amount":"0.05","token_in":"ETH"}"#,
+            Some(2400.0),
+        );
+        assert!(matches!(decision, ApprovalDecision::Require(r) if r.contains("120.00")));
+    }
+
+    #[test]
+    fn swap_below_threshold_auto_runs() {
+        let decision = swap_rules().evaluate("dex_swap", r#"{"amount":12.5}"#, Some(1.0));
+        assert!(matches!(decision, ApprovalDecision::AutoApprove(_)));
+    }
+
+    #[test]
+    fn threshold_compares_usd_value_not_token_units() {
+        // 10 ETH is far over $50 even though the raw amount is under it
+        let rules = swap_rules();
+        let decision = rules.evaluate("dex_swap", r#"{"amount":"10"}"#, Some(2400.0));
+        assert!(matches!(decision, ApprovalDecision::Require(r) if r.contains("24000.00")));
+        // No quote: ask rather than treat the token amount as dollars
+        let decision = rules.evaluate("dex_swap", r#"{"amount":"10"}"#, None);
+        assert!(matches!(decision, ApprovalDecision::Require(r) if r.contains("no USD price")));
+        assert!(rules.needs_quote("dex_swap"));
+        assert!(!rules.needs_quote("exec"));
+    }
+
+    #[test]
+    fn missing_amount_requires_approval() {
+        let decision = swap_rules().evaluate("dex_swap", r#"{"token_in":"ETH"}"#, Some(2400.0));
+        assert!(matches!(decision, ApprovalDecision::Require(_)));
+    }
+
+    #[test]
+    fn unmatched_tool_falls_back_and_patterns_match() {
+        let rules = swap_rules();
+        assert_eq!(
+            rules.evaluate("read_file", "{}", None),
+            ApprovalDecision::NoRule
+        );
+        assert!(matches!(
+            rules.evaluate("exec", r#"{"command":"ls"}"#, None),
+            ApprovalDecision::Require(_)
+        ));
+
+        let prefix = ApprovalRules {
+            rules: vec![ApprovalRule {
+                tool: "sol_*".into(),
+                action: RuleAction::Auto,
+                threshold_usd: None,
+                amount_field: None,
+            }],
+        };
+        assert!(matches!(
+            prefix.evaluate("sol_transfer", "{}", None),
+            ApprovalDecision::AutoApprove(_)
+        ));
+    }
+
+    #[test]
+    fn rules_round_trip_through_config() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let store = SessionStore::from_connection(conn);
+        assert_eq!(load_rules(&store), ApprovalRules::default());
+
+        save_rules(&store, &swap_rules()).unwrap();
+        assert_eq!(load_rules(&store), swap_rules());
+
+        let bad = ApprovalRules {
+            rules: vec![ApprovalRule {
+                tool: "dex_swap".into(),
+                action: RuleAction::Threshold,
+                threshold_usd: None,
+                amount_field: None,
+            }],
+        };
+        assert!(save_rules(&store, &bad).is_err());
+    }
+}
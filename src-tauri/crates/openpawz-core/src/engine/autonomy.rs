@@ -0,0 +1,239 @@
+// Paw Agent Engine — Per-agent autonomy level
+//
+// A single setting per agent that derives its approval gating and tool-call
+// cap, so non-experts don't have to write HIL rules by hand:
+//
+//   manual      every tool that changes something asks first; 25 calls/run
+//   assisted    the built-in tier policy (today's behavior); engine cap
+//   autonomous  everything runs, except financial tools valued at or above
+//               `financial_threshold_usd` (default $50) — or that can't be
+//               valued in USD; at least 250 calls/run
+//
+// Precedence in the agent loop: the global HIL rules (approval_rules), then
+// the agent's own override rules, then the level's defaults, then the
+// built-in tier policy.
+
+use crate::engine::approval_rules::{ApprovalDecision, ApprovalRule, ApprovalRules, RuleAction};
+use crate::engine::tool_metadata::{self, ToolDomain, ToolMutability};
+use crate::engine::types::{AgentAutonomy, AutonomyLevel};
+
+pub const MANUAL_MAX_TOOL_ITERATIONS: u32 = 25;
+pub const AUTONOMOUS_MIN_TOOL_ITERATIONS: u32 = 250;
+pub const DEFAULT_FINANCIAL_THRESHOLD_USD: f64 = 50.0;
+
+/// Trading and payment tools — the ones an autonomous agent still asks about.
+pub fn is_financial(tool: &str) -> bool {
+    matches!(
+        tool_metadata::domain(tool),
+        ToolDomain::Coinbase | ToolDomain::Solana | ToolDomain::Dex
+    ) || tool.starts_with("coinbase_")
+}
+
+/// Whether the tool changes anything (unknown tools are assumed to).
+pub fn is_mutating(tool: &str) -> bool {
+    tool_metadata::mutability(tool) != ToolMutability::ReadOnly
+}
+
+/// Approval gating and limits derived from an agent's [`AgentAutonomy`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutonomyPolicy {
+    pub level: AutonomyLevel,
+    overrides: ApprovalRules,
+    max_tool_iterations: Option<u32>,
+    financial_threshold_usd: f64,
+}
+
+impl Default for AutonomyPolicy {
+    fn default() -> Self {
+        AutonomyPolicy::derive(&AgentAutonomy::default())
+    }
+}
+
+impl AutonomyPolicy {
+    pub fn derive(autonomy: &AgentAutonomy) -> Self {
+        AutonomyPolicy {
+            level: autonomy.level,
+            overrides: ApprovalRules {
+                rules: autonomy.rules.clone(),
+            },
+            max_tool_iterations: autonomy.max_tool_iterations,
+            financial_threshold_usd: autonomy
+                .financial_threshold_usd
+                .filter(|t| t.is_finite() && *t >= 0.0)
+                .unwrap_or(DEFAULT_FINANCIAL_THRESHOLD_USD),
+        }
+    }
+
+    /// Whether deciding on `tool` needs a USD quote: a threshold override,
+    /// or a financial mutation on an autonomous agent.
+    pub fn needs_quote(&self, tool: &str) -> bool {
+        self.overrides.needs_quote(tool)
+            || (self.level == AutonomyLevel::Autonomous && is_financial(tool) && is_mutating(tool))
+    }
+
+    /// Decide whether `tool` needs approval. `NoRule` leaves it to the
+    /// built-in tier policy. `unit_price_usd` values the call for threshold
+    /// rules (see [`ApprovalRules::evaluate`]).
+    pub fn evaluate(
+        &self,
+        tool: &str,
+        args_json: &str,
+        unit_price_usd: Option<f64>,
+    ) -> ApprovalDecision {
+        let decision = self.overrides.evaluate(tool, args_json, unit_price_usd);
+        if decision != ApprovalDecision::NoRule {
+            return decision;
+        }
+        match self.level {
+            AutonomyLevel::Assisted => ApprovalDecision::NoRule,
+            AutonomyLevel::Manual if is_mutating(tool) => ApprovalDecision::Require(format!(
+                "Autonomy: manual — '{}' makes changes, so it needs approval",
+                tool
+            )),
+            AutonomyLevel::Manual => ApprovalDecision::NoRule,
+            AutonomyLevel::Autonomous if is_financial(tool) && is_mutating(tool) => ApprovalRules {
+                rules: vec![ApprovalRule {
+                    tool: tool.to_string(),
+                    action: RuleAction::Threshold,
+                    threshold_usd: Some(self.financial_threshold_usd),
+                    amount_field: None,
+                }],
+            }
+            .evaluate(tool, args_json, unit_price_usd),
+            AutonomyLevel::Autonomous => {
+                ApprovalDecision::AutoApprove("Autonomy: autonomous agent".into())
+            }
+        }
+    }
+
+    /// Tool-call cap for one run, given the engine's `max_tool_iterations`
+    /// (0 = no cap).
+    pub fn max_tool_iterations(&self, configured: u32) -> u32 {
+        if let Some(max) = self.max_tool_iterations {
+            return max;
+        }
+        match self.level {
+            AutonomyLevel::Manual if configured == 0 => MANUAL_MAX_TOOL_ITERATIONS,
+            AutonomyLevel::Manual => configured.min(MANUAL_MAX_TOOL_ITERATIONS),
+            AutonomyLevel::Assisted => configured,
+            AutonomyLevel::Autonomous if configured == 0 => 0,
+            AutonomyLevel::Autonomous => configured.max(AUTONOMOUS_MIN_TOOL_ITERATIONS),
+        }
+    }
+}
+
+/// Reject overrides that could never be applied correctly.
+pub fn validate(autonomy: &AgentAutonomy) -> Result<(), String> {
+    ApprovalRules {
+        rules: autonomy.rules.clone(),
+    }
+    .validate()?;
+    match autonomy.financial_threshold_usd {
+        Some(t) if !t.is_finite() || t < 0.0 => {
+            Err("financial_threshold_usd must be a non-negative amount".into())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(level: AutonomyLevel) -> AutonomyPolicy {
+        AutonomyPolicy::derive(&AgentAutonomy {
+            level,
+            ..Default::default()
+        })
+    }
+
+    fn requires(decision: ApprovalDecision) -> bool {
+        matches!(decision, ApprovalDecision::Require(_))
+    }
+
+    #[test]
+    fn manual_gates_every_mutating_tool() {
+        let manual = policy(AutonomyLevel::Manual);
+        for tool in [
+            "write_file",
+            "exec",
+            "email_send",
+            "dex_swap",
+            "mcp_notes_create",
+        ] {
+            assert!(requires(manual.evaluate(tool, "{}", None)), "{}", tool);
+        }
+        assert_eq!(
+            manual.evaluate("read_file", "{}", None),
+            ApprovalDecision::NoRule
+        );
+        assert_eq!(manual.max_tool_iterations(100), MANUAL_MAX_TOOL_ITERATIONS);
+    }
+
+    #[test]
+    fn autonomous_gates_only_financial_tools_above_threshold() {
+        let autonomous = policy(AutonomyLevel::Autonomous);
+        for tool in ["write_file", "exec", "email_send"] {
+            assert!(
+                matches!(
+                    autonomous.evaluate(tool, "{}", None),
+                    ApprovalDecision::AutoApprove(_)
+                ),
+                "{}",
+                tool
+            );
+        }
+        assert!(autonomous.needs_quote("dex_swap"));
+        assert!(!autonomous.needs_quote("exec"));
+        // 10 USDC at $1 is under the $50 threshold
+        assert!(matches!(
+            autonomous.evaluate("dex_swap", r#"{"amount":"10"}"#, Some(1.0)),
+            ApprovalDecision::AutoApprove(_)
+        ));
+        // 10 ETH at $2,400 is not, whatever the raw amount says
+        assert!(requires(autonomous.evaluate(
+            "dex_swap",
+            r#"{"amount":"10"}"#,
+            Some(2400.0)
+        )));
+        // No USD valuation or no readable amount: ask rather than guess
+        assert!(requires(autonomous.evaluate(
+            "dex_swap",
+            r#"{"amount":"10"}"#,
+            None
+        )));
+        assert!(requires(autonomous.evaluate("dex_swap", "{}", Some(1.0))));
+        assert_eq!(
+            autonomous.max_tool_iterations(100),
+            AUTONOMOUS_MIN_TOOL_ITERATIONS
+        );
+    }
+
+    #[test]
+    fn overrides_beat_the_level_defaults() {
+        let manual = AutonomyPolicy::derive(&AgentAutonomy {
+            level: AutonomyLevel::Manual,
+            rules: vec![ApprovalRule {
+                tool: "write_file".into(),
+                action: RuleAction::Auto,
+                threshold_usd: None,
+                amount_field: None,
+            }],
+            max_tool_iterations: Some(40),
+            financial_threshold_usd: None,
+        });
+        assert!(matches!(
+            manual.evaluate("write_file", "{}", None),
+            ApprovalDecision::AutoApprove(_)
+        ));
+        assert!(requires(manual.evaluate("append_file", "{}", None)));
+        assert_eq!(manual.max_tool_iterations(100), 40);
+
+        let assisted = policy(AutonomyLevel::Assisted);
+        assert_eq!(
+            assisted.evaluate("exec", "{}", None),
+            ApprovalDecision::NoRule
+        );
+        assert_eq!(assisted.max_tool_iterations(100), 100);
+    }
+}
@@ -0,0 +1,165 @@
+// Paw Engine — Daily budget window
+//
+// The "daily" spend counters reset at the user's local midnight (or a
+// configured hour), not UTC midnight. A window runs from one reset to the
+// next; it is identified by its start instant, stored with the counters
+// (see sessions/daily_spend.rs).
+//
+// Windows are derived from the clock only, so the app being closed across
+// a reset needs no bookkeeping: on the next start the stored window start
+// no longer matches the current one and the counters begin again at zero.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The reset schedule: an IANA timezone and the local hour (0–23).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetWindow {
+    pub timezone: Tz,
+    pub reset_hour: u32,
+}
+
+impl Default for BudgetWindow {
+    fn default() -> Self {
+        BudgetWindow {
+            timezone: Tz::UTC,
+            reset_hour: 0,
+        }
+    }
+}
+
+impl BudgetWindow {
+    /// Unknown timezones fall back to UTC; hours past 23 are clamped.
+    pub fn new(timezone: &str, reset_hour: u32) -> Self {
+        BudgetWindow {
+            timezone: timezone.trim().parse().unwrap_or(Tz::UTC),
+            reset_hour: reset_hour.min(23),
+        }
+    }
+
+    /// The reset instant on a given local date. A reset hour skipped by a
+    /// DST jump resets at the first valid time after it.
+    fn reset_on(&self, date: NaiveDate) -> DateTime<Utc> {
+        let mut at =
+            date.and_time(NaiveTime::from_hms_opt(self.reset_hour, 0, 0).unwrap_or(NaiveTime::MIN));
+        for _ in 0..3 {
+            if let Some(local) = self.timezone.from_local_datetime(&at).earliest() {
+                return local.with_timezone(&Utc);
+            }
+            at += Duration::minutes(30);
+        }
+        Utc.from_utc_datetime(&at)
+    }
+
+    /// Start of the window containing `now`.
+    pub fn start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.with_timezone(&self.timezone).date_naive();
+        let reset = self.reset_on(today);
+        if now >= reset {
+            reset
+        } else {
+            self.reset_on(today.pred_opt().unwrap_or(today))
+        }
+    }
+
+    /// Start of the window after the one containing `now`.
+    pub fn next_reset(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.with_timezone(&self.timezone).date_naive();
+        let reset = self.reset_on(today);
+        if now < reset {
+            reset
+        } else {
+            self.reset_on(today.succ_opt().unwrap_or(today))
+        }
+    }
+
+    /// The stored form of the window containing `now`.
+    pub fn key(&self, now: DateTime<Utc>) -> String {
+        format_window_start(&self.start(now))
+    }
+
+    /// Whether counters stored under `window_start` belong to a window that
+    /// has since ended (or can't be read).
+    pub fn has_elapsed(&self, window_start: &str, now: DateTime<Utc>) -> bool {
+        match DateTime::parse_from_rfc3339(window_start) {
+            Ok(start) => start.with_timezone(&Utc) < self.start(now),
+            Err(_) => true,
+        }
+    }
+}
+
+/// UTC, whole seconds, `Z` suffix.
+pub fn format_window_start(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn window_starts_at_local_midnight() {
+        let window = BudgetWindow::new("America/Chicago", 0);
+        // 03:00 UTC on the 16th is still the 15th in Chicago (UTC-5 in October)
+        assert_eq!(
+            window.key(utc("2026-10-16T03:00:00Z")),
+            "2026-10-15T05:00:00Z"
+        );
+        assert_eq!(
+            window.key(utc("2026-10-16T05:00:00Z")),
+            "2026-10-16T05:00:00Z"
+        );
+        assert_eq!(
+            window.next_reset(utc("2026-10-16T03:00:00Z")),
+            utc("2026-10-16T05:00:00Z")
+        );
+    }
+
+    #[test]
+    fn configured_hour_moves_the_boundary() {
+        let window = BudgetWindow::new("Europe/London", 6);
+        // 05:30 local (BST) is before the 06:00 reset — still yesterday's window
+        assert_eq!(
+            window.key(utc("2026-07-01T04:30:00Z")),
+            "2026-06-30T05:00:00Z"
+        );
+        assert_eq!(
+            window.key(utc("2026-07-01T05:00:00Z")),
+            "2026-07-01T05:00:00Z"
+        );
+    }
+
+    #[test]
+    fn spend_resets_when_stored_window_is_before_the_boundary() {
+        let window = BudgetWindow::new("Asia/Tokyo", 0);
+        let stored = window.key(utc("2026-10-15T14:00:00Z")); // 23:00 JST on the 15th
+                                                              // Same local day: the counters still apply
+        assert!(!window.has_elapsed(&stored, utc("2026-10-15T14:59:59Z")));
+        // App reopened after local midnight: the stored window has ended
+        assert!(window.has_elapsed(&stored, utc("2026-10-15T15:00:00Z")));
+        assert!(window.has_elapsed("not a timestamp", utc("2026-10-15T15:00:00Z")));
+    }
+
+    #[test]
+    fn unknown_timezone_falls_back_to_utc_and_dst_gap_is_skipped() {
+        let window = BudgetWindow::new("Mars/Olympus", 30);
+        assert_eq!(
+            window,
+            BudgetWindow {
+                timezone: Tz::UTC,
+                reset_hour: 23
+            }
+        );
+
+        // 02:00 doesn't exist in New York on 2026-03-08; reset at 03:00 EDT
+        let window = BudgetWindow::new("America/New_York", 2);
+        assert_eq!(
+            window.start(utc("2026-03-08T12:00:00Z")),
+            utc("2026-03-08T07:00:00Z")
+        );
+    }
+}
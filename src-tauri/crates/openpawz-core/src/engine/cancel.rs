@@ -0,0 +1,104 @@
+// Paw Agent Engine — Run cancellation
+//
+// A cheap, cloneable token shared between whoever starts an agent run and
+// the run itself. Cancelling sets a flag (checked between steps) and wakes
+// any in-flight await wrapped with `run_cancellable`, so a stuck LLM stream
+// or a slow tool is abandoned promptly instead of at the next round.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Cancellation token for a single agent run.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<Inner>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Idempotent.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// True if both handles refer to the same run's token.
+    pub fn same_as(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Resolve once `cancel` has been called.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.0.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CancelToken")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Drive `fut` to completion unless the token fires first (`None`).
+/// Without a token this is just `fut.await`.
+pub async fn run_cancellable<F: Future>(token: Option<&CancelToken>, fut: F) -> Option<F::Output> {
+    match token {
+        None => Some(fut.await),
+        Some(token) => {
+            if token.is_cancelled() {
+                return None;
+            }
+            tokio::select! {
+                out = fut => Some(out),
+                _ = token.cancelled() => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancel_interrupts_pending_future() {
+        let token = CancelToken::new();
+        let trigger = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            trigger.cancel();
+        });
+        let out = run_cancellable(Some(&token), std::future::pending::<()>()).await;
+        assert!(out.is_none());
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn completes_when_not_cancelled() {
+        let token = CancelToken::new();
+        assert_eq!(run_cancellable(Some(&token), async { 7 }).await, Some(7));
+        assert_eq!(run_cancellable(None, async { 8 }).await, Some(8));
+    }
+}
@@ -160,11 +160,11 @@ pub fn detect_constraints(provider: ProviderKind, model: &str) -> ConstraintConf
             add_additional_properties_false: false,
         },
 
-        // ── Azure AI Foundry ────────────────────────────────────────
-        // Hosts OpenAI models (gpt-*, o1, o3, o4) alongside others.
+        // ── Azure AI Foundry / Azure OpenAI ─────────────────────────
+        // Host OpenAI models (gpt-*, o1, o3, o4); Foundry hosts others too.
         // OpenAI models on Azure enforce the same strict schema
         // validation as the native OpenAI API.
-        ProviderKind::AzureFoundry => {
+        ProviderKind::AzureFoundry | ProviderKind::AzureOpenAI => {
             let supports_strict = supports_openai_strict(model);
             ConstraintConfig {
                 level: if supports_strict {
@@ -0,0 +1,157 @@
+// Paw Engine — CSV export helpers
+//
+// Minimal RFC 4180 writer for the audit/guardrail exports: fields holding a
+// comma, quote, CR or LF are quoted with embedded quotes doubled; rows end
+// in CRLF. Fields a spreadsheet would read as a formula (leading `=`, `+`,
+// `-`, `@`, tab or CR) get a `'` prefix so opening an export never runs
+// one. Plus the date-range filter the exports share.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+/// Leading characters that make a spreadsheet evaluate a cell.
+const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Neutralize a would-be formula, then quote `field` if it needs it.
+pub fn escape_field(field: &str) -> String {
+    let field = if field.starts_with(FORMULA_TRIGGERS) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Header plus rows as a CSV document.
+pub fn to_csv<R, F>(header: &[&str], rows: R) -> String
+where
+    R: IntoIterator<Item = Vec<F>>,
+    F: AsRef<str>,
+{
+    let mut out = String::new();
+    push_row(&mut out, header.iter());
+    for row in rows {
+        push_row(&mut out, row.iter());
+    }
+    out
+}
+
+fn push_row<I: Iterator<Item = T>, T: AsRef<str>>(out: &mut String, fields: I) {
+    let line: Vec<String> = fields.map(|f| escape_field(f.as_ref())).collect();
+    out.push_str(&line.join(","));
+    out.push_str("\r\n");
+}
+
+/// Parse the timestamp formats the logs use: RFC 3339 and SQLite's
+/// `datetime('now')` ("YYYY-MM-DD HH:MM:SS", UTC).
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|t| t.and_utc())
+        })
+}
+
+/// Inclusive date range; either end may be open.
+#[derive(Debug, Clone, Default)]
+pub struct DateRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    /// Bounds given as timestamps or plain dates. A plain `to` date covers
+    /// that whole day.
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self, String> {
+        let bound = |s: &str, end_of_day: bool| -> Result<DateTime<Utc>, String> {
+            if let Some(t) = parse_timestamp(s) {
+                return Ok(t);
+            }
+            let date = NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{}' — use YYYY-MM-DD or RFC 3339", s))?;
+            let time = if end_of_day {
+                date.and_hms_milli_opt(23, 59, 59, 999)
+            } else {
+                date.and_hms_opt(0, 0, 0)
+            };
+            Ok(time.unwrap_or_default().and_utc())
+        };
+        fn non_empty(s: Option<&str>) -> Option<&str> {
+            s.map(str::trim).filter(|s| !s.is_empty())
+        }
+        Ok(DateRange {
+            from: non_empty(from).map(|s| bound(s, false)).transpose()?,
+            to: non_empty(to).map(|s| bound(s, true)).transpose()?,
+        })
+    }
+
+    /// Whether `timestamp` falls inside. Unparseable timestamps only pass
+    /// an unbounded range.
+    pub fn contains(&self, timestamp: &str) -> bool {
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some(t) = parse_timestamp(timestamp) else {
+            return false;
+        };
+        self.from.is_none_or(|from| t >= from) && self.to.is_none_or(|to| t <= to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_commas_quotes_and_newlines() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("line1\nline2"), "\"line1\nline2\"");
+        assert_eq!(escape_field("cr\rhere"), "\"cr\rhere\"");
+
+        let csv = to_csv(
+            &["service", "action"],
+            vec![vec!["slack", "post \"hello, world\"\nthen leave"]],
+        );
+        assert_eq!(
+            csv,
+            "service,action\r\nslack,\"post \"\"hello, world\"\"\nthen leave\"\r\n"
+        );
+    }
+
+    #[test]
+    fn formula_fields_are_neutralized() {
+        assert_eq!(
+            escape_field("=HYPERLINK(\"http://x\")"),
+            "\"'=HYPERLINK(\"\"http://x\"\")\""
+        );
+        assert_eq!(escape_field("+1+1"), "'+1+1");
+        assert_eq!(escape_field("-2"), "'-2");
+        assert_eq!(escape_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(escape_field("\tcmd"), "'\tcmd");
+        assert_eq!(escape_field("a=b"), "a=b");
+        assert_eq!(escape_field(""), "");
+    }
+
+    #[test]
+    fn date_range_filters_both_timestamp_formats() {
+        let range = DateRange::parse(Some("2025-03-01"), Some("2025-03-31")).unwrap();
+        assert!(range.contains("2025-03-01T00:00:00Z"));
+        assert!(range.contains("2025-03-31 23:30:00"));
+        assert!(!range.contains("2025-04-01T00:00:00+00:00"));
+        assert!(!range.contains("2025-02-28 23:59:59"));
+        assert!(!range.contains("garbage"));
+
+        let open = DateRange::parse(None, Some("")).unwrap();
+        assert!(open.contains("garbage"));
+        assert!(DateRange::parse(Some("March"), None).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+// Paw Agent Engine — DEX spending allowance
+//
+// Hard per-transaction and per-day caps on DEX swaps and transfers,
+// independent of the trading policy (which only decides whether a trade may
+// skip the approval prompt). Over-allowance requests are rejected before the
+// approval prompt is shown and again in the DEX tool path before signing.
+//
+// Values are read straight from the tool arguments: ETH/WETH amounts count
+// against the ETH caps, USD stablecoin amounts against the USD caps. There is
+// no price feed to convert between the two, so while any cap is set a spend
+// is rejected when its denomination has no cap of its own, and tokens that
+// can't be valued this way are rejected outright — a hard allowance must
+// fail closed.
+//
+// Stored as JSON under the `dex_allowance` config key. A cap of 0 = no cap.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use serde::{Deserialize, Serialize};
+
+/// engine_config key holding the serialized [`DexAllowance`].
+pub const DEX_ALLOWANCE_KEY: &str = "dex_allowance";
+
+/// Tools whose value is checked against the allowance.
+pub const ALLOWANCE_TOOLS: &[&str] = &["dex_swap", "dex_transfer"];
+
+const ETH_SYMBOLS: &[&str] = &["ETH", "WETH"];
+const USD_STABLECOINS: &[&str] = &["USDC", "USDT", "DAI"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DexAllowance {
+    #[serde(default)]
+    pub max_tx_eth: f64,
+    #[serde(default)]
+    pub max_tx_usd: f64,
+    #[serde(default)]
+    pub max_daily_eth: f64,
+    #[serde(default)]
+    pub max_daily_usd: f64,
+}
+
+/// Value of one DEX transaction in the denominations the allowance tracks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TxValue {
+    pub eth: f64,
+    pub usd: f64,
+}
+
+impl DexAllowance {
+    pub fn is_enabled(&self) -> bool {
+        self.max_tx_eth > 0.0
+            || self.max_tx_usd > 0.0
+            || self.max_daily_eth > 0.0
+            || self.max_daily_usd > 0.0
+    }
+
+    /// Reject negative or non-finite caps.
+    pub fn validate(&self) -> Result<(), String> {
+        let caps = [
+            self.max_tx_eth,
+            self.max_tx_usd,
+            self.max_daily_eth,
+            self.max_daily_usd,
+        ];
+        if caps.iter().all(|c| c.is_finite() && *c >= 0.0) {
+            Ok(())
+        } else {
+            Err("DEX allowance caps must be non-negative numbers (0 = no cap)".into())
+        }
+    }
+
+    fn caps_eth(&self) -> bool {
+        self.max_tx_eth > 0.0 || self.max_daily_eth > 0.0
+    }
+
+    fn caps_usd(&self) -> bool {
+        self.max_tx_usd > 0.0 || self.max_daily_usd > 0.0
+    }
+
+    /// Check one transaction of `value` given what was already spent today.
+    pub fn check(&self, value: TxValue, spent_today: TxValue) -> Result<(), String> {
+        if self.is_enabled() {
+            if value.eth > 0.0 && !self.caps_eth() {
+                return Err(
+                    "DEX allowance: ETH spends can't be checked because only USD caps \
+                     are set. Add an ETH cap or clear the allowance in Settings."
+                        .into(),
+                );
+            }
+            if value.usd > 0.0 && !self.caps_usd() {
+                return Err(
+                    "DEX allowance: stablecoin spends can't be checked because only \
+                     ETH caps are set. Add a USD cap or clear the allowance in Settings."
+                        .into(),
+                );
+            }
+        }
+        let over = |cap: f64, amount: f64| cap > 0.0 && amount > cap;
+        if over(self.max_tx_eth, value.eth) {
+            return Err(format!(
+                "DEX allowance exceeded: {} ETH is over the per-transaction limit of {} ETH.",
+                value.eth, self.max_tx_eth
+            ));
+        }
+        if over(self.max_tx_usd, value.usd) {
+            return Err(format!(
+                "DEX allowance exceeded: ${:.2} is over the per-transaction limit of ${:.2}.",
+                value.usd, self.max_tx_usd
+            ));
+        }
+        if over(self.max_daily_eth, spent_today.eth + value.eth) {
+            return Err(format!(
+                "DEX allowance exceeded: {} ETH would bring today's total to {} ETH \
+                 (daily limit {} ETH).",
+                value.eth,
+                spent_today.eth + value.eth,
+                self.max_daily_eth
+            ));
+        }
+        if over(self.max_daily_usd, spent_today.usd + value.usd) {
+            return Err(format!(
+                "DEX allowance exceeded: ${:.2} would bring today's total to ${:.2} \
+                 (daily limit ${:.2}).",
+                value.usd,
+                spent_today.usd + value.usd,
+                self.max_daily_usd
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Value `amount` of `symbol`, or `None` for tokens the allowance can't price.
+pub fn value_of(symbol: &str, amount: f64) -> Option<TxValue> {
+    let symbol = symbol.trim().to_uppercase();
+    if ETH_SYMBOLS.contains(&symbol.as_str()) {
+        Some(TxValue {
+            eth: amount,
+            usd: 0.0,
+        })
+    } else if USD_STABLECOINS.contains(&symbol.as_str()) {
+        Some(TxValue {
+            eth: 0.0,
+            usd: amount,
+        })
+    } else {
+        None
+    }
+}
+
+/// The token and amount a DEX tool call spends.
+fn spend_of(tool: &str, args: &serde_json::Value) -> Option<(String, f64)> {
+    let token = match tool {
+        "dex_swap" => args["token_in"].as_str()?,
+        "dex_transfer" => args["currency"].as_str()?,
+        _ => return None,
+    };
+    let amount = args["amount"]
+        .as_str()
+        .and_then(|s| s.trim().parse().ok())
+        .or_else(|| args["amount"].as_f64())
+        .filter(|a: &f64| a.is_finite() && *a >= 0.0)?;
+    Some((token.to_string(), amount))
+}
+
+/// Load the configured allowance (disabled when unset or unreadable).
+pub fn load_allowance(store: &SessionStore) -> DexAllowance {
+    store
+        .get_config(DEX_ALLOWANCE_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Validate and persist the allowance.
+pub fn save_allowance(store: &SessionStore, allowance: &DexAllowance) -> EngineResult<()> {
+    allowance
+        .validate()
+        .map_err(crate::atoms::error::EngineError::Config)?;
+    store.set_config(DEX_ALLOWANCE_KEY, &serde_json::to_string(allowance)?)
+}
+
+/// Enforce the configured allowance for a DEX tool call. Non-DEX tools and
+/// a disabled allowance always pass.
+pub fn enforce(store: &SessionStore, tool: &str, args: &serde_json::Value) -> Result<(), String> {
+    if !ALLOWANCE_TOOLS.contains(&tool) {
+        return Ok(());
+    }
+    let allowance = load_allowance(store);
+    if !allowance.is_enabled() {
+        return Ok(());
+    }
+    let (token, amount) =
+        spend_of(tool, args).ok_or("DEX allowance: the transaction amount could not be read.")?;
+    let value = value_of(&token, amount).ok_or_else(|| {
+        format!(
+            "DEX allowance: {} {} can't be valued against the allowance — only ETH and \
+             USD stablecoin amounts can be checked. Spend ETH or a stablecoin instead, \
+             or clear the allowance in Settings.",
+            amount,
+            token.to_uppercase()
+        )
+    })?;
+    let spent = spent_today(store);
+    allowance.check(value, spent)
+}
+
+/// ETH and USD spent today by completed DEX swaps and transfers.
+pub fn spent_today(store: &SessionStore) -> TxValue {
+    let mut total = TxValue::default();
+    for (token, amount) in store.dex_spend_today().unwrap_or_default() {
+        if let Some(v) = value_of(&token, amount) {
+            total.eth += v.eth;
+            total.usd += v.usd;
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    fn allowance(max_tx_eth: f64, max_daily_usd: f64) -> DexAllowance {
+        DexAllowance {
+            max_tx_eth,
+            max_daily_usd,
+            ..Default::default()
+        }
+    }
+
+    fn transfer(currency: &str, amount: &str) -> serde_json::Value {
+        serde_json::json!({ "currency": currency, "amount": amount, "to_address": "0xabc" })
+    }
+
+    #[test]
+    fn over_allowance_transfer_is_rejected() {
+        let store = test_store();
+        save_allowance(&store, &allowance(0.5, 0.0)).unwrap();
+
+        let err = enforce(&store, "dex_transfer", &transfer("ETH", "2")).unwrap_err();
+        assert!(err.contains("per-transaction limit of 0.5 ETH"));
+        assert!(enforce(&store, "dex_transfer", &transfer("eth", "0.25")).is_ok());
+    }
+
+    #[test]
+    fn daily_cap_counts_completed_spend() {
+        let store = test_store();
+        save_allowance(&store, &allowance(0.0, 100.0)).unwrap();
+        store
+            .insert_trade(
+                "dex_transfer",
+                Some("transfer"),
+                Some("USDC"),
+                Some("USDC"),
+                "80",
+                None,
+                None,
+                "completed",
+                None,
+                Some("0xabc"),
+                "",
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let swap = serde_json::json!({ "token_in": "USDT", "token_out": "ETH", "amount": "30" });
+        let err = enforce(&store, "dex_swap", &swap).unwrap_err();
+        assert!(err.contains("daily limit"));
+        let small = serde_json::json!({ "token_in": "USDT", "token_out": "ETH", "amount": "20" });
+        assert!(enforce(&store, "dex_swap", &small).is_ok());
+    }
+
+    #[test]
+    fn unpriced_tokens_fail_closed_only_when_enabled() {
+        let store = test_store();
+        let swap = serde_json::json!({ "token_in": "PEPE", "token_out": "ETH", "amount": "1e9" });
+        assert!(enforce(&store, "dex_swap", &swap).is_ok());
+
+        save_allowance(&store, &allowance(1.0, 0.0)).unwrap();
+        assert!(enforce(&store, "dex_swap", &swap)
+            .unwrap_err()
+            .contains("can't be valued"));
+        assert!(enforce(&store, "dex_quote", &swap).is_ok());
+    }
+
+    #[test]
+    fn stablecoin_spend_fails_closed_with_only_eth_caps() {
+        let store = test_store();
+        save_allowance(&store, &allowance(0.5, 0.0)).unwrap();
+        let swap =
+            serde_json::json!({ "token_in": "USDC", "token_out": "ETH", "amount": "1000000" });
+        assert!(enforce(&store, "dex_swap", &swap)
+            .unwrap_err()
+            .contains("only ETH caps"));
+    }
+
+    #[test]
+    fn eth_spend_fails_closed_with_only_usd_caps() {
+        let store = test_store();
+        save_allowance(&store, &allowance(0.0, 100.0)).unwrap();
+        let err = enforce(&store, "dex_transfer", &transfer("ETH", "500")).unwrap_err();
+        assert!(err.contains("only USD caps"));
+    }
+
+    #[test]
+    fn negative_caps_are_rejected() {
+        let store = test_store();
+        assert!(save_allowance(&store, &allowance(-1.0, 0.0)).is_err());
+    }
+}
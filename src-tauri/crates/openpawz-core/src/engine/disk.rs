@@ -0,0 +1,110 @@
+// Paw Engine — Disk-space preflight
+//
+// Heavy operations (Ollama model pulls, database copies, data-root
+// migration, large workspace writes) check for room before they start, so a
+// full disk is reported up front instead of leaving a half-written file or a
+// truncated database behind.
+//
+// Where the platform can't report free space the check passes — the
+// operation's own I/O errors still apply.
+
+use crate::atoms::error::{EngineError, EngineResult};
+use std::path::Path;
+
+/// Kept free on top of what an operation needs — SQLite's WAL, logs and the
+/// OS need room too.
+pub const SAFETY_MARGIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Workspace writes at or above this size are preflighted.
+pub const LARGE_WRITE_BYTES: u64 = 1024 * 1024;
+
+/// Fail with a clear "insufficient disk space" error unless the filesystem
+/// holding `path` has `required_bytes` (plus the safety margin) free.
+pub fn check_free_space(path: &Path, required_bytes: u64) -> EngineResult<()> {
+    ensure_free_space(path, required_bytes, available_space(path))
+}
+
+fn ensure_free_space(path: &Path, required_bytes: u64, available: Option<u64>) -> EngineResult<()> {
+    let Some(available) = available else {
+        return Ok(());
+    };
+    let needed = required_bytes.saturating_add(SAFETY_MARGIN_BYTES);
+    if available < needed {
+        return Err(EngineError::Other(format!(
+            "Insufficient disk space at {}: {} needed, {} available",
+            path.display(),
+            format_bytes(needed),
+            format_bytes(available)
+        )));
+    }
+    Ok(())
+}
+
+/// Free bytes on the filesystem holding `path` (or its nearest existing
+/// ancestor). `None` where the platform doesn't tell us.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // statvfs field widths differ across platforms
+pub fn available_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a
+    // writable statvfs struct.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Human-readable byte count ("1.5 GB").
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn rejects_when_required_exceeds_available() {
+        let path = Path::new("/data");
+        let err = ensure_free_space(path, 5 * GB, Some(2 * GB)).unwrap_err();
+        assert!(err.to_string().contains("Insufficient disk space"));
+        assert!(err.to_string().contains("2.0 GB available"));
+
+        // The safety margin counts too
+        assert!(ensure_free_space(path, GB, Some(GB)).is_err());
+        assert!(ensure_free_space(path, GB, Some(GB + SAFETY_MARGIN_BYTES)).is_ok());
+    }
+
+    #[test]
+    fn unknown_free_space_passes() {
+        assert!(ensure_free_space(Path::new("/data"), u64::MAX, None).is_ok());
+    }
+
+    #[test]
+    fn formats_byte_counts() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * GB), "3.0 GB");
+    }
+}
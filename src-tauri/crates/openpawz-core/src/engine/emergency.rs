@@ -0,0 +1,142 @@
+// Paw Agent Engine — Emergency stop
+//
+// One switch that halts all agent activity: `halt` flips a process-wide flag
+// and fires a shared cancel token. Every agent run links itself to that
+// token on entry (`watch`), so runs already in flight stop at their next
+// await exactly as if the user had pressed Stop, and `ensure_armed` refuses
+// new runs until `resume` re-arms the engine.
+//
+// The flag lives here rather than in EngineState so every caller of the
+// agent loop — chat, tasks, swarm, channel bridges, headless — sees it.
+// Stopping the bridges and pausing the scheduler is the desktop app's job
+// (commands/emergency.rs); this module only decides whether runs may start.
+
+use crate::engine::cancel::CancelToken;
+use crate::engine::i18n::t;
+use log::warn;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+#[derive(Default)]
+struct HaltState {
+    halted_at: Option<String>,
+    /// Cancelled on halt; replaced with a fresh token on resume.
+    token: CancelToken,
+}
+
+static STATE: LazyLock<Mutex<HaltState>> = LazyLock::new(Default::default);
+
+/// Whether the engine is halted, for the UI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltStatus {
+    pub halted: bool,
+    /// RFC 3339 time of the emergency stop.
+    pub halted_at: Option<String>,
+}
+
+/// Halt all agent activity. Returns false if already halted.
+pub fn halt() -> bool {
+    let mut state = STATE.lock();
+    if state.halted_at.is_some() {
+        return false;
+    }
+    state.halted_at = Some(chrono::Utc::now().to_rfc3339());
+    state.token.cancel();
+    warn!("[emergency] Engine halted — all agent runs cancelled");
+    true
+}
+
+/// Re-arm the engine. Returns false if it was not halted.
+pub fn resume() -> bool {
+    let mut state = STATE.lock();
+    if state.halted_at.take().is_none() {
+        return false;
+    }
+    state.token = CancelToken::new();
+    warn!("[emergency] Engine resumed");
+    true
+}
+
+pub fn is_halted() -> bool {
+    STATE.lock().halted_at.is_some()
+}
+
+pub fn status() -> HaltStatus {
+    let state = STATE.lock();
+    HaltStatus {
+        halted: state.halted_at.is_some(),
+        halted_at: state.halted_at.clone(),
+    }
+}
+
+/// Reject a new run while halted, with a message for the user.
+pub fn ensure_armed() -> Result<(), String> {
+    if is_halted() {
+        Err(t("run.halted", &[]))
+    } else {
+        Ok(())
+    }
+}
+
+/// Cancels a run's token when the engine is halted; stops watching on drop.
+pub struct HaltWatch(Option<tokio::task::AbortHandle>);
+
+impl Drop for HaltWatch {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Tie `run` to the emergency stop. Must be called inside a Tokio runtime.
+pub fn watch(run: &CancelToken) -> HaltWatch {
+    let halt = STATE.lock().token.clone();
+    if halt.is_cancelled() {
+        run.cancel();
+        return HaltWatch(None);
+    }
+    let run = run.clone();
+    let task = tokio::spawn(async move {
+        halt.cancelled().await;
+        run.cancel();
+    });
+    HaltWatch(Some(task.abort_handle()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // One test owns the process-wide switch so parallel tests can't race it.
+    #[tokio::test]
+    async fn runs_are_rejected_after_emergency_stop_until_resume() {
+        assert!(ensure_armed().is_ok());
+        let in_flight = CancelToken::new();
+        let _watch = watch(&in_flight);
+
+        assert!(halt());
+        assert!(!halt(), "second stop is a no-op");
+        tokio::time::timeout(Duration::from_secs(1), in_flight.cancelled())
+            .await
+            .expect("in-flight run should be cancelled by the stop");
+        let err = ensure_armed().unwrap_err();
+        assert!(err.contains("emergency stop"), "{}", err);
+        assert!(status().halted_at.is_some());
+
+        // A run started while halted is cancelled straight away
+        let late = CancelToken::new();
+        let _late_watch = watch(&late);
+        assert!(late.is_cancelled());
+
+        assert!(resume());
+        assert!(ensure_armed().is_ok());
+        assert_eq!(status(), HaltStatus::default());
+        let fresh = CancelToken::new();
+        let _fresh_watch = watch(&fresh);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!fresh.is_cancelled());
+    }
+}
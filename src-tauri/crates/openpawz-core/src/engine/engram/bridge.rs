@@ -16,6 +16,7 @@ use crate::atoms::engram_types::{
 };
 use crate::atoms::error::EngineResult;
 use crate::engine::engram::encryption;
+use crate::engine::memory::capture::fact_importance;
 use crate::engine::memory::EmbeddingClient;
 use crate::engine::sessions::SessionStore;
 use log::{info, warn};
@@ -102,7 +103,8 @@ pub async fn store(
 
 /// Store an auto-captured memory (from fact extraction or session summary).
 ///
-/// Uses `AutoCapture` source and lower default importance.
+/// Uses `AutoCapture` source; importance follows the category, as for the
+/// legacy store (`memory::capture::fact_importance`).
 #[allow(clippy::too_many_arguments)]
 pub async fn store_auto_capture(
     store: &SessionStore,
@@ -146,7 +148,7 @@ pub async fn store_auto_capture(
         content: TieredContent::from_text(&stored_content),
         outcome: None,
         category: category.to_string(),
-        importance: 0.4, // auto-captured starts lower
+        importance: fact_importance(category) as f32,
         agent_id: agent_id.unwrap_or("default").to_string(),
         session_id: session_id.unwrap_or("unknown").to_string(),
         source: MemorySource::AutoCapture,
@@ -516,4 +518,26 @@ mod tests {
         assert_eq!(result.memory_type, "Episodic");
         assert!(result.score > 0.5);
     }
+
+    #[tokio::test]
+    async fn auto_capture_importance_follows_the_category() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::engine::sessions::schema_for_testing(&conn);
+        let store = SessionStore::from_connection(conn);
+
+        let mut stored = Vec::new();
+        for (content, category) in [
+            ("Always answer in French", "instruction"),
+            ("Worked on the quarterly report", "session"),
+        ] {
+            let id = store_auto_capture(
+                &store, content, category, None, None, None, None, None, None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+            stored.push(store.engram_get_episodic(&id).unwrap().unwrap().importance);
+        }
+        assert_eq!(stored, vec![8.0, 3.0]);
+    }
 }
@@ -25,6 +25,7 @@ use crate::engine::engram::intent_classifier;
 use crate::engine::engram::sensory_buffer::{SensoryBuffer, SensoryEntry};
 use crate::engine::engram::tokenizer::Tokenizer;
 use crate::engine::engram::working_memory::WorkingMemory;
+use crate::engine::util::safe_truncate;
 use log::{debug, info};
 
 /// Per-agent cognitive state wrapping all three memory tiers.
@@ -82,7 +83,7 @@ impl CognitiveState {
                 &self.agent_id,
                 cognitive_event::CognitiveEventKind::SensoryPromoted {
                     entry_preview: if content.len() > 60 {
-                        format!("{}…", &content[..60])
+                        format!("{}…", safe_truncate(&content, 60))
                     } else {
                         content
                     },
@@ -19,6 +19,7 @@ use crate::atoms::error::EngineResult;
 use crate::engine::engram::metadata_inference;
 use crate::engine::memory::EmbeddingClient;
 use crate::engine::sessions::SessionStore;
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
 
 /// Max NDCG drop tolerated before consolidation is rolled back (§ Transactional Forgetting).
@@ -827,7 +828,7 @@ fn extract_object(mem: &EpisodicMemory) -> String {
             // Truncate full content to keep object reasonable
             let full = &mem.content.full;
             if full.len() > 200 {
-                format!("{}...", &full[..200])
+                format!("{}...", safe_truncate(full, 200))
             } else {
                 full.clone()
             }
@@ -873,7 +874,7 @@ fn detect_gaps(store: &SessionStore) -> EngineResult<Vec<KnowledgeGap>> {
 
         for (id, content) in rows {
             let snippet = if content.len() > 80 {
-                format!("{}...", &content[..80])
+                format!("{}...", safe_truncate(&content, 80))
             } else {
                 content
             };
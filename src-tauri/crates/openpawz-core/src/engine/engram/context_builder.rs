@@ -40,6 +40,7 @@ use crate::engine::engram::tokenizer::Tokenizer;
 use crate::engine::engram::working_memory::WorkingMemory;
 use crate::engine::memory::EmbeddingClient;
 use crate::engine::sessions::SessionStore;
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
 use std::borrow::Cow;
 
@@ -145,6 +146,7 @@ pub struct ContextBuilder<'a> {
     scope: MemoryScope,
     user_query: Option<String>,
     recall_config: Option<MemorySearchConfig>,
+    recall_limit: Option<usize>,
     hnsw_index: Option<&'a super::hnsw::SharedHnswIndex>,
 
     // Working memory
@@ -181,6 +183,7 @@ impl<'a> ContextBuilder<'a> {
             scope: MemoryScope::default(),
             user_query: None,
             recall_config: None,
+            recall_limit: None,
             hnsw_index: None,
             working_memory: None,
             messages: Vec::new(),
@@ -278,6 +281,12 @@ impl<'a> ContextBuilder<'a> {
         self
     }
 
+    /// Cap the number of recalled memories injected (top-k).
+    pub fn recall_limit(mut self, limit: usize) -> Self {
+        self.recall_limit = Some(limit);
+        self
+    }
+
     /// Set the HNSW vector index for O(log n) approximate nearest-neighbor search.
     pub fn hnsw_index(mut self, index: &'a super::hnsw::SharedHnswIndex) -> Self {
         self.hnsw_index = Some(index);
@@ -391,10 +400,12 @@ impl<'a> ContextBuilder<'a> {
                             // recalled memory has very low relevance, the user
                             // likely switched topics. Injecting stale memories
                             // causes the model to loop on the old topic.
+                            // Scored like the CRAG gate: the raw relevance of
+                            // an episodic hit is a rank-fusion score (≤ 1/61).
                             let top_relevance = result
                                 .memories
                                 .first()
-                                .map(|m| m.trust_score.relevance)
+                                .map(|m| m.trust_score.composite())
                                 .unwrap_or(0.0);
                             if top_relevance < super::gated_search::TOPIC_SHIFT_RELEVANCE_FLOOR {
                                 info!(
@@ -435,6 +446,9 @@ impl<'a> ContextBuilder<'a> {
                                     })
                                     .collect();
                                 recalled_memories.truncate(resistance.max_recalled_memories);
+                                if let Some(limit) = self.recall_limit {
+                                    recalled_memories.truncate(limit);
+                                }
                             }
                         }
                     }
@@ -860,7 +874,7 @@ fn trim_history(
                     .map(|i| &content[..=i])
                     .unwrap_or_else(|| {
                         if content.len() > 80 {
-                            &content[..80]
+                            safe_truncate(content, 80)
                         } else {
                             content
                         }
@@ -1030,4 +1044,89 @@ mod tests {
         assert_eq!(report.context_window, 0);
         assert_eq!(report.memories_injected, 0);
     }
+
+    fn store_episodic(store: &SessionStore, id: &str, content: &str, embedding: Vec<f32>) {
+        use crate::atoms::engram_types::{EpisodicMemory, TieredContent};
+        let memory = EpisodicMemory {
+            id: id.to_string(),
+            content: TieredContent {
+                full: content.to_string(),
+                ..Default::default()
+            },
+            category: "fact".to_string(),
+            agent_id: "default".to_string(),
+            session_id: "s1".to_string(),
+            scope: MemoryScope::agent("default"),
+            importance: 1.0,
+            embedding: Some(embedding),
+            embedding_model: Some("text-embedding-3-small".to_string()),
+            created_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            ..Default::default()
+        };
+        store.engram_store_episodic(&memory).unwrap();
+    }
+
+    /// The chat path: recall_limit / recall_threshold from MemoryConfig decide
+    /// which stored memories reach the assembled system prompt.
+    #[tokio::test]
+    async fn relevant_memory_is_recalled_into_the_system_prompt() {
+        use crate::atoms::types::{EmbeddingProvider, MemoryConfig};
+        use crate::engine::memory::EmbeddingClient;
+        use crate::engine::test_support::{serve, MockReply};
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::engine::sessions::schema_for_testing(&conn);
+        let store = SessionStore::from_connection(conn);
+        store_episodic(
+            &store,
+            "m1",
+            "The user's dog is called Biscuit",
+            vec![0.9, 0.1, 0.0],
+        );
+        store_episodic(
+            &store,
+            "m2",
+            "Quarterly taxes are due in April",
+            vec![0.0, 0.0, 1.0],
+        );
+
+        let (url, _requests) = serve(MockReply::json(
+            200,
+            r#"{"data":[{"embedding":[0.9,0.1,0.0]}]}"#,
+        ))
+        .await;
+        let client = EmbeddingClient::new(&MemoryConfig {
+            embedding_provider: EmbeddingProvider::OpenAI,
+            embedding_base_url: url,
+            embedding_model: "text-embedding-3-small".into(),
+            embedding_api_key: "sk-test".into(),
+            ..Default::default()
+        });
+
+        let context = ContextBuilder::new("gpt-4o")
+            .base_prompt("You are a helpful assistant.")
+            .recall_from(
+                &store,
+                Some(&client),
+                MemoryScope::agent("default"),
+                "What is my dog called?",
+            )
+            .recall_config(MemorySearchConfig {
+                similarity_threshold: 0.5,
+                ..Default::default()
+            })
+            .recall_limit(1)
+            .build()
+            .await
+            .unwrap();
+
+        let prompt = context.system_prompt.unwrap();
+        assert!(
+            prompt.contains("The user's dog is called Biscuit"),
+            "{}",
+            prompt
+        );
+        assert!(!prompt.contains("Quarterly taxes"));
+        assert_eq!(context.budget.memories_injected, 1);
+    }
 }
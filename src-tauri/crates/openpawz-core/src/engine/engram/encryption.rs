@@ -15,6 +15,7 @@
 //   Layer 2 — LLM-assisted secondary scan during consolidation (stage 2.5)
 //             for context-dependent PII that regex cannot catch.
 
+use crate::engine::util::safe_truncate;
 use aes_gcm::aead::Aead;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use hkdf::Hkdf;
@@ -1102,7 +1103,7 @@ pub async fn llm_pii_scan(
             LLM_PII_SYSTEM_PROMPT,
             // Truncate to avoid token waste — PII is usually in the first ~500 chars
             if content.len() > 1000 {
-                &content[..1000]
+                safe_truncate(content, 1000)
             } else {
                 content
             }
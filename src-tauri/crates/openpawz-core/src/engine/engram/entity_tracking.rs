@@ -12,6 +12,7 @@
 use crate::atoms::engram_types::{EntityMention, EntityProfile, EntityType};
 use crate::atoms::error::{EngineError, EngineResult};
 use crate::engine::sessions::SessionStore;
+use crate::engine::types::truncate_utf8;
 use log::info;
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -99,7 +100,7 @@ pub fn process_memory_entities(
             "[engram:entity] Processed {} mentions → {} entities for memory {}",
             mentions.len(),
             profiles.len(),
-            &memory_id[..8]
+            truncate_utf8(memory_id, 8)
         );
     }
 
@@ -335,7 +335,11 @@ pub fn run_fusion(store: &SessionStore) -> EngineResult<FusionReport> {
 
 /// Redirect all edges pointing to `old_id` to point to `new_id` instead.
 /// Returns the number of edges redirected.
-fn redirect_edges(store: &SessionStore, old_id: &str, new_id: &str) -> EngineResult<usize> {
+pub(super) fn redirect_edges(
+    store: &SessionStore,
+    old_id: &str,
+    new_id: &str,
+) -> EngineResult<usize> {
     let mut count = 0;
 
     // Redirect outgoing edges (old_id as source)
@@ -379,7 +383,7 @@ fn redirect_edges(store: &SessionStore, old_id: &str, new_id: &str) -> EngineRes
 }
 
 /// Mark a memory as archived (tombstoned). Recoverable — content is preserved.
-fn tombstone(store: &SessionStore, id: &str) -> EngineResult<()> {
+pub(super) fn tombstone(store: &SessionStore, id: &str) -> EngineResult<()> {
     store.engram_set_consolidation_state(id, ConsolidationState::Archived)
 }
 
@@ -400,7 +404,7 @@ fn audit_fusion(
 }
 
 /// Cosine similarity between two f32 vectors.
-fn cosine_sim(a: &[f32], b: &[f32]) -> f64 {
+pub(super) fn cosine_sim(a: &[f32], b: &[f32]) -> f64 {
     if a.len() != b.len() || a.is_empty() {
         return 0.0;
     }
@@ -0,0 +1,476 @@
+// ── Engram: LLM Memory Merge ────────────────────────────────────────────────
+//
+// Opt-in summarization of related memories. Fusion (memory_fusion.rs) only
+// handles near-duplicate *pairs* with word-overlap heuristics; over months an
+// agent still accumulates many small memories about the same topic. This job
+// groups them by embedding similarity and asks the LLM to rewrite each group
+// as one concise canonical memory.
+//
+// Pipeline (bounded by MemoryMergeConfig):
+//   1. Cluster — each unclaimed memory (most important first) gathers the
+//      unclaimed memories of the same agent within `similarity_threshold`
+//   2. Merge — the LLM rewrites the cluster as one memory
+//   3. Replace — the merged memory takes the highest importance and summed
+//      access count, edges are redirected to it, the originals get a
+//      ConsolidatedInto edge and are tombstoned (recoverable)
+//   4. Audit — one `memory_merge` audit entry listing every source id
+//
+// Encrypted memories are never sent to the model and are left untouched.
+//
+// Stored as JSON under the `memory_merge_config` config key; the host runs
+// the job every `interval_hours` while enabled.
+
+use super::encryption::is_encrypted;
+use super::hnsw::{self, SharedHnswIndex};
+use super::memory_fusion::{cosine_sim, redirect_edges, tombstone};
+use crate::atoms::engram_types::{
+    ConsolidationState, EdgeType, EpisodicMemory, MemoryEdge, MemoryScope, MemorySource,
+    TieredContent,
+};
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::memory::EmbeddingClient;
+use crate::engine::providers::AnyProvider;
+use crate::engine::sessions::SessionStore;
+use crate::engine::util::safe_truncate;
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+
+/// engine_config key holding the serialized [`MemoryMergeConfig`].
+pub const MEMORY_MERGE_CONFIG_KEY: &str = "memory_merge_config";
+
+/// Most recent memories scanned per run.
+const MERGE_SAMPLE_SIZE: usize = 300;
+
+/// Longest memory text included in the merge prompt, in bytes.
+const MAX_PROMPT_MEMORY_BYTES: usize = 1_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryMergeConfig {
+    /// Run periodically in the background.
+    pub enabled: bool,
+    /// Cosine similarity a memory needs to the cluster's lead memory.
+    pub similarity_threshold: f64,
+    /// Largest cluster sent to the model.
+    pub max_cluster_size: usize,
+    /// Clusters merged per run (each costs one LLM call).
+    pub max_clusters_per_run: usize,
+    /// Hours between background runs.
+    pub interval_hours: u32,
+    /// Model for the merge calls; None uses the cheap model, else the default.
+    pub model: Option<String>,
+}
+
+impl Default for MemoryMergeConfig {
+    fn default() -> Self {
+        MemoryMergeConfig {
+            enabled: false,
+            similarity_threshold: 0.85,
+            max_cluster_size: 6,
+            max_clusters_per_run: 10,
+            interval_hours: 24,
+            model: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryMergeReport {
+    pub clusters_found: usize,
+    pub clusters_merged: usize,
+    /// Original memories tombstoned in favour of a merged one.
+    pub memories_replaced: usize,
+    /// Clusters left alone because the model failed or gave an unusable answer.
+    pub clusters_skipped: usize,
+    pub merged_ids: Vec<String>,
+}
+
+/// Load the settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> MemoryMergeConfig {
+    store
+        .get_config(MEMORY_MERGE_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, config: &MemoryMergeConfig) -> EngineResult<()> {
+    store.set_config(MEMORY_MERGE_CONFIG_KEY, &serde_json::to_string(config)?)
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// Clustering
+// ═════════════════════════════════════════════════════════════════════════════
+
+fn mergeable(mem: &EpisodicMemory) -> bool {
+    mem.embedding.is_some()
+        && mem.consolidation_state != ConsolidationState::Archived
+        && !is_encrypted(&mem.content.full)
+}
+
+/// Group related memories. Memories are visited most important first; each
+/// unclaimed one leads a cluster of the unclaimed memories from the same agent
+/// whose similarity to it is at least `threshold`, closest first, up to
+/// `max_size`. Memories without an embedding, archived, or encrypted are
+/// ignored; groups of one are dropped.
+pub fn cluster_memories(
+    memories: &[EpisodicMemory],
+    threshold: f64,
+    max_size: usize,
+) -> Vec<Vec<&EpisodicMemory>> {
+    let mut order: Vec<&EpisodicMemory> = memories.iter().filter(|m| mergeable(m)).collect();
+    order.sort_by(|a, b| {
+        b.importance
+            .partial_cmp(&a.importance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
+
+    let mut claimed: HashSet<&str> = HashSet::new();
+    let mut clusters = Vec::new();
+    for (i, lead) in order.iter().enumerate() {
+        if claimed.contains(lead.id.as_str()) {
+            continue;
+        }
+        let lead_emb = lead.embedding.as_deref().unwrap_or_default();
+        let mut members: Vec<(f64, &EpisodicMemory)> = order[i + 1..]
+            .iter()
+            .filter(|m| m.agent_id == lead.agent_id && !claimed.contains(m.id.as_str()))
+            .filter_map(|m| {
+                let sim = cosine_sim(lead_emb, m.embedding.as_deref().unwrap_or_default());
+                (sim >= threshold).then_some((sim, *m))
+            })
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        members.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut cluster = vec![*lead];
+        cluster.extend(
+            members
+                .into_iter()
+                .take(max_size.max(2) - 1)
+                .map(|(_, m)| m),
+        );
+        claimed.extend(cluster.iter().map(|m| m.id.as_str()));
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// Merging
+// ═════════════════════════════════════════════════════════════════════════════
+
+const MERGE_INSTRUCTIONS: &str = "You merge related memories of an AI assistant into one. \
+     Write a single concise memory that keeps every distinct fact, preference, name, number \
+     and date from the inputs, drops repetition, and prefers the newer memory where they \
+     conflict. Respond with ONLY the merged memory text, no preamble or formatting.";
+
+/// The user prompt listing a cluster's memories, oldest first.
+pub fn merge_prompt(cluster: &[&EpisodicMemory]) -> String {
+    let mut sorted = cluster.to_vec();
+    sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    let mut prompt = String::from("Memories to merge:\n");
+    for (i, mem) in sorted.iter().enumerate() {
+        prompt.push_str(&format!(
+            "{}. [{}] {}\n",
+            i + 1,
+            mem.created_at,
+            safe_truncate(mem.content.full.trim(), MAX_PROMPT_MEMORY_BYTES)
+        ));
+    }
+    prompt
+}
+
+/// Ask `provider` to merge one cluster's prompt (see [`merge_prompt`]).
+pub async fn llm_merge(
+    provider: &AnyProvider,
+    model: &str,
+    prompt: String,
+) -> EngineResult<String> {
+    use crate::engine::types::{Message, MessageContent, Role};
+
+    let message = |role, text: String| Message {
+        role,
+        content: MessageContent::Text(text),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+    };
+    let messages = vec![
+        message(Role::System, MERGE_INSTRUCTIONS.to_string()),
+        message(Role::User, prompt),
+    ];
+    let chunks = provider
+        .chat_stream(&messages, &[], model, Some(0.0), None)
+        .await?;
+    Ok(chunks
+        .iter()
+        .filter_map(|c| c.delta_text.as_deref())
+        .collect::<String>()
+        .trim()
+        .to_string())
+}
+
+fn mean_embedding(cluster: &[&EpisodicMemory]) -> Option<Vec<f32>> {
+    let first = cluster.first()?.embedding.as_ref()?;
+    let mut sum = vec![0.0f32; first.len()];
+    for emb in cluster.iter().filter_map(|m| m.embedding.as_ref()) {
+        if emb.len() != sum.len() {
+            return None;
+        }
+        sum.iter_mut().zip(emb).for_each(|(s, v)| *s += v);
+    }
+    let n = cluster.len() as f32;
+    Some(sum.into_iter().map(|s| s / n).collect())
+}
+
+/// Store `merged_text` as the canonical memory for `cluster` and retire the
+/// originals. Returns the new memory's id.
+pub fn replace_cluster(
+    store: &SessionStore,
+    cluster: &[&EpisodicMemory],
+    merged_text: &str,
+    embedding: Option<(Vec<f32>, Option<String>)>,
+    hnsw_index: Option<&SharedHnswIndex>,
+) -> EngineResult<String> {
+    let lead = cluster
+        .first()
+        .ok_or_else(|| EngineError::Other("Cannot merge an empty cluster".into()))?;
+    let (embedding, embedding_model) = match embedding {
+        Some((emb, model)) => (Some(emb), model),
+        None => (mean_embedding(cluster), lead.embedding_model.clone()),
+    };
+
+    let merged = EpisodicMemory {
+        content: TieredContent {
+            full: merged_text.to_string(),
+            ..TieredContent::default()
+        },
+        category: lead.category.clone(),
+        importance: cluster
+            .iter()
+            .map(|m| m.importance)
+            .fold(lead.importance, f32::max),
+        agent_id: lead.agent_id.clone(),
+        session_id: lead.session_id.clone(),
+        source: MemorySource::Consolidation,
+        consolidation_state: ConsolidationState::Consolidated,
+        scope: lead.scope.clone(),
+        embedding,
+        embedding_model,
+        access_count: cluster.iter().map(|m| m.access_count).sum(),
+        ..EpisodicMemory::default()
+    };
+    store.engram_store_episodic(&merged)?;
+    if let (Some(idx), Some(emb)) = (hnsw_index, &merged.embedding) {
+        hnsw::insert_shared(idx, &merged.id, emb.clone());
+    }
+
+    let now = Utc::now().to_rfc3339();
+    for source in cluster {
+        redirect_edges(store, &source.id, &merged.id)?;
+        store.engram_add_edge(&MemoryEdge {
+            source_id: source.id.clone(),
+            target_id: merged.id.clone(),
+            edge_type: EdgeType::ConsolidatedInto,
+            weight: 1.0,
+            created_at: now.clone(),
+        })?;
+        tombstone(store, &source.id)?;
+        if let Some(idx) = hnsw_index {
+            hnsw::remove_shared(idx, &source.id);
+        }
+    }
+
+    let source_ids: Vec<&str> = cluster.iter().map(|m| m.id.as_str()).collect();
+    store.engram_audit_log(
+        "memory_merge",
+        &merged.id,
+        &merged.agent_id,
+        "merge",
+        Some(&format!("merged={}", source_ids.join(","))),
+    )?;
+    Ok(merged.id)
+}
+
+/// Run one merge pass: cluster the most recent memories and replace up to
+/// `max_clusters_per_run` clusters with the text returned by `merge` for each
+/// cluster's [`merge_prompt`]. A failed or empty answer skips that cluster.
+pub async fn run_memory_merge<F, Fut>(
+    store: &SessionStore,
+    embedding_client: Option<&EmbeddingClient>,
+    hnsw_index: Option<&SharedHnswIndex>,
+    config: &MemoryMergeConfig,
+    merge: F,
+) -> EngineResult<MemoryMergeReport>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = EngineResult<String>>,
+{
+    let mut report = MemoryMergeReport::default();
+    let memories = store.engram_list_episodic(&MemoryScope::global(), None, MERGE_SAMPLE_SIZE)?;
+    let clusters = cluster_memories(
+        &memories,
+        config.similarity_threshold,
+        config.max_cluster_size,
+    );
+    report.clusters_found = clusters.len();
+
+    for cluster in clusters.iter().take(config.max_clusters_per_run) {
+        let merged_text = match merge(merge_prompt(cluster)).await {
+            Ok(text) if !text.trim().is_empty() => text,
+            Ok(_) => {
+                warn!("[engram::merge] Model returned an empty merge — skipping cluster");
+                report.clusters_skipped += 1;
+                continue;
+            }
+            Err(e) => {
+                warn!(
+                    "[engram::merge] Merge call failed: {} — skipping cluster",
+                    e
+                );
+                report.clusters_skipped += 1;
+                continue;
+            }
+        };
+        let embedding = match embedding_client {
+            Some(client) => match client.embed(&merged_text).await {
+                Ok(emb) => Some((emb, Some(client.model_name().to_string()))),
+                Err(e) => {
+                    warn!("[engram::merge] Failed to embed merged memory: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let id = replace_cluster(store, cluster, merged_text.trim(), embedding, hnsw_index)?;
+        report.clusters_merged += 1;
+        report.memories_replaced += cluster.len();
+        report.merged_ids.push(id);
+    }
+
+    if report.clusters_merged > 0 {
+        info!(
+            "[engram::merge] Merged {} cluster(s), replacing {} memories ({} skipped)",
+            report.clusters_merged, report.memories_replaced, report.clusters_skipped
+        );
+    }
+    Ok(report)
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// Tests
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_memory(
+        id: &str,
+        content: &str,
+        embedding: Vec<f32>,
+        importance: f32,
+    ) -> EpisodicMemory {
+        EpisodicMemory {
+            id: id.to_string(),
+            content: TieredContent {
+                full: content.to_string(),
+                ..TieredContent::default()
+            },
+            importance,
+            agent_id: "default".to_string(),
+            session_id: "test".to_string(),
+            scope: MemoryScope::global(),
+            embedding: Some(embedding),
+            ..EpisodicMemory::default()
+        }
+    }
+
+    fn memories() -> Vec<EpisodicMemory> {
+        vec![
+            make_memory("r1", "User prefers Rust for CLIs", vec![1.0, 0.1, 0.0], 6.0),
+            make_memory("r2", "User writes CLIs in Rust", vec![1.0, 0.15, 0.0], 4.0),
+            make_memory("r3", "User likes the clap crate", vec![1.0, 0.2, 0.0], 5.0),
+            make_memory("trip", "Trip to Lisbon in May", vec![0.0, 0.1, 1.0], 7.0),
+        ]
+    }
+
+    #[test]
+    fn clustering_groups_similar_memories() {
+        let mut mems = memories();
+        // Same topic but another agent: never merged across agents
+        let mut other = make_memory("r4", "Rust CLIs", vec![1.0, 0.12, 0.0], 9.0);
+        other.agent_id = "other".into();
+        mems.push(other);
+
+        let clusters = cluster_memories(&mems, 0.9, 6);
+        assert_eq!(clusters.len(), 1);
+        let ids: HashSet<&str> = clusters[0].iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, HashSet::from(["r1", "r2", "r3"]));
+        assert_eq!(clusters[0][0].id, "r1", "most important memory leads");
+
+        let capped = cluster_memories(&mems, 0.9, 2);
+        assert_eq!(capped[0].len(), 2);
+    }
+
+    #[tokio::test]
+    async fn merged_memory_replaces_its_cluster() {
+        let store = SessionStore::open_in_memory().unwrap();
+        crate::engine::sessions::schema_for_testing(&store.conn.lock());
+        for mem in memories() {
+            store.engram_store_episodic(&mem).unwrap();
+        }
+
+        let config = MemoryMergeConfig {
+            enabled: true,
+            similarity_threshold: 0.9,
+            ..Default::default()
+        };
+        let report = run_memory_merge(&store, None, None, &config, |prompt| async move {
+            assert!(prompt.contains("clap crate"));
+            Ok("User builds CLIs in Rust, preferably with clap.".to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(report.clusters_merged, 1);
+        assert_eq!(report.memories_replaced, 3);
+
+        let merged = store
+            .engram_get_episodic(&report.merged_ids[0])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            merged.content.full,
+            "User builds CLIs in Rust, preferably with clap."
+        );
+        assert_eq!(merged.importance, 6.0, "keeps the highest importance");
+        assert!(merged.embedding.is_some());
+
+        for id in ["r1", "r2", "r3"] {
+            let old = store.engram_get_episodic(id).unwrap().unwrap();
+            assert_eq!(old.consolidation_state, ConsolidationState::Archived);
+            let provenance = store.engram_get_edges_from(id).unwrap();
+            assert!(provenance
+                .iter()
+                .any(|e| e.target_id == merged.id && e.edge_type == EdgeType::ConsolidatedInto));
+        }
+        let trip = store.engram_get_episodic("trip").unwrap().unwrap();
+        assert_ne!(trip.consolidation_state, ConsolidationState::Archived);
+
+        // Nothing left to merge on the next pass
+        let again = run_memory_merge(&store, None, None, &config, |_| async {
+            Ok("unused".to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(again.clusters_merged, 0);
+    }
+}
@@ -23,6 +23,7 @@
 //   - meta_cognition: Knowledge confidence mapping & reflection (§38)
 //   - abstraction_tree: 4-level hierarchical semantic compression (§42)
 //   - memory_bus: Multi-agent memory sync pub/sub protocol (§43)
+//   - memory_merge: Opt-in LLM summarization of related memory clusters
 //   - dream_replay: Idle-time memory replay & connection discovery (§44)
 //   - cognitive_event: Observability bus for cognitive pipeline events (§47.6)
 
@@ -47,6 +48,7 @@ pub mod hybrid_search;
 pub mod intent_classifier;
 pub mod memory_bus;
 pub mod memory_fusion;
+pub mod memory_merge;
 pub mod meta_cognition;
 pub mod metadata_inference;
 pub mod model_caps;
@@ -105,8 +107,9 @@ pub use meta_cognition::{
 };
 pub use metadata_inference::{infer_metadata, infer_metadata_full};
 pub use model_caps::{
-    resolve_context_window, resolve_injection_resistance, resolve_max_output_tokens,
-    resolve_model_capabilities,
+    known_model_capabilities, request_capabilities, resolve_context_window,
+    resolve_injection_resistance, resolve_max_output_tokens, resolve_model_capabilities,
+    set_capability_overrides,
 };
 pub use reranking::{cross_type_dedup, rerank_results};
 pub use retrieval_quality::{
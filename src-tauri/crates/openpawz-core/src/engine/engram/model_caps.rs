@@ -11,10 +11,32 @@
 //   1. Try exact model name match
 //   2. Try prefix match (handles date-suffixed IDs like claude-opus-4-6-20260115)
 //   3. Fall back to conservative defaults
+//   4. Apply the user's overrides from EngineConfig::model_capabilities
+//
+// Dropping tools or images from a request (request_capabilities) only trusts
+// exact and family matches, never a loose prefix.
 
 use crate::atoms::engram_types::{ModelCapabilities, ModelProvider, TokenizerType};
+use crate::atoms::types::ModelCapabilityOverride;
+use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
+/// Normalized (key, override) pairs, longest key first.
+static OVERRIDES: LazyLock<RwLock<Vec<(String, ModelCapabilityOverride)>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Install the user's capability overrides (call whenever EngineConfig loads
+/// or changes). Keys are model ids or id prefixes.
+pub fn set_capability_overrides(overrides: &HashMap<String, ModelCapabilityOverride>) {
+    let mut list: Vec<_> = overrides
+        .iter()
+        .map(|(model, o)| (normalize_model_name(model), o.clone()))
+        .collect();
+    list.sort_by_key(|e| std::cmp::Reverse(e.0.len()));
+    *OVERRIDES.write() = list;
+}
+
 /// Normalize a model name for matching.
 /// Strips common suffixes (dates, preview tags) and lowercases.
 pub fn normalize_model_name(model: &str) -> String {
@@ -50,29 +72,73 @@ pub fn normalize_model_name(model: &str) -> String {
 /// Every execution path (chat, tasks, orchestrator, swarm, flows, channels)
 /// must use this instead of hardcoded values.
 pub fn resolve_model_capabilities(model: &str) -> ModelCapabilities {
+    // Unknown model — conservative defaults
+    known_model_capabilities(model).unwrap_or_default()
+}
+
+/// Capabilities for a model the registry or the user's overrides know about.
+/// `None` means nothing is known — callers shouldn't enforce the defaults.
+pub fn known_model_capabilities(model: &str) -> Option<ModelCapabilities> {
     let norm = normalize_model_name(model);
 
     // Try exact match first, then prefix match
-    if let Some(caps) = try_exact_match(&norm) {
-        return caps;
-    }
-    if let Some(caps) = try_prefix_match(&norm) {
-        return caps;
-    }
+    let registered = try_exact_match(&norm).or_else(|| try_prefix_match(&norm));
+    with_overrides(&norm, registered, ModelCapabilities::default())
+}
 
-    // Unknown model — conservative defaults
-    ModelCapabilities::default()
+/// Capabilities to shape a request to `model` by: a registry entry naming
+/// the model or its family (`llama3.2`, `llama3.2:3b`, `qwen2.5-vl-7b`), or
+/// the user's override. Vendor catch-alls like `claude-` or `grok-` and bare
+/// prefixes (`gpt-4` for `gpt-4.5`) are too loose to strip tools or images
+/// on, so those models get `None` and are sent as-is.
+pub fn request_capabilities(model: &str) -> Option<ModelCapabilities> {
+    let norm = normalize_model_name(model);
+    let registered = try_exact_match(&norm).or_else(|| try_family_match(&norm));
+    // An override on an unregistered model only restricts what it names
+    let unregistered = ModelCapabilities {
+        supports_vision: true,
+        ..Default::default()
+    };
+    with_overrides(&norm, registered, unregistered)
+}
+
+/// Apply the user's override for `norm`; `unregistered` is the base when
+/// only the override knows the model.
+fn with_overrides(
+    norm: &str,
+    registered: Option<ModelCapabilities>,
+    unregistered: ModelCapabilities,
+) -> Option<ModelCapabilities> {
+    let overrides = OVERRIDES.read();
+    let user = overrides
+        .iter()
+        .find(|(key, _)| norm.starts_with(key.as_str()))
+        .map(|(_, o)| o);
+
+    let mut caps = match (registered, user) {
+        (Some(caps), _) => caps,
+        (None, Some(_)) => unregistered,
+        (None, None) => return None,
+    };
+    if let Some(o) = user {
+        if let Some(v) = o.context_window {
+            caps.context_window = v;
+        }
+        if let Some(v) = o.supports_tools {
+            caps.supports_tools = v;
+        }
+        if let Some(v) = o.supports_vision {
+            caps.supports_vision = v;
+        }
+    }
+    Some(caps)
 }
 
 /// Convenience: get just the context window size for a model.
 /// Replaces all `cfg.context_window_tokens` reads.
 pub fn resolve_context_window(model: &str, fallback: usize) -> usize {
-    let caps = resolve_model_capabilities(model);
-    if caps.provider == ModelProvider::Unknown {
-        fallback // use the user's configured fallback for truly unknown models
-    } else {
-        caps.context_window
-    }
+    // Use the user's configured fallback for truly unknown models
+    known_model_capabilities(model).map_or(fallback, |caps| caps.context_window)
 }
 
 /// Convenience: get the max output tokens for a model.
@@ -484,6 +550,20 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
                 provider: ModelProvider::OpenAI,
             },
         },
+        ModelEntry {
+            prefix: "gpt-4-vision",
+            caps: ModelCapabilities {
+                context_window: 128_000,
+                max_output_tokens: 4_096,
+                supports_tools: false,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::Cl100kBase,
+                rate_limit_rpm: None,
+                provider: ModelProvider::OpenAI,
+            },
+        },
         ModelEntry {
             prefix: "gpt-4-turbo",
             caps: ModelCapabilities {
@@ -721,6 +801,34 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
         // ═══════════════════════════════════════════════════════════════
         // xAI / Grok
         // ═══════════════════════════════════════════════════════════════
+        ModelEntry {
+            prefix: "grok-4",
+            caps: ModelCapabilities {
+                context_window: 256_000,
+                max_output_tokens: 16_384,
+                supports_tools: true,
+                supports_vision: true,
+                supports_extended_thinking: true,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: Some(60),
+                provider: ModelProvider::XAI,
+            },
+        },
+        ModelEntry {
+            prefix: "grok-vision",
+            caps: ModelCapabilities {
+                context_window: 8_192,
+                max_output_tokens: 4_096,
+                supports_tools: true,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: Some(60),
+                provider: ModelProvider::XAI,
+            },
+        },
         ModelEntry {
             prefix: "grok-3",
             caps: ModelCapabilities {
@@ -766,13 +874,27 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
         // ═══════════════════════════════════════════════════════════════
         // Local / Ollama models
         // ═══════════════════════════════════════════════════════════════
+        ModelEntry {
+            prefix: "llama4",
+            caps: ModelCapabilities {
+                context_window: 128_000,
+                max_output_tokens: 8_192,
+                supports_tools: true,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: None,
+                provider: ModelProvider::Ollama,
+            },
+        },
         ModelEntry {
             prefix: "llama-4",
             caps: ModelCapabilities {
                 context_window: 128_000,
                 max_output_tokens: 8_192,
                 supports_tools: true,
-                supports_vision: false,
+                supports_vision: true,
                 supports_extended_thinking: false,
                 supports_streaming: true,
                 tokenizer: TokenizerType::SentencePiece,
@@ -794,12 +916,26 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
                 provider: ModelProvider::Ollama,
             },
         },
+        ModelEntry {
+            prefix: "llama3.2-vision",
+            caps: ModelCapabilities {
+                context_window: 128_000,
+                max_output_tokens: 2_048,
+                supports_tools: false,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: None,
+                provider: ModelProvider::Ollama,
+            },
+        },
         ModelEntry {
             prefix: "llama3.2",
             caps: ModelCapabilities {
                 context_window: 8_192,
                 max_output_tokens: 2_048,
-                supports_tools: false,
+                supports_tools: true,
                 supports_vision: false,
                 supports_extended_thinking: false,
                 supports_streaming: true,
@@ -822,6 +958,34 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
                 provider: ModelProvider::Ollama,
             },
         },
+        ModelEntry {
+            prefix: "qwen2.5-vl",
+            caps: ModelCapabilities {
+                context_window: 128_000,
+                max_output_tokens: 8_192,
+                supports_tools: true,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: None,
+                provider: ModelProvider::Ollama,
+            },
+        },
+        ModelEntry {
+            prefix: "qwen2.5vl",
+            caps: ModelCapabilities {
+                context_window: 128_000,
+                max_output_tokens: 8_192,
+                supports_tools: true,
+                supports_vision: true,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: None,
+                provider: ModelProvider::Ollama,
+            },
+        },
         ModelEntry {
             prefix: "qwen2.5",
             caps: ModelCapabilities {
@@ -836,6 +1000,20 @@ static REGISTRY: LazyLock<Vec<ModelEntry>> = LazyLock::new(|| {
                 provider: ModelProvider::Ollama,
             },
         },
+        ModelEntry {
+            prefix: "gemma2",
+            caps: ModelCapabilities {
+                context_window: 8_192,
+                max_output_tokens: 2_048,
+                supports_tools: false,
+                supports_vision: false,
+                supports_extended_thinking: false,
+                supports_streaming: true,
+                tokenizer: TokenizerType::SentencePiece,
+                rate_limit_rpm: None,
+                provider: ModelProvider::Ollama,
+            },
+        },
         ModelEntry {
             prefix: "qwen",
             caps: ModelCapabilities {
@@ -869,6 +1047,20 @@ fn try_prefix_match(normalized: &str) -> Option<ModelCapabilities> {
         .map(|e| e.caps.clone())
 }
 
+/// Like `try_prefix_match`, but the entry must name the whole model or be
+/// followed by a tag/variant separator, and catch-alls (`claude-`) don't count.
+fn try_family_match(normalized: &str) -> Option<ModelCapabilities> {
+    REGISTRY
+        .iter()
+        .filter(|e| !e.prefix.ends_with('-'))
+        .find(|e| {
+            normalized
+                .strip_prefix(e.prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '-', '@', '/']))
+        })
+        .map(|e| e.caps.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1017,4 +1209,30 @@ mod tests {
         let r = resolve_injection_resistance("phi-3-mini");
         assert!(r.max_recalled_memories >= 2); // at least the minimum
     }
+
+    #[test]
+    fn test_user_overrides_extend_the_registry() {
+        assert!(known_model_capabilities("acme-local-7b").is_none());
+        let overrides = HashMap::from([(
+            "ACME-Local".to_string(),
+            ModelCapabilityOverride {
+                context_window: Some(64_000),
+                supports_tools: Some(false),
+                ..Default::default()
+            },
+        )]);
+        set_capability_overrides(&overrides);
+
+        let caps = known_model_capabilities("acme-local-7b").unwrap();
+        assert!(!caps.supports_tools);
+        assert!(caps.supports_streaming);
+        assert_eq!(resolve_context_window("acme-local-7b", 8_000), 64_000);
+        // Request shaping only restricts what the override names
+        let request = request_capabilities("acme-local-7b").unwrap();
+        assert!(!request.supports_tools);
+        assert!(request.supports_vision);
+
+        set_capability_overrides(&HashMap::new());
+        assert_eq!(resolve_context_window("acme-local-7b", 8_000), 8_000);
+    }
 }
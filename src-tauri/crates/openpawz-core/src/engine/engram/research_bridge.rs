@@ -10,6 +10,7 @@
 use crate::atoms::engram_types::{EpisodicMemory, MemoryScope, MemorySource, TieredContent};
 use crate::atoms::error::EngineResult;
 use crate::engine::sessions::SessionStore;
+use crate::engine::util::safe_truncate;
 use log::info;
 use serde::{Deserialize, Serialize};
 
@@ -69,13 +70,13 @@ pub fn ingest_findings(
                 .content
                 .find(". ")
                 .map(|end| finding.content[..=end].to_string())
-                .unwrap_or_else(|| finding.content[..200].to_string() + "…")
+                .unwrap_or_else(|| safe_truncate(&finding.content, 200).to_string() + "…")
         } else {
             finding.content.clone()
         };
 
         let key_fact = if finding.content.len() > 100 {
-            finding.content[..100].to_string()
+            safe_truncate(&finding.content, 100).to_string()
         } else {
             finding.content.clone()
         };
@@ -0,0 +1,301 @@
+// Paw Engine — Rotating log file
+//
+// A persistent, size-bounded log under `{data_root}/logs/` that users can
+// attach to bug reports. It sits next to the console / log-dir logger (the
+// host wraps that logger in a `TeeLogger`), has its own level, and runs
+// every message through the secret scrubber before it hits disk.
+//
+// Rotation: when `openpawz.log` would grow past `max_file_bytes` it becomes
+// `openpawz.log.1`, older archives shift up by one, and anything beyond
+// `max_files` total is deleted.
+//
+// Stored as JSON under the `file_log_config` config key; changes apply on
+// the next launch.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::secret_scrub;
+use crate::engine::sessions::SessionStore;
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// engine_config key holding the serialized [`FileLogConfig`].
+pub const FILE_LOG_CONFIG_KEY: &str = "file_log_config";
+
+/// Name of the active log file.
+pub const LOG_FILE_NAME: &str = "openpawz.log";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileLogConfig {
+    pub enabled: bool,
+    /// "error", "warn", "info", "debug" or "trace".
+    pub level: String,
+    /// Size at which the active file is rotated.
+    pub max_file_bytes: u64,
+    /// Files kept in total, the active one included.
+    pub max_files: usize,
+}
+
+impl Default for FileLogConfig {
+    fn default() -> Self {
+        FileLogConfig {
+            enabled: true,
+            level: "info".into(),
+            max_file_bytes: 5 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+impl FileLogConfig {
+    /// The configured level, `Info` when unparseable.
+    pub fn level_filter(&self) -> LevelFilter {
+        LevelFilter::from_str(&self.level).unwrap_or(LevelFilter::Info)
+    }
+}
+
+/// `{data_root}/logs/`
+pub fn log_dir() -> PathBuf {
+    crate::engine::paths::paw_data_dir().join("logs")
+}
+
+/// `{data_root}/logs/openpawz.log`
+pub fn log_file_path() -> PathBuf {
+    log_dir().join(LOG_FILE_NAME)
+}
+
+// ── Rotating file ──────────────────────────────────────────────────────
+
+/// An append-only file that rotates at a size limit and prunes old archives.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile {
+            path: path.to_path_buf(),
+            max_bytes: max_bytes.max(1),
+            max_files: max_files.max(1),
+            file,
+            size,
+        })
+    }
+
+    /// Append `line` plus a newline, rotating first if it wouldn't fit.
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+
+    fn archive(&self, n: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        // With max_files = N we keep the active file plus .1 … .(N-1)
+        let keep = self.max_files - 1;
+        let _ = std::fs::remove_file(self.archive(keep.max(1)));
+        if keep > 0 {
+            for n in (1..keep).rev() {
+                let from = self.archive(n);
+                if from.exists() {
+                    std::fs::rename(&from, self.archive(n + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.archive(1))?;
+        } else {
+            std::fs::remove_file(&self.path)?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+// ── Logger ─────────────────────────────────────────────────────────────
+
+/// Forwards every record to `primary` and, at or above `level`, appends a
+/// scrubbed line to the rotating file.
+pub struct TeeLogger {
+    primary: Box<dyn Log>,
+    file: Mutex<RotatingFile>,
+    level: LevelFilter,
+}
+
+impl Log for TeeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level || self.primary.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.primary.log(record);
+        if record.level() > self.level {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            secret_scrub::scrub(&record.args().to_string())
+        );
+        // A failing log file must never take the app down
+        let _ = self.file.lock().write_line(&line);
+    }
+
+    fn flush(&self) {
+        self.primary.flush();
+        let _ = self.file.lock().flush();
+    }
+}
+
+/// Install the global logger: `primary` alone, or teed into the rotating
+/// file when enabled. Falls back to `primary` alone if the file can't open.
+pub fn install(
+    primary: Box<dyn Log>,
+    primary_level: LevelFilter,
+    config: &FileLogConfig,
+) -> Result<(), log::SetLoggerError> {
+    let opened = if config.enabled {
+        match RotatingFile::open(&log_file_path(), config.max_file_bytes, config.max_files) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                eprintln!("[file_log] Can't open {}: {}", log_file_path().display(), e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    match opened {
+        Some(file) => {
+            let level = config.level_filter();
+            log::set_boxed_logger(Box::new(TeeLogger {
+                primary,
+                file: Mutex::new(file),
+                level,
+            }))?;
+            log::set_max_level(primary_level.max(level));
+        }
+        None => {
+            log::set_boxed_logger(primary)?;
+            log::set_max_level(primary_level);
+        }
+    }
+    Ok(())
+}
+
+/// Load the settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> FileLogConfig {
+    store
+        .get_config(FILE_LOG_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, config: &FileLogConfig) -> EngineResult<()> {
+    store.set_config(FILE_LOG_CONFIG_KEY, &serde_json::to_string(config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "paw-file-log-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rotates_at_size_and_prunes_old_files() {
+        let dir = scratch_dir();
+        let path = dir.join(LOG_FILE_NAME);
+        let mut file = RotatingFile::open(&path, 100, 3).unwrap();
+
+        // 50-byte lines (49 + newline): two fit per file
+        let line = |n: usize| format!("{:0>49}", n);
+        for n in 0..2 {
+            file.write_line(&line(n)).unwrap();
+        }
+        assert!(!file.archive(1).exists());
+
+        file.write_line(&line(2)).unwrap();
+        assert!(file.archive(1).exists());
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 50);
+
+        for n in 3..8 {
+            file.write_line(&line(n)).unwrap();
+        }
+        // Active + .1 + .2 only — .3 was pruned
+        assert!(file.archive(2).exists());
+        assert!(!file.archive(3).exists());
+        let active = std::fs::read_to_string(&path).unwrap();
+        let newest_archive = std::fs::read_to_string(file.archive(1)).unwrap();
+        let oldest_archive = std::fs::read_to_string(file.archive(2)).unwrap();
+        assert_eq!(active, format!("{}\n", line(6)) + &format!("{}\n", line(7)));
+        assert!(newest_archive.contains(&line(4)) && newest_archive.contains(&line(5)));
+        assert!(oldest_archive.contains(&line(2)) && oldest_archive.contains(&line(3)));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn single_file_limit_truncates_in_place() {
+        let dir = scratch_dir();
+        let path = dir.join(LOG_FILE_NAME);
+        let mut file = RotatingFile::open(&path, 10, 1).unwrap();
+        file.write_line("first-line").unwrap();
+        file.write_line("second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second\n");
+        assert!(!file.archive(1).exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn level_parsing_falls_back_to_info() {
+        let mut config = FileLogConfig::default();
+        assert_eq!(config.level_filter(), LevelFilter::Info);
+        config.level = "DEBUG".into();
+        assert_eq!(config.level_filter(), LevelFilter::Debug);
+        config.level = "chatty".into();
+        assert_eq!(config.level_filter(), LevelFilter::Info);
+    }
+}
@@ -0,0 +1,896 @@
+// Paw Agent Engine — Headless single-prompt runner
+//
+// Runs one prompt against an agent without the Tauri UI: loads the engine
+// config from the shared database, picks a provider, and drives the same
+// agent loop as the desktop app (`agent_loop::run_turn`) until the model
+// answers.
+//
+// Only tools that need nothing but the SessionStore are available here
+// (memory_store / memory_search), and tools that would need an approval
+// prompt are denied. The daily budget, max_tool_rounds and
+// max_tool_iterations from the engine config are honoured, and the turn is
+// recorded in telemetry_metrics so headless spend counts toward the same
+// budget.
+
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::agent_loop::{
+    run_turn, ApprovalRequest, RoundUsage, TurnHost, TurnParams, TurnStatus,
+};
+use crate::engine::cancel::CancelToken;
+use crate::engine::memory::recall::{relevant_memories_section, RecallSettings};
+use crate::engine::memory::EmbeddingClient;
+use crate::engine::pricing::estimate_cost_usd;
+use crate::engine::prompt_template::templated_base_prompt;
+use crate::engine::providers::AnyProvider;
+use crate::engine::sessions::SessionStore;
+use crate::engine::types::*;
+use async_trait::async_trait;
+use log::{info, warn};
+use parking_lot::Mutex;
+
+/// Options for a headless run. Defaults come from the engine config.
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessOptions {
+    /// Model override (falls back to the engine's default model).
+    pub model: Option<String>,
+    /// Tool-round cap override (falls back to `max_tool_rounds`).
+    pub max_rounds: Option<u32>,
+    /// Save the conversation as a session visible in the app.
+    pub persist_session: bool,
+    /// Abort the run (LLM stream and tool calls) when cancelled.
+    pub cancel: Option<CancelToken>,
+}
+
+/// Outcome of a headless run.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HeadlessRun {
+    pub session_id: String,
+    pub model: String,
+    pub output: String,
+    pub rounds: u32,
+    pub tool_calls: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Run `prompt` against `agent_id` and return the final answer.
+pub async fn run_once(store: &SessionStore, agent_id: &str, prompt: &str) -> EngineResult<String> {
+    run_once_with(store, agent_id, prompt, &HeadlessOptions::default())
+        .await
+        .map(|r| r.output)
+}
+
+/// Like [`run_once`] but with options and full run statistics.
+pub async fn run_once_with(
+    store: &SessionStore,
+    agent_id: &str,
+    prompt: &str,
+    opts: &HeadlessOptions,
+) -> EngineResult<HeadlessRun> {
+    let config = load_engine_config(store);
+    let agent_model = store
+        .get_agent_definition(agent_id)
+        .ok()
+        .flatten()
+        .and_then(|a| a.model);
+    let model = opts
+        .model
+        .clone()
+        .or(agent_model)
+        .or_else(|| config.default_model.clone())
+        .ok_or_else(|| EngineError::Config("No model configured — run `openpawz setup`".into()))?;
+    let provider_config = pick_provider(&config, &model).ok_or_else(|| {
+        EngineError::Config(format!("No provider configured for model '{}'", model))
+    })?;
+    let provider = AnyProvider::from_config(&provider_config);
+    run_with_provider(store, &provider, &config, agent_id, prompt, &model, opts).await
+}
+
+/// Run the shared agent loop with the provider supplied by the caller (tests
+/// use a mock).
+pub async fn run_with_provider(
+    store: &SessionStore,
+    provider: &AnyProvider,
+    config: &EngineConfig,
+    agent_id: &str,
+    prompt: &str,
+    model: &str,
+    opts: &HeadlessOptions,
+) -> EngineResult<HeadlessRun> {
+    let started = std::time::Instant::now();
+    let session_id = format!("headless-{}", uuid::Uuid::new_v4());
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let max_rounds = opts.max_rounds.unwrap_or(config.max_tool_rounds).max(1);
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+    let system_prompt = build_system_prompt(store, config, agent_id, prompt).await;
+    if opts.persist_session {
+        store.create_session(&session_id, model, Some(&system_prompt), Some(agent_id))?;
+    }
+
+    let mut messages = vec![
+        text_message(Role::System, &system_prompt),
+        text_message(Role::User, prompt),
+    ];
+    if opts.persist_session {
+        persist(store, &session_id, &messages[1]);
+    }
+
+    let host = HeadlessHost {
+        store,
+        config,
+        agent_id,
+        model,
+        // Budgets count spend since the last reset in the user's timezone
+        window: config.budget_window().key(chrono::Utc::now()),
+        tally: Mutex::new(Tally::default()),
+    };
+    let params = TurnParams {
+        model,
+        session_id: &session_id,
+        run_id: &run_id,
+        agent_id,
+        max_rounds,
+        temperature: None,
+        thinking_level: None,
+        auto_approve_all: false,
+        user_approved_tools: &[],
+        max_tool_iterations: config.max_tool_iterations,
+        show_reasoning: false,
+        context_window_tokens: config.context_window_tokens,
+    };
+    let mut tools = tool_definitions();
+    let outcome = run_turn(
+        &host,
+        provider,
+        &mut messages,
+        &mut tools,
+        &params,
+        opts.cancel.as_ref(),
+        None,
+    )
+    .await;
+    // Only a final answer is a successful headless run
+    let result = outcome.and_then(|outcome| match outcome.status {
+        TurnStatus::Completed => Ok(outcome.text),
+        TurnStatus::Cancelled => Err(EngineError::Other("Run cancelled".into())),
+        TurnStatus::MaxRounds => Err(EngineError::Other(format!(
+            "Stopped after {} tool rounds without a final answer",
+            max_rounds
+        ))),
+        TurnStatus::Yielded | TurnStatus::ToolLoop | TurnStatus::ToolLimit => {
+            Err(EngineError::Other(outcome.text))
+        }
+    });
+
+    let tally = host.tally.into_inner();
+    let run = HeadlessRun {
+        session_id: session_id.clone(),
+        model: model.to_string(),
+        output: result.as_ref().cloned().unwrap_or_default(),
+        rounds: tally.rounds,
+        tool_calls: tally.tool_calls,
+        input_tokens: tally.input_tokens,
+        output_tokens: tally.output_tokens,
+        cost_usd: tally.cost_usd,
+    };
+    if opts.persist_session && result.is_ok() {
+        persist(
+            store,
+            &session_id,
+            &text_message(Role::Assistant, &run.output),
+        );
+    }
+
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    if let Err(e) = store.record_metric(
+        &today,
+        &session_id,
+        model,
+        run.input_tokens,
+        run.output_tokens,
+        run.cost_usd,
+        run.tool_calls,
+        0,
+        elapsed_ms,
+        elapsed_ms,
+        run.rounds,
+    ) {
+        warn!("[headless] Failed to record metrics: {}", e);
+    }
+    info!(
+        "[headless] agent={} rounds={} tools={} cost=${:.4}",
+        agent_id, run.rounds, run.tool_calls, run.cost_usd
+    );
+
+    result.map(|_| run)
+}
+
+// ── Host ───────────────────────────────────────────────────────────────
+
+/// Spend and activity of one headless run.
+#[derive(Default)]
+struct Tally {
+    rounds: u32,
+    tool_calls: u32,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost_usd: f64,
+}
+
+/// Drives the shared loop against the store alone: no UI to stream to, no
+/// one to approve gated tools, and only the memory tools.
+struct HeadlessHost<'a> {
+    store: &'a SessionStore,
+    config: &'a EngineConfig,
+    agent_id: &'a str,
+    model: &'a str,
+    /// Budget window the run's spend is recorded under.
+    window: String,
+    tally: Mutex<Tally>,
+}
+
+#[async_trait]
+impl TurnHost for HeadlessHost<'_> {
+    fn store(&self) -> Option<&SessionStore> {
+        Some(self.store)
+    }
+
+    fn emit(&self, _event: EngineEvent) {}
+
+    fn stream_text(&self, _text: &str) {}
+
+    fn budget_exceeded(&self) -> Option<String> {
+        let budget = self.config.daily_budget_usd;
+        if budget > 0.0 {
+            let spent: f64 = self
+                .store
+                .list_daily_spend(&self.window)
+                .map(|rows| rows.iter().map(|r| r.cost_usd).sum())
+                .unwrap_or(0.0);
+            if spent >= budget {
+                return Some(format!(
+                    "Daily budget of ${:.2} reached (spent ${:.2}) — headless run stopped",
+                    budget, spent
+                ));
+            }
+        }
+        let agent_budget = self.config.agent_budget_usd(self.agent_id);
+        if agent_budget > 0.0 {
+            let spent = self
+                .store
+                .agent_daily_spend_usd(&self.window, self.agent_id)
+                .unwrap_or(0.0);
+            if spent >= agent_budget {
+                return Some(format!(
+                    "Daily budget of ${:.2} for agent '{}' reached (spent ${:.2}) — headless run stopped",
+                    agent_budget, self.agent_id, spent
+                ));
+            }
+        }
+        None
+    }
+
+    fn record_usage(&self, _round: u32, usage: &RoundUsage) {
+        let cost = estimate_cost_usd(
+            self.model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_creation_tokens,
+        );
+        {
+            let mut tally = self.tally.lock();
+            tally.rounds += 1;
+            tally.input_tokens += usage.input_tokens;
+            tally.output_tokens += usage.output_tokens;
+            tally.cost_usd += cost;
+        }
+        // Persisted per round so headless spend counts toward the same budget
+        if let Err(e) = self.store.add_daily_spend(
+            &self.window,
+            self.agent_id,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_creation_tokens,
+            cost,
+        ) {
+            warn!("[headless] Failed to record daily spend: {}", e);
+        }
+    }
+
+    /// Nobody is there to answer a prompt, so gated tools are denied.
+    async fn request_approval(
+        &self,
+        _request: ApprovalRequest<'_>,
+        _cancel: &CancelToken,
+    ) -> (bool, &'static str) {
+        (false, "headless")
+    }
+
+    async fn execute_tool(&self, call: &ToolCall) -> ToolResult {
+        let (output, success) = match execute_tool(self.store, self.agent_id, call) {
+            Ok(out) => (out, true),
+            Err(e) => (format!("Error: {}", e), false),
+        };
+        ToolResult {
+            tool_call_id: call.id.clone(),
+            output,
+            success,
+        }
+    }
+
+    fn tool_finished(&self, _call: &ToolCall, _result: &ToolResult, _duration_ms: u64) {
+        self.tally.lock().tool_calls += 1;
+    }
+}
+
+// ── Setup helpers ──────────────────────────────────────────────────────
+
+fn load_engine_config(store: &SessionStore) -> EngineConfig {
+    let config: EngineConfig = match store.get_config("engine_config") {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => EngineConfig::default(),
+    };
+    crate::engine::engram::set_capability_overrides(&config.model_capabilities);
+    crate::engine::i18n::set_locale(config.locale.as_deref());
+    config
+}
+
+/// Provider whose default model matches, else the default provider, else the first.
+fn pick_provider(config: &EngineConfig, model: &str) -> Option<ProviderConfig> {
+    config
+        .providers
+        .iter()
+        .find(|p| p.default_model.as_deref() == Some(model))
+        .or_else(|| {
+            config
+                .default_provider
+                .as_deref()
+                .and_then(|id| config.providers.iter().find(|p| p.id == id))
+        })
+        .or_else(|| config.providers.first())
+        .cloned()
+}
+
+/// The saved memory config, or None when memory was never set up (no
+/// embedding backend to recall with).
+fn load_memory_config(store: &SessionStore) -> Option<MemoryConfig> {
+    let json = store.get_config("memory_config").ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+async fn build_system_prompt(
+    store: &SessionStore,
+    config: &EngineConfig,
+    agent_id: &str,
+    prompt: &str,
+) -> String {
+    let mut parts = Vec::new();
+    // The agent's persona replaces the engine-wide default prompt
+    let persona = store
+        .get_agent_definition(agent_id)
+        .ok()
+        .flatten()
+        .and_then(|a| a.persona);
+    let base = persona.or_else(|| config.default_system_prompt.clone());
+    // Headless runs have no daily notes or skill section for the template to place
+    if let Some(base) = templated_base_prompt(config, base, agent_id, &mut None, &mut String::new())
+    {
+        parts.push(base);
+    }
+    if let Ok(Some(ctx)) = store.compose_agent_context(agent_id) {
+        parts.push(ctx);
+    }
+    if let Some(memory_config) =
+        load_memory_config(store).filter(|m| m.auto_recall && !m.embedding_model.is_empty())
+    {
+        let client = EmbeddingClient::new(&memory_config);
+        let settings = RecallSettings::from_config(&memory_config);
+        if let Some(section) =
+            relevant_memories_section(store, prompt, Some(&client), Some(agent_id), &settings).await
+        {
+            parts.push(section);
+        }
+    }
+    parts.join("\n\n")
+}
+
+fn text_message(role: Role, text: &str) -> Message {
+    Message {
+        role,
+        content: MessageContent::Text(text.to_string()),
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+    }
+}
+
+fn persist(store: &SessionStore, session_id: &str, msg: &Message) {
+    let role = match msg.role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        _ => return,
+    };
+    let content = match &msg.content {
+        MessageContent::Text(t) => t.clone(),
+        MessageContent::Blocks(_) => return,
+    };
+    let stored = StoredMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: session_id.to_string(),
+        role: role.into(),
+        content,
+        tool_calls_json: None,
+        tool_call_id: None,
+        name: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
+    };
+    if let Err(e) = store.add_message(&stored) {
+        warn!("[headless] Failed to persist message: {}", e);
+    }
+}
+
+// ── Tools ──────────────────────────────────────────────────────────────
+
+fn tool_definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "memory_store".into(),
+                description: "Store a fact in long-term memory.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "content": { "type": "string", "description": "The fact to remember" },
+                        "category": { "type": "string", "description": "Category (default: general)" }
+                    },
+                    "required": ["content"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "memory_search".into(),
+                description: "Search long-term memory for facts relevant to a query.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string", "description": "Search query" },
+                        "limit": { "type": "integer", "description": "Max results (default: 5)" }
+                    },
+                    "required": ["query"]
+                }),
+            },
+        },
+    ]
+}
+
+fn execute_tool(store: &SessionStore, agent_id: &str, call: &ToolCall) -> EngineResult<String> {
+    let args: serde_json::Value = if call.function.arguments.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(&call.function.arguments)?
+    };
+    match call.function.name.as_str() {
+        "memory_store" => {
+            let content = args["content"]
+                .as_str()
+                .filter(|s| !s.trim().is_empty())
+                .ok_or("memory_store requires 'content'")?;
+            let category = args["category"].as_str().unwrap_or("general");
+            let id = uuid::Uuid::new_v4().to_string();
+            store.store_memory(&id, content, category, 5, None, Some(agent_id))?;
+            Ok(format!("Stored memory {}", id))
+        }
+        "memory_search" => {
+            let query = args["query"]
+                .as_str()
+                .ok_or("memory_search requires 'query'")?;
+            let limit = args["limit"].as_u64().unwrap_or(5).clamp(1, 20) as usize;
+            let found = store.search_memories_bm25(query, limit, Some(agent_id))?;
+            if found.is_empty() {
+                return Ok("No matching memories.".into());
+            }
+            Ok(found
+                .iter()
+                .map(|m| format!("- [{}] {}", m.category, m.content))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        other => Err(EngineError::Other(format!(
+            "Tool '{}' is not available in headless mode",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atoms::traits::{AiProvider, ProviderError};
+    use crate::engine::sessions::schema::run_migrations;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use rusqlite::Connection;
+    use std::sync::Arc;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    /// Replays a fixed list of responses, one per round.
+    struct MockProvider {
+        rounds: Mutex<Vec<Vec<StreamChunk>>>,
+        /// Fired after this many calls, simulating a user pressing Stop.
+        cancel_after: Option<(usize, CancelToken)>,
+        calls: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl AiProvider for MockProvider {
+        fn name(&self) -> &str {
+            "mock"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Custom
+        }
+        async fn chat_stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            let mut calls = self.calls.lock();
+            *calls += 1;
+            if let Some((n, token)) = &self.cancel_after {
+                if *calls >= *n {
+                    token.cancel();
+                }
+            }
+            let mut rounds = self.rounds.lock();
+            Ok(if rounds.is_empty() {
+                vec![]
+            } else {
+                rounds.remove(0)
+            })
+        }
+    }
+
+    fn text_chunk(text: &str) -> StreamChunk {
+        StreamChunk {
+            delta_text: Some(text.into()),
+            tool_calls: vec![],
+            finish_reason: Some("stop".into()),
+            usage: Some(TokenUsage {
+                input_tokens: 10,
+                output_tokens: 2,
+                total_tokens: 12,
+                ..Default::default()
+            }),
+            model: None,
+            thought_parts: vec![],
+            thinking_text: None,
+        }
+    }
+
+    fn tool_chunk(name: &str, args: &str) -> StreamChunk {
+        StreamChunk {
+            delta_text: None,
+            tool_calls: vec![ToolCallDelta {
+                index: 0,
+                id: Some("call_test_0001".into()),
+                function_name: Some(name.into()),
+                arguments_delta: Some(args.into()),
+                thought_signature: None,
+            }],
+            finish_reason: Some("tool_calls".into()),
+            usage: None,
+            model: None,
+            thought_parts: vec![],
+            thinking_text: None,
+        }
+    }
+
+    fn mock(rounds: Vec<Vec<StreamChunk>>) -> AnyProvider {
+        AnyProvider::from_provider(Box::new(MockProvider {
+            rounds: Mutex::new(rounds),
+            cancel_after: None,
+            calls: Mutex::new(0),
+        }))
+    }
+
+    fn config() -> EngineConfig {
+        EngineConfig {
+            daily_budget_usd: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn trivial_prompt_returns_completion() {
+        let store = test_store();
+        let provider = mock(vec![vec![text_chunk("2 + 2 = 4")]]);
+        let run = run_with_provider(
+            &store,
+            &provider,
+            &config(),
+            "default",
+            "What is 2 + 2?",
+            "mock-model",
+            &HeadlessOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(run.output, "2 + 2 = 4");
+        assert_eq!(run.rounds, 1);
+        assert_eq!(run.tool_calls, 0);
+    }
+
+    #[tokio::test]
+    async fn tool_round_then_answer() {
+        let store = test_store();
+        let provider = mock(vec![
+            vec![tool_chunk(
+                "memory_store",
+                r#"{"content":"User likes green tea"}"#,
+            )],
+            vec![text_chunk("Noted.")],
+        ]);
+        let opts = HeadlessOptions {
+            persist_session: true,
+            ..Default::default()
+        };
+        let run = run_with_provider(
+            &store,
+            &provider,
+            &config(),
+            "agent-x",
+            "Remember I like green tea",
+            "mock-model",
+            &opts,
+        )
+        .await
+        .unwrap();
+        assert_eq!(run.output, "Noted.");
+        assert_eq!(run.rounds, 2);
+        assert_eq!(run.tool_calls, 1);
+        let found = store
+            .search_memories_bm25("green tea", 5, Some("agent-x"))
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(store.get_messages(&run.session_id, 10).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stops_at_round_cap() {
+        let store = test_store();
+        let provider = mock(vec![
+            vec![tool_chunk("memory_search", r#"{"query":"x"}"#)],
+            vec![tool_chunk("memory_search", r#"{"query":"x"}"#)],
+        ]);
+        let opts = HeadlessOptions {
+            max_rounds: Some(1),
+            ..Default::default()
+        };
+        let err = run_with_provider(&store, &provider, &config(), "a", "loop", "m", &opts)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("1 tool rounds"));
+    }
+
+    #[tokio::test]
+    async fn cancel_mid_run_stops_tool_execution() {
+        let store = test_store();
+        let token = CancelToken::new();
+        // Round 1 asks for a tool; cancellation lands while it is streaming.
+        let provider = AnyProvider::from_provider(Box::new(MockProvider {
+            rounds: Mutex::new(vec![
+                vec![tool_chunk("memory_store", r#"{"content":"never stored"}"#)],
+                vec![text_chunk("unreachable")],
+            ]),
+            cancel_after: Some((1, token.clone())),
+            calls: Mutex::new(0),
+        }));
+        let opts = HeadlessOptions {
+            cancel: Some(token),
+            ..Default::default()
+        };
+        let err = run_with_provider(&store, &provider, &config(), "a", "go", "m", &opts)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+        assert!(store
+            .search_memories_bm25("never stored", 5, Some("a"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn stops_at_tool_iteration_limit() {
+        let store = test_store();
+        // The model never concludes: every round asks for another tool, with
+        // different arguments so the repetition detector stays out of it.
+        let provider = mock(
+            (0..10)
+                .map(|i| {
+                    vec![tool_chunk(
+                        "memory_search",
+                        &format!(r#"{{"query":"x{}"}}"#, i),
+                    )]
+                })
+                .collect(),
+        );
+        let config = EngineConfig {
+            max_tool_iterations: 3,
+            ..config()
+        };
+        let opts = HeadlessOptions {
+            max_rounds: Some(50),
+            ..Default::default()
+        };
+        let err = run_with_provider(&store, &provider, &config, "a", "loop", "m", &opts)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("tool-call limit"));
+        // 3 executed rounds, 1 nudged round, 1 stopped round
+        let metrics = store.get_daily_metrics(&chrono::Utc::now().format("%Y-%m-%d").to_string());
+        assert_eq!(metrics.unwrap().tool_calls, 3);
+    }
+
+    #[tokio::test]
+    async fn per_agent_budget_blocks_only_that_agent() {
+        let store = test_store();
+        let config = EngineConfig {
+            daily_budget_usd: 100.0,
+            agent_budgets_usd: [("trader".to_string(), 2.0)].into(),
+            ..config()
+        };
+        let window = config.budget_window().key(chrono::Utc::now());
+        store
+            .add_daily_spend(&window, "trader", 1000, 500, 0, 0, 3.0)
+            .unwrap();
+        let opts = HeadlessOptions::default();
+
+        let provider = mock(vec![vec![text_chunk("should not run")]]);
+        let err = run_with_provider(&store, &provider, &config, "trader", "buy", "m", &opts)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("agent 'trader'"));
+
+        // Other agents are under their (absent) cap and the global budget
+        let provider = mock(vec![vec![text_chunk("hello")]]);
+        let run = run_with_provider(&store, &provider, &config, "chat", "hi", "m", &opts)
+            .await
+            .unwrap();
+        assert_eq!(run.output, "hello");
+        let spend = store.list_daily_spend(&window).unwrap();
+        assert!(spend.iter().any(|s| s.agent_id == "chat"));
+    }
+
+    #[tokio::test]
+    async fn agent_persona_replaces_default_prompt() {
+        let store = test_store();
+        store
+            .upsert_agent_definition(&AgentDefinition {
+                id: "chef".into(),
+                name: "Chef".into(),
+                persona: Some("You are a pastry chef.".into()),
+                model: Some("chef-model".into()),
+                skills: vec![],
+                autonomy: Default::default(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+        let config = EngineConfig {
+            default_system_prompt: Some("You are a helpful assistant.".into()),
+            ..config()
+        };
+
+        let prompt = build_system_prompt(&store, &config, "chef", "croissants?").await;
+        assert!(prompt.starts_with("You are a pastry chef."));
+        assert!(!prompt.contains("helpful assistant"));
+
+        // Agents without a persona keep the engine default
+        let prompt = build_system_prompt(&store, &config, "default", "hi").await;
+        assert!(prompt.starts_with("You are a helpful assistant."));
+    }
+
+    #[tokio::test]
+    async fn system_prompt_template_is_rendered() {
+        let store = test_store();
+        let config = EngineConfig {
+            default_system_prompt: Some("You are a helpful assistant.".into()),
+            system_prompt_template: Some("I am {agent_name}.\n{base_prompt}\n{skills}".into()),
+            ..config()
+        };
+
+        let prompt = build_system_prompt(&store, &config, "agent-night-owl-7", "hi").await;
+        assert!(prompt.starts_with("I am Night Owl.\nYou are a helpful assistant."));
+        assert!(!prompt.contains("{skills}"));
+    }
+
+    /// Model, tool names and last message role of each round.
+    type Rounds = Arc<Mutex<Vec<(String, Vec<String>, Role)>>>;
+
+    /// A new backend only has to implement the trait: it gets the tool list
+    /// and model each round and sees tool results fed back by the loop.
+    struct RecordingProvider {
+        seen: Rounds,
+    }
+
+    #[async_trait]
+    impl AiProvider for RecordingProvider {
+        fn name(&self) -> &str {
+            "recording"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Mistral
+        }
+        async fn chat_stream(
+            &self,
+            messages: &[Message],
+            tools: &[ToolDefinition],
+            model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            let mut seen = self.seen.lock();
+            seen.push((
+                model.to_string(),
+                tools.iter().map(|t| t.function.name.clone()).collect(),
+                messages
+                    .last()
+                    .map(|m| m.role.clone())
+                    .unwrap_or(Role::User),
+            ));
+            Ok(if seen.len() == 1 {
+                vec![tool_chunk("memory_search", r#"{"query":"tea"}"#)]
+            } else {
+                vec![text_chunk("No tea notes yet.")]
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_backend_drives_the_loop() {
+        let store = test_store();
+        let seen = Arc::new(Mutex::new(vec![]));
+        let provider =
+            AnyProvider::from_provider(Box::new(RecordingProvider { seen: seen.clone() }));
+
+        let run = run_with_provider(
+            &store,
+            &provider,
+            &config(),
+            "default",
+            "Any tea notes?",
+            "mistral-small-latest",
+            &HeadlessOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(run.output, "No tea notes yet.");
+        assert_eq!(run.tool_calls, 1);
+
+        let seen = seen.lock();
+        assert_eq!(seen.len(), 2);
+        for (model, tools, _) in seen.iter() {
+            assert_eq!(model, "mistral-small-latest");
+            assert!(tools.iter().any(|t| t == "memory_search"));
+        }
+        assert_eq!(seen[0].2, Role::User);
+        assert_eq!(seen[1].2, Role::Tool);
+    }
+}
@@ -7,6 +7,8 @@
 //   • Retry on 429 (rate limit), 500, 502, 503, 504, 529
 //   • Respects `Retry-After` header
 //   • Circuit breaker: 5 consecutive failures → fail fast for 60s
+//   • Sustained-overload detection: 3× 529 within 60s → stop backing off
+//     so the caller can rotate to a fallback provider
 //   • Bridge reconnect helper with escalating backoff + cap
 //   • Certificate-pinned reqwest::Client factory for known AI providers
 //   • SHA-256 request signing for outbound API call tamper detection
@@ -15,9 +17,10 @@
 use log::{info, warn};
 use parking_lot::Mutex;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 // ── Constants ──────────────────────────────────────────────────────────────
 
@@ -184,6 +187,58 @@ impl CircuitBreaker {
     }
 }
 
+// ── Sustained Overload (529) Tracking ─────────────────────────────────────
+//
+// An isolated 529 is handled by normal backoff. When a provider returns 529
+// repeatedly within a short window, waiting rarely helps — the provider
+// should give up immediately so the request path can rotate to a fallback
+// provider (see `providers::fallback`).
+
+/// HTTP status Anthropic uses for "overloaded".
+pub const OVERLOADED_STATUS: u16 = 529;
+
+/// 529s within the window that count as sustained overload.
+pub const OVERLOAD_ROTATE_THRESHOLD: usize = 3;
+
+/// Sliding window for counting 529s.
+const OVERLOAD_WINDOW: Duration = Duration::from_secs(60);
+
+/// Recent 529 timestamps per provider key.
+#[derive(Default)]
+pub struct OverloadTracker {
+    hits: Mutex<HashMap<String, Vec<Instant>>>,
+}
+
+impl OverloadTracker {
+    /// Record a 529 for `provider`. Returns true once the provider is in
+    /// sustained overload (threshold reached within the window).
+    pub fn record(&self, provider: &str) -> bool {
+        let now = Instant::now();
+        let mut hits = self.hits.lock();
+        let entry = hits.entry(provider.to_string()).or_default();
+        entry.retain(|t| now.duration_since(*t) < OVERLOAD_WINDOW);
+        entry.push(now);
+        entry.len() >= OVERLOAD_ROTATE_THRESHOLD
+    }
+
+    /// Forget recorded 529s after a successful response.
+    pub fn clear(&self, provider: &str) {
+        self.hits.lock().remove(provider);
+    }
+}
+
+static OVERLOAD_TRACKER: LazyLock<OverloadTracker> = LazyLock::new(OverloadTracker::default);
+
+/// Record a 529 from `provider`; true means "stop retrying, rotate instead".
+pub fn record_overload(provider: &str) -> bool {
+    OVERLOAD_TRACKER.record(provider)
+}
+
+/// Reset overload tracking for `provider` after a success.
+pub fn clear_overload(provider: &str) {
+    OVERLOAD_TRACKER.clear(provider)
+}
+
 // ── Certificate-Pinned Client Factory ──────────────────────────────────────
 //
 // Builds a `reqwest::Client` that uses a custom `rustls::ClientConfig` with
@@ -0,0 +1,191 @@
+// Paw Engine — Localized user-facing strings
+//
+// Messages the engine shows to people (errors, status, channel system
+// messages) are looked up by key in a message catalog instead of being
+// hard-coded in English. The locale comes from `EngineConfig::locale`;
+// lookups fall back from "pt-BR" to "pt" to English, and to the key itself
+// if even English is missing.
+//
+// The built-in catalog (locales.json) is embedded at compile time. More
+// languages plug in without a rebuild: `load_catalog_dir` reads
+// `<locale>.json` files (flat key → template objects) and merges them over
+// the built-in strings.
+//
+// Model-facing text — tool descriptions, system prompts, tool results — stays
+// in English and never goes through here.
+
+use log::{info, warn};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+const BUILTIN_JSON: &str = include_str!("locales.json");
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: LazyLock<RwLock<HashMap<String, Catalog>>> = LazyLock::new(|| {
+    let builtin: HashMap<String, Catalog> =
+        serde_json::from_str(BUILTIN_JSON).expect("embedded locales.json is valid");
+    RwLock::new(builtin)
+});
+
+static LOCALE: RwLock<String> = RwLock::new(String::new());
+
+/// "pt_BR" → "pt-br".
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Set the active locale (call whenever EngineConfig loads or changes).
+/// `None` or an empty string means English.
+pub fn set_locale(locale: Option<&str>) {
+    *LOCALE.write() = locale.map(normalize).unwrap_or_default();
+}
+
+/// The active locale, normalized.
+pub fn locale() -> String {
+    let locale = LOCALE.read();
+    if locale.is_empty() {
+        DEFAULT_LOCALE.into()
+    } else {
+        locale.clone()
+    }
+}
+
+/// Locales with at least one string in the catalog, sorted.
+pub fn available_locales() -> Vec<String> {
+    let mut locales: Vec<String> = CATALOGS.read().keys().cloned().collect();
+    locales.sort();
+    locales
+}
+
+/// Merge `strings` into the catalog for `locale`, replacing existing keys.
+pub fn register_catalog(locale: &str, strings: Catalog) {
+    CATALOGS
+        .write()
+        .entry(normalize(locale))
+        .or_default()
+        .extend(strings);
+}
+
+/// Register every `<locale>.json` in `dir`. A missing directory is fine;
+/// unreadable files are skipped with a warning. Returns how many loaded.
+pub fn load_catalog_dir(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut loaded = 0;
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| serde_json::from_str::<Catalog>(&text).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(strings) => {
+                register_catalog(locale, strings);
+                loaded += 1;
+            }
+            Err(e) => warn!("[i18n] Skipping {}: {}", path.display(), e),
+        }
+    }
+    if loaded > 0 {
+        info!("[i18n] Loaded {} catalog(s) from {}", loaded, dir.display());
+    }
+    loaded
+}
+
+/// Look `key` up for `locale` and fill in `{name}` placeholders.
+pub fn translate(locale: &str, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let locale = normalize(locale);
+    let language = locale.split('-').next().unwrap_or_default();
+    let template = {
+        let catalogs = CATALOGS.read();
+        [locale.as_str(), language, DEFAULT_LOCALE]
+            .iter()
+            .find_map(|l| catalogs.get(*l)?.get(key).cloned())
+    };
+    let Some(mut text) = template else {
+        warn!("[i18n] Missing message key: {}", key);
+        return key.to_string();
+    };
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Look `key` up in the active locale.
+pub fn t(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(&locale(), key, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_string_is_localized_and_falls_back_to_english() {
+        let title = "Paw Chat";
+        assert_eq!(
+            translate("es", "webchat.connected", &[("title", &title)]),
+            "Conectado a Paw Chat. ¡Envía un mensaje para empezar a chatear!"
+        );
+        // Region falls back to the language
+        assert_eq!(
+            translate("de_AT", "webchat.connected", &[("title", &title)]),
+            "Verbunden mit Paw Chat. Schreib eine Nachricht, um loszulegen!"
+        );
+
+        // A language without the key (or at all) gets English
+        register_catalog(
+            "xx-test",
+            HashMap::from([("webchat.error".into(), "Fehla: {error}".into())]),
+        );
+        assert_eq!(
+            translate("xx-test", "webchat.connected", &[("title", &title)]),
+            "Connected to Paw Chat. Send a message to start chatting!"
+        );
+        assert_eq!(
+            translate("xx-test", "webchat.error", &[("error", &"boom")]),
+            "Fehla: boom"
+        );
+        assert_eq!(translate("es", "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn every_locale_has_the_same_placeholders_as_english() {
+        let builtin: HashMap<String, Catalog> = serde_json::from_str(BUILTIN_JSON).unwrap();
+        let placeholders = |s: &str| {
+            let mut names: Vec<String> = s
+                .split('{')
+                .skip(1)
+                .filter_map(|p| p.split_once('}').map(|(n, _)| n.to_string()))
+                .collect();
+            names.sort();
+            names
+        };
+        for (locale, strings) in &builtin {
+            for (key, text) in strings {
+                let english = builtin[DEFAULT_LOCALE]
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{}: {} has no English string", locale, key));
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english),
+                    "{} {}",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+}
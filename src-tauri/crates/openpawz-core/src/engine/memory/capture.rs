@@ -0,0 +1,237 @@
+// Paw Agent Engine — Auto-Capture Gating
+//
+// Per-agent policy deciding which extracted facts are actually stored.
+// The global `MemoryConfig::auto_capture` flag stays the master switch;
+// these policies narrow it down per agent:
+//   - enabled         — turn capture off for one agent only
+//   - categories      — allowlist of fact categories (empty = all)
+//   - min_importance  — drop low-value categories (see `fact_importance`)
+//
+// A short cooldown keyed on (agent, user message) prevents the same message
+// from being captured again when a turn is retried or regenerated.
+//
+// Policies live in the engine_config table under `auto_capture_policies`
+// as a JSON map of agent_id → policy.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+const CONFIG_KEY: &str = "auto_capture_policies";
+
+/// How long a captured (agent, message) pair is ignored for re-capture.
+pub const CAPTURE_COOLDOWN: Duration = Duration::from_secs(600);
+
+// ── Policy ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoCapturePolicy {
+    /// Whether auto-capture runs for this agent at all.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Fact categories to keep (e.g. "preference", "instruction").
+    /// Empty = capture every category.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Minimum importance (0–10) a fact's category must carry to be stored.
+    #[serde(default)]
+    pub min_importance: u8,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for AutoCapturePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            categories: vec![],
+            min_importance: 0,
+        }
+    }
+}
+
+impl AutoCapturePolicy {
+    /// Whether a fact of this category passes the category + importance filters.
+    pub fn allows(&self, category: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if !self.categories.is_empty() && !self.categories.iter().any(|c| c == category) {
+            return false;
+        }
+        fact_importance(category) >= self.min_importance
+    }
+
+    /// Keep only the `(content, category)` facts this policy allows.
+    pub fn select(&self, facts: Vec<(String, String)>) -> Vec<(String, String)> {
+        facts
+            .into_iter()
+            .filter(|(_, category)| self.allows(category))
+            .collect()
+    }
+}
+
+/// Importance (0–10) assigned to an auto-captured fact by category.
+/// Explicit user instructions and preferences outrank incidental context.
+pub fn fact_importance(category: &str) -> u8 {
+    match category {
+        "instruction" => 8,
+        "preference" => 7,
+        "decision" => 6,
+        "skill" => 5,
+        "finding" => 5,
+        "context" => 4,
+        _ => 3,
+    }
+}
+
+// ── Persistence ────────────────────────────────────────────────────────
+
+/// All stored per-agent policies.
+pub fn load_policies(store: &SessionStore) -> HashMap<String, AutoCapturePolicy> {
+    match store.get_config(CONFIG_KEY) {
+        Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
+        _ => HashMap::new(),
+    }
+}
+
+/// Policy for one agent (defaults to capture-everything if unset).
+pub fn load_policy(store: &SessionStore, agent_id: &str) -> AutoCapturePolicy {
+    load_policies(store).remove(agent_id).unwrap_or_default()
+}
+
+/// Store (or replace) the policy for one agent.
+pub fn save_policy(
+    store: &SessionStore,
+    agent_id: &str,
+    policy: &AutoCapturePolicy,
+) -> EngineResult<()> {
+    let mut all = load_policies(store);
+    all.insert(agent_id.to_string(), policy.clone());
+    let json = serde_json::to_string(&all)?;
+    store.set_config(CONFIG_KEY, &json)
+}
+
+// ── Cooldown ───────────────────────────────────────────────────────────
+
+/// Remembers recently captured (agent, message) pairs.
+#[derive(Default)]
+pub struct CaptureCooldown {
+    seen: HashMap<u64, Instant>,
+}
+
+impl CaptureCooldown {
+    /// Returns true (and records the pair) if this message hasn't been
+    /// captured for this agent within `CAPTURE_COOLDOWN` of `now`.
+    pub fn claim(&mut self, agent_id: &str, message: &str, now: Instant) -> bool {
+        self.seen
+            .retain(|_, at| now.saturating_duration_since(*at) < CAPTURE_COOLDOWN);
+        let key = cooldown_key(agent_id, message);
+        if self.seen.contains_key(&key) {
+            return false;
+        }
+        self.seen.insert(key, now);
+        true
+    }
+}
+
+fn cooldown_key(agent_id: &str, message: &str) -> u64 {
+    let mut h = DefaultHasher::new();
+    agent_id.hash(&mut h);
+    message.trim().hash(&mut h);
+    h.finish()
+}
+
+static COOLDOWN: Mutex<Option<CaptureCooldown>> = Mutex::new(None);
+
+/// Process-wide cooldown check — call once per turn before extracting facts.
+pub fn claim_capture(agent_id: &str, message: &str) -> bool {
+    COOLDOWN
+        .lock()
+        .get_or_insert_with(CaptureCooldown::default)
+        .claim(agent_id, message, Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts() -> Vec<(String, String)> {
+        vec![
+            ("User prefers dark mode".into(), "preference".into()),
+            ("Always run tests first".into(), "instruction".into()),
+            ("Project uses Postgres".into(), "context".into()),
+        ]
+    }
+
+    #[test]
+    fn disabled_policy_stores_nothing() {
+        let policy = AutoCapturePolicy {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(policy.select(facts()).is_empty());
+    }
+
+    #[test]
+    fn default_policy_keeps_everything() {
+        assert_eq!(AutoCapturePolicy::default().select(facts()).len(), 3);
+    }
+
+    #[test]
+    fn category_filter_excludes_other_categories() {
+        let policy = AutoCapturePolicy {
+            categories: vec!["preference".into()],
+            ..Default::default()
+        };
+        let kept = policy.select(facts());
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].1, "preference");
+    }
+
+    #[test]
+    fn min_importance_drops_low_value_facts() {
+        let policy = AutoCapturePolicy {
+            min_importance: 7,
+            ..Default::default()
+        };
+        let kept: Vec<String> = policy.select(facts()).into_iter().map(|f| f.1).collect();
+        assert_eq!(kept, vec!["preference", "instruction"]);
+    }
+
+    #[test]
+    fn cooldown_blocks_repeat_capture() {
+        let mut cd = CaptureCooldown::default();
+        let t0 = Instant::now();
+        assert!(cd.claim("agent-a", "I prefer tabs", t0));
+        assert!(!cd.claim("agent-a", "I prefer tabs", t0 + Duration::from_secs(5)));
+        // Different agent, same message — independent
+        assert!(cd.claim("agent-b", "I prefer tabs", t0));
+        // After the cooldown expires, capture is allowed again
+        assert!(cd.claim("agent-a", "I prefer tabs", t0 + CAPTURE_COOLDOWN));
+    }
+
+    #[test]
+    fn policy_round_trips_through_store() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::engine::sessions::schema_for_testing(&conn);
+        let store = SessionStore::from_connection(conn);
+
+        assert_eq!(load_policy(&store, "a1"), AutoCapturePolicy::default());
+        let policy = AutoCapturePolicy {
+            enabled: false,
+            categories: vec!["instruction".into()],
+            min_importance: 5,
+        };
+        save_policy(&store, "a1", &policy).unwrap();
+        assert_eq!(load_policy(&store, "a1"), policy);
+        assert_eq!(load_policy(&store, "a2"), AutoCapturePolicy::default());
+    }
+}
@@ -9,6 +9,7 @@
 // so existing setups keep working without configuration changes.
 
 use crate::atoms::error::EngineResult;
+use crate::engine::providers::AnyProvider;
 use crate::engine::types::*;
 use log::{info, warn};
 use reqwest::Client;
@@ -32,6 +33,9 @@ pub struct OpenAiFallback {
     pub embedding_model: String,
     /// Model for classification/PII scanning (e.g. "gpt-4.1-mini")
     pub chat_model: String,
+    /// The chat provider itself. When set, embeddings go through its
+    /// `AiProvider::embed`, which knows the provider's auth and URL scheme.
+    pub provider: Option<ProviderConfig>,
 }
 
 /// Embedding client — calls Ollama, OpenAI, Google, or any compatible API.
@@ -41,6 +45,8 @@ pub struct EmbeddingClient {
     provider: EmbeddingProvider,
     base_url: String,
     model: String,
+    /// Key for the configured OpenAI-compatible endpoint (empty = none).
+    api_key: String,
     /// If set, used as a fallback when the primary is unreachable.
     openai_fallback: Option<OpenAiFallback>,
 }
@@ -52,6 +58,7 @@ impl EmbeddingClient {
             provider: config.embedding_provider.clone(),
             base_url: config.embedding_base_url.clone(),
             model: config.embedding_model.clone(),
+            api_key: config.embedding_api_key.clone(),
             openai_fallback: None,
         }
     }
@@ -212,6 +219,10 @@ impl EmbeddingClient {
 
     // ── Route: OpenAI direct ─────────────────────────────────────────────
     async fn embed_route_openai(&self, text: &str) -> EngineResult<Vec<f32>> {
+        // A dedicated embedding key makes this route independent of the chat provider
+        if !self.api_key.is_empty() {
+            return self.embed_openai(text).await;
+        }
         // Use the configured base_url with /v1/embeddings (or provider fallback)
         if let Some(ref fb) = self.openai_fallback {
             let result = self.embed_openai_provider(text, fb).await;
@@ -361,20 +372,29 @@ impl EmbeddingClient {
     }
 
     /// OpenAI-compatible format: POST /v1/embeddings { model, input }
+    /// Accepts a base URL with or without the trailing `/v1`.
     async fn embed_openai(&self, text: &str) -> EngineResult<Vec<f32>> {
-        let url = format!("{}/v1/embeddings", self.base_url.trim_end_matches('/'));
+        let base = self.base_url.trim_end_matches('/');
+        let url = if base.ends_with("/v1") {
+            format!("{}/embeddings", base)
+        } else {
+            format!("{}/v1/embeddings", base)
+        };
         let body = json!({
             "model": self.model,
             "input": text,
         });
 
-        let resp = self
+        let mut req = self
             .client
             .post(&url)
             .json(&body)
-            .timeout(std::time::Duration::from_secs(30))
-            .send()
-            .await?;
+            .timeout(std::time::Duration::from_secs(30));
+        if !self.api_key.is_empty() {
+            req = req.bearer_auth(&self.api_key);
+        }
+
+        let resp = req.send().await?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -406,6 +426,20 @@ impl EmbeddingClient {
         text: &str,
         fb: &OpenAiFallback,
     ) -> EngineResult<Vec<f32>> {
+        if let Some(ref config) = fb.provider {
+            let vec = AnyProvider::from_config(config)
+                .embed(&[text.to_string()], Some(&fb.embedding_model))
+                .await?
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            if vec.is_empty() {
+                return Err("Empty embedding vector from provider".into());
+            }
+            info!("[memory] Provider embedding OK ({} dims)", vec.len());
+            return Ok(vec);
+        }
+
         let base = fb.base_url.trim_end_matches('/');
         let url = if base.contains(".azure.com") {
             // Azure: embeddings endpoint with api-version
@@ -701,6 +735,7 @@ impl EmbeddingClient {
 
     /// Pull a model from Ollama. Blocks until download completes.
     pub async fn pull_model(&self) -> EngineResult<()> {
+        super::ollama::check_pull_space(&self.base_url, &self.model)?;
         let url = format!("{}/api/pull", self.base_url.trim_end_matches('/'));
         let body = json!({
             "name": self.model,
@@ -738,6 +773,7 @@ impl EmbeddingClient {
     where
         F: FnMut(&str, u64, u64),
     {
+        super::ollama::check_pull_space(&self.base_url, &self.model)?;
         let url = format!("{}/api/pull", self.base_url.trim_end_matches('/'));
         let body = json!({
             "name": self.model,
@@ -781,3 +817,82 @@ impl EmbeddingClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{request_body, serve_once, MockReply};
+
+    fn embedding_reply() -> MockReply {
+        MockReply::json(200, r#"{"data":[{"embedding":[0.1,0.2,0.3]}]}"#)
+    }
+
+    #[tokio::test]
+    async fn openai_provider_hits_v1_embeddings_with_its_own_key() {
+        let (url, server) = serve_once(embedding_reply()).await;
+
+        let config = MemoryConfig {
+            embedding_provider: EmbeddingProvider::OpenAI,
+            embedding_base_url: url,
+            embedding_model: "text-embedding-3-small".into(),
+            embedding_api_key: "sk-embed-test".into(),
+            ..Default::default()
+        };
+        let vec = EmbeddingClient::new(&config).embed("hello").await.unwrap();
+        assert_eq!(vec.len(), 3);
+
+        let raw = server.await.unwrap();
+        let request = String::from_utf8_lossy(&raw).to_string();
+        assert!(request.starts_with("POST /v1/embeddings "), "{}", request);
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer sk-embed-test"));
+        assert!(request_body(&raw).contains(r#""input":"hello""#));
+    }
+
+    #[tokio::test]
+    async fn provider_route_embeds_through_the_chat_provider() {
+        let (url, server) = serve_once(embedding_reply()).await;
+
+        let config = MemoryConfig {
+            embedding_provider: EmbeddingProvider::Provider,
+            ..Default::default()
+        };
+        let provider = ProviderConfig {
+            id: "mistral".into(),
+            kind: ProviderKind::Mistral,
+            api_key: "sk-chat-test".into(),
+            base_url: Some(format!("{}/v1", url)),
+            default_model: None,
+            api_version: None,
+            deployments: Default::default(),
+        };
+        let client = EmbeddingClient::new(&config).with_openai_fallback(OpenAiFallback {
+            api_key: provider.api_key.clone(),
+            base_url: String::new(),
+            embedding_model: provider.kind.default_embedding_model().into(),
+            chat_model: "mistral-small-latest".into(),
+            provider: Some(provider),
+        });
+        assert_eq!(client.embed("hello").await.unwrap(), vec![0.1, 0.2, 0.3]);
+
+        let request = String::from_utf8_lossy(&server.await.unwrap()).to_string();
+        assert!(request.starts_with("POST /v1/embeddings "), "{}", request);
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer sk-chat-test"));
+    }
+
+    #[test]
+    fn only_local_ollama_is_auto_startable() {
+        let mut config = MemoryConfig::default();
+        assert!(config.is_local_ollama());
+
+        config.embedding_provider = EmbeddingProvider::OpenAI;
+        assert!(!config.is_local_ollama());
+
+        config.embedding_provider = EmbeddingProvider::Ollama;
+        config.embedding_base_url = "http://gpu-box:11434".into();
+        assert!(!config.is_local_ollama());
+    }
+}
@@ -7,10 +7,16 @@
 // Module layout:
 //   ollama.rs    — Ollama lifecycle (auto-start, model discovery/pull)
 //   embedding.rs — EmbeddingClient (Ollama + OpenAI-compatible API calls)
+//   capture.rs   — per-agent auto-capture policy + re-capture cooldown
+//   portable.rs  — plain JSON export/import of the memory store
+//   recall.rs    — pre-turn "Relevant memories" injection into the system prompt
 //   mod.rs       — store, search (hybrid BM25+vector), MMR, fact extraction
 
+pub mod capture;
 pub mod embedding;
 pub mod ollama;
+pub mod portable;
+pub mod recall;
 
 // Re-export public API at the module level
 pub use embedding::EmbeddingClient;
@@ -18,6 +24,7 @@ pub use ollama::{ensure_ollama_ready, is_ollama_init_done, OllamaReadyStatus};
 
 use crate::atoms::error::EngineResult;
 use crate::engine::sessions::{f32_vec_to_bytes, SessionStore};
+use crate::engine::types::truncate_utf8;
 use crate::engine::types::*;
 use log::{error, info, warn};
 
@@ -42,21 +49,21 @@ pub async fn store_memory(
                 info!(
                     "[memory] ✓ Embedded {} dims for memory {}",
                     vec.len(),
-                    &id[..8]
+                    truncate_utf8(&id, 8)
                 );
                 Some(f32_vec_to_bytes(&vec))
             }
             Err(e) => {
                 error!(
                     "[memory] ✗ Embedding failed for memory {} — storing without vector: {}",
-                    &id[..8],
+                    truncate_utf8(&id, 8),
                     e
                 );
                 None
             }
         }
     } else {
-        warn!("[memory] No embedding client — storing memory {} without vector (semantic search won't find this)", &id[..8]);
+        warn!("[memory] No embedding client — storing memory {} without vector (semantic search won't find this)", truncate_utf8(&id, 8));
         None
     };
 
@@ -68,9 +75,12 @@ pub async fn store_memory(
         embedding_bytes.as_deref(),
         agent_id,
     )?;
+    if let Some(client) = embedding_client {
+        store_chunk_embeddings(store, &id, content, client).await?;
+    }
     info!(
         "[memory] Stored memory {} cat={} imp={} agent={:?} has_embedding={}",
-        &id[..8],
+        truncate_utf8(&id, 8),
         category,
         importance,
         agent_id,
@@ -79,6 +89,88 @@ pub async fn store_memory(
     Ok(id)
 }
 
+/// Chunk size/overlap from the saved memory config (defaults when unset).
+fn chunk_settings(store: &SessionStore) -> (usize, usize) {
+    let config: MemoryConfig = store
+        .get_config("memory_config")
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    (config.chunk_chars, config.chunk_overlap)
+}
+
+/// Split long content into overlapping segments of about `chunk_chars`
+/// characters, breaking between words. Content that fits in one chunk
+/// yields nothing — its single embedding is already focused.
+pub fn chunk_content(content: &str, chunk_chars: usize, overlap: usize) -> Vec<String> {
+    if chunk_chars == 0 || content.chars().count() <= chunk_chars {
+        return Vec::new();
+    }
+    // Overlap beyond half a chunk would make every chunk mostly repeats
+    let overlap = overlap.min(chunk_chars / 2);
+    let words: Vec<&str> = content.split_whitespace().collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < words.len() {
+        let mut end = start;
+        let mut len = 0;
+        while end < words.len() {
+            let add = words[end].chars().count() + usize::from(end > start);
+            if end > start && len + add > chunk_chars {
+                break;
+            }
+            len += add;
+            end += 1;
+        }
+        chunks.push(words[start..end].join(" "));
+        if end == words.len() {
+            break;
+        }
+        // Step back over the last `overlap` characters, always moving forward
+        let mut next = end;
+        let mut back = 0;
+        while next > start + 1 && back + words[next - 1].chars().count() < overlap {
+            back += words[next - 1].chars().count() + 1;
+            next -= 1;
+        }
+        start = next;
+    }
+    chunks
+}
+
+/// Embed the chunks of a long memory so vector search can match a passage
+/// deep inside it. A chunk that fails to embed is skipped.
+async fn store_chunk_embeddings(
+    store: &SessionStore,
+    memory_id: &str,
+    content: &str,
+    client: &EmbeddingClient,
+) -> EngineResult<()> {
+    let (chunk_chars, overlap) = chunk_settings(store);
+    let chunks = chunk_content(content, chunk_chars, overlap);
+    if chunks.is_empty() {
+        return Ok(());
+    }
+    let mut rows = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+        match client.embed(&chunk).await {
+            Ok(vec) => rows.push((chunk, Some(f32_vec_to_bytes(&vec)))),
+            Err(e) => warn!(
+                "[memory] Chunk embedding failed for memory {}: {}",
+                truncate_utf8(memory_id, 8),
+                e
+            ),
+        }
+    }
+    info!(
+        "[memory] Embedded {} chunk(s) for memory {}",
+        rows.len(),
+        truncate_utf8(memory_id, 8)
+    );
+    store.store_memory_chunks(memory_id, &rows)
+}
+
 /// Jaccard overlap threshold: memories above this are considered near-duplicates.
 pub const DEDUP_OVERLAP_THRESHOLD: f64 = 0.6;
 
@@ -159,8 +251,11 @@ pub fn content_overlap(a: &str, b: &str) -> f64 {
 /// 2. Vector semantic search via embeddings (meaning-aware)
 /// 3. Merge results with weighted scoring (0.4 BM25 + 0.6 vector)
 /// 4. Apply temporal decay (newer memories score higher)
-/// 5. Apply MMR re-ranking (maximize diversity in top results)
-/// 6. Optionally filter by agent_id
+/// 5. Apply retrieval reinforcement (frequently recalled memories score higher)
+/// 6. Apply MMR re-ranking (maximize diversity in top results)
+/// 7. Optionally filter by agent_id
+///
+/// Every memory returned is recorded as retrieved (one batched UPDATE).
 pub async fn search_memories(
     store: &SessionStore,
     query: &str,
@@ -242,13 +337,21 @@ pub async fn search_memories(
             results.len(),
             query_preview
         );
+        record_retrievals(store, &results);
         return Ok(results);
     }
 
     // ── Step 4: Apply temporal decay ───────────────────────────────
     apply_temporal_decay(&mut merged);
 
-    // ── Step 5: MMR re-ranking for diversity ───────────────────────
+    // ── Step 5: Retrieval reinforcement ────────────────────────────
+    let ids: Vec<String> = merged.iter().map(|m| m.id.clone()).collect();
+    match store.get_memory_retrieval_counts(&ids) {
+        Ok(counts) => apply_retrieval_boost(&mut merged, &counts),
+        Err(e) => warn!("[memory] Retrieval count lookup failed: {}", e),
+    }
+
+    // ── Step 6: MMR re-ranking for diversity ───────────────────────
     let merged_count = merged.len();
     let final_results = if query_embedding.is_some() && merged.len() > limit {
         mmr_rerank(&merged, limit, 0.7) // lambda=0.7 (70% relevance, 30% diversity)
@@ -272,6 +375,7 @@ pub async fn search_memories(
         merged_count
     );
 
+    record_retrievals(store, &final_results);
     Ok(final_results)
 }
 
@@ -352,6 +456,30 @@ fn apply_temporal_decay(memories: &mut [Memory]) {
     }
 }
 
+/// Apply retrieval reinforcement: memories recalled often get a small,
+/// logarithmic boost, capped at +20% so relevance still dominates.
+fn apply_retrieval_boost(memories: &mut [Memory], counts: &std::collections::HashMap<String, i64>) {
+    for mem in memories.iter_mut() {
+        let count = counts.get(&mem.id).copied().unwrap_or(0).max(0);
+        if count == 0 {
+            continue;
+        }
+        let boost = (1.0 + 0.05 * (1.0 + count as f64).ln()).min(1.2);
+        if let Some(ref mut score) = mem.score {
+            *score *= boost;
+        }
+    }
+}
+
+/// Record the returned memories as retrieved. Best-effort — a failed
+/// counter update must never fail the search itself.
+fn record_retrievals(store: &SessionStore, memories: &[Memory]) {
+    let ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+    if let Err(e) = store.record_memory_retrievals(&ids) {
+        warn!("[memory] Failed to record retrievals: {}", e);
+    }
+}
+
 /// Maximal Marginal Relevance re-ranking.
 /// Selects diverse results by penalizing redundancy.
 /// lambda: 1.0 = pure relevance, 0.0 = pure diversity. 0.7 is a good default.
@@ -436,7 +564,7 @@ pub async fn backfill_embeddings(
                 if let Err(e) = store.update_memory_embedding(&mem.id, &bytes) {
                     warn!(
                         "[memory] Backfill: failed to update {} — {}",
-                        &mem.id[..8],
+                        truncate_utf8(&mem.id, 8),
                         e
                     );
                     fail += 1;
@@ -447,7 +575,7 @@ pub async fn backfill_embeddings(
             Err(e) => {
                 warn!(
                     "[memory] Backfill: embed failed for {} — {}",
-                    &mem.id[..8],
+                    truncate_utf8(&mem.id, 8),
                     e
                 );
                 fail += 1;
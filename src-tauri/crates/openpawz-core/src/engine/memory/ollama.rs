@@ -38,6 +38,9 @@ pub struct OllamaReadyStatus {
 /// 3. Checks if the configured embedding model is available
 /// 4. If not, pulls it automatically
 /// 5. Does a test embedding to verify everything works
+///
+/// Skipped entirely when the embedding provider isn't Ollama-backed, and
+/// auto-start is only attempted for a local Ollama.
 pub async fn ensure_ollama_ready(config: &MemoryConfig) -> OllamaReadyStatus {
     let client = Client::new();
     let base_url = config.embedding_base_url.trim_end_matches('/');
@@ -53,8 +56,20 @@ pub async fn ensure_ollama_ready(config: &MemoryConfig) -> OllamaReadyStatus {
         error: None,
     };
 
+    // Cloud embedding providers never touch Ollama
+    if !matches!(
+        config.embedding_provider,
+        EmbeddingProvider::Auto | EmbeddingProvider::Ollama
+    ) {
+        info!(
+            "[memory] Embedding provider is {:?} — skipping Ollama setup",
+            config.embedding_provider
+        );
+        return status;
+    }
+
     // Skip if base_url isn't localhost (can't auto-start remote Ollama)
-    let is_local = base_url.contains("localhost") || base_url.contains("127.0.0.1");
+    let is_local = config.is_local_ollama();
 
     // ── Step 1: Check if Ollama is reachable ──
     let reachable = check_ollama_reachable(&client, base_url).await;
@@ -322,12 +337,55 @@ pub(crate) async fn check_model_available_static(
     Ok(false)
 }
 
+/// Rough on-disk size of `model` from its parameter tag — `llama3.2:3b` is
+/// ~2 GB at Ollama's default 4-bit quantisation. Untagged or unrecognised
+/// models assume 1 GiB.
+pub fn estimate_model_bytes(model: &str) -> u64 {
+    const BYTES_PER_PARAM: f64 = 0.6;
+    const DEFAULT_BYTES: u64 = 1024 * 1024 * 1024;
+    let tag = model.split_once(':').map(|(_, t)| t).unwrap_or("");
+    let billions = tag.split('-').find_map(|part| {
+        let count = part.to_ascii_lowercase();
+        let count = count.strip_suffix('b')?;
+        // Mixture-of-experts tags like `8x7b`
+        count
+            .split('x')
+            .map(|n| n.parse::<f64>().ok())
+            .product::<Option<f64>>()
+    });
+    match billions {
+        Some(b) if b > 0.0 => (b * 1e9 * BYTES_PER_PARAM) as u64,
+        _ => DEFAULT_BYTES,
+    }
+}
+
+/// Disk-space preflight for pulling `model` into the Ollama at `base_url`.
+/// Only local instances are checked — a remote server has its own disk.
+/// Models land in `$OLLAMA_MODELS`, or `~/.ollama/models` by default.
+pub fn check_pull_space(base_url: &str, model: &str) -> EngineResult<()> {
+    let local = ["localhost", "127.0.0.1", "[::1]", "0.0.0.0"]
+        .iter()
+        .any(|h| base_url.contains(h));
+    if !local {
+        return Ok(());
+    }
+    let models_dir = match std::env::var_os("OLLAMA_MODELS") {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => match dirs::home_dir() {
+            Some(home) => home.join(".ollama").join("models"),
+            None => return Ok(()),
+        },
+    };
+    crate::engine::disk::check_free_space(&models_dir, estimate_model_bytes(model))
+}
+
 /// Pull a model from Ollama (static version, no &self).
 pub(crate) async fn pull_model_static(
     client: &Client,
     base_url: &str,
     model: &str,
 ) -> EngineResult<()> {
+    check_pull_space(base_url, model)?;
     let url = format!("{}/api/pull", base_url);
     let body = json!({
         "name": model,
@@ -0,0 +1,288 @@
+// Paw Agent Engine — Portable Memory Export/Import
+//
+// Plain JSON dump of the memories table for backup and moving memory
+// between installs. Complements the encrypted Engram archive
+// (engram::encrypted_export) with a human-readable format.
+//
+// Embeddings are optional and stored as base64 little-endian f32 bytes.
+// On import, a vector is kept only if it matches the active embedding
+// model's dimensions; missing or mismatched vectors are re-embedded when
+// requested, otherwise the memory is stored bare and left for backfill.
+
+use super::EmbeddingClient;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::sessions::{f32_vec_to_bytes, SessionStore};
+use crate::engine::types::truncate_utf8;
+use crate::engine::types::Memory;
+use base64::Engine as _;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+/// Current portable export format version.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// A single exported memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableMemory {
+    pub id: String,
+    pub content: String,
+    pub category: String,
+    pub importance: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    pub created_at: String,
+    /// Base64 little-endian f32 vector (only when exported with embeddings).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<String>,
+}
+
+/// Top-level export document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableMemoryExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub memories: Vec<PortableMemory>,
+}
+
+/// Result of an import run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortableImportReport {
+    pub imported: usize,
+    /// Memories whose id already existed (left untouched).
+    pub skipped: usize,
+    /// Memories that got a fresh embedding during import.
+    pub reembedded: usize,
+    /// Memories stored without a vector (pending backfill).
+    pub without_embedding: usize,
+}
+
+// ── Export ─────────────────────────────────────────────────────────────
+
+/// Dump every memory in the store. Embeddings are included only on request
+/// since they dominate the file size.
+pub fn export_memories(
+    store: &SessionStore,
+    include_embeddings: bool,
+) -> EngineResult<PortableMemoryExport> {
+    let memories = store
+        .list_memories_for_export()?
+        .into_iter()
+        .map(|(mem, blob)| PortableMemory {
+            id: mem.id,
+            content: mem.content,
+            category: mem.category,
+            importance: mem.importance,
+            agent_id: mem.agent_id,
+            created_at: mem.created_at,
+            embedding: if include_embeddings {
+                blob.map(|b| base64::engine::general_purpose::STANDARD.encode(b))
+            } else {
+                None
+            },
+        })
+        .collect::<Vec<_>>();
+
+    info!(
+        "[memory] Exported {} memories (embeddings: {})",
+        memories.len(),
+        include_embeddings
+    );
+
+    Ok(PortableMemoryExport {
+        version: EXPORT_VERSION,
+        exported_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        memories,
+    })
+}
+
+// ── Import ─────────────────────────────────────────────────────────────
+
+/// Parse an export document, rejecting versions newer than this build.
+pub fn parse_export(json: &str) -> EngineResult<PortableMemoryExport> {
+    let export: PortableMemoryExport = serde_json::from_str(json)
+        .map_err(|e| EngineError::Other(format!("Invalid memory export JSON: {}", e)))?;
+    if export.version > EXPORT_VERSION {
+        return Err(EngineError::Other(format!(
+            "Unsupported memory export version {} (max {})",
+            export.version, EXPORT_VERSION
+        )));
+    }
+    Ok(export)
+}
+
+/// Restore memories from an export. Existing ids are skipped, never overwritten.
+///
+/// With `reembed` and a client, vectors that are absent or whose dimensions
+/// differ from the active model are recomputed.
+pub async fn import_memories(
+    store: &SessionStore,
+    export: &PortableMemoryExport,
+    client: Option<&EmbeddingClient>,
+    reembed: bool,
+) -> EngineResult<PortableImportReport> {
+    let client = if reembed { client } else { None };
+    let target_dims = match client {
+        Some(c) => match c.test_connection().await {
+            Ok(dims) => Some(dims),
+            Err(e) => {
+                warn!(
+                    "[memory] Import: embedding model unavailable ({}) — importing without re-embedding",
+                    e
+                );
+                None
+            }
+        },
+        None => None,
+    };
+
+    let mut report = PortableImportReport::default();
+
+    for pm in &export.memories {
+        let mut blob = pm.embedding.as_deref().and_then(decode_embedding);
+        if let (Some(dims), Some(b)) = (target_dims, blob.as_ref()) {
+            if b.len() / 4 != dims {
+                blob = None;
+            }
+        }
+
+        let mut reembedded = false;
+        if blob.is_none() && target_dims.is_some() {
+            if let Some(c) = client {
+                match c.embed(&pm.content).await {
+                    Ok(vec) => {
+                        blob = Some(f32_vec_to_bytes(&vec));
+                        reembedded = true;
+                    }
+                    Err(e) => warn!(
+                        "[memory] Import: embed failed for {} — {}",
+                        truncate_utf8(&pm.id, 8),
+                        e
+                    ),
+                }
+            }
+        }
+
+        let mem = Memory {
+            id: pm.id.clone(),
+            content: pm.content.clone(),
+            category: pm.category.clone(),
+            importance: pm.importance.min(10),
+            created_at: pm.created_at.clone(),
+            score: None,
+            agent_id: pm.agent_id.clone().filter(|a| !a.is_empty()),
+        };
+
+        if store.restore_memory(&mem, blob.as_deref())? {
+            report.imported += 1;
+            if reembedded {
+                report.reembedded += 1;
+            }
+            if blob.is_none() {
+                report.without_embedding += 1;
+            }
+        } else {
+            report.skipped += 1;
+        }
+    }
+
+    info!(
+        "[memory] Imported {} memories ({} skipped, {} re-embedded, {} pending backfill)",
+        report.imported, report.skipped, report.reembedded, report.without_embedding
+    );
+
+    Ok(report)
+}
+
+/// Decode a base64 vector, discarding anything that isn't whole f32s.
+fn decode_embedding(b64: &str) -> Option<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(b64)
+        .ok()
+        .filter(|b| !b.is_empty() && b.len() % 4 == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    fn seed(store: &SessionStore, n: usize) {
+        for i in 0..n {
+            let emb = f32_vec_to_bytes(&[i as f32, 0.5, -1.0]);
+            let agent = if i % 2 == 0 { Some("agent-1") } else { None };
+            store
+                .store_memory(
+                    &format!("mem-{}", i),
+                    &format!("Fact number {}", i),
+                    "general",
+                    (i % 10) as u8,
+                    Some(&emb),
+                    agent,
+                )
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip_into_fresh_store() {
+        let source = test_store();
+        seed(&source, 5);
+
+        let export = export_memories(&source, true).unwrap();
+        assert_eq!(export.memories.len(), 5);
+        let json = serde_json::to_string(&export).unwrap();
+
+        let target = test_store();
+        let report = import_memories(&target, &parse_export(&json).unwrap(), None, false)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 5);
+        assert_eq!(report.skipped, 0);
+        assert_eq!(report.without_embedding, 0);
+
+        let mut original = source.list_memories_for_export().unwrap();
+        let mut restored = target.list_memories_for_export().unwrap();
+        original.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+        restored.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+        for ((a, a_emb), (b, b_emb)) in original.iter().zip(restored.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.content, b.content);
+            assert_eq!(a.importance, b.importance);
+            assert_eq!(a.agent_id, b.agent_id);
+            assert_eq!(a.created_at, b.created_at);
+            assert_eq!(a_emb, b_emb);
+        }
+    }
+
+    #[tokio::test]
+    async fn export_without_embeddings_and_reimport_skips_duplicates() {
+        let store = test_store();
+        seed(&store, 3);
+
+        let export = export_memories(&store, false).unwrap();
+        assert!(export.memories.iter().all(|m| m.embedding.is_none()));
+
+        let report = import_memories(&store, &export, None, true).await.unwrap();
+        assert_eq!(report.imported, 0);
+        assert_eq!(report.skipped, 3);
+    }
+
+    #[test]
+    fn rejects_future_versions() {
+        let json = r#"{"version": 99, "exported_at": "", "memories": []}"#;
+        assert!(parse_export(json).is_err());
+    }
+
+    #[test]
+    fn decode_embedding_rejects_partial_floats() {
+        let b64 = base64::engine::general_purpose::STANDARD.encode([1u8, 2, 3]);
+        assert!(decode_embedding(&b64).is_none());
+    }
+}
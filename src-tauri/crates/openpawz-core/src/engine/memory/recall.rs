@@ -0,0 +1,174 @@
+// Paw Agent Engine — Pre-turn memory recall
+//
+// Before a turn, search long-term memory with the latest user message and
+// inject the top matches into the system prompt as a "Relevant memories"
+// section. The number of memories and the similarity threshold come from
+// MemoryConfig (recall_limit / recall_threshold); the section is capped at a
+// token budget so a few long memories can't crowd out the conversation.
+//
+// Recall is skipped when no embedding client is available — keyword-only
+// matches on every turn inject more noise than signal.
+
+use super::{search_memories, EmbeddingClient};
+use crate::engine::engram::tokenizer::Tokenizer;
+use crate::engine::sessions::SessionStore;
+use crate::engine::types::*;
+use log::{info, warn};
+
+/// Token budget for the injected section when none is given.
+pub const DEFAULT_RECALL_TOKEN_BUDGET: usize = 1_000;
+
+/// How much memory to recall into the prompt.
+#[derive(Debug, Clone)]
+pub struct RecallSettings {
+    /// Maximum number of memories (top-k).
+    pub limit: usize,
+    /// Minimum vector similarity (0.0–1.0).
+    pub threshold: f64,
+    /// Token budget for the whole section.
+    pub max_tokens: usize,
+}
+
+impl RecallSettings {
+    pub fn from_config(config: &MemoryConfig) -> Self {
+        RecallSettings {
+            limit: config.recall_limit,
+            threshold: config.recall_threshold,
+            max_tokens: DEFAULT_RECALL_TOKEN_BUDGET,
+        }
+    }
+}
+
+/// Search memories relevant to `query` and format them as a prompt section.
+/// Returns None when recall is skipped or nothing relevant was found.
+pub async fn relevant_memories_section(
+    store: &SessionStore,
+    query: &str,
+    embedding_client: Option<&EmbeddingClient>,
+    agent_id: Option<&str>,
+    settings: &RecallSettings,
+) -> Option<String> {
+    let Some(client) = embedding_client else {
+        info!("[memory] No embedding client — skipping memory injection");
+        return None;
+    };
+    if settings.limit == 0 || query.trim().is_empty() {
+        return None;
+    }
+
+    let memories = match search_memories(
+        store,
+        query,
+        settings.limit,
+        settings.threshold,
+        Some(client),
+        agent_id,
+    )
+    .await
+    {
+        Ok(memories) => memories,
+        Err(e) => {
+            warn!("[memory] Recall for prompt injection failed: {}", e);
+            return None;
+        }
+    };
+    format_memories_section(&memories, settings.max_tokens)
+}
+
+/// Format memories (best first) as a "Relevant memories" section, adding
+/// entries until the next one would exceed `max_tokens`.
+pub fn format_memories_section(memories: &[Memory], max_tokens: usize) -> Option<String> {
+    let tokenizer = Tokenizer::heuristic();
+    let mut section = String::from("## Relevant memories");
+    let mut used = tokenizer.count_tokens(&section);
+    let mut included = 0;
+
+    for memory in memories {
+        let line = format!("\n- [{}] {}", memory.category, memory.content.trim());
+        let tokens = tokenizer.count_tokens(&line);
+        if used + tokens > max_tokens {
+            break;
+        }
+        section.push_str(&line);
+        used += tokens;
+        included += 1;
+    }
+
+    if included == 0 {
+        return None;
+    }
+    info!(
+        "[memory] Injecting {} of {} recalled memories (~{} tokens)",
+        included,
+        memories.len(),
+        used
+    );
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::f32_vec_to_bytes;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    fn store_with_memories() -> SessionStore {
+        let store = test_store();
+        let near = f32_vec_to_bytes(&[0.9, 0.1, 0.0]);
+        let far = f32_vec_to_bytes(&[0.0, 0.0, 1.0]);
+        store
+            .store_memory(
+                "m1",
+                "The user's dog is called Biscuit",
+                "fact",
+                7,
+                Some(&near),
+                Some("default"),
+            )
+            .unwrap();
+        store
+            .store_memory(
+                "m2",
+                "Quarterly taxes are due in April",
+                "fact",
+                5,
+                Some(&far),
+                Some("default"),
+            )
+            .unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn recall_is_skipped_without_embeddings() {
+        let store = store_with_memories();
+        let settings = RecallSettings::from_config(&MemoryConfig::default());
+        let section =
+            relevant_memories_section(&store, "dog", None, Some("default"), &settings).await;
+        assert!(section.is_none());
+    }
+
+    #[test]
+    fn section_respects_token_budget() {
+        let memory = |content: &str| Memory {
+            id: content.into(),
+            content: content.into(),
+            category: "note".into(),
+            importance: 5,
+            created_at: String::new(),
+            score: None,
+            agent_id: None,
+        };
+        let memories = vec![memory("short one"), memory(&"long ".repeat(200))];
+        let section = format_memories_section(&memories, 50).unwrap();
+        assert_eq!(section, "## Relevant memories\n- [note] short one");
+        assert!(format_memories_section(&memories[1..], 50).is_none());
+    }
+}
@@ -1,19 +1,49 @@
 // openpawz-core engine — Pure business logic modules.
 // No Tauri dependency — these modules work in CLI, server, and desktop contexts.
 
+pub mod agent_loop;
+pub mod approval_rules;
 pub mod audit;
+pub mod autonomy;
+pub mod budget_window;
+pub mod cancel;
 pub mod constrained;
+pub mod csv;
+pub mod dex_allowance;
+pub mod disk;
+pub mod emergency;
 pub mod engram;
+pub mod file_log;
+pub mod headless;
 pub mod http;
+pub mod i18n;
 pub mod injection;
 pub mod key_vault;
 pub mod memory;
+pub mod onboarding;
+pub mod partial_reply;
 pub mod paths;
 pub mod pricing;
+pub mod prompt_template;
 pub mod provider_registry;
 pub mod providers;
+pub mod reminders;
+pub mod run_limiter;
+pub mod run_trace;
 pub mod scc;
+pub mod secret_scrub;
+pub mod session_archive;
+pub mod session_search;
 pub mod sessions;
+pub mod storage_migration;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod tool_args;
+pub mod tool_cache;
+pub mod tool_guard;
 pub mod tool_metadata;
+pub mod tool_progress;
+pub mod tool_result_policy;
+pub mod tx_idempotency;
 pub mod types;
 pub mod util;
@@ -0,0 +1,206 @@
+// Paw Engine — First-run onboarding
+//
+// Onboarding spans several independent checks — a chat provider, a default
+// model, local embeddings for memory, a first set of skills — that used to
+// be probed one command at a time. The host gathers the raw facts into
+// `OnboardingFacts`; `evaluate()` turns them into a per-step status and the
+// single next action the guided-setup UI should offer.
+//
+// Every step is derived from current state only, so checks are idempotent:
+// re-running one after the user fixes something simply reports it complete.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    /// At least one AI provider is configured.
+    Provider,
+    /// A default chat model is chosen.
+    Model,
+    /// An embedding model is reachable, so memory search works.
+    Embeddings,
+    /// At least one skill is enabled.
+    Skills,
+    /// The setup wizard has been dismissed as done.
+    Finish,
+}
+
+impl OnboardingStep {
+    /// Steps in the order the guided setup walks them.
+    pub const ALL: [OnboardingStep; 5] = [
+        OnboardingStep::Provider,
+        OnboardingStep::Model,
+        OnboardingStep::Embeddings,
+        OnboardingStep::Skills,
+        OnboardingStep::Finish,
+    ];
+
+    /// Optional steps are recommended but don't block finishing.
+    pub fn is_optional(self) -> bool {
+        matches!(self, OnboardingStep::Embeddings | OnboardingStep::Skills)
+    }
+}
+
+/// Raw state the steps are judged on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OnboardingFacts {
+    pub providers_configured: usize,
+    pub default_model: Option<String>,
+    pub ollama_running: bool,
+    pub ollama_models: usize,
+    pub embedding_ready: bool,
+    pub skills_enabled: usize,
+    pub wizard_complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepStatus {
+    pub step: OnboardingStep,
+    pub complete: bool,
+    pub optional: bool,
+    /// What to do (incomplete) or what was found (complete).
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStatus {
+    pub steps: Vec<StepStatus>,
+    /// First incomplete step, None when everything is done.
+    pub next_step: Option<OnboardingStep>,
+    pub next_action: String,
+    /// All required steps before `Finish` are complete, so the wizard can
+    /// be finished.
+    pub ready: bool,
+}
+
+/// Status of a single step.
+pub fn step_status(step: OnboardingStep, facts: &OnboardingFacts) -> StepStatus {
+    let (complete, detail) = match step {
+        OnboardingStep::Provider => {
+            if facts.providers_configured > 0 {
+                (
+                    true,
+                    format!("{} provider(s) configured", facts.providers_configured),
+                )
+            } else if facts.ollama_running && facts.ollama_models > 0 {
+                (
+                    false,
+                    format!(
+                        "Use local Ollama ({} model(s) found) or configure a provider",
+                        facts.ollama_models
+                    ),
+                )
+            } else {
+                (false, "Configure a provider".to_string())
+            }
+        }
+        OnboardingStep::Model => match facts.default_model.as_deref() {
+            Some(model) if !model.is_empty() => (true, format!("Default model: {}", model)),
+            _ => (false, "Choose a default model".to_string()),
+        },
+        OnboardingStep::Embeddings => {
+            if facts.embedding_ready {
+                (true, "Embedding model ready".to_string())
+            } else if facts.ollama_running {
+                (
+                    false,
+                    "Pull the embedding model for memory search".to_string(),
+                )
+            } else {
+                (
+                    false,
+                    "Install or start Ollama to enable memory search".to_string(),
+                )
+            }
+        }
+        OnboardingStep::Skills => {
+            if facts.skills_enabled > 0 {
+                (true, format!("{} skill(s) enabled", facts.skills_enabled))
+            } else {
+                (false, "Enable some skills".to_string())
+            }
+        }
+        OnboardingStep::Finish => {
+            if facts.wizard_complete {
+                (true, "Setup complete".to_string())
+            } else {
+                (false, "Finish setup".to_string())
+            }
+        }
+    };
+    StepStatus {
+        step,
+        complete,
+        optional: step.is_optional(),
+        detail,
+    }
+}
+
+/// Status of every step plus the next recommended action.
+pub fn evaluate(facts: &OnboardingFacts) -> OnboardingStatus {
+    let steps: Vec<StepStatus> = OnboardingStep::ALL
+        .iter()
+        .map(|&step| step_status(step, facts))
+        .collect();
+    let ready = steps
+        .iter()
+        .filter(|s| s.step != OnboardingStep::Finish)
+        .all(|s| s.complete || s.optional);
+    let next = steps.iter().find(|s| !s.complete);
+    OnboardingStatus {
+        next_step: next.map(|s| s.step),
+        next_action: next
+            .map(|s| s.detail.clone())
+            .unwrap_or_else(|| "Setup complete".to_string()),
+        ready,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_install_without_ollama_asks_for_a_provider() {
+        let status = evaluate(&OnboardingFacts::default());
+        assert_eq!(status.next_step, Some(OnboardingStep::Provider));
+        assert_eq!(status.next_action, "Configure a provider");
+        assert!(!status.ready);
+        assert!(status.steps.iter().all(|s| !s.complete));
+    }
+
+    #[test]
+    fn local_ollama_is_offered_for_the_provider_step() {
+        let facts = OnboardingFacts {
+            ollama_running: true,
+            ollama_models: 2,
+            ..Default::default()
+        };
+        let status = evaluate(&facts);
+        assert_eq!(status.next_step, Some(OnboardingStep::Provider));
+        assert!(status.next_action.contains("Ollama"));
+    }
+
+    #[test]
+    fn optional_steps_do_not_block_readiness() {
+        let facts = OnboardingFacts {
+            providers_configured: 1,
+            default_model: Some("gpt-4o".into()),
+            ..Default::default()
+        };
+        let status = evaluate(&facts);
+        assert!(status.ready);
+        assert_eq!(status.next_step, Some(OnboardingStep::Embeddings));
+
+        let done = evaluate(&OnboardingFacts {
+            embedding_ready: true,
+            skills_enabled: 3,
+            wizard_complete: true,
+            ..facts
+        });
+        assert_eq!(done.next_step, None);
+        assert_eq!(done.next_action, "Setup complete");
+    }
+}
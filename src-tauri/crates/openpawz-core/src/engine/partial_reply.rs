@@ -0,0 +1,178 @@
+// Paw Agent Engine — Streaming reply persistence
+//
+// Chat turns are stored when the run completes, so a reply that is cut off
+// (Stop, a crash, a dropped connection) used to vanish with it. The run now
+// mirrors its streamed text into an assistant row flagged `incomplete`,
+// saving at most every `SAVE_INTERVAL`. On success the caller discards the
+// row and stores the finished messages as before; otherwise the partial
+// reply stays in the session, marked so the UI can show it was truncated.
+//
+// Reasoning streamed by thinking models is collected alongside the text but
+// never mixed into it; the caller stores it on the final assistant message.
+
+use crate::engine::sessions::SessionStore;
+use log::warn;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between saves while text is streaming.
+pub const SAVE_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+struct State {
+    text: String,
+    reasoning: String,
+    saved_len: usize,
+    last_save: Option<Instant>,
+}
+
+/// The in-progress assistant reply for one run.
+pub struct PartialReply {
+    session_id: String,
+    message_id: String,
+    state: Mutex<State>,
+}
+
+impl PartialReply {
+    pub fn new(session_id: &str) -> Self {
+        PartialReply {
+            session_id: session_id.to_string(),
+            message_id: uuid::Uuid::new_v4().to_string(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Id of the message row the reply is saved under.
+    pub fn message_id(&self) -> &str {
+        &self.message_id
+    }
+
+    /// Separate the next model round's text from the previous round's.
+    pub fn begin_round(&self) {
+        let mut state = self.state.lock();
+        if !state.text.is_empty() && !state.text.ends_with("\n\n") {
+            state.text.push_str("\n\n");
+        }
+    }
+
+    /// Append streamed text, saving if the last save is old enough.
+    pub fn push(&self, store: &SessionStore, delta: &str) {
+        let mut state = self.state.lock();
+        state.text.push_str(delta);
+        let due = state
+            .last_save
+            .is_none_or(|at| at.elapsed() >= SAVE_INTERVAL);
+        if due {
+            self.save(store, &mut state);
+        }
+    }
+
+    /// Append streamed reasoning. Held in memory only — a reply cut off
+    /// mid-run keeps its text, not the thinking behind it.
+    pub fn push_reasoning(&self, delta: &str) {
+        self.state.lock().reasoning.push_str(delta);
+    }
+
+    /// All reasoning streamed during the run, if any.
+    pub fn reasoning(&self) -> Option<String> {
+        let state = self.state.lock();
+        let reasoning = state.reasoning.trim();
+        (!reasoning.is_empty()).then(|| reasoning.to_string())
+    }
+
+    /// Save any text not yet written, e.g. when the run fails.
+    pub fn flush(&self, store: &SessionStore) {
+        let mut state = self.state.lock();
+        if state.text.len() != state.saved_len {
+            self.save(store, &mut state);
+        }
+    }
+
+    /// Drop the saved reply — the run finished and its messages replace it.
+    pub fn discard(&self, store: &SessionStore) {
+        if let Err(e) = store.discard_partial_reply(&self.message_id) {
+            warn!("[engine] Failed to discard partial reply: {}", e);
+        }
+    }
+
+    fn save(&self, store: &SessionStore, state: &mut State) {
+        // Whitespace-only text isn't worth a message row
+        if state.text.trim().is_empty() {
+            return;
+        }
+        match store.save_partial_reply(&self.session_id, &self.message_id, &state.text) {
+            Ok(()) => {
+                state.saved_len = state.text.len();
+                state.last_save = Some(Instant::now());
+            }
+            Err(e) => warn!("[engine] Failed to save partial reply: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with_session() -> SessionStore {
+        let store = SessionStore::open_in_memory().unwrap();
+        store.create_session("s1", "gpt-4", None, None).unwrap();
+        store
+    }
+
+    #[test]
+    fn interrupted_stream_leaves_a_flagged_partial_message() {
+        let store = store_with_session();
+        let reply = PartialReply::new("s1");
+        reply.begin_round();
+        reply.push(&store, "The answer ");
+        // Within the save interval — buffered, not yet written
+        reply.push(&store, "is forty");
+        // The run fails here; whatever streamed is flushed and the reply
+        // is never discarded
+        reply.flush(&store);
+
+        let messages = store.get_messages("s1", 100).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, reply.message_id());
+        assert_eq!(messages[0].role, "assistant");
+        assert_eq!(messages[0].content, "The answer is forty");
+        assert!(messages[0].incomplete);
+        assert_eq!(store.get_session("s1").unwrap().unwrap().message_count, 1);
+    }
+
+    #[test]
+    fn completed_run_discards_the_partial_copy() {
+        let store = store_with_session();
+        let reply = PartialReply::new("s1");
+        reply.push(&store, "Checking the weather");
+        reply.begin_round();
+        reply.push(&store, "It's sunny.");
+        reply.flush(&store);
+        assert_eq!(
+            store.get_messages("s1", 100).unwrap()[0].content,
+            "Checking the weather\n\nIt's sunny."
+        );
+
+        reply.discard(&store);
+        assert!(store.get_messages("s1", 100).unwrap().is_empty());
+        assert_eq!(store.get_session("s1").unwrap().unwrap().message_count, 0);
+    }
+
+    #[test]
+    fn reasoning_is_kept_apart_from_the_reply_text() {
+        let store = store_with_session();
+        let reply = PartialReply::new("s1");
+        assert_eq!(reply.reasoning(), None);
+        reply.push_reasoning("The user wants a number. ");
+        reply.push(&store, "42");
+        reply.push_reasoning("Forty-two it is.");
+        reply.flush(&store);
+
+        assert_eq!(store.get_messages("s1", 100).unwrap()[0].content, "42");
+        assert_eq!(
+            reply.reasoning().as_deref(),
+            Some("The user wants a number. Forty-two it is.")
+        );
+    }
+}
@@ -0,0 +1,304 @@
+// Paw Agent Engine — System-prompt template
+//
+// Power users can lay out the base system prompt themselves
+// (`EngineConfig::system_prompt_template`) using named placeholders that are
+// filled in when the prompt is assembled:
+//
+//   {base_prompt}     the agent's configured system prompt / persona
+//   {agent_name}      display name of the agent
+//   {date}            today's date in the user's timezone (YYYY-MM-DD, weekday)
+//   {memory_summary}  today's memory notes
+//   {skills}          instructions for the enabled skills
+//   {workspace}       the agent's workspace directory
+//
+// Sections a template places itself ({memory_summary}, {skills}) are not
+// appended again by the composer. Only `{lower_snake}` tokens count as
+// placeholders, so JSON or code braces in a template are left alone.
+
+use crate::engine::paths::agent_workspace_dir;
+use crate::engine::types::EngineConfig;
+use log::warn;
+
+/// Matches the built-in layout: the base prompt, with every other section
+/// appended by the composer as before.
+pub const DEFAULT_PROMPT_TEMPLATE: &str = "{base_prompt}";
+
+/// Every placeholder a template may reference.
+pub const TEMPLATE_VARIABLES: &[&str] = &[
+    "base_prompt",
+    "agent_name",
+    "date",
+    "memory_summary",
+    "skills",
+    "workspace",
+];
+
+/// Values substituted into a template.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVariables {
+    pub base_prompt: String,
+    pub agent_name: String,
+    pub date: String,
+    pub memory_summary: String,
+    pub skills: String,
+    pub workspace: String,
+}
+
+impl PromptVariables {
+    fn get(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "base_prompt" => &self.base_prompt,
+            "agent_name" => &self.agent_name,
+            "date" => &self.date,
+            "memory_summary" => &self.memory_summary,
+            "skills" => &self.skills,
+            "workspace" => &self.workspace,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// A rendered template. Unknown placeholders stay in `text` verbatim and are
+/// listed in `unknown`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedPrompt {
+    pub text: String,
+    pub unknown: Vec<String>,
+}
+
+/// Split `template` into literal text and placeholder names, in order.
+fn tokens(template: &str) -> Vec<(&str, bool)> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(after.len());
+        if name_len > 0 && after[name_len..].starts_with('}') {
+            out.push((&rest[..start], false));
+            out.push((&after[..name_len], true));
+            rest = &after[name_len + 1..];
+        } else {
+            out.push((&rest[..=start], false));
+            rest = after;
+        }
+    }
+    out.push((rest, false));
+    out
+}
+
+/// Placeholder names referenced by `template`, in order of appearance.
+pub fn referenced_variables(template: &str) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for (token, is_var) in tokens(template) {
+        if is_var && !names.contains(&token) {
+            names.push(token);
+        }
+    }
+    names
+}
+
+/// Check that every placeholder in `template` is a known variable.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let unknown: Vec<&str> = referenced_variables(template)
+        .into_iter()
+        .filter(|name| !TEMPLATE_VARIABLES.contains(name))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown system prompt variable(s): {}. Available: {}",
+            unknown
+                .iter()
+                .map(|n| format!("{{{}}}", n))
+                .collect::<Vec<_>>()
+                .join(", "),
+            TEMPLATE_VARIABLES
+                .iter()
+                .map(|n| format!("{{{}}}", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+/// Substitute `vars` into `template`.
+pub fn render_template(template: &str, vars: &PromptVariables) -> RenderedPrompt {
+    let mut text = String::with_capacity(template.len());
+    let mut unknown = Vec::new();
+    for (token, is_var) in tokens(template) {
+        if !is_var {
+            text.push_str(token);
+            continue;
+        }
+        match vars.get(token) {
+            Some(value) => text.push_str(value),
+            None => {
+                text.push('{');
+                text.push_str(token);
+                text.push('}');
+                if !unknown.iter().any(|u| u == token) {
+                    unknown.push(token.to_string());
+                }
+            }
+        }
+    }
+    RenderedPrompt {
+        text: text.trim().to_string(),
+        unknown,
+    }
+}
+
+/// The base prompt for a run, laid out by the user's system-prompt template
+/// when one is set (`EngineConfig::system_prompt_template`). Chat, task and
+/// channel and headless runs all go
+/// through here.
+pub fn templated_base_prompt(
+    config: &EngineConfig,
+    base_system_prompt: Option<String>,
+    agent_id: &str,
+    todays_memories: &mut Option<String>,
+    skill_instructions: &mut String,
+) -> Option<String> {
+    match config
+        .system_prompt_template
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    {
+        Some(template) => apply_prompt_template(
+            template,
+            base_system_prompt.as_deref(),
+            agent_id,
+            &config.user_timezone,
+            todays_memories,
+            skill_instructions,
+        ),
+        None => base_system_prompt,
+    }
+}
+
+/// Render the user's system-prompt template into the base prompt.
+///
+/// Sections the template places itself (`{skills}`, `{memory_summary}`) are
+/// taken out of `skill_instructions` / `todays_memories` so the composer
+/// doesn't append them a second time. Unknown placeholders are left as-is
+/// and logged.
+pub fn apply_prompt_template(
+    template: &str,
+    base_system_prompt: Option<&str>,
+    agent_id: &str,
+    user_timezone: &str,
+    todays_memories: &mut Option<String>,
+    skill_instructions: &mut String,
+) -> Option<String> {
+    let referenced = referenced_variables(template);
+    let date = match user_timezone.parse::<chrono_tz::Tz>() {
+        Ok(tz) => chrono::Utc::now()
+            .with_timezone(&tz)
+            .format("%Y-%m-%d %A")
+            .to_string(),
+        Err(_) => chrono::Local::now().format("%Y-%m-%d %A").to_string(),
+    };
+    let vars = PromptVariables {
+        base_prompt: base_system_prompt.unwrap_or_default().to_string(),
+        agent_name: agent_display_name(agent_id),
+        date,
+        memory_summary: if referenced.contains(&"memory_summary") {
+            todays_memories.take().unwrap_or_default()
+        } else {
+            String::new()
+        },
+        skills: if referenced.contains(&"skills") {
+            std::mem::take(skill_instructions)
+        } else {
+            String::new()
+        },
+        workspace: agent_workspace_dir(agent_id).display().to_string(),
+    };
+
+    let rendered = render_template(template, &vars);
+    if !rendered.unknown.is_empty() {
+        warn!(
+            "[engine] System prompt template has unknown variable(s) {:?} — left unsubstituted",
+            rendered.unknown
+        );
+    }
+    if rendered.text.is_empty() {
+        None
+    } else {
+        Some(rendered.text)
+    }
+}
+
+/// Resolve a human-friendly agent name so the model knows its own identity.
+/// The default agent's display name is "Pawz" (set in frontend), but the
+/// backend only stores agent_id. For non-default agents, the agent_id slug
+/// is title-cased ("agent-code-monkey-123" → "Code Monkey").
+pub fn agent_display_name(agent_id: &str) -> String {
+    if agent_id == "default" {
+        return "Pawz".to_string();
+    }
+    agent_id
+        .strip_prefix("agent-")
+        .unwrap_or(agent_id)
+        .split('-')
+        .filter(|s| !s.chars().all(|c| c.is_ascii_digit()))
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> PromptVariables {
+        PromptVariables {
+            base_prompt: "You are a helpful assistant.".into(),
+            agent_name: "Pawz".into(),
+            date: "2026-03-14 Saturday".into(),
+            memory_summary: "- Bought milk".into(),
+            skills: "## Email\nUse email_send.".into(),
+            workspace: "/home/me/.paw/workspaces/default".into(),
+        }
+    }
+
+    #[test]
+    fn fills_known_variables_and_flags_unknown_ones() {
+        let template = "# {agent_name} ({date})\n{base_prompt}\n\nWorkspace: {workspace}\n\
+            {skills}\n{memory_summary}\nMood: {mood} / {mood}\nJSON stays: {\"a\": 1} {Caps}";
+        let rendered = render_template(template, &vars());
+        assert_eq!(
+            rendered.text,
+            "# Pawz (2026-03-14 Saturday)\nYou are a helpful assistant.\n\n\
+            Workspace: /home/me/.paw/workspaces/default\n## Email\nUse email_send.\n\
+            - Bought milk\nMood: {mood} / {mood}\nJSON stays: {\"a\": 1} {Caps}"
+        );
+        assert_eq!(rendered.unknown, vec!["mood".to_string()]);
+    }
+
+    #[test]
+    fn default_template_is_the_base_prompt() {
+        let rendered = render_template(DEFAULT_PROMPT_TEMPLATE, &vars());
+        assert_eq!(rendered.text, "You are a helpful assistant.");
+        assert!(rendered.unknown.is_empty());
+        assert!(validate_template(DEFAULT_PROMPT_TEMPLATE).is_ok());
+    }
+
+    #[test]
+    fn validation_names_unknown_variables() {
+        assert!(validate_template("{agent_name} on {date}: {skills}").is_ok());
+        let err = validate_template("{agent_name} {user_name} {user_name}").unwrap_err();
+        assert!(err.starts_with("Unknown system prompt variable(s): {user_name}. Available:"));
+        assert!(err.contains("{memory_summary}"));
+    }
+}
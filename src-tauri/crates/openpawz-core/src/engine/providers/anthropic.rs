@@ -4,7 +4,8 @@
 
 use crate::atoms::traits::{AiProvider, ProviderError};
 use crate::engine::http::{
-    pinned_client, sign_and_log_request, update_last_audit_status, CircuitBreaker,
+    clear_overload, pinned_client, record_overload, sign_and_log_request, update_last_audit_status,
+    CircuitBreaker, OVERLOADED_STATUS,
 };
 use crate::engine::providers::openai::{
     is_retryable_status, parse_retry_after, retry_delay, MAX_RETRIES,
@@ -619,6 +620,15 @@ impl AnthropicProvider {
                 if status == 401 || status == 403 {
                     return Err(ProviderError::Auth(last_error));
                 }
+                // Sustained overload: backing off won't help — surface the
+                // 529 now so a fallback provider can take over.
+                if status == OVERLOADED_STATUS && record_overload("anthropic") {
+                    warn!("[engine] Anthropic in sustained overload — skipping further retries");
+                    return Err(ProviderError::Api {
+                        status,
+                        message: last_error,
+                    });
+                }
                 if is_retryable_status(status) && attempt < MAX_RETRIES {
                     continue;
                 }
@@ -658,6 +668,7 @@ impl AnthropicProvider {
             }
 
             ANTHROPIC_CIRCUIT.record_success();
+            clear_overload("anthropic");
             return Ok(chunks);
         }
 
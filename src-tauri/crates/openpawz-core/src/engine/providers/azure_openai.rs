@@ -0,0 +1,196 @@
+// Paw Agent Engine — Azure OpenAI deployment routing
+//
+// Azure OpenAI speaks the OpenAI wire format but routes by deployment:
+//
+//   {endpoint}/openai/deployments/{deployment}/chat/completions?api-version=…
+//
+// authenticated with an `api-key` header instead of a Bearer token. The
+// model a request names is looked up in the provider's `deployments` map;
+// unmapped models are assumed to be deployed under their own name. The
+// OpenAI-compatible provider uses this for URLs; everything else about the
+// request is unchanged.
+
+use crate::engine::types::{ProviderConfig, ProviderKind};
+use std::collections::HashMap;
+
+/// Used when neither the config nor the base URL names an api-version.
+pub const DEFAULT_API_VERSION: &str = "2024-10-21";
+
+/// Resolved endpoint, api-version and deployment map for one provider.
+#[derive(Debug, Clone)]
+pub struct AzureOpenAiRouting {
+    /// `https://{resource}.openai.azure.com` — no path, no query.
+    pub endpoint: String,
+    pub api_version: String,
+    deployments: HashMap<String, String>,
+}
+
+impl AzureOpenAiRouting {
+    /// Routing for `config`. The base URL may be the bare resource endpoint
+    /// or any URL under it (a pasted deployment URL works too); an
+    /// `api-version` in its query is used when the config doesn't set one.
+    pub fn from_config(config: &ProviderConfig) -> Self {
+        let base = config.base_url.as_deref().unwrap_or("").trim();
+        let (path, query) = base.split_once('?').unwrap_or((base, ""));
+        let endpoint = path
+            .split("/openai")
+            .next()
+            .unwrap_or(path)
+            .trim_end_matches('/')
+            .to_string();
+        let api_version = config
+            .api_version
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                query
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix("api-version="))
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| DEFAULT_API_VERSION.to_string());
+        AzureOpenAiRouting {
+            endpoint,
+            api_version,
+            deployments: config.deployments.clone(),
+        }
+    }
+
+    /// Deployment serving `model`.
+    pub fn deployment_for<'a>(&'a self, model: &'a str) -> &'a str {
+        self.deployments
+            .get(model)
+            .map(String::as_str)
+            .unwrap_or(model)
+    }
+
+    pub fn chat_url(&self, model: &str) -> String {
+        self.deployment_url(model, "chat/completions")
+    }
+
+    pub fn embeddings_url(&self, model: &str) -> String {
+        self.deployment_url(model, "embeddings")
+    }
+
+    pub fn models_url(&self) -> String {
+        format!(
+            "{}/openai/models?api-version={}",
+            self.endpoint, self.api_version
+        )
+    }
+
+    fn deployment_url(&self, model: &str, operation: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/{}?api-version={}",
+            self.endpoint,
+            self.deployment_for(model),
+            operation,
+            self.api_version
+        )
+    }
+}
+
+/// Check an Azure OpenAI provider has what routing needs: an https
+/// endpoint, an api-version and a deployment for its default model.
+pub fn validate_config(config: &ProviderConfig) -> Result<(), String> {
+    if config.kind != ProviderKind::AzureOpenAI {
+        return Ok(());
+    }
+    let base = config.base_url.as_deref().unwrap_or("").trim();
+    if base.is_empty() {
+        return Err(
+            "Azure OpenAI needs a base URL (https://<resource>.openai.azure.com)".to_string(),
+        );
+    }
+    if !base.starts_with("https://") {
+        return Err(format!("Azure OpenAI base URL must use https: {}", base));
+    }
+    let has_version = config
+        .api_version
+        .as_deref()
+        .is_some_and(|v| !v.trim().is_empty())
+        || base.contains("api-version=");
+    if !has_version {
+        return Err("Azure OpenAI needs an api-version (e.g. 2024-10-21)".to_string());
+    }
+    let has_deployment = config
+        .default_model
+        .as_deref()
+        .is_some_and(|m| !m.trim().is_empty())
+        || !config.deployments.is_empty();
+    if !has_deployment {
+        return Err(
+            "Azure OpenAI needs a deployment — set the default model to your deployment name"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(base_url: &str, api_version: Option<&str>) -> ProviderConfig {
+        ProviderConfig {
+            id: "azure".into(),
+            kind: ProviderKind::AzureOpenAI,
+            api_key: "azure-key".into(),
+            base_url: Some(base_url.into()),
+            default_model: Some("gpt-4o".into()),
+            api_version: api_version.map(str::to_string),
+            deployments: [("gpt-4o".to_string(), "prod-gpt4o".to_string())].into(),
+        }
+    }
+
+    #[test]
+    fn models_map_to_deployment_urls() {
+        let routing = AzureOpenAiRouting::from_config(&config(
+            "https://contoso.openai.azure.com/",
+            Some("2024-10-21"),
+        ));
+        assert_eq!(
+            routing.chat_url("gpt-4o"),
+            "https://contoso.openai.azure.com/openai/deployments/prod-gpt4o/chat/completions?api-version=2024-10-21"
+        );
+        // Unmapped models use their own name as the deployment
+        assert_eq!(
+            routing.embeddings_url("text-embedding-3-small"),
+            "https://contoso.openai.azure.com/openai/deployments/text-embedding-3-small/embeddings?api-version=2024-10-21"
+        );
+    }
+
+    #[test]
+    fn pasted_deployment_url_is_reduced_to_the_endpoint() {
+        let routing = AzureOpenAiRouting::from_config(&config(
+            "https://contoso.openai.azure.com/openai/deployments/x/chat/completions?api-version=2025-01-01-preview",
+            None,
+        ));
+        assert_eq!(routing.endpoint, "https://contoso.openai.azure.com");
+        assert_eq!(routing.api_version, "2025-01-01-preview");
+    }
+
+    #[test]
+    fn validation_requires_endpoint_version_and_deployment() {
+        assert!(validate_config(&config(
+            "https://contoso.openai.azure.com",
+            Some("2024-10-21")
+        ))
+        .is_ok());
+        assert!(validate_config(&config("", Some("2024-10-21"))).is_err());
+        assert!(validate_config(&config(
+            "http://contoso.openai.azure.com",
+            Some("2024-10-21")
+        ))
+        .is_err());
+        assert!(validate_config(&config("https://contoso.openai.azure.com", None)).is_err());
+
+        let mut no_deployment = config("https://contoso.openai.azure.com", Some("2024-10-21"));
+        no_deployment.default_model = None;
+        no_deployment.deployments.clear();
+        assert!(validate_config(&no_deployment).is_err());
+    }
+}
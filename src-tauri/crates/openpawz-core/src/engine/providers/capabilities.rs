@@ -0,0 +1,224 @@
+// Paw Agent Engine — Capability-aware request shaping
+//
+// Before a request goes out, drop what the target model can't accept
+// (per engram::model_caps::request_capabilities): tools for models without
+// tool calling, and image/document blocks for text-only models. Sending them
+// anyway gets a confusing 400 from the provider. Only models the registry
+// names exactly or by family are shaped; anything else passes through
+// untouched.
+
+use crate::engine::engram::request_capabilities;
+use crate::engine::types::{ContentBlock, Message, MessageContent, ToolDefinition};
+use log::info;
+use std::borrow::Cow;
+
+/// Shape `messages` / `tools` for `model`.
+pub(crate) fn fit_request_to_model<'a>(
+    messages: &'a [Message],
+    tools: &'a [ToolDefinition],
+    model: &str,
+) -> (Cow<'a, [Message]>, &'a [ToolDefinition]) {
+    let Some(caps) = request_capabilities(model) else {
+        return (Cow::Borrowed(messages), tools);
+    };
+
+    let tools = if caps.supports_tools || tools.is_empty() {
+        tools
+    } else {
+        info!(
+            "[engine] {} does not support tool calling — sending without {} tools",
+            model,
+            tools.len()
+        );
+        &[]
+    };
+
+    let has_media = messages.iter().any(|m| match &m.content {
+        MessageContent::Blocks(blocks) => blocks
+            .iter()
+            .any(|b| !matches!(b, ContentBlock::Text { .. })),
+        MessageContent::Text(_) => false,
+    });
+    if caps.supports_vision || !has_media {
+        return (Cow::Borrowed(messages), tools);
+    }
+
+    info!(
+        "[engine] {} is text-only — replacing image/document content with placeholders",
+        model
+    );
+    let messages = messages
+        .iter()
+        .map(|m| match &m.content {
+            MessageContent::Blocks(blocks) => Message {
+                content: MessageContent::Text(text_only(blocks)),
+                ..m.clone()
+            },
+            MessageContent::Text(_) => m.clone(),
+        })
+        .collect();
+    (Cow::Owned(messages), tools)
+}
+
+fn text_only(blocks: &[ContentBlock]) -> String {
+    blocks
+        .iter()
+        .map(|block| match block {
+            ContentBlock::Text { text } => text.clone(),
+            ContentBlock::ImageUrl { .. } => {
+                "[image omitted — the current model can't view images]".to_string()
+            }
+            ContentBlock::Document { name, .. } => format!(
+                "[document {}omitted — the current model can't read attachments]",
+                name.as_deref()
+                    .map(|n| format!("\"{}\" ", n))
+                    .unwrap_or_default()
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atoms::traits::{AiProvider, ProviderError};
+    use crate::engine::providers::AnyProvider;
+    use crate::engine::types::{FunctionDefinition, ImageUrlData, ProviderKind, Role, StreamChunk};
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    /// Records what actually reached the backend.
+    #[derive(Default)]
+    struct Seen {
+        tools: usize,
+        messages: Vec<Message>,
+    }
+
+    struct RecordingProvider(Arc<Mutex<Seen>>);
+
+    #[async_trait]
+    impl AiProvider for RecordingProvider {
+        fn name(&self) -> &str {
+            "recording"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Ollama
+        }
+        async fn chat_stream(
+            &self,
+            messages: &[Message],
+            tools: &[ToolDefinition],
+            _model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            let mut seen = self.0.lock();
+            seen.tools = tools.len();
+            seen.messages = messages.to_vec();
+            Ok(vec![])
+        }
+    }
+
+    fn tool() -> ToolDefinition {
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "exec".into(),
+                description: "Run a command".into(),
+                parameters: serde_json::json!({ "type": "object" }),
+            },
+        }
+    }
+
+    fn photo_message() -> Message {
+        Message {
+            role: Role::User,
+            content: MessageContent::Blocks(vec![
+                ContentBlock::Text {
+                    text: "What is in this photo?".into(),
+                },
+                ContentBlock::ImageUrl {
+                    image_url: ImageUrlData {
+                        url: "data:image/png;base64,iVBORw0KGgo=".into(),
+                        detail: None,
+                    },
+                },
+            ]),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+        }
+    }
+
+    async fn send(model: &str) -> Seen {
+        let seen = Arc::new(Mutex::new(Seen::default()));
+        let provider = AnyProvider::from_provider(Box::new(RecordingProvider(seen.clone())));
+        provider
+            .chat_stream(&[photo_message()], &[tool()], model, None, None)
+            .await
+            .unwrap();
+        let mut seen = seen.lock();
+        std::mem::take(&mut *seen)
+    }
+
+    #[tokio::test]
+    async fn non_tool_text_only_model_gets_a_plain_request() {
+        // gemma2 is registered without tool calling or vision
+        let seen = send("gemma2:9b").await;
+        assert_eq!(seen.tools, 0);
+        match &seen.messages[0].content {
+            MessageContent::Text(text) => {
+                assert!(text.starts_with("What is in this photo?\n"));
+                assert!(text.contains("[image omitted"));
+            }
+            other => panic!("expected text content, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn capable_and_unknown_models_are_untouched() {
+        for model in [
+            "gpt-4o",
+            "gpt-4-turbo-2024-04-09",
+            "gpt-4-vision-preview",
+            "gpt-4.5-preview",
+            "llama3.2-vision:11b",
+            "qwen2.5-vl-7b-instruct",
+            "qwen2.5vl:7b",
+            "llama4:scout",
+            "llama-4-maverick",
+            "grok-2-vision-1212",
+            "grok-4",
+            "my-custom-finetune",
+        ] {
+            let seen = send(model).await;
+            assert!(
+                matches!(&seen.messages[0].content, MessageContent::Blocks(b) if b.len() == 2),
+                "{model} lost its image"
+            );
+        }
+        for model in [
+            "llama3.2",
+            "llama3.2:3b",
+            "qwen2.5-vl-7b-instruct",
+            "grok-4",
+        ] {
+            assert_eq!(send(model).await.tools, 1, "{model} lost its tools");
+        }
+    }
+
+    #[tokio::test]
+    async fn families_match_on_tag_boundaries_only() {
+        // `gpt-4` is text-only, but `gpt-4.5` isn't part of that family
+        let seen = send("gpt-4-0613").await;
+        assert!(matches!(&seen.messages[0].content, MessageContent::Text(_)));
+        // Vendor catch-alls don't strip anything
+        let seen = send("claude-next-experimental").await;
+        assert!(matches!(
+            &seen.messages[0].content,
+            MessageContent::Blocks(_)
+        ));
+    }
+}
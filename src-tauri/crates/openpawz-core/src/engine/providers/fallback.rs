@@ -0,0 +1,335 @@
+// Paw Agent Engine — Provider fallback chain
+//
+// Wraps the primary provider together with the other configured providers.
+// The request rotates to the next provider in the chain, and stays there for
+// the rest of the run, when the active one fails with a 529 — only surfaced
+// once the provider is in sustained overload; isolated 529s are absorbed by
+// the provider's own backoff.
+//
+// Channel runs also rotate on billing, quota, auth or rate-limit errors
+// (`AnyProvider::with_billing_fallback`): nobody is at the keyboard to fix
+// the account. Chat and task runs surface those errors instead.
+// Every rotation is recorded in a small ring buffer for the diagnostics panel.
+
+use super::capabilities::fit_request_to_model;
+use super::AnyProvider;
+use crate::atoms::traits::{AiProvider, ModelInfo, ProviderError};
+use crate::engine::http::OVERLOADED_STATUS;
+use crate::engine::types::{Message, ProviderConfig, ProviderKind, StreamChunk, ToolDefinition};
+use async_trait::async_trait;
+use log::warn;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::LazyLock;
+
+/// Rotation events kept for diagnostics.
+const ROTATION_LOG_CAPACITY: usize = 100;
+
+/// One provider rotation away from a failing provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderRotationEvent {
+    pub timestamp: String,
+    pub from_provider: String,
+    pub to_provider: String,
+    pub to_model: String,
+    pub reason: String,
+}
+
+static ROTATION_LOG: LazyLock<Mutex<VecDeque<ProviderRotationEvent>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(ROTATION_LOG_CAPACITY)));
+
+fn record_rotation(event: ProviderRotationEvent) {
+    let mut log = ROTATION_LOG.lock();
+    if log.len() == ROTATION_LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(event);
+}
+
+/// Recent provider rotations, newest first.
+pub fn recent_rotation_events(limit: usize) -> Vec<ProviderRotationEvent> {
+    ROTATION_LOG
+        .lock()
+        .iter()
+        .rev()
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// A provider plus the model to use on it (`None` = the caller's model).
+struct ChainLink {
+    label: String,
+    provider: AnyProvider,
+    model: Option<String>,
+}
+
+/// Primary provider with fallbacks, tried in order.
+pub struct FallbackProvider {
+    chain: Vec<ChainLink>,
+    active: AtomicUsize,
+    /// Also rotate on billing/auth/rate-limit errors, not just overload.
+    rotate_on_billing: bool,
+}
+
+impl FallbackProvider {
+    /// `fallbacks` are `(label, provider, model)` triples in rotation order;
+    /// a `None` model keeps the caller's model.
+    pub fn new(
+        primary_label: &str,
+        primary: AnyProvider,
+        fallbacks: Vec<(String, AnyProvider, Option<String>)>,
+    ) -> Self {
+        let mut chain = vec![ChainLink {
+            label: primary_label.to_string(),
+            provider: primary,
+            model: None,
+        }];
+        chain.extend(
+            fallbacks
+                .into_iter()
+                .map(|(label, provider, model)| ChainLink {
+                    label,
+                    provider,
+                    model,
+                }),
+        );
+        Self {
+            chain,
+            active: AtomicUsize::new(0),
+            rotate_on_billing: false,
+        }
+    }
+
+    /// Also rotate on billing, quota, auth or rate-limit errors.
+    pub fn rotate_on_billing(mut self) -> Self {
+        self.rotate_on_billing = true;
+        self
+    }
+
+    fn link(&self) -> &ChainLink {
+        &self.chain[self.active.load(Ordering::SeqCst).min(self.chain.len() - 1)]
+    }
+}
+
+/// Detect billing, auth, quota, or rate-limit errors that warrant trying
+/// a different provider instead of failing outright.
+pub fn is_provider_billing_error(err: &str) -> bool {
+    let lower = err.to_lowercase();
+    lower.contains("credit balance")
+        || lower.contains("insufficient_quota")
+        || lower.contains("billing")
+        || lower.contains("rate_limit")
+        || lower.contains("quota exceeded")
+        || lower.contains("payment required")
+        || lower.contains("account")
+        || (lower.contains("api error 4")
+            && (lower.contains("401")
+                || lower.contains("402")
+                || lower.contains("403")
+                || lower.contains("429")))
+}
+
+fn is_overloaded(err: &ProviderError) -> bool {
+    matches!(err, ProviderError::Api { status, .. } if *status == OVERLOADED_STATUS)
+}
+
+impl FallbackProvider {
+    /// Whether `err` should move the request to the next provider.
+    fn should_rotate(&self, err: &ProviderError) -> bool {
+        is_overloaded(err)
+            || (self.rotate_on_billing && is_provider_billing_error(&err.to_string()))
+    }
+}
+
+#[async_trait]
+impl AiProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        self.link().provider.0.name()
+    }
+
+    fn kind(&self) -> ProviderKind {
+        self.link().provider.kind()
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        model: &str,
+        temperature: Option<f64>,
+        thinking_level: Option<&str>,
+    ) -> Result<Vec<StreamChunk>, ProviderError> {
+        loop {
+            let idx = self.active.load(Ordering::SeqCst);
+            let link = &self.chain[idx];
+            let link_model = link.model.as_deref().unwrap_or(model);
+            // Fallback models may lack tools/vision the primary had
+            let (messages, tools) = fit_request_to_model(messages, tools, link_model);
+            let result = link
+                .provider
+                .0
+                .chat_stream(&messages, tools, link_model, temperature, thinking_level)
+                .await;
+            match result {
+                Err(e) if self.should_rotate(&e) && idx + 1 < self.chain.len() => {
+                    let next = &self.chain[idx + 1];
+                    let next_model = next.model.as_deref().unwrap_or(model);
+                    warn!(
+                        "[engine] {} failed ({}) — rotating to {} / {}",
+                        link.label, e, next.label, next_model
+                    );
+                    record_rotation(ProviderRotationEvent {
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        from_provider: link.label.clone(),
+                        to_provider: next.label.clone(),
+                        to_model: next_model.to_string(),
+                        reason: e.to_string(),
+                    });
+                    self.active.store(idx + 1, Ordering::SeqCst);
+                }
+                other => return other,
+            }
+        }
+    }
+
+    async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, ProviderError> {
+        self.link().provider.0.embed(texts, model).await
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, ProviderError> {
+        self.link().provider.0.list_models().await
+    }
+}
+
+impl AnyProvider {
+    /// Build the provider for `primary`, rotating to the other configured
+    /// providers on sustained 529 overload. Fallbacks run on their default
+    /// model, or the caller's model when they have none.
+    pub fn with_fallback(primary: &ProviderConfig, all: &[ProviderConfig]) -> Self {
+        Self::fallback_chain(primary, all, false)
+    }
+
+    /// Like [`AnyProvider::with_fallback`], but billing, quota, auth and
+    /// rate-limit errors rotate too. For unattended (channel) runs.
+    pub fn with_billing_fallback(primary: &ProviderConfig, all: &[ProviderConfig]) -> Self {
+        Self::fallback_chain(primary, all, true)
+    }
+
+    fn fallback_chain(
+        primary: &ProviderConfig,
+        all: &[ProviderConfig],
+        rotate_on_billing: bool,
+    ) -> Self {
+        let fallbacks: Vec<(String, AnyProvider, Option<String>)> = all
+            .iter()
+            .filter(|p| p.id != primary.id)
+            .map(|p| {
+                (
+                    p.id.clone(),
+                    AnyProvider::from_config(p),
+                    p.default_model.clone(),
+                )
+            })
+            .collect();
+        let provider = AnyProvider::from_config(primary);
+        if fallbacks.is_empty() {
+            return provider;
+        }
+        let chain = FallbackProvider::new(&primary.id, provider, fallbacks);
+        AnyProvider::from_provider(Box::new(if rotate_on_billing {
+            chain.rotate_on_billing()
+        } else {
+            chain
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::http::record_overload;
+    use crate::engine::test_support::{serve, MockReply, Requests};
+
+    async fn mock_anthropic(id: &str, status: u16, body: &str) -> (ProviderConfig, Requests) {
+        let reply = MockReply::json(status, body).header("retry-after", "0");
+        let (url, requests) = serve(reply).await;
+        let config = ProviderConfig {
+            id: id.into(),
+            kind: ProviderKind::Anthropic,
+            api_key: "sk-ant-test".into(),
+            base_url: Some(url),
+            default_model: Some(format!("{}-model", id)),
+            api_version: None,
+            deployments: Default::default(),
+        };
+        (config, requests)
+    }
+
+    #[tokio::test]
+    async fn three_consecutive_529s_rotate_to_fallback() {
+        let (primary, primary_hits) = mock_anthropic(
+            "anthropic",
+            529,
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        )
+        .await;
+        let (backup, backup_hits) = mock_anthropic("foundry", 200, "").await;
+
+        let provider = AnyProvider::with_fallback(&primary, &[primary.clone(), backup]);
+        let result = provider
+            .chat_stream(&[], &[], "claude-sonnet-4", None, None)
+            .await;
+        assert!(result.is_ok(), "{:?}", result.err());
+        // The real provider gave up after the third 529 instead of
+        // exhausting its retries, and the chain moved on
+        assert_eq!(primary_hits.lock().len(), 3);
+        assert_eq!(backup_hits.lock().len(), 1);
+        let event = recent_rotation_events(ROTATION_LOG_CAPACITY)
+            .into_iter()
+            .find(|e| e.from_provider == "anthropic" && e.to_provider == "foundry");
+        assert_eq!(event.map(|e| e.to_model), Some("foundry-model".to_string()));
+    }
+
+    #[tokio::test]
+    async fn billing_error_rotates_only_when_opted_in() {
+        let (primary, primary_hits) = mock_anthropic(
+            "anthropic-billing",
+            400,
+            r#"{"type":"error","error":{"type":"invalid_request_error","message":"Your credit balance is too low"}}"#,
+        )
+        .await;
+        let (backup, backup_hits) = mock_anthropic("foundry-billing", 200, "").await;
+        let all = [primary.clone(), backup];
+
+        // Chat and task runs surface the error to the user
+        let provider = AnyProvider::with_fallback(&primary, &all);
+        let result = provider
+            .chat_stream(&[], &[], "claude-sonnet-4", None, None)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(primary_hits.lock().len(), 1);
+        assert_eq!(backup_hits.lock().len(), 0);
+
+        // Channel runs move on to the next provider
+        let provider = AnyProvider::with_billing_fallback(&primary, &all);
+        let result = provider
+            .chat_stream(&[], &[], "claude-sonnet-4", None, None)
+            .await;
+        assert!(result.is_ok(), "{:?}", result.err());
+        assert_eq!(primary_hits.lock().len(), 2);
+        assert_eq!(backup_hits.lock().len(), 1);
+    }
+
+    #[test]
+    fn isolated_529_does_not_trigger_rotation() {
+        let key = format!("test-{}", uuid::Uuid::new_v4());
+        assert!(!record_overload(&key));
+        crate::engine::http::clear_overload(&key);
+        assert!(!record_overload(&key));
+        assert!(!record_overload(&key));
+        assert!(record_overload(&key));
+    }
+}
@@ -326,22 +326,14 @@ impl GoogleProvider {
         }])
     }
 
-    /// Inner implementation with full SSE + retry logic + error classification.
-    async fn chat_stream_inner(
-        &self,
+    /// Build the `streamGenerateContent` request body.
+    fn build_request(
         messages: &[Message],
         tools: &[ToolDefinition],
         model: &str,
         temperature: Option<f64>,
         thinking_level: Option<&str>,
-    ) -> Result<Vec<StreamChunk>, ProviderError> {
-        let url = format!(
-            "{}/models/{}:streamGenerateContent?alt=sse&key={}",
-            self.base_url.trim_end_matches('/'),
-            model,
-            self.api_key.as_str()
-        );
-
+    ) -> Value {
         let (system_instruction, mut contents) = Self::format_messages(messages);
 
         // Guard: Gemini requires at least one content entry.
@@ -458,6 +450,232 @@ impl GoogleProvider {
                 );
             }
         }
+        body
+    }
+
+    /// Parse one SSE `data:` event into stream chunks: text, thought parts,
+    /// `functionCall` parts (as tool call deltas), blocked-response notices
+    /// and usage.
+    fn parse_sse_event(v: &Value) -> Vec<StreamChunk> {
+        let mut chunks = Vec::new();
+        // Extract actual model version from Google's response
+        let api_model = v["modelVersion"].as_str().map(|s| s.to_string());
+
+        // Parse Google's streaming format
+        let mut fc_index_counter: usize = 0; // unique index per function call
+        if let Some(candidates) = v["candidates"].as_array() {
+            for candidate in candidates {
+                let content = &candidate["content"];
+                let finish_reason = candidate["finishReason"].as_str().map(|s| s.to_string());
+
+                // Detect blocked/empty responses (e.g. SAFETY, RECITATION, OTHER)
+                // Also check for empty parts array [] — not just null
+                let parts_empty = content.is_null()
+                    || content["parts"].is_null()
+                    || content["parts"]
+                        .as_array()
+                        .map(|a| a.is_empty())
+                        .unwrap_or(false);
+                if parts_empty {
+                    if let Some(ref reason) = finish_reason {
+                        // Log ALL empty-content responses, including STOP
+                        let safety_info = candidate
+                            .get("safetyRatings")
+                            .map(|r| r.to_string())
+                            .unwrap_or_else(|| "none".to_string());
+                        warn!(
+                            "[engine] Google: empty content chunk — finishReason={} safety={}",
+                            reason,
+                            safe_truncate(&safety_info, 500)
+                        );
+                        if reason != "STOP" {
+                            // Emit a visible error chunk so the agent loop can surface it
+                            let msg = match reason.as_str() {
+                                "SAFETY" => "My response was blocked by Google's safety filter. Try rephrasing your request.".to_string(),
+                                "RECITATION" => "My response was blocked by a recitation filter. Try rephrasing.".to_string(),
+                                "MAX_TOKENS" => "I ran out of output tokens. Try shortening the conversation or compacting the session.".to_string(),
+                                "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII" =>
+                                    format!("Response blocked ({reason}). Try rephrasing your request."),
+                                "MALFORMED_FUNCTION_CALL" => {
+                                    warn!("[engine] Google: MALFORMED_FUNCTION_CALL — model produced invalid tool call JSON");
+                                    "[MALFORMED_TOOL_CALL] The model tried to call a tool but produced invalid JSON. \
+                                    Simplify the call — pass body as a JSON object, not an escaped string.".to_string()
+                                }
+                                other => format!(
+                                    "The model returned an empty response (reason: {other}). Please retry or rephrase."
+                                ),
+                            };
+                            chunks.push(StreamChunk {
+                                delta_text: Some(msg),
+                                tool_calls: vec![],
+                                finish_reason: finish_reason.clone(),
+                                usage: None,
+                                model: api_model.clone(),
+                                thought_parts: vec![],
+                                thinking_text: None,
+                            });
+                        }
+                    }
+                    continue;
+                }
+
+                if let Some(parts) = content["parts"].as_array() {
+                    // First pass: collect thought parts (only those with "thought": true).
+                    // Note: thoughtSignature alone does NOT indicate thinking — it's a
+                    // round-trip signature. We still collect it for API replay.
+                    let mut collected_thoughts: Vec<ThoughtPart> = Vec::new();
+                    for part in parts {
+                        if part
+                            .get("thought")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false)
+                        {
+                            if let Some(text) = part["text"].as_str() {
+                                let sig = part
+                                    .get("thoughtSignature")
+                                    .or_else(|| part.get("thought_signature"))
+                                    .and_then(|v| v.as_str());
+                                // Emit thinking text to the frontend
+                                chunks.push(StreamChunk {
+                                    delta_text: None,
+                                    tool_calls: vec![],
+                                    finish_reason: None,
+                                    usage: None,
+                                    model: api_model.clone(),
+                                    thought_parts: vec![],
+                                    thinking_text: Some(text.to_string()),
+                                });
+                                info!(
+                                    "[engine] Google: thought part detected (len={})",
+                                    text.len()
+                                );
+                                if let Some(s) = sig {
+                                    collected_thoughts.push(ThoughtPart {
+                                        text: text.to_string(),
+                                        thought_signature: s.to_string(),
+                                    });
+                                }
+                            }
+                        }
+                    }
+
+                    // Second pass: process text and functionCall parts
+                    for part in parts {
+                        // Skip thought parts (already collected above)
+                        if part
+                            .get("thought")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false)
+                        {
+                            continue;
+                        }
+                        // Collect thoughtSignature from text parts for round-tripping
+                        if let Some(text) = part["text"].as_str() {
+                            let sig = part
+                                .get("thoughtSignature")
+                                .or_else(|| part.get("thought_signature"))
+                                .and_then(|v| v.as_str());
+                            if let Some(s) = sig {
+                                collected_thoughts.push(ThoughtPart {
+                                    text: text.to_string(),
+                                    thought_signature: s.to_string(),
+                                });
+                            }
+                            chunks.push(StreamChunk {
+                                delta_text: Some(text.to_string()),
+                                tool_calls: vec![],
+                                finish_reason: finish_reason.clone(),
+                                usage: None,
+                                model: api_model.clone(),
+                                thought_parts: vec![],
+                                thinking_text: None,
+                            });
+                        }
+                        if let Some(fc) = part.get("functionCall") {
+                            let name = fc["name"].as_str().unwrap_or("").to_string();
+                            let args = fc["args"].clone();
+                            // thought_signature can be at the part level OR inside functionCall
+                            let thought_sig = part
+                                .get("thoughtSignature")
+                                .or_else(|| part.get("thought_signature"))
+                                .or_else(|| fc.get("thoughtSignature"))
+                                .or_else(|| fc.get("thought_signature"))
+                                .and_then(|v| v.as_str())
+                                .map(|s| s.to_string());
+                            if thought_sig.is_some() {
+                                info!("[engine] Google: captured thoughtSignature for fn={}", name);
+                            } else {
+                                warn!("[engine] Google: NO thoughtSignature found for fn={} (part keys: {:?})", name, part.as_object().map(|o| o.keys().collect::<Vec<_>>()));
+                            }
+                            let fc_idx = fc_index_counter;
+                            fc_index_counter += 1;
+                            chunks.push(StreamChunk {
+                                delta_text: None,
+                                tool_calls: vec![ToolCallDelta {
+                                    index: fc_idx,
+                                    id: Some(format!("call_{}", uuid::Uuid::new_v4())),
+                                    function_name: Some(name),
+                                    arguments_delta: Some(
+                                        serde_json::to_string(&args).unwrap_or_default(),
+                                    ),
+                                    thought_signature: thought_sig,
+                                }],
+                                finish_reason: finish_reason.clone(),
+                                usage: None,
+                                model: api_model.clone(),
+                                // Attach thought parts to the first functionCall chunk
+                                thought_parts: collected_thoughts.clone(),
+                                thinking_text: None,
+                            });
+                            // Only attach thoughts to first function call chunk
+                            collected_thoughts.clear();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Gemini reports usage in usageMetadata
+        if let Some(um) = v.get("usageMetadata") {
+            let input = um["promptTokenCount"].as_u64().unwrap_or(0);
+            let output = um["candidatesTokenCount"].as_u64().unwrap_or(0);
+            if input > 0 || output > 0 {
+                chunks.push(StreamChunk {
+                    delta_text: None,
+                    tool_calls: vec![],
+                    finish_reason: None,
+                    usage: Some(TokenUsage {
+                        input_tokens: input,
+                        output_tokens: output,
+                        total_tokens: um["totalTokenCount"].as_u64().unwrap_or(input + output),
+                        ..Default::default()
+                    }),
+                    model: api_model.clone(),
+                    thought_parts: vec![],
+                    thinking_text: None,
+                });
+            }
+        }
+        chunks
+    }
+
+    /// Inner implementation with full SSE + retry logic + error classification.
+    async fn chat_stream_inner(
+        &self,
+        messages: &[Message],
+        tools: &[ToolDefinition],
+        model: &str,
+        temperature: Option<f64>,
+        thinking_level: Option<&str>,
+    ) -> Result<Vec<StreamChunk>, ProviderError> {
+        let url = format!(
+            "{}/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.base_url.trim_end_matches('/'),
+            model,
+            self.api_key.as_str()
+        );
+
+        let body = Self::build_request(messages, tools, model, temperature, thinking_level);
         info!("[engine] Google request model={}", model);
 
         // Circuit breaker: reject immediately if too many recent failures
@@ -565,214 +783,12 @@ impl GoogleProvider {
                         } else {
                             log::debug!(
                                 "[engine] Google SSE: {}... ({}b)",
-                                &data[..500],
+                                safe_truncate(data, 500),
                                 data.len()
                             );
                         }
                         if let Ok(v) = serde_json::from_str::<Value>(data) {
-                            // Extract actual model version from Google's response
-                            let api_model = v["modelVersion"].as_str().map(|s| s.to_string());
-
-                            // Parse Google's streaming format
-                            let mut fc_index_counter: usize = 0; // unique index per function call
-                            if let Some(candidates) = v["candidates"].as_array() {
-                                for candidate in candidates {
-                                    let content = &candidate["content"];
-                                    let finish_reason =
-                                        candidate["finishReason"].as_str().map(|s| s.to_string());
-
-                                    // Detect blocked/empty responses (e.g. SAFETY, RECITATION, OTHER)
-                                    // Also check for empty parts array [] — not just null
-                                    let parts_empty = content.is_null()
-                                        || content["parts"].is_null()
-                                        || content["parts"]
-                                            .as_array()
-                                            .map(|a| a.is_empty())
-                                            .unwrap_or(false);
-                                    if parts_empty {
-                                        if let Some(ref reason) = finish_reason {
-                                            // Log ALL empty-content responses, including STOP
-                                            let safety_info = candidate
-                                                .get("safetyRatings")
-                                                .map(|r| r.to_string())
-                                                .unwrap_or_else(|| "none".to_string());
-                                            warn!(
-                                                "[engine] Google: empty content chunk — finishReason={} safety={}",
-                                                reason,
-                                                safe_truncate(&safety_info, 500)
-                                            );
-                                            if reason != "STOP" {
-                                                // Emit a visible error chunk so the agent loop can surface it
-                                                let msg = match reason.as_str() {
-                                                    "SAFETY" => "My response was blocked by Google's safety filter. Try rephrasing your request.".to_string(),
-                                                    "RECITATION" => "My response was blocked by a recitation filter. Try rephrasing.".to_string(),
-                                                    "MAX_TOKENS" => "I ran out of output tokens. Try shortening the conversation or compacting the session.".to_string(),
-                                                    "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII" =>
-                                                        format!("Response blocked ({reason}). Try rephrasing your request."),
-                                                    "MALFORMED_FUNCTION_CALL" => {
-                                                        warn!("[engine] Google: MALFORMED_FUNCTION_CALL — model produced invalid tool call JSON");
-                                                        "[MALFORMED_TOOL_CALL] The model tried to call a tool but produced invalid JSON. \
-                                                        Simplify the call — pass body as a JSON object, not an escaped string.".to_string()
-                                                    }
-                                                    other => format!(
-                                                        "The model returned an empty response (reason: {other}). Please retry or rephrase."
-                                                    ),
-                                                };
-                                                chunks.push(StreamChunk {
-                                                    delta_text: Some(msg),
-                                                    tool_calls: vec![],
-                                                    finish_reason: finish_reason.clone(),
-                                                    usage: None,
-                                                    model: api_model.clone(),
-                                                    thought_parts: vec![],
-                                                    thinking_text: None,
-                                                });
-                                            }
-                                        }
-                                        continue;
-                                    }
-
-                                    if let Some(parts) = content["parts"].as_array() {
-                                        // First pass: collect thought parts (only those with "thought": true).
-                                        // Note: thoughtSignature alone does NOT indicate thinking — it's a
-                                        // round-trip signature. We still collect it for API replay.
-                                        let mut collected_thoughts: Vec<ThoughtPart> = Vec::new();
-                                        for part in parts {
-                                            if part
-                                                .get("thought")
-                                                .and_then(|v| v.as_bool())
-                                                .unwrap_or(false)
-                                            {
-                                                if let Some(text) = part["text"].as_str() {
-                                                    let sig = part
-                                                        .get("thoughtSignature")
-                                                        .or_else(|| part.get("thought_signature"))
-                                                        .and_then(|v| v.as_str());
-                                                    // Emit thinking text to the frontend
-                                                    chunks.push(StreamChunk {
-                                                        delta_text: None,
-                                                        tool_calls: vec![],
-                                                        finish_reason: None,
-                                                        usage: None,
-                                                        model: api_model.clone(),
-                                                        thought_parts: vec![],
-                                                        thinking_text: Some(text.to_string()),
-                                                    });
-                                                    info!("[engine] Google: thought part detected (len={})", text.len());
-                                                    if let Some(s) = sig {
-                                                        collected_thoughts.push(ThoughtPart {
-                                                            text: text.to_string(),
-                                                            thought_signature: s.to_string(),
-                                                        });
-                                                    }
-                                                }
-                                            }
-                                        }
-
-                                        // Second pass: process text and functionCall parts
-                                        for part in parts {
-                                            // Skip thought parts (already collected above)
-                                            if part
-                                                .get("thought")
-                                                .and_then(|v| v.as_bool())
-                                                .unwrap_or(false)
-                                            {
-                                                continue;
-                                            }
-                                            // Collect thoughtSignature from text parts for round-tripping
-                                            if let Some(text) = part["text"].as_str() {
-                                                let sig = part
-                                                    .get("thoughtSignature")
-                                                    .or_else(|| part.get("thought_signature"))
-                                                    .and_then(|v| v.as_str());
-                                                if let Some(s) = sig {
-                                                    collected_thoughts.push(ThoughtPart {
-                                                        text: text.to_string(),
-                                                        thought_signature: s.to_string(),
-                                                    });
-                                                }
-                                                chunks.push(StreamChunk {
-                                                    delta_text: Some(text.to_string()),
-                                                    tool_calls: vec![],
-                                                    finish_reason: finish_reason.clone(),
-                                                    usage: None,
-                                                    model: api_model.clone(),
-                                                    thought_parts: vec![],
-                                                    thinking_text: None,
-                                                });
-                                            }
-                                            if let Some(fc) = part.get("functionCall") {
-                                                let name =
-                                                    fc["name"].as_str().unwrap_or("").to_string();
-                                                let args = fc["args"].clone();
-                                                // thought_signature can be at the part level OR inside functionCall
-                                                let thought_sig = part
-                                                    .get("thoughtSignature")
-                                                    .or_else(|| part.get("thought_signature"))
-                                                    .or_else(|| fc.get("thoughtSignature"))
-                                                    .or_else(|| fc.get("thought_signature"))
-                                                    .and_then(|v| v.as_str())
-                                                    .map(|s| s.to_string());
-                                                if thought_sig.is_some() {
-                                                    info!("[engine] Google: captured thoughtSignature for fn={}", name);
-                                                } else {
-                                                    warn!("[engine] Google: NO thoughtSignature found for fn={} (part keys: {:?})", name, part.as_object().map(|o| o.keys().collect::<Vec<_>>()));
-                                                }
-                                                let fc_idx = fc_index_counter;
-                                                fc_index_counter += 1;
-                                                chunks.push(StreamChunk {
-                                                    delta_text: None,
-                                                    tool_calls: vec![ToolCallDelta {
-                                                        index: fc_idx,
-                                                        id: Some(format!(
-                                                            "call_{}",
-                                                            uuid::Uuid::new_v4()
-                                                        )),
-                                                        function_name: Some(name),
-                                                        arguments_delta: Some(
-                                                            serde_json::to_string(&args)
-                                                                .unwrap_or_default(),
-                                                        ),
-                                                        thought_signature: thought_sig,
-                                                    }],
-                                                    finish_reason: finish_reason.clone(),
-                                                    usage: None,
-                                                    model: api_model.clone(),
-                                                    // Attach thought parts to the first functionCall chunk
-                                                    thought_parts: collected_thoughts.clone(),
-                                                    thinking_text: None,
-                                                });
-                                                // Only attach thoughts to first function call chunk
-                                                collected_thoughts.clear();
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            // Gemini reports usage in usageMetadata
-                            if let Some(um) = v.get("usageMetadata") {
-                                let input = um["promptTokenCount"].as_u64().unwrap_or(0);
-                                let output = um["candidatesTokenCount"].as_u64().unwrap_or(0);
-                                if input > 0 || output > 0 {
-                                    chunks.push(StreamChunk {
-                                        delta_text: None,
-                                        tool_calls: vec![],
-                                        finish_reason: None,
-                                        usage: Some(TokenUsage {
-                                            input_tokens: input,
-                                            output_tokens: output,
-                                            total_tokens: um["totalTokenCount"]
-                                                .as_u64()
-                                                .unwrap_or(input + output),
-                                            ..Default::default()
-                                        }),
-                                        model: api_model.clone(),
-                                        thought_parts: vec![],
-                                        thinking_text: None,
-                                    });
-                                }
-                            }
+                            chunks.extend(Self::parse_sse_event(&v));
                         }
                     }
                 }
@@ -821,3 +837,137 @@ impl AiProvider for GoogleProvider {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(role: Role, text: &str) -> Message {
+        Message {
+            role,
+            content: MessageContent::Text(text.into()),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+        }
+    }
+
+    fn weather_tool() -> ToolDefinition {
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "get_weather".into(),
+                description: "Current weather for a city".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"],
+                    "additionalProperties": false
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn tool_request_uses_gemini_schema() {
+        let mut call = text(Role::Assistant, "");
+        call.tool_calls = Some(vec![ToolCall {
+            id: "call_1".into(),
+            call_type: "function".into(),
+            function: FunctionCall {
+                name: "get_weather".into(),
+                arguments: r#"{"city":"Oslo"}"#.into(),
+            },
+            thought_signature: Some("sig-1".into()),
+            thought_parts: vec![],
+        }]);
+        let mut result = text(Role::Tool, "4°C, light snow");
+        result.tool_call_id = Some("call_1".into());
+        result.name = Some("get_weather".into());
+        let messages = [
+            text(Role::System, "You are helpful."),
+            text(Role::User, "Weather in Oslo?"),
+            call,
+            result,
+        ];
+
+        let body = GoogleProvider::build_request(
+            &messages,
+            &[weather_tool()],
+            "gemini-2.0-flash",
+            None,
+            None,
+        );
+
+        assert_eq!(
+            body["systemInstruction"]["parts"][0]["text"],
+            "You are helpful."
+        );
+        let contents = body["contents"].as_array().unwrap();
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(contents[0]["parts"][0]["text"], "Weather in Oslo?");
+        assert_eq!(contents[1]["role"], "model");
+        let fc = &contents[1]["parts"][0];
+        assert_eq!(fc["functionCall"]["name"], "get_weather");
+        assert_eq!(fc["functionCall"]["args"]["city"], "Oslo");
+        assert_eq!(fc["thoughtSignature"], "sig-1");
+        let response = &contents[2]["parts"][0]["functionResponse"];
+        assert_eq!(response["name"], "get_weather");
+        assert_eq!(response["response"]["result"], "4°C, light snow");
+
+        let declaration = &body["tools"][0]["functionDeclarations"][0];
+        assert_eq!(declaration["name"], "get_weather");
+        assert_eq!(declaration["parameters"]["required"][0], "city");
+        // Gemini rejects JSON-Schema keywords it doesn't know
+        assert!(declaration["parameters"]
+            .get("additionalProperties")
+            .is_none());
+    }
+
+    #[test]
+    fn function_call_event_becomes_tool_call() {
+        let event = json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        { "text": "Checking." },
+                        {
+                            "functionCall": { "name": "get_weather", "args": { "city": "Oslo" } },
+                            "thoughtSignature": "sig-2"
+                        }
+                    ]
+                },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 40,
+                "candidatesTokenCount": 12,
+                "totalTokenCount": 52
+            },
+            "modelVersion": "gemini-2.0-flash-001"
+        });
+
+        let chunks = GoogleProvider::parse_sse_event(&event);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].delta_text.as_deref(), Some("Checking."));
+
+        let call = &chunks[1].tool_calls[0];
+        assert_eq!(call.index, 0);
+        assert!(call.id.as_deref().is_some_and(|id| id.starts_with("call_")));
+        assert_eq!(call.function_name.as_deref(), Some("get_weather"));
+        let args: Value = serde_json::from_str(call.arguments_delta.as_deref().unwrap()).unwrap();
+        assert_eq!(args, json!({ "city": "Oslo" }));
+        assert_eq!(call.thought_signature.as_deref(), Some("sig-2"));
+        assert_eq!(chunks[1].model.as_deref(), Some("gemini-2.0-flash-001"));
+
+        let usage = chunks[2].usage.as_ref().unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (40, 12));
+    }
+
+    #[test]
+    fn gemini_is_an_alias_for_google() {
+        let kind: ProviderKind = serde_json::from_str("\"gemini\"").unwrap();
+        assert_eq!(kind, ProviderKind::Google);
+    }
+}
@@ -3,16 +3,41 @@
 // never requires modifying the factory enum — just implement the trait.
 
 pub mod anthropic;
+pub mod azure_openai;
+mod capabilities;
+pub mod fallback;
 pub mod google;
 pub mod openai;
 
 pub use anthropic::AnthropicProvider;
+pub use fallback::{
+    is_provider_billing_error, recent_rotation_events, FallbackProvider, ProviderRotationEvent,
+};
 pub use google::GoogleProvider;
 pub use openai::OpenAiProvider;
 
 use crate::atoms::error::EngineResult;
-use crate::atoms::traits::{AiProvider, ModelInfo};
-use crate::engine::types::{Message, ProviderConfig, ProviderKind, StreamChunk, ToolDefinition};
+use crate::atoms::traits::{AiProvider, ModelInfo, ProviderError, ProviderFailure};
+use crate::engine::types::{
+    Message, MessageContent, ProviderConfig, ProviderKind, Role, StreamChunk, ToolDefinition,
+};
+use capabilities::fit_request_to_model;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Upper bound for a connection test, provider retries included.
+const TEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Outcome of [`AnyProvider::test_connection`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderTestResult {
+    pub ok: bool,
+    /// Why the test failed; None on success.
+    pub reason: Option<ProviderFailure>,
+    pub message: String,
+    /// Models the provider listed, when it supports listing.
+    pub models: Option<usize>,
+}
 
 // ── Provider factory ───────────────────────────────────────────────────────────
 
@@ -55,9 +80,15 @@ impl AnyProvider {
         AnyProvider(provider)
     }
 
+    /// Wrap an already-constructed provider (custom backends, test doubles).
+    pub fn from_provider(provider: Box<dyn AiProvider>) -> Self {
+        AnyProvider(provider)
+    }
+
     /// Chat completion with SSE streaming.
     /// Returns `Err(String)` so existing callers in agent_loop.rs / commands.rs
-    /// need zero changes.
+    /// need zero changes.  Tools and images the model can't take are dropped
+    /// first (see `capabilities`).
     pub async fn chat_stream(
         &self,
         messages: &[Message],
@@ -66,8 +97,9 @@ impl AnyProvider {
         temperature: Option<f64>,
         thinking_level: Option<&str>,
     ) -> EngineResult<Vec<StreamChunk>> {
+        let (messages, tools) = fit_request_to_model(messages, tools, model);
         self.0
-            .chat_stream(messages, tools, model, temperature, thinking_level)
+            .chat_stream(&messages, tools, model, temperature, thinking_level)
             .await
             .map_err(|e| crate::atoms::error::EngineError::Other(e.to_string()))
     }
@@ -84,4 +116,208 @@ impl AnyProvider {
             .await
             .map_err(|e| crate::atoms::error::EngineError::Other(e.to_string()))
     }
+
+    /// Check the key and endpoint with the cheapest authenticated call:
+    /// the model list, or a one-word chat with `model` where the provider
+    /// can't list models. Failures are classified so settings can tell a
+    /// bad key from a network problem.
+    pub async fn test_connection(&self, model: Option<&str>) -> ProviderTestResult {
+        let outcome = tokio::time::timeout(TEST_TIMEOUT, self.probe(model))
+            .await
+            .unwrap_or_else(|_| Err(ProviderError::Transport("request timed out".into())));
+        match outcome {
+            Ok(models) => ProviderTestResult {
+                ok: true,
+                reason: None,
+                message: match models {
+                    Some(n) => format!("Connected — {} model(s) available", n),
+                    None => "Connected".to_string(),
+                },
+                models,
+            },
+            Err(e) => {
+                let reason = e.failure();
+                ProviderTestResult {
+                    ok: false,
+                    reason: Some(reason),
+                    message: format!("{} ({})", reason.hint(), e),
+                    models: None,
+                }
+            }
+        }
+    }
+
+    async fn probe(&self, model: Option<&str>) -> Result<Option<usize>, ProviderError> {
+        match self.0.list_models().await {
+            Ok(models) => return Ok(Some(models.len())),
+            Err(ProviderError::Unsupported(_)) => {}
+            Err(e) => return Err(e),
+        }
+        let model = model.filter(|m| !m.is_empty()).ok_or_else(|| {
+            ProviderError::ModelNotFound("set a default model to test with".into())
+        })?;
+        let ping = Message {
+            role: Role::User,
+            content: MessageContent::Text("ping".into()),
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+        };
+        self.0.chat_stream(&[ping], &[], model, None, None).await?;
+        Ok(None)
+    }
+
+    /// Embed `texts` with `model` (defaults to the provider kind's
+    /// embedding model).
+    pub async fn embed(
+        &self,
+        texts: &[String],
+        model: Option<&str>,
+    ) -> EngineResult<Vec<Vec<f32>>> {
+        let kind = self.0.kind();
+        let model = model.unwrap_or_else(|| kind.default_embedding_model());
+        self.0
+            .embed(texts, model)
+            .await
+            .map_err(|e| crate::atoms::error::EngineError::Other(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    /// Minimal backend: only what the trait requires, plus embeddings.
+    struct EmbeddingMock {
+        models: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl AiProvider for EmbeddingMock {
+        fn name(&self) -> &str {
+            "embedding-mock"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Mistral
+        }
+        async fn chat_stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            Ok(vec![])
+        }
+        async fn embed(
+            &self,
+            texts: &[String],
+            model: &str,
+        ) -> Result<Vec<Vec<f32>>, ProviderError> {
+            self.models.lock().push(model.to_string());
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+    }
+
+    fn config(kind: ProviderKind) -> ProviderConfig {
+        ProviderConfig {
+            id: "test".into(),
+            kind,
+            api_key: "sk-test".into(),
+            base_url: None,
+            default_model: None,
+            api_version: None,
+            deployments: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_provider_embeds_through_any_provider() {
+        let models = Arc::new(Mutex::new(vec![]));
+        let provider = AnyProvider::from_provider(Box::new(EmbeddingMock {
+            models: models.clone(),
+        }));
+
+        let texts = vec!["hi".to_string(), "hello".to_string()];
+        let vectors = provider.embed(&texts, None).await.unwrap();
+        assert_eq!(vectors, vec![vec![2.0], vec![5.0]]);
+        provider.embed(&texts, Some("custom-embed")).await.unwrap();
+        // No model given — the provider kind's default is used
+        assert_eq!(*models.lock(), vec!["mistral-embed", "custom-embed"]);
+    }
+
+    /// Backend whose every call fails with the given error.
+    struct FailingMock(fn() -> ProviderError);
+
+    #[async_trait]
+    impl AiProvider for FailingMock {
+        fn name(&self) -> &str {
+            "failing-mock"
+        }
+        fn kind(&self) -> ProviderKind {
+            ProviderKind::Custom
+        }
+        async fn chat_stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolDefinition],
+            _model: &str,
+            _temperature: Option<f64>,
+            _thinking_level: Option<&str>,
+        ) -> Result<Vec<StreamChunk>, ProviderError> {
+            Err((self.0)())
+        }
+        async fn list_models(&self) -> Result<Vec<ModelInfo>, ProviderError> {
+            Err((self.0)())
+        }
+    }
+
+    #[tokio::test]
+    async fn unauthorized_is_reported_as_invalid_key() {
+        let provider = AnyProvider::from_provider(Box::new(FailingMock(|| ProviderError::Api {
+            status: 401,
+            message: "list_models error: invalid api key".into(),
+        })));
+        let result = provider.test_connection(Some("gpt-4o")).await;
+        assert!(!result.ok);
+        assert_eq!(result.reason, Some(ProviderFailure::InvalidKey));
+        assert!(result.message.starts_with("Invalid API key"));
+    }
+
+    #[tokio::test]
+    async fn connection_refused_is_reported_as_connectivity() {
+        // Nothing listens on port 1
+        let mut cfg = config(ProviderKind::OpenAI);
+        cfg.base_url = Some("http://127.0.0.1:1/v1".into());
+        let result = AnyProvider::from_config(&cfg).test_connection(None).await;
+        assert!(!result.ok);
+        assert_eq!(result.reason, Some(ProviderFailure::Connectivity));
+    }
+
+    #[test]
+    fn provider_errors_map_to_failures() {
+        let api = |status| ProviderError::Api {
+            status,
+            message: String::new(),
+        };
+        assert_eq!(api(403).failure(), ProviderFailure::InvalidKey);
+        assert_eq!(api(404).failure(), ProviderFailure::NotFound);
+        assert_eq!(api(500).failure(), ProviderFailure::Upstream);
+        assert_eq!(
+            ProviderError::ModelNotFound("x".into()).failure(),
+            ProviderFailure::NotFound
+        );
+        assert_eq!(
+            ProviderError::RateLimited {
+                message: String::new(),
+                retry_after_secs: None
+            }
+            .failure(),
+            ProviderFailure::Upstream
+        );
+    }
 }
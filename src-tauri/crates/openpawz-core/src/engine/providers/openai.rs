@@ -2,6 +2,7 @@
 // Handles: OpenAI, OpenRouter, Ollama, Azure OpenAI, and any OpenAI-compatible REST API.
 // Implements the AiProvider Golden Trait.
 
+use super::azure_openai::AzureOpenAiRouting;
 use crate::atoms::traits::{AiProvider, ModelInfo, ProviderError};
 use crate::engine::types::{
     ContentBlock, Message, MessageContent, ProviderConfig, ProviderKind, Role, StreamChunk,
@@ -71,6 +72,36 @@ fn extract_invalid_schema_tool(body: &str) -> Option<String> {
     }
 }
 
+/// Vectors from an OpenAI `/embeddings` response, in input order.
+fn parse_embeddings(body: &Value, expected: usize) -> Result<Vec<Vec<f32>>, ProviderError> {
+    let invalid = |msg: &str| ProviderError::Transport(format!("embed response: {}", msg));
+    let mut items: Vec<(u64, Vec<f32>)> = body["data"]
+        .as_array()
+        .ok_or_else(|| invalid("missing data"))?
+        .iter()
+        .enumerate()
+        .map(|(pos, item)| {
+            let vector = item["embedding"]
+                .as_array()
+                .ok_or_else(|| invalid("missing embedding"))?
+                .iter()
+                .map(|v| v.as_f64().map(|f| f as f32))
+                .collect::<Option<Vec<f32>>>()
+                .ok_or_else(|| invalid("non-numeric embedding"))?;
+            Ok((item["index"].as_u64().unwrap_or(pos as u64), vector))
+        })
+        .collect::<Result<_, ProviderError>>()?;
+    if items.len() != expected {
+        return Err(invalid(&format!(
+            "expected {} embeddings, got {}",
+            expected,
+            items.len()
+        )));
+    }
+    items.sort_by_key(|(index, _)| *index);
+    Ok(items.into_iter().map(|(_, vector)| vector).collect())
+}
+
 // ── OpenAI provider struct ─────────────────────────────────────────────────
 
 pub struct OpenAiProvider {
@@ -87,6 +118,8 @@ pub struct OpenAiProvider {
     /// True when the endpoint uses the OpenAI Responses API format
     /// (e.g. Azure AI Foundry o3-pro at /openai/responses).
     is_responses_api: bool,
+    /// Deployment routing for `ProviderKind::AzureOpenAI`.
+    azure_openai: Option<AzureOpenAiRouting>,
 }
 
 impl OpenAiProvider {
@@ -167,7 +200,15 @@ impl OpenAiProvider {
             }
         }
 
-        let is_azure = base_url.contains(".azure.com");
+        // Azure OpenAI: URLs are built per request from the deployment the
+        // model maps to, so only the resource endpoint is kept here.
+        let azure_openai = (config.kind == ProviderKind::AzureOpenAI)
+            .then(|| AzureOpenAiRouting::from_config(config));
+        if let Some(routing) = &azure_openai {
+            base_url = routing.endpoint.clone();
+        }
+
+        let is_azure = base_url.contains(".azure.com") || azure_openai.is_some();
         let circuit = get_circuit(&base_url);
         OpenAiProvider {
             client: pinned_client(),
@@ -177,6 +218,35 @@ impl OpenAiProvider {
             provider_kind: config.kind,
             circuit,
             is_responses_api,
+            azure_openai,
+        }
+    }
+
+    /// Add the auth header: `api-key` for Azure, Bearer token otherwise.
+    fn authorize(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if self.is_azure {
+            req.header("api-key", self.api_key.as_str())
+        } else {
+            req.header("Authorization", format!("Bearer {}", self.api_key.as_str()))
+        }
+    }
+
+    /// Chat completions endpoint for `model`.
+    fn chat_url(&self, model: &str) -> String {
+        if let Some(routing) = &self.azure_openai {
+            routing.chat_url(model)
+        } else if self.is_azure {
+            if self.base_url.contains("/chat/completions") {
+                // Full endpoint URL — already normalised in constructor.
+                // Preserves the user's api-version and path.
+                self.base_url.clone()
+            } else {
+                // Legacy base URL (e.g. /models) — append path.
+                let base = self.base_url.trim_end_matches('/');
+                format!("{}/chat/completions?api-version=2025-03-01-preview", base)
+            }
+        } else {
+            format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
         }
     }
 
@@ -421,15 +491,11 @@ impl OpenAiProvider {
                 );
             }
 
-            let mut req = self
-                .client
-                .post(url)
-                .header("Content-Type", "application/json");
-            if self.is_azure {
-                req = req.header("api-key", self.api_key.as_str());
-            } else {
-                req = req.header("Authorization", format!("Bearer {}", self.api_key.as_str()));
-            }
+            let req = self.authorize(
+                self.client
+                    .post(url)
+                    .header("Content-Type", "application/json"),
+            );
 
             let body_bytes = serde_json::to_vec(&body).unwrap_or_default();
             sign_and_log_request("openai-responses", model, &body_bytes);
@@ -790,19 +856,7 @@ impl AiProvider for OpenAiProvider {
                 .await;
         }
 
-        let url = if self.is_azure {
-            if self.base_url.contains("/chat/completions") {
-                // Full endpoint URL — already normalised in constructor.
-                // Preserves the user's api-version and path.
-                self.base_url.clone()
-            } else {
-                // Legacy base URL (e.g. /models) — append path.
-                let base = self.base_url.trim_end_matches('/');
-                format!("{}/chat/completions?api-version=2025-03-01-preview", base)
-            }
-        } else {
-            format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
-        };
+        let url = self.chat_url(model);
 
         let mut body = json!({
             "model": model,
@@ -882,15 +936,11 @@ impl AiProvider for OpenAiProvider {
             }
 
             // Azure uses api-key header; everyone else uses Bearer token
-            let mut req = self
-                .client
-                .post(&url)
-                .header("Content-Type", "application/json");
-            if self.is_azure {
-                req = req.header("api-key", self.api_key.as_str());
-            } else {
-                req = req.header("Authorization", format!("Bearer {}", self.api_key.as_str()));
-            }
+            let req = self.authorize(
+                self.client
+                    .post(&url)
+                    .header("Content-Type", "application/json"),
+            );
 
             // Sign the outbound request body for tamper detection
             let body_bytes = serde_json::to_vec(&body).unwrap_or_default();
@@ -1047,13 +1097,54 @@ impl AiProvider for OpenAiProvider {
         }
     }
 
+    /// `POST /embeddings` — served by OpenAI, Ollama (`/v1`), Mistral and
+    /// most other OpenAI-compatible APIs.
+    async fn embed(&self, texts: &[String], model: &str) -> Result<Vec<Vec<f32>>, ProviderError> {
+        if self.is_responses_api || (self.is_azure && self.azure_openai.is_none()) {
+            return Err(ProviderError::Unsupported(
+                "embeddings are not routed for Azure AI Foundry endpoints".into(),
+            ));
+        }
+        let url = match &self.azure_openai {
+            Some(routing) => routing.embeddings_url(model),
+            None => format!("{}/embeddings", self.base_url.trim_end_matches('/')),
+        };
+        let response = self
+            .authorize(self.client.post(&url))
+            .json(&json!({ "model": model, "input": texts }))
+            .send()
+            .await
+            .map_err(|e| ProviderError::Transport(format!("embed request failed: {}", e)))?;
+
+        let status = response.status().as_u16();
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(match status {
+                401 | 403 => ProviderError::Auth(body),
+                404 => ProviderError::ModelNotFound(model.to_string()),
+                _ => ProviderError::Api {
+                    status,
+                    message: format!("embed error: {}", body),
+                },
+            });
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ProviderError::Transport(format!("embed parse error: {}", e)))?;
+        parse_embeddings(&body, texts.len())
+    }
+
     /// List available models from the provider.
     /// For Azure AI Foundry this calls `GET /models?api-version=…` which
     /// returns all deployed models in the resource.
     /// For Ollama this calls `GET /api/tags`.
     /// For other OpenAI-compatible APIs this calls `GET /models`.
     async fn list_models(&self) -> Result<Vec<ModelInfo>, ProviderError> {
-        let url = if self.is_azure {
+        let url = if let Some(routing) = &self.azure_openai {
+            routing.models_url()
+        } else if self.is_azure {
             // Reconstruct the models list URL from the stored endpoint.
             let base = &self.base_url;
             let api_version = base
@@ -1096,14 +1187,8 @@ impl AiProvider for OpenAiProvider {
 
         info!("[engine] Listing models from {}", url);
 
-        let mut req = self.client.get(&url);
-        if self.is_azure {
-            req = req.header("api-key", self.api_key.as_str());
-        } else {
-            req = req.header("Authorization", format!("Bearer {}", self.api_key.as_str()));
-        }
-
-        let response = req
+        let response = self
+            .authorize(self.client.get(&url))
             .send()
             .await
             .map_err(|e| ProviderError::Transport(format!("list_models request failed: {}", e)))?;
@@ -1159,3 +1244,87 @@ impl AiProvider for OpenAiProvider {
         Ok(models)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embeddings_are_returned_in_input_order() {
+        let body = json!({
+            "object": "list",
+            "data": [
+                { "object": "embedding", "index": 1, "embedding": [0.5, -0.25] },
+                { "object": "embedding", "index": 0, "embedding": [1.0, 0.0] }
+            ],
+            "model": "text-embedding-3-small"
+        });
+        assert_eq!(
+            parse_embeddings(&body, 2).unwrap(),
+            vec![vec![1.0, 0.0], vec![0.5, -0.25]]
+        );
+        assert!(parse_embeddings(&body, 3).is_err());
+        assert!(parse_embeddings(&json!({ "error": "nope" }), 1).is_err());
+    }
+
+    #[test]
+    fn reasoning_and_answer_stream_into_separate_fields() {
+        let lines = [
+            r#"{"model":"o3-mini","choices":[{"delta":{"reasoning_content":"17 is prime, "}}]}"#,
+            r#"{"model":"o3-mini","choices":[{"delta":{"reasoning":"so the next is 19."}}]}"#,
+            r#"{"model":"o3-mini","choices":[{"delta":{"content":"The next prime "}}]}"#,
+            r#"{"model":"o3-mini","choices":[{"delta":{"content":"is 19."},"finish_reason":"stop"}]}"#,
+        ];
+        let (mut reasoning, mut content) = (String::new(), String::new());
+        for line in lines {
+            let chunk = OpenAiProvider::parse_sse_chunk(line).unwrap();
+            reasoning.extend(chunk.thinking_text);
+            content.extend(chunk.delta_text);
+        }
+        assert_eq!(reasoning, "17 is prime, so the next is 19.");
+        assert_eq!(content, "The next prime is 19.");
+        assert!(OpenAiProvider::parse_sse_chunk("[DONE]").is_none());
+    }
+
+    #[test]
+    fn azure_openai_requests_use_deployment_url_and_api_key_header() {
+        let azure = ProviderConfig {
+            id: "azure".into(),
+            kind: ProviderKind::AzureOpenAI,
+            api_key: "azure-key".into(),
+            base_url: Some("https://contoso.openai.azure.com".into()),
+            default_model: Some("gpt-4o".into()),
+            api_version: Some("2024-10-21".into()),
+            deployments: [("gpt-4o".to_string(), "prod-gpt4o".to_string())].into(),
+        };
+        let provider = OpenAiProvider::new(&azure);
+        let request = provider
+            .authorize(provider.client.post(provider.chat_url("gpt-4o")))
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://contoso.openai.azure.com/openai/deployments/prod-gpt4o/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(request.headers()["api-key"], "azure-key");
+        assert!(request.headers().get("authorization").is_none());
+
+        // Plain OpenAI keeps the Bearer token and /chat/completions
+        let openai = OpenAiProvider::new(&ProviderConfig {
+            kind: ProviderKind::OpenAI,
+            base_url: None,
+            api_version: None,
+            deployments: Default::default(),
+            ..azure
+        });
+        let request = openai
+            .authorize(openai.client.post(openai.chat_url("gpt-4o")))
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert_eq!(request.headers()["authorization"], "Bearer azure-key");
+    }
+}
@@ -0,0 +1,178 @@
+// Paw Engine — Reminders
+//
+// "Remind me to…" support: parse when a reminder should fire and decide
+// which stored reminders are due. Reminders live in the `reminders` table
+// (see sessions/reminders.rs); the host polls `SessionStore::due_reminders`
+// and delivers each one once.
+//
+// Due-ness is judged from stored state only, so a reminder whose time
+// passed while the app was closed is simply due on the next poll — it is
+// delivered late and flagged as missed rather than dropped.
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+
+/// A reminder delivered more than this after its time is reported as late.
+pub const MISSED_AFTER: Duration = Duration::minutes(2);
+
+/// The stored form of a fire time: UTC, whole seconds, `Z` suffix. Fixed
+/// width so timestamps compare correctly as strings in SQL.
+pub fn format_fire_at(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Parse a reminder time: a relative offset ("in 2 hours", "30m",
+/// "in 1 hour and 15 minutes", "in an hour"), an RFC 3339 timestamp, or a
+/// local "YYYY-MM-DD HH:MM[:SS]". Times in the past are rejected.
+pub fn parse_when(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Missing reminder time".into());
+    }
+
+    let at = if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        at.with_timezone(&Utc)
+    } else if let Some(local) = parse_local(input) {
+        local
+    } else {
+        let offset = parse_relative(input).ok_or_else(|| {
+            format!(
+                "Couldn't understand '{}'. Use a relative time like 'in 2 hours' \
+                 or an ISO timestamp like 2025-06-01T09:00:00Z",
+                input
+            )
+        })?;
+        now + offset
+    };
+
+    if at <= now {
+        return Err(format!("{} is in the past", format_fire_at(&at)));
+    }
+    Ok(at)
+}
+
+fn parse_local(input: &str) -> Option<DateTime<Utc>> {
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())
+    .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+    .map(|local| local.with_timezone(&Utc))
+}
+
+/// Sum of "<n> <unit>" pairs, with an optional leading "in".
+fn parse_relative(input: &str) -> Option<Duration> {
+    let lower = input.to_lowercase().replace(',', " ");
+    let body = lower.trim().strip_prefix("in ").unwrap_or(lower.trim());
+
+    // Split "2h30m" / "2 hours 30 minutes" into alternating number and unit tokens
+    let mut tokens: Vec<String> = Vec::new();
+    for word in body.split_whitespace().filter(|w| *w != "and") {
+        let mut rest = word;
+        while !rest.is_empty() {
+            let is_digit = rest.starts_with(|c: char| c.is_ascii_digit());
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            tokens.push(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+    }
+
+    let mut total = Duration::zero();
+    let mut tokens = tokens.into_iter();
+    while let Some(amount) = tokens.next() {
+        let amount: i64 = match amount.as_str() {
+            "a" | "an" | "one" => 1,
+            n => n.parse().ok()?,
+        };
+        let unit = match tokens.next()?.as_str() {
+            "s" | "sec" | "secs" | "second" | "seconds" => Duration::seconds(1),
+            "m" | "min" | "mins" | "minute" | "minutes" => Duration::minutes(1),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Duration::hours(1),
+            "d" | "day" | "days" => Duration::days(1),
+            "w" | "week" | "weeks" => Duration::weeks(1),
+            _ => return None,
+        };
+        total += unit * i32::try_from(amount).ok()?;
+    }
+    (total > Duration::zero()).then_some(total)
+}
+
+/// Whether a reminder set for `fire_at` should fire at `now`.
+pub fn is_due(fire_at: &DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    *fire_at <= now
+}
+
+/// Whether a due reminder is being delivered late enough to call out —
+/// typically because the app was closed at the time.
+pub fn is_missed(fire_at: &DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - *fire_at > MISSED_AFTER
+}
+
+/// The text delivered to the user for a due reminder.
+pub fn notification_text(text: &str, fire_at: &DateTime<Utc>, now: DateTime<Utc>) -> String {
+    if is_missed(fire_at, now) {
+        format!(
+            "Reminder: {} (was due {}, missed while the app was closed)",
+            text,
+            fire_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        )
+    } else {
+        format!("Reminder: {}", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_relative_times() {
+        let at = |s: &str| parse_when(s, now()).map(|t| t - now());
+        assert_eq!(at("in 2 hours"), Ok(Duration::hours(2)));
+        assert_eq!(at("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(at("in an hour"), Ok(Duration::hours(1)));
+        assert_eq!(at("in 1 hour and 15 minutes"), Ok(Duration::minutes(75)));
+        assert_eq!(at("2h30m"), Ok(Duration::minutes(150)));
+        assert_eq!(at("In 3 Days"), Ok(Duration::days(3)));
+        assert!(at("tomorrow-ish").is_err());
+        assert!(at("in 2 fortnights").is_err());
+        assert!(at("in 0 minutes").is_err());
+    }
+
+    #[test]
+    fn parses_absolute_times_and_rejects_the_past() {
+        assert_eq!(
+            parse_when("2025-06-01T15:30:00+02:00", now()).map(|t| format_fire_at(&t)),
+            Ok("2025-06-01T13:30:00Z".to_string())
+        );
+        assert!(parse_when("2025-06-01T11:59:00Z", now()).is_err());
+    }
+
+    #[test]
+    fn due_and_missed_detection() {
+        let fire_at = now();
+        assert!(!is_due(&fire_at, now() - Duration::seconds(1)));
+        assert!(is_due(&fire_at, now()));
+        assert!(!is_missed(&fire_at, now() + Duration::seconds(30)));
+        // Passed while the app was closed: still due, but flagged as missed
+        let later = now() + Duration::hours(5);
+        assert!(is_due(&fire_at, later));
+        assert!(is_missed(&fire_at, later));
+        assert_eq!(
+            notification_text("stretch", &fire_at, now()),
+            "Reminder: stretch"
+        );
+        assert!(notification_text("stretch", &fire_at, later).contains("missed"));
+    }
+}
@@ -0,0 +1,211 @@
+// Paw Engine — Concurrent run limiter
+//
+// Caps how many agent runs (chat, tasks, swarm) execute at once so a burst
+// of channels or agents can't exhaust memory or provider rate limits.
+// `max_concurrent_runs` sets the cap; `run_limit_policy` decides what a
+// chat run beyond it does — wait for a slot or fail straight away with a
+// clear message. Background runs always wait: rejecting them would
+// silently drop scheduled work.
+//
+// Unlike a plain semaphore the cap can be changed while runs are in flight;
+// a lowered cap takes effect as runs finish.
+
+use crate::engine::i18n::t;
+use crate::engine::types::RunLimitPolicy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+struct Slots {
+    active: usize,
+    queued: usize,
+    max: usize,
+}
+
+/// Shared counter of running agent turns. Clone the `Arc` into spawned runs.
+#[derive(Debug)]
+pub struct RunLimiter {
+    slots: Mutex<Slots>,
+    freed: Notify,
+    policy: Mutex<RunLimitPolicy>,
+}
+
+/// Holds one run slot; the slot is released on drop.
+#[derive(Debug)]
+pub struct RunPermit {
+    limiter: Arc<RunLimiter>,
+}
+
+impl Drop for RunPermit {
+    fn drop(&mut self) {
+        self.limiter.slots.lock().active -= 1;
+        self.limiter.freed.notify_waiters();
+    }
+}
+
+/// Snapshot for diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunLimiterStatus {
+    pub active: usize,
+    pub queued: usize,
+    pub max: usize,
+    pub policy: RunLimitPolicy,
+}
+
+impl RunLimiter {
+    /// A cap of 0 is treated as 1 so runs can always make progress.
+    pub fn new(max: u32, policy: RunLimitPolicy) -> Arc<Self> {
+        Arc::new(RunLimiter {
+            slots: Mutex::new(Slots {
+                max: (max as usize).max(1),
+                ..Default::default()
+            }),
+            freed: Notify::new(),
+            policy: Mutex::new(policy),
+        })
+    }
+
+    /// Apply a changed config.
+    pub fn configure(&self, max: u32, policy: RunLimitPolicy) {
+        self.slots.lock().max = (max as usize).max(1);
+        *self.policy.lock() = policy;
+        // A raised cap may admit waiting runs
+        self.freed.notify_waiters();
+    }
+
+    pub fn status(&self) -> RunLimiterStatus {
+        let slots = self.slots.lock();
+        RunLimiterStatus {
+            active: slots.active,
+            queued: slots.queued,
+            max: slots.max,
+            policy: *self.policy.lock(),
+        }
+    }
+
+    /// Take a slot if one is free.
+    pub fn try_acquire(self: &Arc<Self>) -> Option<RunPermit> {
+        let mut slots = self.slots.lock();
+        if slots.active < slots.max {
+            slots.active += 1;
+            Some(RunPermit {
+                limiter: Arc::clone(self),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Wait for a slot regardless of policy.
+    pub async fn acquire_queued(self: &Arc<Self>) -> RunPermit {
+        loop {
+            // Register for the wake-up before checking, so a slot freed in
+            // between isn't missed.
+            let freed = self.freed.notified();
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+            let _queued = QueuedGuard::new(self);
+            freed.await;
+        }
+    }
+
+    /// Take a slot if one is free. Otherwise `Ok(None)` under `Queue` (the
+    /// caller waits with [`acquire_queued`](Self::acquire_queued)) or a
+    /// user-facing error under `Reject`.
+    pub fn try_acquire_or_reject(self: &Arc<Self>) -> Result<Option<RunPermit>, String> {
+        if let Some(permit) = self.try_acquire() {
+            return Ok(Some(permit));
+        }
+        if *self.policy.lock() == RunLimitPolicy::Reject {
+            let max = self.slots.lock().max;
+            return Err(t("run.limit_reached", &[("max", &max)]));
+        }
+        Ok(None)
+    }
+
+    /// Take a slot per the configured policy: wait under `Queue`, fail
+    /// under `Reject` when every slot is busy.
+    pub async fn acquire(self: &Arc<Self>) -> Result<RunPermit, String> {
+        match self.try_acquire_or_reject()? {
+            Some(permit) => Ok(permit),
+            None => Ok(self.acquire_queued().await),
+        }
+    }
+}
+
+/// Counts a waiting run in `queued`, including when the wait is cancelled.
+struct QueuedGuard<'a>(&'a RunLimiter);
+
+impl<'a> QueuedGuard<'a> {
+    fn new(limiter: &'a RunLimiter) -> Self {
+        limiter.slots.lock().queued += 1;
+        QueuedGuard(limiter)
+    }
+}
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.slots.lock().queued -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn run_beyond_the_cap_is_rejected_under_reject_policy() {
+        let limiter = RunLimiter::new(2, RunLimitPolicy::Reject);
+        let _a = limiter.acquire().await.unwrap();
+        let _b = limiter.acquire().await.unwrap();
+        let err = limiter.acquire().await.unwrap_err();
+        assert!(err.contains("all 2 slots"), "{}", err);
+        assert_eq!(limiter.status().active, 2);
+
+        drop(_a);
+        assert!(limiter.acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn run_beyond_the_cap_waits_for_a_slot_under_queue_policy() {
+        let limiter = RunLimiter::new(1, RunLimitPolicy::Queue);
+        let first = limiter.acquire().await.unwrap();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+        assert_eq!(limiter.status().queued, 1);
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("queued run should start once the slot frees")
+            .unwrap()
+            .unwrap();
+        assert_eq!(limiter.status().queued, 0);
+        assert_eq!(limiter.status().active, 0);
+    }
+
+    #[tokio::test]
+    async fn raising_the_cap_admits_queued_runs() {
+        let limiter = RunLimiter::new(1, RunLimitPolicy::Queue);
+        let _held = limiter.acquire().await.unwrap();
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire_queued().await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        limiter.configure(2, RunLimitPolicy::Queue);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("raised cap should admit the queued run")
+            .unwrap();
+    }
+}
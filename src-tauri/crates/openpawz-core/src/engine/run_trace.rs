@@ -0,0 +1,194 @@
+// Paw Agent Engine — Run Trace (structured per-run timeline)
+// Records ordered spans for each LLM call and tool call of an agent run,
+// plus a final outcome span, and persists them per (session, run) so the
+// Inspector can render a timeline via `engine_get_run_trace`.
+//
+// §Security: tool args are summarized (sensitive keys masked, values
+// truncated) and both args and errors pass through secret_scrub.
+
+use crate::engine::secret_scrub;
+use crate::engine::sessions::{RunTraceSpan, SessionStore};
+use crate::engine::util::safe_truncate;
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+/// Max chars of the rendered args summary.
+const ARGS_SUMMARY_MAX: usize = 200;
+/// Max chars kept per individual argument value.
+const ARG_VALUE_MAX: usize = 60;
+/// Max chars of an error preview on failed tools / outcomes.
+const ERROR_PREVIEW_MAX: usize = 200;
+
+/// Builder for one run's trace. Cheap: a Vec push per step.
+pub struct RunTrace {
+    session_id: String,
+    run_id: String,
+    spans: Vec<RunTraceSpan>,
+}
+
+impl RunTrace {
+    pub fn new(session_id: &str, run_id: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            run_id: run_id.to_string(),
+            spans: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: &str,
+        name: &str,
+        round: u32,
+        duration_ms: u64,
+        success: bool,
+        attributes: BTreeMap<String, String>,
+    ) {
+        let end_ms = Utc::now().timestamp_millis();
+        self.spans.push(RunTraceSpan {
+            seq: self.spans.len() as u32,
+            kind: kind.to_string(),
+            name: name.to_string(),
+            round,
+            start_ms: end_ms - duration_ms as i64,
+            duration_ms,
+            success,
+            attributes,
+        });
+    }
+
+    /// Record a model call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn llm(
+        &mut self,
+        round: u32,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        tool_calls_requested: usize,
+        duration_ms: u64,
+        error: Option<&str>,
+    ) {
+        let mut attrs = BTreeMap::new();
+        attrs.insert("input_tokens".into(), input_tokens.to_string());
+        attrs.insert("output_tokens".into(), output_tokens.to_string());
+        attrs.insert("tool_calls".into(), tool_calls_requested.to_string());
+        if let Some(e) = error {
+            attrs.insert("error".into(), error_preview(e));
+        }
+        self.push("llm", model, round, duration_ms, error.is_none(), attrs);
+    }
+
+    /// Record a tool execution. Denied calls are recorded as failures.
+    pub fn tool(
+        &mut self,
+        round: u32,
+        name: &str,
+        args_json: &str,
+        output: &str,
+        success: bool,
+        duration_ms: u64,
+    ) {
+        let mut attrs = BTreeMap::new();
+        attrs.insert("args".into(), summarize_args(args_json));
+        attrs.insert("result_bytes".into(), output.len().to_string());
+        if !success {
+            attrs.insert("error".into(), error_preview(output));
+        }
+        self.push("tool", name, round, duration_ms, success, attrs);
+    }
+
+    /// Record the final outcome ("completed", "max_rounds", "error", …).
+    pub fn outcome(&mut self, round: u32, status: &str, success: bool, detail: &str) {
+        let mut attrs = BTreeMap::new();
+        if !detail.is_empty() {
+            attrs.insert("detail".into(), error_preview(detail));
+        }
+        self.push("outcome", status, round, 0, success, attrs);
+    }
+
+    pub fn spans(&self) -> &[RunTraceSpan] {
+        &self.spans
+    }
+
+    /// Persist the trace. Failures are logged, never propagated.
+    pub fn persist(&self, store: &SessionStore) {
+        if let Err(e) = store.save_run_trace(&self.session_id, &self.run_id, &self.spans) {
+            log::warn!("[telemetry] Failed to persist run trace: {}", e);
+        }
+    }
+}
+
+/// Render tool args as `key=value, …` with sensitive keys masked and every
+/// value truncated. Non-object args are truncated as-is.
+pub fn summarize_args(args_json: &str) -> String {
+    let summary = match serde_json::from_str::<serde_json::Value>(args_json) {
+        Ok(serde_json::Value::Object(map)) => map
+            .iter()
+            .map(|(k, v)| {
+                let value = if secret_scrub::is_secret_field(k) {
+                    "[redacted]".to_string()
+                } else {
+                    let raw = match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    safe_truncate(&raw, ARG_VALUE_MAX).to_string()
+                };
+                format!("{}={}", k, value)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => args_json.to_string(),
+    };
+    secret_scrub::scrub(safe_truncate(&summary, ARGS_SUMMARY_MAX))
+}
+
+fn error_preview(text: &str) -> String {
+    secret_scrub::scrub(safe_truncate(text, ERROR_PREVIEW_MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    #[test]
+    fn run_with_one_tool_call_traces_llm_then_tool() {
+        let store = test_store();
+        let mut trace = RunTrace::new("s1", "run-1");
+        trace.llm(1, "gpt-test", 120, 15, 1, 800, None);
+        trace.tool(1, "read_file", r#"{"path":"notes.md"}"#, "hello", true, 12);
+        trace.llm(2, "gpt-test", 140, 30, 0, 600, None);
+        trace.outcome(2, "completed", true, "");
+        trace.persist(&store);
+
+        let spans = store.get_run_trace("s1", "run-1").unwrap();
+        let kinds: Vec<&str> = spans.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, ["llm", "tool", "llm", "outcome"]);
+        assert_eq!(spans[0].name, "gpt-test");
+        assert_eq!(spans[0].attributes["input_tokens"], "120");
+        assert_eq!(spans[1].name, "read_file");
+        assert_eq!(spans[1].attributes["args"], "path=notes.md");
+        assert_eq!(spans[1].attributes["result_bytes"], "5");
+        assert!(spans.windows(2).all(|w| w[0].seq < w[1].seq));
+        assert!(store.get_run_trace("s1", "other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn args_are_redacted_and_capped() {
+        let long = "x".repeat(500);
+        let json = format!(r#"{{"api_key":"sk-abc","body":"{}"}}"#, long);
+        let summary = summarize_args(&json);
+        assert!(summary.contains("api_key=[redacted]"));
+        assert!(!summary.contains("sk-abc"));
+        assert!(summary.len() <= ARGS_SUMMARY_MAX + 4);
+    }
+}
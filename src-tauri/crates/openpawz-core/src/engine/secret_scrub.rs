@@ -0,0 +1,207 @@
+// Paw Agent Engine — Secret Scrubber
+//
+// Redacts known credential values from strings before they are logged,
+// returned to the model as tool output, or surfaced in the UI.
+//
+// Skill credentials are decrypted server-side at tool execution time and never
+// injected into prompts — but a tool that echoes a request URL
+// (e.g. `…?key=…&token=…`) or an upstream error body can still leak them.
+// The skill vault registers every decrypted secret here; output paths call
+// `scrub()` on anything that leaves the engine.
+//
+// Matching is exact-substring on the raw and URL-encoded forms of each value.
+// Values shorter than MIN_SECRET_LEN are ignored so short, common strings
+// ("true", "en", a port number) don't get blanked out of unrelated output.
+
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use zeroize::Zeroizing;
+
+/// Replacement marker for redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Minimum length for a value to be treated as a scrubbable secret.
+pub const MIN_SECRET_LEN: usize = 8;
+
+/// Credential field names that hold secrets (case-insensitive substring match).
+/// Fields like `base_url`, `chat_id`, or `wallet_address` are not secrets and
+/// are left alone so tool output that legitimately contains them stays intact.
+const SECRET_KEY_HINTS: &[&str] = &[
+    "key", "token", "secret", "password", "passwd", "auth", "private", "cookie",
+];
+
+// ── Scrubber ───────────────────────────────────────────────────────────
+
+/// A set of secret values to redact from arbitrary text.
+#[derive(Default)]
+pub struct SecretScrubber {
+    /// Patterns sorted longest-first so a secret that contains another secret
+    /// is redacted as a whole rather than leaving a partial tail behind.
+    patterns: Vec<Zeroizing<String>>,
+}
+
+impl SecretScrubber {
+    /// Build a scrubber from a set of secret values.
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut scrubber = Self::default();
+        scrubber.extend(values);
+        scrubber
+    }
+
+    /// Add secret values (and their URL-encoded forms) to the scrubber.
+    pub fn extend<I, S>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for value in values {
+            let value = value.as_ref().trim();
+            if value.len() < MIN_SECRET_LEN {
+                continue;
+            }
+            let encoded = urlencoding::encode(value);
+            for candidate in [value, encoded.as_ref()] {
+                if !self.patterns.iter().any(|p| p.as_str() == candidate) {
+                    self.patterns.push(Zeroizing::new(candidate.to_string()));
+                }
+            }
+        }
+        self.patterns.sort_by_key(|p| std::cmp::Reverse(p.len()));
+    }
+
+    /// Number of distinct patterns (raw + encoded) being scrubbed.
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Replace every occurrence of a known secret with `[REDACTED]`.
+    /// Strings that contain no secrets are returned unchanged.
+    pub fn scrub(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for pattern in &self.patterns {
+            if out.contains(pattern.as_str()) {
+                out = out.replace(pattern.as_str(), REDACTED);
+            }
+        }
+        out
+    }
+}
+
+/// Whether a credential field name denotes a secret value.
+pub fn is_secret_field(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    SECRET_KEY_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+// ── Process-wide registry ──────────────────────────────────────────────
+
+/// All secrets decrypted so far in this process.
+static ACTIVE: RwLock<Option<SecretScrubber>> = RwLock::new(None);
+
+/// Register secret values so `scrub()` redacts them from now on.
+pub fn register_secrets<I, S>(values: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut guard = ACTIVE.write();
+    guard
+        .get_or_insert_with(SecretScrubber::default)
+        .extend(values);
+}
+
+/// Register the secret fields of a decrypted credential map.
+/// Non-secret fields (URLs, ids, addresses) are skipped — see `is_secret_field`.
+pub fn register_credentials(creds: &HashMap<String, String>) {
+    register_secrets(
+        creds
+            .iter()
+            .filter(|(k, _)| is_secret_field(k))
+            .map(|(_, v)| v.as_str()),
+    );
+}
+
+/// Redact every registered secret from `text`.
+pub fn scrub(text: &str) -> String {
+    match ACTIVE.read().as_ref() {
+        Some(scrubber) if !scrubber.is_empty() => scrubber.scrub(text),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_known_token() {
+        let s = SecretScrubber::new(["abcd1234efgh5678"]);
+        let out = s.scrub("GET https://api.example.com/1/me?key=xyz&token=abcd1234efgh5678 failed");
+        assert!(!out.contains("abcd1234efgh5678"));
+        assert!(out.contains("token=[REDACTED]"));
+    }
+
+    #[test]
+    fn non_secret_passes_through() {
+        let s = SecretScrubber::new(["abcd1234efgh5678"]);
+        let text = "Board 'Roadmap' has 12 cards";
+        assert_eq!(s.scrub(text), text);
+    }
+
+    #[test]
+    fn redacts_url_encoded_form() {
+        let s = SecretScrubber::new(["p@ss/word+123"]);
+        let out = s.scrub("url=https://x.test/?pw=p%40ss%2Fword%2B123");
+        assert!(!out.contains("p%40ss%2Fword%2B123"));
+        assert!(out.contains(REDACTED));
+    }
+
+    #[test]
+    fn short_values_ignored() {
+        let s = SecretScrubber::new(["true", "en"]);
+        assert!(s.is_empty());
+        assert_eq!(s.scrub("true en"), "true en");
+    }
+
+    #[test]
+    fn longest_match_wins() {
+        let s = SecretScrubber::new(["secretvalue", "secretvalue-extended"]);
+        assert_eq!(s.scrub("x=secretvalue-extended"), "x=[REDACTED]");
+    }
+
+    #[test]
+    fn secret_field_names() {
+        assert!(is_secret_field("TRELLO_API_KEY"));
+        assert!(is_secret_field("api_token"));
+        assert!(is_secret_field("DEX_PRIVATE_KEY"));
+        assert!(is_secret_field("password"));
+        assert!(!is_secret_field("base_url"));
+        assert!(!is_secret_field("DEX_WALLET_ADDRESS"));
+        assert!(!is_secret_field("chat_id"));
+    }
+
+    #[test]
+    fn global_registry_scrubs_registered_credentials() {
+        let mut creds = HashMap::new();
+        creds.insert(
+            "api_token".to_string(),
+            "tok-registry-test-0001".to_string(),
+        );
+        creds.insert(
+            "base_url".to_string(),
+            "https://registry.example".to_string(),
+        );
+        register_credentials(&creds);
+        let out = scrub("token=tok-registry-test-0001 base=https://registry.example");
+        assert!(!out.contains("tok-registry-test-0001"));
+        assert!(out.contains("https://registry.example"));
+    }
+}
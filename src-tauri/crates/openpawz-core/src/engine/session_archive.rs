@@ -0,0 +1,164 @@
+// Paw Engine — Session archiving
+//
+// Sessions pile up into the hundreds. `archive_old_sessions` hides the ones
+// nobody has touched in a while: they keep their messages but drop out of
+// the default session list, optionally with a one-line summary stored for
+// the archive view. Sending a message to an archived session brings it back.
+//
+// Summaries are built from what the session already holds — its label or
+// opening request — so archiving never spends tokens.
+//
+// Stored as JSON under the `session_archive_config` config key; the host
+// runs the routine every `interval_hours` while enabled.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use crate::engine::types::Session;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+/// engine_config key holding the serialized [`SessionArchiveConfig`].
+pub const SESSION_ARCHIVE_CONFIG_KEY: &str = "session_archive_config";
+
+/// Longest summary kept, in characters.
+pub const SUMMARY_MAX_CHARS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionArchiveConfig {
+    /// Run periodically in the background.
+    pub enabled: bool,
+    /// Sessions not updated for this many days are archived.
+    pub older_than_days: u32,
+    /// Store a one-line summary with each archived session.
+    pub summarize: bool,
+    /// Hours between background runs.
+    pub interval_hours: u32,
+}
+
+impl Default for SessionArchiveConfig {
+    fn default() -> Self {
+        SessionArchiveConfig {
+            enabled: false,
+            older_than_days: 30,
+            summarize: true,
+            interval_hours: 24,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveReport {
+    pub archived: usize,
+    pub summarized: usize,
+    pub session_ids: Vec<String>,
+}
+
+/// A one-line description of a session: its label, else its opening user
+/// message, collapsed to a single line and cut at [`SUMMARY_MAX_CHARS`].
+pub fn one_line_summary(session: &Session, first_user_message: Option<&str>) -> Option<String> {
+    let source = session
+        .label
+        .as_deref()
+        .filter(|l| !l.trim().is_empty())
+        .or(first_user_message)?;
+    let line = source.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.is_empty() {
+        return None;
+    }
+    let mut summary: String = line.chars().take(SUMMARY_MAX_CHARS).collect();
+    if line.chars().count() > SUMMARY_MAX_CHARS {
+        summary.pop();
+        summary.push('…');
+    }
+    Some(format!("{} ({} messages)", summary, session.message_count))
+}
+
+/// Archive every session not updated in the last `older_than_days` days,
+/// storing a summary for each when `summarize` is set.
+pub fn archive_old_sessions(
+    store: &SessionStore,
+    older_than_days: u32,
+    summarize: bool,
+) -> EngineResult<ArchiveReport> {
+    let mut report = ArchiveReport::default();
+    for session in store.list_stale_sessions(older_than_days)? {
+        let summary = if summarize {
+            let first = store.first_user_message(&session.id)?;
+            one_line_summary(&session, first.as_deref())
+        } else {
+            None
+        };
+        store.set_session_archived(&session.id, true, summary.as_deref())?;
+        report.archived += 1;
+        if summary.is_some() {
+            report.summarized += 1;
+        }
+        report.session_ids.push(session.id);
+    }
+    if report.archived > 0 {
+        info!(
+            "[engine] Archived {} session(s) untouched for {}+ days",
+            report.archived, older_than_days
+        );
+    }
+    Ok(report)
+}
+
+/// Load the settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> SessionArchiveConfig {
+    store
+        .get_config(SESSION_ARCHIVE_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, config: &SessionArchiveConfig) -> EngineResult<()> {
+    store.set_config(SESSION_ARCHIVE_CONFIG_KEY, &serde_json::to_string(config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(label: Option<&str>, message_count: i64) -> Session {
+        Session {
+            id: "s1".into(),
+            label: label.map(str::to_string),
+            model: "m".into(),
+            system_prompt: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            message_count,
+            agent_id: None,
+            parent_session_id: None,
+            forked_from_message_id: None,
+            archived: false,
+            summary: None,
+        }
+    }
+
+    #[test]
+    fn summary_prefers_label_then_first_message() {
+        assert_eq!(
+            one_line_summary(&session(Some("Trip planning"), 12), Some("ignored")).as_deref(),
+            Some("Trip planning (12 messages)")
+        );
+        assert_eq!(
+            one_line_summary(&session(None, 2), Some("  Fix the\nbuild   please ")).as_deref(),
+            Some("Fix the build please (2 messages)")
+        );
+        assert_eq!(one_line_summary(&session(Some(" "), 0), None), None);
+    }
+
+    #[test]
+    fn long_summaries_are_cut_to_one_line() {
+        let long = "word ".repeat(50);
+        let summary = one_line_summary(&session(None, 1), Some(&long)).unwrap();
+        let text = summary.trim_end_matches(" (1 messages)");
+        assert_eq!(text.chars().count(), SUMMARY_MAX_CHARS);
+        assert!(text.ends_with('…'));
+    }
+}
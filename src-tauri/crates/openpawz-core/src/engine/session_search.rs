@@ -0,0 +1,302 @@
+// Paw Engine — Semantic search across past sessions
+//
+// Finds the conversation you had about something even when you don't
+// remember the words used in it. Each session's user/assistant text is cut
+// into a few chunks (a whole transcript, not one message at a time) and each
+// chunk is embedded once with the memory EmbeddingClient; a query is embedded
+// and compared against every chunk, and sessions are ranked by their best
+// chunk, which doubles as the snippet shown.
+//
+// Chunks are re-built when a session's message count changes. When no
+// embedding provider is configured (or a chunk failed to embed), the chunks
+// are still stored and searched with FTS5 keyword matching instead.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::memory::EmbeddingClient;
+use crate::engine::sessions::embedding::cosine_similarity;
+use crate::engine::sessions::{SessionChunk, SessionStore};
+use crate::engine::types::StoredMessage;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Target chunk size in characters.
+pub const CHUNK_CHARS: usize = 1500;
+
+/// Long sessions get bigger chunks rather than more of them, so indexing
+/// cost per session stays bounded.
+pub const MAX_CHUNKS_PER_SESSION: usize = 24;
+
+/// Longest snippet returned with a hit, in characters.
+pub const SNIPPET_CHARS: usize = 200;
+
+/// Most messages read from one session when chunking it.
+const MAX_MESSAGES_INDEXED: i64 = 5000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSearchHit {
+    pub session_id: String,
+    pub label: Option<String>,
+    pub score: f64,
+    pub snippet: String,
+    pub updated_at: String,
+    /// false when the hit came from the keyword fallback.
+    pub semantic: bool,
+}
+
+/// Cut a transcript into chunks of roughly [`CHUNK_CHARS`], never more than
+/// [`MAX_CHUNKS_PER_SESSION`]. Only user and assistant text is kept — tool
+/// output is noisy and usually huge.
+pub fn chunk_messages(messages: &[StoredMessage]) -> Vec<String> {
+    let lines: Vec<String> = messages
+        .iter()
+        .filter(|m| m.role == "user" || m.role == "assistant")
+        .filter_map(|m| {
+            let text = m.content.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.is_empty() {
+                return None;
+            }
+            let speaker = if m.role == "user" {
+                "User"
+            } else {
+                "Assistant"
+            };
+            Some(format!("{}: {}", speaker, text))
+        })
+        .collect();
+
+    let text: Vec<char> = lines.join("\n").chars().collect();
+    let chunk_chars = CHUNK_CHARS.max(text.len().div_ceil(MAX_CHUNKS_PER_SESSION));
+    text.chunks(chunk_chars)
+        .map(|c| c.iter().collect::<String>().trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Up to [`SNIPPET_CHARS`] of a chunk, starting a little before the first
+/// query word it contains (or at the start).
+pub fn snippet(content: &str, query: &str) -> String {
+    let text: Vec<char> = content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .collect();
+    let lower: Vec<char> = text.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let first_match = query
+        .split_whitespace()
+        .filter(|w| w.chars().count() > 2)
+        .filter_map(|w| {
+            let word: Vec<char> = w.chars().map(|c| c.to_ascii_lowercase()).collect();
+            lower
+                .windows(word.len())
+                .position(|win| win == word.as_slice())
+        })
+        .min();
+    let start = first_match.map(|i| i.saturating_sub(40)).unwrap_or(0);
+    let end = (start + SNIPPET_CHARS).min(text.len());
+    let mut out: String = text[start..end].iter().collect();
+    if start > 0 {
+        out.insert(0, '…');
+    }
+    if end < text.len() {
+        out.push('…');
+    }
+    out
+}
+
+/// Score every chunk against the query vector and keep each session's best
+/// chunk, best sessions first.
+pub fn rank_chunks_by_embedding(
+    query_vec: &[f32],
+    chunks: &[SessionChunk],
+    limit: usize,
+) -> Vec<(SessionChunk, f64)> {
+    let mut best: HashMap<&str, (&SessionChunk, f64)> = HashMap::new();
+    for chunk in chunks {
+        let Some(embedding) = chunk.embedding.as_deref() else {
+            continue;
+        };
+        let score = cosine_similarity(query_vec, embedding);
+        match best.get(chunk.session_id.as_str()) {
+            Some((_, s)) if *s >= score => {}
+            _ => {
+                best.insert(&chunk.session_id, (chunk, score));
+            }
+        }
+    }
+    let mut ranked: Vec<(SessionChunk, f64)> =
+        best.into_values().map(|(c, s)| (c.clone(), s)).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Rank sessions against an already-indexed store. Uses the query vector
+/// when there is one and some chunks are embedded; otherwise FTS.
+pub fn search_sessions(
+    store: &SessionStore,
+    query: &str,
+    query_vec: Option<&[f32]>,
+    limit: usize,
+) -> EngineResult<Vec<SessionSearchHit>> {
+    let embedded = match query_vec {
+        Some(_) => store.list_embedded_session_chunks()?,
+        None => Vec::new(),
+    };
+    let (ranked, semantic) = match query_vec {
+        Some(vec) if !embedded.is_empty() => {
+            (rank_chunks_by_embedding(vec, &embedded, limit), true)
+        }
+        _ => {
+            // FTS returns chunks, not sessions: over-fetch, then keep the
+            // first (best) chunk per session.
+            let mut seen = std::collections::HashSet::new();
+            let ranked = store
+                .search_session_chunks_fts(query, limit * 4)?
+                .into_iter()
+                .filter(|(chunk, _)| seen.insert(chunk.session_id.clone()))
+                .take(limit)
+                .collect();
+            (ranked, false)
+        }
+    };
+
+    let mut hits = Vec::with_capacity(ranked.len());
+    for (chunk, score) in ranked {
+        // Chunks of a deleted session can linger until the next re-index
+        let Some(session) = store.get_session(&chunk.session_id)? else {
+            continue;
+        };
+        hits.push(SessionSearchHit {
+            session_id: session.id,
+            label: session.label,
+            score,
+            snippet: snippet(&chunk.content, query),
+            updated_at: session.updated_at,
+            semantic,
+        });
+    }
+    Ok(hits)
+}
+
+/// (Re-)chunk one session and embed its chunks. A chunk that fails to embed
+/// is stored without a vector so it stays reachable through FTS.
+pub async fn index_session(
+    store: &SessionStore,
+    client: Option<&EmbeddingClient>,
+    session_id: &str,
+) -> EngineResult<usize> {
+    let Some(session) = store.get_session(session_id)? else {
+        return Ok(0);
+    };
+    let messages = store.get_messages(session_id, MAX_MESSAGES_INDEXED)?;
+    let mut chunks = Vec::new();
+    for content in chunk_messages(&messages) {
+        let embedding = match client {
+            Some(client) => match client.embed(&content).await {
+                Ok(vec) => Some(vec),
+                Err(e) => {
+                    warn!(
+                        "[session-search] Embedding failed for {}: {}",
+                        session_id, e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        chunks.push((content, embedding));
+    }
+    store.replace_session_chunks(session_id, session.message_count, &chunks)?;
+    Ok(chunks.len())
+}
+
+/// Index up to `max` sessions whose chunks are missing or stale, most
+/// recently updated first. Returns how many were indexed.
+pub async fn index_pending_sessions(
+    store: &SessionStore,
+    client: Option<&EmbeddingClient>,
+    max: usize,
+) -> EngineResult<usize> {
+    let pending = store.sessions_needing_chunks(max)?;
+    for session_id in &pending {
+        index_session(store, client, session_id).await?;
+    }
+    if !pending.is_empty() {
+        info!("[session-search] Indexed {} session(s)", pending.len());
+    }
+    Ok(pending.len())
+}
+
+/// Embed the query and rank sessions by meaning, falling back to keyword
+/// search when there's no embedding client or the query can't be embedded.
+pub async fn search_sessions_semantic(
+    store: &SessionStore,
+    client: Option<&EmbeddingClient>,
+    query: &str,
+    limit: usize,
+) -> EngineResult<Vec<SessionSearchHit>> {
+    let query_vec = match client {
+        Some(client) => match client.embed(query).await {
+            Ok(vec) => Some(vec),
+            Err(e) => {
+                warn!(
+                    "[session-search] Query embedding failed, using keywords: {}",
+                    e
+                );
+                None
+            }
+        },
+        None => None,
+    };
+    search_sessions(store, query, query_vec.as_deref(), limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> StoredMessage {
+        StoredMessage {
+            id: String::new(),
+            session_id: "s".into(),
+            role: role.into(),
+            content: content.into(),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: String::new(),
+            incomplete: false,
+            reasoning: None,
+        }
+    }
+
+    #[test]
+    fn chunks_skip_tool_output_and_stay_bounded() {
+        let chunks = chunk_messages(&[
+            msg("user", "hello"),
+            msg("tool", "huge tool output"),
+            msg("assistant", "hi there"),
+        ]);
+        assert_eq!(chunks, vec!["User: hello\nAssistant: hi there".to_string()]);
+
+        let long: Vec<StoredMessage> = (0..400).map(|_| msg("user", &"x".repeat(500))).collect();
+        let chunks = chunk_messages(&long);
+        assert!(chunks.len() <= MAX_CHUNKS_PER_SESSION);
+        assert!(chunks.len() > 1);
+    }
+
+    #[test]
+    fn snippet_centres_on_the_query() {
+        let content = format!(
+            "{} the kubernetes cluster {}",
+            "a ".repeat(200),
+            "b ".repeat(200)
+        );
+        let s = snippet(&content, "Kubernetes");
+        assert!(s.starts_with('…') && s.ends_with('…'));
+        assert!(s.contains("kubernetes cluster"));
+        assert_eq!(snippet("short text", "missing"), "short text");
+    }
+}
@@ -0,0 +1,109 @@
+// sessions/agent_notes.rs — Per-agent scratchpad notes.
+// Exact-key notes an agent stashes for itself across turns. Lighter than
+// memory: no embeddings, no search. A note is agent-wide or, when written
+// with a session id, visible only inside that session.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// A single scratchpad note.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentNote {
+    pub agent_id: String,
+    /// None for agent-wide notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub key: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+fn note_from_row(row: &rusqlite::Row) -> rusqlite::Result<AgentNote> {
+    let session_id: String = row.get(1)?;
+    Ok(AgentNote {
+        agent_id: row.get(0)?,
+        session_id: (!session_id.is_empty()).then_some(session_id),
+        key: row.get(2)?,
+        value: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+impl SessionStore {
+    /// Write a note (upsert). `session_id` scopes it to one session.
+    pub fn note_write(
+        &self,
+        agent_id: &str,
+        session_id: Option<&str>,
+        key: &str,
+        value: &str,
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO agent_notes (agent_id, session_id, key, value, updated_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))
+             ON CONFLICT(agent_id, session_id, key)
+             DO UPDATE SET value = ?4, updated_at = datetime('now')",
+            params![agent_id, session_id.unwrap_or(""), key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Read a note. With a session id the session's note wins over an
+    /// agent-wide one under the same key.
+    pub fn note_read(
+        &self,
+        agent_id: &str,
+        session_id: Option<&str>,
+        key: &str,
+    ) -> EngineResult<Option<AgentNote>> {
+        let conn = self.conn.lock();
+        let note = conn
+            .query_row(
+                "SELECT agent_id, session_id, key, value, updated_at FROM agent_notes
+                 WHERE agent_id = ?1 AND key = ?2 AND session_id IN ('', ?3)
+                 ORDER BY session_id DESC LIMIT 1",
+                params![agent_id, key, session_id.unwrap_or("")],
+                note_from_row,
+            )
+            .optional()?;
+        Ok(note)
+    }
+
+    /// List an agent's notes: agent-wide ones, plus the session's own when a
+    /// session id is given.
+    pub fn note_list(
+        &self,
+        agent_id: &str,
+        session_id: Option<&str>,
+    ) -> EngineResult<Vec<AgentNote>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT agent_id, session_id, key, value, updated_at FROM agent_notes
+             WHERE agent_id = ?1 AND session_id IN ('', ?2)
+             ORDER BY key, session_id",
+        )?;
+        let notes = stmt
+            .query_map(params![agent_id, session_id.unwrap_or("")], note_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(notes)
+    }
+
+    /// Delete a note from exactly the given scope. Returns whether it existed.
+    pub fn note_delete(
+        &self,
+        agent_id: &str,
+        session_id: Option<&str>,
+        key: &str,
+    ) -> EngineResult<bool> {
+        let conn = self.conn.lock();
+        let deleted = conn.execute(
+            "DELETE FROM agent_notes WHERE agent_id = ?1 AND session_id = ?2 AND key = ?3",
+            params![agent_id, session_id.unwrap_or(""), key],
+        )?;
+        Ok(deleted > 0)
+    }
+}
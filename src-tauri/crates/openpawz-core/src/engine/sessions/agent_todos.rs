@@ -0,0 +1,103 @@
+// sessions/agent_todos.rs — Per-agent todo list.
+// Subtasks an agent plans and checks off during long-running work. Separate
+// from the `tasks` board: these are the agent's own working checklist.
+// Status only moves forward: pending → done.
+
+use super::SessionStore;
+use crate::atoms::error::{EngineError, EngineResult};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+pub const TODO_PENDING: &str = "pending";
+pub const TODO_DONE: &str = "done";
+
+/// A single todo item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTodo {
+    pub id: i64,
+    pub agent_id: String,
+    pub description: String,
+    /// "pending" or "done".
+    pub status: String,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+const TODO_COLUMNS: &str = "id, agent_id, description, status, created_at, completed_at";
+
+fn todo_from_row(row: &rusqlite::Row) -> rusqlite::Result<AgentTodo> {
+    Ok(AgentTodo {
+        id: row.get(0)?,
+        agent_id: row.get(1)?,
+        description: row.get(2)?,
+        status: row.get(3)?,
+        created_at: row.get(4)?,
+        completed_at: row.get(5)?,
+    })
+}
+
+impl SessionStore {
+    /// Add a pending todo for an agent.
+    pub fn todo_add(&self, agent_id: &str, description: &str) -> EngineResult<AgentTodo> {
+        let description = description.trim();
+        if description.is_empty() {
+            return Err(EngineError::Other(
+                "Todo description cannot be empty".into(),
+            ));
+        }
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO agent_todos (agent_id, description, status) VALUES (?1, ?2, ?3)",
+            params![agent_id, description, TODO_PENDING],
+        )?;
+        let id = conn.last_insert_rowid();
+        let todo = conn.query_row(
+            &format!("SELECT {} FROM agent_todos WHERE id = ?1", TODO_COLUMNS),
+            params![id],
+            todo_from_row,
+        )?;
+        Ok(todo)
+    }
+
+    /// Mark one of the agent's todos done. Fails if it doesn't exist, belongs
+    /// to another agent, or is already done.
+    pub fn todo_complete(&self, agent_id: &str, id: i64) -> EngineResult<AgentTodo> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE agent_todos SET status = ?3, completed_at = datetime('now')
+             WHERE id = ?1 AND agent_id = ?2 AND status = ?4",
+            params![id, agent_id, TODO_DONE, TODO_PENDING],
+        )?;
+        let todo = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM agent_todos WHERE id = ?1 AND agent_id = ?2",
+                    TODO_COLUMNS
+                ),
+                params![id, agent_id],
+                todo_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| EngineError::Other(format!("Todo {} not found", id)))?;
+        if updated == 0 {
+            return Err(EngineError::Other(format!("Todo {} is already done", id)));
+        }
+        Ok(todo)
+    }
+
+    /// An agent's todos in creation order, optionally only those with `status`.
+    pub fn todo_list(&self, agent_id: &str, status: Option<&str>) -> EngineResult<Vec<AgentTodo>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM agent_todos
+             WHERE agent_id = ?1 AND (?2 IS NULL OR status = ?2)
+             ORDER BY id",
+            TODO_COLUMNS
+        ))?;
+        let todos = stmt
+            .query_map(params![agent_id, status], todo_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(todos)
+    }
+}
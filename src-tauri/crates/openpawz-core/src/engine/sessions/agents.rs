@@ -0,0 +1,427 @@
+// Agent Definitions — CRUD operations on the `agents` table.
+// One row per agent: display name, persona prompt, default model, the
+// skills enabled for it and its autonomy settings. Prompt assembly, model
+// routing and approval gating read from here.
+
+use super::SessionStore;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::types::AgentDefinition;
+use log::info;
+use rusqlite::{params, OptionalExtension, Row};
+
+/// The agent used when a request names none. Always present; can't be deleted.
+pub const DEFAULT_AGENT_ID: &str = "default";
+
+/// Mint an agent id from a display name: `agent-<slug>-<millis>`. The
+/// numeric suffix keeps ids unique and is dropped from display names.
+pub fn new_agent_id(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("agent-{}-{}", slug, chrono::Utc::now().timestamp_millis())
+}
+
+fn agent_from_row(row: &Row) -> rusqlite::Result<AgentDefinition> {
+    let skills: String = row.get(4)?;
+    let autonomy: String = row.get(7)?;
+    Ok(AgentDefinition {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        persona: row.get(2)?,
+        model: row.get(3)?,
+        skills: serde_json::from_str(&skills).unwrap_or_default(),
+        autonomy: serde_json::from_str(&autonomy).unwrap_or_default(),
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+impl SessionStore {
+    /// List all agent definitions, the default agent first.
+    pub fn list_agent_definitions(&self) -> EngineResult<Vec<AgentDefinition>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, persona, model, skills, created_at, updated_at, autonomy
+             FROM agents ORDER BY id != 'default', name COLLATE NOCASE",
+        )?;
+        let agents = stmt
+            .query_map([], agent_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(agents)
+    }
+
+    /// Get one agent's definition.
+    pub fn get_agent_definition(&self, agent_id: &str) -> EngineResult<Option<AgentDefinition>> {
+        let conn = self.conn.lock();
+        let agent = conn
+            .query_row(
+                "SELECT id, name, persona, model, skills, created_at, updated_at, autonomy
+                 FROM agents WHERE id = ?1",
+                params![agent_id],
+                agent_from_row,
+            )
+            .optional()?;
+        Ok(agent)
+    }
+
+    /// Create or update an agent definition. Blank persona / model are stored
+    /// as NULL so the engine defaults apply.
+    pub fn upsert_agent_definition(&self, agent: &AgentDefinition) -> EngineResult<()> {
+        if agent.id.trim().is_empty() {
+            return Err(EngineError::Config("Agent id is required".into()));
+        }
+        let name = if agent.name.trim().is_empty() {
+            agent.id.as_str()
+        } else {
+            agent.name.trim()
+        };
+        let persona = agent.persona.as_deref().filter(|p| !p.trim().is_empty());
+        let model = agent.model.as_deref().filter(|m| !m.trim().is_empty());
+        let skills = serde_json::to_string(&agent.skills)?;
+        crate::engine::autonomy::validate(&agent.autonomy).map_err(EngineError::Config)?;
+        let autonomy = serde_json::to_string(&agent.autonomy)?;
+
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO agents (id, name, persona, model, skills, autonomy)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                persona = excluded.persona,
+                model = excluded.model,
+                skills = excluded.skills,
+                autonomy = excluded.autonomy,
+                updated_at = datetime('now')",
+            params![agent.id, name, persona, model, skills, autonomy],
+        )?;
+        Ok(())
+    }
+
+    /// Delete an agent definition. The default agent can't be deleted.
+    pub fn delete_agent_definition(&self, agent_id: &str) -> EngineResult<()> {
+        if agent_id == DEFAULT_AGENT_ID {
+            return Err(EngineError::Config(
+                "The default agent can't be deleted".into(),
+            ));
+        }
+        let conn = self.conn.lock();
+        conn.execute("DELETE FROM agents WHERE id = ?1", params![agent_id])?;
+        Ok(())
+    }
+
+    /// Duplicate an agent under a fresh id: its definition, soul files,
+    /// standalone-agent entry and community skill assignments, plus its
+    /// memories when `include_memories` is set (re-scoped to the clone).
+    ///
+    /// Skill credentials live in the skill vault keyed by skill, not agent,
+    /// so the clone references the same vault entries — nothing is copied.
+    pub fn clone_agent(
+        &self,
+        source_id: &str,
+        new_name: &str,
+        include_memories: bool,
+    ) -> EngineResult<AgentDefinition> {
+        let source = self
+            .get_agent_definition(source_id)?
+            .ok_or_else(|| EngineError::Config(format!("Agent '{}' not found", source_id)))?;
+        let name = if new_name.trim().is_empty() {
+            format!("{} (copy)", source.name)
+        } else {
+            new_name.trim().to_string()
+        };
+        let clone_id = new_agent_id(&name);
+
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO agents (id, name, persona, model, skills, autonomy)
+             SELECT ?2, ?3, persona, model, skills, autonomy FROM agents WHERE id = ?1",
+            params![source_id, clone_id, name],
+        )?;
+        tx.execute(
+            "INSERT INTO agent_files (agent_id, file_name, content)
+             SELECT ?2, file_name, content FROM agent_files WHERE agent_id = ?1",
+            params![source_id, clone_id],
+        )?;
+        // The default agent has no standalone entry — give the clone one so it
+        // shows up alongside other user-created agents.
+        let copied = tx.execute(
+            "INSERT INTO project_agents
+                (project_id, agent_id, role, specialty, status, current_task, model, system_prompt, capabilities)
+             SELECT project_id, ?2, role, specialty, 'idle', NULL, model, system_prompt, capabilities
+             FROM project_agents WHERE project_id = '_standalone' AND agent_id = ?1",
+            params![source_id, clone_id],
+        )?;
+        if copied == 0 {
+            tx.execute(
+                "INSERT OR IGNORE INTO project_agents
+                    (project_id, agent_id, role, specialty, status, model, system_prompt, capabilities)
+                 VALUES ('_standalone', ?1, 'assistant', 'general', 'idle', ?2, ?3, '[]')",
+                params![clone_id, source.model, source.persona],
+            )?;
+        }
+
+        // Community skills scoped to the source agent are scoped to the clone too.
+        // The table is created lazily at app startup, so it may not exist yet.
+        let has_community_skills: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'community_skills')",
+            [],
+            |row| row.get(0),
+        )?;
+        let scoped: Vec<(String, Vec<String>)> = if !has_community_skills {
+            Vec::new()
+        } else {
+            let mut stmt = tx.prepare("SELECT id, agent_ids FROM community_skills")?;
+            let rows = stmt
+                .query_map([], |row| {
+                    let ids: String = row.get(1)?;
+                    Ok((row.get(0)?, serde_json::from_str(&ids).unwrap_or_default()))
+                })?
+                .filter_map(|r| r.ok())
+                .filter(|(_, ids): &(String, Vec<String>)| ids.iter().any(|a| a == source_id))
+                .collect();
+            rows
+        };
+        for (skill_id, mut agent_ids) in scoped {
+            agent_ids.push(clone_id.clone());
+            tx.execute(
+                "UPDATE community_skills SET agent_ids = ?1, updated_at = datetime('now') WHERE id = ?2",
+                params![serde_json::to_string(&agent_ids)?, skill_id],
+            )?;
+        }
+
+        if include_memories {
+            let memories: Vec<(String, String, i64, Option<Vec<u8>>)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT content, category, importance, embedding FROM memories WHERE agent_id = ?1",
+                )?;
+                let rows = stmt
+                    .query_map(params![source_id], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                    })?
+                    .filter_map(|r| r.ok())
+                    .collect();
+                rows
+            };
+            for (content, category, importance, embedding) in &memories {
+                let id = uuid::Uuid::new_v4().to_string();
+                tx.execute(
+                    "INSERT INTO memories (id, content, category, importance, embedding, agent_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![id, content, category, importance, embedding, clone_id],
+                )?;
+                tx.execute(
+                    "INSERT INTO memories_fts (id, content, category, agent_id) VALUES (?1, ?2, ?3, ?4)",
+                    params![id, content, category, clone_id],
+                )
+                .ok();
+            }
+            info!(
+                "[agents] Copied {} memories from {} to {}",
+                memories.len(),
+                source_id,
+                clone_id
+            );
+        }
+        tx.commit()?;
+        drop(conn);
+
+        info!(
+            "[agents] Cloned agent {} → {} ('{}')",
+            source_id, clone_id, name
+        );
+        self.get_agent_definition(&clone_id)?
+            .ok_or_else(|| EngineError::Other(format!("Clone {} was not saved", clone_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema_for_testing;
+    use crate::engine::types::AutonomyLevel;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        schema_for_testing(&conn);
+        SessionStore::from_connection(conn)
+    }
+
+    #[test]
+    fn default_agent_is_seeded_and_protected() {
+        let store = test_store();
+        let default = store
+            .get_agent_definition(DEFAULT_AGENT_ID)
+            .unwrap()
+            .unwrap();
+        assert_eq!(default.name, "Pawz");
+        assert!(default.persona.is_none());
+        assert!(default.skills.is_empty());
+        assert!(store.delete_agent_definition(DEFAULT_AGENT_ID).is_err());
+    }
+
+    #[test]
+    fn create_update_and_delete_agent() {
+        let store = test_store();
+        let mut agent = AgentDefinition {
+            id: "researcher".into(),
+            name: "Researcher".into(),
+            persona: Some("You dig up primary sources.".into()),
+            model: Some("claude-sonnet-4-20250514".into()),
+            skills: vec!["web_search".into(), "notes".into()],
+            autonomy: Default::default(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        store.upsert_agent_definition(&agent).unwrap();
+
+        let saved = store.get_agent_definition("researcher").unwrap().unwrap();
+        assert_eq!(
+            saved.persona.as_deref(),
+            Some("You dig up primary sources.")
+        );
+        assert_eq!(saved.model.as_deref(), Some("claude-sonnet-4-20250514"));
+        assert_eq!(saved.skills, vec!["web_search", "notes"]);
+        assert!(!saved.created_at.is_empty());
+
+        assert_eq!(saved.autonomy.level, AutonomyLevel::Assisted);
+
+        // Blank model falls back to the engine default
+        agent.model = Some("  ".into());
+        agent.autonomy.level = AutonomyLevel::Manual;
+        store.upsert_agent_definition(&agent).unwrap();
+        let saved = store.get_agent_definition("researcher").unwrap().unwrap();
+        assert!(saved.model.is_none());
+        assert_eq!(saved.autonomy.level, AutonomyLevel::Manual);
+
+        let ids: Vec<String> = store
+            .list_agent_definitions()
+            .unwrap()
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, vec!["default", "researcher"]);
+
+        store.delete_agent_definition("researcher").unwrap();
+        assert!(store.get_agent_definition("researcher").unwrap().is_none());
+    }
+
+    #[test]
+    fn clone_copies_config_and_skills_under_a_fresh_id() {
+        let store = test_store();
+        store
+            .upsert_agent_definition(&AgentDefinition {
+                id: "writer".into(),
+                name: "Writer".into(),
+                persona: Some("You write crisp prose.".into()),
+                model: Some("gpt-4o".into()),
+                skills: vec!["notion".into()],
+                autonomy: Default::default(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+        store
+            .set_agent_file("writer", "SOUL.md", "Be concise.")
+            .unwrap();
+        store
+            .store_memory(
+                "m1",
+                "Prefers British spelling",
+                "preference",
+                6,
+                None,
+                Some("writer"),
+            )
+            .unwrap();
+
+        let clone = store.clone_agent("writer", "Editor", false).unwrap();
+        assert_ne!(clone.id, "writer");
+        assert!(clone.id.starts_with("agent-editor-"));
+        assert_eq!(clone.name, "Editor");
+        assert_eq!(clone.persona.as_deref(), Some("You write crisp prose."));
+        assert_eq!(clone.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(clone.skills, vec!["notion"]);
+        let soul = store.get_agent_file(&clone.id, "SOUL.md").unwrap().unwrap();
+        assert_eq!(soul.content, "Be concise.");
+        assert!(store
+            .list_all_agents()
+            .unwrap()
+            .iter()
+            .any(|(_, a)| a.agent_id == clone.id));
+        // Memories stay with the source unless asked for
+        let conn = store.conn.lock();
+        let count = |agent: &str| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM memories WHERE agent_id = ?1",
+                params![agent],
+                |r| r.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(count(&clone.id), 0);
+        drop(conn);
+
+        let with_memories = store.clone_agent("writer", "", true).unwrap();
+        assert_eq!(with_memories.name, "Writer (copy)");
+        assert_ne!(with_memories.id, clone.id);
+        let conn = store.conn.lock();
+        let copied: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM memories WHERE agent_id = ?1",
+                params![with_memories.id],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(copied, 1);
+    }
+
+    #[test]
+    fn clone_extends_community_skill_scope() {
+        let store = test_store();
+        store.init_community_skills_table().unwrap();
+        store
+            .upsert_agent_definition(&AgentDefinition {
+                id: "writer".into(),
+                name: "Writer".into(),
+                persona: None,
+                model: None,
+                skills: vec![],
+                autonomy: Default::default(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+        store
+            .save_community_skill(&crate::engine::sessions::CommunitySkill {
+                id: "acme/skills/style".into(),
+                name: "Style".into(),
+                description: String::new(),
+                instructions: "Use the house style.".into(),
+                source: "acme/skills".into(),
+                enabled: true,
+                agent_ids: vec!["writer".into()],
+                installed_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+
+        let clone = store.clone_agent("writer", "Editor", false).unwrap();
+        let skills = store.list_community_skills().unwrap();
+        assert_eq!(skills[0].agent_ids, vec!["writer".to_string(), clone.id]);
+    }
+
+    #[test]
+    fn cloning_unknown_agent_fails() {
+        let store = test_store();
+        assert!(store.clone_agent("nobody", "Somebody", false).is_err());
+    }
+}
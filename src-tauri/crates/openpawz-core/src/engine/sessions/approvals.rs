@@ -0,0 +1,244 @@
+// Approvals — audit trail of HIL decisions and what came of them.
+// One row per gated tool call: who decided (user, rule, policy, timeout),
+// the decision, and — once the tool has run — its outcome and any on-chain
+// transaction hash. Listed for review via `engine_list_approvals`.
+//
+// §Security: arguments are stored as a redacted summary, never raw JSON.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use crate::engine::secret_scrub;
+use crate::engine::util::safe_truncate;
+use regex::Regex;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Max chars of the stored arguments summary.
+const ARGS_SUMMARY_MAX: usize = 500;
+/// Max chars kept per argument value.
+const ARG_VALUE_MAX: usize = 80;
+/// Max chars of the stored outcome preview.
+const OUTCOME_PREVIEW_MAX: usize = 300;
+
+/// Wallet-specific argument names redacted on top of the generic secret hints.
+const EXTRA_SECRET_ARGS: &[&str] = &["mnemonic", "seed"];
+
+/// Explorer links (`…/tx/<hash>`) cover both EVM and Solana tool output.
+static EXPLORER_TX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/tx/(0x[0-9a-fA-F]{64}|[1-9A-HJ-NP-Za-km-z]{64,90})").unwrap());
+static EVM_TX_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b0x[0-9a-fA-F]{64}\b").unwrap());
+
+/// One logged approval decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub id: i64,
+    pub session_id: String,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    /// Redacted `key=value, …` summary of the arguments.
+    pub args_summary: String,
+    /// "approved" or "denied".
+    pub decision: String,
+    /// "user", "rule", "autonomy", "agent_policy", "trading_policy", "timeout", …
+    pub decider: String,
+    pub created_at: String,
+    /// `None` until the tool has run (always `None` for denials).
+    pub success: Option<bool>,
+    pub outcome: Option<String>,
+    pub tx_hash: Option<String>,
+}
+
+/// Summarize tool arguments with secret-looking fields masked.
+pub fn redact_args(args_json: &str) -> String {
+    let summary = match serde_json::from_str::<serde_json::Value>(args_json) {
+        Ok(serde_json::Value::Object(map)) => map
+            .iter()
+            .map(|(k, v)| {
+                let lower = k.to_lowercase();
+                let value = if secret_scrub::is_secret_field(k)
+                    || EXTRA_SECRET_ARGS.iter().any(|m| lower.contains(m))
+                {
+                    "[redacted]".to_string()
+                } else {
+                    let raw = match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    safe_truncate(&raw, ARG_VALUE_MAX).to_string()
+                };
+                format!("{}={}", k, value)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => args_json.to_string(),
+    };
+    secret_scrub::scrub(safe_truncate(&summary, ARGS_SUMMARY_MAX))
+}
+
+/// Pull a transaction hash / signature out of a tool's output, if any.
+pub fn extract_tx_hash(output: &str) -> Option<String> {
+    EXPLORER_TX_RE
+        .captures(output)
+        .map(|c| c[1].to_string())
+        .or_else(|| EVM_TX_RE.find(output).map(|m| m.as_str().to_string()))
+}
+
+impl SessionStore {
+    /// Log a decision for a gated tool call. Returns the row id.
+    pub fn record_approval_decision(
+        &self,
+        session_id: &str,
+        tool_call_id: &str,
+        tool_name: &str,
+        args_json: &str,
+        approved: bool,
+        decider: &str,
+    ) -> EngineResult<i64> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO approvals (session_id, tool_call_id, tool_name, args_summary, decision, decider)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                session_id,
+                tool_call_id,
+                tool_name,
+                redact_args(args_json),
+                if approved { "approved" } else { "denied" },
+                decider,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Attach the execution outcome to approval `id`. The tx hash is
+    /// extracted from the output when present; denials are left untouched.
+    pub fn record_approval_outcome(
+        &self,
+        id: i64,
+        success: bool,
+        output: &str,
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "UPDATE approvals SET success = ?2, outcome = ?3, tx_hash = ?4
+             WHERE id = ?1 AND decision = 'approved'",
+            params![
+                id,
+                success,
+                secret_scrub::scrub(safe_truncate(output, OUTCOME_PREVIEW_MAX)),
+                extract_tx_hash(output),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent approvals first, optionally for one session only.
+    pub fn list_approvals(
+        &self,
+        session_id: Option<&str>,
+        limit: u32,
+    ) -> EngineResult<Vec<ApprovalRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, tool_call_id, tool_name, args_summary, decision, decider,
+                    created_at, success, outcome, tx_hash
+             FROM approvals WHERE (?1 IS NULL OR session_id = ?1)
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+        let records = stmt
+            .query_map(params![session_id, limit], |row| {
+                Ok(ApprovalRecord {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    tool_call_id: row.get(2)?,
+                    tool_name: row.get(3)?,
+                    args_summary: row.get(4)?,
+                    decision: row.get(5)?,
+                    decider: row.get(6)?,
+                    created_at: row.get(7)?,
+                    success: row.get(8)?,
+                    outcome: row.get(9)?,
+                    tx_hash: row.get(10)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    const SWAP_OUTPUT: &str = "[ok] Swap Confirmed\n\n0.5 ETH → ~1500 USDC\n\
+        Transaction: https://etherscan.io/tx/0x9f2c4a1b8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a39281706f5e4d3\n\
+        Status: success";
+
+    #[test]
+    fn approved_swap_records_decision_and_tx_hash() {
+        let store = test_store();
+        let id = store
+            .record_approval_decision(
+                "s1",
+                "call-1",
+                "dex_swap",
+                r#"{"token_in":"ETH","token_out":"USDC","amount":"0.5","private_key":"0xdeadbeef"}"#,
+                true,
+                "user",
+            )
+            .unwrap();
+        store
+            .record_approval_outcome(id, true, SWAP_OUTPUT)
+            .unwrap();
+
+        let records = store.list_approvals(Some("s1"), 10).unwrap();
+        assert_eq!(records.len(), 1);
+        let r = &records[0];
+        assert_eq!(r.tool_name, "dex_swap");
+        assert_eq!(r.decision, "approved");
+        assert_eq!(r.decider, "user");
+        assert_eq!(r.success, Some(true));
+        assert_eq!(
+            r.tx_hash.as_deref(),
+            Some("0x9f2c4a1b8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a39281706f5e4d3")
+        );
+        assert!(r.args_summary.contains("amount=0.5"));
+        assert!(r.args_summary.contains("private_key=[redacted]"));
+        assert!(!r.args_summary.contains("deadbeef"));
+    }
+
+    #[test]
+    fn denial_has_no_outcome() {
+        let store = test_store();
+        let id = store
+            .record_approval_decision("s1", "call-2", "dex_transfer", "{}", false, "timeout")
+            .unwrap();
+        // A stray outcome for a denied call must not be attached
+        store
+            .record_approval_outcome(id, true, SWAP_OUTPUT)
+            .unwrap();
+
+        let r = &store.list_approvals(None, 10).unwrap()[0];
+        assert_eq!(r.decision, "denied");
+        assert_eq!(r.success, None);
+        assert_eq!(r.tx_hash, None);
+        assert!(store.list_approvals(Some("other"), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn extracts_solana_signature_from_explorer_link() {
+        let sig = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+        let out = format!("| Transaction | [{0}](https://solscan.io/tx/{0}) |", sig);
+        assert_eq!(extract_tx_hash(&out).as_deref(), Some(sig));
+        assert_eq!(extract_tx_hash("no hash here"), None);
+    }
+}
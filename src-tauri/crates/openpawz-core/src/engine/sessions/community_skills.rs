@@ -1,6 +1,7 @@
 // Pawz Agent Engine — Community Skills Store
 // SQLite-backed community skill management for SessionStore.
 
+use super::skill_vault::note_skills_changed;
 use super::SessionStore;
 use crate::atoms::error::EngineResult;
 use serde::{Deserialize, Serialize};
@@ -69,6 +70,7 @@ impl SessionStore {
                 skill.source, skill.enabled as i32, agent_ids_json, skill.installed_at, skill.updated_at
             ],
         )?;
+        note_skills_changed();
         Ok(())
     }
 
@@ -111,6 +113,7 @@ impl SessionStore {
             "UPDATE community_skills SET enabled = ?1, updated_at = datetime('now') WHERE id = ?2",
             rusqlite::params![enabled as i32, id],
         )?;
+        note_skills_changed();
         Ok(())
     }
 
@@ -121,6 +124,7 @@ impl SessionStore {
             "DELETE FROM community_skills WHERE id = ?1",
             rusqlite::params![id],
         )?;
+        note_skills_changed();
         Ok(())
     }
 
@@ -133,6 +137,7 @@ impl SessionStore {
             "UPDATE community_skills SET agent_ids = ?1, updated_at = datetime('now') WHERE id = ?2",
             rusqlite::params![agent_ids_json, id],
         )?;
+        note_skills_changed();
         Ok(())
     }
 }
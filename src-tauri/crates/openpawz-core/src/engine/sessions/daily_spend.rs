@@ -0,0 +1,97 @@
+// sessions/daily_spend.rs — Per-agent spend counters for the daily budgets.
+// One row per (budget window, agent). The window is keyed by its start
+// instant (engine::budget_window), and the in-memory tracker is rebuilt from
+// the current window's rows at startup so the budgets hold across restarts.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailySpend {
+    pub window_start: String,
+    pub agent_id: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_create_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl SessionStore {
+    /// Add one round's usage to an agent's counters for the window.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_daily_spend(
+        &self,
+        window_start: &str,
+        agent_id: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_create_tokens: u64,
+        cost_usd: f64,
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO daily_spend
+                (window_start, agent_id, input_tokens, output_tokens,
+                 cache_read_tokens, cache_create_tokens, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(window_start, agent_id) DO UPDATE SET
+                input_tokens = input_tokens + ?3,
+                output_tokens = output_tokens + ?4,
+                cache_read_tokens = cache_read_tokens + ?5,
+                cache_create_tokens = cache_create_tokens + ?6,
+                cost_usd = cost_usd + ?7",
+            params![
+                window_start,
+                agent_id,
+                input_tokens as i64,
+                output_tokens as i64,
+                cache_read_tokens as i64,
+                cache_create_tokens as i64,
+                cost_usd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every agent's counters for the window, highest spend first.
+    pub fn list_daily_spend(&self, window_start: &str) -> EngineResult<Vec<DailySpend>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT window_start, agent_id, input_tokens, output_tokens,
+                    cache_read_tokens, cache_create_tokens, cost_usd
+             FROM daily_spend WHERE window_start = ?1
+             ORDER BY cost_usd DESC, agent_id ASC",
+        )?;
+        let rows = stmt
+            .query_map(params![window_start], |row| {
+                Ok(DailySpend {
+                    window_start: row.get(0)?,
+                    agent_id: row.get(1)?,
+                    input_tokens: row.get::<_, i64>(2)? as u64,
+                    output_tokens: row.get::<_, i64>(3)? as u64,
+                    cache_read_tokens: row.get::<_, i64>(4)? as u64,
+                    cache_create_tokens: row.get::<_, i64>(5)? as u64,
+                    cost_usd: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// One agent's spend in the window in USD (0 when it hasn't run).
+    pub fn agent_daily_spend_usd(&self, window_start: &str, agent_id: &str) -> EngineResult<f64> {
+        let conn = self.conn.lock();
+        let cost = conn
+            .query_row(
+                "SELECT cost_usd FROM daily_spend WHERE window_start = ?1 AND agent_id = ?2",
+                params![window_start, agent_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(cost.unwrap_or(0.0))
+    }
+}
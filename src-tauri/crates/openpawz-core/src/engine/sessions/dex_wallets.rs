@@ -0,0 +1,89 @@
+// Named DEX wallets — one row per wallet (id → label, address, encrypted key).
+// Lives alongside the skill vault tables. The key column holds the vault-
+// encrypted private key; callers encrypt before insert and decrypt in Rust
+// only when signing. The legacy single wallet (DEX_PRIVATE_KEY credential)
+// is untouched.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Public view of a named wallet — never carries the key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DexWalletRecord {
+    pub id: String,
+    pub label: String,
+    pub address: String,
+    pub created_at: String,
+}
+
+impl SessionStore {
+    pub fn insert_dex_wallet(
+        &self,
+        id: &str,
+        label: &str,
+        address: &str,
+        encrypted_key: &str,
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO dex_wallets (id, label, address, encrypted_key) VALUES (?1, ?2, ?3, ?4)",
+            params![id, label, address, encrypted_key],
+        )?;
+        Ok(())
+    }
+
+    /// All named wallets, oldest first.
+    pub fn list_dex_wallets(&self) -> EngineResult<Vec<DexWalletRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, label, address, created_at FROM dex_wallets ORDER BY created_at, rowid",
+        )?;
+        let wallets = stmt
+            .query_map([], |row| {
+                Ok(DexWalletRecord {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    address: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(wallets)
+    }
+
+    /// Look a wallet up by id or (case-insensitive) label. Returns the
+    /// record and its encrypted key.
+    pub fn find_dex_wallet(
+        &self,
+        selector: &str,
+    ) -> EngineResult<Option<(DexWalletRecord, String)>> {
+        let conn = self.conn.lock();
+        let found = conn
+            .query_row(
+                "SELECT id, label, address, created_at, encrypted_key FROM dex_wallets
+                 WHERE id = ?1 OR label = ?1 COLLATE NOCASE LIMIT 1",
+                params![selector.trim()],
+                |row| {
+                    Ok((
+                        DexWalletRecord {
+                            id: row.get(0)?,
+                            label: row.get(1)?,
+                            address: row.get(2)?,
+                            created_at: row.get(3)?,
+                        },
+                        row.get(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(found)
+    }
+
+    pub fn delete_dex_wallet(&self, id: &str) -> EngineResult<bool> {
+        let conn = self.conn.lock();
+        let n = conn.execute("DELETE FROM dex_wallets WHERE id = ?1", params![id])?;
+        Ok(n > 0)
+    }
+}
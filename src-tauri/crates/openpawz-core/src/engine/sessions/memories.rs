@@ -57,12 +57,50 @@ impl SessionStore {
     pub fn delete_memory(&self, id: &str) -> EngineResult<()> {
         let conn = self.conn.lock();
         conn.execute("DELETE FROM memories WHERE id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM memory_chunks WHERE memory_id = ?1",
+            params![id],
+        )?;
         // Sync FTS5 index
         conn.execute("DELETE FROM memories_fts WHERE id = ?1", params![id])
             .ok();
         Ok(())
     }
 
+    /// Replace the chunk embeddings of a long memory (see memory::chunk_content).
+    pub fn store_memory_chunks(
+        &self,
+        memory_id: &str,
+        chunks: &[(String, Option<Vec<u8>>)],
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM memory_chunks WHERE memory_id = ?1",
+            params![memory_id],
+        )?;
+        for (index, (content, embedding)) in chunks.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO memory_chunks (memory_id, chunk_index, content, embedding)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![memory_id, index as i64, content, embedding],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Number of chunk rows stored for a memory.
+    pub fn count_memory_chunks(&self, memory_id: &str) -> EngineResult<usize> {
+        let conn = self.conn.lock();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memory_chunks WHERE memory_id = ?1",
+            params![memory_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     /// Get a single memory by ID.
     pub fn get_memory_by_id(&self, id: &str) -> EngineResult<Option<Memory>> {
         let conn = self.conn.lock();
@@ -97,6 +135,11 @@ impl SessionStore {
                 id
             )));
         }
+        // The chunks describe the old content
+        conn.execute(
+            "DELETE FROM memory_chunks WHERE memory_id = ?1",
+            params![id],
+        )?;
         // Sync FTS5 index
         conn.execute(
             "INSERT OR REPLACE INTO memories_fts (id, content, category, agent_id) \
@@ -168,6 +211,8 @@ impl SessionStore {
     }
 
     /// Search memories by cosine similarity against a query embedding.
+    /// Chunks of long memories are scored too; each memory is returned once,
+    /// with its best score.
     /// Falls back to keyword search if no embeddings are stored.
     pub fn search_memories_by_embedding(
         &self,
@@ -179,7 +224,11 @@ impl SessionStore {
         let conn = self.conn.lock();
 
         let mut stmt = conn.prepare(
-            "SELECT id, content, category, importance, embedding, created_at, agent_id FROM memories WHERE embedding IS NOT NULL"
+            "SELECT id, content, category, importance, embedding, created_at, agent_id FROM memories WHERE embedding IS NOT NULL
+             UNION ALL
+             SELECT m.id, m.content, m.category, m.importance, c.embedding, m.created_at, m.agent_id
+             FROM memory_chunks c JOIN memories m ON m.id = c.memory_id
+             WHERE c.embedding IS NOT NULL"
         )?;
 
         let mut scored: Vec<(Memory, f64)> = stmt
@@ -237,6 +286,9 @@ impl SessionStore {
             .collect();
 
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        // Best-scoring row first, so this keeps each memory's best match
+        let mut seen = std::collections::HashSet::new();
+        scored.retain(|(m, _)| seen.insert(m.id.clone()));
         scored.truncate(limit);
 
         Ok(scored.into_iter().map(|(m, _)| m).collect())
@@ -373,6 +425,56 @@ impl SessionStore {
         Ok(memories)
     }
 
+    /// Get every memory with its raw embedding blob, oldest first (for portable export).
+    pub fn list_memories_for_export(&self) -> EngineResult<Vec<(Memory, Option<Vec<u8>>)>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, content, category, importance, created_at, agent_id, embedding
+             FROM memories ORDER BY created_at ASC",
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                let mem = Memory::from_row(row)?;
+                let embedding: Option<Vec<u8>> = row.get(6)?;
+                Ok((mem, embedding))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(rows)
+    }
+
+    /// Insert a memory keeping its original `created_at` (portable import).
+    /// Returns false without touching anything if the id already exists.
+    pub fn restore_memory(&self, mem: &Memory, embedding: Option<&[u8]>) -> EngineResult<bool> {
+        let conn = self.conn.lock();
+        let aid = mem.agent_id.as_deref().unwrap_or("");
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO memories (id, content, category, importance, embedding, agent_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                mem.id,
+                mem.content,
+                mem.category,
+                mem.importance as i32,
+                embedding,
+                aid,
+                mem.created_at
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        // Sync FTS5 index
+        conn.execute(
+            "INSERT OR REPLACE INTO memories_fts (id, content, category, agent_id) VALUES (?1, ?2, ?3, ?4)",
+            params![mem.id, mem.content, mem.category, aid],
+        ).ok(); // Best-effort FTS sync
+        Ok(true)
+    }
+
     /// List memories that have no embedding vector (for backfill).
     pub fn list_memories_without_embeddings(&self, limit: usize) -> EngineResult<Vec<Memory>> {
         let conn = self.conn.lock();
@@ -400,6 +502,87 @@ impl SessionStore {
         Ok(())
     }
 
+    // ── Retrieval tracking ─────────────────────────────────────────────
+
+    /// Bump `retrieval_count` and stamp `last_retrieved_at` for every id in one
+    /// statement. Called after each search with the ids that made the top results.
+    pub fn record_memory_retrievals(&self, ids: &[String]) -> EngineResult<usize> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let conn = self.conn.lock();
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "UPDATE memories
+             SET retrieval_count = retrieval_count + 1, last_retrieved_at = datetime('now')
+             WHERE id IN ({})",
+            placeholders
+        );
+        let updated = conn.execute(&sql, rusqlite::params_from_iter(ids.iter()))?;
+        Ok(updated)
+    }
+
+    /// Look up retrieval counts for a set of memory ids (missing ids are omitted).
+    pub fn get_memory_retrieval_counts(
+        &self,
+        ids: &[String],
+    ) -> EngineResult<std::collections::HashMap<String, i64>> {
+        let mut counts = std::collections::HashMap::new();
+        if ids.is_empty() {
+            return Ok(counts);
+        }
+        let conn = self.conn.lock();
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "SELECT id, retrieval_count FROM memories WHERE id IN ({})",
+            placeholders
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        for (id, count) in stmt
+            .query_map(rusqlite::params_from_iter(ids.iter()), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+        {
+            counts.insert(id, count);
+        }
+        Ok(counts)
+    }
+
+    /// Delete memories older than `max_age_secs` that were retrieved fewer than
+    /// `min_retrievals` times and not retrieved within the same window.
+    /// Returns the number of memories removed.
+    pub fn prune_stale_memories(
+        &self,
+        max_age_secs: i64,
+        min_retrievals: i64,
+    ) -> EngineResult<usize> {
+        let conn = self.conn.lock();
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(max_age_secs);
+        let cutoff_str = cutoff.format("%Y-%m-%d %H:%M:%S").to_string();
+        let stale_filter = "created_at < ?1 AND retrieval_count < ?2
+             AND (last_retrieved_at IS NULL OR last_retrieved_at < ?1)";
+
+        // Sync FTS5 index first — the filter can't be evaluated once rows are gone
+        conn.execute(
+            &format!(
+                "DELETE FROM memories_fts WHERE id IN (SELECT id FROM memories WHERE {})",
+                stale_filter
+            ),
+            params![cutoff_str, min_retrievals],
+        )
+        .ok();
+        let deleted = conn.execute(
+            &format!("DELETE FROM memories WHERE {}", stale_filter),
+            params![cutoff_str, min_retrievals],
+        )?;
+        conn.execute(
+            "DELETE FROM memory_chunks WHERE memory_id NOT IN (SELECT id FROM memories)",
+            [],
+        )?;
+        Ok(deleted)
+    }
+
     /// Get memories created today — lightweight daily context injection.
     /// Returns a compact summary string (max 10 entries, highest importance first).
     pub fn get_todays_memories(&self, agent_id: &str) -> EngineResult<Option<String>> {
@@ -1,33 +1,95 @@
 use super::SessionStore;
-use crate::atoms::error::EngineResult;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::tool_result_policy;
 use crate::engine::types::{ContentBlock, Message, MessageContent, Role, StoredMessage, ToolCall};
-use rusqlite::params;
+use log::info;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const MESSAGE_COLUMNS: &str =
+    "id, session_id, role, content, tool_calls_json, tool_call_id, name, created_at, incomplete, \
+     reasoning";
+
+fn message_from_row(row: &Row) -> rusqlite::Result<StoredMessage> {
+    Ok(StoredMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        tool_calls_json: row.get(4)?,
+        tool_call_id: row.get(5)?,
+        name: row.get(6)?,
+        created_at: row.get(7)?,
+        incomplete: row.get(8)?,
+        reasoning: row.get(9)?,
+    })
+}
+
+/// Give every tool call in `json` a non-empty id that is unique within the
+/// message. Missing or duplicate ids become `call_{message_id}_{index}`, so
+/// the result is the same every time the message is stored or replayed.
+/// Unparseable JSON is returned unchanged.
+fn normalize_tool_call_ids(message_id: &str, json: &str) -> String {
+    let Ok(mut calls) = serde_json::from_str::<Vec<ToolCall>>(json) else {
+        return json.to_string();
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut changed = false;
+    for (index, call) in calls.iter_mut().enumerate() {
+        if call.id.trim().is_empty() || !seen.insert(call.id.clone()) {
+            call.id = format!("call_{}_{}", message_id, index);
+            seen.insert(call.id.clone());
+            changed = true;
+        }
+    }
+    if !changed {
+        return json.to_string();
+    }
+    serde_json::to_string(&calls).unwrap_or_else(|_| json.to_string())
+}
 
 impl SessionStore {
     // ── Message CRUD ───────────────────────────────────────────────────
 
+    /// Store a message. Tool linkage is normalized first: every tool call
+    /// gets a stable id, and a tool result whose `tool_call_id` doesn't match
+    /// an unanswered call is re-linked to one (by tool name, then order) or
+    /// rejected as an orphan.
     pub fn add_message(&self, msg: &StoredMessage) -> EngineResult<()> {
         let conn = self.conn.lock();
 
+        let tool_calls_json = match (msg.role.as_str(), msg.tool_calls_json.as_deref()) {
+            ("assistant", Some(json)) => Some(normalize_tool_call_ids(&msg.id, json)),
+            _ => msg.tool_calls_json.clone(),
+        };
+        let tool_call_id = if msg.role == "tool" {
+            Some(Self::link_tool_result(&conn, msg)?)
+        } else {
+            msg.tool_call_id.clone()
+        };
+
         conn.execute(
-            "INSERT INTO messages (id, session_id, role, content, tool_calls_json, tool_call_id, name)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO messages (id, session_id, role, content, tool_calls_json, tool_call_id, name, incomplete, reasoning)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 msg.id,
                 msg.session_id,
                 msg.role,
                 msg.content,
-                msg.tool_calls_json,
-                msg.tool_call_id,
+                tool_calls_json,
+                tool_call_id,
                 msg.name,
+                msg.incomplete,
+                msg.reasoning,
             ],
         )?;
 
-        // Update session stats — increment rather than re-counting
+        // Update session stats — increment rather than re-counting. A new
+        // message brings an archived session back into the list.
         conn.execute(
             "UPDATE sessions SET
                 message_count = message_count + 1,
-                updated_at = datetime('now')
+                updated_at = datetime('now'),
+                archived = 0
              WHERE id = ?1",
             params![msg.session_id],
         )?;
@@ -35,6 +97,243 @@ impl SessionStore {
         Ok(())
     }
 
+    /// The call id a tool result should be stored under: its own when it
+    /// answers a pending call of the session's latest tool-calling assistant
+    /// message, otherwise the pending call with the same tool name, or else
+    /// the first pending call.
+    fn link_tool_result(conn: &Connection, msg: &StoredMessage) -> EngineResult<String> {
+        let orphan = || {
+            EngineError::Other(format!(
+                "Orphaned tool result {} in session {}: no pending tool call",
+                msg.id, msg.session_id
+            ))
+        };
+        let (call_rowid, calls_json): (i64, String) = conn
+            .query_row(
+                "SELECT rowid, tool_calls_json FROM messages
+                 WHERE session_id = ?1 AND role = 'assistant' AND tool_calls_json IS NOT NULL
+                 ORDER BY created_at DESC, rowid DESC LIMIT 1",
+                params![msg.session_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .ok_or_else(orphan)?;
+        let calls: Vec<ToolCall> = serde_json::from_str(&calls_json).unwrap_or_default();
+
+        let mut stmt = conn.prepare(
+            "SELECT tool_call_id FROM messages
+             WHERE session_id = ?1 AND role = 'tool' AND rowid > ?2 AND tool_call_id IS NOT NULL",
+        )?;
+        let answered: Vec<String> = stmt
+            .query_map(params![msg.session_id, call_rowid], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        let pending: Vec<&ToolCall> = calls
+            .iter()
+            .filter(|tc| !answered.contains(&tc.id))
+            .collect();
+
+        if let Some(id) = msg.tool_call_id.as_deref() {
+            if pending.iter().any(|tc| tc.id == id) {
+                return Ok(id.to_string());
+            }
+        }
+        let relinked = pending
+            .iter()
+            .find(|tc| msg.name.as_deref() == Some(tc.function.name.as_str()))
+            .or_else(|| pending.first())
+            .ok_or_else(orphan)?;
+        log::warn!(
+            "[engine] Re-linked tool result {} from {:?} to call {}",
+            msg.id,
+            msg.tool_call_id,
+            relinked.id
+        );
+        Ok(relinked.id.clone())
+    }
+
+    // ── Partial (streaming) replies ────────────────────────────────────
+
+    /// Save `content` as the in-progress assistant reply `message_id`. The
+    /// row is created on the first save and stays flagged `incomplete`
+    /// until discarded.
+    pub fn save_partial_reply(
+        &self,
+        session_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE messages SET content = ?1 WHERE id = ?2 AND incomplete = 1",
+            params![content, message_id],
+        )?;
+        if updated == 0 {
+            conn.execute(
+                "INSERT INTO messages (id, session_id, role, content, incomplete)
+                 VALUES (?1, ?2, 'assistant', ?3, 1)",
+                params![message_id, session_id, content],
+            )?;
+            conn.execute(
+                "UPDATE sessions SET
+                    message_count = message_count + 1,
+                    updated_at = datetime('now')
+                 WHERE id = ?1",
+                params![session_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Remove an in-progress reply once the run's final messages are stored.
+    /// Completed messages are never touched.
+    pub fn discard_partial_reply(&self, message_id: &str) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        let Some(session_id) = conn
+            .query_row(
+                "SELECT session_id FROM messages WHERE id = ?1 AND incomplete = 1",
+                params![message_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?
+        else {
+            return Ok(());
+        };
+        conn.execute("DELETE FROM messages WHERE id = ?1", params![message_id])?;
+        conn.execute(
+            "UPDATE sessions SET
+                message_count = (SELECT COUNT(*) FROM messages WHERE session_id = ?1),
+                updated_at = datetime('now')
+             WHERE id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete every message in `message_id`'s session that comes after it,
+    /// so the conversation can continue from that point. Returns the number
+    /// of messages deleted.
+    pub fn truncate_session_after(&self, message_id: &str) -> EngineResult<usize> {
+        let conn = self.conn.lock();
+        let (session_id, _) = Self::message_session_and_role(&conn, message_id)?;
+        Self::truncate_after_locked(&conn, &session_id, message_id)
+    }
+
+    /// Replace the text of user message `message_id` and drop the assistant
+    /// and tool turns that answered it (everything after it), ready for the
+    /// agent to answer the edited message. Returns the updated message.
+    pub fn edit_and_resend(
+        &self,
+        session_id: &str,
+        message_id: &str,
+        new_content: &str,
+    ) -> EngineResult<StoredMessage> {
+        let conn = self.conn.lock();
+        let (owner, role) = Self::message_session_and_role(&conn, message_id)?;
+        if owner != session_id {
+            return Err(EngineError::Other(format!(
+                "Message {} is not in session {}",
+                message_id, session_id
+            )));
+        }
+        if role != "user" {
+            return Err(EngineError::Other(format!(
+                "Only user messages can be edited (message {} is {})",
+                message_id, role
+            )));
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE messages SET content = ?1 WHERE id = ?2",
+            params![new_content, message_id],
+        )?;
+        let dropped = Self::truncate_after_locked(&tx, session_id, message_id)?;
+        let message = tx.query_row(
+            &format!("SELECT {} FROM messages WHERE id = ?1", MESSAGE_COLUMNS),
+            params![message_id],
+            message_from_row,
+        )?;
+        tx.commit()?;
+
+        info!(
+            "[engine] Edited message {} in session {} — dropped {} later message(s)",
+            message_id, session_id, dropped
+        );
+        Ok(message)
+    }
+
+    /// Drop the reply to the session's last user message — the trailing
+    /// assistant turns along with their tool calls and results — so the agent
+    /// can answer it again. Returns the user message that is kept.
+    pub fn regenerate_last(&self, session_id: &str) -> EngineResult<StoredMessage> {
+        let conn = self.conn.lock();
+        let last_user = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM messages WHERE session_id = ?1 AND role = 'user'
+                     ORDER BY created_at DESC, rowid DESC LIMIT 1",
+                    MESSAGE_COLUMNS
+                ),
+                params![session_id],
+                message_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| {
+                EngineError::Other(format!(
+                    "Session {} has no user message to regenerate from",
+                    session_id
+                ))
+            })?;
+        let dropped = Self::truncate_after_locked(&conn, session_id, &last_user.id)?;
+        info!(
+            "[engine] Regenerating in session {} — dropped {} trailing message(s)",
+            session_id, dropped
+        );
+        Ok(last_user)
+    }
+
+    fn message_session_and_role(
+        conn: &Connection,
+        message_id: &str,
+    ) -> EngineResult<(String, String)> {
+        conn.query_row(
+            "SELECT session_id, role FROM messages WHERE id = ?1",
+            params![message_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| EngineError::Other(format!("Message {} not found", message_id)))
+    }
+
+    /// Delete the messages ordered after `message_id` (same ordering as
+    /// `get_messages`) and refresh the session's stats.
+    fn truncate_after_locked(
+        conn: &Connection,
+        session_id: &str,
+        message_id: &str,
+    ) -> EngineResult<usize> {
+        let deleted = conn.execute(
+            "DELETE FROM messages
+             WHERE session_id = ?1
+               AND rowid IN (
+                 SELECT m.rowid FROM messages m, messages t
+                 WHERE t.id = ?2 AND m.session_id = ?1
+                   AND (m.created_at > t.created_at
+                        OR (m.created_at = t.created_at AND m.rowid > t.rowid))
+               )",
+            params![session_id, message_id],
+        )?;
+        conn.execute(
+            "UPDATE sessions SET
+                message_count = (SELECT COUNT(*) FROM messages WHERE session_id = ?1),
+                updated_at = datetime('now')
+             WHERE id = ?1",
+            params![session_id],
+        )?;
+        Ok(deleted)
+    }
+
     /// Load raw conversation messages as (role, content) pairs.
     /// Used by the Engram ContextBuilder for budget-aware trimming.
     pub fn load_conversation_raw(
@@ -45,6 +344,20 @@ impl SessionStore {
         self.get_messages(session_id, 50)
     }
 
+    /// Text of the earliest user message in a session, if any.
+    pub fn first_user_message(&self, session_id: &str) -> EngineResult<Option<String>> {
+        let conn = self.conn.lock();
+        let content = conn
+            .query_row(
+                "SELECT content FROM messages WHERE session_id = ?1 AND role = 'user'
+                 ORDER BY created_at ASC, rowid ASC LIMIT 1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(content)
+    }
+
     pub fn get_messages(&self, session_id: &str, limit: i64) -> EngineResult<Vec<StoredMessage>> {
         let conn = self.conn.lock();
 
@@ -54,27 +367,16 @@ impl SessionStore {
         // messages and silently drops the user's latest message once the
         // session exceeds N messages — causing the model to answer stale context.
         // Uses rowid as tiebreaker for messages with identical timestamps.
-        let mut stmt = conn.prepare(
-            "SELECT id, session_id, role, content, tool_calls_json, tool_call_id, name, created_at
-             FROM (
-               SELECT id, session_id, role, content, tool_calls_json, tool_call_id, name, created_at, rowid
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {cols} FROM (
+               SELECT {cols}, rowid
                FROM messages WHERE session_id = ?1 ORDER BY created_at DESC, rowid DESC LIMIT ?2
              ) ORDER BY created_at ASC, rowid ASC",
-        )?;
+            cols = MESSAGE_COLUMNS
+        ))?;
 
         let messages = stmt
-            .query_map(params![session_id, limit], |row| {
-                Ok(StoredMessage {
-                    id: row.get(0)?,
-                    session_id: row.get(1)?,
-                    role: row.get(2)?,
-                    content: row.get(3)?,
-                    tool_calls_json: row.get(4)?,
-                    tool_call_id: row.get(5)?,
-                    name: row.get(6)?,
-                    created_at: row.get(7)?,
-                })
-            })?
+            .query_map(params![session_id, limit], message_from_row)?
             .filter_map(|r| r.ok())
             .collect();
 
@@ -135,6 +437,12 @@ impl SessionStore {
             });
         }
 
+        // ── Old tool results → placeholders (configurable) ─────────────
+        // Large outputs the model has already acted on are elided from the
+        // context; the stored messages keep the full text.
+        let tool_result_policy = tool_result_policy::load_config(self);
+        tool_result_policy::apply(&mut messages, &tool_result_policy);
+
         // ── Agent-scoped history filtering (VS Code pattern) ───────────
         // Non-default agents only see messages relevant to their context.
         // We skip delegate-agent tool results (messages with name starting
@@ -481,89 +789,65 @@ impl SessionStore {
         }
     }
 
-    /// Ensure every assistant message with tool_calls has matching tool_result
-    /// messages immediately after it.  Orphaned tool_use IDs (from context
-    /// truncation or prior crashes) cause Anthropic to return HTTP 400.
+    /// Ensure every assistant message with tool_calls is followed by exactly
+    /// one tool_result per call, in call order.  Orphaned tool_use IDs (from
+    /// context truncation or prior crashes) cause Anthropic to return HTTP 400,
+    /// and results nobody asked for confuse every provider.
     ///
-    /// Strategy:
-    /// 1. Remove leading orphaned tool-result messages that have no preceding
-    ///    assistant message with tool_calls.
-    /// 2. For each assistant message with tool_calls, collect the set of
-    ///    tool_call IDs and check the immediately following messages.  Inject
-    ///    a synthetic tool_result for any missing ID.
+    /// Strategy, in one pass:
+    /// 1. Tool results are only kept in the block right after the assistant
+    ///    message that called them — any other tool message is an orphan
+    ///    and is dropped, as are results for unknown IDs and duplicates.
+    /// 2. Within a block, results are re-ordered to match the calls, and a
+    ///    synthetic result is injected for any call without one.  System
+    ///    messages injected between the call and its results move after them.
     fn sanitize_tool_pairs(messages: &mut Vec<Message>) {
-        use std::collections::HashSet;
-
-        // ── Pass 1: strip leading orphan tool results ──────────────────
-        // After truncation the first non-system messages might be tool results
-        // whose parent assistant message was dropped.
-        let first_non_system = messages
-            .iter()
-            .position(|m| m.role != Role::System)
-            .unwrap_or(0);
-        let mut strip_end = first_non_system;
-        while strip_end < messages.len() && messages[strip_end].role == Role::Tool {
-            strip_end += 1;
-        }
-        if strip_end > first_non_system {
-            let removed = strip_end - first_non_system;
-            log::warn!(
-                "[engine] Removing {} orphaned leading tool_result messages",
-                removed
-            );
-            messages.drain(first_non_system..strip_end);
-        }
-
-        // ── Pass 2: ensure every assistant+tool_calls has matching results ─
-        let mut i = 0;
-        while i < messages.len() {
-            let has_tc = messages[i].role == Role::Assistant
-                && messages[i]
-                    .tool_calls
-                    .as_ref()
-                    .map(|tc| !tc.is_empty())
-                    .unwrap_or(false);
-
-            if !has_tc {
-                i += 1;
-                continue;
-            }
+        let mut sanitized = Vec::with_capacity(messages.len());
+        let mut orphans = 0;
+        let mut injected = 0;
+        let mut iter = std::mem::take(messages).into_iter().peekable();
+
+        while let Some(msg) = iter.next() {
+            let expected_ids: Vec<String> = match (&msg.role, &msg.tool_calls) {
+                (Role::Tool, _) => {
+                    orphans += 1;
+                    continue;
+                }
+                (Role::Assistant, Some(calls)) if !calls.is_empty() => {
+                    calls.iter().map(|tc| tc.id.clone()).collect()
+                }
+                _ => {
+                    sanitized.push(msg);
+                    continue;
+                }
+            };
 
-            // Collect expected tool_call IDs from this assistant message
-            let expected_ids: Vec<String> = messages[i]
-                .tool_calls
-                .as_ref()
-                .unwrap()
-                .iter()
-                .map(|tc| tc.id.clone())
-                .collect();
-
-            // Scan following messages for tool results, skipping System messages
-            // (context injections can insert System messages between assistant
-            // and tool-result blocks).
-            let mut found_ids = HashSet::new();
-            let mut j = i + 1;
-            while j < messages.len() {
-                match messages[j].role {
+            let mut results: Vec<Option<Message>> = expected_ids.iter().map(|_| None).collect();
+            let mut interleaved = Vec::new();
+            while let Some(next) = iter.peek() {
+                match next.role {
                     Role::Tool => {
-                        if let Some(ref tcid) = messages[j].tool_call_id {
-                            found_ids.insert(tcid.clone());
+                        let result = iter.next().expect("peeked");
+                        let slot = result
+                            .tool_call_id
+                            .as_ref()
+                            .and_then(|id| expected_ids.iter().position(|e| e == id));
+                        match slot {
+                            Some(k) if results[k].is_none() => results[k] = Some(result),
+                            _ => orphans += 1,
                         }
-                        j += 1;
-                    }
-                    Role::System => {
-                        // Skip injected system messages — don't break the scan
-                        j += 1;
                     }
+                    // Context injections can sit between a call and its results
+                    Role::System => interleaved.push(iter.next().expect("peeked")),
                     _ => break,
                 }
             }
 
-            // Inject synthetic results for any missing tool_call IDs
-            let mut injected = 0;
-            for expected_id in &expected_ids {
-                if !found_ids.contains(expected_id) {
-                    let synthetic = Message {
+            sanitized.push(msg);
+            for (result, expected_id) in results.into_iter().zip(&expected_ids) {
+                sanitized.push(result.unwrap_or_else(|| {
+                    injected += 1;
+                    Message {
                         role: Role::Tool,
                         content: MessageContent::Text(
                             "[Tool execution was interrupted or result was lost.]".into(),
@@ -571,27 +855,24 @@ impl SessionStore {
                         tool_calls: None,
                         tool_call_id: Some(expected_id.clone()),
                         name: Some("_synthetic".into()),
-                    };
-                    // Insert right after the assistant message (at position i+1+injected)
-                    messages.insert(i + 1 + injected, synthetic);
-                    injected += 1;
-                }
-            }
-
-            if injected > 0 {
-                log::warn!(
-                    "[engine] Injected {} synthetic tool_result(s) for orphaned tool_use IDs",
-                    injected
-                );
+                    }
+                }));
             }
+            sanitized.extend(interleaved);
+        }
 
-            // Advance past this assistant message + all following tool/system results
-            i += 1;
-            while i < messages.len()
-                && (messages[i].role == Role::Tool || messages[i].role == Role::System)
-            {
-                i += 1;
-            }
+        if orphans > 0 {
+            log::warn!(
+                "[engine] Removed {} orphaned or duplicate tool_result message(s)",
+                orphans
+            );
+        }
+        if injected > 0 {
+            log::warn!(
+                "[engine] Injected {} synthetic tool_result(s) for orphaned tool_use IDs",
+                injected
+            );
         }
+        *messages = sanitized;
     }
 }
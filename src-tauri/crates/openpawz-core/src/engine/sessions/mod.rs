@@ -10,10 +10,20 @@
 //   trades         — trade history insert/query/summary
 //   positions      — stop-loss / take-profit position tracking
 //   agent_files    — soul/persona file CRUD + context composition
+//   agents         — agent definitions (name, persona, default model, skills)
 //   memories       — vector+FTS memory store + search
 //   tasks          — task CRUD, cron scheduling, task agents
 //   projects       — project CRUD, project agents, message bus
 //   embedding      — bytes_to_f32_vec, f32_vec_to_bytes, cosine_similarity
+//   run_traces     — per-run LLM/tool span traces for the Inspector timeline
+//   approvals      — HIL approval decisions linked to their outcomes
+//   dex_wallets    — named DEX wallets (label, address, encrypted key)
+//   agent_notes    — per-agent scratchpad notes (exact-key, optional session scope)
+//   agent_todos    — per-agent subtask checklist (pending → done)
+//   reminders      — timed reminders (pending → fired | cancelled)
+//   daily_spend    — per-agent daily token/cost counters for budgets
+//   session_chunks — chunked transcripts + embeddings for session search
+//   secure_delete  — "forget this conversation": zero, delete, VACUUM
 
 use crate::atoms::error::EngineResult;
 use log::info;
@@ -25,12 +35,18 @@ use std::sync::Arc;
 
 mod agent_files;
 mod agent_messages;
+mod agent_notes;
+mod agent_todos;
+pub mod agents;
+pub mod approvals;
 mod canvas;
 pub mod community_skills;
 mod config;
+mod daily_spend;
 mod dashboard_tabs;
 mod dashboard_windows;
 mod dashboards;
+mod dex_wallets;
 pub mod embedding;
 pub mod engram;
 mod flows;
@@ -38,9 +54,14 @@ mod memories;
 mod messages;
 mod positions;
 mod projects;
+mod reminders;
+mod run_traces;
 pub mod schema;
+mod secure_delete;
+mod session_chunks;
 #[allow(clippy::module_inception)]
 mod sessions;
+mod skill_kv;
 mod skill_outputs;
 mod skill_storage;
 mod skill_vault;
@@ -52,11 +73,22 @@ mod trades;
 
 // ── Re-exports (preserve crate::engine::sessions::* API) ─────────────────────
 
+pub use agent_notes::AgentNote;
+pub use agent_todos::{AgentTodo, TODO_DONE, TODO_PENDING};
+pub use agents::DEFAULT_AGENT_ID;
+pub use approvals::ApprovalRecord;
 pub use community_skills::get_community_skill_instructions;
 pub use community_skills::CommunitySkill;
+pub use daily_spend::DailySpend;
+pub use dex_wallets::DexWalletRecord;
 pub use embedding::f32_vec_to_bytes;
+pub use reminders::Reminder;
+pub use run_traces::RunTraceSpan;
+pub use secure_delete::SecureDeleteReport;
+pub use session_chunks::SessionChunk;
 pub use skill_outputs::SkillOutput;
 pub use skill_storage::SkillStorageItem;
+pub use skill_vault::{note_skills_changed, skill_generation};
 
 /// Get the path to the engine's SQLite database.
 pub fn engine_db_path() -> PathBuf {
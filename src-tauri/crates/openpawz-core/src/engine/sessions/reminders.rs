@@ -0,0 +1,153 @@
+// sessions/reminders.rs — Timed reminders set by agents.
+// A reminder is pending until it fires or is cancelled. Fire times are
+// stored in the fixed-width form from `reminders::format_fire_at`, so due
+// detection is a plain string comparison.
+
+use super::SessionStore;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::reminders::format_fire_at;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// A stored reminder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub agent_id: String,
+    pub text: String,
+    /// UTC, RFC 3339.
+    pub fire_at: String,
+    /// "pending", "fired" or "cancelled".
+    pub status: String,
+    /// Channel to also deliver through (e.g. "whatsapp"), if any.
+    pub channel: Option<String>,
+    /// Channel-specific address, e.g. a WhatsApp JID.
+    pub recipient: Option<String>,
+    pub created_at: String,
+    pub fired_at: Option<String>,
+}
+
+impl Reminder {
+    pub fn fire_at_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.fire_at)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+const REMINDER_COLUMNS: &str =
+    "id, agent_id, text, fire_at, status, channel, recipient, created_at, fired_at";
+
+fn reminder_from_row(row: &rusqlite::Row) -> rusqlite::Result<Reminder> {
+    Ok(Reminder {
+        id: row.get(0)?,
+        agent_id: row.get(1)?,
+        text: row.get(2)?,
+        fire_at: row.get(3)?,
+        status: row.get(4)?,
+        channel: row.get(5)?,
+        recipient: row.get(6)?,
+        created_at: row.get(7)?,
+        fired_at: row.get(8)?,
+    })
+}
+
+impl SessionStore {
+    /// Schedule a reminder.
+    pub fn reminder_add(
+        &self,
+        agent_id: &str,
+        text: &str,
+        fire_at: &DateTime<Utc>,
+        channel: Option<&str>,
+        recipient: Option<&str>,
+    ) -> EngineResult<Reminder> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO reminders (agent_id, text, fire_at, channel, recipient)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![agent_id, text, format_fire_at(fire_at), channel, recipient],
+        )?;
+        let reminder = conn.query_row(
+            &format!("SELECT {} FROM reminders WHERE id = ?1", REMINDER_COLUMNS),
+            params![conn.last_insert_rowid()],
+            reminder_from_row,
+        )?;
+        Ok(reminder)
+    }
+
+    /// An agent's reminders, soonest first. Only pending ones unless
+    /// `include_done`.
+    pub fn reminder_list(&self, agent_id: &str, include_done: bool) -> EngineResult<Vec<Reminder>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reminders
+             WHERE agent_id = ?1 AND (?2 OR status = 'pending')
+             ORDER BY fire_at, id",
+            REMINDER_COLUMNS
+        ))?;
+        let reminders = stmt
+            .query_map(params![agent_id, include_done], reminder_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(reminders)
+    }
+
+    /// Cancel one of the agent's pending reminders.
+    pub fn reminder_cancel(&self, agent_id: &str, id: i64) -> EngineResult<Reminder> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE reminders SET status = 'cancelled'
+             WHERE id = ?1 AND agent_id = ?2 AND status = 'pending'",
+            params![id, agent_id],
+        )?;
+        let reminder = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM reminders WHERE id = ?1 AND agent_id = ?2",
+                    REMINDER_COLUMNS
+                ),
+                params![id, agent_id],
+                reminder_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| EngineError::Other(format!("Reminder {} not found", id)))?;
+        if updated == 0 {
+            return Err(EngineError::Other(format!(
+                "Reminder {} is already {}",
+                id, reminder.status
+            )));
+        }
+        Ok(reminder)
+    }
+
+    /// Pending reminders due at `now`, oldest first — including any whose
+    /// time passed while the app was closed.
+    pub fn due_reminders(&self, now: &DateTime<Utc>) -> EngineResult<Vec<Reminder>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM reminders
+             WHERE status = 'pending' AND fire_at <= ?1
+             ORDER BY fire_at, id",
+            REMINDER_COLUMNS
+        ))?;
+        let reminders = stmt
+            .query_map(params![format_fire_at(now)], reminder_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(reminders)
+    }
+
+    /// Mark a reminder fired. Returns false if it was no longer pending, so
+    /// each reminder is delivered at most once.
+    pub fn mark_reminder_fired(&self, id: i64) -> EngineResult<bool> {
+        let conn = self.conn.lock();
+        let updated = conn.execute(
+            "UPDATE reminders SET status = 'fired', fired_at = datetime('now')
+             WHERE id = ?1 AND status = 'pending'",
+            params![id],
+        )?;
+        Ok(updated > 0)
+    }
+}
@@ -0,0 +1,115 @@
+// Run Traces — ordered LLM/tool/outcome spans for a single agent run.
+// Written once when a run ends; read back by the Inspector timeline via
+// `engine_get_run_trace(session_id, run_id)`. Attribute values are expected
+// to be redacted and size-capped by the caller.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Runs kept per session — older traces are dropped on save.
+pub const MAX_TRACED_RUNS_PER_SESSION: usize = 50;
+
+/// One step of an agent run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunTraceSpan {
+    /// Position within the run (0-based, execution order).
+    pub seq: u32,
+    /// "llm", "tool" or "outcome".
+    pub kind: String,
+    /// Model name for LLM spans, tool name for tool spans, status for outcome.
+    pub name: String,
+    pub round: u32,
+    pub start_ms: i64,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl SessionStore {
+    /// Replace the stored trace for a run and trim old runs of the session.
+    pub fn save_run_trace(
+        &self,
+        session_id: &str,
+        run_id: &str,
+        spans: &[RunTraceSpan],
+    ) -> EngineResult<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM run_trace_spans WHERE session_id = ?1 AND run_id = ?2",
+            params![session_id, run_id],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO run_trace_spans
+                    (session_id, run_id, seq, kind, name, round, start_ms, duration_ms, success, attributes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for span in spans {
+                stmt.execute(params![
+                    session_id,
+                    run_id,
+                    span.seq,
+                    span.kind,
+                    span.name,
+                    span.round,
+                    span.start_ms,
+                    span.duration_ms as i64,
+                    span.success,
+                    serde_json::to_string(&span.attributes)?,
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM run_trace_spans WHERE session_id = ?1 AND run_id NOT IN (
+                SELECT run_id FROM run_trace_spans WHERE session_id = ?1
+                GROUP BY run_id ORDER BY MIN(start_ms) DESC LIMIT ?2
+             )",
+            params![session_id, MAX_TRACED_RUNS_PER_SESSION as i64],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Spans of one run in execution order (empty if the run wasn't traced).
+    pub fn get_run_trace(&self, session_id: &str, run_id: &str) -> EngineResult<Vec<RunTraceSpan>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT seq, kind, name, round, start_ms, duration_ms, success, attributes
+             FROM run_trace_spans WHERE session_id = ?1 AND run_id = ?2 ORDER BY seq",
+        )?;
+        let spans = stmt
+            .query_map(params![session_id, run_id], |row| {
+                let attrs: String = row.get(7)?;
+                Ok(RunTraceSpan {
+                    seq: row.get(0)?,
+                    kind: row.get(1)?,
+                    name: row.get(2)?,
+                    round: row.get(3)?,
+                    start_ms: row.get(4)?,
+                    duration_ms: row.get::<_, i64>(5)? as u64,
+                    success: row.get(6)?,
+                    attributes: serde_json::from_str(&attrs).unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(spans)
+    }
+
+    /// Traced run ids for a session, newest first.
+    pub fn list_traced_runs(&self, session_id: &str) -> EngineResult<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT run_id FROM run_trace_spans WHERE session_id = ?1
+             GROUP BY run_id ORDER BY MIN(start_ms) DESC",
+        )?;
+        let ids = stmt
+            .query_map(params![session_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ids)
+    }
+}
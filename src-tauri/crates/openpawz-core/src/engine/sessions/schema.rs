@@ -213,6 +213,42 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
     )
     .ok();
 
+    // Conversation branching: a fork points at its parent session and the
+    // message it was taken at
+    conn.execute("ALTER TABLE sessions ADD COLUMN parent_session_id TEXT", [])
+        .ok();
+    conn.execute(
+        "ALTER TABLE sessions ADD COLUMN forked_from_message_id TEXT",
+        [],
+    )
+    .ok();
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_sessions_parent ON sessions(parent_session_id)",
+        [],
+    )
+    .ok();
+
+    // Archiving: stale sessions drop out of the default list, keeping a
+    // one-line summary for display
+    conn.execute(
+        "ALTER TABLE sessions ADD COLUMN archived INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .ok();
+    conn.execute("ALTER TABLE sessions ADD COLUMN summary TEXT", [])
+        .ok();
+
+    // Streaming replies are saved as they arrive and flagged until the run
+    // completes, so an interrupted turn leaves a marked partial message
+    conn.execute(
+        "ALTER TABLE messages ADD COLUMN incomplete INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .ok();
+    // Reasoning from thinking models, stored beside the reply's content
+    conn.execute("ALTER TABLE messages ADD COLUMN reasoning TEXT", [])
+        .ok();
+
     // ── Positions table: stop-loss / take-profit tracking ────────────
     conn.execute_batch(
         "
@@ -273,6 +309,72 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
     )
     .ok();
 
+    // ── Skill state (KV scoped to the calling skill) ────────────────
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS skill_kv (
+            skill_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL DEFAULT '',
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (skill_id, key)
+        );
+    ",
+    )
+    .ok();
+
+    // ── Agent scratchpad notes (exact-key, per agent / session) ──────
+    // session_id is '' for agent-wide notes so it can sit in the key.
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS agent_notes (
+            agent_id TEXT NOT NULL,
+            session_id TEXT NOT NULL DEFAULT '',
+            key TEXT NOT NULL,
+            value TEXT NOT NULL DEFAULT '',
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (agent_id, session_id, key)
+        );
+    ",
+    )
+    .ok();
+
+    // ── Agent todo list (self-tracked subtasks) ──────────────────────
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS agent_todos (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            agent_id TEXT NOT NULL,
+            description TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            completed_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_agent_todos_agent ON agent_todos(agent_id, status);
+    ",
+    )
+    .ok();
+
+    // ── Reminders (fire at a time, survive restarts) ────────────────
+    // fire_at is normalized UTC RFC 3339 so it compares as a string.
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            agent_id TEXT NOT NULL,
+            text TEXT NOT NULL,
+            fire_at TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            channel TEXT,
+            recipient TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            fired_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_reminders_due ON reminders(status, fire_at);
+    ",
+    )
+    .ok();
+
     // ── Phase 2: Memory Intelligence migrations ──────────────────────
     // Add agent_id column to memories (for per-agent memory scope)
     conn.execute(
@@ -281,6 +383,15 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
     )
     .ok();
 
+    // Retrieval tracking (reinforcement boost + stale-memory pruning)
+    conn.execute(
+        "ALTER TABLE memories ADD COLUMN retrieval_count INTEGER NOT NULL DEFAULT 0",
+        [],
+    )
+    .ok();
+    conn.execute("ALTER TABLE memories ADD COLUMN last_retrieved_at TEXT", [])
+        .ok();
+
     // Create FTS5 virtual table for BM25 full-text search
     conn.execute_batch(
         "
@@ -331,6 +442,32 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
         CREATE INDEX IF NOT EXISTS idx_agent_messages_channel ON agent_messages(channel, created_at DESC);
     ").ok();
 
+    // ── Agent definitions ───────────────────────────────────────────
+    // Name, persona, default model and skills per agent. Seeded with the
+    // default agent; standalone project agents are imported further down
+    // (after the `_standalone` seed) — INSERT OR IGNORE keeps user edits.
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS agents (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            persona TEXT,
+            model TEXT,
+            skills TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        INSERT OR IGNORE INTO agents (id, name) VALUES ('default', 'Pawz');
+    ",
+    )
+    .ok();
+    // Autonomy level and overrides (AgentAutonomy as JSON)
+    conn.execute(
+        "ALTER TABLE agents ADD COLUMN autonomy TEXT NOT NULL DEFAULT '{}'",
+        [],
+    )
+    .ok();
+
     // ── Agent Squads ────────────────────────────────────────────────
     conn.execute_batch(
         "
@@ -394,6 +531,33 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
         [],
     )?;
 
+    // ── Import standalone agents into agent definitions ─────────────
+    // Once: agents created since get their definition directly, and one the
+    // user deletes must stay deleted.
+    let agents_imported: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM engine_config WHERE key = 'migration_agents_imported'",
+            [],
+            |r| r.get::<_, i64>(0),
+        )
+        .unwrap_or(0)
+        > 0;
+    if !agents_imported {
+        conn.execute(
+            "INSERT OR IGNORE INTO agents (id, name, persona, model)
+             SELECT agent_id, agent_id, system_prompt, NULLIF(model, '')
+             FROM project_agents
+             WHERE project_id = '_standalone' AND agent_id IS NOT NULL AND agent_id != ''",
+            [],
+        )
+        .ok();
+        conn.execute(
+            "INSERT OR REPLACE INTO engine_config (key, value) VALUES ('migration_agents_imported', '1')",
+            [],
+        )
+        .ok();
+    }
+
     // ── One-time dedup migration ─────────────────────────────────────
     // Removes duplicate messages caused by a historical bug that re-inserted
     // messages on every agent turn.  Guarded by a config flag so it only runs once.
@@ -550,6 +714,102 @@ pub fn run_migrations(conn: &Connection) -> EngineResult<()> {
     )
     .ok();
 
+    // ── Daily spend per agent (budget counters, survive restarts) ───
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS daily_spend (
+            date TEXT NOT NULL,
+            agent_id TEXT NOT NULL,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_create_tokens INTEGER NOT NULL DEFAULT 0,
+            cost_usd REAL NOT NULL DEFAULT 0.0,
+            PRIMARY KEY (date, agent_id)
+        );",
+    )
+    .ok();
+    // Counters are keyed by the UTC instant their budget window began (see
+    // engine::budget_window) rather than the UTC date, so resets follow the
+    // user's local day. Fails harmlessly once renamed.
+    conn.execute(
+        "ALTER TABLE daily_spend RENAME COLUMN date TO window_start",
+        [],
+    )
+    .ok();
+
+    // ── Session search index: chunked transcripts + embeddings ──────
+    // message_count is the session's count when it was chunked, so stale
+    // sessions can be found and re-indexed. The FTS table is the keyword
+    // fallback when no embedding model is reachable.
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS session_chunks (
+            session_id TEXT NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            embedding BLOB,
+            message_count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (session_id, chunk_index)
+        );
+        CREATE VIRTUAL TABLE IF NOT EXISTS session_chunks_fts USING fts5(
+            session_id UNINDEXED,
+            chunk_index UNINDEXED,
+            content
+        );",
+    )
+    .ok();
+
+    // ── Memory chunks: per-segment embeddings of long memories ──────
+    // Vector search scores these alongside the parent memory's own
+    // embedding and returns the parent.
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS memory_chunks (
+            memory_id TEXT NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            embedding BLOB,
+            PRIMARY KEY (memory_id, chunk_index)
+        );",
+    )
+    .ok();
+
+    // ── Run traces: ordered LLM/tool spans per agent run ────────────
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS run_trace_spans (
+            session_id TEXT NOT NULL,
+            run_id TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            name TEXT NOT NULL,
+            round INTEGER NOT NULL DEFAULT 0,
+            start_ms INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL DEFAULT 0,
+            success INTEGER NOT NULL DEFAULT 1,
+            attributes TEXT NOT NULL DEFAULT '{}',
+            PRIMARY KEY (session_id, run_id, seq)
+        );",
+    )
+    .ok();
+
+    // ── Approvals: HIL decisions linked to their outcomes ────────────
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS approvals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL,
+            tool_call_id TEXT NOT NULL,
+            tool_name TEXT NOT NULL,
+            args_summary TEXT NOT NULL DEFAULT '',
+            decision TEXT NOT NULL,
+            decider TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            success INTEGER,
+            outcome TEXT,
+            tx_hash TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_approvals_session ON approvals(session_id);
+        CREATE INDEX IF NOT EXISTS idx_approvals_call ON approvals(tool_call_id);",
+    )
+    .ok();
+
     // ── Tool Registry: persistent embedding index (Phase 2) ─────────
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS tool_embeddings (
@@ -627,5 +887,35 @@ mod tests {
         assert!(tables.contains(&"engine_config".to_string()));
         assert!(tables.contains(&"tool_embeddings".to_string()));
         assert!(tables.contains(&"tool_sequences".to_string()));
+        assert!(tables.contains(&"agents".to_string()));
+    }
+
+    #[test]
+    fn deleted_standalone_agents_are_not_reimported() {
+        let conn = in_memory_db();
+        run_migrations(&conn).unwrap();
+        // A database from before agent definitions, with one standalone agent
+        conn.execute_batch(
+            "DELETE FROM engine_config WHERE key = 'migration_agents_imported';
+             INSERT INTO project_agents (project_id, agent_id, system_prompt, model)
+             VALUES ('_standalone', 'agent-scout', 'You scout.', '');",
+        )
+        .unwrap();
+        let count = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM agents WHERE id = 'agent-scout'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap()
+        };
+
+        run_migrations(&conn).unwrap();
+        assert_eq!(count(&conn), 1);
+
+        conn.execute("DELETE FROM agents WHERE id = 'agent-scout'", [])
+            .unwrap();
+        run_migrations(&conn).unwrap();
+        assert_eq!(count(&conn), 0);
     }
 }
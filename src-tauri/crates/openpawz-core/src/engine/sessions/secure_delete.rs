@@ -0,0 +1,143 @@
+// sessions/secure_delete.rs — "Forget this conversation".
+// A plain DELETE leaves the text in SQLite free pages and WAL frames until
+// they happen to be reused. Secure deletion zeroes the content in place,
+// deletes the rows with secure_delete on (restoring the connection's own
+// setting afterwards), merges the full-text index so no stale segment keeps
+// the old terms, truncates the WAL and VACUUMs so the file is rebuilt
+// without the freed pages.
+
+use super::sessions::delete_session_rows;
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use crate::engine::engram::schema::pad_to_bucket;
+use log::{info, warn};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SecureDeleteReport {
+    pub messages_erased: usize,
+    pub memories_erased: usize,
+    /// Canvas components and context checkpoints saved from the session.
+    pub workspace_items_erased: usize,
+    /// False when VACUUM couldn't run (e.g. a reader held the database);
+    /// the rows are still gone and their pages zeroed.
+    pub vacuumed: bool,
+}
+
+impl SessionStore {
+    /// Irrecoverably delete a session: its messages, search chunks, notes,
+    /// canvas components and checkpoints, plus the episodic memories it
+    /// produced when `scrub_memories` is set.
+    pub fn secure_delete_session(
+        &self,
+        id: &str,
+        scrub_memories: bool,
+    ) -> EngineResult<SecureDeleteReport> {
+        let previous: i64 = {
+            let conn = self.conn.lock();
+            let previous = conn.query_row("PRAGMA secure_delete", [], |row| row.get(0))?;
+            conn.execute_batch("PRAGMA secure_delete = ON;")?;
+            previous
+        };
+        let result = self.erase_session(id, scrub_memories);
+        let restore = match previous {
+            0 => "OFF",
+            2 => "FAST",
+            _ => "ON",
+        };
+        if let Err(e) = self
+            .conn
+            .lock()
+            .execute_batch(&format!("PRAGMA secure_delete = {};", restore))
+        {
+            warn!("[engine] Restoring secure_delete failed: {}", e);
+        }
+        result
+    }
+
+    fn erase_session(&self, id: &str, scrub_memories: bool) -> EngineResult<SecureDeleteReport> {
+        let mut report = SecureDeleteReport::default();
+        let memory_ids: Vec<String> = {
+            let conn = self.conn.lock();
+            let tx = conn.unchecked_transaction()?;
+            // Overwrite before deleting so no page — including the WAL frame
+            // carrying the DELETE — holds the original text.
+            report.messages_erased = tx.execute(
+                "UPDATE messages SET content = '', tool_calls_json = NULL, name = NULL, reasoning = NULL
+                 WHERE session_id = ?1",
+                params![id],
+            )?;
+            tx.execute(
+                "UPDATE session_chunks SET content = '', embedding = NULL WHERE session_id = ?1",
+                params![id],
+            )?;
+            tx.execute(
+                "UPDATE session_chunks_fts SET content = '' WHERE session_id = ?1",
+                params![id],
+            )?;
+            tx.execute(
+                "UPDATE agent_notes SET value = '' WHERE session_id = ?1",
+                params![id],
+            )?;
+            delete_session_rows(&tx, id)?;
+            report.workspace_items_erased += tx.execute(
+                "DELETE FROM canvas_components WHERE session_id = ?1",
+                params![id],
+            )?;
+            report.workspace_items_erased += tx.execute(
+                "DELETE FROM workspace_checkpoints WHERE session_id = ?1",
+                params![id],
+            )?;
+
+            let ids = if scrub_memories {
+                let mut stmt =
+                    tx.prepare("SELECT id FROM episodic_memories WHERE session_id = ?1")?;
+                let ids = stmt
+                    .query_map(params![id], |row| row.get(0))?
+                    .collect::<Result<Vec<String>, _>>()?;
+                ids
+            } else {
+                Vec::new()
+            };
+            tx.commit()?;
+            ids
+        };
+
+        for memory_id in &memory_ids {
+            self.engram_secure_erase_episodic(memory_id)?;
+        }
+        report.memories_erased = memory_ids.len();
+
+        let conn = self.conn.lock();
+        // Merge the FTS index into one segment so the deleted terms aren't
+        // left behind in older segments, which VACUUM would copy verbatim.
+        if let Err(e) = conn
+            .execute_batch("INSERT INTO session_chunks_fts(session_chunks_fts) VALUES('optimize');")
+        {
+            warn!("[engine] Optimizing the session search index failed: {}", e);
+        }
+        // Fold the WAL (which still holds pre-delete pages) into the main
+        // file and truncate it, then rebuild the file without free pages.
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .ok();
+        match conn.execute_batch("VACUUM;") {
+            Ok(()) => {
+                report.vacuumed = true;
+                conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                    .ok();
+                // VACUUM compacts away the size-bucket padding
+                if let Err(e) = pad_to_bucket(&conn) {
+                    warn!("[engine] Re-padding after VACUUM failed: {}", e);
+                }
+            }
+            Err(e) => warn!("[engine] VACUUM after secure delete failed: {}", e),
+        }
+
+        info!(
+            "[engine] Securely deleted session {} ({} messages, {} memories, vacuumed={})",
+            id, report.messages_erased, report.memories_erased, report.vacuumed
+        );
+        Ok(report)
+    }
+}
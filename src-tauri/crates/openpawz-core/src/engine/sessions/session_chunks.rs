@@ -0,0 +1,136 @@
+// sessions/session_chunks.rs — Search index over session transcripts.
+// Each session is stored as a handful of text chunks (see
+// engine::session_search) with an optional embedding per chunk, mirrored
+// into an FTS5 table for keyword fallback. A session is re-chunked as a
+// whole whenever its message count changes.
+
+use super::embedding::{bytes_to_f32_vec, f32_vec_to_bytes};
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::params;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionChunk {
+    pub session_id: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl SessionStore {
+    /// Replace a session's chunks. `message_count` records how much of the
+    /// session the chunks cover.
+    pub fn replace_session_chunks(
+        &self,
+        session_id: &str,
+        message_count: i64,
+        chunks: &[(String, Option<Vec<f32>>)],
+    ) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM session_chunks WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "DELETE FROM session_chunks_fts WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        for (index, (content, embedding)) in chunks.iter().enumerate() {
+            let blob = embedding.as_deref().map(f32_vec_to_bytes);
+            tx.execute(
+                "INSERT INTO session_chunks (session_id, chunk_index, content, embedding, message_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![session_id, index as i64, content, blob, message_count],
+            )?;
+            tx.execute(
+                "INSERT INTO session_chunks_fts (session_id, chunk_index, content)
+                 VALUES (?1, ?2, ?3)",
+                params![session_id, index as i64, content],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Sessions with messages whose chunks are missing or out of date,
+    /// most recently updated first.
+    pub fn sessions_needing_chunks(&self, limit: usize) -> EngineResult<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT s.id FROM sessions s
+             LEFT JOIN (
+                SELECT session_id, MAX(message_count) AS indexed
+                FROM session_chunks GROUP BY session_id
+             ) c ON c.session_id = s.id
+             WHERE s.message_count > 0
+               AND (c.indexed IS NULL OR c.indexed != s.message_count)
+             ORDER BY s.updated_at DESC
+             LIMIT ?1",
+        )?;
+        let ids = stmt
+            .query_map(params![limit as i64], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Every chunk that has an embedding.
+    pub fn list_embedded_session_chunks(&self) -> EngineResult<Vec<SessionChunk>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, chunk_index, content, embedding
+             FROM session_chunks WHERE embedding IS NOT NULL",
+        )?;
+        let chunks = stmt
+            .query_map([], |row| {
+                let blob: Vec<u8> = row.get(3)?;
+                Ok(SessionChunk {
+                    session_id: row.get(0)?,
+                    chunk_index: row.get(1)?,
+                    content: row.get(2)?,
+                    embedding: Some(bytes_to_f32_vec(&blob)),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(chunks)
+    }
+
+    /// BM25 keyword search over chunks, best first. Scores are positive
+    /// (higher = better).
+    pub fn search_session_chunks_fts(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> EngineResult<Vec<(SessionChunk, f64)>> {
+        let fts_query = crate::engine::engram::encryption::sanitize_fts5_query(query)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        if fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, chunk_index, content, rank
+             FROM session_chunks_fts
+             WHERE session_chunks_fts MATCH ?1
+             ORDER BY rank
+             LIMIT ?2",
+        )?;
+        let hits = stmt
+            .query_map(params![fts_query, limit as i64], |row| {
+                let rank: f64 = row.get(3)?;
+                Ok((
+                    SessionChunk {
+                        session_id: row.get(0)?,
+                        chunk_index: row.get(1)?,
+                        content: row.get(2)?,
+                        embedding: None,
+                    },
+                    -rank,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(hits)
+    }
+}
@@ -1,8 +1,55 @@
 use super::SessionStore;
-use crate::atoms::error::EngineResult;
-use crate::engine::types::Session;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::types::{Session, StoredMessage};
 use log::info;
-use rusqlite::params;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+const SESSION_COLUMNS: &str = "id, label, model, system_prompt, created_at, updated_at, \
+     message_count, agent_id, parent_session_id, forked_from_message_id, archived, summary";
+
+/// Delete a session and everything stored under it. Shared with
+/// `secure_delete_session`, which runs it under the same lock.
+pub(super) fn delete_session_rows(conn: &Connection, id: &str) -> EngineResult<()> {
+    conn.execute("DELETE FROM messages WHERE session_id = ?1", params![id])?;
+    conn.execute(
+        "DELETE FROM run_trace_spans WHERE session_id = ?1",
+        params![id],
+    )?;
+    conn.execute("DELETE FROM agent_notes WHERE session_id = ?1", params![id])?;
+    conn.execute(
+        "DELETE FROM session_chunks WHERE session_id = ?1",
+        params![id],
+    )?;
+    conn.execute(
+        "DELETE FROM session_chunks_fts WHERE session_id = ?1",
+        params![id],
+    )?;
+    conn.execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+    // Forks outlive their parent as top-level sessions
+    conn.execute(
+        "UPDATE sessions SET parent_session_id = NULL, forked_from_message_id = NULL
+         WHERE parent_session_id = ?1",
+        params![id],
+    )?;
+    Ok(())
+}
+
+fn session_from_row(row: &Row) -> rusqlite::Result<Session> {
+    Ok(Session {
+        id: row.get(0)?,
+        label: row.get(1)?,
+        model: row.get(2)?,
+        system_prompt: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+        message_count: row.get(6)?,
+        agent_id: row.get(7)?,
+        parent_session_id: row.get(8)?,
+        forked_from_message_id: row.get(9)?,
+        archived: row.get(10)?,
+        summary: row.get(11)?,
+    })
+}
 
 impl SessionStore {
     // ── Session CRUD ───────────────────────────────────────────────────
@@ -30,6 +77,10 @@ impl SessionStore {
             updated_at: chrono::Utc::now().to_rfc3339(),
             message_count: 0,
             agent_id: agent_id.map(|s| s.to_string()),
+            parent_session_id: None,
+            forked_from_message_id: None,
+            archived: false,
+            summary: None,
         })
     }
 
@@ -43,41 +94,54 @@ impl SessionStore {
         limit: i64,
         agent_id: Option<&str>,
     ) -> EngineResult<Vec<Session>> {
-        let conn = self.conn.lock();
+        self.list_sessions_visible(limit, agent_id, true)
+    }
 
-        let (sql, params_vec): (String, Vec<Box<dyn rusqlite::types::ToSql>>) = if let Some(aid) =
-            agent_id
-        {
-            (
-                "SELECT id, label, model, system_prompt, created_at, updated_at, message_count, agent_id \
-                 FROM sessions WHERE agent_id = ?1 ORDER BY updated_at DESC LIMIT ?2".to_string(),
-                vec![Box::new(aid.to_string()) as Box<dyn rusqlite::types::ToSql>, Box::new(limit)],
-            )
+    /// List sessions, optionally filtered by agent_id, leaving out archived
+    /// ones unless `include_archived`.
+    pub fn list_sessions_visible(
+        &self,
+        limit: i64,
+        agent_id: Option<&str>,
+        include_archived: bool,
+    ) -> EngineResult<Vec<Session>> {
+        let conn = self.conn.lock();
+        let archived = if include_archived {
+            ""
         } else {
-            (
-                "SELECT id, label, model, system_prompt, created_at, updated_at, message_count, agent_id \
-                 FROM sessions ORDER BY updated_at DESC LIMIT ?1".to_string(),
-                vec![Box::new(limit) as Box<dyn rusqlite::types::ToSql>],
-            )
+            "AND archived = 0"
         };
 
+        let (sql, params_vec): (String, Vec<Box<dyn rusqlite::types::ToSql>>) =
+            if let Some(aid) = agent_id {
+                (
+                    format!(
+                        "SELECT {} FROM sessions WHERE agent_id = ?1 {} \
+                         ORDER BY updated_at DESC LIMIT ?2",
+                        SESSION_COLUMNS, archived
+                    ),
+                    vec![
+                        Box::new(aid.to_string()) as Box<dyn rusqlite::types::ToSql>,
+                        Box::new(limit),
+                    ],
+                )
+            } else {
+                (
+                    format!(
+                        "SELECT {} FROM sessions WHERE 1 = 1 {} \
+                         ORDER BY updated_at DESC LIMIT ?1",
+                        SESSION_COLUMNS, archived
+                    ),
+                    vec![Box::new(limit) as Box<dyn rusqlite::types::ToSql>],
+                )
+            };
+
         let mut stmt = conn.prepare(&sql)?;
         let param_refs: Vec<&dyn rusqlite::types::ToSql> =
             params_vec.iter().map(|b| b.as_ref()).collect();
 
         let sessions = stmt
-            .query_map(param_refs.as_slice(), |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    label: row.get(1)?,
-                    model: row.get(2)?,
-                    system_prompt: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                    message_count: row.get(6)?,
-                    agent_id: row.get(7)?,
-                })
-            })?
+            .query_map(param_refs.as_slice(), session_from_row)?
             .filter_map(|r| r.ok())
             .collect();
 
@@ -88,21 +152,9 @@ impl SessionStore {
         let conn = self.conn.lock();
 
         let result = conn.query_row(
-            "SELECT id, label, model, system_prompt, created_at, updated_at, message_count, agent_id
-             FROM sessions WHERE id = ?1",
+            &format!("SELECT {} FROM sessions WHERE id = ?1", SESSION_COLUMNS),
             params![id],
-            |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    label: row.get(1)?,
-                    model: row.get(2)?,
-                    system_prompt: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                    message_count: row.get(6)?,
-                    agent_id: row.get(7)?,
-                })
-            },
+            session_from_row,
         );
 
         match result {
@@ -112,6 +164,130 @@ impl SessionStore {
         }
     }
 
+    /// Fork `id` at `at_message_id`: a new session (same model, prompt and
+    /// agent) holding copies of the messages up to and including that one,
+    /// linked back to the parent. The original is left untouched.
+    pub fn fork_session(&self, id: &str, at_message_id: &str) -> EngineResult<Session> {
+        let parent = self
+            .get_session(id)?
+            .ok_or_else(|| EngineError::Other(format!("Session {} not found", id)))?;
+
+        let conn = self.conn.lock();
+        let in_session = conn
+            .query_row(
+                "SELECT 1 FROM messages WHERE id = ?1 AND session_id = ?2",
+                params![at_message_id, id],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if !in_session {
+            return Err(EngineError::Other(format!(
+                "Message {} is not in session {}",
+                at_message_id, id
+            )));
+        }
+
+        let fork_id = format!("eng-{}", uuid::Uuid::new_v4());
+        let label = parent.label.as_deref().map(|l| format!("{} (fork)", l));
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO sessions (id, label, model, system_prompt, agent_id,
+                                   parent_session_id, forked_from_message_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                fork_id,
+                label,
+                parent.model,
+                parent.system_prompt,
+                parent.agent_id,
+                id,
+                at_message_id
+            ],
+        )?;
+
+        // Copies get fresh ids but keep created_at and insertion order, so
+        // the fork reads exactly like the parent up to the branch point
+        let mut stmt = tx.prepare(
+            "SELECT m.role, m.content, m.tool_calls_json, m.tool_call_id, m.name, m.created_at,
+                    m.incomplete, m.reasoning
+             FROM messages m, messages t
+             WHERE t.id = ?2 AND m.session_id = ?1
+               AND (m.created_at < t.created_at
+                    OR (m.created_at = t.created_at AND m.rowid <= t.rowid))
+             ORDER BY m.created_at ASC, m.rowid ASC",
+        )?;
+        let copies: Vec<StoredMessage> = stmt
+            .query_map(params![id, at_message_id], |row| {
+                Ok(StoredMessage {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    session_id: fork_id.clone(),
+                    role: row.get(0)?,
+                    content: row.get(1)?,
+                    tool_calls_json: row.get(2)?,
+                    tool_call_id: row.get(3)?,
+                    name: row.get(4)?,
+                    created_at: row.get(5)?,
+                    incomplete: row.get(6)?,
+                    reasoning: row.get(7)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        drop(stmt);
+        for msg in &copies {
+            tx.execute(
+                "INSERT INTO messages (id, session_id, role, content, tool_calls_json,
+                                       tool_call_id, name, created_at, incomplete, reasoning)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    msg.id,
+                    msg.session_id,
+                    msg.role,
+                    msg.content,
+                    msg.tool_calls_json,
+                    msg.tool_call_id,
+                    msg.name,
+                    msg.created_at,
+                    msg.incomplete,
+                    msg.reasoning
+                ],
+            )?;
+        }
+        tx.execute(
+            "UPDATE sessions SET message_count = ?1 WHERE id = ?2",
+            params![copies.len() as i64, fork_id],
+        )?;
+        let fork = tx.query_row(
+            &format!("SELECT {} FROM sessions WHERE id = ?1", SESSION_COLUMNS),
+            params![fork_id],
+            session_from_row,
+        )?;
+        tx.commit()?;
+
+        info!(
+            "[engine] Forked session {} at {} into {} ({} messages)",
+            id,
+            at_message_id,
+            fork_id,
+            copies.len()
+        );
+        Ok(fork)
+    }
+
+    /// Sessions forked directly from `id`, oldest first.
+    pub fn list_session_forks(&self, id: &str) -> EngineResult<Vec<Session>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM sessions WHERE parent_session_id = ?1 ORDER BY created_at ASC, rowid ASC",
+            SESSION_COLUMNS
+        ))?;
+        let forks = stmt
+            .query_map(params![id], session_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(forks)
+    }
+
     pub fn rename_session(&self, id: &str, label: &str) -> EngineResult<()> {
         let conn = self.conn.lock();
         conn.execute(
@@ -121,13 +297,51 @@ impl SessionStore {
         Ok(())
     }
 
-    pub fn delete_session(&self, id: &str) -> EngineResult<()> {
+    /// Unarchived sessions not updated in the last `older_than_days` days,
+    /// oldest first.
+    pub fn list_stale_sessions(&self, older_than_days: u32) -> EngineResult<Vec<Session>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM sessions
+             WHERE archived = 0 AND updated_at < datetime('now', ?1)
+             ORDER BY updated_at ASC",
+            SESSION_COLUMNS
+        ))?;
+        let sessions = stmt
+            .query_map(
+                params![format!("-{} days", older_than_days)],
+                session_from_row,
+            )?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(sessions)
+    }
+
+    /// Archive or unarchive a session. A summary, when given, replaces the
+    /// stored one. `updated_at` is left alone so archiving doesn't make a
+    /// session look recently used.
+    pub fn set_session_archived(
+        &self,
+        id: &str,
+        archived: bool,
+        summary: Option<&str>,
+    ) -> EngineResult<()> {
         let conn = self.conn.lock();
-        conn.execute("DELETE FROM messages WHERE session_id = ?1", params![id])?;
-        conn.execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+        let changed = conn.execute(
+            "UPDATE sessions SET archived = ?1, summary = COALESCE(?2, summary) WHERE id = ?3",
+            params![archived, summary, id],
+        )?;
+        if changed == 0 {
+            return Err(EngineError::Other(format!("Session {} not found", id)));
+        }
         Ok(())
     }
 
+    pub fn delete_session(&self, id: &str) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        delete_session_rows(&conn, id)
+    }
+
     /// Clear all messages for a session but keep the session itself.
     pub fn clear_messages(&self, session_id: &str) -> EngineResult<()> {
         let conn = self.conn.lock();
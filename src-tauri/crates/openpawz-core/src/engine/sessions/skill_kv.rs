@@ -0,0 +1,95 @@
+// sessions/skill_kv.rs — Per-skill state (counters, cursors, last-run times).
+// Unlike skill_storage, callers never pick the namespace: the tool layer
+// resolves which skill is calling and only ever passes that skill's id.
+
+use super::SessionStore;
+use crate::atoms::error::EngineResult;
+use rusqlite::{params, OptionalExtension};
+
+impl SessionStore {
+    /// Set a key in a skill's state (upsert).
+    pub fn skill_kv_set(&self, skill_id: &str, key: &str, value: &str) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO skill_kv (skill_id, key, value, updated_at)
+             VALUES (?1, ?2, ?3, datetime('now'))
+             ON CONFLICT(skill_id, key) DO UPDATE SET value = ?3, updated_at = datetime('now')",
+            params![skill_id, key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Get a key from a skill's state.
+    pub fn skill_kv_get(&self, skill_id: &str, key: &str) -> EngineResult<Option<String>> {
+        let conn = self.conn.lock();
+        let value = conn
+            .query_row(
+                "SELECT value FROM skill_kv WHERE skill_id = ?1 AND key = ?2",
+                params![skill_id, key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Delete a key from a skill's state. Returns whether it existed.
+    pub fn skill_kv_delete(&self, skill_id: &str, key: &str) -> EngineResult<bool> {
+        let conn = self.conn.lock();
+        let deleted = conn.execute(
+            "DELETE FROM skill_kv WHERE skill_id = ?1 AND key = ?2",
+            params![skill_id, key],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete all of a skill's state (used during uninstall).
+    pub fn skill_kv_clear(&self, skill_id: &str) -> EngineResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "DELETE FROM skill_kv WHERE skill_id = ?1",
+            params![skill_id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        SessionStore::from_connection(conn)
+    }
+
+    #[test]
+    fn test_set_get_delete() {
+        let store = test_store();
+        assert_eq!(store.skill_kv_get("s1", "cursor").unwrap(), None);
+        store.skill_kv_set("s1", "cursor", "10").unwrap();
+        store.skill_kv_set("s1", "cursor", "20").unwrap();
+        assert_eq!(
+            store.skill_kv_get("s1", "cursor").unwrap(),
+            Some("20".to_string())
+        );
+        assert!(store.skill_kv_delete("s1", "cursor").unwrap());
+        assert!(!store.skill_kv_delete("s1", "cursor").unwrap());
+        assert_eq!(store.skill_kv_get("s1", "cursor").unwrap(), None);
+    }
+
+    #[test]
+    fn test_isolation() {
+        let store = test_store();
+        store.skill_kv_set("s1", "count", "1").unwrap();
+        store.skill_kv_set("s2", "count", "2").unwrap();
+        assert_eq!(store.skill_kv_get("s1", "count").unwrap(), Some("1".into()));
+        assert!(!store.skill_kv_delete("s3", "count").unwrap());
+
+        store.skill_kv_clear("s1").unwrap();
+        assert_eq!(store.skill_kv_get("s1", "count").unwrap(), None);
+        assert_eq!(store.skill_kv_get("s2", "count").unwrap(), Some("2".into()));
+    }
+}
@@ -3,6 +3,21 @@
 
 use super::SessionStore;
 use crate::atoms::error::EngineResult;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bumped whenever skill enablement, instructions or installed skills
+/// change, so anything derived from them can be cached until the next bump.
+static SKILL_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Current skill-configuration generation.
+pub fn skill_generation() -> u64 {
+    SKILL_GENERATION.load(Ordering::Acquire)
+}
+
+/// Record that skill configuration changed (invalidates derived caches).
+pub fn note_skills_changed() {
+    SKILL_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
 
 impl SessionStore {
     /// Initialize the skill vault tables (call from open()).
@@ -29,6 +44,14 @@ impl SessionStore {
                 instructions TEXT NOT NULL,
                 updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             );
+
+            CREATE TABLE IF NOT EXISTS dex_wallets (
+                id TEXT PRIMARY KEY,
+                label TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                address TEXT NOT NULL,
+                encrypted_key TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
         ",
         )?;
         Ok(())
@@ -110,6 +133,7 @@ impl SessionStore {
              ON CONFLICT(skill_id) DO UPDATE SET enabled = ?2, updated_at = datetime('now')",
             rusqlite::params![skill_id, enabled as i32],
         )?;
+        note_skills_changed();
         Ok(())
     }
 
@@ -153,6 +177,7 @@ impl SessionStore {
                 rusqlite::params![skill_id, enabled as i32],
             )?;
         }
+        note_skills_changed();
         Ok(())
     }
 
@@ -217,6 +242,7 @@ impl SessionStore {
                 rusqlite::params![skill_id, instructions],
             )?;
         }
+        note_skills_changed();
         Ok(())
     }
 }
@@ -148,4 +148,20 @@ impl SessionStore {
             "daily_spent_usd": daily_spent,
         }))
     }
+
+    /// (token, amount) of every completed DEX swap and transfer today.
+    /// Amounts are in token units — the DEX allowance values them.
+    pub fn dex_spend_today(&self) -> EngineResult<Vec<(String, f64)>> {
+        let conn = self.conn.lock();
+        let today_start = format!("{} 00:00:00", Utc::now().format("%Y-%m-%d"));
+        let mut stmt = conn.prepare(
+            "SELECT currency, CAST(amount AS REAL) FROM trade_history
+             WHERE trade_type IN ('dex_swap', 'dex_transfer') AND status = 'completed'
+               AND currency IS NOT NULL AND created_at >= ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![&today_start], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(String, f64)>, _>>()?;
+        Ok(rows)
+    }
 }
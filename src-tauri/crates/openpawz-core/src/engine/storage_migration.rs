@@ -0,0 +1,638 @@
+// Paw Engine — Data root migration
+//
+// Moves the Paw data root (Settings → Storage) to a new location instead of
+// just repointing it, so conversations, workspaces, skills and browser
+// profiles come along:
+//
+//   1. validate  — target isn't the current root, isn't nested in it, and
+//                  doesn't already hold Paw data
+//   2. plan      — size everything and check the target has room
+//   3. copy      — engine.db through the SQLite online backup API (a
+//                  consistent snapshot of the live database), then the
+//                  directories file by file
+//   4. verify    — `PRAGMA integrity_check`, per-table row counts, and file
+//                  count / byte totals for every directory
+//   5. switch    — write `storage.conf` so the next launch uses the new root
+//   6. cleanup   — optionally schedule deleting the migrated items from the
+//                  old root
+//
+// Any failure before the switch removes whatever was written to the target,
+// leaving the old root untouched. A dry run stops after the plan.
+//
+// The running engine keeps its open database, log file and catalogs in the
+// old root, so the app restarts straight after a migration (the caller holds
+// the write connection until then, so nothing lands in the old database
+// after the snapshot). The old copies are deleted on that next launch, by
+// `finish_pending_cleanup`, once nothing has them open.
+
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::{disk, paths};
+use log::{info, warn};
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The database file inside the data root.
+pub const DB_FILE: &str = "engine.db";
+
+/// Directories under the data root that move with it.
+pub const MIGRATED_DIRS: &[&str] = &[
+    "workspaces",
+    "skills",
+    "browser-profiles",
+    "logs",
+    "locales",
+];
+
+/// Directories still written to while the copy runs (the active log file),
+/// so their copy can't be compared byte for byte.
+const LIVE_DIRS: &[&str] = &["logs"];
+
+/// Marker in the new root naming an old root whose migrated items are
+/// deleted on the next launch.
+pub const PENDING_CLEANUP_FILE: &str = "migration-cleanup.pending";
+
+/// Database pages copied per backup step (progress granularity).
+const BACKUP_PAGES_PER_STEP: i32 = 256;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MigrationOptions {
+    /// Only validate and size the migration; copy nothing.
+    pub dry_run: bool,
+    /// Delete the migrated items from the old root on the next launch.
+    pub remove_old: bool,
+}
+
+/// Progress event, emitted as each stage advances.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationProgress {
+    /// "plan", "database", "files", "verify", "switch", "cleanup", "done"
+    pub stage: String,
+    /// Item being processed (`engine.db`, `workspaces`, …).
+    pub item: String,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub old_root: String,
+    pub new_root: String,
+    pub dry_run: bool,
+    /// Bytes planned (dry run) or copied.
+    pub total_bytes: u64,
+    pub files_copied: u64,
+    /// Free space at the target, when the platform reports it.
+    pub available_bytes: Option<u64>,
+    /// Whether the old copies will be deleted on the next launch.
+    pub cleanup_scheduled: bool,
+}
+
+/// Migrate the data root from `old_root` to `new_root`. `conn` is the live
+/// engine database, read through the backup API. `progress` receives stage
+/// updates; byte counts are throttled to roughly one event per percent.
+pub fn migrate_data_root(
+    conn: &Connection,
+    old_root: &Path,
+    new_root: &Path,
+    options: &MigrationOptions,
+    progress: &mut dyn FnMut(&MigrationProgress),
+) -> EngineResult<MigrationReport> {
+    validate_target(old_root, new_root)?;
+
+    // ── Plan ──
+    let db_bytes = db_size(old_root);
+    let dir_bytes: u64 = MIGRATED_DIRS
+        .iter()
+        .map(|d| tree_stats(&old_root.join(d)).1)
+        .sum();
+    let total_bytes = db_bytes + dir_bytes;
+    let available_bytes = disk::available_space(new_root);
+    progress(&MigrationProgress {
+        stage: "plan".into(),
+        item: String::new(),
+        copied_bytes: 0,
+        total_bytes,
+    });
+    disk::check_free_space(new_root, total_bytes)?;
+
+    let mut report = MigrationReport {
+        old_root: old_root.to_string_lossy().to_string(),
+        new_root: new_root.to_string_lossy().to_string(),
+        dry_run: options.dry_run,
+        total_bytes,
+        files_copied: 0,
+        available_bytes,
+        cleanup_scheduled: false,
+    };
+    if options.dry_run {
+        return Ok(report);
+    }
+
+    // ── Copy + verify, rolling back on failure ──
+    let created_root = !new_root.exists();
+    std::fs::create_dir_all(new_root)?;
+    let mut tracker = Tracker {
+        copied: 0,
+        total: total_bytes,
+        last_emitted: 0,
+        progress,
+    };
+    match copy_and_verify(conn, old_root, new_root, &mut tracker) {
+        Ok(files) => report.files_copied = files,
+        Err(e) => {
+            warn!("[storage] Migration failed, rolling back: {}", e);
+            rollback(new_root, created_root);
+            return Err(e);
+        }
+    }
+
+    // ── Switch ──
+    tracker.emit("switch", "storage.conf", true);
+    let new_conf = if new_root == paths::default_data_dir() {
+        None
+    } else {
+        Some(new_root.to_string_lossy().to_string())
+    };
+    if let Err(e) = paths::save_data_root_to_conf(new_conf.as_deref()) {
+        rollback(new_root, created_root);
+        return Err(EngineError::Config(e));
+    }
+    paths::set_data_root_override(new_conf.map(PathBuf::from));
+    info!(
+        "[storage] Data root migrated: {} → {} ({} bytes, {} files)",
+        old_root.display(),
+        new_root.display(),
+        report.total_bytes,
+        report.files_copied
+    );
+
+    // ── Cleanup ──
+    // Deleting now would pull the database and log file out from under the
+    // running process — leave a marker for the next launch instead.
+    if options.remove_old {
+        tracker.emit("cleanup", "", true);
+        match std::fs::write(
+            new_root.join(PENDING_CLEANUP_FILE),
+            old_root.to_string_lossy().as_bytes(),
+        ) {
+            Ok(()) => report.cleanup_scheduled = true,
+            Err(e) => warn!("[storage] Could not schedule old-root cleanup: {}", e),
+        }
+    }
+    tracker.emit("done", "", true);
+    Ok(report)
+}
+
+/// Delete the old root's migrated items if the last migration into `root`
+/// asked for it. Call at startup, before the database and log file open.
+/// Returns what couldn't be deleted.
+pub fn finish_pending_cleanup(root: &Path) -> Vec<String> {
+    let marker = root.join(PENDING_CLEANUP_FILE);
+    let Ok(old_root) = std::fs::read_to_string(&marker) else {
+        return Vec::new();
+    };
+    let old_root = PathBuf::from(old_root.trim());
+    let _ = std::fs::remove_file(&marker);
+    // Never delete the root now in use
+    if old_root.as_os_str().is_empty() || resolve(&old_root) == resolve(root) {
+        return Vec::new();
+    }
+    info!(
+        "[storage] Removing migrated data from old root {}",
+        old_root.display()
+    );
+    remove_migrated(&old_root)
+}
+
+/// Refuse targets that would overwrite data or copy a tree into itself.
+fn validate_target(old_root: &Path, new_root: &Path) -> EngineResult<()> {
+    if !new_root.is_absolute() {
+        return Err(EngineError::Config(format!(
+            "New data root must be an absolute path: {}",
+            new_root.display()
+        )));
+    }
+    let old = resolve(old_root);
+    let new = resolve(new_root);
+    if old == new {
+        return Err(EngineError::Config(
+            "New data root is the current data root".into(),
+        ));
+    }
+    if new.starts_with(&old) || old.starts_with(&new) {
+        return Err(EngineError::Config(format!(
+            "New data root {} can't contain or be inside the current root {}",
+            new.display(),
+            old.display()
+        )));
+    }
+    for item in std::iter::once(DB_FILE).chain(MIGRATED_DIRS.iter().copied()) {
+        if new.join(item).exists() {
+            return Err(EngineError::Config(format!(
+                "{} already contains '{}' — choose an empty folder",
+                new.display(),
+                item
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Canonicalize the longest existing prefix of `path` so a target that
+/// doesn't exist yet still compares correctly against the current root.
+fn resolve(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(real) = ancestor.canonicalize() {
+            return real.join(path.strip_prefix(ancestor).unwrap_or(Path::new("")));
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Byte / event bookkeeping for progress reporting.
+struct Tracker<'a> {
+    copied: u64,
+    total: u64,
+    last_emitted: u64,
+    progress: &'a mut dyn FnMut(&MigrationProgress),
+}
+
+impl Tracker<'_> {
+    fn emit(&mut self, stage: &str, item: &str, force: bool) {
+        let step = (self.total / 100).max(1);
+        if !force && self.copied.saturating_sub(self.last_emitted) < step {
+            return;
+        }
+        self.last_emitted = self.copied;
+        (self.progress)(&MigrationProgress {
+            stage: stage.into(),
+            item: item.into(),
+            copied_bytes: self.copied,
+            total_bytes: self.total,
+        });
+    }
+}
+
+fn copy_and_verify(
+    conn: &Connection,
+    old_root: &Path,
+    new_root: &Path,
+    tracker: &mut Tracker,
+) -> EngineResult<u64> {
+    // Database — online backup, page by page
+    let new_db = new_root.join(DB_FILE);
+    {
+        let mut dst = Connection::open(&new_db)?;
+        let backup = Backup::new(conn, &mut dst)?;
+        let base = tracker.copied;
+        let db_bytes = db_size(old_root);
+        loop {
+            let step = backup.step(BACKUP_PAGES_PER_STEP)?;
+            let p = backup.progress();
+            if p.pagecount > 0 {
+                let done = (p.pagecount - p.remaining).max(0) as u64;
+                tracker.copied = base + db_bytes * done / p.pagecount as u64;
+            }
+            tracker.emit("database", DB_FILE, false);
+            match step {
+                StepResult::Done => break,
+                StepResult::More => {}
+                _ => std::thread::sleep(Duration::from_millis(50)),
+            }
+        }
+    }
+    tracker.emit("database", DB_FILE, true);
+
+    // Directories
+    let mut files = 1;
+    for dir in MIGRATED_DIRS {
+        let src = old_root.join(dir);
+        if !src.exists() {
+            continue;
+        }
+        tracker.emit("files", dir, true);
+        files += copy_tree(&src, &new_root.join(dir), dir, tracker)?;
+    }
+
+    // Verify
+    tracker.emit("verify", DB_FILE, true);
+    verify_database(conn, &new_db)?;
+    for dir in MIGRATED_DIRS {
+        let src = old_root.join(dir);
+        if !src.exists() || LIVE_DIRS.contains(dir) {
+            continue;
+        }
+        tracker.emit("verify", dir, true);
+        let expected = tree_stats(&src);
+        let actual = tree_stats(&new_root.join(dir));
+        if expected != actual {
+            return Err(EngineError::Other(format!(
+                "Copy of '{}' doesn't match: {} files / {} bytes expected, {} / {} copied",
+                dir, expected.0, expected.1, actual.0, actual.1
+            )));
+        }
+    }
+    Ok(files)
+}
+
+/// Copy `src` into `dst` recursively. Symlinks (e.g. Chrome's Singleton*
+/// lock links) are skipped — they point at per-process state.
+fn copy_tree(src: &Path, dst: &Path, item: &str, tracker: &mut Tracker) -> EngineResult<u64> {
+    std::fs::create_dir_all(dst)?;
+    let mut files = 0;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let kind = entry.file_type()?;
+        let target = dst.join(entry.file_name());
+        if kind.is_dir() {
+            files += copy_tree(&entry.path(), &target, item, tracker)?;
+        } else if kind.is_file() {
+            tracker.copied += std::fs::copy(entry.path(), &target)?;
+            files += 1;
+            tracker.emit("files", item, false);
+        }
+    }
+    Ok(files)
+}
+
+/// The copy passes SQLite's integrity check and every table has the same
+/// number of rows as the source.
+fn verify_database(src: &Connection, copy_path: &Path) -> EngineResult<()> {
+    let copy = Connection::open(copy_path)?;
+    let integrity: String = copy.query_row("PRAGMA integrity_check", [], |r| r.get(0))?;
+    if integrity != "ok" {
+        return Err(EngineError::Other(format!(
+            "Copied database failed integrity check: {}",
+            integrity
+        )));
+    }
+    let mut stmt = src.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table'
+         AND name NOT LIKE 'sqlite_%' AND sql NOT LIKE 'CREATE VIRTUAL TABLE%'",
+    )?;
+    let tables: Vec<String> = stmt
+        .query_map([], |r| r.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+    for table in tables {
+        let sql = format!("SELECT COUNT(*) FROM \"{}\"", table.replace('"', "\"\""));
+        let expected: i64 = src.query_row(&sql, [], |r| r.get(0))?;
+        let actual: i64 = copy.query_row(&sql, [], |r| r.get(0))?;
+        if expected != actual {
+            return Err(EngineError::Other(format!(
+                "Copied database table '{}' has {} rows, expected {}",
+                table, actual, expected
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Remove what a failed migration wrote to the target.
+fn rollback(new_root: &Path, created_root: bool) {
+    if created_root {
+        let _ = std::fs::remove_dir_all(new_root);
+        return;
+    }
+    for item in [
+        DB_FILE,
+        "engine.db-wal",
+        "engine.db-shm",
+        "engine.db-journal",
+    ] {
+        let _ = std::fs::remove_file(new_root.join(item));
+    }
+    for dir in MIGRATED_DIRS {
+        let _ = std::fs::remove_dir_all(new_root.join(dir));
+    }
+}
+
+/// Delete the migrated items from the old root. The root itself stays —
+/// the default root also holds `storage.conf`. Returns what couldn't go.
+fn remove_migrated(old_root: &Path) -> Vec<String> {
+    let mut left = Vec::new();
+    for item in [DB_FILE, "engine.db-wal", "engine.db-shm"] {
+        let path = old_root.join(item);
+        if path.exists() && std::fs::remove_file(&path).is_err() {
+            left.push(path.to_string_lossy().to_string());
+        }
+    }
+    for dir in MIGRATED_DIRS {
+        let path = old_root.join(dir);
+        if path.exists() && std::fs::remove_dir_all(&path).is_err() {
+            left.push(path.to_string_lossy().to_string());
+        }
+    }
+    if !left.is_empty() {
+        warn!("[storage] Could not remove old data: {:?}", left);
+    }
+    left
+}
+
+/// Database size including an un-checkpointed WAL.
+fn db_size(root: &Path) -> u64 {
+    ["engine.db", "engine.db-wal"]
+        .iter()
+        .filter_map(|f| std::fs::metadata(root.join(f)).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// (regular file count, total bytes) under `path`, symlinks excluded.
+fn tree_stats(path: &Path) -> (u64, u64) {
+    let mut stats = (0, 0);
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => {
+                    let (files, bytes) = tree_stats(&entry.path());
+                    stats.0 += files;
+                    stats.1 += bytes;
+                }
+                Ok(t) if t.is_file() => {
+                    stats.0 += 1;
+                    stats.1 += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+                _ => {}
+            }
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "paw-migrate-{}-{}-{}",
+            name,
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn seed_root(root: &Path) -> Connection {
+        let conn = Connection::open(root.join(DB_FILE)).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE messages (id INTEGER PRIMARY KEY, content TEXT);
+             INSERT INTO messages (content) VALUES ('hello'), ('world');",
+        )
+        .unwrap();
+        let ws = root.join("workspaces").join("default");
+        std::fs::create_dir_all(&ws).unwrap();
+        std::fs::write(ws.join("notes.md"), "remember the milk").unwrap();
+        let skill = root.join("skills").join("weather");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("pawz-skill.toml"), "[skill]\nid = \"weather\"").unwrap();
+        conn
+    }
+
+    #[test]
+    fn dry_run_plans_without_copying() {
+        let base = scratch_dir("dry");
+        let old = base.join("old");
+        std::fs::create_dir_all(&old).unwrap();
+        let conn = seed_root(&old);
+        let new = base.join("new");
+
+        let options = MigrationOptions {
+            dry_run: true,
+            remove_old: false,
+        };
+        let report = migrate_data_root(&conn, &old, &new, &options, &mut |_| {}).unwrap();
+        assert!(report.dry_run);
+        assert!(report.total_bytes > 0);
+        assert!(!new.exists());
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn migrates_small_data_dir() {
+        let base = scratch_dir("copy");
+        let old = base.join("old");
+        std::fs::create_dir_all(&old).unwrap();
+        let conn = seed_root(&old);
+        let new = base.join("new");
+
+        let mut stages = Vec::new();
+        let files = copy_only(&conn, &old, &new, &mut |p| stages.push(p.stage.clone()));
+        assert_eq!(files, 3); // engine.db + notes.md + pawz-skill.toml
+        assert!(stages.iter().any(|s| s == "database"));
+        assert!(stages.iter().any(|s| s == "verify"));
+
+        let copy = Connection::open(new.join(DB_FILE)).unwrap();
+        let count: i64 = copy
+            .query_row("SELECT COUNT(*) FROM messages", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            std::fs::read_to_string(new.join("workspaces/default/notes.md")).unwrap(),
+            "remember the milk"
+        );
+        assert!(new.join("skills/weather/pawz-skill.toml").exists());
+        // Old data is untouched
+        assert!(old.join("workspaces/default/notes.md").exists());
+
+        // Old copies go on the next launch, not while they're in use
+        std::fs::write(
+            new.join(PENDING_CLEANUP_FILE),
+            old.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(finish_pending_cleanup(&new), Vec::<String>::new());
+        assert!(!old.join("workspaces").exists());
+        assert!(!old.join(DB_FILE).exists());
+        assert!(!new.join(PENDING_CLEANUP_FILE).exists());
+        assert!(new.join(DB_FILE).exists());
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn logs_and_locales_move_too() {
+        let base = scratch_dir("extra");
+        let old = base.join("old");
+        std::fs::create_dir_all(&old).unwrap();
+        let conn = seed_root(&old);
+        std::fs::create_dir_all(old.join("logs")).unwrap();
+        std::fs::write(old.join("logs/openpawz.log"), "started\n").unwrap();
+        std::fs::create_dir_all(old.join("locales")).unwrap();
+        std::fs::write(old.join("locales/de.json"), "{}").unwrap();
+        let new = base.join("new");
+
+        let files = copy_only(&conn, &old, &new, &mut |_| {});
+        assert_eq!(files, 5);
+        assert!(new.join("logs/openpawz.log").exists());
+        assert!(new.join("locales/de.json").exists());
+
+        // A cleanup marker pointing at the root in use is ignored
+        std::fs::write(
+            new.join(PENDING_CLEANUP_FILE),
+            new.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+        finish_pending_cleanup(&new);
+        assert!(new.join("logs/openpawz.log").exists());
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn refuses_nested_or_occupied_targets() {
+        let base = scratch_dir("validate");
+        let old = base.join("old");
+        std::fs::create_dir_all(&old).unwrap();
+        let conn = seed_root(&old);
+
+        let options = MigrationOptions::default();
+        let nested = old.join("inner");
+        assert!(migrate_data_root(&conn, &old, &nested, &options, &mut |_| {}).is_err());
+        assert!(migrate_data_root(&conn, &old, &old, &options, &mut |_| {}).is_err());
+
+        let occupied = base.join("occupied");
+        std::fs::create_dir_all(occupied.join("workspaces")).unwrap();
+        let err = migrate_data_root(&conn, &old, &occupied, &options, &mut |_| {}).unwrap_err();
+        assert!(err.to_string().contains("already contains"));
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn failed_copy_rolls_back_target() {
+        let base = scratch_dir("rollback");
+        let new = base.join("new");
+        std::fs::create_dir_all(new.join("workspaces")).unwrap();
+        std::fs::write(new.join(DB_FILE), "partial").unwrap();
+        rollback(&new, false);
+        assert!(new.exists());
+        assert!(!new.join(DB_FILE).exists());
+        assert!(!new.join("workspaces").exists());
+        rollback(&new, true);
+        assert!(!new.exists());
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    /// Copy + verify without touching the real `storage.conf`.
+    fn copy_only(
+        conn: &Connection,
+        old: &Path,
+        new: &Path,
+        progress: &mut dyn FnMut(&MigrationProgress),
+    ) -> u64 {
+        validate_target(old, new).unwrap();
+        std::fs::create_dir_all(new).unwrap();
+        let mut tracker = Tracker {
+            copied: 0,
+            total: db_size(old),
+            last_emitted: 0,
+            progress,
+        };
+        copy_and_verify(conn, old, new, &mut tracker).unwrap()
+    }
+}
@@ -0,0 +1,130 @@
+// Paw Agent Engine — Test support
+//
+// A minimal HTTP mock for unit tests that drive a real client against a
+// local endpoint. It speaks just enough HTTP/1.1 over a raw TCP listener to
+// read one request (head plus a Content-Length body) and answer it with a
+// canned reply.
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Raw requests received by [`serve`], in arrival order.
+pub type Requests = Arc<Mutex<Vec<Vec<u8>>>>;
+
+/// The response a mock endpoint answers with.
+pub struct MockReply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    /// Extra response headers, as `name: value` lines.
+    pub headers: Vec<String>,
+}
+
+impl MockReply {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        MockReply {
+            status,
+            content_type,
+            body: body.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self::new(status, "application/json", body.into())
+    }
+
+    /// Add a response header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push(format!("{}: {}", name, value));
+        self
+    }
+
+    async fn write_to(&self, socket: &mut TcpStream) {
+        let mut head = format!(
+            "HTTP/1.1 {} X\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        );
+        for header in &self.headers {
+            head.push_str(header);
+            head.push_str("\r\n");
+        }
+        head.push_str("\r\n");
+        let _ = socket.write_all(head.as_bytes()).await;
+        let _ = socket.write_all(&self.body).await;
+    }
+}
+
+/// Answer every request with `reply`. Returns the base URL (`http://host:port`)
+/// and the raw requests received so far.
+pub async fn serve(reply: MockReply) -> (String, Requests) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Requests::default();
+    let seen = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let raw = read_request(&mut socket).await;
+            seen.lock().push(raw);
+            reply.write_to(&mut socket).await;
+        }
+    });
+    (format!("http://{}", addr), requests)
+}
+
+/// Answer a single request with `reply`. The handle yields the raw request.
+pub async fn serve_once(reply: MockReply) -> (String, JoinHandle<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let raw = read_request(&mut socket).await;
+        reply.write_to(&mut socket).await;
+        raw
+    });
+    (format!("http://{}", addr), handle)
+}
+
+/// Read one request: the head and as much body as its Content-Length names.
+pub async fn read_request(socket: &mut TcpStream) -> Vec<u8> {
+    let mut raw = Vec::new();
+    let mut buf = [0u8; 4096];
+    while let Ok(n) = socket.read(&mut buf).await {
+        if n == 0 {
+            break;
+        }
+        raw.extend_from_slice(&buf[..n]);
+        if let Some(split) = head_end(&raw) {
+            if raw.len() >= split + 4 + content_length(&raw[..split]) {
+                break;
+            }
+        }
+    }
+    raw
+}
+
+/// The body of a raw request, as text.
+pub fn request_body(raw: &[u8]) -> String {
+    let start = head_end(raw).map_or(raw.len(), |split| split + 4);
+    String::from_utf8_lossy(&raw[start..]).to_string()
+}
+
+fn head_end(raw: &[u8]) -> Option<usize> {
+    raw.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+fn content_length(head: &[u8]) -> usize {
+    String::from_utf8_lossy(head)
+        .lines()
+        .find_map(|l| {
+            let (k, v) = l.split_once(':')?;
+            k.eq_ignore_ascii_case("content-length")
+                .then(|| v.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0)
+}
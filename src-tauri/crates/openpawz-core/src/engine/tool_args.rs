@@ -0,0 +1,386 @@
+// Paw Agent Engine — Tool argument validation
+//
+// Every ToolDefinition declares a JSON-schema `parameters`, so the executor
+// checks incoming args against it before dispatch instead of leaving each
+// tool to discover a missing or mistyped field halfway through. The errors
+// go back to the model, which can fix the call and retry.
+//
+// Covers the subset tool schemas actually use: `type` (string or list),
+// `required`, `properties`, `items` and `enum`. Anything else (`anyOf`,
+// `pattern`, `additionalProperties`…) is not enforced — extra args are left
+// to the tool. A null for an optional field counts as absent, since strict
+// providers make the model send every property.
+//
+// Before validating, `coerce` repairs the usual model quirks the schema can
+// disambiguate — "3000" for an integer, "true" for a boolean, "a,b" for an
+// array, 0.5 for a string — so executors reading `as_u64`/`as_bool`/
+// `as_array` don't silently miss a value that was sent.
+
+use serde_json::Value;
+
+/// Errors reported at most, so a badly confused call stays readable.
+const MAX_ERRORS: usize = 10;
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        // 3.0 is fine for an integer field; 3.5 is not
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        // Unknown type keywords aren't ours to reject
+        _ => true,
+    }
+}
+
+fn describe(path: &str) -> String {
+    if path.is_empty() {
+        "arguments".into()
+    } else {
+        format!("'{}'", path)
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    let types = declared_types(schema);
+    if !types.is_empty() && !types.iter().any(|ty| matches_type(value, ty)) {
+        errors.push(format!(
+            "{} must be {}, got {}",
+            describe(path),
+            types.join(" or "),
+            type_name(value)
+        ));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let options: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            errors.push(format!(
+                "{} must be one of {}, got {}",
+                describe(path),
+                options.join(", "),
+                value
+            ));
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        for name in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if fields.get(name).is_none_or(Value::is_null) {
+                errors.push(format!(
+                    "missing required field {}",
+                    describe(&join(path, name))
+                ));
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (name, field) in fields.iter().filter(|(_, v)| !v.is_null()) {
+                if let Some(field_schema) = properties.get(name) {
+                    check(field_schema, field, &join(path, name), errors);
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check(item_schema, item, &format!("{}[{}]", path, i), errors);
+        }
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn declared_types(schema: &serde_json::Map<String, Value>) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(tys)) => tys.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    }
+}
+
+/// `value` converted to the first of `types` it can stand for, if any.
+fn coerce_value(value: &Value, types: &[&str]) -> Option<Value> {
+    types.iter().find_map(|ty| match (*ty, value) {
+        ("integer", Value::String(s)) => {
+            let s = s.trim();
+            s.parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| s.parse::<u64>().map(Value::from))
+                .ok()
+        }
+        ("number", Value::String(s)) => {
+            let s = s.trim();
+            s.parse::<i64>().map(Value::from).ok().or_else(|| {
+                s.parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite())
+                    .map(Value::from)
+            })
+        }
+        ("boolean", Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        ("string", Value::Number(_) | Value::Bool(_)) => Some(Value::String(value.to_string())),
+        ("array", Value::String(s)) => {
+            let s = s.trim();
+            if s.starts_with('[') {
+                serde_json::from_str::<Value>(s)
+                    .ok()
+                    .filter(Value::is_array)
+            } else {
+                Some(Value::Array(
+                    s.split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(|item| Value::String(item.to_string()))
+                        .collect(),
+                ))
+            }
+        }
+        ("object", Value::String(s)) => serde_json::from_str::<Value>(s.trim())
+            .ok()
+            .filter(Value::is_object),
+        _ => None,
+    })
+}
+
+fn coerce_at(schema: &Value, value: &mut Value, path: &str, coerced: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let types = declared_types(schema);
+    if !value.is_null() && !types.is_empty() && !types.iter().any(|ty| matches_type(value, ty)) {
+        if let Some(fixed) = coerce_value(value, &types) {
+            coerced.push(format!(
+                "{}: {} → {}",
+                describe(path),
+                type_name(value),
+                type_name(&fixed)
+            ));
+            *value = fixed;
+        }
+    }
+    match value {
+        Value::Object(fields) => {
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, field) in fields.iter_mut() {
+                    if let Some(field_schema) = properties.get(name) {
+                        coerce_at(field_schema, field, &join(path, name), coerced);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter_mut().enumerate() {
+                    coerce_at(item_schema, item, &format!("{}[{}]", path, i), coerced);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewrite misformatted values in `args` to the types the schema declares,
+/// where the intent is unambiguous. Returns one line per coercion made.
+pub fn coerce(schema: &Value, args: &mut Value) -> Vec<String> {
+    let mut coerced = Vec::new();
+    coerce_at(schema, args, "", &mut coerced);
+    coerced
+}
+
+/// Check `args` against a tool's declared `parameters` schema. Returns every
+/// violation found (up to a cap), each naming the offending field.
+pub fn validate(schema: &Value, args: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    check(schema, args, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.truncate(MAX_ERRORS);
+        Err(errors)
+    }
+}
+
+/// Tool output for a call rejected by [`validate`].
+pub fn rejection_message(tool: &str, errors: &[String]) -> String {
+    format!(
+        "ERROR: Invalid arguments for '{}':\n- {}\nCheck the tool's parameter schema and call it again with corrected arguments.",
+        tool,
+        errors.join("\n- ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// trello_create_card as the Trello integration declares it.
+    fn trello_create_card() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "idList": { "type": "string", "description": "List to add the card to" },
+                "name": { "type": "string", "description": "Card title" },
+                "desc": { "type": "string" },
+                "pos": { "type": "string", "enum": ["top", "bottom"] },
+                "idLabels": { "type": "array", "items": { "type": "string" } },
+                "due": { "type": ["string", "null"] }
+            },
+            "required": ["idList", "name"]
+        })
+    }
+
+    #[test]
+    fn trello_create_card_requires_name() {
+        let schema = trello_create_card();
+        let errors =
+            validate(&schema, &json!({ "idList": "5f1a", "desc": "Follow up" })).unwrap_err();
+        assert_eq!(errors, vec!["missing required field 'name'"]);
+        let message = rejection_message("trello_create_card", &errors);
+        assert!(message.contains("trello_create_card") && message.contains("'name'"));
+
+        assert!(validate(&schema, &json!({ "idList": "5f1a", "name": "Ship it" })).is_ok());
+        // Strict providers send optional fields as null
+        assert!(validate(
+            &schema,
+            &json!({ "idList": "5f1a", "name": "Ship it", "pos": null, "due": null })
+        )
+        .is_ok());
+        assert!(validate(&schema, &json!({ "idList": "5f1a", "name": null })).is_err());
+    }
+
+    #[test]
+    fn reports_type_and_enum_violations_with_paths() {
+        let schema = trello_create_card();
+        let errors = validate(
+            &schema,
+            &json!({ "idList": 42, "name": "x", "pos": "middle", "idLabels": ["a", 7] }),
+        )
+        .unwrap_err();
+        assert!(errors.contains(&"'idList' must be string, got integer".to_string()));
+        assert!(errors.iter().any(|e| e.starts_with("'pos' must be one of")));
+        assert!(errors.contains(&"'idLabels[1]' must be string, got integer".to_string()));
+        assert_eq!(errors.len(), 3);
+
+        assert!(validate(&schema, &json!("card")).is_err());
+    }
+
+    #[test]
+    fn integers_numbers_and_nested_objects() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": "integer" },
+                "ratio": { "type": "number" },
+                "filter": {
+                    "type": "object",
+                    "properties": { "status": { "type": "string" } },
+                    "required": ["status"]
+                }
+            }
+        });
+        assert!(validate(&schema, &json!({ "limit": 5, "ratio": 0.5 })).is_ok());
+        assert!(validate(&schema, &json!({ "limit": 5.0 })).is_ok());
+        assert!(validate(&schema, &json!({ "limit": 5.5 })).is_err());
+        assert_eq!(
+            validate(&schema, &json!({ "filter": {} })).unwrap_err(),
+            vec!["missing required field 'filter.status'"]
+        );
+        // Undeclared args and schemaless tools pass through
+        assert!(validate(&schema, &json!({ "extra": true })).is_ok());
+        assert!(validate(&json!({}), &json!({ "anything": 1 })).is_ok());
+    }
+
+    #[test]
+    fn coerces_stringly_typed_args() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "fee_tier": { "type": "integer" },
+                "with_usd": { "type": "boolean" },
+                "tokens": { "type": "array", "items": { "type": "string" } },
+                "amount": { "type": "string" },
+                "ratio": { "type": "number" }
+            }
+        });
+        let mut args = json!({
+            "fee_tier": "3000",
+            "with_usd": "true",
+            "tokens": "a, b",
+            "amount": 0.5,
+            "ratio": "1.25"
+        });
+        let coerced = coerce(&schema, &mut args);
+        assert_eq!(
+            args,
+            json!({
+                "fee_tier": 3000,
+                "with_usd": true,
+                "tokens": ["a", "b"],
+                "amount": "0.5",
+                "ratio": 1.25
+            })
+        );
+        assert!(coerced.contains(&"'fee_tier': string → integer".to_string()));
+        assert_eq!(coerced.len(), 5);
+        assert!(validate(&schema, &args).is_ok());
+    }
+
+    #[test]
+    fn leaves_well_typed_and_unparseable_values_alone() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "fee_tier": { "type": "integer" },
+                "flag": { "type": "boolean" },
+                "ids": { "type": "array", "items": { "type": "integer" } }
+            }
+        });
+        let mut args = json!({ "fee_tier": 500, "flag": "yes", "ids": "[1, \"2\"]", "other": "3" });
+        let coerced = coerce(&schema, &mut args);
+        // JSON-encoded arrays are parsed, then their items coerced
+        assert_eq!(args["ids"], json!([1, 2]));
+        assert_eq!(args["flag"], "yes");
+        assert_eq!(args["other"], "3");
+        assert_eq!(coerced.len(), 2);
+        // Still rejected: "yes" isn't a boolean
+        assert!(validate(&schema, &args).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+// Paw Agent Engine — Tool result cache
+//
+// Opt-in LRU cache for idempotent read tools. Repeated identical reads
+// (same board, same balance, same static URL within seconds) are answered
+// from memory instead of spending API quota and a network round-trip.
+//
+// Only tools the metadata registry knows as ReadOnly are cached — unknown,
+// dynamic (MCP) and mutating tools always execute. When any other tool runs,
+// cached reads in its domain are dropped (a `write_file` invalidates
+// `read_file`, a `dex_swap` invalidates `dex_balance`).
+//
+// Keys are (agent, tool name, normalized args); only successful results are
+// stored. Stored as JSON under the `tool_cache_config` config key.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use crate::engine::tool_metadata::{self, ToolDomain, ToolMutability};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// engine_config key holding the serialized [`ToolCacheConfig`].
+pub const TOOL_CACHE_CONFIG_KEY: &str = "tool_cache_config";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolCacheConfig {
+    /// Off by default — stale reads are worse than slow ones unless asked for.
+    pub enabled: bool,
+    /// TTL for cacheable tools without an override.
+    pub default_ttl_secs: u64,
+    /// Per-tool TTLs. 0 = never cache that tool.
+    pub ttl_secs: BTreeMap<String, u64>,
+    pub max_entries: usize,
+}
+
+impl Default for ToolCacheConfig {
+    fn default() -> Self {
+        // Balances move fast — keep them short
+        let ttl_secs = [("dex_balance", 10), ("dex_portfolio", 10)]
+            .into_iter()
+            .map(|(tool, secs)| (tool.to_string(), secs))
+            .collect();
+        ToolCacheConfig {
+            enabled: false,
+            default_ttl_secs: 30,
+            ttl_secs,
+            max_entries: 256,
+        }
+    }
+}
+
+impl ToolCacheConfig {
+    /// TTL for `tool`, or None when it must not be cached.
+    pub fn ttl_for(&self, tool: &str) -> Option<Duration> {
+        if !self.enabled || !is_cacheable(tool) {
+            return None;
+        }
+        let secs = self
+            .ttl_secs
+            .get(tool)
+            .copied()
+            .unwrap_or(self.default_ttl_secs);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
+/// Known read-only tools only. Dynamic names fall through to "execute".
+pub fn is_cacheable(tool: &str) -> bool {
+    tool_metadata::get(tool).is_some_and(|m| m.mutability == ToolMutability::ReadOnly)
+}
+
+/// Hit / miss counters for the settings UI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    tool: String,
+    output: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct ToolResultCache {
+    config: ToolCacheConfig,
+    /// Least recently used first.
+    entries: Vec<Entry>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ToolResultCache {
+    pub fn new(config: ToolCacheConfig) -> Self {
+        ToolResultCache {
+            config,
+            entries: Vec::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn config(&self) -> &ToolCacheConfig {
+        &self.config
+    }
+
+    /// Apply new settings. Cached results are dropped since their TTLs
+    /// were computed under the old ones.
+    pub fn set_config(&mut self, config: ToolCacheConfig) {
+        self.config = config;
+        self.flush();
+    }
+
+    /// Cached output for this call, if fresh.
+    pub fn get(&mut self, tool: &str, args: &serde_json::Value, agent_id: &str) -> Option<String> {
+        self.get_at(tool, args, agent_id, Instant::now())
+    }
+
+    /// Remember a successful result (ignored for tools that aren't cacheable
+    /// and for outputs that report a failure).
+    pub fn put(&mut self, tool: &str, args: &serde_json::Value, agent_id: &str, output: &str) {
+        self.put_at(tool, args, agent_id, output, Instant::now());
+    }
+
+    /// Call after executing `tool`: anything that isn't a cacheable read
+    /// drops the cached reads of its domain. Tools whose effects can't be
+    /// scoped (`exec`, MCP and unknown tools) drop everything.
+    pub fn note_executed(&mut self, tool: &str) {
+        if is_cacheable(tool) || self.entries.is_empty() {
+            return;
+        }
+        match tool_metadata::domain(tool) {
+            ToolDomain::System | ToolDomain::Mcp | ToolDomain::Other => {
+                self.flush();
+            }
+            domain => self
+                .entries
+                .retain(|e| tool_metadata::domain(&e.tool) != domain),
+        }
+    }
+
+    /// Drop every cached result. Returns how many were dropped.
+    pub fn flush(&mut self) -> usize {
+        let dropped = self.entries.len();
+        self.entries.clear();
+        dropped
+    }
+
+    pub fn stats(&self) -> ToolCacheStats {
+        ToolCacheStats {
+            entries: self.entries.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    fn get_at(
+        &mut self,
+        tool: &str,
+        args: &serde_json::Value,
+        agent_id: &str,
+        now: Instant,
+    ) -> Option<String> {
+        self.config.ttl_for(tool)?;
+        let key = cache_key(tool, args, agent_id);
+        let Some(idx) = self.entries.iter().position(|e| e.key == key) else {
+            self.misses += 1;
+            return None;
+        };
+        if self.entries[idx].expires_at <= now {
+            self.entries.remove(idx);
+            self.misses += 1;
+            return None;
+        }
+        // Move to the most-recently-used end
+        let entry = self.entries.remove(idx);
+        let output = entry.output.clone();
+        self.entries.push(entry);
+        self.hits += 1;
+        Some(output)
+    }
+
+    fn put_at(
+        &mut self,
+        tool: &str,
+        args: &serde_json::Value,
+        agent_id: &str,
+        output: &str,
+        now: Instant,
+    ) {
+        let Some(ttl) = self.config.ttl_for(tool) else {
+            return;
+        };
+        if reports_failure(output) {
+            return;
+        }
+        let key = cache_key(tool, args, agent_id);
+        self.entries.retain(|e| e.key != key);
+        while !self.entries.is_empty() && self.entries.len() >= self.config.max_entries {
+            self.entries.remove(0);
+        }
+        if self.config.max_entries == 0 {
+            return;
+        }
+        self.entries.push(Entry {
+            key,
+            tool: tool.to_string(),
+            output: output.to_string(),
+            expires_at: now + ttl,
+        });
+    }
+}
+
+/// Tools report many failures as text rather than Err ("Error: …",
+/// "[failed] …", fetch's "HTTP 404 Error"); those are never cached, so a
+/// retry re-executes.
+fn reports_failure(output: &str) -> bool {
+    let first_line = output.trim_start().lines().next().unwrap_or("");
+    first_line.starts_with("Error")
+        || first_line.starts_with("[failed]")
+        || (first_line.starts_with("HTTP ") && first_line.ends_with(" Error"))
+}
+
+/// Agent + tool + args with object keys sorted, so `{"a":1,"b":2}` and
+/// `{"b":2,"a":1}` share an entry.
+fn cache_key(tool: &str, args: &serde_json::Value, agent_id: &str) -> String {
+    let mut key = format!("{}\u{0}{}\u{0}", agent_id, tool);
+    write_normalized(args, &mut key);
+    key
+}
+
+pub(crate) fn write_normalized(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, k) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(k.clone()).to_string());
+                out.push(':');
+                write_normalized(&map[k], out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_normalized(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// Load the cache settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> ToolCacheConfig {
+    store
+        .get_config(TOOL_CACHE_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, config: &ToolCacheConfig) -> EngineResult<()> {
+    store.set_config(TOOL_CACHE_CONFIG_KEY, &serde_json::to_string(config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache() -> ToolResultCache {
+        ToolResultCache::new(ToolCacheConfig {
+            enabled: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn identical_read_hits_within_ttl_and_reexecutes_after() {
+        let mut cache = cache();
+        let start = Instant::now();
+        let args = json!({ "board_id": "b1", "include": ["cards", "lists"] });
+        let reordered = json!({ "include": ["cards", "lists"], "board_id": "b1" });
+
+        assert!(cache
+            .get_at("trello_get_board", &args, "a", start)
+            .is_none());
+        cache.put_at("trello_get_board", &args, "a", "board json", start);
+
+        let later = start + Duration::from_secs(29);
+        assert_eq!(
+            cache
+                .get_at("trello_get_board", &reordered, "a", later)
+                .as_deref(),
+            Some("board json")
+        );
+        // Other agents and other args don't share the entry
+        assert!(cache
+            .get_at("trello_get_board", &args, "b", later)
+            .is_none());
+        assert!(cache
+            .get_at("trello_get_board", &json!({ "board_id": "b2" }), "a", later)
+            .is_none());
+
+        let expired = start + Duration::from_secs(31);
+        assert!(cache
+            .get_at("trello_get_board", &args, "a", expired)
+            .is_none());
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn per_tool_ttl_overrides_default() {
+        let mut cache = cache();
+        let start = Instant::now();
+        let args = json!({ "token": "ETH" });
+        cache.put_at("dex_balance", &args, "a", "1.5 ETH", start);
+        assert!(cache
+            .get_at("dex_balance", &args, "a", start + Duration::from_secs(9))
+            .is_some());
+        assert!(cache
+            .get_at("dex_balance", &args, "a", start + Duration::from_secs(11))
+            .is_none());
+    }
+
+    #[test]
+    fn mutating_and_unknown_tools_are_never_cached() {
+        let mut cache = cache();
+        let now = Instant::now();
+        for tool in ["dex_swap", "write_file", "exec", "mcp_someserver_do_thing"] {
+            cache.put_at(tool, &json!({}), "a", "done", now);
+            assert!(cache.get_at(tool, &json!({}), "a", now).is_none(), "{tool}");
+        }
+        assert_eq!(cache.stats().entries, 0);
+
+        // Disabled cache stores nothing
+        let mut off = ToolResultCache::new(ToolCacheConfig::default());
+        off.put_at("fetch", &json!({ "url": "https://x" }), "a", "page", now);
+        assert!(off
+            .get_at("fetch", &json!({ "url": "https://x" }), "a", now)
+            .is_none());
+    }
+
+    #[test]
+    fn failure_outputs_are_not_cached() {
+        let mut cache = cache();
+        let now = Instant::now();
+        let url = json!({ "url": "https://x" });
+        for output in [
+            "Error: connection refused",
+            "HTTP 503 Error\nURL: https://x\n\nbusy",
+            "[failed] Nothing found",
+        ] {
+            cache.put_at("fetch", &url, "a", output, now);
+            assert!(cache.get_at("fetch", &url, "a", now).is_none(), "{output}");
+        }
+        cache.put_at(
+            "fetch",
+            &url,
+            "a",
+            "HTTP 200 OK\nURL: https://x\n\npage",
+            now,
+        );
+        assert!(cache.get_at("fetch", &url, "a", now).is_some());
+    }
+
+    #[test]
+    fn writes_invalidate_cached_reads() {
+        let mut cache = cache();
+        let now = Instant::now();
+        let path = json!({ "path": "notes.md" });
+        cache.put_at("read_file", &path, "a", "old", now);
+        cache.put_at("fetch", &json!({ "url": "https://x" }), "a", "page", now);
+
+        cache.note_executed("write_file");
+        assert!(cache.get_at("read_file", &path, "a", now).is_none());
+        assert!(cache
+            .get_at("fetch", &json!({ "url": "https://x" }), "a", now)
+            .is_some());
+
+        // exec can touch anything
+        cache.put_at("read_file", &path, "a", "old", now);
+        cache.note_executed("exec");
+        assert_eq!(cache.stats().entries, 0);
+
+        cache.put_at("read_file", &path, "a", "new", now);
+        assert_eq!(cache.flush(), 1);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ToolResultCache::new(ToolCacheConfig {
+            enabled: true,
+            max_entries: 2,
+            ..Default::default()
+        });
+        let now = Instant::now();
+        let url = |u: &str| json!({ "url": u });
+        cache.put_at("fetch", &url("1"), "a", "one", now);
+        cache.put_at("fetch", &url("2"), "a", "two", now);
+        // Touch 1 so 2 becomes the eviction candidate
+        assert!(cache.get_at("fetch", &url("1"), "a", now).is_some());
+        cache.put_at("fetch", &url("3"), "a", "three", now);
+
+        assert!(cache.get_at("fetch", &url("1"), "a", now).is_some());
+        assert!(cache.get_at("fetch", &url("2"), "a", now).is_none());
+        assert!(cache.get_at("fetch", &url("3"), "a", now).is_some());
+    }
+}
@@ -0,0 +1,103 @@
+// Paw Agent Engine — Tool-iteration guard
+//
+// Safety net against pathological loops where the model keeps requesting
+// tools without ever concluding. Counts tool calls across a whole run
+// (independent of max_tool_rounds, since one round can batch many calls):
+//
+// - Under the limit → continue.
+// - A round that would exceed it → skip those calls and nudge the model
+//   to answer with what it has.
+// - Any further tool request after the nudge → stop the run.
+
+/// What the agent loop should do with the tool calls of the current round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationVerdict {
+    /// Execute the calls as normal.
+    Continue,
+    /// Skip the calls and inject [`ToolIterationGuard::nudge_message`].
+    Nudge,
+    /// Terminate the run with [`ToolIterationGuard::stop_message`].
+    Stop,
+}
+
+/// Per-run counter for `max_tool_iterations`.
+#[derive(Debug, Clone)]
+pub struct ToolIterationGuard {
+    /// 0 disables the guard.
+    limit: u32,
+    count: u32,
+    nudged: bool,
+}
+
+impl ToolIterationGuard {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            count: 0,
+            nudged: false,
+        }
+    }
+
+    /// Judge a round that requests `calls` tool calls. Calls are only
+    /// counted when the verdict is `Continue`.
+    pub fn record(&mut self, calls: usize) -> IterationVerdict {
+        if self.limit == 0 || calls == 0 {
+            return IterationVerdict::Continue;
+        }
+        if self.nudged {
+            return IterationVerdict::Stop;
+        }
+        if self.count.saturating_add(calls as u32) > self.limit {
+            self.nudged = true;
+            return IterationVerdict::Nudge;
+        }
+        self.count += calls as u32;
+        IterationVerdict::Continue
+    }
+
+    /// Tool calls executed so far in this run.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn nudge_message(&self) -> String {
+        format!(
+            "[SYSTEM] You have reached the tool-call limit for this run ({} calls). \
+             Do NOT call any more tools. Reply to the user now with what you have \
+             accomplished so far and what remains to be done.",
+            self.limit
+        )
+    }
+
+    pub fn stop_message(&self) -> String {
+        format!(
+            "I stopped after reaching the tool-call limit for this run ({} calls) without \
+             finishing. You can continue the conversation or raise the limit in \
+             Settings → Engine (Max Tool Calls per Run).",
+            self.limit
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nudges_then_stops() {
+        let mut guard = ToolIterationGuard::new(3);
+        assert_eq!(guard.record(2), IterationVerdict::Continue);
+        assert_eq!(guard.record(1), IterationVerdict::Continue);
+        assert_eq!(guard.record(1), IterationVerdict::Nudge);
+        assert_eq!(guard.count(), 3);
+        assert_eq!(guard.record(1), IterationVerdict::Stop);
+    }
+
+    #[test]
+    fn zero_limit_disables() {
+        let mut guard = ToolIterationGuard::new(0);
+        for _ in 0..1000 {
+            assert_eq!(guard.record(5), IterationVerdict::Continue);
+        }
+    }
+}
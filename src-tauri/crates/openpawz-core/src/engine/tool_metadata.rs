@@ -70,6 +70,7 @@ pub enum ToolDomain {
     Discord,
     Discourse,
     Trello,
+    Notion,
     Microsoft,
     Mcp,
     N8n,
@@ -225,6 +226,19 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
     tool!("create_task", Reversible, WriteLocal, Tasks, true, true),
     tool!("list_tasks", Safe, ReadOnly, Tasks, true, true),
     tool!("manage_task", Reversible, WriteLocal, Tasks, true, false),
+    tool!("task_add", Reversible, WriteLocal, Tasks, true, false),
+    tool!("task_complete", Reversible, WriteLocal, Tasks, true, false),
+    tool!("task_list", Safe, ReadOnly, Tasks, true, false),
+    tool!("reminder_set", Reversible, WriteLocal, Tasks, true, false),
+    tool!("reminder_list", Safe, ReadOnly, Tasks, true, false),
+    tool!(
+        "reminder_cancel",
+        Reversible,
+        WriteLocal,
+        Tasks,
+        true,
+        false
+    ),
     // ── Skills ──────────────────────────────────────────────────────────
     tool!("skill_search", Safe, ReadOnly, Skills, true, false),
     tool!("skill_list", Safe, ReadOnly, Skills, true, false),
@@ -283,6 +297,26 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
         true,
         false
     ),
+    tool!("skill_state_get", Safe, ReadOnly, Storage, true, false),
+    tool!(
+        "skill_state_set",
+        Reversible,
+        WriteLocal,
+        Storage,
+        true,
+        false
+    ),
+    tool!(
+        "skill_state_delete",
+        Reversible,
+        WriteLocal,
+        Storage,
+        true,
+        false
+    ),
+    tool!("note_write", Reversible, WriteLocal, Storage, true, false),
+    tool!("note_read", Safe, ReadOnly, Storage, true, false),
+    tool!("note_list", Safe, ReadOnly, Storage, true, false),
     // ── Email ───────────────────────────────────────────────────────────
     tool!("email_send", External, WriteSideEffect, Email, false, false),
     tool!("email_read", Safe, ReadOnly, Email, true, true),
@@ -339,6 +373,7 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
         true,
         true
     ),
+    tool!("speak", External, WriteSideEffect, Integrations, true, true),
     tool!(
         "service_api",
         External,
@@ -547,6 +582,36 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
         true,
         false
     ),
+    // ── Notion ──────────────────────────────────────────────────────────
+    tool!("notion_search", Safe, ReadOnly, Notion, true, false),
+    tool!("notion_list_databases", Safe, ReadOnly, Notion, true, false),
+    tool!("notion_query_database", Safe, ReadOnly, Notion, true, false),
+    tool!("notion_get_page", Safe, ReadOnly, Notion, true, false),
+    tool!("notion_get_blocks", Safe, ReadOnly, Notion, true, false),
+    tool!(
+        "notion_create_page",
+        External,
+        WriteSideEffect,
+        Notion,
+        true,
+        false
+    ),
+    tool!(
+        "notion_update_page",
+        External,
+        WriteSideEffect,
+        Notion,
+        true,
+        false
+    ),
+    tool!(
+        "notion_append_blocks",
+        External,
+        WriteSideEffect,
+        Notion,
+        true,
+        false
+    ),
     // ── Coinbase ────────────────────────────────────────────────────────
     tool!("coinbase_prices", Safe, ReadOnly, Coinbase, true, false),
     tool!("coinbase_balance", Safe, ReadOnly, Coinbase, true, false),
@@ -598,6 +663,9 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
     ),
     // ── EVM DEX ─────────────────────────────────────────────────────────
     tool!("dex_balance", Safe, ReadOnly, Dex, true, false),
+    tool!("dex_wallet_list", Safe, ReadOnly, Dex, true, false),
+    tool!("dex_tx_history", Safe, ReadOnly, Dex, true, false),
+    tool!("dex_switch_chain", External, WriteLocal, Dex, true, false),
     tool!("dex_quote", Safe, ReadOnly, Dex, true, false),
     tool!("dex_portfolio", Safe, ReadOnly, Dex, true, false),
     tool!("dex_token_info", Safe, ReadOnly, Dex, true, false),
@@ -609,6 +677,14 @@ pub static TOOL_REGISTRY: &[ToolMeta] = &[
     tool!("dex_trending", Safe, ReadOnly, Dex, true, false),
     tool!("dex_swap", Dangerous, WriteSideEffect, Dex, true, false),
     tool!("dex_transfer", Dangerous, WriteSideEffect, Dex, true, false),
+    tool!(
+        "dex_sign_typed_data",
+        Dangerous,
+        WriteSideEffect,
+        Dex,
+        true,
+        false
+    ),
     tool!(
         "dex_wallet_create",
         Dangerous,
@@ -665,7 +741,16 @@ static TOOL_MAP: LazyLock<HashMap<&'static str, &'static ToolMeta>> =
 
 /// Look up metadata for a known tool. Returns `None` for MCP/dynamic tools.
 pub fn get(name: &str) -> Option<&'static ToolMeta> {
-    TOOL_MAP.get(name).copied()
+    TOOL_MAP.get(registry_name(name)).copied()
+}
+
+/// Per-skill state tools (`skill_state_get__<skill>`) share their
+/// family's entry.
+fn registry_name(name: &str) -> &str {
+    match name.split_once("__") {
+        Some((family, _)) if family.starts_with("skill_state_") => family,
+        _ => name,
+    }
 }
 
 /// Get the safety tier for a tool. Unknown tools default to `External`
@@ -674,6 +759,23 @@ pub fn tier(name: &str) -> ToolTier {
     get(name).map_or(ToolTier::External, |m| m.tier)
 }
 
+/// Safety tier for one call. Some tools reach other people only with
+/// certain arguments; those calls are raised to `External` so they go
+/// through approval like any other outbound message.
+pub fn call_tier(name: &str, arguments: &str) -> ToolTier {
+    let base = tier(name);
+    if name == "reminder_set" {
+        let args: serde_json::Value = serde_json::from_str(arguments).unwrap_or_default();
+        let delivers = ["channel", "recipient"]
+            .iter()
+            .any(|k| args[*k].as_str().is_some_and(|v| !v.trim().is_empty()));
+        if delivers {
+            return ToolTier::External;
+        }
+    }
+    base
+}
+
 /// Get the mutability classification. Unknown tools default to `WriteSideEffect`
 /// (never speculate) which is the safe default.
 pub fn mutability(name: &str) -> ToolMutability {
@@ -707,6 +809,9 @@ pub fn domain(name: &str) -> ToolDomain {
     if name.starts_with("trello_") {
         return ToolDomain::Trello;
     }
+    if name.starts_with("notion_") {
+        return ToolDomain::Notion;
+    }
     if name.starts_with("outlook_")
         || name.starts_with("onedrive_")
         || name.starts_with("teams_")
@@ -768,6 +873,7 @@ pub fn domain_str(name: &str) -> &'static str {
         ToolDomain::Discord => "discord",
         ToolDomain::Discourse => "discourse",
         ToolDomain::Trello => "trello",
+        ToolDomain::Notion => "notion",
         ToolDomain::Microsoft => "microsoft",
         ToolDomain::Mcp => "mcp",
         ToolDomain::N8n => "n8n",
@@ -852,6 +958,32 @@ pub fn auto_approved_tools() -> Vec<&'static str> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn per_skill_state_tools_share_their_family_entry() {
+        assert_eq!(tier("skill_state_get__acme_counter"), ToolTier::Safe);
+        assert_eq!(tier("skill_state_set__acme_counter"), ToolTier::Reversible);
+        assert!(worker_allowed("skill_state_delete__acme_counter"));
+    }
+
+    #[test]
+    fn channel_reminders_need_approval() {
+        assert_eq!(
+            call_tier("reminder_set", r#"{"when":"in 1h","text":"stretch"}"#),
+            ToolTier::Reversible
+        );
+        assert_eq!(
+            call_tier(
+                "reminder_set",
+                r#"{"when":"in 1h","text":"hi","channel":"whatsapp","recipient":"123@s.whatsapp.net"}"#
+            ),
+            ToolTier::External
+        );
+        assert_eq!(
+            call_tier("memory_store", r#"{"channel":"x"}"#),
+            tier("memory_store")
+        );
+    }
+
     #[test]
     fn no_duplicate_tool_names() {
         let mut seen = std::collections::HashSet::new();
@@ -0,0 +1,209 @@
+// Paw Agent Engine — Live tool progress events
+//
+// `tool_request` only fires when a tool needs approval and `tool_result`
+// carries the full output, so a turn full of auto-approved tools shows
+// nothing until each one finishes. `run_with_progress` wraps a tool run and
+// emits `tool_start` (name + short argument summary) before it and
+// `tool_end` (short result summary, success, latency) after it, so the chat
+// can render "Running trello_create_card…" live and then the outcome.
+//
+// Both summaries are size-capped, scrubbed of registered secrets, and
+// argument values under secret-looking keys are masked — these payloads are
+// for display only; the model and the audit log still see the full data.
+
+use crate::engine::secret_scrub;
+use crate::engine::types::{EngineEvent, ToolCall, ToolResult};
+use crate::engine::util::safe_truncate;
+use serde_json::Value;
+use std::future::Future;
+use std::time::Instant;
+
+/// Longest argument value shown in `args_summary`.
+const MAX_ARG_VALUE_CHARS: usize = 60;
+/// Cap on the whole argument summary.
+pub const MAX_ARGS_SUMMARY_BYTES: usize = 200;
+/// Cap on the result summary.
+pub const MAX_RESULT_SUMMARY_BYTES: usize = 300;
+
+const MASK: &str = "•••";
+
+fn cap(text: &str, max_bytes: usize) -> String {
+    let text = text.trim();
+    if text.len() <= max_bytes {
+        text.to_string()
+    } else {
+        format!("{}…", safe_truncate(text, max_bytes))
+    }
+}
+
+fn value_preview(key: &str, value: &Value) -> String {
+    if secret_scrub::is_secret_field(key) {
+        return MASK.into();
+    }
+    let text = match value {
+        Value::String(s) => format!("\"{}\"", s.replace('\n', " ")),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(map) => format!("{{{} fields}}", map.len()),
+        other => other.to_string(),
+    };
+    let mut chars = text.chars();
+    let short: String = chars.by_ref().take(MAX_ARG_VALUE_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}…", short)
+    } else {
+        short
+    }
+}
+
+/// One-line `key=value` summary of a tool call's JSON arguments.
+pub fn summarize_args(args_json: &str) -> String {
+    let summary = match serde_json::from_str::<Value>(args_json) {
+        Ok(Value::Object(map)) => map
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, value_preview(k, v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Ok(Value::Null) | Err(_) if args_json.trim().is_empty() => String::new(),
+        Ok(other) => other.to_string(),
+        Err(_) => "(unparsed arguments)".into(),
+    };
+    cap(&secret_scrub::scrub(&summary), MAX_ARGS_SUMMARY_BYTES)
+}
+
+/// Short, single-line excerpt of a tool's output.
+pub fn summarize_result(output: &str) -> String {
+    let first_lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .take(3)
+        .collect::<Vec<_>>()
+        .join(" · ");
+    cap(&secret_scrub::scrub(&first_lines), MAX_RESULT_SUMMARY_BYTES)
+}
+
+/// Run one tool call, emitting `tool_start` before and `tool_end` after.
+/// `run` must always produce a result (map cancellation and timeouts to a
+/// failed `ToolResult`) so every start is paired with an end.
+pub async fn run_with_progress<E, F>(
+    emit: E,
+    session_id: &str,
+    run_id: &str,
+    tool_call: &ToolCall,
+    run: F,
+) -> ToolResult
+where
+    E: Fn(EngineEvent),
+    F: Future<Output = ToolResult>,
+{
+    emit(EngineEvent::ToolStart {
+        session_id: session_id.to_string(),
+        run_id: run_id.to_string(),
+        tool_call_id: tool_call.id.clone(),
+        tool_name: tool_call.function.name.clone(),
+        args_summary: summarize_args(&tool_call.function.arguments),
+    });
+    let started = Instant::now();
+    let result = run.await;
+    emit(EngineEvent::ToolEnd {
+        session_id: session_id.to_string(),
+        run_id: run_id.to_string(),
+        tool_call_id: tool_call.id.clone(),
+        tool_name: tool_call.function.name.clone(),
+        result_summary: summarize_result(&result.output),
+        success: result.success,
+        duration_ms: started.elapsed().as_millis() as u64,
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::types::FunctionCall;
+    use parking_lot::Mutex;
+
+    fn call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".into(),
+            call_type: "function".into(),
+            function: FunctionCall {
+                name: name.into(),
+                arguments: arguments.into(),
+            },
+            thought_signature: None,
+            thought_parts: vec![],
+        }
+    }
+
+    async fn events_for(success: bool) -> Vec<EngineEvent> {
+        let events = Mutex::new(Vec::new());
+        let tc = call(
+            "trello_create_card",
+            r#"{"name":"Ship it","api_token":"abc123"}"#,
+        );
+        let result = run_with_progress(|e| events.lock().push(e), "s1", "r1", &tc, async {
+            ToolResult {
+                tool_call_id: "call_1".into(),
+                output: if success {
+                    "Created card 42".into()
+                } else {
+                    "Error: board not found".into()
+                },
+                success,
+            }
+        })
+        .await;
+        assert_eq!(result.success, success);
+        events.into_inner()
+    }
+
+    #[tokio::test]
+    async fn tool_run_emits_start_then_end_with_success_flag() {
+        for success in [true, false] {
+            let events = events_for(success).await;
+            assert_eq!(events.len(), 2);
+            let EngineEvent::ToolStart {
+                tool_name,
+                args_summary,
+                ..
+            } = &events[0]
+            else {
+                panic!("first event should be tool_start: {:?}", events[0]);
+            };
+            assert_eq!(tool_name, "trello_create_card");
+            assert!(
+                args_summary.contains(r#"name="Ship it""#),
+                "{}",
+                args_summary
+            );
+            assert!(!args_summary.contains("abc123"), "{}", args_summary);
+
+            let EngineEvent::ToolEnd {
+                tool_call_id,
+                success: ended_ok,
+                result_summary,
+                ..
+            } = &events[1]
+            else {
+                panic!("second event should be tool_end: {:?}", events[1]);
+            };
+            assert_eq!(tool_call_id, "call_1");
+            assert_eq!(*ended_ok, success);
+            assert!(!result_summary.is_empty());
+        }
+    }
+
+    #[test]
+    fn summaries_are_size_capped() {
+        let long = "x".repeat(5_000);
+        let args = serde_json::json!({ "content": long, "path": "notes.md" }).to_string();
+        let summary = summarize_args(&args);
+        assert!(summary.len() <= MAX_ARGS_SUMMARY_BYTES + '…'.len_utf8());
+        assert!(summary.contains("path=\"notes.md\""));
+
+        let output = format!("line one\n\n{}\nline three\nline four", long);
+        assert!(summarize_result(&output).len() <= MAX_RESULT_SUMMARY_BYTES + '…'.len_utf8());
+        assert_eq!(summarize_args(""), "");
+    }
+}
@@ -0,0 +1,189 @@
+// Paw Agent Engine — Tool-result context policy
+//
+// Tool output is the bulkiest part of most histories: a file read or a web
+// fetch can be tens of kilobytes, and it is re-sent with every later turn.
+// Once a result is `after_turns` user turns old the model has already acted
+// on it, so `load_conversation` swaps its content for a short placeholder —
+// an excerpt (`summarize`) or just a reference (`drop`). The message itself
+// stays, so tool_use / tool_result pairing is intact, and the stored row is
+// never modified: the UI still shows the full output.
+//
+// Separate from conversation compaction, which rewrites the whole history.
+// Stored as JSON under the `tool_result_policy` config key.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use crate::engine::types::{Message, MessageContent, Role};
+use serde::{Deserialize, Serialize};
+
+/// engine_config key holding the serialized [`ToolResultPolicy`].
+pub const TOOL_RESULT_POLICY_KEY: &str = "tool_result_policy";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResultMode {
+    /// Send old results in full.
+    Keep,
+    /// Replace old results with a short excerpt.
+    #[default]
+    Summarize,
+    /// Replace old results with a one-line reference.
+    Drop,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolResultPolicy {
+    pub mode: ToolResultMode,
+    /// User turns after which a result counts as old.
+    pub after_turns: usize,
+    /// Results at or below this size are always kept.
+    pub min_chars: usize,
+    /// Excerpt length for `summarize`.
+    pub excerpt_chars: usize,
+}
+
+impl Default for ToolResultPolicy {
+    fn default() -> Self {
+        ToolResultPolicy {
+            mode: ToolResultMode::Summarize,
+            after_turns: 3,
+            min_chars: 1_000,
+            excerpt_chars: 200,
+        }
+    }
+}
+
+/// Replace old, large tool results in `messages` with placeholders.
+/// Returns the number of results elided.
+pub fn apply(messages: &mut [Message], policy: &ToolResultPolicy) -> usize {
+    if policy.mode == ToolResultMode::Keep {
+        return 0;
+    }
+    let mut elided = 0;
+    // Walk newest → oldest so the user turns after each message are known
+    let mut turns_after = 0;
+    for msg in messages.iter_mut().rev() {
+        match msg.role {
+            Role::User => turns_after += 1,
+            Role::Tool if turns_after >= policy.after_turns.max(1) => {
+                let MessageContent::Text(text) = &msg.content else {
+                    continue;
+                };
+                if text.chars().count() <= policy.min_chars {
+                    continue;
+                }
+                let placeholder = placeholder(msg.name.as_deref(), text, turns_after, policy);
+                msg.content = MessageContent::Text(placeholder);
+                elided += 1;
+            }
+            _ => {}
+        }
+    }
+    if elided > 0 {
+        log::info!(
+            "[engine] Tool-result policy: elided {} old tool result(s) from context",
+            elided
+        );
+    }
+    elided
+}
+
+fn placeholder(name: Option<&str>, text: &str, turns: usize, policy: &ToolResultPolicy) -> String {
+    let name = name.unwrap_or("tool");
+    let chars = text.chars().count();
+    match policy.mode {
+        ToolResultMode::Summarize => {
+            let excerpt: String = text.chars().take(policy.excerpt_chars).collect();
+            format!(
+                "[Earlier `{}` result ({} chars) trimmed from context after {} turns. \
+                 Excerpt: {}…]",
+                name,
+                chars,
+                turns,
+                excerpt.trim()
+            )
+        }
+        _ => format!(
+            "[Earlier `{}` result ({} chars) omitted from context — re-run the tool if it's needed again.]",
+            name, chars
+        ),
+    }
+}
+
+/// Load the policy, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> ToolResultPolicy {
+    store
+        .get_config(TOOL_RESULT_POLICY_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, policy: &ToolResultPolicy) -> EngineResult<()> {
+    store.set_config(TOOL_RESULT_POLICY_KEY, &serde_json::to_string(policy)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: Role, text: &str) -> Message {
+        let name = (role == Role::Tool).then(|| "fetch".to_string());
+        Message {
+            role,
+            content: MessageContent::Text(text.to_string()),
+            tool_calls: None,
+            tool_call_id: None,
+            name,
+        }
+    }
+
+    #[test]
+    fn only_old_large_results_are_replaced() {
+        let big = "x".repeat(5_000);
+        let mut messages = vec![
+            msg(Role::User, "fetch it"),
+            msg(Role::Tool, &big),
+            msg(Role::Tool, "small"),
+            msg(Role::User, "turn 2"),
+            msg(Role::User, "turn 3"),
+            msg(Role::Tool, &big),
+        ];
+        let policy = ToolResultPolicy {
+            after_turns: 2,
+            ..Default::default()
+        };
+        assert_eq!(apply(&mut messages, &policy), 1);
+        let old = messages[1].content.as_text();
+        assert!(old.starts_with("[Earlier `fetch` result (5000 chars)"));
+        assert!(old.len() < 400);
+        assert_eq!(messages[2].content.as_text(), "small");
+        assert_eq!(messages[5].content.as_text().len(), 5_000);
+    }
+
+    #[test]
+    fn keep_mode_leaves_history_alone() {
+        let mut messages = vec![
+            msg(Role::Tool, &"y".repeat(5_000)),
+            msg(Role::User, "a"),
+            msg(Role::User, "b"),
+            msg(Role::User, "c"),
+        ];
+        let policy = ToolResultPolicy {
+            mode: ToolResultMode::Keep,
+            ..Default::default()
+        };
+        assert_eq!(apply(&mut messages, &policy), 0);
+        let drop = ToolResultPolicy {
+            mode: ToolResultMode::Drop,
+            ..Default::default()
+        };
+        assert_eq!(apply(&mut messages, &drop), 1);
+        assert!(messages[0]
+            .content
+            .as_text()
+            .contains("omitted from context"));
+    }
+}
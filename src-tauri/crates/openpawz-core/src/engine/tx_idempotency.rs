@@ -0,0 +1,370 @@
+// Paw Agent Engine — Idempotent financial tool calls
+//
+// A retried turn (network blip, stream resume) can replay the model's last
+// tool call, and for tools that move funds the replay must not broadcast a
+// second transaction. Each call is keyed by a hash of the tool name, its
+// normalized arguments (minus `reason` and the confirmation timeout) and
+// the nonce context it signs in — chain and wallet address — so a replay
+// from any session or agent is caught. Within the window a duplicate gets the first call's
+// result, tx hash included, instead of executing again; one that arrives
+// while the first is still running is refused.
+//
+// Only calls that broadcast a transaction which hasn't reverted are
+// remembered: one that errored, was refused before sending ("[failed] Swap
+// not submitted") or reverted moved nothing and may be retried at once. A
+// call that was dropped mid-flight (cancelled run) may or may not have
+// broadcast, so its duplicates are refused for the window. `allow_repeat:
+// true` sends an intentional identical repeat anyway. In memory only — the
+// window is minutes, not days.
+
+use crate::engine::tool_cache::write_normalized;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Tools whose calls are deduplicated.
+pub const IDEMPOTENT_TOOLS: &[&str] = &["dex_swap", "dex_transfer"];
+
+/// How long an executed call blocks identical ones.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Arguments that describe the call rather than change what it does.
+const IGNORED_ARGS: &[&str] = &["reason", "confirm_timeout_secs", REPEAT_ARG];
+
+/// Set to true to execute a call even though an identical one already ran
+/// (or was interrupted) within the window.
+pub const REPEAT_ARG: &str = "allow_repeat";
+
+static LEDGER: LazyLock<Mutex<CallLedger>> =
+    LazyLock::new(|| Mutex::new(CallLedger::new(DEFAULT_WINDOW)));
+
+enum Prior {
+    Running,
+    Done(String),
+    Interrupted,
+}
+
+/// How a claimed call ended.
+enum Ending<'a> {
+    /// A transaction was broadcast and hasn't reverted; the tool's output.
+    Broadcast(&'a str),
+    /// Nothing (or only a reverted transaction) went out.
+    NotSent,
+    /// Dropped before it reported back.
+    Interrupted,
+}
+
+struct CallLedger {
+    window: Duration,
+    calls: HashMap<String, (Prior, Instant)>,
+}
+
+/// What to do with a financial tool call.
+pub enum Claim {
+    /// First of its kind — execute, then report back through the guard.
+    Execute(CallGuard),
+    /// Already executed within the window; the message carries its result.
+    Duplicate(String),
+    /// An identical call is executing right now.
+    InFlight(String),
+    /// An identical call was cut off and may have broadcast.
+    Interrupted(String),
+}
+
+/// Held while a claimed call executes. Dropping it without `complete`
+/// (e.g. the run was cancelled) marks the call interrupted.
+pub struct CallGuard {
+    key: String,
+    finished: bool,
+}
+
+impl CallLedger {
+    fn new(window: Duration) -> Self {
+        CallLedger {
+            window,
+            calls: HashMap::new(),
+        }
+    }
+
+    /// The prior call for `key`, or None after recording this one as running.
+    /// With `repeat`, only a running call counts as prior.
+    fn begin_at(&mut self, key: &str, repeat: bool, now: Instant) -> Option<&Prior> {
+        let window = self.window;
+        self.calls.retain(|_, (prior, at)| {
+            matches!(prior, Prior::Running) || now.duration_since(*at) < window
+        });
+        let running = matches!(self.calls.get(key), Some((Prior::Running, _)));
+        if !self.calls.contains_key(key) || (repeat && !running) {
+            self.calls.insert(key.to_string(), (Prior::Running, now));
+            return None;
+        }
+        self.calls.get(key).map(|(prior, _)| prior)
+    }
+
+    /// Record how a running call ended.
+    fn finish_at(&mut self, key: &str, ending: Ending, now: Instant) {
+        match ending {
+            Ending::Broadcast(output) => {
+                self.calls
+                    .insert(key.to_string(), (Prior::Done(output.to_string()), now));
+            }
+            Ending::NotSent => {
+                self.calls.remove(key);
+            }
+            Ending::Interrupted => {
+                self.calls
+                    .insert(key.to_string(), (Prior::Interrupted, now));
+            }
+        }
+    }
+}
+
+impl CallGuard {
+    /// Record the call's result. `broadcast` is whether it sent a
+    /// transaction that hasn't reverted; only those block identical calls.
+    pub fn complete(mut self, result: &Result<String, String>, broadcast: bool) {
+        let ending = match result {
+            Ok(output) if broadcast => Ending::Broadcast(output),
+            _ => Ending::NotSent,
+        };
+        LEDGER.lock().finish_at(&self.key, ending, Instant::now());
+        self.finished = true;
+    }
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            LEDGER
+                .lock()
+                .finish_at(&self.key, Ending::Interrupted, Instant::now());
+        }
+    }
+}
+
+/// Strings trimmed and case-folded (symbols, hex addresses), ignored args
+/// dropped at the top level.
+fn normalize(args: &serde_json::Value, top_level: bool) -> serde_json::Value {
+    match args {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .filter(|(k, _)| !(top_level && IGNORED_ARGS.contains(&k.as_str())))
+                .map(|(k, v)| (k.clone(), normalize(v, false)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(|v| normalize(v, false)).collect())
+        }
+        serde_json::Value::String(s) => serde_json::Value::String(s.trim().to_lowercase()),
+        other => other.clone(),
+    }
+}
+
+/// SHA-256 over tool, normalized args and nonce context (chain, wallet).
+fn call_key(tool: &str, args: &serde_json::Value, context: &[&str]) -> String {
+    let mut material = format!("{}\u{0}", tool);
+    for part in context {
+        material.push_str(&part.trim().to_lowercase());
+        material.push('\u{0}');
+    }
+    write_normalized(&normalize(args, true), &mut material);
+    format!("{:x}", Sha256::digest(material.as_bytes()))
+}
+
+/// Claim a call to `tool` before executing it. None for tools that aren't
+/// deduplicated.
+pub fn claim(tool: &str, args: &serde_json::Value, context: &[&str]) -> Option<Claim> {
+    if !IDEMPOTENT_TOOLS.contains(&tool) {
+        return None;
+    }
+    let key = call_key(tool, args, context);
+    let repeat = args[REPEAT_ARG].as_bool().unwrap_or(false);
+    let mins = DEFAULT_WINDOW.as_secs() / 60;
+    let claim = match LEDGER.lock().begin_at(&key, repeat, Instant::now()) {
+        None => Claim::Execute(CallGuard {
+            key,
+            finished: false,
+        }),
+        Some(Prior::Done(output)) => Claim::Duplicate(format!(
+            "[duplicate] An identical {} call already ran in the last {} minutes, so it was not sent again. If the user wants it repeated, call again with {}: true. Its result:\n\n{}",
+            tool, mins, REPEAT_ARG, output
+        )),
+        Some(Prior::Running) => Claim::InFlight(format!(
+            "An identical {} call is still running — not sending it twice. Wait for its result.",
+            tool
+        )),
+        Some(Prior::Interrupted) => Claim::Interrupted(format!(
+            "An identical {} call was interrupted before it reported back and may already have been broadcast. Check the wallet's recent transactions before repeating it (identical calls are refused for {} minutes unless {}: true is set).",
+            tool, mins, REPEAT_ARG
+        )),
+    };
+    log::info!(
+        "[idempotency] {} → {}",
+        tool,
+        match &claim {
+            Claim::Execute(_) => "execute",
+            Claim::Duplicate(_) => "duplicate",
+            Claim::InFlight(_) => "in flight",
+            Claim::Interrupted(_) => "interrupted",
+        }
+    );
+    Some(claim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Claim, then either "execute" or answer from the ledger. Ok results
+    /// count as broadcast unless they report a failure.
+    fn call(
+        args: &serde_json::Value,
+        wallet: &str,
+        broadcasts: &mut u32,
+        result: Result<String, String>,
+    ) -> Result<String, String> {
+        match claim("dex_swap", args, &["8453", wallet]).unwrap() {
+            Claim::Execute(guard) => {
+                *broadcasts += 1;
+                let sent = result
+                    .as_ref()
+                    .is_ok_and(|out| !out.starts_with("[failed]"));
+                guard.complete(&result, sent);
+                result
+            }
+            Claim::Duplicate(output) => Ok(output),
+            Claim::InFlight(e) | Claim::Interrupted(e) => Err(e),
+        }
+    }
+
+    #[test]
+    fn identical_swap_within_window_returns_cached_tx_hash() {
+        let wallet = "0x1000000000000000000000000000000000000001";
+        let swap = json!({ "token_in": "ETH", "token_out": "USDC", "amount": "0.1", "reason": "rebalance" });
+        let sent =
+            Ok("[ok] Swap Confirmed\nTransaction: https://basescan.org/tx/0xabc123".to_string());
+        let mut broadcasts = 0;
+
+        let first = call(&swap, wallet, &mut broadcasts, sent.clone()).unwrap();
+        assert!(first.contains("0xabc123"));
+        // The replay differs only in reason text and symbol case
+        let replay = json!({ "token_in": "eth", "token_out": "usdc", "amount": "0.1", "reason": "retrying" });
+        let second = call(&replay, wallet, &mut broadcasts, sent.clone()).unwrap();
+        assert_eq!(broadcasts, 1, "replay must not broadcast again");
+        assert!(second.starts_with("[duplicate]") && second.contains("0xabc123"));
+
+        // A different amount, or another wallet, is a different call
+        let bigger = json!({ "token_in": "ETH", "token_out": "USDC", "amount": "0.2" });
+        call(&bigger, wallet, &mut broadcasts, sent.clone()).unwrap();
+        call(
+            &swap,
+            "0x2000000000000000000000000000000000000002",
+            &mut broadcasts,
+            sent,
+        )
+        .unwrap();
+        assert_eq!(broadcasts, 3);
+    }
+
+    #[test]
+    fn failed_calls_can_be_retried_and_interrupted_ones_cannot() {
+        let wallet = "0x3000000000000000000000000000000000000003";
+        let swap = json!({ "token_in": "ETH", "token_out": "DAI", "amount": "1" });
+        let mut broadcasts = 0;
+        call(&swap, wallet, &mut broadcasts, Err("No ETH for gas".into())).unwrap_err();
+        call(&swap, wallet, &mut broadcasts, Ok("[ok] sent".into())).unwrap();
+        assert_eq!(broadcasts, 2);
+
+        let transfer = json!({ "currency": "USDC", "amount": "5", "to_address": "0xabc" });
+        let context = ["1", wallet];
+        let Some(Claim::Execute(guard)) = claim("dex_transfer", &transfer, &context) else {
+            panic!("first call executes");
+        };
+        assert!(matches!(
+            claim("dex_transfer", &transfer, &context),
+            Some(Claim::InFlight(_))
+        ));
+        drop(guard); // run cancelled mid-broadcast
+        assert!(matches!(
+            claim("dex_transfer", &transfer, &context),
+            Some(Claim::Interrupted(_))
+        ));
+        assert!(claim("dex_balance", &transfer, &context).is_none());
+    }
+
+    #[test]
+    fn calls_that_sent_nothing_are_not_remembered() {
+        let wallet = "0x4000000000000000000000000000000000000004";
+        let swap = json!({ "token_in": "ETH", "token_out": "WBTC", "amount": "0.5" });
+        let mut broadcasts = 0;
+        let refused =
+            "[failed] Swap not submitted\n\nthe pre-flight check reverted. Nothing was spent.";
+        call(&swap, wallet, &mut broadcasts, Ok(refused.into())).unwrap();
+        call(&swap, wallet, &mut broadcasts, Ok(refused.into())).unwrap();
+        assert_eq!(broadcasts, 2, "a swap that never went out may be retried");
+
+        // A reverted receipt is reported as Ok but swapped nothing
+        let transfer = json!({ "currency": "USDC", "amount": "7", "to_address": "0xdef" });
+        let context = ["8453", wallet];
+        for _ in 0..2 {
+            let Some(Claim::Execute(guard)) = claim("dex_transfer", &transfer, &context) else {
+                panic!("a reverted transfer may be retried");
+            };
+            guard.complete(
+                &Ok("[pending] Transfer Submitted\nStatus: reverted".into()),
+                false,
+            );
+        }
+    }
+
+    #[test]
+    fn allow_repeat_sends_an_intentional_duplicate() {
+        let wallet = "0x5000000000000000000000000000000000000005";
+        let swap = json!({ "token_in": "ETH", "token_out": "USDT", "amount": "0.3" });
+        let mut broadcasts = 0;
+        call(&swap, wallet, &mut broadcasts, Ok("[ok] first".into())).unwrap();
+        let again = call(&swap, wallet, &mut broadcasts, Ok("[ok] second".into())).unwrap();
+        assert!(again.starts_with("[duplicate]") && again.contains("allow_repeat"));
+
+        let repeat = json!({ "token_in": "ETH", "token_out": "USDT", "amount": "0.3", "allow_repeat": true });
+        let out = call(&repeat, wallet, &mut broadcasts, Ok("[ok] second".into())).unwrap();
+        assert_eq!(out, "[ok] second");
+        assert_eq!(broadcasts, 2);
+        // The repeat is itself remembered under the same key
+        let replay = call(&swap, wallet, &mut broadcasts, Ok("[ok] third".into())).unwrap();
+        assert!(replay.contains("[ok] second"));
+
+        // It never overrides a call that is still running
+        let context = ["1", wallet];
+        let Some(Claim::Execute(_guard)) = claim("dex_transfer", &swap, &context) else {
+            panic!("first call executes");
+        };
+        assert!(matches!(
+            claim("dex_transfer", &repeat, &context),
+            Some(Claim::InFlight(_))
+        ));
+    }
+
+    #[test]
+    fn executed_calls_expire_after_the_window() {
+        let mut ledger = CallLedger::new(Duration::from_secs(60));
+        let start = Instant::now();
+        assert!(ledger.begin_at("k", false, start).is_none());
+        ledger.finish_at("k", Ending::Broadcast("tx"), start);
+        assert!(matches!(
+            ledger.begin_at("k", false, start + Duration::from_secs(59)),
+            Some(Prior::Done(_))
+        ));
+        assert!(ledger
+            .begin_at("k", false, start + Duration::from_secs(61))
+            .is_none());
+        // Running calls never expire, however long confirmation takes
+        assert!(matches!(
+            ledger.begin_at("k", false, start + Duration::from_secs(3600)),
+            Some(Prior::Running)
+        ));
+    }
+}
@@ -45,6 +45,19 @@ impl ProviderKind {
             // Azure AI Foundry: user fills in their resource URL;
             // OpenAiProvider normalises it to …/models at construction time.
             ProviderKind::AzureFoundry => "",
+            // Azure OpenAI: resource endpoint is per-customer; see
+            // providers::azure_openai for deployment routing.
+            ProviderKind::AzureOpenAI => "",
+        }
+    }
+
+    /// Embedding model to request when embedding through this provider.
+    pub fn default_embedding_model(&self) -> &str {
+        match self {
+            ProviderKind::Google => "text-embedding-004",
+            ProviderKind::Mistral => "mistral-embed",
+            ProviderKind::Ollama => "nomic-embed-text",
+            _ => "text-embedding-3-small",
         }
     }
 }
@@ -78,6 +91,37 @@ impl MessageContent {
     }
 }
 
+// ── Finish Reasons ─────────────────────────────────────────────────────
+
+impl FinishReason {
+    /// Map a provider's raw finish reason: OpenAI `finish_reason`, Anthropic
+    /// `stop_reason`, Google `finishReason`.
+    pub fn from_provider(reason: &str) -> Self {
+        match reason.trim().to_ascii_lowercase().as_str() {
+            // OpenAI "stop", Anthropic "end_turn"/"stop_sequence", Google "STOP"
+            "stop" | "end_turn" | "stop_sequence" | "complete" | "finished" => FinishReason::Stop,
+            // OpenAI "length", Anthropic "max_tokens", Google "MAX_TOKENS"
+            "length" | "max_tokens" => FinishReason::Length,
+            // OpenAI "tool_calls" (legacy "function_call"), Anthropic "tool_use"
+            "tool_calls" | "function_call" | "tool_use" => FinishReason::ToolCalls,
+            // OpenAI "content_filter", Anthropic "refusal", Google safety family
+            "content_filter" | "refusal" | "safety" | "recitation" | "blocklist"
+            | "prohibited_content" | "spii" | "image_safety" => FinishReason::ContentFilter,
+            _ => FinishReason::Other,
+        }
+    }
+
+    /// Combine the reasons reported across a response's chunks. Some streams
+    /// end with a generic "stop" after the real reason (Anthropic's
+    /// message_stop), so a plain Stop never overrides something specific.
+    pub fn merge(current: Option<Self>, next: Self) -> Self {
+        match (current, next) {
+            (Some(prev), FinishReason::Stop) => prev,
+            _ => next,
+        }
+    }
+}
+
 // ── Tool Calling ───────────────────────────────────────────────────────
 
 // A Gemini "thought" part that must be echoed back with function calls
@@ -151,15 +195,30 @@ impl Default for MemoryConfig {
             embedding_provider: EmbeddingProvider::Auto,
             embedding_base_url: "http://localhost:11434".into(),
             embedding_model: "nomic-embed-text".into(),
+            embedding_api_key: String::new(),
             embedding_dims: 768,
             auto_recall: true,
             auto_capture: true,
             recall_limit: 5,
             recall_threshold: 0.3,
+            chunk_chars: 1200,
+            chunk_overlap: 200,
         }
     }
 }
 
+impl MemoryConfig {
+    /// True when embeddings come from an Ollama instance on this machine —
+    /// the only case where auto-starting `ollama serve` makes sense.
+    pub fn is_local_ollama(&self) -> bool {
+        let base_url = self.embedding_base_url.as_str();
+        matches!(
+            self.embedding_provider,
+            EmbeddingProvider::Auto | EmbeddingProvider::Ollama
+        ) && (base_url.contains("localhost") || base_url.contains("127.0.0.1"))
+    }
+}
+
 // Statistics about the memory store.
 
 // ── Model Routing (Multi-Model Agent System) ──────────────────────────
@@ -207,6 +266,30 @@ impl ModelRouting {
         }
     }
 
+    /// Like [`resolve`](Self::resolve), with the model the agent's own
+    /// definition pins ranked after a per-agent override and before the
+    /// specialty and role models.
+    pub fn resolve_for_agent(
+        &self,
+        agent_id: &str,
+        agent_model: Option<&str>,
+        role: &str,
+        specialty: &str,
+        fallback: &str,
+    ) -> String {
+        if self
+            .agent_models
+            .get(agent_id)
+            .is_some_and(|m| !m.is_empty())
+        {
+            return self.resolve(agent_id, role, specialty, fallback);
+        }
+        match agent_model.filter(|m| !m.is_empty()) {
+            Some(m) => m.to_string(),
+            None => self.resolve(agent_id, role, specialty, fallback),
+        }
+    }
+
     /// Resolve model using auto-tier: cheap_model for simple tasks, fallback for complex.
     /// Returns (model_name, was_downgraded)
     pub fn resolve_auto_tier(&self, message: &str, fallback: &str) -> (String, bool) {
@@ -232,7 +315,7 @@ impl ModelRouting {
 // serde default helpers for EngineConfig live in crate::atoms::types
 use crate::atoms::types::{
     default_context_window_tokens, default_daily_budget_usd, default_max_concurrent_runs,
-    default_user_timezone,
+    default_max_tool_iterations, default_user_timezone,
 };
 
 impl Default for EngineConfig {
@@ -262,17 +345,169 @@ You have FULL ACCESS — use your tools proactively to accomplish tasks. Don't j
 
 Be thorough, resourceful, and action-oriented. When the user asks you to do something, do it completely. Never ask the user to provide file paths, config locations, or technical details you can discover yourself using your tools."#.into()),
             max_tool_rounds: 20,
+            max_tool_iterations: default_max_tool_iterations(),
             tool_timeout_secs: 300,
             user_timezone: default_user_timezone(),
             model_routing: ModelRouting::default(),
             max_concurrent_runs: default_max_concurrent_runs(),
+            run_limit_policy: RunLimitPolicy::default(),
             daily_budget_usd: default_daily_budget_usd(),
+            agent_budgets_usd: Default::default(),
+            budget_timezone: None,
+            budget_reset_hour: 0,
             context_window_tokens: default_context_window_tokens(),
             weather_location: None,
+            model_capabilities: Default::default(),
+            system_prompt_template: None,
+            show_reasoning: default_show_reasoning(),
+            locale: None,
         }
     }
 }
 
+impl EngineConfig {
+    /// Daily budget for `agent_id` in USD, 0 when the agent has no cap.
+    pub fn agent_budget_usd(&self, agent_id: &str) -> f64 {
+        self.agent_budgets_usd
+            .get(agent_id)
+            .copied()
+            .filter(|b| *b > 0.0)
+            .unwrap_or(0.0)
+    }
+
+    /// When the daily budgets reset: `budget_reset_hour` in the budget
+    /// timezone, falling back to the user's timezone.
+    pub fn budget_window(&self) -> crate::engine::budget_window::BudgetWindow {
+        let timezone = self
+            .budget_timezone
+            .as_deref()
+            .filter(|tz| !tz.trim().is_empty())
+            .unwrap_or(&self.user_timezone);
+        crate::engine::budget_window::BudgetWindow::new(timezone, self.budget_reset_hour)
+    }
+}
+
 // ── Tasks ──────────────────────────────────────────────────────────────
 
 // ── Orchestrator: Projects ────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_utf8_shortens_ids_safely() {
+        assert_eq!(
+            truncate_utf8("0b3f5c2e-1d4a-4c8e-9f00-123456789abc", 8),
+            "0b3f5c2e"
+        );
+        assert_eq!(truncate_utf8("abc", 8), "abc");
+        assert_eq!(truncate_utf8("", 8), "");
+        // 'ü' straddles byte 8 — must back off rather than panic
+        assert_eq!(truncate_utf8("mem-abcü-1", 8), "mem-abc");
+    }
+
+    #[test]
+    fn agent_definition_model_ranks_below_the_routing_override() {
+        let mut routing = ModelRouting {
+            worker_model: Some("worker-model".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            routing.resolve_for_agent("a1", Some("own-model"), "worker", "", "default"),
+            "own-model"
+        );
+        assert_eq!(
+            routing.resolve_for_agent("a1", None, "worker", "", "default"),
+            "worker-model"
+        );
+        routing
+            .agent_models
+            .insert("a1".into(), "override-model".into());
+        assert_eq!(
+            routing.resolve_for_agent("a1", Some("own-model"), "worker", "", "default"),
+            "override-model"
+        );
+    }
+
+    #[test]
+    fn maps_openai_finish_reasons() {
+        assert_eq!(FinishReason::from_provider("stop"), FinishReason::Stop);
+        assert_eq!(FinishReason::from_provider("length"), FinishReason::Length);
+        assert_eq!(
+            FinishReason::from_provider("tool_calls"),
+            FinishReason::ToolCalls
+        );
+        assert_eq!(
+            FinishReason::from_provider("function_call"),
+            FinishReason::ToolCalls
+        );
+        assert_eq!(
+            FinishReason::from_provider("content_filter"),
+            FinishReason::ContentFilter
+        );
+    }
+
+    #[test]
+    fn maps_anthropic_stop_reasons() {
+        assert_eq!(FinishReason::from_provider("end_turn"), FinishReason::Stop);
+        assert_eq!(
+            FinishReason::from_provider("stop_sequence"),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            FinishReason::from_provider("max_tokens"),
+            FinishReason::Length
+        );
+        assert_eq!(
+            FinishReason::from_provider("tool_use"),
+            FinishReason::ToolCalls
+        );
+        assert_eq!(
+            FinishReason::from_provider("refusal"),
+            FinishReason::ContentFilter
+        );
+    }
+
+    #[test]
+    fn maps_google_finish_reasons() {
+        assert_eq!(FinishReason::from_provider("STOP"), FinishReason::Stop);
+        assert_eq!(
+            FinishReason::from_provider("MAX_TOKENS"),
+            FinishReason::Length
+        );
+        for reason in [
+            "SAFETY",
+            "RECITATION",
+            "BLOCKLIST",
+            "PROHIBITED_CONTENT",
+            "SPII",
+        ] {
+            assert_eq!(
+                FinishReason::from_provider(reason),
+                FinishReason::ContentFilter
+            );
+        }
+        assert_eq!(
+            FinishReason::from_provider("MALFORMED_FUNCTION_CALL"),
+            FinishReason::Other
+        );
+    }
+
+    #[test]
+    fn trailing_stop_keeps_the_specific_reason() {
+        let reason = FinishReason::merge(None, FinishReason::Length);
+        assert_eq!(
+            FinishReason::merge(Some(reason), FinishReason::Stop),
+            FinishReason::Length
+        );
+        assert_eq!(
+            FinishReason::merge(None, FinishReason::Stop),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            FinishReason::merge(Some(FinishReason::Stop), FinishReason::ContentFilter),
+            FinishReason::ContentFilter
+        );
+    }
+}
@@ -52,4 +52,15 @@ mod tests {
         assert_eq!(safe_truncate(s, 5), "aé"); // mid-中
         assert_eq!(safe_truncate(s, 6), "aé中");
     }
+
+    #[test]
+    fn snippet_with_multibyte_at_cut() {
+        // Mirrors the `if len > N { format!("{}...", &s[..N]) }` previews that
+        // used to panic when a multibyte char straddled byte N.
+        let content = format!("{}é{}", "a".repeat(196), "b".repeat(10)); // é spans bytes 196..198
+        let snippet = format!("{}...", safe_truncate(&content, 197));
+        assert!(snippet.starts_with(&"a".repeat(196)));
+        assert!(!snippet.contains('é'));
+        assert!(std::str::from_utf8(snippet.as_bytes()).is_ok());
+    }
 }
@@ -2,6 +2,9 @@
 //
 // Thin Tauri command wrappers for:
 //   - Agent CRUD    (engine_list_all_agents, _create_agent, _delete_agent)
+//   - Agent Config  (engine_agent_config_list, _get, _save, _delete)
+//   - Agent Cloning (engine_clone_agent)
+//   - Agent Bundles (engine_export_agent, engine_import_agent)
 //   - Agent Files   (engine_agent_file_list, _get, _set, _delete)
 //
 // All commands are 1-3 lines: extract, delegate to SessionStore, return.
@@ -10,6 +13,8 @@ use log::info;
 use tauri::State;
 
 use crate::commands::state::EngineState;
+use crate::engine::agent_bundle::{self, AgentBundle, AgentImportReport};
+use crate::engine::sessions::DEFAULT_AGENT_ID;
 use crate::engine::types::*;
 
 // ── Agent CRUD ────────────────────────────────────────────────────────────────
@@ -61,6 +66,22 @@ pub fn engine_create_agent(
         capabilities: capabilities.unwrap_or_default(),
     };
     state.store.add_project_agent("_standalone", &agent)?;
+    let mut definition = state
+        .store
+        .get_agent_definition(&agent_id)?
+        .unwrap_or_else(|| AgentDefinition {
+            id: agent_id.clone(),
+            name: agent_id.clone(),
+            persona: None,
+            model: None,
+            skills: vec![],
+            autonomy: Default::default(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        });
+    definition.persona = agent.system_prompt;
+    definition.model = agent.model;
+    state.store.upsert_agent_definition(&definition)?;
     info!("[engine] Created standalone agent: {}", agent_id);
     Ok(())
 }
@@ -69,10 +90,124 @@ pub fn engine_create_agent(
 #[tauri::command]
 pub fn engine_delete_agent(state: State<'_, EngineState>, agent_id: String) -> Result<(), String> {
     state.store.delete_agent("_standalone", &agent_id)?;
+    if agent_id != DEFAULT_AGENT_ID {
+        state.store.delete_agent_definition(&agent_id)?;
+    }
     info!("[engine] Deleted standalone agent: {}", agent_id);
     Ok(())
 }
 
+// ── Agent Config (name, persona, model, skills) ─────────────────────────────
+
+#[tauri::command]
+pub fn engine_agent_config_list(
+    state: State<'_, EngineState>,
+) -> Result<Vec<AgentDefinition>, String> {
+    state
+        .store
+        .list_agent_definitions()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn engine_agent_config_get(
+    state: State<'_, EngineState>,
+    agent_id: Option<String>,
+) -> Result<Option<AgentDefinition>, String> {
+    let aid = agent_id.unwrap_or_else(|| DEFAULT_AGENT_ID.into());
+    state
+        .store
+        .get_agent_definition(&aid)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn engine_agent_config_save(
+    state: State<'_, EngineState>,
+    agent: AgentDefinition,
+) -> Result<(), String> {
+    info!(
+        "[engine] Saving agent config: {} (model={:?}, {} skills)",
+        agent.id,
+        agent.model,
+        agent.skills.len()
+    );
+    state
+        .store
+        .upsert_agent_definition(&agent)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn engine_agent_config_delete(
+    state: State<'_, EngineState>,
+    agent_id: String,
+) -> Result<(), String> {
+    state
+        .store
+        .delete_agent_definition(&agent_id)
+        .map_err(|e| e.to_string())?;
+    info!("[engine] Deleted agent config: {}", agent_id);
+    Ok(())
+}
+
+// ── Agent Cloning ─────────────────────────────────────────────────────────────
+
+/// Duplicate an agent under a fresh id — config, soul files, skill
+/// assignments and service permissions, plus its memories if asked.
+#[tauri::command]
+pub fn engine_clone_agent(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+    source_id: String,
+    new_name: String,
+    include_memories: Option<bool>,
+) -> Result<AgentDefinition, String> {
+    let clone = state
+        .store
+        .clone_agent(&source_id, &new_name, include_memories.unwrap_or(false))
+        .map_err(|e| e.to_string())?;
+    let services =
+        crate::commands::guardrails::copy_agent_permissions(&app_handle, &source_id, &clone.id)?;
+    if !services.is_empty() {
+        info!(
+            "[engine] Clone {} inherits service permissions for: {}",
+            clone.id,
+            services.join(", ")
+        );
+    }
+    Ok(clone)
+}
+
+// ── Agent Bundles (share / import) ────────────────────────────────────────────
+
+/// Export an agent as a portable bundle (no secrets).
+#[tauri::command]
+pub fn engine_export_agent(
+    state: State<'_, EngineState>,
+    agent_id: String,
+    include_toml_skills: Option<bool>,
+) -> Result<AgentBundle, String> {
+    agent_bundle::export_agent(&state.store, &agent_id, include_toml_skills.unwrap_or(true))
+        .map_err(|e| e.to_string())
+}
+
+/// Import a bundle as a new agent. The report lists credentials to prompt
+/// for and bundled skills awaiting the user's confirmation.
+#[tauri::command]
+pub fn engine_import_agent(
+    state: State<'_, EngineState>,
+    bundle: AgentBundle,
+    confirm_elevated_skills: Option<bool>,
+) -> Result<AgentImportReport, String> {
+    agent_bundle::import_agent(
+        &state.store,
+        &bundle,
+        confirm_elevated_skills.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
 // ── Agent Files (Soul / Persona) ──────────────────────────────────────────────
 
 #[tauri::command]
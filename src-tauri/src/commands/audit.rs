@@ -1,9 +1,11 @@
 // commands/audit.rs — Tauri IPC commands for the unified signed audit log.
 //
-// Exposes: query, stats, verify chain integrity, export.
+// Exposes: query, stats, verify chain integrity, export, and the HIL
+// approvals log (decision → outcome).
 
 use crate::commands::state::EngineState;
 use crate::engine::audit;
+use crate::engine::sessions::ApprovalRecord;
 use tauri::State;
 
 /// Query recent audit log entries with optional filters.
@@ -30,6 +32,19 @@ pub fn engine_audit_stats(state: State<'_, EngineState>) -> Result<audit::AuditS
     audit::stats(&state.store).map_err(|e| e.to_string())
 }
 
+/// List logged HIL approval decisions (newest first) with their outcomes.
+#[tauri::command]
+pub fn engine_list_approvals(
+    state: State<'_, EngineState>,
+    session_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ApprovalRecord>, String> {
+    state
+        .store
+        .list_approvals(session_id.as_deref(), limit.unwrap_or(100))
+        .map_err(|e| e.to_string())
+}
+
 /// Verify the HMAC chain integrity of the entire audit log.
 /// Returns { "intact": true, "count": N } or { "intact": false, "broken_at": row_id }.
 #[tauri::command]
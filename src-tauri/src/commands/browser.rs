@@ -1,7 +1,8 @@
-// commands/browser.rs — Browser profile management, screenshot serving,
+// commands/browser.rs — Browser profile management, screenshot and image serving,
 // per-agent workspace management, and outbound domain allowlist.
 
 use crate::commands::state::EngineState;
+use crate::engine::sessions::SessionStore;
 use log::info;
 use tauri::State;
 
@@ -265,6 +266,51 @@ pub fn engine_screenshot_get(filename: String) -> Result<ScreenshotEntry, String
     })
 }
 
+/// A file produced by `image_generate` or `speak`, for display in chat.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GeneratedMediaEntry {
+    pub path: String,
+    pub mime_type: String,
+    pub base64: String,
+}
+
+/// Get a generated image as base64. Only files in an agent workspace's
+/// `generated-images/` directory are served.
+#[tauri::command]
+pub fn engine_generated_image_get(path: String) -> Result<GeneratedMediaEntry, String> {
+    let file = std::path::Path::new(&path);
+    if !crate::engine::image_gen::is_generated_image(file) {
+        return Err(format!("Not a generated image: {}", path));
+    }
+    let data = std::fs::read(file).map_err(|e| format!("Failed to read image: {}", e))?;
+    let mime_type = match file.extension().and_then(|e| e.to_str()) {
+        Some("jpg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "image/png",
+    };
+    Ok(GeneratedMediaEntry {
+        path,
+        mime_type: mime_type.into(),
+        base64: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &data),
+    })
+}
+
+/// Get audio saved by the `speak` tool as base64 MP3, for the chat's player.
+#[tauri::command]
+pub fn engine_generated_audio_get(path: String) -> Result<GeneratedMediaEntry, String> {
+    let file = std::path::Path::new(&path);
+    if !crate::engine::speech::is_generated_audio(file) {
+        return Err(format!("Not generated audio: {}", path));
+    }
+    let data = std::fs::read(file).map_err(|e| format!("Failed to read audio: {}", e))?;
+    Ok(GeneratedMediaEntry {
+        path,
+        mime_type: "audio/mpeg".into(),
+        base64: base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &data),
+    })
+}
+
 /// Delete a screenshot.
 #[tauri::command]
 pub fn engine_screenshot_delete(filename: String) -> Result<(), String> {
@@ -501,6 +547,54 @@ pub fn engine_network_set_policy(
     Ok(())
 }
 
+// ── Outbound User-Agent & per-domain headers ───────────────────────────
+
+#[tauri::command]
+pub fn engine_outbound_http_get(
+    state: State<'_, EngineState>,
+) -> Result<crate::engine::net::OutboundConfig, String> {
+    Ok(crate::engine::net::load_config(&state.store))
+}
+
+#[tauri::command]
+pub fn engine_outbound_http_set(
+    state: State<'_, EngineState>,
+    config: crate::engine::net::OutboundConfig,
+) -> Result<(), String> {
+    crate::engine::net::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[network] Outbound headers saved: user_agent={} domains={}",
+        config.user_agent.is_some(),
+        config.domain_headers.len()
+    );
+    Ok(())
+}
+
+// ── robots.txt & per-host rate limits ──────────────────────────────────
+
+#[tauri::command]
+pub fn engine_web_politeness_get(
+    state: State<'_, EngineState>,
+) -> Result<crate::engine::robots::PolitenessConfig, String> {
+    Ok(crate::engine::robots::load_config(&state.store))
+}
+
+#[tauri::command]
+pub fn engine_web_politeness_set(
+    state: State<'_, EngineState>,
+    config: crate::engine::robots::PolitenessConfig,
+) -> Result<(), String> {
+    crate::engine::robots::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[network] Politeness saved: robots={} browser_robots={} api_robots={} min_interval={}ms",
+        config.respect_robots,
+        config.respect_robots_in_browser,
+        config.respect_robots_for_apis,
+        config.min_interval_ms
+    );
+    Ok(())
+}
+
 /// Check if a URL is allowed by the outbound policy.
 /// Returns (allowed: bool, domain: String).
 #[tauri::command]
@@ -508,12 +602,18 @@ pub fn engine_network_check_url(
     state: State<'_, EngineState>,
     url: String,
 ) -> Result<(bool, String), String> {
-    let policy: NetworkPolicy = match state.store.get_config("network_policy") {
+    Ok(check_url_against_policy(&state.store, &url))
+}
+
+/// Evaluate a URL against the stored outbound policy. Shared by the
+/// `engine_network_check_url` command and backend downloads (skill installs).
+pub fn check_url_against_policy(store: &SessionStore, url: &str) -> (bool, String) {
+    let policy: NetworkPolicy = match store.get_config("network_policy") {
         Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_default(),
         _ => NetworkPolicy::default(),
     };
 
-    let domain = extract_domain(&url);
+    let domain = extract_domain(url);
 
     // Always block blocked domains
     if policy
@@ -521,7 +621,7 @@ pub fn engine_network_check_url(
         .iter()
         .any(|d| domain_matches(&domain, d))
     {
-        return Ok((false, domain));
+        return (false, domain);
     }
 
     // If allowlist is enabled, check against it
@@ -530,11 +630,11 @@ pub fn engine_network_check_url(
             .allowed_domains
             .iter()
             .any(|d| domain_matches(&domain, d));
-        return Ok((allowed, domain));
+        return (allowed, domain);
     }
 
     // If allowlist is disabled, all non-blocked domains are allowed
-    Ok((true, domain))
+    (true, domain)
 }
 
 /// Public wrapper for use by tool_executor network policy enforcement
@@ -149,6 +149,17 @@ channel_commands!(
     crate::engine::whatsapp::WhatsAppConfig
 );
 
+// ── WhatsApp extras ───────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn engine_whatsapp_rotate_webhook_secret(
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    crate::engine::whatsapp::rotate_webhook_secret(&app_handle)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ── Telegram — hand-written (unique API surface) ──────────────────────────────
 //
 // Differences from standard channels:
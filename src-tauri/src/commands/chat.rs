@@ -17,8 +17,14 @@ use crate::engine::chat as chat_org;
 use crate::engine::engram;
 use crate::engine::memory;
 use crate::engine::providers::AnyProvider;
+use crate::engine::sessions::SecureDeleteReport;
 use crate::engine::types::*;
 use crate::engine::util::safe_truncate;
+use openpawz_core::engine::cancel::CancelToken;
+use openpawz_core::engine::emergency;
+use openpawz_core::engine::partial_reply::PartialReply;
+use openpawz_core::engine::session_archive::{self, ArchiveReport};
+use openpawz_core::engine::session_search::{self, SessionSearchHit};
 
 // ── Chat ─────────────────────────────────────────────────────────────────────
 
@@ -28,13 +34,23 @@ use crate::engine::util::safe_truncate;
 pub async fn engine_chat_send(
     app_handle: tauri::AppHandle,
     state: State<'_, EngineState>,
-    request: ChatRequest,
+    mut request: ChatRequest,
 ) -> Result<ChatResponse, String> {
+    // ── Emergency stop: refuse before anything is stored or queued ────────
+    emergency::ensure_armed()?;
+
     let run_id = uuid::Uuid::new_v4().to_string();
 
     // Reset swarm counters so sub-agents can wake fresh for this human turn
     crate::engine::swarm::reset_all_counters();
 
+    // ── Agent config (persona / default model / skills) ───────────────────
+    let agent_config = state
+        .store
+        .get_agent_definition(request.agent_id.as_deref().unwrap_or("default"))
+        .unwrap_or(None);
+    let agent_model = agent_config.as_ref().and_then(|a| a.model.clone());
+
     // ── Resolve or create session ──────────────────────────────────────────
     let session_id = match &request.session_id {
         Some(id) if !id.is_empty() => {
@@ -43,8 +59,9 @@ pub async fn engine_chat_send(
                 let raw = request.model.clone().unwrap_or_default();
                 let model = if raw.is_empty() || raw.eq_ignore_ascii_case("default") {
                     let cfg = state.config.lock();
-                    cfg.default_model
+                    agent_model
                         .clone()
+                        .or_else(|| cfg.default_model.clone())
                         .unwrap_or_else(|| "gpt-5.1".to_string())
                 } else {
                     raw
@@ -63,8 +80,9 @@ pub async fn engine_chat_send(
             let raw = request.model.clone().unwrap_or_default();
             let model = if raw.is_empty() || raw.eq_ignore_ascii_case("default") {
                 let cfg = state.config.lock();
-                cfg.default_model
+                agent_model
                     .clone()
+                    .or_else(|| cfg.default_model.clone())
                     .unwrap_or_else(|| "gpt-5.1".to_string())
             } else {
                 raw
@@ -84,6 +102,9 @@ pub async fn engine_chat_send(
     // queue the message and signal the active agent to wrap up.
     {
         let has_active_run = state.active_runs.lock().contains_key(&session_id);
+        if has_active_run && request.regenerate {
+            return Err("A response is still running — stop it before regenerating.".into());
+        }
         if has_active_run {
             info!(
                 "[engine] Session {} has active run — queuing request and signaling yield",
@@ -101,8 +122,9 @@ pub async fn engine_chat_send(
                 let cfg = state.config.lock();
                 let raw = request.model.clone().unwrap_or_default();
                 let m = if raw.is_empty() || raw.eq_ignore_ascii_case("default") {
-                    cfg.default_model
+                    agent_model
                         .clone()
+                        .or_else(|| cfg.default_model.clone())
                         .unwrap_or_else(|| "gpt-5.1".to_string())
                 } else {
                     normalize_model_name(&raw).to_string()
@@ -141,14 +163,24 @@ pub async fn engine_chat_send(
         }
     }
 
+    // ── Concurrent run cap: reject now, or wait for a slot once spawned ───
+    let run_permit = state.run_limiter.try_acquire_or_reject()?;
+
+    // ── Regenerate: drop the last reply and answer the same message again ──
+    if request.regenerate {
+        let last_user = state.store.regenerate_last(&session_id)?;
+        request.message = last_user.content;
+    }
+
     // ── Resolve model and provider ─────────────────────────────────────────
     let (provider_config, model) = {
         let cfg = state.config.lock();
 
         let raw_model = request.model.clone().unwrap_or_default();
         let base_model = if raw_model.is_empty() || raw_model.eq_ignore_ascii_case("default") {
-            cfg.default_model
+            agent_model
                 .clone()
+                .or_else(|| cfg.default_model.clone())
                 .unwrap_or_else(|| "gpt-5.1".to_string())
         } else {
             raw_model
@@ -202,23 +234,39 @@ pub async fn engine_chat_send(
     };
 
     // ── Store the user message ─────────────────────────────────────────────
-    let user_msg = StoredMessage {
-        id: uuid::Uuid::new_v4().to_string(),
-        session_id: session_id.clone(),
-        role: "user".into(),
-        content: request.message.clone(),
-        tool_calls_json: None,
-        tool_call_id: None,
-        name: None,
-        created_at: chrono::Utc::now().to_rfc3339(),
-    };
-    state.store.add_message(&user_msg)?;
+    // An edit rewrites the earlier message and drops the stale replies after
+    // it, so the history the agent loads below ends at the edited message.
+    // A regenerate reuses the user message that's already there.
+    if let Some(edit_id) = request.edit_message_id.as_deref() {
+        state
+            .store
+            .edit_and_resend(&session_id, edit_id, &request.message)?;
+    } else if !request.regenerate {
+        let user_msg = StoredMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: session_id.clone(),
+            role: "user".into(),
+            content: request.message.clone(),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        state.store.add_message(&user_msg)?;
+    }
 
     // ── Base system prompt ─────────────────────────────────────────────────
-    let base_system_prompt = request.system_prompt.clone().or_else(|| {
-        let cfg = state.config.lock();
-        cfg.default_system_prompt.clone()
-    });
+    // Request override → agent persona → engine default
+    let base_system_prompt = request
+        .system_prompt
+        .clone()
+        .or_else(|| agent_config.as_ref().and_then(|a| a.persona.clone()))
+        .or_else(|| {
+            let cfg = state.config.lock();
+            cfg.default_system_prompt.clone()
+        });
 
     // ── Soul context + today's memories ───────────────────────────────────
     let agent_id_owned = request
@@ -275,6 +323,17 @@ pub async fn engine_chat_send(
         );
     }
 
+    // ── User system-prompt template ────────────────────────────────────────
+    let mut todays_memories = todays_memories;
+    let mut skill_instructions = skill_instructions;
+    let base_system_prompt = chat_org::templated_base_prompt(
+        &state.config.lock(),
+        base_system_prompt,
+        &agent_id_owned,
+        &mut todays_memories,
+        &mut skill_instructions,
+    );
+
     // ── Runtime context block (extracted values for organism) ─────────────
     let runtime_context = {
         let cfg = state.config.lock();
@@ -302,9 +361,9 @@ pub async fn engine_chat_send(
     // compose_chat_system_prompt → budget trimming → load_conversation pipeline.
     let agent_roster = chat_org::build_agent_roster(&state.store, &agent_id_owned);
 
-    let auto_recall_on = {
+    let (auto_recall_on, recall_limit, recall_threshold) = {
         let mcfg = state.memory_config.lock();
-        mcfg.auto_recall
+        (mcfg.auto_recall, mcfg.recall_limit, mcfg.recall_threshold)
     };
 
     let context_window_override = {
@@ -370,14 +429,23 @@ pub async fn engine_chat_send(
     if let Some(ref roster) = agent_roster {
         builder = builder.agent_roster(roster.clone());
     }
-    if auto_recall_on {
+    // Recall needs embeddings — keyword-only matches every turn are mostly noise
+    if auto_recall_on && emb_client_for_recall.is_some() {
         builder = builder.recall_from(
             &state.store,
             emb_client_for_recall.as_ref(),
             recall_scope,
             request.message.clone(),
         );
+        builder = builder
+            .recall_config(crate::atoms::engram_types::MemorySearchConfig {
+                similarity_threshold: recall_threshold as f32,
+                ..Default::default()
+            })
+            .recall_limit(recall_limit);
         builder = builder.hnsw_index(&state.hnsw_index);
+    } else if auto_recall_on {
+        info!("[engram:chat] No embedding client — skipping memory auto-recall");
     }
     // Wire working memory into the ContextBuilder (Tier 1 → prompt assembly)
     builder = builder.working_memory(&cognitive.working_memory);
@@ -475,7 +543,7 @@ pub async fn engine_chat_send(
             // Sanitize after reconstruction: context trimming may have
             // dropped assistant messages while keeping their tool results
             // (or vice versa). This prevents 400 errors from the API.
-            crate::engine::agent_loop::helpers::sanitize_tool_pairs(&mut chat_messages);
+            openpawz_core::engine::agent_loop::helpers::sanitize_tool_pairs(&mut chat_messages);
 
             (ctx.system_prompt, chat_messages, ctx.budget)
         }
@@ -542,6 +610,7 @@ pub async fn engine_chat_send(
     let loaded_tools = state.loaded_tools.lock().clone();
     let mut tools = chat_org::build_chat_tools(
         &state.store,
+        request.agent_id.as_deref().unwrap_or("default"),
         request.tools_enabled.unwrap_or(true),
         request.tool_filter.as_deref(),
         &app_handle,
@@ -620,6 +689,8 @@ pub async fn engine_chat_send(
         let cfg = state.config.lock();
         cfg.daily_budget_usd
     };
+    // Other providers take over on billing/auth errors or sustained overload
+    let configured_providers = state.config.lock().providers.clone();
 
     let session_id_clone = session_id.clone();
     let run_id_clone = run_id.clone();
@@ -628,7 +699,7 @@ pub async fn engine_chat_send(
     let pre_loop_msg_count = messages.len();
     let app = app_handle.clone();
     let agent_id_for_spawn = agent_id_owned.clone();
-    let sem = state.run_semaphore.clone();
+    let run_limiter = state.run_limiter.clone();
     let panic_session_id = session_id.clone();
     let panic_run_id = run_id.clone();
     let panic_app = app_handle.clone();
@@ -644,26 +715,45 @@ pub async fn engine_chat_send(
         signal
     };
     let yield_signal_for_spawn = yield_signal.clone();
+
+    // ── Fresh cancellation token for this run ─────────────────────────────
+    let cancel_token = CancelToken::new();
+    state
+        .cancel_tokens
+        .lock()
+        .insert(session_id.clone(), cancel_token.clone());
+    let cancel_tokens_cleanup = state.cancel_tokens.clone();
+    let cancel_token_for_cleanup = cancel_token.clone();
     let request_queue = state.request_queue.clone();
     let yield_signals_cleanup = state.yield_signals.clone();
 
+    // ── Streamed reply is saved as it arrives, in case the run dies ───────
+    let partial_reply = PartialReply::new(&session_id);
+
     // ── Spawn agent loop ───────────────────────────────────────────────────
     let handle = tauri::async_runtime::spawn(async move {
-        // Chat gets priority — short timeout then proceed anyway
-        let _permit = match tokio::time::timeout(
-            std::time::Duration::from_secs(2),
-            sem.acquire_owned(),
-        )
-        .await
-        {
-            Ok(Ok(permit)) => Some(permit),
-            _ => {
-                info!("[engine] Chat bypassing concurrency limit (all slots busy)");
-                None
+        let _permit = match run_permit {
+            Some(permit) => permit,
+            None => {
+                let status = run_limiter.status();
+                info!(
+                    "[engine] All {} run slots busy — session {} queued",
+                    status.max, session_id_clone
+                );
+                let _ = app.emit(
+                    "engine-event",
+                    EngineEvent::RunQueued {
+                        session_id: session_id_clone.clone(),
+                        run_id: run_id_clone.clone(),
+                        active_runs: status.active,
+                        max_runs: status.max,
+                    },
+                );
+                run_limiter.acquire_queued().await
             }
         };
 
-        let provider = AnyProvider::from_config(&provider_config);
+        let provider = AnyProvider::with_fallback(&provider_config, &configured_providers);
 
         match agent_loop::run_agent_turn(
             &app,
@@ -684,6 +774,8 @@ pub async fn engine_chat_send(
             auto_approve_all,
             &user_approved_tools,
             Some(&yield_signal_for_spawn),
+            Some(&cancel_token),
+            Some(&partial_reply),
         )
         .await
         {
@@ -691,10 +783,21 @@ pub async fn engine_chat_send(
                 info!("[engine] Agent turn complete: {} chars", final_text.len());
 
                 if let Some(engine_state) = app.try_state::<EngineState>() {
+                    // The finished messages below supersede the streamed copy
+                    partial_reply.discard(&engine_state.store);
+
+                    // The run's reasoning goes on its final assistant message
+                    let last_assistant = messages
+                        .iter()
+                        .skip(pre_loop_msg_count)
+                        .rposition(|m| m.role == Role::Assistant)
+                        .map(|i| i + pre_loop_msg_count);
+                    let mut reasoning = partial_reply.reasoning();
+
                     // Persist only NEW messages (skip pre-loaded history)
                     // Skip empty assistant messages — they waste context and
                     // cause the model to mimic the empty-response pattern.
-                    for msg in messages.iter().skip(pre_loop_msg_count) {
+                    for (i, msg) in messages.iter().enumerate().skip(pre_loop_msg_count) {
                         if msg.role == Role::Assistant || msg.role == Role::Tool {
                             // Don't persist empty assistant messages that have
                             // no tool_calls. Assistant messages WITH tool_calls
@@ -726,6 +829,12 @@ pub async fn engine_chat_send(
                                 tool_call_id: msg.tool_call_id.clone(),
                                 name: msg.name.clone(),
                                 created_at: chrono::Utc::now().to_rfc3339(),
+                                incomplete: false,
+                                reasoning: if Some(i) == last_assistant {
+                                    reasoning.take()
+                                } else {
+                                    None
+                                },
                             };
                             if let Err(e) = engine_state.store.add_message(&stored) {
                                 error!("[engine] Failed to store message: {}", e);
@@ -752,15 +861,26 @@ pub async fn engine_chat_send(
                     // Auto-capture memorable facts via Engram (with dedup guard)
                     // Uses LLM-powered extraction for 5x better fact coverage.
                     // Falls back to heuristic extraction if LLM call fails.
-                    if auto_capture_on && !final_text.is_empty() {
-                        let extraction_provider = AnyProvider::from_config(&provider_config);
-                        let facts = memory::extract_memorable_facts_llm(
+                    let capture_policy =
+                        memory::capture::load_policy(&engine_state.store, &agent_id_for_spawn);
+                    if auto_capture_on
+                        && capture_policy.enabled
+                        && !final_text.is_empty()
+                        && memory::capture::claim_capture(
+                            &agent_id_for_spawn,
                             &user_message_for_capture,
-                            &final_text,
-                            &extraction_provider,
-                            &model,
                         )
-                        .await;
+                    {
+                        let extraction_provider = AnyProvider::from_config(&provider_config);
+                        let facts = capture_policy.select(
+                            memory::extract_memorable_facts_llm(
+                                &user_message_for_capture,
+                                &final_text,
+                                &extraction_provider,
+                                &model,
+                            )
+                            .await,
+                        );
                         if !facts.is_empty() {
                             let emb_client = engine_state.embedding_client();
                             for (content, category) in &facts {
@@ -872,6 +992,10 @@ pub async fn engine_chat_send(
             }
             Err(e) => {
                 error!("[engine] Agent turn failed: {}", e);
+                // Keep what was streamed, flagged incomplete
+                if let Some(engine_state) = app.try_state::<EngineState>() {
+                    partial_reply.flush(&engine_state.store);
+                }
                 let _ = app.emit(
                     "engine-event",
                     EngineEvent::Error {
@@ -901,6 +1025,16 @@ pub async fn engine_chat_send(
         // Always clean up the abort handle and yield signal when the task finishes
         cleanup_runs.lock().remove(&cleanup_session_id);
         yield_signals_cleanup.lock().remove(&yield_cleanup_session);
+        {
+            // Only drop our own token — a newer run may have replaced it
+            let mut tokens = cancel_tokens_cleanup.lock();
+            if tokens
+                .get(&yield_cleanup_session)
+                .is_some_and(|t| t.same_as(&cancel_token_for_cleanup))
+            {
+                tokens.remove(&yield_cleanup_session);
+            }
+        }
 
         // ── Process next queued request (VS Code pattern) ─────────────
         // After the current request completes, check if there are queued
@@ -957,6 +1091,7 @@ pub async fn engine_chat_send(
                         model: None,
                         total_rounds: None,
                         max_rounds: None,
+                        finish_reason: None,
                     },
                 );
             } else {
@@ -984,10 +1119,35 @@ pub fn engine_chat_history(
     session_id: String,
     limit: Option<i64>,
 ) -> Result<Vec<StoredMessage>, String> {
-    state
+    let mut messages = state
         .store
         .get_messages(&session_id, limit.unwrap_or(200))
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if !state.config.lock().show_reasoning {
+        for msg in &mut messages {
+            msg.reasoning = None;
+        }
+    }
+    Ok(messages)
+}
+
+/// Cancel the in-flight agent run for a session.
+/// Unlike `engine_chat_abort`, the loop stops cooperatively: the LLM stream
+/// and any running tool are abandoned, pending tool calls get a "cancelled"
+/// result, and the partial turn is persisted so the history stays valid.
+#[tauri::command]
+pub fn engine_cancel_run(
+    state: State<'_, EngineState>,
+    session_id: String,
+) -> Result<bool, String> {
+    match state.cancel_tokens.lock().get(&session_id) {
+        Some(token) => {
+            token.cancel();
+            info!("[engine] Cancellation requested for session {}", session_id);
+            Ok(true)
+        }
+        None => Ok(false), // Run already finished
+    }
 }
 
 /// Abort an in-flight agent run for the given session.
@@ -1014,10 +1174,15 @@ pub fn engine_sessions_list(
     state: State<'_, EngineState>,
     limit: Option<i64>,
     agent_id: Option<String>,
+    include_archived: Option<bool>,
 ) -> Result<Vec<Session>, String> {
     state
         .store
-        .list_sessions_filtered(limit.unwrap_or(50), agent_id.as_deref())
+        .list_sessions_visible(
+            limit.unwrap_or(50),
+            agent_id.as_deref(),
+            include_archived.unwrap_or(false),
+        )
         .map_err(|e| e.to_string())
 }
 
@@ -1044,6 +1209,106 @@ pub fn engine_session_delete(
         .map_err(|e| e.to_string())
 }
 
+/// "Forget this conversation": erase the session so its content isn't
+/// recoverable from the database file, optionally with the memories it
+/// produced.
+#[tauri::command]
+pub fn engine_session_secure_delete(
+    state: State<'_, EngineState>,
+    session_id: String,
+    scrub_memories: Option<bool>,
+) -> Result<SecureDeleteReport, String> {
+    info!(
+        "[engine] Secure delete requested for session {}",
+        session_id
+    );
+    state
+        .store
+        .secure_delete_session(&session_id, scrub_memories.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// Fork a session at a message; returns the new session.
+#[tauri::command]
+pub fn engine_session_fork(
+    state: State<'_, EngineState>,
+    session_id: String,
+    message_id: String,
+) -> Result<Session, String> {
+    state
+        .store
+        .fork_session(&session_id, &message_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn engine_session_forks(
+    state: State<'_, EngineState>,
+    session_id: String,
+) -> Result<Vec<Session>, String> {
+    state
+        .store
+        .list_session_forks(&session_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Archive or restore a single session.
+#[tauri::command]
+pub fn engine_session_set_archived(
+    state: State<'_, EngineState>,
+    session_id: String,
+    archived: bool,
+) -> Result<(), String> {
+    state
+        .store
+        .set_session_archived(&session_id, archived, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Archive sessions untouched for `older_than_days` (default from the
+/// archive settings), optionally storing a one-line summary for each.
+#[tauri::command]
+pub fn engine_sessions_archive_old(
+    state: State<'_, EngineState>,
+    older_than_days: Option<u32>,
+    summarize: Option<bool>,
+) -> Result<ArchiveReport, String> {
+    let config = session_archive::load_config(&state.store);
+    session_archive::archive_old_sessions(
+        &state.store,
+        older_than_days.unwrap_or(config.older_than_days),
+        summarize.unwrap_or(config.summarize),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Sessions not re-indexed before a search runs; the rest catch up on
+/// later searches.
+const SEARCH_INDEX_BATCH: usize = 50;
+
+/// Find past sessions by meaning rather than exact words. Brings the chunk
+/// index up to date first, then ranks sessions by their best-matching chunk
+/// (keyword search when no embedding provider is configured).
+#[tauri::command]
+pub async fn engine_sessions_search_semantic(
+    state: State<'_, EngineState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SessionSearchHit>, String> {
+    let client = state.embedding_client();
+    session_search::index_pending_sessions(&state.store, client.as_ref(), SEARCH_INDEX_BATCH)
+        .await
+        .map_err(|e| e.to_string())?;
+    session_search::search_sessions_semantic(
+        &state.store,
+        client.as_ref(),
+        &query,
+        limit.unwrap_or(10).clamp(1, 50),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn engine_session_clear(
     state: State<'_, EngineState>,
@@ -3,6 +3,13 @@
 use crate::commands::state::EngineState;
 use crate::engine::types::*;
 use log::info;
+use openpawz_core::engine::approval_rules::{self, ApprovalRules};
+use openpawz_core::engine::budget_window;
+use openpawz_core::engine::file_log::{self, FileLogConfig};
+use openpawz_core::engine::session_archive::{self, SessionArchiveConfig};
+use openpawz_core::engine::storage_migration::{self, MigrationOptions, MigrationReport};
+use openpawz_core::engine::tool_cache::{self, ToolCacheConfig, ToolCacheStats};
+use openpawz_core::engine::tool_result_policy::{self, ToolResultPolicy};
 use std::sync::atomic::Ordering;
 use tauri::State;
 
@@ -28,6 +35,135 @@ pub fn engine_sandbox_set_config(
     crate::engine::sandbox::save_sandbox_config(&state.store, &config).map_err(|e| e.to_string())
 }
 
+// ── HIL approval rules ─────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn engine_approval_rules_get(state: State<'_, EngineState>) -> Result<ApprovalRules, String> {
+    Ok(approval_rules::load_rules(&state.store))
+}
+
+#[tauri::command]
+pub fn engine_approval_rules_set(
+    state: State<'_, EngineState>,
+    rules: ApprovalRules,
+) -> Result<(), String> {
+    info!(
+        "[engine] Updating HIL approval rules ({} rules)",
+        rules.rules.len()
+    );
+    approval_rules::save_rules(&state.store, &rules).map_err(|e| e.to_string())
+}
+
+// ── Tool result cache ──────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn engine_tool_cache_get_config(
+    state: State<'_, EngineState>,
+) -> Result<ToolCacheConfig, String> {
+    Ok(state.tool_cache.lock().config().clone())
+}
+
+#[tauri::command]
+pub fn engine_tool_cache_set_config(
+    state: State<'_, EngineState>,
+    config: ToolCacheConfig,
+) -> Result<(), String> {
+    tool_cache::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Tool cache {} (default TTL {}s, {} overrides)",
+        if config.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        config.default_ttl_secs,
+        config.ttl_secs.len()
+    );
+    state.tool_cache.lock().set_config(config);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn engine_tool_cache_stats(state: State<'_, EngineState>) -> Result<ToolCacheStats, String> {
+    Ok(state.tool_cache.lock().stats())
+}
+
+/// Drop all cached tool results. Returns how many were dropped.
+#[tauri::command]
+pub fn engine_tool_cache_flush(state: State<'_, EngineState>) -> Result<usize, String> {
+    let dropped = state.tool_cache.lock().flush();
+    info!("[engine] Flushed {} cached tool results", dropped);
+    Ok(dropped)
+}
+
+// ── Tool-result context policy ─────────────────────────────────────────
+
+#[tauri::command]
+pub fn engine_tool_result_policy_get(
+    state: State<'_, EngineState>,
+) -> Result<ToolResultPolicy, String> {
+    Ok(tool_result_policy::load_config(&state.store))
+}
+
+/// Save the policy. It applies from the next message sent.
+#[tauri::command]
+pub fn engine_tool_result_policy_set(
+    state: State<'_, EngineState>,
+    policy: ToolResultPolicy,
+) -> Result<(), String> {
+    tool_result_policy::save_config(&state.store, &policy).map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Tool-result policy saved: {:?} after {} turns (> {} chars)",
+        policy.mode, policy.after_turns, policy.min_chars
+    );
+    Ok(())
+}
+
+// ── Session archiving ──────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn engine_session_archive_get_config(
+    state: State<'_, EngineState>,
+) -> Result<SessionArchiveConfig, String> {
+    Ok(session_archive::load_config(&state.store))
+}
+
+/// Save the archive settings. The background run picks them up on its
+/// next check.
+#[tauri::command]
+pub fn engine_session_archive_set_config(
+    state: State<'_, EngineState>,
+    config: SessionArchiveConfig,
+) -> Result<(), String> {
+    session_archive::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Session archive config saved: enabled={} after {} days every {}h",
+        config.enabled, config.older_than_days, config.interval_hours
+    );
+    Ok(())
+}
+
+// ── Log file ───────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn engine_file_log_get_config(state: State<'_, EngineState>) -> Result<FileLogConfig, String> {
+    Ok(file_log::load_config(&state.store))
+}
+
+/// Save log file settings. They apply on the next launch.
+#[tauri::command]
+pub fn engine_file_log_set_config(
+    state: State<'_, EngineState>,
+    config: FileLogConfig,
+) -> Result<(), String> {
+    file_log::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Log file config saved: enabled={} level={} max={}B x{}",
+        config.enabled, config.level, config.max_file_bytes, config.max_files
+    );
+    Ok(())
+}
+
 // ── Engine configuration ───────────────────────────────────────────────
 
 #[tauri::command]
@@ -36,6 +172,13 @@ pub fn engine_get_config(state: State<'_, EngineState>) -> Result<EngineConfig,
     Ok(cfg.clone())
 }
 
+/// Locales the engine has messages for (built-in plus any loaded from
+/// the `locales/` folder in the data directory).
+#[tauri::command]
+pub fn engine_available_locales() -> Vec<String> {
+    openpawz_core::engine::i18n::available_locales()
+}
+
 /// Get the current daily token spend and budget status.
 #[tauri::command]
 pub fn engine_get_daily_spend(state: State<'_, EngineState>) -> Result<serde_json::Value, String> {
@@ -45,15 +188,48 @@ pub fn engine_get_daily_spend(state: State<'_, EngineState>) -> Result<serde_jso
         .daily_tokens
         .cache_create_tokens
         .load(Ordering::Relaxed);
-    let budget = {
+    let (budget, agent_budgets, window) = {
         let cfg = state.config.lock();
-        cfg.daily_budget_usd
+        (
+            cfg.daily_budget_usd,
+            cfg.agent_budgets_usd.clone(),
+            cfg.budget_window(),
+        )
     };
     let budget_pct = if budget > 0.0 {
         (estimated_usd / budget * 100.0).min(100.0)
     } else {
         0.0
     };
+    // Every agent that has spent today, plus capped agents that haven't yet
+    let mut agent_spend = state.daily_tokens.agent_spend();
+    for agent_id in agent_budgets.keys() {
+        agent_spend.entry(agent_id.clone()).or_insert(0.0);
+    }
+    let mut agents: Vec<(String, f64)> = agent_spend.into_iter().collect();
+    agents.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let agents: Vec<serde_json::Value> = agents
+        .into_iter()
+        .map(|(agent_id, spent)| {
+            let agent_budget = agent_budgets
+                .get(&agent_id)
+                .copied()
+                .filter(|b| *b > 0.0)
+                .unwrap_or(0.0);
+            let agent_pct = if agent_budget > 0.0 {
+                (spent / agent_budget * 100.0).min(100.0)
+            } else {
+                0.0
+            };
+            serde_json::json!({
+                "agent_id": agent_id,
+                "estimated_usd": format!("{:.2}", spent),
+                "budget_usd": agent_budget,
+                "budget_pct": format!("{:.0}", agent_pct),
+                "over_budget": agent_budget > 0.0 && spent >= agent_budget,
+            })
+        })
+        .collect();
     Ok(serde_json::json!({
         "input_tokens": input_tokens,
         "output_tokens": output_tokens,
@@ -63,6 +239,9 @@ pub fn engine_get_daily_spend(state: State<'_, EngineState>) -> Result<serde_jso
         "budget_usd": budget,
         "budget_pct": format!("{:.0}", budget_pct),
         "over_budget": budget > 0.0 && estimated_usd >= budget,
+        "agents": agents,
+        "window_start": state.daily_tokens.window_start(),
+        "resets_at": budget_window::format_window_start(&window.next_reset(chrono::Utc::now())),
     }))
 }
 
@@ -71,12 +250,29 @@ pub fn engine_set_config(
     state: State<'_, EngineState>,
     config: EngineConfig,
 ) -> Result<(), String> {
+    if let Some(template) = &config.system_prompt_template {
+        openpawz_core::engine::prompt_template::validate_template(template)?;
+    }
     let json = serde_json::to_string(&config).map_err(|e| format!("Serialize error: {}", e))?;
 
     // Persist to DB
     state.store.set_config("engine_config", &json)?;
 
     // Update in-memory config
+    crate::engine::engram::set_capability_overrides(&config.model_capabilities);
+    openpawz_core::engine::i18n::set_locale(config.locale.as_deref());
+    let window = config.budget_window();
+    if *state.daily_tokens.window.lock() != window {
+        // New reset schedule: reload the spend persisted for its window
+        state.daily_tokens.set_window(window);
+        let rows = state
+            .store
+            .list_daily_spend(&state.daily_tokens.window_start())?;
+        state.daily_tokens.restore(&rows);
+    }
+    state
+        .run_limiter
+        .configure(config.max_concurrent_runs, config.run_limit_policy);
     let mut cfg = state.config.lock();
     *cfg = config;
 
@@ -93,6 +289,7 @@ pub fn engine_upsert_provider(
     state: State<'_, EngineState>,
     provider: ProviderConfig,
 ) -> Result<(), String> {
+    crate::engine::providers::azure_openai::validate_config(&provider)?;
     let mut cfg = state.config.lock();
 
     // Update existing or add new
@@ -177,6 +374,36 @@ pub async fn engine_list_provider_models(
         .collect())
 }
 
+/// Test a provider's key and endpoint before (or after) saving it.
+/// An empty key falls back to the saved provider with the same id, so the
+/// settings form can test without re-entering a masked key.
+#[tauri::command]
+pub async fn engine_test_provider(
+    state: State<'_, EngineState>,
+    provider: ProviderConfig,
+) -> Result<crate::engine::providers::ProviderTestResult, String> {
+    let mut provider = provider;
+    if provider.api_key.is_empty() {
+        let cfg = state.config.lock();
+        if let Some(saved) = cfg.providers.iter().find(|p| p.id == provider.id) {
+            provider.api_key = saved.api_key.clone();
+        }
+    }
+
+    crate::engine::providers::azure_openai::validate_config(&provider)?;
+
+    let model = provider.default_model.clone();
+    let result = crate::engine::providers::AnyProvider::from_config(&provider)
+        .test_connection(model.as_deref())
+        .await;
+    info!(
+        "[engine] Provider test {}: {}",
+        provider.id,
+        if result.ok { "ok" } else { &result.message }
+    );
+    Ok(result)
+}
+
 /// Check if the engine is configured and ready to use.
 #[tauri::command]
 pub fn engine_status(state: State<'_, EngineState>) -> Result<serde_json::Value, String> {
@@ -184,6 +411,7 @@ pub fn engine_status(state: State<'_, EngineState>) -> Result<serde_json::Value,
 
     let has_providers = !cfg.providers.is_empty();
     let has_api_key = cfg.providers.iter().any(|p| !p.api_key.is_empty());
+    let runs = state.run_limiter.status();
 
     Ok(serde_json::json!({
         "ready": has_providers && has_api_key,
@@ -191,6 +419,9 @@ pub fn engine_status(state: State<'_, EngineState>) -> Result<serde_json::Value,
         "has_api_key": has_api_key,
         "default_model": cfg.default_model,
         "default_provider": cfg.default_provider,
+        "active_runs": runs.active,
+        "queued_runs": runs.queued,
+        "max_concurrent_runs": runs.max,
     }))
 }
 
@@ -297,6 +528,8 @@ pub async fn engine_auto_setup(state: State<'_, EngineState>) -> Result<serde_js
     } else {
         // No models at all — pull a small one
         info!("[engine] Ollama has no models — pulling llama3.2:3b");
+        openpawz_core::engine::memory::ollama::check_pull_space(base_url, "llama3.2:3b")
+            .map_err(|e| e.to_string())?;
         let pull_body = serde_json::json!({ "name": "llama3.2:3b", "stream": false });
         match client
             .post(format!("{}/api/pull", base_url))
@@ -328,6 +561,8 @@ pub async fn engine_auto_setup(state: State<'_, EngineState>) -> Result<serde_js
         api_key: String::new(),
         base_url: Some(base_url.to_string()),
         default_model: Some(model_name.clone()),
+        api_version: None,
+        deployments: Default::default(),
     };
 
     {
@@ -379,6 +614,9 @@ pub fn engine_storage_get_paths(
     let browser_dir = data_root.join("browser-profiles");
     let browser_size = dir_size(&browser_dir);
 
+    let log_file = file_log::log_file_path();
+    let log_size = dir_size(&file_log::log_dir());
+
     // Get workspace path from frontend config (if stored in engine config)
     let workspace_path = state.store.get_config("user_workspace_path").ok().flatten();
 
@@ -394,7 +632,10 @@ pub fn engine_storage_get_paths(
         "skills_size": skills_size,
         "browser_dir": browser_dir.to_string_lossy(),
         "browser_size": browser_size,
+        "log_file": log_file.to_string_lossy(),
+        "log_size": log_size,
         "workspace_path": workspace_path,
+        "available_bytes": openpawz_core::engine::disk::available_space(&data_root),
     }))
 }
 
@@ -425,6 +666,58 @@ pub fn engine_storage_set_data_root(
     Ok(())
 }
 
+/// Move the data root to `new_root`: copy the database (SQLite backup API),
+/// workspaces, skills, browser profiles, logs and locales, verify them, then
+/// repoint `storage.conf`. Emits `storage-migration-progress` events as it
+/// goes. A completed migration restarts the app onto the new root; with
+/// `remove_old` the old copies are deleted on that launch.
+#[tauri::command]
+pub async fn engine_storage_migrate_data(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+    new_root: String,
+    remove_old: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<MigrationReport, String> {
+    use tauri::Emitter;
+    let options = MigrationOptions {
+        dry_run: dry_run.unwrap_or(false),
+        remove_old: remove_old.unwrap_or(false),
+    };
+    let conn = state.store.conn.clone();
+    let old_root = crate::engine::paths::paw_data_dir();
+    info!(
+        "[storage] Migrating data root {} → {} (dry_run={}, remove_old={})",
+        old_root.display(),
+        new_root,
+        options.dry_run,
+        options.remove_old
+    );
+    tokio::task::spawn_blocking(move || {
+        let conn = conn.lock();
+        let report = storage_migration::migrate_data_root(
+            &conn,
+            &old_root,
+            std::path::Path::new(&new_root),
+            &options,
+            &mut |progress| {
+                let _ = app_handle.emit("storage-migration-progress", progress);
+            },
+        )?;
+        if report.dry_run {
+            return Ok(report);
+        }
+        // The store, log file and catalogs are still open in the old root.
+        // Restart while holding the write connection, so nothing is written
+        // to the old database after the snapshot.
+        info!("[storage] Restarting onto the new data root");
+        app_handle.restart()
+    })
+    .await
+    .map_err(|e| format!("Migration task failed: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
 /// Recursive directory size in bytes.
 fn dir_size(path: &std::path::Path) -> u64 {
     if !path.exists() {
@@ -0,0 +1,111 @@
+// commands/emergency.rs — Global emergency stop ("panic button")
+//
+// engine_emergency_stop halts the engine (openpawz_core::engine::emergency):
+// in-flight runs are cancelled, new runs are rejected, the cron heartbeat
+// skips its ticks, and every channel bridge is stopped. engine_resume
+// re-arms the engine; bridges stay off until the user starts them again.
+
+use crate::commands::state::EngineState;
+use crate::engine::channels::ChannelStatus;
+use crate::engine::{
+    discord, irc, matrix, mattermost, nextcloud, nostr, slack, telegram, twitch, webchat, webhook,
+    whatsapp,
+};
+use log::warn;
+use openpawz_core::engine::emergency::{self, HaltStatus};
+use serde::Serialize;
+use tauri::{Emitter, State};
+
+/// Result of a stop or resume, also emitted as `engine-emergency`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmergencyStatus {
+    #[serde(flatten)]
+    pub halt: HaltStatus,
+    /// Runs that were cancelled by this stop.
+    pub cancelled_runs: usize,
+    /// Bridges that were running and have been stopped.
+    pub stopped_bridges: Vec<String>,
+}
+
+type Bridge = (&'static str, fn(&tauri::AppHandle) -> ChannelStatus, fn());
+
+const BRIDGES: &[Bridge] = &[
+    ("discord", discord::get_status, discord::stop_bridge),
+    ("irc", irc::get_status, irc::stop_bridge),
+    ("matrix", matrix::get_status, matrix::stop_bridge),
+    (
+        "mattermost",
+        mattermost::get_status,
+        mattermost::stop_bridge,
+    ),
+    ("nextcloud", nextcloud::get_status, nextcloud::stop_bridge),
+    ("nostr", nostr::get_status, nostr::stop_bridge),
+    ("slack", slack::get_status, slack::stop_bridge),
+    ("twitch", twitch::get_status, twitch::stop_bridge),
+    ("webchat", webchat::get_status, webchat::stop_bridge),
+    ("webhook", webhook::get_status, webhook::stop_bridge),
+    ("whatsapp", whatsapp::get_status, whatsapp::stop_bridge),
+];
+
+fn stop_all_bridges(app_handle: &tauri::AppHandle) -> Vec<String> {
+    let mut stopped = Vec::new();
+    for (name, status, stop) in BRIDGES {
+        if status(app_handle).running {
+            stop();
+            stopped.push(name.to_string());
+        }
+    }
+    if telegram::get_status(app_handle).running {
+        telegram::stop_bridge();
+        stopped.push("telegram".into());
+    }
+    stopped
+}
+
+/// Stop all agent activity immediately and block new runs until resumed.
+#[tauri::command]
+pub fn engine_emergency_stop(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+) -> Result<EmergencyStatus, String> {
+    emergency::halt();
+
+    // Chat runs have their own tokens; the halt token covers everything else
+    let cancelled_runs = {
+        let tokens = state.cancel_tokens.lock();
+        tokens.values().for_each(|token| token.cancel());
+        tokens.len()
+    };
+    let stopped_bridges = stop_all_bridges(&app_handle);
+    warn!(
+        "[emergency] Stopped {} run(s) and bridges {:?}",
+        cancelled_runs, stopped_bridges
+    );
+
+    let status = EmergencyStatus {
+        halt: emergency::status(),
+        cancelled_runs,
+        stopped_bridges,
+    };
+    let _ = app_handle.emit("engine-emergency", &status);
+    Ok(status)
+}
+
+/// Re-arm the engine after an emergency stop.
+#[tauri::command]
+pub fn engine_resume(app_handle: tauri::AppHandle) -> Result<EmergencyStatus, String> {
+    emergency::resume();
+    let status = EmergencyStatus {
+        halt: emergency::status(),
+        cancelled_runs: 0,
+        stopped_bridges: Vec::new(),
+    };
+    let _ = app_handle.emit("engine-emergency", &status);
+    Ok(status)
+}
+
+/// Whether an emergency stop is active.
+#[tauri::command]
+pub fn engine_emergency_status() -> Result<HaltStatus, String> {
+    Ok(emergency::status())
+}
@@ -2,11 +2,28 @@
 //
 // Phase 3.5: rate limits, agent permissions, credential audit trail.
 
+use crate::commands::state::EngineState;
 use crate::engine::channels;
+use crate::engine::secret_scrub;
+use openpawz_core::engine::csv::{to_csv, DateRange};
+use openpawz_core::engine::sessions::approvals::redact_args;
+use openpawz_core::engine::tool_metadata;
 use serde::{Deserialize, Serialize};
+use tauri::State;
 
 // ── Types ──────────────────────────────────────────────────────────────
 
+/// How a rate-limit window moves. Sliding counts the last `windowMinutes`
+/// continuously; fixed resets at clock-aligned boundaries, so a burst can
+/// straddle two windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitWindowType {
+    #[default]
+    Sliding,
+    Fixed,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     pub service: String,
@@ -14,6 +31,8 @@ pub struct RateLimitConfig {
     pub max_actions: u32,
     #[serde(rename = "windowMinutes")]
     pub window_minutes: u32,
+    #[serde(rename = "windowType", default)]
+    pub window_type: RateLimitWindowType,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,23 +119,27 @@ pub fn engine_guardrails_get_rate_limits(
     Ok(load_rate_limits(&app_handle))
 }
 
-/// Set / update rate limit for a service.
+/// Set / update rate limit for a service. `window_type` defaults to sliding.
 #[tauri::command]
 pub fn engine_guardrails_set_rate_limit(
     app_handle: tauri::AppHandle,
     service: String,
     max_actions: u32,
     window_minutes: u32,
+    window_type: Option<RateLimitWindowType>,
 ) -> Result<(), String> {
+    let window_type = window_type.unwrap_or_default();
     let mut limits = load_rate_limits(&app_handle);
     if let Some(existing) = limits.iter_mut().find(|l| l.service == service) {
         existing.max_actions = max_actions;
         existing.window_minutes = window_minutes;
+        existing.window_type = window_type;
     } else {
         limits.push(RateLimitConfig {
             service,
             max_actions,
             window_minutes,
+            window_type,
         });
     }
     save_rate_limits(&app_handle, &limits)
@@ -177,6 +200,33 @@ pub fn engine_guardrails_set_permission(
     save_permissions(&app_handle, &perms)
 }
 
+/// Give `to_agent` the same service permissions as `from_agent` (used when
+/// cloning an agent). Returns the services copied. These are access grants
+/// only — credentials stay in the vault and are never duplicated.
+pub(crate) fn copy_agent_permissions(
+    app_handle: &tauri::AppHandle,
+    from_agent: &str,
+    to_agent: &str,
+) -> Result<Vec<String>, String> {
+    let mut perms = load_permissions(app_handle);
+    let copied: Vec<AgentServicePermission> = perms
+        .iter()
+        .filter(|p| p.agent_id == from_agent)
+        .map(|p| AgentServicePermission {
+            agent_id: to_agent.to_string(),
+            ..p.clone()
+        })
+        .collect();
+    if copied.is_empty() {
+        return Ok(vec![]);
+    }
+    let services = copied.iter().map(|p| p.service.clone()).collect();
+    perms.retain(|p| p.agent_id != to_agent);
+    perms.extend(copied);
+    save_permissions(app_handle, &perms)?;
+    Ok(services)
+}
+
 // ── Audit Log Commands ─────────────────────────────────────────────────
 
 /// Log a credential/integration usage event.
@@ -221,6 +271,148 @@ pub fn engine_guardrails_clear_audit(app_handle: tauri::AppHandle) -> Result<(),
     save_audit_log(&app_handle, &[])
 }
 
+// ── CSV Export ─────────────────────────────────────────────────────────
+
+/// Logs `engine_guardrails_export_csv` can export.
+const EXPORT_KINDS: &[&str] = &["credentials", "approvals", "actions"];
+
+/// Max approvals read for an export.
+const APPROVAL_EXPORT_LIMIT: u32 = 100_000;
+
+/// Export an audit log as CSV: `credentials` (credential usage), `approvals`
+/// (HIL decisions) or `actions` (integration action log). Optionally limited
+/// to a date range (`YYYY-MM-DD` or RFC 3339, inclusive) and a service —
+/// for approvals, a tool name or tool domain. Arguments are redacted and
+/// free text is run through the secret scrubber.
+#[tauri::command]
+pub fn engine_guardrails_export_csv(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+    kind: String,
+    from: Option<String>,
+    to: Option<String>,
+    service: Option<String>,
+) -> Result<String, String> {
+    let range = DateRange::parse(from.as_deref(), to.as_deref())?;
+    let service = service.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let service_matches = |s: &str| service.is_none_or(|want| s == want);
+
+    match kind.as_str() {
+        "credentials" => {
+            Ok(credentials_csv(load_audit_log(&app_handle).iter().filter(
+                |l| range.contains(&l.timestamp) && service_matches(&l.service),
+            )))
+        }
+        "approvals" => {
+            let approvals = state
+                .store
+                .list_approvals(None, APPROVAL_EXPORT_LIMIT)
+                .map_err(|e| e.to_string())?;
+            let rows = approvals.into_iter().rev().filter(|a| {
+                range.contains(&a.created_at)
+                    && (service_matches(&a.tool_name)
+                        || service_matches(tool_metadata::domain_str(&a.tool_name)))
+            });
+            Ok(to_csv(
+                &[
+                    "id",
+                    "created_at",
+                    "session_id",
+                    "tool_name",
+                    "arguments",
+                    "decision",
+                    "decider",
+                    "success",
+                    "outcome",
+                    "tx_hash",
+                ],
+                rows.map(|a| {
+                    vec![
+                        a.id.to_string(),
+                        a.created_at,
+                        a.session_id,
+                        a.tool_name,
+                        // Stored redacted; scrub again in case of older rows
+                        secret_scrub::scrub(&a.args_summary),
+                        a.decision,
+                        a.decider,
+                        a.success.map(|s| s.to_string()).unwrap_or_default(),
+                        secret_scrub::scrub(a.outcome.as_deref().unwrap_or("")),
+                        a.tx_hash.unwrap_or_default(),
+                    ]
+                }),
+            ))
+        }
+        "actions" => {
+            let log = crate::commands::action_log::engine_action_log_list(
+                app_handle.clone(),
+                Some(u32::MAX),
+                None,
+            )?;
+            Ok(to_csv(
+                &[
+                    "timestamp",
+                    "agent",
+                    "service",
+                    "action",
+                    "status",
+                    "duration_ms",
+                    "summary",
+                    "input",
+                    "error",
+                ],
+                log.into_iter()
+                    .filter(|a| range.contains(&a.timestamp) && service_matches(&a.service))
+                    .map(|a| {
+                        vec![
+                            a.timestamp,
+                            a.agent,
+                            a.service,
+                            a.action,
+                            a.status,
+                            a.duration_ms.to_string(),
+                            secret_scrub::scrub(&a.summary),
+                            a.input
+                                .map(|input| redact_args(&input.to_string()))
+                                .unwrap_or_default(),
+                            secret_scrub::scrub(a.error_message.as_deref().unwrap_or("")),
+                        ]
+                    }),
+            ))
+        }
+        other => Err(format!(
+            "Unknown export kind '{}'. Use one of: {}",
+            other,
+            EXPORT_KINDS.join(", ")
+        )),
+    }
+}
+
+fn credentials_csv<'a>(logs: impl Iterator<Item = &'a CredentialUsageLog>) -> String {
+    to_csv(
+        &[
+            "timestamp",
+            "agent",
+            "service",
+            "action",
+            "access_level",
+            "approved",
+            "result",
+        ],
+        logs.map(|l| {
+            vec![
+                l.timestamp.clone(),
+                l.agent.clone(),
+                l.service.clone(),
+                secret_scrub::scrub(&l.action),
+                l.access_level.clone(),
+                l.approved.to_string(),
+                l.result.clone(),
+            ]
+        }),
+    )
+}
+
 // ── Token Info Commands ────────────────────────────────────────────────
 
 /// Check which tokens are expiring soon (within N days).
@@ -272,3 +464,46 @@ pub fn engine_guardrails_update_token_info(
 
     save_token_info(&app_handle, &tokens)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limits_saved_before_window_types_default_to_sliding() {
+        let config: RateLimitConfig =
+            serde_json::from_str(r#"{"service":"slack","maxActions":30,"windowMinutes":15}"#)
+                .unwrap();
+        assert_eq!(config.window_type, RateLimitWindowType::Sliding);
+        let fixed: RateLimitConfig = serde_json::from_str(
+            r#"{"service":"slack","maxActions":30,"windowMinutes":15,"windowType":"fixed"}"#,
+        )
+        .unwrap();
+        assert_eq!(fixed.window_type, RateLimitWindowType::Fixed);
+    }
+
+    #[test]
+    fn credential_csv_escapes_fields() {
+        let log = CredentialUsageLog {
+            timestamp: "2025-03-01T10:00:00Z".into(),
+            agent: "default".into(),
+            service: "slack".into(),
+            action: "post \"hi, all\"\nin #general".into(),
+            access_level: "write".into(),
+            approved: true,
+            result: "success".into(),
+        };
+        let csv = credentials_csv([log].iter());
+        let mut lines = csv.split("\r\n");
+        assert_eq!(
+            lines.next(),
+            Some("timestamp,agent,service,action,access_level,approved,result")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "2025-03-01T10:00:00Z,default,slack,\"post \"\"hi, all\"\"\nin #general\",write,true,success"
+            )
+        );
+    }
+}
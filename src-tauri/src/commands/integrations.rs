@@ -6,6 +6,7 @@
 
 use crate::engine::channels;
 use crate::engine::state::EngineState;
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use tauri::Manager;
@@ -293,7 +294,7 @@ pub async fn engine_calendar_events_today(
         warn!(
             "[calendar] Google Calendar API returned {}: {}",
             status,
-            &body[..body.len().min(200)]
+            safe_truncate(&body, 200)
         );
         // Return empty instead of erroring — the token might be expired
         // and the background refresh will fix it on the next cycle.
@@ -1,5 +1,6 @@
 // commands/mail.rs — Himalaya email bridge commands + Gmail API bridge.
 
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -470,7 +471,7 @@ pub async fn engine_gmail_inbox(page_size: Option<u32>) -> Result<Vec<GmailMessa
         warn!(
             "[gmail] API returned {}: {}",
             status,
-            &body[..body.len().min(200)]
+            safe_truncate(&body, 200)
         );
         return Ok(vec![]);
     }
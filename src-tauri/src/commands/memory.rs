@@ -3,10 +3,12 @@
 // session store for CRUD. The old engine::memory module is no longer used
 // for core CRUD — only shared utilities (EmbeddingClient) remain.
 
-use crate::commands::state::EngineState;
+use crate::commands::state::{normalize_model_name, resolve_provider_for_model, EngineState};
 use crate::engine::engram;
+use crate::engine::engram::memory_merge::{self, MemoryMergeConfig, MemoryMergeReport};
 use crate::engine::memory; // Still needed for backfill, embeddings, ensure_ollama_ready
 use crate::engine::types::*;
+use crate::engine::util::safe_truncate;
 use log::info;
 use tauri::State;
 
@@ -261,6 +263,121 @@ pub fn engine_set_memory_config(
     Ok(())
 }
 
+/// Get the auto-capture policy for an agent (defaults apply if unset).
+#[tauri::command]
+pub fn engine_memory_get_capture_policy(
+    state: State<'_, EngineState>,
+    agent_id: String,
+) -> Result<memory::capture::AutoCapturePolicy, String> {
+    Ok(memory::capture::load_policy(&state.store, &agent_id))
+}
+
+/// Set the auto-capture policy for an agent.
+#[tauri::command]
+pub fn engine_memory_set_capture_policy(
+    state: State<'_, EngineState>,
+    agent_id: String,
+    policy: memory::capture::AutoCapturePolicy,
+) -> Result<(), String> {
+    if policy.min_importance > 10 {
+        return Err("min_importance must be between 0 and 10".into());
+    }
+    memory::capture::save_policy(&state.store, &agent_id, &policy)?;
+    info!(
+        "[engine] Auto-capture policy updated for agent '{}' (enabled={})",
+        agent_id, policy.enabled
+    );
+    Ok(())
+}
+
+// ── Memory merge (LLM summarization of related memories) ───────────────
+
+/// Provider and model for merge calls: the configured model, else the cheap
+/// model, else the default model.
+fn memory_merge_model(
+    state: &EngineState,
+    config: &MemoryMergeConfig,
+) -> Option<(ProviderConfig, String)> {
+    let cfg = state.config.lock();
+    let model = config
+        .model
+        .clone()
+        .or_else(|| cfg.model_routing.cheap_model.clone())
+        .filter(|m| !m.is_empty())
+        .or_else(|| cfg.default_model.clone())?;
+    let model = normalize_model_name(&model).to_string();
+    let provider = resolve_provider_for_model(&model, &cfg.providers)
+        .or_else(|| {
+            cfg.default_provider
+                .as_ref()
+                .and_then(|dp| cfg.providers.iter().find(|p| p.id == *dp).cloned())
+        })
+        .or_else(|| cfg.providers.first().cloned())?;
+    Some((provider, model))
+}
+
+/// Run one bounded merge pass with `config`. Shared by the background job
+/// and [`engine_memory_merge_run`].
+pub(crate) async fn run_memory_merge(
+    state: &EngineState,
+    config: &MemoryMergeConfig,
+) -> Result<MemoryMergeReport, String> {
+    let (provider_config, model) =
+        memory_merge_model(state, config).ok_or("No AI provider configured")?;
+    let provider = crate::engine::providers::AnyProvider::from_config(&provider_config);
+    let emb_client = state.embedding_client();
+    memory_merge::run_memory_merge(
+        &state.store,
+        emb_client.as_ref(),
+        Some(&state.hnsw_index),
+        config,
+        |prompt| memory_merge::llm_merge(&provider, &model, prompt),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn engine_memory_merge_get_config(
+    state: State<'_, EngineState>,
+) -> Result<MemoryMergeConfig, String> {
+    Ok(memory_merge::load_config(&state.store))
+}
+
+/// Save the merge settings. The background job picks them up on its next
+/// check.
+#[tauri::command]
+pub fn engine_memory_merge_set_config(
+    state: State<'_, EngineState>,
+    config: MemoryMergeConfig,
+) -> Result<(), String> {
+    if !(0.5..=1.0).contains(&config.similarity_threshold) {
+        return Err("similarity_threshold must be between 0.5 and 1.0".into());
+    }
+    if config.max_cluster_size < 2 {
+        return Err("max_cluster_size must be at least 2".into());
+    }
+    memory_merge::save_config(&state.store, &config).map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Memory merge config saved: enabled={} threshold={} up to {} clusters every {}h",
+        config.enabled,
+        config.similarity_threshold,
+        config.max_clusters_per_run,
+        config.interval_hours
+    );
+    Ok(())
+}
+
+/// Run a merge pass now with the saved settings, even when the background
+/// job is disabled.
+#[tauri::command]
+pub async fn engine_memory_merge_run(
+    state: State<'_, EngineState>,
+) -> Result<MemoryMergeReport, String> {
+    let config = memory_merge::load_config(&state.store);
+    run_memory_merge(&state, &config).await
+}
+
 // ── Embedding / Ollama ─────────────────────────────────────────────────
 
 #[tauri::command]
@@ -389,6 +506,74 @@ pub async fn engine_memory_backfill(
     }))
 }
 
+/// Prune stale legacy memories: older than `max_age_days` and retrieved fewer
+/// than `min_retrievals` times (default 1 — i.e. never retrieved).
+#[tauri::command]
+pub fn engine_memory_prune_stale(
+    state: State<'_, EngineState>,
+    max_age_days: Option<u32>,
+    min_retrievals: Option<u32>,
+) -> Result<usize, String> {
+    let days = max_age_days.unwrap_or(90).max(1);
+    let min = min_retrievals.unwrap_or(1);
+    let removed = state
+        .store
+        .prune_stale_memories(days as i64 * 86_400, min as i64)
+        .map_err(|e| e.to_string())?;
+    info!(
+        "[engine] Pruned {} stale memories (older than {}d, <{} retrievals)",
+        removed, days, min
+    );
+    Ok(removed)
+}
+
+/// Export all legacy memories as portable JSON. Writes to `path` and returns
+/// it when given; otherwise returns the JSON document itself.
+#[tauri::command]
+pub fn engine_memory_export(
+    state: State<'_, EngineState>,
+    include_embeddings: Option<bool>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let export =
+        memory::portable::export_memories(&state.store, include_embeddings.unwrap_or(false))?;
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    match path {
+        Some(p) => {
+            std::fs::write(&p, json).map_err(|e| format!("Failed to write {}: {}", p, e))?;
+            info!(
+                "[engine] Exported {} memories to {}",
+                export.memories.len(),
+                p
+            );
+            Ok(p)
+        }
+        None => Ok(json),
+    }
+}
+
+/// Import memories from a portable JSON export. Existing ids are skipped;
+/// with `reembed`, missing or mismatched vectors are recomputed.
+#[tauri::command]
+pub async fn engine_memory_import(
+    state: State<'_, EngineState>,
+    path: String,
+    reembed: Option<bool>,
+) -> Result<memory::portable::PortableImportReport, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let export = memory::portable::parse_export(&json)?;
+    let emb_client = state.embedding_client();
+    memory::portable::import_memories(
+        &state.store,
+        &export,
+        emb_client.as_ref(),
+        reembed.unwrap_or(true),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /// Save working memory snapshot for an agent (called on agent switch).
 #[tauri::command]
 pub fn engine_working_memory_save(
@@ -488,7 +673,7 @@ pub fn engine_message_feedback(
 
     info!(
         "[engine] Message feedback recorded: {} (helpful={}, trust updated {} memories)",
-        &feedback_id[..8.min(feedback_id.len())],
+        truncate_utf8(&feedback_id, 8),
         helpful,
         updated
     );
@@ -564,7 +749,7 @@ pub fn engine_memory_embedding_projection(
                 _synthetic_position(cluster_idx, idx, entry.0, mem.importance as f32 / 10.0);
 
             let snippet = if mem.content.len() > 200 {
-                format!("{}...", &mem.content[..197])
+                format!("{}...", safe_truncate(&mem.content, 197))
             } else {
                 mem.content.clone()
             };
@@ -732,7 +917,7 @@ fn _extract_metadata(
             mem.content.full.clone()
         };
         let snippet = if decrypted.len() > 200 {
-            format!("{}...", &decrypted[..197])
+            format!("{}...", safe_truncate(&decrypted, 197))
         } else {
             decrypted
         };
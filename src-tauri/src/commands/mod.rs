@@ -15,6 +15,7 @@ pub mod chat;
 pub mod config;
 pub mod dashboard_tabs;
 pub mod dashboards;
+pub mod emergency;
 pub mod export;
 pub mod flows;
 pub mod forge;
@@ -27,6 +28,7 @@ pub mod memory;
 pub mod n8n;
 pub mod oauth;
 pub mod ollama;
+pub mod onboarding;
 pub mod project;
 pub mod queries;
 pub mod skill_wizard;
@@ -2,6 +2,7 @@
 
 use crate::engine::channels;
 use crate::engine::n8n_engine;
+use crate::engine::secret_scrub::{is_secret_field, SecretScrubber};
 use crate::engine::skills;
 use crate::engine::state::EngineState;
 use crate::engine::util::safe_truncate;
@@ -2812,11 +2813,7 @@ pub async fn engine_integrations_test_credentials(
         "trello" => {
             let api_key = credentials.get("api_key").cloned().unwrap_or_default();
             let api_token = credentials.get("api_token").cloned().unwrap_or_default();
-            let url = format!(
-                "https://api.trello.com/1/members/me?key={}&token={}",
-                api_key, api_token
-            );
-            _test_get(&client, &url, "Trello").await
+            _test_trello(&client, TRELLO_API_BASE, &api_key, &api_token).await
         }
         "telegram" => {
             let token = credentials
@@ -2899,6 +2896,23 @@ pub async fn engine_integrations_test_credentials(
         }
     };
 
+    // §Security: Token-in-URL services (Trello, Telegram) surface the full
+    // request URL in reqwest errors — scrub the submitted secrets before the
+    // result is logged or returned to the UI.
+    let scrubber = SecretScrubber::new(
+        credentials
+            .iter()
+            .filter(|(k, _)| is_secret_field(k))
+            .map(|(_, v)| v),
+    );
+    let result = result
+        .map(|mut r| {
+            r.message = scrubber.scrub(&r.message);
+            r.details = r.details.map(|d| scrubber.scrub(&d));
+            r
+        })
+        .map_err(|e| scrubber.scrub(&e.to_string()));
+
     match &result {
         Ok(r) => {
             if r.success {
@@ -2918,7 +2932,7 @@ pub async fn engine_integrations_test_credentials(
         Err(e) => warn!("[test-credentials] '{}' — error: {}", service_id, e),
     }
 
-    result.map_err(|e| e.to_string())
+    result
 }
 
 /// Save service credentials to the app config store.
@@ -3156,6 +3170,63 @@ async fn _test_get(
     }
 }
 
+const TRELLO_API_BASE: &str = "https://api.trello.com/1";
+
+/// Trello accepts the key/token pair in an OAuth-style `Authorization` header.
+/// Sending them there instead of as `?key=…&token=…` keeps both out of the
+/// request URL — and therefore out of reqwest error strings and logs.
+fn trello_auth_header(api_key: &str, api_token: &str) -> String {
+    format!(
+        "OAuth oauth_consumer_key=\"{}\", oauth_token=\"{}\"",
+        api_key, api_token
+    )
+}
+
+async fn _test_trello(
+    client: &reqwest::Client,
+    base_url: &str,
+    api_key: &str,
+    api_token: &str,
+) -> Result<CredentialTestResult, String> {
+    if api_key.is_empty() || api_token.is_empty() {
+        return Ok(CredentialTestResult {
+            success: false,
+            message: "API key and token are both required".into(),
+            details: None,
+        });
+    }
+    // Belt and braces: Trello echoes nothing sensitive today, but scrub the
+    // body and error text in case an upstream proxy reflects the header.
+    let scrubber = SecretScrubber::new([api_key, api_token]);
+    match client
+        .get(format!("{}/members/me", base_url))
+        .header("Authorization", trello_auth_header(api_key, api_token))
+        .header("Accept", "application/json")
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => Ok(CredentialTestResult {
+            success: true,
+            message: "Connected to Trello".into(),
+            details: None,
+        }),
+        Ok(resp) => {
+            let status = resp.status().as_u16();
+            let body = resp.text().await.unwrap_or_default();
+            Ok(CredentialTestResult {
+                success: false,
+                message: format!("Trello returned HTTP {}", status),
+                details: Some(scrubber.scrub(&body)),
+            })
+        }
+        Err(e) => Ok(CredentialTestResult {
+            success: false,
+            message: "Could not reach Trello".into(),
+            details: Some(scrubber.scrub(&classify_reqwest_error(&e))),
+        }),
+    }
+}
+
 async fn _test_notion(
     client: &reqwest::Client,
     token: &str,
@@ -3663,6 +3734,37 @@ pub fn service_to_skill_id(service_id: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn trello_auth_header_format() {
+        assert_eq!(
+            trello_auth_header("k123", "t456"),
+            "OAuth oauth_consumer_key=\"k123\", oauth_token=\"t456\""
+        );
+    }
+
+    #[tokio::test]
+    async fn trello_error_never_contains_credentials() {
+        let key = "trellokey0123456789abcdef";
+        let token = "trellotoken0123456789abcdefghijkl";
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(2))
+            .build()
+            .unwrap();
+        // Port 9 on loopback is closed — forces a connect error.
+        let result = _test_trello(&client, "http://127.0.0.1:9/1", key, token)
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let text = format!("{} {}", result.message, result.details.unwrap_or_default());
+        assert!(
+            text.contains("127.0.0.1"),
+            "expected URL in error: {}",
+            text
+        );
+        assert!(!text.contains(key));
+        assert!(!text.contains(token));
+    }
+
     #[test]
     fn discover_nodes_basic() {
         let pkg_json: serde_json::Value = serde_json::json!({
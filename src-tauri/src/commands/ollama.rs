@@ -120,6 +120,9 @@ pub async fn engine_ollama_pull_model(
 
     info!("[ollama] Pulling model '{}' from {}", model_name, base_url);
 
+    openpawz_core::engine::memory::ollama::check_pull_space(&base_url, &model_name)
+        .map_err(|e| e.to_string())?;
+
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(1800)) // 30 min timeout for large models
         .build()
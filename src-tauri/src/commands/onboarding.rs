@@ -0,0 +1,105 @@
+// commands/onboarding.rs — First-run onboarding status
+//
+// Gathers the facts the guided setup needs (providers, default model, local
+// Ollama, embeddings, skills, wizard flag) in one place and hands them to
+// `engine::onboarding::evaluate()`. Probes use short timeouts so the status
+// stays cheap to poll while the wizard is open.
+
+use crate::commands::ollama::engine_ollama_list_models;
+use crate::commands::state::EngineState;
+use crate::engine::skills;
+use crate::engine::types::EmbeddingProvider;
+use openpawz_core::engine::onboarding::{
+    self, OnboardingFacts, OnboardingStatus, OnboardingStep, StepStatus,
+};
+use std::time::Duration;
+use tauri::State;
+
+/// How long the Ollama / embedding probes may take.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+async fn gather_facts(
+    app_handle: &tauri::AppHandle,
+    state: &EngineState,
+) -> Result<OnboardingFacts, String> {
+    let (providers_configured, default_model) = {
+        let cfg = state.config.lock();
+        let default_model = cfg
+            .default_model
+            .clone()
+            .or_else(|| cfg.providers.iter().find_map(|p| p.default_model.clone()))
+            .filter(|m| !m.is_empty());
+        (cfg.providers.len(), default_model)
+    };
+
+    let ollama_models =
+        tokio::time::timeout(PROBE_TIMEOUT, engine_ollama_list_models(app_handle.clone()))
+            .await
+            .ok()
+            .and_then(|r| r.ok());
+
+    let embedding_ready = match state.embedding_client() {
+        Some(client) => {
+            let provider = state.memory_config.lock().embedding_provider.clone();
+            match provider {
+                EmbeddingProvider::Auto | EmbeddingProvider::Ollama => {
+                    tokio::time::timeout(PROBE_TIMEOUT, client.check_model_available())
+                        .await
+                        .ok()
+                        .and_then(|r| r.ok())
+                        .unwrap_or(false)
+                }
+                // Remote embedding endpoints are assumed reachable once configured
+                _ => true,
+            }
+        }
+        None => false,
+    };
+
+    let mut skills_enabled = 0;
+    for def in skills::builtin_skills() {
+        let enabled = state
+            .store
+            .get_skill_enabled_state(&def.id)
+            .map_err(|e| e.to_string())?
+            .unwrap_or(def.default_enabled);
+        if enabled {
+            skills_enabled += 1;
+        }
+    }
+
+    Ok(OnboardingFacts {
+        providers_configured,
+        default_model,
+        ollama_running: ollama_models.is_some(),
+        ollama_models: ollama_models.map(|m| m.len()).unwrap_or(0),
+        embedding_ready,
+        skills_enabled,
+        wizard_complete: state
+            .store
+            .is_onboarding_complete()
+            .map_err(|e| e.to_string())?,
+    })
+}
+
+/// Every onboarding step with its completion state, plus the next
+/// recommended action.
+#[tauri::command]
+pub async fn engine_onboarding_status(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+) -> Result<OnboardingStatus, String> {
+    let facts = gather_facts(&app_handle, &state).await?;
+    Ok(onboarding::evaluate(&facts))
+}
+
+/// Re-check a single onboarding step.
+#[tauri::command]
+pub async fn engine_onboarding_step(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+    step: OnboardingStep,
+) -> Result<StepStatus, String> {
+    let facts = gather_facts(&app_handle, &state).await?;
+    Ok(onboarding::step_status(step, &facts))
+}
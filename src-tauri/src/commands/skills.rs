@@ -2,6 +2,7 @@
 // Credential encryption lives in engine/skills.rs.
 // TOML manifest commands (Phase F.1) + MCP server sharing (Phase F.3).
 
+use crate::commands::browser::check_url_against_policy;
 use crate::commands::state::EngineState;
 use crate::engine::channels;
 use crate::engine::mcp::types::{McpServerConfig, McpTransport};
@@ -17,6 +18,16 @@ pub fn engine_skills_list(
     skills::get_all_skill_status(&state.store).map_err(|e| e.to_string())
 }
 
+/// Consolidated status for the settings page: enabled state, credential
+/// presence (never values), instruction overrides, source and MCP state.
+#[tauri::command]
+pub async fn engine_skills_status(
+    state: State<'_, EngineState>,
+) -> Result<Vec<skills::SkillStatusSummary>, String> {
+    let connected = state.mcp_registry.lock().await.connected_ids();
+    skills::get_skills_status_summary(&state.store, &connected).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn engine_skill_set_enabled(
     state: State<'_, EngineState>,
@@ -125,6 +136,34 @@ pub fn engine_skill_set_instructions(
         .map_err(|e| e.to_string())
 }
 
+/// User override of the built-in tools a third-party skill may drive.
+/// `None` = no override (manifest declaration or safe default).
+#[tauri::command]
+pub fn engine_skill_get_permitted_tools(
+    state: State<'_, EngineState>,
+    skill_id: String,
+) -> Result<Option<Vec<String>>, String> {
+    Ok(skills::get_permitted_tools_override(
+        &state.store,
+        &skill_id,
+    ))
+}
+
+#[tauri::command]
+pub fn engine_skill_set_permitted_tools(
+    state: State<'_, EngineState>,
+    skill_id: String,
+    tools: Option<Vec<String>>,
+) -> Result<(), String> {
+    info!(
+        "[engine] Skill {} permitted tools → {:?}",
+        skill_id,
+        tools.as_deref()
+    );
+    skills::set_permitted_tools_override(&state.store, &skill_id, tools.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 // ── Community Skills (skills.sh) ───────────────────────────────────────
 
 #[tauri::command]
@@ -197,6 +236,10 @@ pub fn engine_community_skill_remove(
     state
         .store
         .remove_community_skill(&skill_id)
+        .map_err(|e| e.to_string())?;
+    state
+        .store
+        .skill_kv_clear(&skill_id)
         .map_err(|e| e.to_string())
 }
 
@@ -237,6 +280,8 @@ pub fn engine_community_skill_set_agents(
 /// Scan `~/.paw/skills/*/pawz-skill.toml` and return all valid entries.
 #[tauri::command]
 pub fn engine_toml_skills_scan() -> Result<Vec<skills::TomlSkillEntry>, String> {
+    // Manifests edited on disk by hand take effect on the next rescan
+    crate::engine::sessions::note_skills_changed();
     Ok(skills::scan_toml_skills())
 }
 
@@ -278,7 +323,7 @@ pub async fn engine_toml_skill_install(
             };
 
             let mut config = McpServerConfig {
-                id: format!("skill-{}", skill_id),
+                id: skills::skill_mcp_server_id(&skill_id),
                 name: manifest.skill.name.clone(),
                 transport,
                 command: mcp.command.clone(),
@@ -352,7 +397,7 @@ pub async fn engine_toml_skill_uninstall(
 ) -> Result<(), String> {
     info!("[engine] Uninstalling TOML skill '{}'", skill_id);
 
-    let mcp_id = format!("skill-{}", skill_id);
+    let mcp_id = skills::skill_mcp_server_id(&skill_id);
 
     // 1. Disconnect MCP server if running
     {
@@ -378,7 +423,12 @@ pub async fn engine_toml_skill_uninstall(
         }
     }
 
-    // 3. Remove skill files from disk
+    // 3. Drop the skill's persisted state
+    if let Err(e) = state.store.skill_kv_clear(&skill_id) {
+        log::warn!("[engine] Failed to clear state for '{}': {}", skill_id, e);
+    }
+
+    // 4. Remove skill files from disk
     skills::uninstall_toml_skill(&skill_id)
 }
 
@@ -445,6 +495,86 @@ pub async fn engine_pawzhub_install(
     engine_toml_skill_install(app_handle, state, skill_id, toml_content).await
 }
 
+/// Install a skill from a URL or GitHub source (`owner/repo[/dir]`).
+///
+/// Downloads and validates the manifest and its bundled files, checks every
+/// URL against the outbound network policy, then writes the bundle to the
+/// skills dir and rescans. Skills declaring credentials or an MCP server are
+/// only previewed (`installed: false`) until called again with `confirmed`.
+#[tauri::command]
+pub async fn engine_install_community_skill(
+    app_handle: tauri::AppHandle,
+    state: State<'_, EngineState>,
+    source: String,
+    confirmed: Option<bool>,
+) -> Result<skills::RemoteSkillInstall, String> {
+    let manifest_url = skills::resolve_manifest_url(&source)?;
+    info!("[engine] Remote skill install from {}", manifest_url);
+
+    let (allowed, domain) = check_url_against_policy(&state.store, &manifest_url);
+    if !allowed {
+        return Err(format!(
+            "Domain '{}' is blocked by the network policy",
+            domain
+        ));
+    }
+
+    let fetched = skills::fetch_remote_skill(&manifest_url).await?;
+    let manifest = &fetched.manifest;
+    let skill_id = manifest.skill.id.clone();
+
+    if let Some(mcp) = &manifest.mcp {
+        if !mcp.url.is_empty() {
+            let (allowed, domain) = check_url_against_policy(&state.store, &mcp.url);
+            if !allowed {
+                return Err(format!(
+                    "Skill MCP server domain '{}' is blocked by the network policy",
+                    domain
+                ));
+            }
+        }
+    }
+
+    if skills::builtin_skills().iter().any(|s| s.id == skill_id) {
+        return Err(format!(
+            "Skill id '{}' conflicts with a built-in skill",
+            skill_id
+        ));
+    }
+
+    let mut result = skills::RemoteSkillInstall::preview(&fetched);
+    if result.requires_confirmation && !confirmed.unwrap_or(false) {
+        info!(
+            "[engine] Skill '{}' declares credentials/MCP — awaiting user confirmation",
+            skill_id
+        );
+        return Ok(result);
+    }
+
+    skills::write_bundled_files(&skill_id, &fetched.files)?;
+    let path = engine_toml_skill_install(
+        app_handle,
+        state,
+        skill_id.clone(),
+        fetched.toml_content.clone(),
+    )
+    .await?;
+
+    let found = skills::scan_toml_skills()
+        .iter()
+        .any(|e| e.definition.id == skill_id);
+    if !found {
+        return Err(format!(
+            "Skill '{}' was written but failed to load on rescan",
+            skill_id
+        ));
+    }
+
+    result.installed = true;
+    result.path = Some(path);
+    Ok(result)
+}
+
 // ── Skill Outputs (Phase F.2 — Dashboard Widgets) ──────────────────
 
 /// List all skill outputs for dashboard widget rendering.
@@ -2,7 +2,9 @@
 // Exposes daily/weekly metrics and session metric history to the frontend.
 
 use crate::atoms::types::TelemetryMetricRow;
+use crate::engine::providers::{recent_rotation_events, ProviderRotationEvent};
 use crate::engine::sessions::telemetry::{TelemetryDailySummary, TelemetryModelBreakdown};
+use crate::engine::sessions::RunTraceSpan;
 use crate::engine::state::EngineState;
 use tauri::State;
 
@@ -66,3 +68,34 @@ pub fn engine_purge_old_metrics(
         .purge_metrics_before(&cutoff_date)
         .map_err(|e| e.to_string())
 }
+
+/// Get the ordered span trace (LLM calls, tool calls, outcome) of one run.
+#[tauri::command]
+pub fn engine_get_run_trace(
+    state: State<'_, EngineState>,
+    session_id: String,
+    run_id: String,
+) -> Result<Vec<RunTraceSpan>, String> {
+    state
+        .store
+        .get_run_trace(&session_id, &run_id)
+        .map_err(|e| e.to_string())
+}
+
+/// List run ids with a stored trace for a session, newest first.
+#[tauri::command]
+pub fn engine_list_run_traces(
+    state: State<'_, EngineState>,
+    session_id: String,
+) -> Result<Vec<String>, String> {
+    state
+        .store
+        .list_traced_runs(&session_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Recent provider rotations caused by sustained 529 overload, newest first.
+#[tauri::command]
+pub fn engine_provider_rotation_events(limit: Option<usize>) -> Vec<ProviderRotationEvent> {
+    recent_rotation_events(limit.unwrap_or(50))
+}
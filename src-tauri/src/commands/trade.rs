@@ -1,8 +1,10 @@
 // commands/trade.rs — Thin wrappers for trading history, policy, and position commands.
 
 use crate::commands::state::EngineState;
+use crate::engine::dex::{key_backup, wallets};
 use crate::engine::types::*;
 use log::info;
+use openpawz_core::engine::dex_allowance::{self, DexAllowance};
 use tauri::State;
 
 // ── Trading ────────────────────────────────────────────────────────────
@@ -47,6 +49,94 @@ pub fn engine_trading_policy_set(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn engine_dex_allowance_get(state: State<'_, EngineState>) -> Result<DexAllowance, String> {
+    Ok(dex_allowance::load_allowance(&state.store))
+}
+
+#[tauri::command]
+pub fn engine_dex_allowance_set(
+    state: State<'_, EngineState>,
+    allowance: DexAllowance,
+) -> Result<(), String> {
+    info!(
+        "[engine] Updating DEX allowance: tx={} ETH / ${}, daily={} ETH / ${}",
+        allowance.max_tx_eth,
+        allowance.max_tx_usd,
+        allowance.max_daily_eth,
+        allowance.max_daily_usd
+    );
+    dex_allowance::save_allowance(&state.store, &allowance).map_err(|e| e.to_string())
+}
+
+// ── DEX wallet backup ─────────────────────────────────────────────────
+
+/// DANGER: returns a DEX wallet's raw private key (`wallet` = named wallet
+/// id or label; omitted = default wallet). Requires the typed confirmation
+/// phrase, is rate-limited, and is recorded in the audit log.
+/// Never wire this into an agent tool.
+#[tauri::command]
+pub fn engine_dex_export_private_key(
+    state: State<'_, EngineState>,
+    confirmation_phrase: String,
+    wallet: Option<String>,
+) -> Result<String, String> {
+    let vault_key = crate::engine::skills::get_vault_key().map_err(|e| e.to_string())?;
+    key_backup::export_private_key(
+        &state.store,
+        &vault_key,
+        &key_backup::EXPORT_LIMITER,
+        &confirmation_phrase,
+        wallet.as_deref(),
+    )
+    .map(|key| key.to_string())
+    .map_err(|e| e.to_string())
+}
+
+/// Restore a DEX wallet from a private key — as the default wallet, or as
+/// the named wallet `wallet`. Returns the wallet address.
+#[tauri::command]
+pub fn engine_dex_import_private_key(
+    state: State<'_, EngineState>,
+    private_key: String,
+    replace_existing: Option<bool>,
+    wallet: Option<String>,
+) -> Result<String, String> {
+    let private_key = zeroize::Zeroizing::new(private_key);
+    let vault_key = crate::engine::skills::get_vault_key().map_err(|e| e.to_string())?;
+    key_backup::import_private_key(
+        &state.store,
+        &vault_key,
+        &private_key,
+        replace_existing.unwrap_or(false),
+        wallet.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// ── Named DEX wallets ─────────────────────────────────────────────────
+
+/// Named wallets plus the active wallet id (`null` = default wallet).
+#[tauri::command]
+pub fn engine_dex_wallets_list(state: State<'_, EngineState>) -> Result<serde_json::Value, String> {
+    let wallets = state.store.list_dex_wallets().map_err(|e| e.to_string())?;
+    Ok(serde_json::json!({
+        "active": wallets::active_wallet(&state.store),
+        "wallets": wallets,
+    }))
+}
+
+/// Select the wallet DEX tools use when no `wallet` arg is given.
+/// `None` or "default" selects the original wallet.
+#[tauri::command]
+pub fn engine_dex_set_active_wallet(
+    state: State<'_, EngineState>,
+    wallet: Option<String>,
+) -> Result<(), String> {
+    info!("[engine] Setting active DEX wallet: {:?}", wallet);
+    wallets::set_active_wallet(&state.store, wallet.as_deref()).map_err(|e| e.to_string())
+}
+
 // ── Positions (Stop-Loss / Take-Profit) ───────────────────────────────
 
 #[tauri::command]
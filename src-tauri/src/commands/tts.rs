@@ -266,50 +266,41 @@ async fn tts_openai(
 
 /// ElevenLabs TTS — calls api.elevenlabs.io/v1/text-to-speech/{voice_id}
 async fn tts_elevenlabs(api_key: &str, text: &str, config: &TtsConfig) -> Result<String, String> {
+    use crate::engine::speech::{self, SpeechConfig};
+
     let clean = strip_markdown(text);
     if clean.trim().is_empty() {
         return Err("No speakable text after stripping markdown".into());
     }
 
+    let speech_config = SpeechConfig {
+        base_url: speech::ELEVENLABS_BASE_URL.into(),
+        api_key: api_key.to_string(),
+        voice_id: config.voice.clone(),
+        model: config.elevenlabs_model.clone(),
+        max_chars: usize::MAX,
+    };
+    let voice_settings = serde_json::json!({
+        "stability": config.stability,
+        "similarity_boost": config.similarity_boost,
+        "speed": config.speed,
+    });
+
     // ElevenLabs has a 5000 char limit per request
     let chunks = chunk_text(&clean, 4800);
     let client = reqwest::Client::new();
     let mut all_audio = Vec::new();
 
     for chunk in &chunks {
-        let body = serde_json::json!({
-            "text": chunk,
-            "model_id": config.elevenlabs_model,
-            "voice_settings": {
-                "stability": config.stability,
-                "similarity_boost": config.similarity_boost,
-                "speed": config.speed,
-            }
-        });
-
-        let resp = client
-            .post(format!(
-                "https://api.elevenlabs.io/v1/text-to-speech/{}",
-                config.voice
-            ))
-            .header("xi-api-key", api_key)
-            .header("Accept", "audio/mpeg")
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("ElevenLabs TTS request failed: {}", e))?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(format!("ElevenLabs TTS error ({}): {}", status, body));
-        }
-
-        let bytes = resp
-            .bytes()
-            .await
-            .map_err(|e| format!("ElevenLabs TTS read error: {}", e))?;
-        all_audio.extend_from_slice(&bytes);
+        let audio = speech::synthesize(
+            &client,
+            &speech_config,
+            &config.voice,
+            chunk,
+            Some(voice_settings.clone()),
+        )
+        .await?;
+        all_audio.extend_from_slice(&audio);
     }
 
     info!(
@@ -377,56 +368,25 @@ async fn stt_openai_whisper(
     audio_base64: &str,
     mime_type: &str,
 ) -> Result<String, String> {
+    use crate::engine::speech::{self, SttEndpoint};
+
     let audio_bytes =
         base64::Engine::decode(&base64::engine::general_purpose::STANDARD, audio_base64)
             .map_err(|e| format!("Audio decode error: {}", e))?;
 
-    let ext = match mime_type {
-        "audio/webm" | "audio/webm;codecs=opus" => "webm",
-        "audio/ogg" | "audio/ogg;codecs=opus" => "ogg",
-        "audio/mp4" => "mp4",
-        "audio/wav" | "audio/wave" => "wav",
-        "audio/mpeg" | "audio/mp3" => "mp3",
-        _ => "webm",
+    let endpoint = SttEndpoint {
+        base_url: base_url.to_string(),
+        api_key: Some(api_key.to_string()),
+        model: speech::DEFAULT_STT_MODEL.into(),
     };
-
-    let file_part = reqwest::multipart::Part::bytes(audio_bytes)
-        .file_name(format!("audio.{}", ext))
-        .mime_str(mime_type)
-        .map_err(|e| format!("MIME error: {}", e))?;
-
-    let form = reqwest::multipart::Form::new()
-        .text("model", "whisper-1")
-        .text("language", "en")
-        .part("file", file_part);
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(format!(
-            "{}/audio/transcriptions",
-            base_url.trim_end_matches('/')
-        ))
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| format!("Whisper API request failed: {}", e))?;
-
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        return Err(format!("Whisper API error ({}): {}", status, body));
-    }
-
-    let result: serde_json::Value = resp
-        .json()
-        .await
-        .map_err(|e| format!("Whisper API JSON parse error: {}", e))?;
-
-    result["text"]
-        .as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Whisper API: no text in response".into())
+    speech::transcribe(
+        &reqwest::Client::new(),
+        &endpoint,
+        audio_bytes,
+        mime_type,
+        Some("en"),
+    )
+    .await
 }
 
 /// Google Cloud Speech-to-Text v1 (short audio, synchronous recognize)
@@ -497,7 +457,8 @@ async fn stt_google_cloud(
         Ok(transcript)
     }
 }
-fn strip_markdown(text: &str) -> String {
+/// Reduce markdown to speakable plain text.
+pub(crate) fn strip_markdown(text: &str) -> String {
     let mut out = text.to_string();
     // Remove code blocks
     while let Some(start) = out.find("```") {
@@ -0,0 +1,339 @@
+// Pawz Agent Engine — Portable Agent Bundles
+//
+// Export an agent as a shareable JSON bundle and import it elsewhere:
+// its name, persona, default model, skill selections and soul files, plus
+// the TOML manifests of any installed TOML skills it uses (on request).
+//
+// Secrets never travel. The bundle only lists which credentials the agent's
+// skills need; on import, the ones missing from the local vault are
+// reported so the UI can prompt for them.
+
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::sessions::agents::new_agent_id;
+use crate::engine::sessions::SessionStore;
+use crate::engine::skills::{self, TomlSkillEntry};
+use crate::engine::types::{AgentDefinition, ProjectAgent};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// A soul / persona file (SOUL.md, IDENTITY.md, …).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledFile {
+    pub file_name: String,
+    pub content: String,
+}
+
+/// A TOML skill shipped with the bundle (manifest only).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledSkill {
+    pub id: String,
+    pub manifest: String,
+}
+
+/// A credential one of the agent's skills needs. Never carries a value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequiredCredential {
+    pub skill_id: String,
+    pub key: String,
+    pub label: String,
+    pub required: bool,
+}
+
+/// Top-level bundle document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub name: String,
+    #[serde(default)]
+    pub persona: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<BundledFile>,
+    #[serde(default)]
+    pub toml_skills: Vec<BundledSkill>,
+    #[serde(default)]
+    pub credentials: Vec<RequiredCredential>,
+}
+
+/// Result of an import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentImportReport {
+    pub agent: AgentDefinition,
+    /// TOML skills installed from the bundle.
+    pub installed_skills: Vec<String>,
+    /// Bundled TOML skills left uninstalled because they ask for secrets,
+    /// run an MCP server or need elevated tools — install after review.
+    pub skills_needing_confirmation: Vec<String>,
+    /// Credentials to ask the user for (not yet in the local vault).
+    pub missing_credentials: Vec<RequiredCredential>,
+}
+
+// ── Export ─────────────────────────────────────────────────────────────
+
+/// Bundle `agent_id`. TOML skill manifests are included only on request.
+pub fn export_agent(
+    store: &SessionStore,
+    agent_id: &str,
+    include_toml_skills: bool,
+) -> EngineResult<AgentBundle> {
+    export_agent_with(
+        store,
+        agent_id,
+        include_toml_skills,
+        &skills::scan_toml_skills(),
+    )
+}
+
+fn export_agent_with(
+    store: &SessionStore,
+    agent_id: &str,
+    include_toml_skills: bool,
+    toml_skills: &[TomlSkillEntry],
+) -> EngineResult<AgentBundle> {
+    let agent = store
+        .get_agent_definition(agent_id)?
+        .ok_or_else(|| EngineError::Config(format!("Agent '{}' not found", agent_id)))?;
+
+    let files: Vec<BundledFile> = store
+        .list_agent_files(agent_id)?
+        .into_iter()
+        .map(|f| BundledFile {
+            file_name: f.file_name,
+            content: f.content,
+        })
+        .collect();
+
+    let builtins = skills::builtin_skills();
+    let mut credentials = Vec::new();
+    let mut bundled = Vec::new();
+    for skill_id in &agent.skills {
+        let fields = if let Some(def) = builtins.iter().find(|d| &d.id == skill_id) {
+            def.required_credentials.clone()
+        } else if let Some(entry) = toml_skills.iter().find(|e| &e.definition.id == skill_id) {
+            if include_toml_skills {
+                let path = std::path::Path::new(&entry.source_dir).join("pawz-skill.toml");
+                match std::fs::read_to_string(&path) {
+                    Ok(manifest) => bundled.push(BundledSkill {
+                        id: skill_id.clone(),
+                        manifest,
+                    }),
+                    Err(e) => warn!("[bundle] Can't read {}: {}", path.display(), e),
+                }
+            }
+            entry.definition.required_credentials.clone()
+        } else {
+            vec![]
+        };
+        credentials.extend(fields.into_iter().map(|f| RequiredCredential {
+            skill_id: skill_id.clone(),
+            key: f.key,
+            label: f.label,
+            required: f.required,
+        }));
+    }
+
+    info!(
+        "[bundle] Exported agent {} ({} files, {} TOML skills)",
+        agent_id,
+        files.len(),
+        bundled.len()
+    );
+    Ok(AgentBundle {
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        name: agent.name,
+        persona: agent.persona,
+        model: agent.model,
+        skills: agent.skills,
+        files,
+        toml_skills: bundled,
+        credentials,
+    })
+}
+
+// ── Import ─────────────────────────────────────────────────────────────
+
+/// Create a new agent from `bundle`, installing the TOML skills it ships
+/// that aren't installed yet. Skills that need consent (see
+/// `skills::toml::requires_confirmation`) are installed only when
+/// `confirm_elevated_skills` is set.
+pub fn import_agent(
+    store: &SessionStore,
+    bundle: &AgentBundle,
+    confirm_elevated_skills: bool,
+) -> EngineResult<AgentImportReport> {
+    if bundle.version != BUNDLE_VERSION {
+        return Err(EngineError::Config(format!(
+            "Unsupported agent bundle version {} (expected {})",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+    if bundle.name.trim().is_empty() {
+        return Err(EngineError::Config("Agent bundle has no name".into()));
+    }
+
+    let mut installed_skills = Vec::new();
+    let mut skills_needing_confirmation = Vec::new();
+    if !bundle.toml_skills.is_empty() {
+        let installed = skills::scan_toml_skills();
+        for skill in &bundle.toml_skills {
+            if installed.iter().any(|e| e.definition.id == skill.id) {
+                continue;
+            }
+            let manifest = skills::parse_manifest(&skill.manifest)?;
+            if skills::toml::requires_confirmation(&manifest) && !confirm_elevated_skills {
+                skills_needing_confirmation.push(skill.id.clone());
+                continue;
+            }
+            skills::install_toml_skill(&skill.id, &skill.manifest)?;
+            installed_skills.push(skill.id.clone());
+        }
+    }
+
+    let id = new_agent_id(&bundle.name);
+    store.upsert_agent_definition(&AgentDefinition {
+        id: id.clone(),
+        name: bundle.name.trim().to_string(),
+        persona: bundle.persona.clone(),
+        model: bundle.model.clone(),
+        skills: bundle.skills.clone(),
+        autonomy: Default::default(),
+        created_at: String::new(),
+        updated_at: String::new(),
+    })?;
+    for file in &bundle.files {
+        store.set_agent_file(&id, &file.file_name, &file.content)?;
+    }
+    store.add_project_agent(
+        "_standalone",
+        &ProjectAgent {
+            agent_id: id.clone(),
+            role: "assistant".into(),
+            specialty: "general".into(),
+            status: "idle".into(),
+            current_task: None,
+            model: bundle.model.clone(),
+            system_prompt: bundle.persona.clone(),
+            capabilities: vec![],
+        },
+    )?;
+
+    let missing_credentials = bundle
+        .credentials
+        .iter()
+        .filter(|c| {
+            store
+                .get_skill_credential(&c.skill_id, &c.key)
+                .ok()
+                .flatten()
+                .is_none()
+        })
+        .cloned()
+        .collect();
+
+    let agent = store
+        .get_agent_definition(&id)?
+        .ok_or_else(|| EngineError::Other(format!("Imported agent {} was not saved", id)))?;
+    info!(
+        "[bundle] Imported agent '{}' as {} ({} TOML skills installed)",
+        agent.name,
+        id,
+        installed_skills.len()
+    );
+    Ok(AgentImportReport {
+        agent,
+        installed_skills,
+        skills_needing_confirmation,
+        missing_credentials,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema_for_testing;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        schema_for_testing(&conn);
+        let store = SessionStore::from_connection(conn);
+        store.init_skill_tables().unwrap();
+        store
+    }
+
+    fn credentialed_skill() -> (String, String) {
+        let def = skills::builtin_skills()
+            .into_iter()
+            .find(|d| !d.required_credentials.is_empty())
+            .expect("a built-in skill with credentials");
+        let key = def.required_credentials[0].key.clone();
+        (def.id, key)
+    }
+
+    #[test]
+    fn round_trip_preserves_config_without_secrets() {
+        let store = test_store();
+        let (skill_id, key) = credentialed_skill();
+        store
+            .upsert_agent_definition(&AgentDefinition {
+                id: "analyst".into(),
+                name: "Analyst".into(),
+                persona: Some("You read balance sheets.".into()),
+                model: Some("gpt-4o".into()),
+                skills: vec![skill_id.clone()],
+                autonomy: Default::default(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+        store
+            .set_agent_file("analyst", "SOUL.md", "Skeptical, precise.")
+            .unwrap();
+        store
+            .set_skill_credential(&skill_id, &key, "super-secret-value")
+            .unwrap();
+
+        let bundle = export_agent_with(&store, "analyst", true, &[]).unwrap();
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("super-secret-value"));
+        assert!(bundle
+            .credentials
+            .iter()
+            .any(|c| c.skill_id == skill_id && c.key == key));
+
+        // Import into a fresh install — the credential has to be asked for
+        let other = test_store();
+        let parsed: AgentBundle = serde_json::from_str(&json).unwrap();
+        let report = import_agent(&other, &parsed, false).unwrap();
+        let agent = report.agent;
+        assert!(agent.id.starts_with("agent-analyst-"));
+        assert_eq!(agent.name, "Analyst");
+        assert_eq!(agent.persona.as_deref(), Some("You read balance sheets."));
+        assert_eq!(agent.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(agent.skills, vec![skill_id.clone()]);
+        let soul = other.get_agent_file(&agent.id, "SOUL.md").unwrap().unwrap();
+        assert_eq!(soul.content, "Skeptical, precise.");
+        assert!(report
+            .missing_credentials
+            .iter()
+            .any(|c| c.skill_id == skill_id && c.key == key));
+        assert!(report.installed_skills.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_bundle_version() {
+        let store = test_store();
+        let mut bundle = export_agent_with(&store, "default", false, &[]).unwrap();
+        bundle.version = BUNDLE_VERSION + 1;
+        let err = import_agent(&store, &bundle, false).unwrap_err();
+        assert!(err.to_string().contains("Unsupported agent bundle version"));
+    }
+}
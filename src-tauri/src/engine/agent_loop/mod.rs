@@ -1,27 +1,40 @@
-// Paw Agent Engine — Agentic Loop
-// The core orchestration loop: send to model → tool calls → execute → repeat.
-// This is the core agent loop that drives Pawz AI interactions.
+// Paw Agent Engine — Agentic Loop (desktop host)
+// The loop itself (model → tool calls → execute → repeat) lives in
+// `openpawz_core::engine::agent_loop` and is shared with the headless
+// runner. This module runs it inside the app: events stream to the frontend,
+// tools run with the full desktop toolset, approvals wait on the UI, and
+// spend is tracked by the engine's DailyTokenTracker.
 
 pub(crate) mod helpers;
 mod trading;
 
 use crate::atoms::error::EngineResult;
+use crate::engine::binary_ipc::{BatchConfig, EventBatcher};
 use crate::engine::providers::AnyProvider;
-use crate::engine::state::{DailyTokenTracker, PendingApprovals};
+use crate::engine::sessions::SessionStore;
+use crate::engine::speculative::{SpeculationConfig, SpeculationStats};
+use crate::engine::state::{DailyTokenTracker, EngineState, PendingApprovals, YieldSignal};
 use crate::engine::telemetry::{integration as telem, RunCollector};
 use crate::engine::tools;
 use crate::engine::types::*;
+use async_trait::async_trait;
 use log::{info, warn};
-use openpawz_core::engine::tool_metadata::{self, ToolTier};
-use std::time::{Duration, Instant};
+use openpawz_core::engine::agent_loop::{
+    run_turn, ApprovalRequest, PlanOutcome, RoundUsage, TurnHost, TurnParams, TurnStats,
+};
+use openpawz_core::engine::cancel::{run_cancellable, CancelToken};
+use openpawz_core::engine::i18n;
+use openpawz_core::engine::partial_reply::PartialReply;
+use parking_lot::Mutex;
+use std::time::Duration;
 use tauri::{Emitter, Manager};
-use trading::check_trading_auto_approve;
+use trading::{check_trading_auto_approve, quote_unit_price_usd};
 
 /// Run a complete agent turn: send messages to the model, execute tool calls,
 /// and repeat until the model produces a final text response or max rounds hit.
 ///
 /// Emits `engine-event` Tauri events for real-time streaming to the frontend.
-#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
 pub async fn run_agent_turn(
     app_handle: &tauri::AppHandle,
     provider: &AnyProvider,
@@ -40,1068 +53,478 @@ pub async fn run_agent_turn(
     thinking_level: Option<&str>,
     auto_approve_all: bool,
     user_approved_tools: &[String],
-    yield_signal: Option<&crate::engine::state::YieldSignal>,
+    yield_signal: Option<&YieldSignal>,
+    cancel: Option<&CancelToken>,
+    partial_reply: Option<&PartialReply>,
 ) -> EngineResult<String> {
-    let mut round = 0;
-    let mut final_text = String::new();
-    let mut last_input_tokens: u64 = 0; // Only the LAST round's input (= actual context size)
-    let mut total_output_tokens: u64 = 0; // Sum of all rounds' output tokens
-    let mut total_cache_read: u64 = 0; // Sum of all rounds' cache read tokens
-    let mut total_cache_create: u64 = 0; // Sum of all rounds' cache creation tokens
+    let state = app_handle.try_state::<EngineState>();
+    // Reasoning is always kept (see PartialReply); show_reasoning only
+    // decides whether it streams to the UI.
+    let (max_tool_iterations, show_reasoning, agent_budget_usd, context_window_tokens) = state
+        .as_ref()
+        .map(|es| {
+            let cfg = es.config.lock();
+            (
+                cfg.max_tool_iterations,
+                cfg.show_reasoning,
+                // Per-agent daily cap, enforced alongside the global daily_budget_usd
+                cfg.agent_budget_usd(agent_id),
+                cfg.context_window_tokens,
+            )
+        })
+        .unwrap_or((0, true, 0.0, 32_000));
+    let speculation_config = state
+        .as_ref()
+        .map(|es| es.speculation_config.clone())
+        .unwrap_or_default();
 
     // ── Telemetry: per-turn collector (Canvas Phase 5) ────────────────
-    let mut telem_collector = RunCollector::new(session_id, run_id, model);
-    let telem_root_id = telem_collector.root_span("agent_turn");
-    let turn_start = Instant::now();
-    let mut tool_duration_total_ms: u64 = 0;
-    let mut tool_call_count: u32 = 0;
-
-    // Circuit breaker: track consecutive failures per tool name.
-    // After MAX_CONSECUTIVE_TOOL_FAILS of the same tool, inject a system nudge.
-    // After HARD_STOP_TOOL_FAILS, block further execution of that tool entirely.
-    let mut tool_fail_counter: std::collections::HashMap<String, u32> =
-        std::collections::HashMap::new();
-    const MAX_CONSECUTIVE_TOOL_FAILS: u32 = 3;
-    const HARD_STOP_TOOL_FAILS: u32 = 5;
+    let mut telemetry = RunCollector::new(session_id, run_id, model);
+    let telemetry_root = telemetry.root_span("agent_turn");
 
-    // Repetition detector: track the tool-call "signature" (hashed tool names
-    // + args) for each round.  If the same signature appears consecutively
-    // MAX_REPEATED_SIGNATURES times, the model is stuck in a tool-calling loop
-    // (common after model/context changes mid-conversation).
-    let mut round_signatures: Vec<u64> = Vec::new();
-    const MAX_REPEATED_SIGNATURES: usize = 3;
-
-    // ── Phase 3: Binary IPC delta batcher ─────────────────────────────
-    // Batches streaming text deltas before emitting IPC events to the frontend.
-    // Reduces per-token IPC overhead for fast models (50-100 tokens/sec).
-    let mut delta_batcher = crate::engine::binary_ipc::EventBatcher::new(
+    let host = TauriHost {
+        app_handle,
         session_id,
         run_id,
-        crate::engine::binary_ipc::BatchConfig::default(),
-    );
-
-    // ── Phase 4: Speculative tool execution tracking ──────────────────
-    // Track the previously-executed tool name so the speculative engine can
-    // record A→B transitions and predict the next tool call.
-    let mut previous_tool: Option<String> = None;
-    // Load speculation config and stats tracker from engine state
-    let speculation_config = app_handle
-        .try_state::<crate::engine::state::EngineState>()
-        .map(|es| es.speculation_config.clone())
-        .unwrap_or_default();
-    let mut speculation_stats = crate::engine::speculative::SpeculationStats::default();
-
-    loop {
-        round += 1;
-
-        // ── Yield check: if a new user message was queued, wrap up gracefully ─
-        // VS Code pattern: when yield is requested, the agent stops its loop
-        // and returns whatever it has so far.  The queued message will be
-        // processed next by the request queue handler.
-        if let Some(ys) = yield_signal {
-            if ys.is_yield_requested() {
-                warn!(
-                    "[engine] Yield requested — wrapping up agent turn at round {}",
-                    round
-                );
-                if final_text.is_empty() {
-                    final_text = "I was wrapping up to handle your new message. \
-                        My previous work may be incomplete."
-                        .to_string();
-                }
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::Complete {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        text: final_text.clone(),
-                        tool_calls_count: 0,
-                        usage: None,
-                        model: None,
-                        total_rounds: Some(round),
-                        max_rounds: Some(max_rounds),
-                    },
-                );
-                return Ok(final_text);
-            }
-        }
-
-        if round > max_rounds {
-            warn!(
-                "[engine] Max tool rounds ({}) reached, stopping",
-                max_rounds
-            );
-            if final_text.is_empty() {
-                final_text = format!(
-                    "I completed {} tool-call rounds but ran out of steps before I could \
-                    write a final summary.  You can continue the conversation or increase \
-                    the max tool rounds in Settings → Engine (currently {}).",
-                    max_rounds, max_rounds
-                );
-                // Emit the fallback text so the frontend shows *something*
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::Complete {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        text: final_text.clone(),
-                        tool_calls_count: 0,
-                        usage: None,
-                        model: None,
-                        total_rounds: Some(round),
-                        max_rounds: Some(max_rounds),
-                    },
-                );
-            }
-            return Ok(final_text);
-        }
-
-        info!(
-            "[engine] Agent round {}/{} session={} run={}",
-            round, max_rounds, session_id, run_id
-        );
+        model,
+        agent_id,
+        pending_approvals,
+        tool_timeout_secs,
+        daily_budget_usd,
+        agent_budget_usd,
+        daily_tokens,
+        yield_signal,
+        telemetry: Mutex::new(telemetry),
+        telemetry_root,
+        // ── Phase 3: Binary IPC delta batcher ─────────────────────────
+        // Batches streaming text deltas before emitting IPC events to the
+        // frontend. Reduces per-token IPC overhead for fast models.
+        delta_batcher: Mutex::new(EventBatcher::new(
+            session_id,
+            run_id,
+            BatchConfig::default(),
+        )),
+        speculation_config,
+        speculation: Mutex::new(Speculation::default()),
+    };
+    let params = TurnParams {
+        model,
+        session_id,
+        run_id,
+        agent_id,
+        max_rounds,
+        temperature,
+        thinking_level,
+        auto_approve_all,
+        user_approved_tools,
+        max_tool_iterations,
+        show_reasoning,
+        context_window_tokens,
+    };
+    let outcome = run_turn(
+        &host,
+        provider,
+        messages,
+        tools,
+        &params,
+        cancel,
+        partial_reply,
+    )
+    .await?;
+    Ok(outcome.text)
+}
 
-        // ── Budget check: stop before making the API call if over daily limit
-        if daily_budget_usd > 0.0 {
-            if let Some(tracker) = daily_tokens {
-                if let Some(spent) = tracker.check_budget(daily_budget_usd) {
-                    let msg = format!(
-                        "Daily budget exceeded (${:.2} spent, ${:.2} limit). Stopping to prevent further costs. \
-                        You can adjust your daily budget in Settings → Engine.",
-                        spent, daily_budget_usd
-                    );
-                    warn!("[engine] {}", msg);
-                    let _ = app_handle.emit(
-                        "engine-event",
-                        EngineEvent::Error {
-                            session_id: session_id.to_string(),
-                            run_id: run_id.to_string(),
-                            message: msg.clone(),
-                        },
-                    );
-                    return Err(msg.into());
-                }
-            }
-        }
+// ── Phase 4: Speculative tool execution tracking ──────────────────────
+/// The previously-executed tool, so the speculative engine can record A→B
+/// transitions and predict the next tool call.
+#[derive(Default)]
+struct Speculation {
+    previous_tool: Option<String>,
+    stats: SpeculationStats,
+}
 
-        // ── 1. Call the AI model ──────────────────────────────────────
-        let chunks = provider
-            .chat_stream(messages, tools, model, temperature, thinking_level)
-            .await?;
+/// Runs the shared agent loop against the Tauri app.
+struct TauriHost<'a> {
+    app_handle: &'a tauri::AppHandle,
+    session_id: &'a str,
+    run_id: &'a str,
+    model: &'a str,
+    agent_id: &'a str,
+    pending_approvals: &'a PendingApprovals,
+    tool_timeout_secs: u64,
+    daily_budget_usd: f64,
+    agent_budget_usd: f64,
+    daily_tokens: Option<&'a DailyTokenTracker>,
+    yield_signal: Option<&'a YieldSignal>,
+    telemetry: Mutex<RunCollector>,
+    telemetry_root: String,
+    delta_batcher: Mutex<EventBatcher>,
+    speculation_config: SpeculationConfig,
+    speculation: Mutex<Speculation>,
+}
 
-        // ── 2. Assemble the response from chunks ──────────────────────
-        let mut text_accum = String::new();
-        let mut tool_call_map: std::collections::HashMap<
-            usize,
-            (String, String, String, Option<String>, Vec<ThoughtPart>),
-        > = std::collections::HashMap::new();
-        // (id, name, arguments, thought_signature, thought_parts)
-        let mut has_tool_calls = false;
-        let mut _finished = false;
+impl TauriHost<'_> {
+    fn emit_delta(&self, text: String) {
+        self.emit(EngineEvent::Delta {
+            session_id: self.session_id.to_string(),
+            run_id: self.run_id.to_string(),
+            text,
+        });
+    }
 
-        // Extract the confirmed model name from the API response
-        let confirmed_model: Option<String> = chunks.iter().find_map(|c| c.model.clone());
+    fn emit_error(&self, message: String) {
+        self.emit(EngineEvent::Error {
+            session_id: self.session_id.to_string(),
+            run_id: self.run_id.to_string(),
+            message,
+        });
+    }
+}
 
-        for chunk in &chunks {
-            // Accumulate text deltas
-            if let Some(dt) = &chunk.delta_text {
-                text_accum.push_str(dt);
+#[async_trait]
+impl TurnHost for TauriHost<'_> {
+    fn store(&self) -> Option<&SessionStore> {
+        self.app_handle
+            .try_state::<EngineState>()
+            .map(|es| &es.inner().store)
+    }
 
-                // Phase 3: Batch deltas before emitting to reduce IPC overhead.
-                // push_delta returns Some(batch) when flush is needed.
-                if let Some(batch) = delta_batcher.push_delta(dt) {
-                    let _ = app_handle.emit(
-                        "engine-event",
-                        EngineEvent::Delta {
-                            session_id: session_id.to_string(),
-                            run_id: run_id.to_string(),
-                            text: batch.combined_text,
-                        },
-                    );
-                }
-            }
+    fn emit(&self, event: EngineEvent) {
+        let _ = self.app_handle.emit("engine-event", event);
+    }
 
-            // Emit thinking/reasoning text to frontend
-            if let Some(tt) = &chunk.thinking_text {
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::ThinkingDelta {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        text: tt.clone(),
-                    },
-                );
-            }
+    fn stream_text(&self, text: &str) {
+        // push_delta returns Some(batch) when a flush is due
+        let batch = self.delta_batcher.lock().push_delta(text);
+        if let Some(batch) = batch {
+            self.emit_delta(batch.combined_text);
+        }
+    }
 
-            // Accumulate tool call deltas
-            for tc_delta in &chunk.tool_calls {
-                has_tool_calls = true;
-                let entry = tool_call_map.entry(tc_delta.index).or_insert_with(|| {
-                    (
-                        String::new(),
-                        String::new(),
-                        String::new(),
-                        None,
-                        Vec::new(),
-                    )
-                });
+    fn yield_requested(&self) -> bool {
+        self.yield_signal.is_some_and(|ys| ys.is_yield_requested())
+    }
 
-                if let Some(id) = &tc_delta.id {
-                    entry.0.push_str(id);
-                }
-                if let Some(name) = &tc_delta.function_name {
-                    entry.1.push_str(name);
-                }
-                if let Some(args_delta) = &tc_delta.arguments_delta {
-                    entry.2.push_str(args_delta);
-                }
-                if tc_delta.thought_signature.is_some() {
-                    entry.3 = tc_delta.thought_signature.clone();
-                }
+    /// Over the global or this agent's daily limit — whichever is hit first.
+    fn budget_exceeded(&self) -> Option<String> {
+        let tracker = self.daily_tokens?;
+        let exceeded = if self.daily_budget_usd > 0.0 {
+            tracker.check_budget(self.daily_budget_usd).map(|spent| {
+                i18n::t(
+                    "budget.daily_exceeded",
+                    &[
+                        ("spent", &format!("{:.2}", spent)),
+                        ("limit", &format!("{:.2}", self.daily_budget_usd)),
+                    ],
+                )
+            })
+        } else {
+            None
+        };
+        exceeded.or_else(|| {
+            if self.agent_budget_usd <= 0.0 {
+                return None;
             }
-
-            // Collect thought parts from chunks that have tool calls
-            if !chunk.thought_parts.is_empty() {
-                // Attach to the first tool call index
-                let first_idx = chunk.tool_calls.first().map(|tc| tc.index).unwrap_or(0);
-                let entry = tool_call_map.entry(first_idx).or_insert_with(|| {
-                    (
-                        String::new(),
-                        String::new(),
-                        String::new(),
-                        None,
-                        Vec::new(),
+            tracker
+                .check_agent_budget(self.agent_id, self.agent_budget_usd)
+                .map(|spent| {
+                    i18n::t(
+                        "budget.agent_exceeded",
+                        &[
+                            ("agent", &self.agent_id),
+                            ("spent", &format!("{:.2}", spent)),
+                            ("limit", &format!("{:.2}", self.agent_budget_usd)),
+                        ],
                     )
-                });
-                entry.4.extend(chunk.thought_parts.clone());
-            }
-
-            if let Some(reason) = &chunk.finish_reason {
-                // Normalize across providers: OpenAI="stop", Anthropic="end_turn",
-                // Google="STOP", others may return "complete"/"finished".
-                let r = reason.to_lowercase();
-                if r == "stop" || r == "end_turn" || r == "complete" || r == "finished" {
-                    _finished = true;
-                }
-            }
+                })
+        })
+    }
 
-            // Track token usage — input tokens reflect the full context sent
-            // each round, so we keep only the LAST round's input tokens (not a sum).
-            // Output tokens are truly incremental, so we sum those across rounds.
-            if let Some(usage) = &chunk.usage {
-                last_input_tokens = usage.input_tokens; // overwrite, not accumulate
-                total_output_tokens += usage.output_tokens;
+    fn record_usage(&self, round: u32, usage: &RoundUsage) {
+        let Some(tracker) = self.daily_tokens else {
+            return;
+        };
+        let round_cost = tracker.record(
+            self.agent_id,
+            self.model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_creation_tokens,
+        );
+        // Persist so the counters survive a restart within the window
+        if let Some(store) = self.store() {
+            if let Err(e) = store.add_daily_spend(
+                &tracker.window_start(),
+                self.agent_id,
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cache_read_tokens,
+                usage.cache_creation_tokens,
+                round_cost,
+            ) {
+                warn!("[engine] Failed to persist daily spend: {}", e);
             }
         }
-
-        // Gather cache token usage from all chunks for accurate cost tracking
-        let round_cache_read: u64 = chunks
-            .iter()
-            .filter_map(|c| c.usage.as_ref())
-            .map(|u| u.cache_read_tokens)
-            .sum();
-        let round_cache_create: u64 = chunks
-            .iter()
-            .filter_map(|c| c.usage.as_ref())
-            .map(|u| u.cache_creation_tokens)
-            .sum();
-
-        // Accumulate cache totals across rounds
-        total_cache_read += round_cache_read;
-        total_cache_create += round_cache_create;
-
-        // ── Record this round's token usage against the daily budget tracker
-        if let Some(tracker) = daily_tokens {
-            let round_input = last_input_tokens;
-            let round_output = chunks
-                .iter()
-                .filter_map(|c| c.usage.as_ref())
-                .map(|u| u.output_tokens)
-                .sum::<u64>();
-            tracker.record(
-                model,
-                round_input,
-                round_output,
-                round_cache_read,
-                round_cache_create,
+        let (total_in, total_out, est_usd) = tracker.estimated_spend_usd();
+        if round == 1 || round % 5 == 0 {
+            info!(
+                "[engine] Daily spend: ~${:.2} ({} in / {} out tokens today, cache read={} create={})",
+                est_usd, total_in, total_out, usage.cache_read_tokens, usage.cache_creation_tokens
             );
-            let (total_in, total_out, est_usd) = tracker.estimated_spend_usd();
-            if round == 1 || round % 5 == 0 {
-                info!("[engine] Daily spend: ~${:.2} ({} in / {} out tokens today, cache read={} create={})",
-                    est_usd, total_in, total_out, round_cache_read, round_cache_create);
-            }
-
-            // ── Budget warnings: emit events at 50%, 75%, 90% thresholds
-            if daily_budget_usd > 0.0 {
-                if let Some(pct) = tracker.check_budget_warning(daily_budget_usd) {
-                    let msg = format!(
-                        "Budget warning: {}% of daily budget used (${:.2} of ${:.2})",
-                        pct, est_usd, daily_budget_usd
-                    );
-                    warn!("[engine] {}", msg);
-                    let _ = app_handle.emit(
-                        "engine-event",
-                        EngineEvent::Error {
-                            session_id: session_id.to_string(),
-                            run_id: run_id.to_string(),
-                            message: msg,
-                        },
-                    );
-                }
-            }
         }
 
-        // ── 3. If no tool calls, we're done ──────────────────────────
-        if !has_tool_calls || tool_call_map.is_empty() {
-            final_text = text_accum.clone();
-
-            // Retry on malformed tool calls (Gemini JSON issues)
-            // Skip retry when constrained decoding is active — the parse failure
-            // indicates a deeper issue, not a model formatting mistake.
-            let constraint_level =
-                crate::engine::constrained::detect_constraints(provider.kind(), model).level;
-            let constrained_active =
-                constraint_level != crate::engine::constrained::ConstraintLevel::None;
-            if !constrained_active
-                && helpers::handle_malformed_tool_call(&final_text, messages, round, max_rounds)
-            {
-                continue;
-            }
-
-            // Retry on empty response (nudge with user recap)
-            if helpers::handle_empty_response(&final_text, messages, round, max_rounds) {
-                continue;
-            }
-
-            // Persistent empty → fallback message
-            if final_text.is_empty() {
-                warn!(
-                    "[engine] Model returned empty response (0 chars, 0 tool calls) at round {}",
-                    round
-                );
-                final_text = helpers::empty_response_fallback();
-            }
-
-            // ── Grounding check: verify response addresses user's message ──
-            // Run only once (round > 1 means we already retried) and only when
-            // the model produced substantive text (not a fallback).
-            if round == 1 {
-                if let Some(correction) =
-                    crate::engine::chat::grounding_check(messages, &final_text)
-                {
-                    // Push the ungrounded response as assistant message so the
-                    // model sees what it said, then inject the correction.
-                    messages.push(Message {
-                        role: Role::Assistant,
-                        content: MessageContent::Text(text_accum),
-                        tool_calls: None,
-                        tool_call_id: None,
-                        name: None,
-                    });
-                    messages.push(Message {
-                        role: Role::System,
-                        content: MessageContent::Text(correction),
-                        tool_calls: None,
-                        tool_call_id: None,
-                        name: None,
-                    });
-                    info!(
-                        "[engine] Grounding check failed at round {} — retrying with correction",
-                        round
-                    );
-                    continue;
-                }
-            }
-
-            // Add assistant message to history
-            messages.push(Message {
-                role: Role::Assistant,
-                content: MessageContent::Text(text_accum),
-                tool_calls: None,
-                tool_call_id: None,
-                name: None,
-            });
-
-            // Emit completion event
-            let usage = if last_input_tokens > 0 || total_output_tokens > 0 {
-                Some(TokenUsage {
-                    input_tokens: last_input_tokens,
-                    output_tokens: total_output_tokens,
-                    total_tokens: last_input_tokens + total_output_tokens,
-                    cache_creation_tokens: round_cache_create,
-                    cache_read_tokens: round_cache_read,
-                })
-            } else {
-                None
-            };
-            let _ = app_handle.emit(
-                "engine-event",
-                EngineEvent::Complete {
-                    session_id: session_id.to_string(),
-                    run_id: run_id.to_string(),
-                    text: final_text.clone(),
-                    tool_calls_count: 0,
-                    usage,
-                    model: confirmed_model.clone(),
-                    total_rounds: Some(round),
-                    max_rounds: Some(max_rounds),
-                },
-            );
-
-            // ── Telemetry flush (Canvas Phase 5) ──────────────────────
-            {
-                let total_ms = turn_start.elapsed().as_millis() as u64;
-                let llm_ms = total_ms.saturating_sub(tool_duration_total_ms);
-                let mut summary = telem_collector.build_summary(
-                    last_input_tokens,
-                    total_output_tokens,
-                    round,
-                    tool_call_count,
-                );
-                summary.total_duration_ms = total_ms;
-                summary.llm_duration_ms = llm_ms;
-                summary.tool_duration_ms = tool_duration_total_ms;
-                summary.cost_usd = crate::engine::types::estimate_cost_usd(
-                    model,
-                    last_input_tokens,
-                    total_output_tokens,
-                    total_cache_read,
-                    total_cache_create,
-                );
-
-                if let Some(es) = app_handle.try_state::<crate::engine::state::EngineState>() {
-                    telem::persist_summary(&es.store, &summary);
-                }
-                telem::emit_summary(app_handle, &summary);
-            }
-
-            // ── Phase 3: Flush remaining batched deltas ───────────────
-            if let Some(batch) = delta_batcher.flush() {
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::Delta {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        text: batch.combined_text,
-                    },
+        // ── Budget warnings: emit events at 50%, 75%, 90% thresholds
+        if self.daily_budget_usd > 0.0 {
+            if let Some(pct) = tracker.check_budget_warning(self.daily_budget_usd) {
+                let msg = format!(
+                    "Budget warning: {}% of daily budget used (${:.2} of ${:.2})",
+                    pct, est_usd, self.daily_budget_usd
                 );
+                warn!("[engine] {}", msg);
+                self.emit_error(msg);
             }
-            // Log binary IPC batcher stats for the session
-            crate::engine::binary_ipc::log_session_stats(&delta_batcher.stats(), 0);
-
-            // ── Phase 4: Log speculation stats for the session ────────
-            crate::engine::speculative::log_session_speculation_stats(&speculation_stats);
-
-            return Ok(final_text);
         }
+    }
 
-        // ── 4. Process tool calls ─────────────────────────────────────
-        let mut tool_calls: Vec<ToolCall> = Vec::new();
-        let mut sorted_indices: Vec<usize> = tool_call_map.keys().cloned().collect();
-        sorted_indices.sort();
-
-        for idx in sorted_indices {
-            let entry = match tool_call_map.get(&idx) {
-                Some(e) => e,
-                None => continue, // skip missing indices (shouldn't happen)
-            };
-            let (id, name, arguments, thought_sig, thoughts) = entry;
-
-            info!(
-                "[engine] tool_call_map[{}]: id={:?} name={:?} args_len={}",
-                idx,
-                id,
-                name,
-                arguments.len()
-            );
+    fn grounding_check(&self, messages: &[Message], reply: &str) -> Option<String> {
+        crate::engine::chat::grounding_check(messages, reply)
+    }
 
-            // Generate ID if provider didn't supply one, or if the
-            // accumulated ID is suspiciously short (SSE chunk corruption).
-            let call_id = if id.is_empty() || (id.len() < 8 && !id.starts_with("call_")) {
-                if !id.is_empty() {
-                    warn!(
-                        "[engine] Replacing suspicious tool_call id '{}' (len={}) with generated UUID",
-                        id, id.len()
-                    );
-                }
-                format!("call_{}", uuid::Uuid::new_v4())
-            } else {
-                id.clone()
-            };
+    async fn quote_unit_price_usd(&self, tool_name: &str, args: &str) -> Option<f64> {
+        quote_unit_price_usd(tool_name, args, self.app_handle).await
+    }
 
-            tool_calls.push(ToolCall {
-                id: call_id.clone(),
-                call_type: "function".into(),
-                function: FunctionCall {
-                    name: name.clone(),
-                    arguments: arguments.clone(),
-                },
-                thought_signature: thought_sig.clone(),
-                thought_parts: thoughts.clone(),
-            });
-        }
+    fn trading_auto_approve(&self, tool_name: &str, args: &str) -> bool {
+        check_trading_auto_approve(tool_name, args, self.app_handle)
+    }
 
-        // Add assistant message with tool calls to history
-        messages.push(Message {
-            role: Role::Assistant,
-            content: MessageContent::Text(text_accum),
-            tool_calls: Some(tool_calls.clone()),
-            tool_call_id: None,
-            name: None,
+    async fn request_approval(
+        &self,
+        request: ApprovalRequest<'_>,
+        cancel: &CancelToken,
+    ) -> (bool, &'static str) {
+        let tc = request.call;
+        // Register a oneshot channel for approval
+        let (approval_tx, approval_rx) = tokio::sync::oneshot::channel::<bool>();
+        self.pending_approvals
+            .lock()
+            .insert(tc.id.clone(), approval_tx);
+
+        // Emit tool request event — frontend will show approval modal
+        self.emit(EngineEvent::ToolRequest {
+            session_id: self.session_id.to_string(),
+            run_id: self.run_id.to_string(),
+            tool_call: tc.clone(),
+            tool_tier: Some(request.tier.to_string()),
+            round_number: Some(request.round + 1),
+            loaded_tools: None,
+            context_tokens: None,
+            approval_reason: request.reason,
         });
 
-        // ── Repetition detector: break tool-calling loops ──────────────
-        // Hash the sorted tool names + full args into a u64 fingerprint.
-        // If the same fingerprint appears MAX_REPEATED_SIGNATURES times
-        // consecutively, the model is stuck repeating the same tool calls
-        // (common when model or context is changed mid-conversation).
-        // Uses a hash to avoid UTF-8 boundary issues and keep memory flat.
-        {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-
-            let mut sig_parts: Vec<(&str, &str)> = tool_calls
-                .iter()
-                .map(|tc| (tc.function.name.as_str(), tc.function.arguments.as_str()))
-                .collect();
-            sig_parts.sort();
-
-            let mut hasher = DefaultHasher::new();
-            for (name, args) in &sig_parts {
-                name.hash(&mut hasher);
-                args.hash(&mut hasher);
+        // Wait for user approval (with timeout)
+        let timeout_duration = Duration::from_secs(self.tool_timeout_secs);
+        let waited = run_cancellable(
+            Some(cancel),
+            tokio::time::timeout(timeout_duration, approval_rx),
+        )
+        .await;
+        match waited {
+            None => {
+                self.pending_approvals.lock().remove(&tc.id);
+                (false, "cancelled")
             }
-            let signature = hasher.finish();
-            round_signatures.push(signature);
-
-            // Check the last N signatures for consecutive repetition
-            let sig_len = round_signatures.len();
-            if sig_len >= MAX_REPEATED_SIGNATURES {
-                let all_same = round_signatures[sig_len - MAX_REPEATED_SIGNATURES..]
-                    .iter()
-                    .all(|&s| s == signature);
-                if all_same {
-                    // Check whether we (or detect_response_loop) already
-                    // injected a loop/redirect message. If so, the model
-                    // ignored the first nudge — hard-break to prevent
-                    // unbounded redirect stacking.
-                    let already_redirected = messages.iter().any(|m| {
-                        m.role == Role::System && {
-                            let t = m.content.as_text_ref();
-                            t.contains("stuck in a tool-calling loop")
-                                || t.contains("stuck in a response loop")
-                                || t.contains("stuck repeating yourself")
-                                || t.contains("TOPIC CHANGE")
-                                || t.contains("stuck asking clarifying questions")
-                        }
-                    });
-                    if already_redirected {
-                        warn!(
-                            "[engine] Model ignored tool-loop redirect — hard-breaking agent turn"
-                        );
-                        messages.pop(); // remove the repeated assistant message
-                        return Ok(
-                            "I was stuck calling the same tools repeatedly and couldn't make \
-                            progress. Please try rephrasing your request or switching context."
-                                .to_string(),
-                        );
-                    }
-
-                    warn!(
-                        "[engine] Tool-call loop detected: same tool signature repeated {} times — injecting redirect",
-                        MAX_REPEATED_SIGNATURES
-                    );
-                    // Remove the assistant message we just pushed (it has the repeated tools)
-                    messages.pop();
-                    // Inject a redirect message
-                    messages.push(Message {
-                        role: Role::System,
-                        content: MessageContent::Text(
-                            "[SYSTEM] You are stuck in a tool-calling loop — you have called the \
-                            same tools with the same arguments multiple times in a row. STOP calling \
-                            tools and provide a direct text response to the user summarizing what you \
-                            have accomplished and any issues encountered. Do NOT make any more tool calls."
-                                .to_string(),
-                        ),
-                        tool_calls: None,
-                        tool_call_id: None,
-                        name: None,
-                    });
-                    continue; // Go back to model call — it should now produce text
-                }
+            Some(Ok(Ok(allowed))) => (allowed, "user"),
+            Some(Ok(Err(_))) => {
+                warn!("[engine] Approval channel closed for {}", tc.id);
+                (false, "system")
             }
-        }
-
-        // ── 5. Execute each tool call (with HIL approval) ──────────────
-        //
-        // Tool tiers (VS Code-inspired, adapted for Pawz multi-capability scope):
-        //
-        //  T1 — SAFE: Read-only, zero side effects → always auto-approve
-        //  T2 — REVERSIBLE: Local writes that can be undone (files, memory, tasks) → auto-approve
-        //  T3 — EXTERNAL: Irreversible outbound actions (send email, post to Slack,
-        //        create Google docs) → require approval, offer "Always Allow"
-        //  T4 — DANGEROUS: Shell exec, financial trades, destructive ops → always prompt
-        //
-        let tc_count = tool_calls.len();
-
-        // ── Plan interception: if the model called execute_plan, hand off
-        // to the DAG executor instead of normal tool-by-tool execution ──
-        if tool_calls.len() == 1 && tool_calls[0].function.name == "execute_plan" {
-            let tc = &tool_calls[0];
-            info!("[engine] Intercepting execute_plan — routing to DAG executor");
-
-            let args_str = &tc.function.arguments;
-            let args: serde_json::Value =
-                match serde_json::from_str(if args_str.trim().is_empty() {
-                    "{}"
-                } else {
-                    args_str
-                }) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        let err_msg = format!(
-                            "Failed to parse execute_plan arguments: {}. \
-                         Please provide a valid JSON plan with 'nodes' array.",
-                            e
-                        );
-                        messages.push(Message {
-                            role: Role::Tool,
-                            content: MessageContent::Text(err_msg),
-                            tool_calls: None,
-                            tool_call_id: Some(tc.id.clone()),
-                            name: Some("execute_plan".to_string()),
-                        });
-                        continue;
-                    }
-                };
-
-            // Parse the plan
-            let plan = match crate::engine::plan::parse_plan(&args) {
-                Ok(p) => p,
-                Err(e) => {
-                    messages.push(Message {
-                        role: Role::Tool,
-                        content: MessageContent::Text(format!(
-                            "Plan parsing failed: {}. Fix the plan and retry, or call tools individually.",
-                            e
-                        )),
-                        tool_calls: None,
-                        tool_call_id: Some(tc.id.clone()),
-                        name: Some("execute_plan".to_string()),
-                    });
-                    continue;
-                }
-            };
-
-            // Validate against available tools
-            let validation_errors = crate::engine::plan::validate_plan(&plan, tools);
-            if !validation_errors.is_empty() {
-                let err_list: Vec<String> =
-                    validation_errors.iter().map(|e| e.to_string()).collect();
-                messages.push(Message {
-                    role: Role::Tool,
-                    content: MessageContent::Text(format!(
-                        "Plan validation failed:\n- {}\nFix these issues and retry, or call tools individually.",
-                        err_list.join("\n- ")
-                    )),
-                    tool_calls: None,
-                    tool_call_id: Some(tc.id.clone()),
-                    name: Some("execute_plan".to_string()),
-                });
-                continue;
+            Some(Err(_)) => {
+                warn!(
+                    "[engine] Approval timeout ({}s) for tool {}",
+                    self.tool_timeout_secs, tc.function.name
+                );
+                // Clean up the pending entry
+                self.pending_approvals.lock().remove(&tc.id);
+                (false, "timeout")
             }
-
-            // Execute the plan (parallel DAG execution)
-            let results =
-                crate::engine::plan::execute_plan(&plan, app_handle, agent_id, session_id, run_id)
-                    .await;
-
-            // Build results context for the model
-            let results_context = crate::engine::plan::build_results_context(&plan, &results);
-
-            // Track tool calls and timing for telemetry
-            let plan_node_count = plan.nodes.len() as u32;
-            tool_call_count += plan_node_count;
-
-            messages.push(Message {
-                role: Role::Tool,
-                content: MessageContent::Text(results_context),
-                tool_calls: None,
-                tool_call_id: Some(tc.id.clone()),
-                name: Some("execute_plan".to_string()),
-            });
-
-            // Continue the loop — model will synthesize results into a response
-            info!(
-                "[engine] Plan execution complete: {} nodes executed, feeding results back to model",
-                plan_node_count
-            );
-            continue;
         }
+    }
 
-        for tc in &tool_calls {
-            info!("[engine] Tool call: {} id={}", tc.function.name, tc.id);
-
-            // ─── Tool classification via centralized registry ───
-            let tool_name = tc.function.name.as_str();
-            let tool_tier = tool_metadata::tier(tool_name);
-            let auto_approved = tool_metadata::auto_approved_tools();
-
-            // Determine the tier label for the tool (sent to frontend for UI hints)
-            let _tool_tier = match tool_tier {
-                ToolTier::Safe => "safe",
-                ToolTier::Reversible => "reversible",
-                ToolTier::External => "external",
-                ToolTier::Dangerous => "dangerous",
-            };
-
-            // Trading write tools check the policy-based approval function
-            let is_trading_dangerous = tool_tier == ToolTier::Dangerous
-                && (tool_name.starts_with("sol_")
-                    || tool_name.starts_with("dex_")
-                    || tool_name.starts_with("coinbase_"));
-
-            // ── Circuit breaker: block tools that already hit HARD_STOP ──
-            if let Some(count) = tool_fail_counter.get(&tc.function.name) {
-                if *count >= HARD_STOP_TOOL_FAILS {
-                    warn!(
-                        "[engine] Circuit breaker: blocking '{}' (already failed {} times)",
-                        tc.function.name, count
-                    );
-                    messages.push(Message {
-                        role: Role::Tool,
-                        content: MessageContent::Text(format!(
-                            "Error: Tool '{}' is blocked after {} consecutive failures. Use a different tool or tell the user.",
-                            tc.function.name, count
-                        )),
-                        tool_calls: None,
-                        tool_call_id: Some(tc.id.clone()),
-                        name: Some(tc.function.name.clone()),
-                    });
-                    continue;
-                }
-            }
-
-            let skip_hil = if auto_approve_all
-                || auto_approved.contains(&tool_name)
-                || user_approved_tools.iter().any(|t| t == &tc.function.name)
-            {
-                true
-            } else if is_trading_dangerous {
-                check_trading_auto_approve(&tc.function.name, &tc.function.arguments, app_handle)
-            } else {
-                false
-            };
-
-            let approved = if skip_hil {
-                // Distinguish agent-level auto-approve from safe-tool auto-approve in logs
-                if auto_approve_all && !auto_approved.contains(&tool_name) {
-                    info!(
-                        "[engine] Tool auto-approved (agent policy): {}",
-                        tc.function.name
-                    );
-                    // Emit audit event so frontend can track agent-policy approvals
-                    let _ = app_handle.emit(
-                        "engine-event",
-                        EngineEvent::ToolAutoApproved {
-                            session_id: session_id.to_string(),
-                            run_id: run_id.to_string(),
-                            tool_name: tc.function.name.clone(),
-                            tool_call_id: tc.id.clone(),
-                        },
-                    );
-                } else {
-                    info!("[engine] Auto-approved safe tool: {}", tc.function.name);
-                }
-                true
-            } else {
-                info!("[engine] Tool requires user approval: {}", tc.function.name);
-                // Register a oneshot channel for approval
-                let (approval_tx, approval_rx) = tokio::sync::oneshot::channel::<bool>();
-                {
-                    let mut map = pending_approvals.lock();
-                    map.insert(tc.id.clone(), approval_tx);
-                }
-
-                // Emit tool request event — frontend will show approval modal
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::ToolRequest {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        tool_call: tc.clone(),
-                        tool_tier: Some(_tool_tier.to_string()),
-                        round_number: Some(round + 1),
-                        loaded_tools: None,
-                        context_tokens: None,
-                    },
-                );
-
-                // Wait for user approval (with timeout)
-                let timeout_duration = Duration::from_secs(tool_timeout_secs);
-                match tokio::time::timeout(timeout_duration, approval_rx).await {
-                    Ok(Ok(allowed)) => allowed,
-                    Ok(Err(_)) => {
-                        warn!("[engine] Approval channel closed for {}", tc.id);
-                        false
-                    }
-                    Err(_) => {
-                        warn!(
-                            "[engine] Approval timeout ({}s) for tool {}",
-                            tool_timeout_secs, tc.function.name
-                        );
-                        // Clean up the pending entry
-                        let mut map = pending_approvals.lock();
-                        map.remove(&tc.id);
-                        false
-                    }
-                }
-            };
-
-            if !approved {
-                info!(
-                    "[engine] Tool DENIED by user: {} id={}",
-                    tc.function.name, tc.id
-                );
-
-                // Audit: log tool denial
-                if let Some(es) = app_handle.try_state::<crate::engine::state::EngineState>() {
-                    crate::engine::audit::log_tool_denied(
-                        &es.store,
-                        agent_id,
-                        session_id,
-                        &tc.function.name,
-                        &tc.id,
-                    );
-                }
-
-                // Emit denial as tool result
-                let _ = app_handle.emit(
-                    "engine-event",
-                    EngineEvent::ToolResultEvent {
-                        session_id: session_id.to_string(),
-                        run_id: run_id.to_string(),
-                        tool_call_id: tc.id.clone(),
-                        output: "Tool execution denied by user.".into(),
-                        success: false,
-                        duration_ms: None,
-                    },
+    /// Hand `execute_plan` to the DAG executor instead of normal
+    /// tool-by-tool execution.
+    async fn execute_plan(&self, tc: &ToolCall, tools: &[ToolDefinition]) -> Option<PlanOutcome> {
+        info!("[engine] Intercepting execute_plan — routing to DAG executor");
+
+        let args_str = &tc.function.arguments;
+        let args: serde_json::Value = match serde_json::from_str(if args_str.trim().is_empty() {
+            "{}"
+        } else {
+            args_str
+        }) {
+            Ok(v) => v,
+            Err(e) => {
+                let err_msg = format!(
+                    "Failed to parse execute_plan arguments: {}. \
+                     Please provide a valid JSON plan with 'nodes' array.",
+                    e
                 );
-
-                // Add denial to message history so the model knows
-                messages.push(Message {
-                    role: Role::Tool,
-                    content: MessageContent::Text("Tool execution denied by user.".into()),
-                    tool_calls: None,
-                    tool_call_id: Some(tc.id.clone()),
-                    name: Some(tc.function.name.clone()),
+                return Some(PlanOutcome {
+                    output: err_msg,
+                    nodes: 0,
                 });
-                continue;
             }
-
-            // Execute the tool (pass agent_id so tools know which agent is calling)
-            let tool_timer = telem::ToolTimer::start(&tc.function.name);
-            let result = tools::execute_tool(tc, app_handle, agent_id).await;
-            let tool_ms = tool_timer.finish(&telem_collector, &telem_root_id, result.success);
-            tool_duration_total_ms += tool_ms;
-            tool_call_count += 1;
-
-            info!(
-                "[engine] Tool result: {} success={} output_len={}",
-                tc.function.name,
-                result.success,
-                result.output.len()
-            );
-
-            // Audit: log tool execution result
-            if let Some(es) = app_handle.try_state::<crate::engine::state::EngineState>() {
-                crate::engine::audit::log_tool_call(
-                    &es.store,
-                    agent_id,
-                    session_id,
-                    &tc.function.name,
-                    &tc.id,
-                    &tc.function.arguments,
-                    result.success,
-                    &result.output,
+        };
+
+        // Parse the plan
+        let plan = match crate::engine::plan::parse_plan(&args) {
+            Ok(p) => p,
+            Err(e) => {
+                let err_msg = format!(
+                    "Plan parsing failed: {}. Fix the plan and retry, or call tools individually.",
+                    e
                 );
+                return Some(PlanOutcome {
+                    output: err_msg,
+                    nodes: 0,
+                });
             }
-
-            // Emit tool result event
-            let _ = app_handle.emit(
-                "engine-event",
-                EngineEvent::ToolResultEvent {
-                    session_id: session_id.to_string(),
-                    run_id: run_id.to_string(),
-                    tool_call_id: tc.id.clone(),
-                    output: result.output.clone(),
-                    success: result.success,
-                    duration_ms: Some(tool_ms),
-                },
+        };
+
+        // Validate against available tools
+        let validation_errors = crate::engine::plan::validate_plan(&plan, tools);
+        if !validation_errors.is_empty() {
+            let err_list: Vec<String> = validation_errors.iter().map(|e| e.to_string()).collect();
+            let err_msg = format!(
+                "Plan validation failed:\n- {}\nFix these issues and retry, or call tools individually.",
+                err_list.join("\n- ")
             );
-
-            // Add tool result to message history
-            messages.push(Message {
-                role: Role::Tool,
-                content: MessageContent::Text(result.output.clone()),
-                tool_calls: None,
-                tool_call_id: Some(tc.id.clone()),
-                name: Some(tc.function.name.clone()),
+            return Some(PlanOutcome {
+                output: err_msg,
+                nodes: 0,
             });
+        }
 
-            // ── Circuit breaker: track consecutive failures per tool ──
-            if !result.success {
-                let count = tool_fail_counter
-                    .entry(tc.function.name.clone())
-                    .or_insert(0);
-                *count += 1;
-                if *count >= HARD_STOP_TOOL_FAILS {
-                    warn!(
-                        "[engine] Circuit breaker HARD STOP: tool '{}' failed {} consecutive times. Blocking further calls.",
-                        tc.function.name, count
-                    );
-                    messages.push(Message {
-                        role: Role::System,
-                        content: MessageContent::Text(format!(
-                            "[SYSTEM] HARD STOP: The tool '{}' has failed {} times in a row and is now BLOCKED. \
-                            Do NOT call '{}' again — it will not work. \
-                            Instead, tell the user what happened and suggest they check their \
-                            skill configuration or try a different approach. Provide a text summary now.",
-                            tc.function.name, count, tc.function.name
-                        )),
-                        tool_calls: None,
-                        tool_call_id: None,
-                        name: None,
-                    });
-                } else if *count >= MAX_CONSECUTIVE_TOOL_FAILS {
-                    warn!(
-                        "[engine] Circuit breaker: tool '{}' failed {} consecutive times. Injecting stop-retry nudge.",
-                        tc.function.name, count
-                    );
-                    messages.push(Message {
-                        role: Role::System,
-                        content: MessageContent::Text(format!(
-                            "[SYSTEM] The tool '{}' has failed {} times in a row. \
-                            Stop calling '{}' with the same arguments — try a DIFFERENT tool or approach instead. \
-                            Use `request_tools` to discover alternative tools that might work better. \
-                            For example, if google_api failed, try dedicated tools like google_docs_create, \
-                            google_drive_upload, or google_drive_share instead.",
-                            tc.function.name, count, tc.function.name
-                        )),
-                        tool_calls: None,
-                        tool_call_id: None,
-                        name: None,
-                    });
-                }
-            } else {
-                // Reset counter on success
-                tool_fail_counter.remove(&tc.function.name);
-            }
+        // Execute the plan (parallel DAG execution)
+        let results = crate::engine::plan::execute_plan(
+            &plan,
+            self.app_handle,
+            self.agent_id,
+            self.session_id,
+            self.run_id,
+        )
+        .await;
+
+        // Build results context for the model
+        let results_context = crate::engine::plan::build_results_context(&plan, &results);
+        let plan_node_count = plan.nodes.len() as u32;
+        info!(
+            "[engine] Plan execution complete: {} nodes executed, feeding results back to model",
+            plan_node_count
+        );
+        Some(PlanOutcome {
+            output: results_context,
+            nodes: plan_node_count,
+        })
+    }
+
+    /// Execute the tool (pass agent_id so tools know which agent is calling).
+    async fn execute_tool(&self, call: &ToolCall) -> ToolResult {
+        tools::in_session(
+            self.session_id,
+            tools::execute_tool(call, self.app_handle, self.agent_id),
+        )
+        .await
+    }
+
+    fn tool_finished(&self, call: &ToolCall, result: &ToolResult, duration_ms: u64) {
+        telem::record_tool_span(
+            &self.telemetry.lock(),
+            &self.telemetry_root,
+            &call.function.name,
+            duration_ms,
+            result.success,
+        );
 
-            // ── Phase 4: Record tool transition & predict next tool ───
-            // After each tool execution, record the A→B transition in SQLite
-            // and predict the next likely tool call for speculative pre-warming.
-            if let Some(es) = app_handle.try_state::<crate::engine::state::EngineState>() {
-                let conn = es.store.conn();
-                let db = conn.lock();
-                if let Some(candidate) = crate::engine::speculative::predict_and_record(
-                    &db,
-                    previous_tool.as_deref(),
-                    &tc.function.name,
-                    &speculation_config,
-                ) {
-                    speculation_stats.predictions += 1;
-                    info!(
-                        "[speculative] Predicted next tool: {} (p={:.2})",
-                        candidate.tool_name, candidate.probability
-                    );
+        // ── Phase 4: Record tool transition & predict next tool ───────
+        // After each tool execution, record the A→B transition in SQLite
+        // and predict the next likely tool call for speculative pre-warming.
+        let mut speculation = self.speculation.lock();
+        if let Some(store) = self.store() {
+            let conn = store.conn();
+            let db = conn.lock();
+            if let Some(candidate) = crate::engine::speculative::predict_and_record(
+                &db,
+                speculation.previous_tool.as_deref(),
+                &call.function.name,
+                &self.speculation_config,
+            ) {
+                speculation.stats.predictions += 1;
+                info!(
+                    "[speculative] Predicted next tool: {} (p={:.2})",
+                    candidate.tool_name, candidate.probability
+                );
 
-                    // Pre-warm connection for the predicted tool's API domain
-                    if speculation_config.warm_connections {
-                        if let Some(target) =
-                            crate::engine::speculative::warm_target_for_domain(&candidate.tool_name)
-                        {
-                            if let Ok(dur) = crate::engine::speculative::warm_connection(&target) {
-                                speculation_stats.connections_warmed += 1;
-                                info!(
-                                    "[speculative] Pre-warmed connection to {}:{} in {:.1}ms",
-                                    target.host,
-                                    target.port,
-                                    dur.as_secs_f64() * 1000.0
-                                );
-                            }
+                // Pre-warm connection for the predicted tool's API domain
+                if self.speculation_config.warm_connections {
+                    if let Some(target) =
+                        crate::engine::speculative::warm_target_for_domain(&candidate.tool_name)
+                    {
+                        if let Ok(dur) = crate::engine::speculative::warm_connection(&target) {
+                            speculation.stats.connections_warmed += 1;
+                            info!(
+                                "[speculative] Pre-warmed connection to {}:{} in {:.1}ms",
+                                target.host,
+                                target.port,
+                                dur.as_secs_f64() * 1000.0
+                            );
                         }
                     }
-                } else {
-                    // Still record transition even if no prediction
-                    // (predict_and_record already does this internally)
                 }
             }
-            // Update previous_tool for the next iteration's transition recording
-            previous_tool = Some(tc.function.name.clone());
         }
+        // Update previous_tool for the next transition recording
+        speculation.previous_tool = Some(call.function.name.clone());
+    }
 
-        // ── 6. Tool RAG: refresh tools if request_tools was called ─────
-        helpers::refresh_tool_rag(app_handle, tools);
-
-        // ── 7. Mid-loop context truncation ─────────────────────────────
-        // §24 Checkpoint: snapshot conversation state before truncation destroys messages
-        if let Some(es) = app_handle.try_state::<crate::engine::state::EngineState>() {
-            let checkpoint_msgs: Vec<crate::atoms::engram_types::CheckpointMessage> = messages
-                .iter()
-                .map(|m| crate::atoms::engram_types::CheckpointMessage {
-                    role: format!("{:?}", m.role).to_lowercase(),
-                    content: match &m.content {
-                        MessageContent::Text(t) => t.clone(),
-                        MessageContent::Blocks(blocks) => blocks
-                            .iter()
-                            .filter_map(|b| match b {
-                                ContentBlock::Text { text } => Some(text.as_str()),
-                                _ => None,
-                            })
-                            .collect::<Vec<_>>()
-                            .join("\n"),
-                    },
-                    timestamp: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-                })
-                .collect();
-            let empty_wm = crate::atoms::engram_types::WorkingMemorySnapshot {
-                agent_id: agent_id.to_string(),
-                slots: vec![],
-                momentum_embeddings: vec![],
-                saved_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-            };
-            let empty_hashes = std::collections::HashMap::new();
-            let req = crate::engine::engram::context_continuity::CaptureCheckpointRequest {
-                agent_id,
-                session_id,
-                messages: &checkpoint_msgs,
-                working_memory: &empty_wm,
-                file_hashes: &empty_hashes,
-                tasks: &[],
-                key_decisions: &[],
-            };
-            if let Err(e) =
-                crate::engine::engram::context_continuity::capture_checkpoint(&es.store, &req)
-            {
-                warn!(
-                    "[engine] Failed to capture pre-truncation checkpoint: {}",
-                    e
-                );
-            }
-        }
-        helpers::truncate_mid_loop(app_handle, messages);
+    /// Tool RAG: pick up tools `request_tools` loaded this round.
+    fn refresh_tools(&self, tools: &mut Vec<ToolDefinition>) {
+        helpers::refresh_tool_rag(self.app_handle, self.agent_id, tools);
+    }
 
-        // ── 8. Loop: send tool results back to model ──────────────────
-        info!(
-            "[engine] {} tool calls executed, feeding results back to model",
-            tc_count
+    fn completed(&self, stats: &TurnStats) {
+        // ── Telemetry flush (Canvas Phase 5) ──────────────────────────
+        let mut summary = self.telemetry.lock().build_summary(
+            stats.input_tokens,
+            stats.output_tokens,
+            stats.rounds,
+            stats.tool_calls,
         );
+        summary.total_duration_ms = stats.duration_ms;
+        summary.llm_duration_ms = stats.duration_ms.saturating_sub(stats.tool_duration_ms);
+        summary.tool_duration_ms = stats.tool_duration_ms;
+        summary.cost_usd = estimate_cost_usd(
+            self.model,
+            stats.input_tokens,
+            stats.output_tokens,
+            stats.cache_read_tokens,
+            stats.cache_creation_tokens,
+        );
+        if let Some(store) = self.store() {
+            telem::persist_summary(store, &summary);
+        }
+        telem::emit_summary(self.app_handle, &summary);
 
-        // NOTE: Do NOT emit Complete here — only emit Complete when the model
-        // produces a final text response (no more tool calls). Intermediate
-        // Complete events were causing premature stream resolution on the frontend.
+        // ── Phase 3: Flush remaining batched deltas ───────────────────
+        let mut batcher = self.delta_batcher.lock();
+        if let Some(batch) = batcher.flush() {
+            self.emit_delta(batch.combined_text);
+        }
+        // Log binary IPC batcher stats for the session
+        crate::engine::binary_ipc::log_session_stats(&batcher.stats(), 0);
 
-        // Continue the loop — model will see tool results and either respond or call more tools
+        // ── Phase 4: Log speculation stats for the session ────────────
+        crate::engine::speculative::log_session_speculation_stats(&self.speculation.lock().stats);
     }
 }
@@ -6,8 +6,43 @@
 use crate::engine::state::EngineState;
 use crate::engine::types::*;
 use log::info;
+use std::time::Duration;
 use tauri::Manager;
 
+/// How long valuing a trade for the approval rules may take before the
+/// call is treated as unpriced (and so asks for approval).
+const QUOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// USD price of one unit of the token a swap or transfer spends, for
+/// threshold approval rules. None for other tools, or when no quote came back.
+pub(crate) async fn quote_unit_price_usd(
+    tool_name: &str,
+    args_str: &str,
+    app_handle: &tauri::AppHandle,
+) -> Option<f64> {
+    let args: serde_json::Value = serde_json::from_str(args_str).ok()?;
+    let token = args["token_in"].as_str().or(args["currency"].as_str())?;
+    let quote = async {
+        match tool_name {
+            "dex_swap" | "dex_transfer" => {
+                let creds = crate::engine::tools::get_skill_creds("dex", app_handle).ok()?;
+                crate::engine::dex::token_usd_price(&creds, token).await
+            }
+            "sol_swap" | "sol_transfer" => {
+                let (mint, _) = crate::engine::sol_dex::helpers::resolve_token(token).ok()?;
+                crate::engine::sol_dex::get_token_price_usd(&mint)
+                    .await
+                    .ok()
+            }
+            _ => None,
+        }
+    };
+    tokio::time::timeout(QUOTE_TIMEOUT, quote)
+        .await
+        .ok()
+        .flatten()
+}
+
 /// Policy-based auto-approval for trading write tools (all chains: Coinbase, Solana, EVM DEX).
 /// Checks configurable limits (max trade size, daily loss, allowed pairs, transfer caps).
 /// Returns false (requiring HIL) when no policy is configured or limits exceeded.
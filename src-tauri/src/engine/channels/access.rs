@@ -6,6 +6,7 @@ use super::PendingUser;
 use crate::atoms::error::EngineResult;
 use crate::engine::state::EngineState;
 use log::info;
+use openpawz_core::engine::i18n::t;
 use tauri::Manager;
 
 /// Check access control. Returns Ok(()) if allowed, Err(denial message) if denied.
@@ -21,7 +22,7 @@ pub fn check_access(
     match dm_policy {
         "allowlist" => {
             if !allowed_users.contains(&user_id.to_string()) {
-                return Err("⛔ You're not on the allowlist. Ask the Paw owner to add you.".into());
+                return Err(t("access.not_allowlisted", &[]).into());
             }
         }
         "pairing" => {
@@ -34,7 +35,7 @@ pub fn check_access(
                         requested_at: chrono::Utc::now().to_rfc3339(),
                     });
                 }
-                return Err("Pairing request sent to Paw. Waiting for approval...".into());
+                return Err(t("access.pairing_requested", &[]).into());
             }
         }
         // "open" — allow everyone
@@ -55,6 +55,13 @@ pub async fn run_channel_agent(
     // Per-user per-agent session: eng-{channel}-{agent}-{user_id}
     let session_id = format!("eng-{}-{}-{}", channel_prefix, agent_id, user_id);
 
+    // The agent's definition: its model ranks below a routing override, its
+    // persona leads the prompt, its skill list gates the skill tools
+    let agent_def = engine_state
+        .store
+        .get_agent_definition(agent_id)
+        .unwrap_or(None);
+
     // Get provider config — channel bridges use the DEFAULT model (not worker_model).
     // Channel bridges handle complex multi-step tasks (creating 15+ Discord channels,
     // managing permissions, etc.) that require a capable model. The worker_model is
@@ -66,11 +73,12 @@ pub async fn run_channel_agent(
             .default_model
             .clone()
             .unwrap_or_else(|| "gpt-5.1".into());
-        // Use "channel" role — falls through to the default model since there's
-        // no channel-specific override in model routing. Users can add one in
-        // agent_models if they want a specific model for a specific agent.
-        let model = normalize_model_name(&cfg.model_routing.resolve(
+        // Use "channel" role — falls through to the agent's own model, then the
+        // default model, since there's no channel-specific override in model
+        // routing. Users can add one in agent_models for a specific agent.
+        let model = normalize_model_name(&cfg.model_routing.resolve_for_agent(
             agent_id,
+            agent_def.as_ref().and_then(|a| a.model.as_deref()),
             "channel",
             "",
             &default_model,
@@ -171,6 +179,8 @@ pub async fn run_channel_agent(
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     engine_state.store.add_message(&user_msg)?;
 
@@ -298,6 +308,19 @@ pub async fn run_channel_agent(
         - **If a call fails, try again.** Don't give up or ask the user to do it manually.\n\
         - **Keep responses short.** Brief updates between actions, not essays.";
 
+    // The agent's persona (laid out by the user's system-prompt template when
+    // one is set) stands in for the skipped base prompt.
+    let base_section = chat_org::templated_base_prompt(
+        &engine_state.config.lock(),
+        agent_def.as_ref().and_then(|a| a.persona.clone()),
+        agent_id,
+        &mut engine_state
+            .store
+            .get_todays_memories(agent_id)
+            .unwrap_or(None),
+        &mut String::new(),
+    );
+
     let full_system_prompt = {
         let cognitive = cognitive_lock.lock().await;
 
@@ -318,6 +341,10 @@ pub async fn run_channel_agent(
         // Conversation discipline at priority 1
         builder = builder.custom_section("conversation_discipline", discipline_text, 1);
 
+        if let Some(ref base) = base_section {
+            builder = builder.custom_section("base_prompt", base.clone(), 1);
+        }
+
         // Pre-recalled memories from gated_search above (with CRAG quality gating)
         if let Some(ref recalled) = channel_recalled {
             let mut mem_parts: Vec<String> = vec!["## Recalled Context".to_string()];
@@ -359,6 +386,7 @@ pub async fn run_channel_agent(
                     parts.push(cc.to_string());
                 }
                 parts.push(discipline_text.to_string());
+                parts.extend(base_section.clone());
                 Some(parts.join("\n\n---\n\n"))
             }
         }
@@ -400,8 +428,13 @@ pub async fn run_channel_agent(
     //   - self_info: introspect own config when asked
     let mut tools: Vec<ToolDefinition> = {
         let mut all_builtins = crate::engine::tools::builtin_tools();
-        // Add all discord tools
-        all_builtins.extend(crate::engine::tools::discord::definitions());
+        // Add all discord tools, unless the agent's skill list leaves discord out
+        let discord_allowed = agent_def
+            .as_ref()
+            .is_none_or(|a| a.skills.is_empty() || a.skills.iter().any(|s| s == "discord"));
+        if discord_allowed {
+            all_builtins.extend(crate::engine::tools::discord::definitions());
+        }
         let whitelist = [
             "fetch",
             "memory_store",
@@ -448,7 +481,11 @@ pub async fn run_channel_agent(
         filtered
     };
 
-    let provider = AnyProvider::from_config(&provider_config);
+    // Other providers take over on billing/auth errors or sustained overload
+    let provider = {
+        let cfg = engine_state.config.lock();
+        AnyProvider::with_billing_fallback(&provider_config, &cfg.providers)
+    };
     let run_id = uuid::Uuid::new_v4().to_string();
 
     // Channel bridge tool policy: deny side-effect tools that the agent loop
@@ -515,106 +552,39 @@ pub async fn run_channel_agent(
         cfg.daily_budget_usd
     };
     let daily_tokens_tracker = engine_state.daily_tokens.clone();
+    let run_limiter = engine_state.run_limiter.clone();
 
-    // Run the agent loop — with provider fallback on billing/auth errors
-    let result = {
-        let primary_result = agent_loop::run_agent_turn(
-            app_handle,
-            &provider,
-            &model,
-            &mut messages,
-            &mut tools,
-            &session_id,
-            &run_id,
-            max_rounds,
-            None,
-            &approvals,
-            tool_timeout,
-            agent_id,
-            daily_budget,
-            Some(&daily_tokens_tracker),
-            None,  // thinking_level
-            false, // auto_approve_all — channels use safe default; Phase C adds per-channel policy
-            &[],   // user_approved_tools — not available from channels
-            None,  // yield_signal
-        )
-        .await;
+    // Acquire semaphore slot, like chat, tasks and swarm runs
+    let _permit = run_limiter.acquire_queued().await;
+    info!(
+        "[{}] Agent '{}' acquired run slot",
+        channel_prefix, agent_id
+    );
 
-        // If the primary provider failed with a billing/auth/rate error, try fallback providers
-        match &primary_result {
-            Err(e) if is_provider_billing_error(&e.to_string()) => {
-                warn!(
-                    "[{}] Primary provider failed ({}), trying fallback providers",
-                    channel_prefix, e
-                );
-                let fallback_providers: Vec<ProviderConfig> = {
-                    let cfg = engine_state.config.lock();
-                    cfg.providers
-                        .iter()
-                        .filter(|p| p.id != provider_config.id)
-                        .cloned()
-                        .collect()
-                };
-
-                let mut fallback_result = primary_result;
-                for fb_provider_cfg in &fallback_providers {
-                    let fb_model = fb_provider_cfg
-                        .default_model
-                        .clone()
-                        .unwrap_or_else(|| normalize_model_name(&model).to_string());
-                    let fb_provider = AnyProvider::from_config(fb_provider_cfg);
-                    info!(
-                        "[{}] Trying fallback: {:?} / {}",
-                        channel_prefix, fb_provider_cfg.kind, fb_model
-                    );
-
-                    // Reset messages to pre-loop state for retry
-                    messages.truncate(pre_loop_msg_count);
-
-                    let fb_run_id = uuid::Uuid::new_v4().to_string();
-                    match agent_loop::run_agent_turn(
-                        app_handle,
-                        &fb_provider,
-                        &fb_model,
-                        &mut messages,
-                        &mut tools,
-                        &session_id,
-                        &fb_run_id,
-                        max_rounds,
-                        None,
-                        &approvals,
-                        tool_timeout,
-                        agent_id,
-                        daily_budget,
-                        Some(&daily_tokens_tracker),
-                        None,  // thinking_level
-                        false, // auto_approve_all — channels: safe default
-                        &[],   // user_approved_tools
-                        None,  // yield_signal
-                    )
-                    .await
-                    {
-                        Ok(text) => {
-                            info!(
-                                "[{}] Fallback {:?} succeeded",
-                                channel_prefix, fb_provider_cfg.kind
-                            );
-                            fallback_result = Ok(text);
-                            break;
-                        }
-                        Err(fb_err) => {
-                            warn!(
-                                "[{}] Fallback {:?} also failed: {}",
-                                channel_prefix, fb_provider_cfg.kind, fb_err
-                            );
-                        }
-                    }
-                }
-                fallback_result
-            }
-            _ => primary_result,
-        }
-    };
+    // Run the agent loop
+    let result = agent_loop::run_agent_turn(
+        app_handle,
+        &provider,
+        &model,
+        &mut messages,
+        &mut tools,
+        &session_id,
+        &run_id,
+        max_rounds,
+        None,
+        &approvals,
+        tool_timeout,
+        agent_id,
+        daily_budget,
+        Some(&daily_tokens_tracker),
+        None,  // thinking_level
+        false, // auto_approve_all — channels use safe default; Phase C adds per-channel policy
+        &[],   // user_approved_tools — not available from channels
+        None,  // yield_signal
+        None,  // cancel
+        None,  // partial_reply
+    )
+    .await;
 
     // Stop the auto-approver
     auto_approver.abort();
@@ -638,6 +608,8 @@ pub async fn run_channel_agent(
                 tool_call_id: msg.tool_call_id.clone(),
                 name: msg.name.clone(),
                 created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
             };
             if let Err(e) = engine_state.store.add_message(&stored) {
                 error!("[{}] Failed to store message: {}", channel_prefix, e);
@@ -656,8 +628,15 @@ pub async fn run_channel_agent(
         }
 
         let auto_capture = engine_state.memory_config.lock().auto_capture;
-        if auto_capture && !final_text.is_empty() {
-            let facts = memory::extract_memorable_facts_heuristic(message, final_text);
+        let capture_policy = memory::capture::load_policy(&engine_state.store, agent_id);
+        if auto_capture
+            && capture_policy.enabled
+            && !final_text.is_empty()
+            && memory::capture::claim_capture(agent_id, message)
+        {
+            let facts = capture_policy.select(memory::extract_memorable_facts_heuristic(
+                message, final_text,
+            ));
             if !facts.is_empty() {
                 let emb_client = engine_state.embedding_client();
                 for (content, category) in &facts {
@@ -666,7 +645,7 @@ pub async fn run_channel_agent(
                         &engine_state.store,
                         content,
                         category,
-                        5,
+                        memory::capture::fact_importance(category),
                         emb_client.as_ref(),
                         None,
                     )
@@ -698,26 +677,6 @@ pub async fn run_channel_agent(
     result
 }
 
-// ── Utility ────────────────────────────────────────────────────────────
-
-/// Detect billing, auth, quota, or rate-limit errors that warrant trying
-/// a different provider instead of failing outright.
-pub(crate) fn is_provider_billing_error(err: &str) -> bool {
-    let lower = err.to_lowercase();
-    lower.contains("credit balance")
-        || lower.contains("insufficient_quota")
-        || lower.contains("billing")
-        || lower.contains("rate_limit")
-        || lower.contains("quota exceeded")
-        || lower.contains("payment required")
-        || lower.contains("account")
-        || (lower.contains("api error 4")
-            && (lower.contains("401")
-                || lower.contains("402")
-                || lower.contains("403")
-                || lower.contains("429")))
-}
-
 /// Convenience wrapper: resolve routing config to determine the agent_id,
 /// then call run_channel_agent with that agent. Channels should prefer this
 /// over calling run_channel_agent directly.
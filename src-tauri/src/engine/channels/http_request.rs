@@ -0,0 +1,191 @@
+// Bounded HTTP request reading for the raw TCP listeners (web chat, webhook,
+// WhatsApp). Every request gets one overall deadline — so a client trickling
+// bytes (slowloris) can't hold a connection — plus header and body caps.
+// Violations are answered with 408 / 413 and the connection is closed.
+
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+#[derive(Debug, Clone, Copy)]
+pub struct HttpLimits {
+    pub max_header_bytes: usize,
+    pub max_body_bytes: usize,
+    /// Deadline for the whole request, not per read.
+    pub timeout: Duration,
+}
+
+impl Default for HttpLimits {
+    fn default() -> Self {
+        HttpLimits {
+            max_header_bytes: 16 * 1024,
+            max_body_bytes: 64 * 1024,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RequestError {
+    /// Client closed the connection before sending anything.
+    Closed,
+    TooLarge,
+    Timeout,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestError::Closed => write!(f, "connection closed"),
+            RequestError::TooLarge => write!(f, "request too large"),
+            RequestError::Timeout => write!(f, "request timed out"),
+            RequestError::Io(e) => write!(f, "read: {}", e),
+        }
+    }
+}
+
+fn header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|i| i + 4)
+}
+
+fn content_length(headers: &[u8]) -> Option<usize> {
+    String::from_utf8_lossy(headers).lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("content-length")
+            .then(|| value.trim().parse().ok())?
+    })
+}
+
+async fn read_until_complete<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    limits: &HttpLimits,
+) -> Result<Vec<u8>, RequestError> {
+    let mut buf = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+    let mut expected_len: Option<usize> = None;
+
+    loop {
+        if expected_len.is_none() {
+            if let Some(end) = header_end(&buf) {
+                let body_len = content_length(&buf[..end]).unwrap_or(0);
+                if body_len > limits.max_body_bytes {
+                    return Err(RequestError::TooLarge);
+                }
+                expected_len = Some(end + body_len);
+            } else if buf.len() > limits.max_header_bytes {
+                return Err(RequestError::TooLarge);
+            }
+        }
+        if expected_len.is_some_and(|len| buf.len() >= len) {
+            return Ok(buf);
+        }
+
+        let n = stream.read(&mut chunk).await.map_err(RequestError::Io)?;
+        if n == 0 {
+            return if buf.is_empty() {
+                Err(RequestError::Closed)
+            } else {
+                // Peer stopped sending — hand over what arrived
+                Ok(buf)
+            };
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Read one HTTP request: headers, then a `Content-Length` body. Bytes read
+/// past the request (none for well-behaved clients) are returned with it.
+pub async fn read_request<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    limits: &HttpLimits,
+) -> Result<Vec<u8>, RequestError> {
+    tokio::time::timeout(limits.timeout, read_until_complete(stream, limits))
+        .await
+        .unwrap_or(Err(RequestError::Timeout))
+}
+
+/// Answer a limit violation (413 / 408) and close the connection.
+pub async fn reject_request<S: AsyncWrite + Unpin>(stream: &mut S, error: &RequestError) {
+    let status = match error {
+        RequestError::TooLarge => "413 Payload Too Large",
+        RequestError::Timeout => "408 Request Timeout",
+        RequestError::Closed | RequestError::Io(_) => return,
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> HttpLimits {
+        HttpLimits {
+            max_header_bytes: 1024,
+            max_body_bytes: 64,
+            timeout: Duration::from_millis(100),
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_body_split_across_writes() {
+        let (mut client, mut server) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            client
+                .write_all(b"POST /auth HTTP/1.1\r\nContent-Length: 11\r\n\r\nhello")
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            client.write_all(b" world").await.unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+
+        let request = read_request(&mut server, &limits()).await.unwrap();
+        assert!(request.ends_with(b"\r\n\r\nhello world"));
+    }
+
+    #[tokio::test]
+    async fn oversized_request_is_rejected() {
+        let (mut client, mut server) = tokio::io::duplex(4096);
+        client
+            .write_all(b"POST /auth HTTP/1.1\r\ncontent-length: 65\r\n\r\n")
+            .await
+            .unwrap();
+        let err = read_request(&mut server, &limits()).await.unwrap_err();
+        assert!(matches!(err, RequestError::TooLarge));
+
+        // Headers that never end are capped too
+        let (mut client, mut server) = tokio::io::duplex(4096);
+        client.write_all(&[b'a'; 2048]).await.unwrap();
+        let err = read_request(&mut server, &limits()).await.unwrap_err();
+        assert!(matches!(err, RequestError::TooLarge));
+
+        reject_request(&mut server, &err).await;
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+
+    #[tokio::test]
+    async fn slow_client_times_out() {
+        let (mut client, mut server) = tokio::io::duplex(4096);
+        // Trickles a header line and then stalls without finishing
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: x")
+            .await
+            .unwrap();
+
+        let err = read_request(&mut server, &limits()).await.unwrap_err();
+        assert!(matches!(err, RequestError::Timeout));
+
+        reject_request(&mut server, &err).await;
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout"));
+    }
+}
@@ -6,9 +6,11 @@
 //   - ChannelConfig trait  — common config shape for load/save/user management
 //   - split_message()      — splits long responses for platform message limits
 //   - Access control       — allowlist / pairing logic
+//   - read_request()       — bounded HTTP request reads for raw TCP listeners
 
 mod access;
 mod agent;
+mod http_request;
 
 use crate::atoms::error::{EngineError, EngineResult};
 use crate::engine::state::EngineState;
@@ -18,6 +20,7 @@ use tauri::Manager;
 // Re-export public API
 pub use access::{approve_user_generic, check_access, deny_user_generic, remove_user_generic};
 pub use agent::{run_channel_agent, run_routed_channel_agent};
+pub use http_request::{read_request, reject_request, HttpLimits, RequestError};
 
 // ── Common Channel Config ──────────────────────────────────────────────
 
@@ -102,23 +105,7 @@ pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
     chunks
 }
 
-/// Detect billing, auth, quota, or rate-limit errors that warrant trying
-/// a different provider instead of failing outright.
-pub fn is_provider_billing_error(err: &str) -> bool {
-    let lower = err.to_lowercase();
-    lower.contains("credit balance")
-        || lower.contains("insufficient_quota")
-        || lower.contains("billing")
-        || lower.contains("rate_limit")
-        || lower.contains("quota exceeded")
-        || lower.contains("payment required")
-        || lower.contains("account")
-        || (lower.contains("api error 4")
-            && (lower.contains("401")
-                || lower.contains("402")
-                || lower.contains("403")
-                || lower.contains("429")))
-}
+pub use crate::engine::providers::is_provider_billing_error;
 
 #[cfg(test)]
 mod tests {
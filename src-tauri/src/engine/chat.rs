@@ -17,6 +17,9 @@ use crate::engine::tools;
 use crate::engine::types::*;
 use crate::engine::util::safe_truncate;
 use log::{info, warn};
+pub use openpawz_core::engine::prompt_template::{
+    agent_display_name, apply_prompt_template, templated_base_prompt,
+};
 
 // ── Tool builder ───────────────────────────────────────────────────────────────
 
@@ -31,12 +34,14 @@ use log::{info, warn};
 ///
 /// # Parameters
 /// - `store`         — session store (used to check which skills are enabled)
+/// - `agent_id`      — the agent whose skill list (if any) picks the skill tools
 /// - `tools_enabled` — if false, returns an empty list immediately
 /// - `tool_filter`   — optional list of tool names to retain (allow-list)
 /// - `app_handle`    — needed to probe whether the Telegram bridge is configured
 /// - `loaded_tools`  — tool names previously loaded via request_tools this turn
 pub fn build_chat_tools(
     store: &SessionStore,
+    agent_id: &str,
     tools_enabled: bool,
     tool_filter: Option<&[String]>,
     app_handle: &tauri::AppHandle,
@@ -49,18 +54,17 @@ pub fn build_chat_tools(
     // ── Build the full tool registry (same as before) ──────────────────
     let mut all_tools = crate::engine::tools::builtin_tools();
 
-    let enabled_ids: Vec<String> = skills::builtin_skills()
-        .iter()
-        .filter(|s| store.is_skill_enabled(&s.id).unwrap_or(false))
-        .map(|s| s.id.clone())
-        .collect();
+    let enabled_ids = skills::enabled_skill_ids(store, agent_id);
     if !enabled_ids.is_empty() {
         info!("[engine] Skills enabled: {:?}", enabled_ids);
         all_tools.extend(crate::engine::tools::skill_tools(&enabled_ids));
     }
 
-    // Auto-add telegram tools when bridge configured but skill not enabled
-    if !enabled_ids.contains(&"telegram".to_string()) {
+    // Auto-add telegram tools when bridge configured but skill not enabled,
+    // unless the agent pins its own skill list
+    if !enabled_ids.contains(&"telegram".to_string())
+        && skills::agent_skill_list(store, agent_id).is_none()
+    {
         if let Ok(tg_cfg) = crate::engine::telegram::load_telegram_config(app_handle) {
             if !tg_cfg.bot_token.is_empty() {
                 info!("[engine] Auto-adding telegram tools (bridge configured)");
@@ -70,6 +74,11 @@ pub fn build_chat_tools(
         }
     }
 
+    // State tools of the third-party skills active for this agent
+    all_tools.extend(crate::engine::tools::skill_state::agent_definitions(
+        store, agent_id,
+    ));
+
     // Add MCP tools (always included — they're external servers)
     let mcp_tools = crate::engine::tools::mcp_tools(app_handle);
     if !mcp_tools.is_empty() {
@@ -81,6 +90,7 @@ pub fn build_chat_tools(
     let is_core = |name: &str| tool_index::CORE_TOOLS.contains(&name);
     let is_loaded = |name: &str| loaded_tools.contains(name);
     let is_mcp = |name: &str| name.starts_with("mcp_");
+    let is_skill_state = |name: &str| name.starts_with("skill_state_");
     // If the agent policy explicitly lists skill tools, auto-include them
     // so users don't have to rely on request_tools for tools they manually enabled.
     let is_policy_allowed = |name: &str| tool_filter.is_some_and(|f| f.iter().any(|n| n == name));
@@ -96,6 +106,7 @@ pub fn build_chat_tools(
             is_core(name)
                 || is_loaded(name)
                 || is_mcp(name)
+                || is_skill_state(name)
                 || is_policy_allowed(name)
                 || is_skill_required(name)
         })
@@ -136,28 +147,7 @@ pub fn build_runtime_context(
     agent_id: &str,
     user_timezone: &str,
 ) -> String {
-    // Resolve a human-friendly agent name so the model knows its own identity.
-    // The default agent's display name is "Pawz" (set in frontend), but the
-    // backend only stores agent_id. For non-default agents, the agent_id slug
-    // is title-cased ("agent-code-monkey-123" → "Code Monkey").
-    let agent_display_name: String = if agent_id == "default" {
-        "Pawz".to_string()
-    } else {
-        agent_id
-            .strip_prefix("agent-")
-            .unwrap_or(agent_id)
-            .split('-')
-            .filter(|s| !s.chars().all(|c| c.is_ascii_digit()))
-            .map(|w| {
-                let mut chars = w.chars();
-                match chars.next() {
-                    Some(c) => c.to_uppercase().to_string() + chars.as_str(),
-                    None => String::new(),
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" ")
-    };
+    let agent_display_name = agent_display_name(agent_id);
     let now_utc = chrono::Utc::now();
     let time_str = if let Ok(tz) = user_timezone.parse::<chrono_tz::Tz>() {
         let local: chrono::DateTime<chrono_tz::Tz> = now_utc.with_timezone(&tz);
@@ -799,7 +789,7 @@ pub fn grounding_check(messages: &[Message], response_text: &str) -> Option<Stri
         "fetching",
         "running",
     ];
-    let response_start = &response_lower[..response_lower.len().min(200)];
+    let response_start = safe_truncate(&response_lower, 200);
     if tool_phrases.iter().any(|p| response_start.contains(p)) {
         return None;
     }
@@ -1474,3 +1464,50 @@ pub fn process_attachments(
 
     last_msg.content = MessageContent::Blocks(blocks);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILLS: &str = "## Skill: Weather\nUse `weather_lookup` for forecasts.";
+    const MEMORIES: &str = "## Today's Notes\n- User prefers Celsius.";
+
+    fn compose(template: &str) -> String {
+        let mut todays_memories = Some(MEMORIES.to_string());
+        let mut skill_instructions = SKILLS.to_string();
+        let base = apply_prompt_template(
+            template,
+            Some("You are a helpful assistant."),
+            "agent-weather-bot-42",
+            "UTC",
+            &mut todays_memories,
+            &mut skill_instructions,
+        );
+        compose_chat_system_prompt(
+            base.as_deref(),
+            String::new(),
+            None,
+            todays_memories.as_deref(),
+            &skill_instructions,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn template_placed_sections_are_not_appended_again() {
+        let prompt = compose("I am {agent_name}.\n{skills}\n{memory_summary}\n{base_prompt}");
+        assert!(prompt.starts_with("I am Weather Bot.\n## Skill: Weather"));
+        assert_eq!(prompt.matches(SKILLS).count(), 1);
+        assert_eq!(prompt.matches(MEMORIES).count(), 1);
+        assert_eq!(prompt.matches("You are a helpful assistant.").count(), 1);
+    }
+
+    #[test]
+    fn sections_the_template_leaves_out_are_still_appended() {
+        let prompt = compose("{base_prompt}\nReply in French.");
+        assert!(prompt.starts_with("You are a helpful assistant.\nReply in French."));
+        assert_eq!(prompt.matches(SKILLS).count(), 1);
+        assert_eq!(prompt.matches(MEMORIES).count(), 1);
+        assert!(prompt.find(SKILLS) > prompt.find("Reply in French."));
+    }
+}
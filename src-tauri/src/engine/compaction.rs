@@ -77,7 +77,7 @@ fn build_summary_prompt(messages: &[StoredMessage]) -> Vec<Message> {
         if msg.role == "tool" {
             let name = msg.name.as_deref().unwrap_or("unknown");
             let output_preview = if msg.content.len() > 200 {
-                format!("{}… (truncated)", &msg.content[..200])
+                format!("{}… (truncated)", safe_truncate(&msg.content, 200))
             } else {
                 msg.content.clone()
             };
@@ -87,7 +87,7 @@ fn build_summary_prompt(messages: &[StoredMessage]) -> Vec<Message> {
             ));
         } else {
             let content_preview = if msg.content.len() > 500 {
-                format!("{}… (truncated)", &msg.content[..500])
+                format!("{}… (truncated)", safe_truncate(&msg.content, 500))
             } else {
                 msg.content.clone()
             };
@@ -255,6 +255,8 @@ pub async fn compact_session(
         tool_call_id: None,
         name: Some("session_compaction".to_string()),
         created_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        incomplete: false,
+        reasoning: None,
     };
     store.add_message(&summary_msg)?;
 
@@ -366,6 +368,8 @@ mod tests {
             tool_call_id: None,
             name: None,
             created_at: "2025-01-01".into(),
+            incomplete: false,
+            reasoning: None,
         };
         let tokens = estimate_message_tokens(&msg);
         assert!(tokens > 0);
@@ -384,6 +388,8 @@ mod tests {
                 tool_call_id: None,
                 name: None,
                 created_at: "2025-01-01".into(),
+                incomplete: false,
+                reasoning: None,
             })
             .collect();
 
@@ -405,6 +411,8 @@ mod tests {
                 tool_call_id: None,
                 name: None,
                 created_at: "2025-01-01".into(),
+                incomplete: false,
+                reasoning: None,
             })
             .collect();
 
@@ -423,6 +431,8 @@ mod tests {
             tool_call_id: None,
             name: None,
             created_at: "2025-01-01".into(),
+            incomplete: false,
+            reasoning: None,
         };
         let tokens = estimate_message_tokens(&msg);
         // (12 + 33) / 4 + 4 = 11 + 4 = 15
@@ -440,6 +450,8 @@ mod tests {
             tool_call_id: None,
             name: None,
             created_at: "2025-01-01".into(),
+            incomplete: false,
+            reasoning: None,
         };
         let tokens = estimate_message_tokens(&msg);
         assert_eq!(tokens, 4); // 0/4 + 4 = 4 (overhead)
@@ -458,6 +470,8 @@ mod tests {
                 tool_call_id: None,
                 name: None,
                 created_at: "2025-01-01".into(),
+                incomplete: false,
+                reasoning: None,
             })
             .collect();
 
@@ -480,6 +494,8 @@ mod tests {
                 tool_call_id: None,
                 name: None,
                 created_at: "2025-01-01".into(),
+                incomplete: false,
+                reasoning: None,
             })
             .collect();
 
@@ -515,6 +531,8 @@ mod tests {
             tool_call_id: None,
             name: None,
             created_at: "2025-01-01".into(),
+            incomplete: false,
+            reasoning: None,
         };
         let tokens = estimate_message_tokens(&msg);
         assert_eq!(tokens, (20 + 4000) / 4 + 4); // 1005 + 4 = 1009
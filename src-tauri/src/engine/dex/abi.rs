@@ -284,6 +284,91 @@ pub(crate) fn encode_transfer(to: &[u8; 20], amount: &[u8; 32]) -> Vec<u8> {
     data
 }
 
+/// ABI-encode ERC-2612 DOMAIN_SEPARATOR() call
+pub(crate) fn encode_domain_separator() -> Vec<u8> {
+    function_selector("DOMAIN_SEPARATOR()").to_vec()
+}
+
+/// ABI-encode ERC-2612 nonces(owner) call
+pub(crate) fn encode_nonces(owner: &[u8; 20]) -> Vec<u8> {
+    let mut data = function_selector("nonces(address)").to_vec();
+    data.extend_from_slice(&abi_encode_address(owner));
+    data
+}
+
+/// ABI-encode EIP-712 version() call (not every permit token exposes it)
+pub(crate) fn encode_version() -> Vec<u8> {
+    function_selector("version()").to_vec()
+}
+
+/// Encode SwapRouter02.selfPermit — redeems an ERC-2612 permit for the router
+/// selfPermit(address token, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)
+pub(crate) fn encode_self_permit(
+    token: &[u8; 20],
+    value: &[u8; 32],
+    deadline: u64,
+    v: u8,
+    r: &[u8; 32],
+    s: &[u8; 32],
+) -> Vec<u8> {
+    let selector = function_selector("selfPermit(address,uint256,uint256,uint8,bytes32,bytes32)");
+    let mut data = selector.to_vec();
+    data.extend_from_slice(&abi_encode_address(token));
+    data.extend_from_slice(&abi_encode_uint256(value));
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&deadline.to_be_bytes());
+    data.extend_from_slice(&word);
+    let mut word = [0u8; 32];
+    word[31] = v;
+    data.extend_from_slice(&word);
+    data.extend_from_slice(r);
+    data.extend_from_slice(s);
+    data
+}
+
+/// Encode SwapRouter02.unwrapWETH9 — unwraps the router's WETH balance to ETH
+/// unwrapWETH9(uint256 amountMinimum, address recipient)
+pub(crate) fn encode_unwrap_weth9(amount_minimum: &[u8; 32], recipient: &[u8; 20]) -> Vec<u8> {
+    let mut data = function_selector("unwrapWETH9(uint256,address)").to_vec();
+    data.extend_from_slice(&abi_encode_uint256(amount_minimum));
+    data.extend_from_slice(&abi_encode_address(recipient));
+    data
+}
+
+/// Encode SwapRouter02.multicall with a deadline
+/// multicall(uint256 deadline, bytes[] data)
+pub(crate) fn encode_multicall(deadline: u64, calls: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = function_selector("multicall(uint256,bytes[])").to_vec();
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&deadline.to_be_bytes());
+    data.extend_from_slice(&word);
+    let mut word = [0u8; 32];
+    word[31] = 64; // offset to bytes[] = 0x40
+    data.extend_from_slice(&word);
+
+    // bytes[]: length, then one offset per element (relative to the first offset), then elements
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(calls.len() as u64).to_be_bytes());
+    data.extend_from_slice(&word);
+    let mut offset = calls.len() * 32;
+    let mut tail = Vec::new();
+    for call in calls {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&(offset as u64).to_be_bytes());
+        data.extend_from_slice(&word);
+
+        let mut len = [0u8; 32];
+        len[24..].copy_from_slice(&(call.len() as u64).to_be_bytes());
+        tail.extend_from_slice(&len);
+        tail.extend_from_slice(call);
+        let padding = (32 - call.len() % 32) % 32;
+        tail.extend(std::iter::repeat(0u8).take(padding));
+        offset += 32 + call.len() + padding;
+    }
+    data.extend_from_slice(&tail);
+    data
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,6 +395,28 @@ mod tests {
         assert_eq!(hex_encode(&sel), "0x095ea7b3");
     }
 
+    #[test]
+    fn router_permit_selectors() {
+        // SwapRouter02 selfPermit and multicall(uint256,bytes[])
+        let sel = function_selector("selfPermit(address,uint256,uint256,uint8,bytes32,bytes32)");
+        assert_eq!(hex_encode(&sel), "0xf3995c67");
+        let sel = function_selector("multicall(uint256,bytes[])");
+        assert_eq!(hex_encode(&sel), "0x5ae401dc");
+    }
+
+    #[test]
+    fn multicall_encodes_dynamic_array() {
+        let data = encode_multicall(1, &[vec![0xaa; 4], vec![0xbb; 36]]);
+        // selector + deadline + offset + length + 2 offsets + (len + 32) + (len + 64)
+        assert_eq!(data.len(), 4 + 32 * 5 + 64 + 96);
+        assert_eq!(data[4 + 31], 1);
+        assert_eq!(data[4 + 32 + 31], 0x40);
+        assert_eq!(data[4 + 64 + 31], 2);
+        assert_eq!(data[4 + 96 + 31], 0x40); // first element after the two offsets
+        assert_eq!(data[4 + 128 + 31], 0x80); // second after the first's 64 bytes
+        assert_eq!(&data[4 + 192..4 + 196], &[0xaa; 4]);
+    }
+
     #[test]
     fn abi_encode_address_padding() {
         let addr = [0u8; 20];
@@ -361,4 +468,33 @@ mod tests {
         // token_a(20) + fee(3) + token_b(20) + fee(3) + token_c(20) = 66 bytes
         assert_eq!(path.len(), 66);
     }
+
+    #[test]
+    fn encode_quote_exact_input_pads_the_path() {
+        let path = build_multihop_path(&[&[1u8; 20], &[2u8; 20], &[3u8; 20]], &[3000, 3000]);
+        let data = encode_quote_exact_input(&path, &[0u8; 32]);
+        assert_eq!(
+            &data[..4],
+            &function_selector("quoteExactInput(bytes,uint256)")
+        );
+        assert_eq!(data[4 + 31], 0x40); // offset to path
+        assert_eq!(data[4 + 64 + 31], 66); // path length
+                                           // selector + offset + amountIn + length + 66 bytes padded to 96
+        assert_eq!(data.len(), 4 + 32 * 3 + 96);
+        assert_eq!(&data[4 + 96..4 + 96 + 66], path.as_slice());
+    }
+
+    #[test]
+    fn encode_unwrap_weth9_layout() {
+        let mut min = [0u8; 32];
+        min[31] = 7;
+        let data = encode_unwrap_weth9(&min, &[9u8; 20]);
+        assert_eq!(
+            &data[..4],
+            &function_selector("unwrapWETH9(uint256,address)")
+        );
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(data[4 + 31], 7);
+        assert_eq!(&data[4 + 32 + 12..], &[9u8; 20]);
+    }
 }
@@ -0,0 +1,466 @@
+// Paw Agent Engine — DEX Chain Registry + Active Chain
+//
+// Per-chain contract addresses (WETH, Uniswap V3 quoter / router) and token
+// lists, plus the persisted active-chain selection made with
+// `dex_switch_chain`.
+//
+// The chain context is resolved once per tool call and injected into the
+// credential map as DEX_CHAIN_ID (and the chain's DEX_RPC_URL), so tools read
+// it instead of calling eth_chainId every time:
+//   - active chain set → its validated RPC (DEX_RPC_URL_<id> credential)
+//   - otherwise        → DEX_RPC_URL, chain id detected once and cached
+// Switching chains clears the detection cache.
+
+use super::constants::KNOWN_TOKENS;
+use super::rpc::eth_chain_id;
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::sessions::SessionStore;
+use crate::engine::skills::encrypt_credential;
+use log::info;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// engine_config key holding the active chain id (unset = follow DEX_RPC_URL).
+pub const ACTIVE_CHAIN_KEY: &str = "dex_active_chain";
+
+/// Credential-map key carrying the resolved chain id into the DEX tools.
+pub(crate) const CHAIN_ID_CRED: &str = "DEX_CHAIN_ID";
+
+/// Contracts and tokens for one supported EVM chain.
+pub(crate) struct ChainInfo {
+    pub id: u64,
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub weth: &'static str,
+    pub quoter: &'static str,
+    pub router: &'static str,
+    /// (symbol, address, decimals); "ETH" is the native-asset placeholder.
+    pub tokens: &'static [(&'static str, &'static str, u8)],
+}
+
+const NATIVE_ETH: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+const BASE_TOKENS: &[(&str, &str, u8)] = &[
+    ("ETH", NATIVE_ETH, 18),
+    ("WETH", "0x4200000000000000000000000000000000000006", 18),
+    ("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", 6),
+    ("DAI", "0x50c5725949A6F0c72E6C4a641F24049A917DB0Cb", 18),
+];
+
+const ARBITRUM_TOKENS: &[(&str, &str, u8)] = &[
+    ("ETH", NATIVE_ETH, 18),
+    ("WETH", "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1", 18),
+    ("USDC", "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", 6),
+    ("USDT", "0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9", 6),
+    ("DAI", "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1", 18),
+    ("WBTC", "0x2f2a2543B76A4166549F7aaB2e75Bef0aefC5B0f", 8),
+    ("ARB", "0x912CE59144191C1204E64559FE8253a0e49E6548", 18),
+];
+
+const OPTIMISM_TOKENS: &[(&str, &str, u8)] = &[
+    ("ETH", NATIVE_ETH, 18),
+    ("WETH", "0x4200000000000000000000000000000000000006", 18),
+    ("USDC", "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85", 6),
+    ("USDT", "0x94b008aA00579c1307B0EF2c499aD98a8ce58e58", 6),
+    ("DAI", "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1", 18),
+    ("OP", "0x4200000000000000000000000000000000000042", 18),
+];
+
+const SEPOLIA_TOKENS: &[(&str, &str, u8)] = &[
+    ("ETH", NATIVE_ETH, 18),
+    ("WETH", "0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14", 18),
+    ("USDC", "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238", 6),
+];
+
+pub(crate) static CHAINS: &[ChainInfo] = &[
+    ChainInfo {
+        id: 1,
+        name: "Ethereum Mainnet",
+        aliases: &["ethereum", "mainnet", "eth"],
+        weth: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        quoter: "0x61fFE014bA17989E743c5F6cB21bF9697530B21e",
+        router: "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45",
+        tokens: KNOWN_TOKENS,
+    },
+    ChainInfo {
+        id: 8453,
+        name: "Base",
+        aliases: &["base"],
+        weth: "0x4200000000000000000000000000000000000006",
+        quoter: "0x3d4e44Eb1374240CE5F1B871ab261CD16335B76a",
+        router: "0x2626664c2603336E57B271c5C0b26F421741e481",
+        tokens: BASE_TOKENS,
+    },
+    ChainInfo {
+        id: 42161,
+        name: "Arbitrum One",
+        aliases: &["arbitrum", "arb"],
+        weth: "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+        quoter: "0x61fFE014bA17989E743c5F6cB21bF9697530B21e",
+        router: "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45",
+        tokens: ARBITRUM_TOKENS,
+    },
+    ChainInfo {
+        id: 10,
+        name: "Optimism",
+        aliases: &["optimism", "op"],
+        weth: "0x4200000000000000000000000000000000000006",
+        quoter: "0x61fFE014bA17989E743c5F6cB21bF9697530B21e",
+        router: "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45",
+        tokens: OPTIMISM_TOKENS,
+    },
+    ChainInfo {
+        id: 11155111,
+        name: "Sepolia Testnet",
+        aliases: &["sepolia"],
+        weth: "0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14",
+        quoter: "0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3",
+        router: "0x3bFA4769FB09eefC5a80d6E87c3B9C650f7Ae48E",
+        tokens: SEPOLIA_TOKENS,
+    },
+];
+
+/// rpc_url → detected chain id, for the no-active-chain path.
+static DETECTED_CHAINS: LazyLock<Mutex<HashMap<String, u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn chain_by_id(id: u64) -> Option<&'static ChainInfo> {
+    CHAINS.iter().find(|c| c.id == id)
+}
+
+/// Look a chain up by numeric id, name, or alias (case-insensitive).
+pub(crate) fn find_chain(name_or_id: &str) -> Option<&'static ChainInfo> {
+    let needle = name_or_id.trim();
+    if let Ok(id) = needle.parse::<u64>() {
+        return chain_by_id(id);
+    }
+    CHAINS.iter().find(|c| {
+        c.name.eq_ignore_ascii_case(needle)
+            || c.aliases.iter().any(|a| a.eq_ignore_ascii_case(needle))
+    })
+}
+
+/// Chain for the resolved context in `creds`; Ethereum mainnet when the
+/// chain is unknown (the pre-registry behaviour).
+pub(crate) fn chain_of(creds: &HashMap<String, String>) -> &'static ChainInfo {
+    creds
+        .get(CHAIN_ID_CRED)
+        .and_then(|id| id.parse().ok())
+        .and_then(chain_by_id)
+        .unwrap_or(&CHAINS[0])
+}
+
+/// Chain id from the resolved context, querying the RPC only when the
+/// context couldn't be resolved up front.
+pub(crate) async fn chain_id_of(
+    creds: &HashMap<String, String>,
+    rpc_url: &str,
+) -> EngineResult<u64> {
+    match creds.get(CHAIN_ID_CRED).and_then(|id| id.parse().ok()) {
+        Some(id) => Ok(id),
+        None => eth_chain_id(rpc_url).await,
+    }
+}
+
+/// The registry entry for the chain a swap or quote runs on. Unlike
+/// `chain_of`, never falls back to Ethereum mainnet: an unresolved context is
+/// detected with one eth_chainId call, and a chain without Uniswap contracts
+/// in the registry is an error rather than a swap against the wrong router.
+pub(crate) async fn supported_chain(
+    creds: &HashMap<String, String>,
+    rpc_url: &str,
+) -> EngineResult<&'static ChainInfo> {
+    let id = chain_id_of(creds, rpc_url).await?;
+    chain_by_id(id).ok_or_else(|| {
+        EngineError::Other(format!(
+            "Chain ID {} is not supported for swaps (no Uniswap V3 contracts or token list for it). Supported: {}. Point DEX_RPC_URL at a supported chain or use dex_switch_chain.",
+            id,
+            supported_chains()
+        ))
+    })
+}
+
+/// "ethereum (1), base (8453), …" for error messages.
+fn supported_chains() -> String {
+    CHAINS
+        .iter()
+        .map(|c| format!("{} ({})", c.aliases[0], c.id))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Private relay (e.g. Flashbots Protect) for swap submission on `chain_id`:
+/// DEX_PRIVATE_RELAY_URL_<chain id>, or DEX_PRIVATE_RELAY_URL on Ethereum
+/// mainnet (where Flashbots Protect lives). `None` = public mempool.
+pub(crate) fn private_relay_url(creds: &HashMap<String, String>, chain_id: u64) -> Option<&str> {
+    creds
+        .get(&format!("DEX_PRIVATE_RELAY_URL_{}", chain_id))
+        .or_else(|| {
+            (chain_id == 1)
+                .then(|| creds.get("DEX_PRIVATE_RELAY_URL"))
+                .flatten()
+        })
+        .map(|u| u.trim())
+        .filter(|u| !u.is_empty())
+}
+
+fn rpc_cred_key(chain_id: u64) -> String {
+    format!("DEX_RPC_URL_{}", chain_id)
+}
+
+/// Id of the persisted active chain, if any.
+pub fn active_chain(store: &SessionStore) -> Option<u64> {
+    store
+        .get_config(ACTIVE_CHAIN_KEY)
+        .ok()
+        .flatten()
+        .and_then(|id| id.parse().ok())
+}
+
+/// Persist `chain_id` (already validated against `rpc_url`) as the active
+/// chain, store its RPC URL in the vault, and drop cached chain detections.
+pub fn set_active_chain(
+    store: &SessionStore,
+    vault_key: &[u8],
+    chain_id: u64,
+    rpc_url: &str,
+) -> EngineResult<()> {
+    store.set_skill_credential(
+        "dex",
+        &rpc_cred_key(chain_id),
+        &encrypt_credential(rpc_url, vault_key)?,
+    )?;
+    store.set_config(ACTIVE_CHAIN_KEY, &chain_id.to_string())?;
+    DETECTED_CHAINS.lock().clear();
+    crate::engine::secret_scrub::register_secrets([rpc_url]);
+    Ok(())
+}
+
+/// Forget the active chain — tools follow DEX_RPC_URL again.
+pub fn clear_active_chain(store: &SessionStore) -> EngineResult<()> {
+    store.set_config(ACTIVE_CHAIN_KEY, "")?;
+    DETECTED_CHAINS.lock().clear();
+    Ok(())
+}
+
+/// Credentials with the chain context applied: DEX_RPC_URL pointed at the
+/// active chain's RPC and DEX_CHAIN_ID set. Only queries the RPC the first
+/// time an unpinned DEX_RPC_URL is seen.
+pub async fn chain_context(
+    store: &SessionStore,
+    base: &HashMap<String, String>,
+) -> EngineResult<HashMap<String, String>> {
+    let mut creds = base.clone();
+    if let Some(id) = active_chain(store) {
+        let rpc_url = base.get(&rpc_cred_key(id)).ok_or_else(|| {
+            EngineError::Other(format!(
+                "No RPC URL stored for the active chain ({}). Run dex_switch_chain with an rpc_url.",
+                id
+            ))
+        })?;
+        creds.insert("DEX_RPC_URL".into(), rpc_url.clone());
+        creds.insert(CHAIN_ID_CRED.into(), id.to_string());
+        return Ok(creds);
+    }
+
+    let Some(rpc_url) = base.get("DEX_RPC_URL") else {
+        return Ok(creds);
+    };
+    let cached = DETECTED_CHAINS.lock().get(rpc_url).copied();
+    let id = match cached {
+        Some(id) => Some(id),
+        None => match eth_chain_id(rpc_url).await {
+            Ok(id) => {
+                DETECTED_CHAINS.lock().insert(rpc_url.clone(), id);
+                Some(id)
+            }
+            // Leave the context unresolved; tools fall back to querying
+            Err(_) => None,
+        },
+    };
+    if let Some(id) = id {
+        creds.insert(CHAIN_ID_CRED.into(), id.to_string());
+    }
+    Ok(creds)
+}
+
+/// Switch the DEX tools to another chain after checking its RPC really
+/// serves that chain.
+pub async fn execute_dex_switch_chain(
+    store: &SessionStore,
+    vault_key: &[u8],
+    args: &serde_json::Value,
+    creds: &HashMap<String, String>,
+) -> EngineResult<String> {
+    let target = args["chain"]
+        .as_str()
+        .ok_or("dex_switch_chain: missing 'chain'")?;
+    if target.trim().eq_ignore_ascii_case("auto") {
+        clear_active_chain(store)?;
+        return Ok("Active chain cleared — DEX tools follow DEX_RPC_URL again.".into());
+    }
+    let chain = find_chain(target).ok_or_else(|| {
+        EngineError::Other(format!(
+            "Unsupported chain '{}'. Supported: {}",
+            target,
+            supported_chains()
+        ))
+    })?;
+
+    let rpc_url = args["rpc_url"]
+        .as_str()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .map(String::from)
+        .or_else(|| creds.get(&rpc_cred_key(chain.id)).cloned())
+        .or_else(|| creds.get("DEX_RPC_URL").cloned())
+        .ok_or_else(|| {
+            EngineError::Other(format!(
+                "No RPC URL known for {}. Pass rpc_url for that chain.",
+                chain.name
+            ))
+        })?;
+
+    let actual = eth_chain_id(&rpc_url).await?;
+    if actual != chain.id {
+        return Err(EngineError::Other(format!(
+            "RPC endpoint serves chain ID {}, not {} ({}). Pass the rpc_url for {}.",
+            actual, chain.id, chain.name, chain.name
+        )));
+    }
+
+    set_active_chain(store, vault_key, chain.id, &rpc_url)?;
+    info!(
+        "[dex] Active chain switched to {} ({})",
+        chain.name, chain.id
+    );
+    Ok(format!(
+        "[ok] Switched to {} (chain ID {}).\n\nWETH: {}\nKnown tokens: {}",
+        chain.name,
+        chain.id,
+        chain.weth,
+        chain
+            .tokens
+            .iter()
+            .map(|(s, _, _)| *s)
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::dex::tokens::{resolve_for_swap, resolve_token};
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    const VAULT_KEY: [u8; 32] = [3u8; 32];
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let store = SessionStore::from_connection(conn);
+        store.init_skill_tables().unwrap();
+        store
+    }
+
+    /// What `get_skill_creds` would return after the switch.
+    fn stored_creds(store: &SessionStore, chain_id: u64, rpc_url: &str) -> HashMap<String, String> {
+        assert!(store
+            .get_skill_credential("dex", &rpc_cred_key(chain_id))
+            .unwrap()
+            .is_some());
+        HashMap::from([
+            ("DEX_RPC_URL".to_string(), "http://mainnet".to_string()),
+            (rpc_cred_key(chain_id), rpc_url.to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn switching_chains_updates_weth_and_token_list() {
+        let store = test_store();
+
+        set_active_chain(&store, &VAULT_KEY, 1, "http://mainnet").unwrap();
+        let creds = chain_context(&store, &stored_creds(&store, 1, "http://mainnet"))
+            .await
+            .unwrap();
+        let chain = chain_of(&creds);
+        assert_eq!(chain.id, 1);
+        assert_eq!(
+            resolve_for_swap(chain, "ETH").unwrap().0,
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+        );
+        assert!(resolve_token(chain, "PEPE").is_ok());
+
+        set_active_chain(&store, &VAULT_KEY, 8453, "http://base").unwrap();
+        assert_eq!(active_chain(&store), Some(8453));
+        let creds = chain_context(&store, &stored_creds(&store, 8453, "http://base"))
+            .await
+            .unwrap();
+        let chain = chain_of(&creds);
+        assert_eq!(creds["DEX_RPC_URL"], "http://base");
+        assert_eq!(chain.name, "Base");
+        assert_eq!(
+            resolve_for_swap(chain, "ETH").unwrap().0,
+            "0x4200000000000000000000000000000000000006"
+        );
+        assert_eq!(
+            resolve_token(chain, "USDC").unwrap().0,
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+        );
+        // Mainnet-only tokens are no longer offered
+        assert!(resolve_token(chain, "PEPE").is_err());
+    }
+
+    #[tokio::test]
+    async fn swaps_refuse_chains_outside_the_registry() {
+        // Resolved from the chain context, so no RPC call is made
+        let base = HashMap::from([(CHAIN_ID_CRED.to_string(), "8453".to_string())]);
+        let chain = supported_chain(&base, "http://unused").await.unwrap();
+        assert_eq!(chain.router, chain_by_id(8453).unwrap().router);
+
+        let polygon = HashMap::from([(CHAIN_ID_CRED.to_string(), "137".to_string())]);
+        let err = supported_chain(&polygon, "http://unused")
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Chain ID 137 is not supported"), "{}", err);
+        // chain_of would have silently used mainnet's contracts
+        assert_eq!(chain_of(&polygon).id, 1);
+    }
+
+    #[test]
+    fn private_relay_is_per_chain() {
+        let creds = HashMap::from([
+            (
+                "DEX_PRIVATE_RELAY_URL".to_string(),
+                "https://rpc.flashbots.net/fast".to_string(),
+            ),
+            (
+                "DEX_PRIVATE_RELAY_URL_8453".to_string(),
+                "https://relay.base.example".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            private_relay_url(&creds, 1),
+            Some("https://rpc.flashbots.net/fast")
+        );
+        assert_eq!(
+            private_relay_url(&creds, 8453),
+            Some("https://relay.base.example")
+        );
+        // The mainnet relay is never used for other chains
+        assert_eq!(private_relay_url(&creds, 42161), None);
+    }
+
+    #[test]
+    fn chains_resolve_by_name_alias_or_id() {
+        assert_eq!(find_chain("arbitrum").unwrap().id, 42161);
+        assert_eq!(find_chain("BASE").unwrap().id, 8453);
+        assert_eq!(find_chain("10").unwrap().name, "Optimism");
+        assert!(find_chain("solana").is_none());
+    }
+}
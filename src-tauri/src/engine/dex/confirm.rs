@@ -0,0 +1,215 @@
+// Paw Agent Engine — DEX Confirmation Depth
+//
+// A mined receipt is one confirmation, but on reorg-prone chains that block
+// can still be dropped. wait_for_confirmation keeps polling until the
+// transaction is N blocks deep (receipt blockNumber vs. the chain head),
+// re-reading the receipt each time so a reorg that drops or moves the
+// transaction resets the count. N comes from DEX_CONFIRMATIONS_<chain id>,
+// else DEX_CONFIRMATIONS, else 1 (the receipt alone). The whole wait is
+// bounded by `confirm_timeout_secs` when the tool call sets it.
+
+use super::rpc::{
+    eth_block_number, eth_get_transaction_receipt, next_poll_delay, wait_for_receipt, ReceiptStatus,
+};
+use log::info;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Confirmations required when none are configured: the receipt alone.
+pub(crate) const DEFAULT_CONFIRMATIONS: u64 = 1;
+/// Upper bound on a configured depth (~13 minutes on Ethereum mainnet).
+const MAX_CONFIRMATIONS: u64 = 64;
+/// Default time allowed for the transaction to be mined…
+const MINING_TIMEOUT: Duration = Duration::from_secs(120);
+/// …plus this much per extra confirmation (one 12s Ethereum block).
+const PER_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(12);
+/// Bounds on a `confirm_timeout_secs` argument.
+const MIN_TIMEOUT_SECS: u64 = 5;
+const MAX_TIMEOUT_SECS: u64 = 1800;
+
+/// Confirmation depth for `chain_id`: DEX_CONFIRMATIONS_<chain id>, else
+/// DEX_CONFIRMATIONS, else DEFAULT_CONFIRMATIONS. Clamped to 1..=64.
+pub(crate) fn required_confirmations(creds: &HashMap<String, String>, chain_id: u64) -> u64 {
+    creds
+        .get(&format!("DEX_CONFIRMATIONS_{}", chain_id))
+        .or_else(|| creds.get("DEX_CONFIRMATIONS"))
+        .and_then(|n| n.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CONFIRMATIONS)
+        .clamp(1, MAX_CONFIRMATIONS)
+}
+
+/// How many blocks deep a transaction mined in `receipt_block` is at `head`
+/// (1 when its block is the head; 0 if the node's head lags behind it).
+pub(crate) fn confirmation_count(receipt_block: u64, head: u64) -> u64 {
+    head.checked_sub(receipt_block).map_or(0, |depth| depth + 1)
+}
+
+/// Whether a transaction mined in `receipt_block` is `required` blocks deep.
+pub(crate) fn is_confirmed(receipt_block: u64, head: u64, required: u64) -> bool {
+    confirmation_count(receipt_block, head) >= required
+}
+
+/// Tool output of a call that may broadcast a transaction.
+pub struct TxReport {
+    pub text: String,
+    /// A transaction was broadcast and hasn't reverted (confirmed or still
+    /// pending) — repeating the call would move funds a second time.
+    pub landed: bool,
+}
+
+/// Outcome of waiting for a submitted transaction.
+pub(crate) struct Confirmation {
+    /// "confirmed", "reverted", or "pending" (not mined or not yet deep enough).
+    pub status: &'static str,
+    /// The latest receipt seen, once mined.
+    pub receipt: Option<serde_json::Value>,
+    pub confirmations: u64,
+    pub required: u64,
+}
+
+impl Confirmation {
+    pub(crate) fn is_confirmed(&self) -> bool {
+        self.status == "confirmed"
+    }
+
+    /// Status for tool output, with the depth when more than one
+    /// confirmation is required.
+    pub(crate) fn describe(&self) -> String {
+        if self.required <= 1 || self.status == "reverted" || self.receipt.is_none() {
+            self.status.to_string()
+        } else {
+            format!(
+                "{} ({}/{} confirmations)",
+                self.status, self.confirmations, self.required
+            )
+        }
+    }
+}
+
+fn receipt_block(receipt: &serde_json::Value) -> Option<u64> {
+    let hex = receipt["blockNumber"].as_str()?;
+    u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok()
+}
+
+/// How long to wait for a transaction: `args["confirm_timeout_secs"]`
+/// (clamped to 5s..30min), else two minutes plus 12s per extra confirmation.
+pub(crate) fn confirm_timeout(args: &serde_json::Value, required: u64) -> Duration {
+    match args["confirm_timeout_secs"].as_u64() {
+        Some(secs) => Duration::from_secs(secs.clamp(MIN_TIMEOUT_SECS, MAX_TIMEOUT_SECS)),
+        None => MINING_TIMEOUT + PER_CONFIRMATION_TIMEOUT * (required.max(1) - 1) as u32,
+    }
+}
+
+/// Wait until `tx_hash` is `required` blocks deep, reverts, or `max_wait`
+/// runs out (then "pending", with whatever depth was reached).
+pub(crate) async fn wait_for_confirmation(
+    rpc_url: &str,
+    tx_hash: &str,
+    required: u64,
+    max_wait: Duration,
+) -> Confirmation {
+    let deadline = tokio::time::Instant::now() + max_wait;
+    let mut outcome = Confirmation {
+        status: "pending",
+        receipt: None,
+        confirmations: 0,
+        required,
+    };
+    let mut latest = match wait_for_receipt(rpc_url, tx_hash, max_wait).await {
+        ReceiptStatus::Confirmed(receipt) => Some(receipt),
+        ReceiptStatus::Reverted(receipt) => {
+            outcome.status = "reverted";
+            outcome.receipt = Some(receipt);
+            return outcome;
+        }
+        ReceiptStatus::Pending => return outcome,
+    };
+    let mut delay = Duration::from_secs(1);
+    loop {
+        if let Some(receipt) = latest.take() {
+            let mined_in = receipt_block(&receipt);
+            outcome.receipt = Some(receipt);
+            if required <= 1 {
+                outcome.confirmations = 1;
+                outcome.status = "confirmed";
+                return outcome;
+            }
+            if let (Some(mined_in), Ok(head)) = (mined_in, eth_block_number(rpc_url).await) {
+                let depth = confirmation_count(mined_in, head);
+                if depth != outcome.confirmations {
+                    info!("[dex] {} — {}/{} confirmations", tx_hash, depth, required);
+                }
+                outcome.confirmations = depth;
+                if is_confirmed(mined_in, head, required) {
+                    outcome.status = "confirmed";
+                    return outcome;
+                }
+            }
+        }
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return outcome;
+        }
+        tokio::time::sleep(delay.min(remaining)).await;
+        delay = next_poll_delay(delay);
+        // Re-read the receipt every round: a reorg can drop or move it
+        match eth_get_transaction_receipt(rpc_url, tx_hash).await {
+            Ok(Some(receipt)) if receipt["status"].as_str() != Some("0x1") => {
+                outcome.status = "reverted";
+                outcome.receipt = Some(receipt);
+                return outcome;
+            }
+            Ok(Some(receipt)) => latest = Some(receipt),
+            // Dropped by a reorg — wait for it to be mined again
+            Ok(None) => {
+                outcome.receipt = None;
+                outcome.confirmations = 0;
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmed_once_n_blocks_deep() {
+        // Mined in the head block: one confirmation
+        assert_eq!(confirmation_count(100, 100), 1);
+        assert!(is_confirmed(100, 100, 1));
+        assert!(!is_confirmed(100, 100, 3));
+        assert!(!is_confirmed(100, 101, 3));
+        assert!(is_confirmed(100, 102, 3));
+        assert!(is_confirmed(100, 150, 3));
+        // A lagging node whose head is behind the receipt block
+        assert_eq!(confirmation_count(100, 99), 0);
+        assert!(!is_confirmed(100, 99, 1));
+    }
+
+    #[test]
+    fn timeout_comes_from_args_or_depth() {
+        let none = serde_json::json!({});
+        assert_eq!(confirm_timeout(&none, 1), Duration::from_secs(120));
+        assert_eq!(confirm_timeout(&none, 6), Duration::from_secs(180));
+        let set = |secs: u64| serde_json::json!({ "confirm_timeout_secs": secs });
+        assert_eq!(confirm_timeout(&set(600), 1), Duration::from_secs(600));
+        assert_eq!(confirm_timeout(&set(0), 1), Duration::from_secs(5));
+        assert_eq!(confirm_timeout(&set(99_999), 12), Duration::from_secs(1800));
+    }
+
+    #[test]
+    fn depth_is_configured_per_chain() {
+        let mut creds = HashMap::new();
+        assert_eq!(required_confirmations(&creds, 1), DEFAULT_CONFIRMATIONS);
+        creds.insert("DEX_CONFIRMATIONS".to_string(), "3".to_string());
+        creds.insert("DEX_CONFIRMATIONS_8453".to_string(), "12".to_string());
+        assert_eq!(required_confirmations(&creds, 1), 3);
+        assert_eq!(required_confirmations(&creds, 8453), 12);
+        creds.insert("DEX_CONFIRMATIONS".to_string(), "0".to_string());
+        assert_eq!(required_confirmations(&creds, 1), 1);
+        creds.insert("DEX_CONFIRMATIONS".to_string(), "1000".to_string());
+        assert_eq!(required_confirmations(&creds, 1), MAX_CONFIRMATIONS);
+    }
+}
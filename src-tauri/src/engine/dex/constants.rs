@@ -1,7 +1,7 @@
 // Paw Agent Engine — DEX Constants
 // Well-known token addresses, contract addresses, and configuration defaults.
 
-/// Well-known ERC-20 tokens on Ethereum mainnet
+/// Well-known ERC-20 tokens on Ethereum mainnet (other chains: see `chains`)
 pub(crate) const KNOWN_TOKENS: &[(&str, &str, u8)] = &[
     ("ETH", "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE", 18),
     ("WETH", "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", 18),
@@ -17,11 +17,6 @@ pub(crate) const KNOWN_TOKENS: &[(&str, &str, u8)] = &[
     ("AAVE", "0x7Fc66500c84A76Ad7e9c93437bFc5Ac33E2DDaE9", 18),
 ];
 
-/// Uniswap V3 contract addresses (Ethereum mainnet)
-pub(crate) const UNISWAP_QUOTER_V2: &str = "0x61fFE014bA17989E743c5F6cB21bF9697530B21e";
-pub(crate) const UNISWAP_SWAP_ROUTER_02: &str = "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45";
-pub(crate) const WETH_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
-
 /// Default slippage tolerance (0.5%)
 pub(crate) const DEFAULT_SLIPPAGE_BPS: u64 = 50;
 /// Maximum allowed slippage (5%)
@@ -31,7 +26,7 @@ pub(crate) const DEFAULT_FEE_TIER: u64 = 3000;
 
 /// ERC-20 Transfer event topic: keccak256("Transfer(address,address,uint256)")
 pub(crate) const TRANSFER_EVENT_TOPIC: &str =
-    "0xddf252ad1be2c89b69c2b068fc378daa0952e8da11aeba5c4f27ead9083c756cc2";
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
 
 /// Returns the block explorer base TX URL for a given EVM chain ID.
 /// Used to build transaction links in tool output.
@@ -48,6 +43,20 @@ pub(crate) fn explorer_tx_url(chain_id: u64) -> &'static str {
     }
 }
 
+/// Returns the Etherscan-compatible explorer API base for a given EVM chain
+/// ID, or `None` when the chain has no known explorer API.
+pub(crate) fn explorer_api_url(chain_id: u64) -> Option<&'static str> {
+    match chain_id {
+        1 => Some("https://api.etherscan.io/api"),
+        11155111 => Some("https://api-sepolia.etherscan.io/api"),
+        137 => Some("https://api.polygonscan.com/api"),
+        42161 => Some("https://api.arbiscan.io/api"),
+        10 => Some("https://api-optimistic.etherscan.io/api"),
+        8453 => Some("https://api.basescan.org/api"),
+        _ => None,
+    }
+}
+
 /// Returns a human-readable network name for a given EVM chain ID.
 pub(crate) fn chain_name(chain_id: u64) -> &'static str {
     match chain_id {
@@ -0,0 +1,439 @@
+// Paw Agent Engine — EIP-712 Typed Data Signing
+//
+// Implements the EIP-712 hashing rules (encodeType, hashStruct,
+// domainSeparator) and signs the resulting digest with the wallet key for
+// dApp sign-in and gasless approvals (EIP-2612 permit). Nothing is
+// broadcast; the signature is only returned.
+//
+//   digest = keccak256(0x19 0x01 ‖ domainSeparator ‖ hashStruct(message))
+
+use super::chains::CHAIN_ID_CRED;
+use super::primitives::{
+    address_from_pubkey, hex_decode, hex_encode, keccak256, parse_address, parse_u256_decimal,
+};
+use crate::atoms::error::{EngineError, EngineResult};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Canonical EIP712Domain fields, used when `types` doesn't declare them.
+const DOMAIN_FIELDS: &[(&str, &str)] = &[
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// An `eth_signTypedData_v4` payload.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    #[serde(default)]
+    pub domain: serde_json::Value,
+    #[serde(default)]
+    pub message: serde_json::Value,
+}
+
+fn err(msg: impl Into<String>) -> EngineError {
+    EngineError::Other(format!("EIP-712: {}", msg.into()))
+}
+
+/// `Foo[]` / `Foo[3]` → `Foo`.
+fn base_type(ty: &str) -> &str {
+    ty.split('[').next().unwrap_or(ty)
+}
+
+impl TypedData {
+    /// Parse from a JSON object or a JSON-encoded string.
+    pub(crate) fn from_value(value: &serde_json::Value) -> EngineResult<Self> {
+        let mut data: TypedData = match value {
+            serde_json::Value::String(s) => serde_json::from_str(s),
+            other => serde_json::from_value(other.clone()),
+        }
+        .map_err(|e| err(format!("invalid typed data: {}", e)))?;
+
+        if !data.types.contains_key(DOMAIN_TYPE) {
+            let fields = DOMAIN_FIELDS
+                .iter()
+                .filter(|(name, _)| !data.domain[*name].is_null())
+                .map(|(name, ty)| TypedField {
+                    name: name.to_string(),
+                    ty: ty.to_string(),
+                })
+                .collect();
+            data.types.insert(DOMAIN_TYPE.into(), fields);
+        }
+        if !data.types.contains_key(&data.primary_type) {
+            return Err(err(format!(
+                "primaryType '{}' is not defined in types",
+                data.primary_type
+            )));
+        }
+        Ok(data)
+    }
+
+    fn fields(&self, ty: &str) -> EngineResult<&Vec<TypedField>> {
+        self.types
+            .get(ty)
+            .ok_or_else(|| err(format!("unknown type '{}'", ty)))
+    }
+
+    fn collect_dependencies(&self, ty: &str, found: &mut BTreeSet<String>) {
+        let ty = base_type(ty);
+        if found.contains(ty) {
+            return;
+        }
+        let Some(fields) = self.types.get(ty) else {
+            return;
+        };
+        found.insert(ty.to_string());
+        for field in fields {
+            self.collect_dependencies(&field.ty, found);
+        }
+    }
+
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`
+    pub(crate) fn encode_type(&self, primary: &str) -> EngineResult<String> {
+        let mut deps = BTreeSet::new();
+        self.collect_dependencies(primary, &mut deps);
+        deps.remove(primary);
+
+        let mut out = String::new();
+        for ty in std::iter::once(primary.to_string()).chain(deps) {
+            let fields = self
+                .fields(&ty)?
+                .iter()
+                .map(|f| format!("{} {}", f.ty, f.name))
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(&format!("{}({})", ty, fields));
+        }
+        Ok(out)
+    }
+
+    pub(crate) fn type_hash(&self, ty: &str) -> EngineResult<[u8; 32]> {
+        Ok(keccak256(self.encode_type(ty)?.as_bytes()))
+    }
+
+    /// keccak256(typeHash ‖ encodeData(value))
+    pub(crate) fn hash_struct(
+        &self,
+        ty: &str,
+        value: &serde_json::Value,
+    ) -> EngineResult<[u8; 32]> {
+        let mut encoded = self.type_hash(ty)?.to_vec();
+        for field in self.fields(ty)? {
+            encoded.extend_from_slice(&self.encode_value(&field.ty, &value[&field.name])?);
+        }
+        Ok(keccak256(&encoded))
+    }
+
+    fn encode_value(&self, ty: &str, value: &serde_json::Value) -> EngineResult<[u8; 32]> {
+        if let Some(open) = ty.rfind('[').filter(|_| ty.ends_with(']')) {
+            let items = value
+                .as_array()
+                .ok_or_else(|| err(format!("expected an array for '{}'", ty)))?;
+            let fixed_len = &ty[open + 1..ty.len() - 1];
+            if !fixed_len.is_empty() && fixed_len.parse::<usize>().ok() != Some(items.len()) {
+                return Err(err(format!("'{}' needs exactly {} items", ty, fixed_len)));
+            }
+            let mut encoded = Vec::with_capacity(items.len() * 32);
+            for item in items {
+                encoded.extend_from_slice(&self.encode_value(&ty[..open], item)?);
+            }
+            return Ok(keccak256(&encoded));
+        }
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value);
+        }
+        encode_atomic(ty, value)
+    }
+
+    pub(crate) fn domain_separator(&self) -> EngineResult<[u8; 32]> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// The digest wallets sign for `eth_signTypedData_v4`.
+    pub(crate) fn signing_digest(&self) -> EngineResult<[u8; 32]> {
+        let mut buf = vec![0x19u8, 0x01];
+        buf.extend_from_slice(&self.domain_separator()?);
+        buf.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        Ok(keccak256(&buf))
+    }
+}
+
+fn value_str<'a>(ty: &str, value: &'a serde_json::Value) -> EngineResult<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| err(format!("expected a string for '{}'", ty)))
+}
+
+/// Raw bytes of a hex string; unlike `hex_decode`, "0x" is empty.
+fn hex_bytes(ty: &str, s: &str) -> EngineResult<Vec<u8>> {
+    if s.trim_start_matches("0x").is_empty() {
+        return Ok(Vec::new());
+    }
+    hex_decode(s).map_err(|_| err(format!("invalid hex for '{}'", ty)))
+}
+
+fn encode_atomic(ty: &str, value: &serde_json::Value) -> EngineResult<[u8; 32]> {
+    let mut word = [0u8; 32];
+    match ty {
+        "string" => Ok(keccak256(value_str(ty, value)?.as_bytes())),
+        "bytes" => Ok(keccak256(&hex_bytes(ty, value_str(ty, value)?)?)),
+        "bool" => {
+            let b = value
+                .as_bool()
+                .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+                .ok_or_else(|| err("expected a boolean"))?;
+            word[31] = b as u8;
+            Ok(word)
+        }
+        "address" => {
+            word[12..].copy_from_slice(&parse_address(value_str(ty, value)?)?);
+            Ok(word)
+        }
+        _ if ty.starts_with("bytes") => {
+            let size: usize = ty[5..]
+                .parse()
+                .map_err(|_| err(format!("unsupported type '{}'", ty)))?;
+            let bytes = hex_bytes(ty, value_str(ty, value)?)?;
+            if size == 0 || size > 32 || bytes.len() > size {
+                return Err(err(format!("value too long for '{}'", ty)));
+            }
+            word[..bytes.len()].copy_from_slice(&bytes);
+            Ok(word)
+        }
+        _ if ty.starts_with("uint") => encode_integer(ty, value, false),
+        _ if ty.starts_with("int") => encode_integer(ty, value, true),
+        _ => Err(err(format!("unsupported type '{}'", ty))),
+    }
+}
+
+/// Numbers, decimal strings, or 0x-hex strings → 32-byte big-endian word
+/// (two's complement for negative `intN`).
+fn encode_integer(ty: &str, value: &serde_json::Value, signed: bool) -> EngineResult<[u8; 32]> {
+    let text = match value {
+        serde_json::Value::Number(n) if n.is_u64() || n.is_i64() => n.to_string(),
+        serde_json::Value::String(s) => s.trim().to_string(),
+        _ => return Err(err(format!("expected an integer for '{}'", ty))),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) if signed => (true, rest),
+        Some(_) => return Err(err(format!("'{}' can't be negative", ty))),
+        None => (false, text.as_str()),
+    };
+    let mut word = if let Some(hex) = digits.strip_prefix("0x") {
+        let bytes = hex_bytes(ty, hex)?;
+        if bytes.len() > 32 {
+            return Err(err(format!("value too large for '{}'", ty)));
+        }
+        let mut w = [0u8; 32];
+        w[32 - bytes.len()..].copy_from_slice(&bytes);
+        w
+    } else if digits.is_empty() {
+        return Err(err(format!("expected an integer for '{}'", ty)));
+    } else {
+        parse_u256_decimal(digits)?
+    };
+    if negative {
+        // Two's complement: invert and add one
+        for b in word.iter_mut() {
+            *b = !*b;
+        }
+        for b in word.iter_mut().rev() {
+            let (sum, overflow) = b.overflowing_add(1);
+            *b = sum;
+            if !overflow {
+                break;
+            }
+        }
+    }
+    Ok(word)
+}
+
+/// 65-byte `r ‖ s ‖ v` signature (v = 27/28).
+pub(crate) fn sign_digest(
+    digest: &[u8; 32],
+    key: &k256::ecdsa::SigningKey,
+) -> EngineResult<[u8; 65]> {
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(digest)
+        .map_err(|e| EngineError::Other(e.to_string()))?;
+    let mut sig = [0u8; 65];
+    sig[..64].copy_from_slice(&signature.to_bytes());
+    sig[64] = 27 + recovery_id.to_byte();
+    Ok(sig)
+}
+
+/// Sign typed data with the wallet key. Never broadcasts anything.
+pub async fn execute_dex_sign_typed_data(
+    args: &serde_json::Value,
+    creds: &HashMap<String, String>,
+) -> EngineResult<String> {
+    let private_key_hex = creds.get("DEX_PRIVATE_KEY").ok_or("Missing private key")?;
+    let raw = args
+        .get("typed_data")
+        .ok_or("dex_sign_typed_data: missing 'typed_data'")?;
+    let data = TypedData::from_value(raw)?;
+    let digest = data.signing_digest()?;
+
+    let pk_bytes = hex_decode(private_key_hex)?;
+    let key = k256::ecdsa::SigningKey::from_slice(&pk_bytes)
+        .map_err(|e| EngineError::Other(e.to_string()))?;
+    let signer = address_from_pubkey(key.verifying_key().to_encoded_point(false).as_bytes());
+    let signature = hex_encode(&sign_digest(&digest, &key)?);
+
+    let domain_name = data.domain["name"].as_str().unwrap_or("(unnamed)");
+    let domain_chain = match &data.domain["chainId"] {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    };
+    let active_chain = creds
+        .get(CHAIN_ID_CRED)
+        .and_then(|id| id.parse::<u64>().ok());
+    let chain_note = match (domain_chain, active_chain) {
+        (Some(domain), Some(active)) if domain != active => format!(
+            "\n\nNote: the domain targets chain {}, but the active chain is {}.",
+            domain, active
+        ),
+        _ => String::new(),
+    };
+    let chain = domain_chain.map_or("not set".to_string(), |id| id.to_string());
+    let permit_note = if data.primary_type.to_lowercase().contains("permit") {
+        "\n\nNote: this is a token permit — whoever holds this signature can move the approved tokens without another on-chain approval."
+    } else {
+        ""
+    };
+    Ok(format!(
+        "[ok] Typed data signed (not broadcast)\n\nSigner: {}\nDomain: {} (chain {})\nPrimary type: {}\nDigest: {}\nSignature: {}{}{}",
+        signer,
+        domain_name,
+        chain,
+        data.primary_type,
+        hex_encode(&digest),
+        signature,
+        chain_note,
+        permit_note
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The "Ether Mail" example from the EIP-712 specification.
+    fn mail_example() -> TypedData {
+        TypedData::from_value(&serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn spec_example_hashes_match() {
+        let data = mail_example();
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex_encode(&data.type_hash("Mail").unwrap()),
+            "0xa0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+        assert_eq!(
+            hex_encode(&data.domain_separator().unwrap()),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex_encode(&data.hash_struct("Mail", &data.message).unwrap()),
+            "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex_encode(&data.signing_digest().unwrap()),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn spec_example_signature_matches() {
+        // The spec's signer key is keccak256("cow")
+        let key = k256::ecdsa::SigningKey::from_slice(&keccak256(b"cow")).unwrap();
+        assert_eq!(
+            address_from_pubkey(key.verifying_key().to_encoded_point(false).as_bytes()),
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        );
+        let digest = mail_example().signing_digest().unwrap();
+        assert_eq!(
+            hex_encode(&sign_digest(&digest, &key).unwrap()),
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+    }
+
+    #[test]
+    fn domain_type_is_inferred_and_integers_encode() {
+        // No EIP712Domain in types: inferred from the domain's keys
+        let data = TypedData::from_value(&serde_json::json!({
+            "types": { "Login": [{ "name": "nonce", "type": "uint256" }] },
+            "primaryType": "Login",
+            "domain": { "name": "Ether Mail", "version": "1", "chainId": 1,
+                        "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC" },
+            "message": { "nonce": "0x2a" }
+        }))
+        .unwrap();
+        assert_eq!(
+            hex_encode(&data.domain_separator().unwrap()),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+
+        let word = encode_integer("int256", &serde_json::json!(-1), true).unwrap();
+        assert_eq!(word, [0xffu8; 32]);
+        let word = encode_integer("uint256", &serde_json::json!("42"), false).unwrap();
+        assert_eq!(word[31], 42);
+        assert!(encode_integer("uint8", &serde_json::json!("-1"), false).is_err());
+    }
+}
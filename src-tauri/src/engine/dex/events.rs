@@ -0,0 +1,150 @@
+// Paw Agent Engine — DEX Event Log Decoding
+//
+// Decodes ERC-20 `Transfer(address indexed from, address indexed to,
+// uint256 value)` logs from a transaction receipt or an eth_getLogs result.
+// ERC-721 transfers share the topic but index the token id (four topics,
+// no data) and are skipped.
+
+use super::constants::TRANSFER_EVENT_TOPIC;
+use super::primitives::hex_decode;
+
+/// One decoded ERC-20 Transfer log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TransferEvent {
+    /// Token contract that emitted the log (lowercase, 0x-prefixed).
+    pub token: String,
+    /// Sender (lowercase, 0x-prefixed); the zero address for mints.
+    pub from: String,
+    /// Recipient (lowercase, 0x-prefixed); the zero address for burns.
+    pub to: String,
+    /// Raw amount as a 32-byte big-endian uint256.
+    pub value: [u8; 32],
+    /// Block and transaction of the log (absent on pending logs).
+    pub block_number: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+/// The address held in an indexed topic (its low 20 bytes).
+fn topic_address(topic: &str) -> Option<String> {
+    let hex = topic.trim_start_matches("0x");
+    (hex.len() == 64).then(|| format!("0x{}", hex[24..].to_lowercase()))
+}
+
+fn decode_transfer(log: &serde_json::Value) -> Option<TransferEvent> {
+    let topics = log["topics"].as_array()?;
+    if topics.len() != 3
+        || !topics[0]
+            .as_str()?
+            .eq_ignore_ascii_case(TRANSFER_EVENT_TOPIC)
+    {
+        return None;
+    }
+    let data = hex_decode(log["data"].as_str()?).ok()?;
+    let value: [u8; 32] = data.get(..32)?.try_into().ok()?;
+    Some(TransferEvent {
+        token: log["address"].as_str()?.to_lowercase(),
+        from: topic_address(topics[1].as_str()?)?,
+        to: topic_address(topics[2].as_str()?)?,
+        value,
+        block_number: log["blockNumber"]
+            .as_str()
+            .and_then(|b| u64::from_str_radix(b.trim_start_matches("0x"), 16).ok()),
+        tx_hash: log["transactionHash"].as_str().map(String::from),
+    })
+}
+
+/// All ERC-20 Transfer events in a receipt (its `logs`) or in a bare array
+/// of logs, in log order. Logs from every token contract are included; use
+/// `TransferEvent::token` to tell them apart.
+pub(crate) fn parse_transfer_logs(receipt: &serde_json::Value) -> Vec<TransferEvent> {
+    receipt["logs"]
+        .as_array()
+        .or_else(|| receipt.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(decode_transfer)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::dex::primitives::{hex_encode, keccak256};
+    use serde_json::json;
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+
+    fn word(n: u128) -> String {
+        format!("0x{:064x}", n)
+    }
+
+    fn padded(address: &str) -> String {
+        format!("0x{:0>64}", address.trim_start_matches("0x"))
+    }
+
+    #[test]
+    fn transfer_topic_is_the_event_signature_hash() {
+        assert_eq!(
+            hex_encode(&keccak256(b"Transfer(address,address,uint256)")),
+            TRANSFER_EVENT_TOPIC
+        );
+    }
+
+    #[test]
+    fn decodes_a_known_transfer_log() {
+        // 250 USDC from 0x28C6…1d60 to 0xd8dA…6045
+        let receipt = json!({
+            "status": "0x1",
+            "logs": [{
+                "address": USDC,
+                "topics": [
+                    TRANSFER_EVENT_TOPIC,
+                    padded("28C6c06298d514Db089934071355E5743bf21d60"),
+                    padded("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"),
+                ],
+                "data": word(250_000_000),
+                "blockNumber": "0x12a05f2",
+                "transactionHash": "0xabc1",
+                "logIndex": "0x4",
+            }],
+        });
+        let mut value = [0u8; 32];
+        value[16..].copy_from_slice(&250_000_000u128.to_be_bytes());
+        assert_eq!(
+            parse_transfer_logs(&receipt),
+            vec![TransferEvent {
+                token: USDC.to_lowercase(),
+                from: "0x28c6c06298d514db089934071355e5743bf21d60".into(),
+                to: "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".into(),
+                value,
+                block_number: Some(19_531_250),
+                tx_hash: Some("0xabc1".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn keeps_transfers_of_every_token_and_skips_other_logs() {
+        let a = padded("1111111111111111111111111111111111111111");
+        let b = padded("2222222222222222222222222222222222222222");
+        let logs = json!([
+            { "address": USDC, "topics": [TRANSFER_EVENT_TOPIC, a, b], "data": word(5) },
+            // Approval(owner, spender, value)
+            {
+                "address": USDC,
+                "topics": ["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925", a, b],
+                "data": word(9),
+            },
+            // ERC-721 transfer: token id indexed, no data
+            { "address": DAI, "topics": [TRANSFER_EVENT_TOPIC, a, b, word(7)], "data": "0x" },
+            { "address": DAI, "topics": [TRANSFER_EVENT_TOPIC, b, a], "data": word(3) },
+        ]);
+        let events = parse_transfer_logs(&logs);
+        let tokens: Vec<String> = events.iter().map(|e| e.token.clone()).collect();
+        assert_eq!(tokens, vec![USDC.to_lowercase(), DAI.to_lowercase()]);
+        assert_eq!(events[1].from, "0x2222222222222222222222222222222222222222");
+        assert_eq!(events[1].value[31], 3);
+        assert!(parse_transfer_logs(&json!({ "logs": [] })).is_empty());
+    }
+}
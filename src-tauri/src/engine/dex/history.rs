@@ -0,0 +1,558 @@
+// Paw Agent Engine — DEX Wallet Transaction History
+//
+// Reads the wallet's on-chain history from the chain's block explorer API
+// (Etherscan, Basescan, Arbiscan, … — all share the Etherscan API shape):
+// normal transactions plus ERC-20 token transfers, newest first.
+//
+// The explorer key is read from DEX_EXPLORER_API_KEY_<chain id> (e.g.
+// DEX_EXPLORER_API_KEY_8453 for Base), falling back to DEX_EXPLORER_API_KEY.
+// Without a key, on chains without a known explorer API, or when the caller
+// asks for a block window or one token, ERC-20 Transfer logs are scanned
+// over RPC instead, with net inbound/outbound flow per token.
+
+use super::chains::{chain_id_of, chain_of, ChainInfo};
+use super::constants::{chain_name, explorer_api_url, explorer_tx_url, TRANSFER_EVENT_TOPIC};
+use super::events::{parse_transfer_logs, TransferEvent};
+use super::primitives::{hex_encode, parse_u256_decimal, raw_to_amount};
+use super::rpc::{chunked_get_logs, eth_block_number};
+use super::tokens::{
+    assumed_decimals_note, fetch_token_symbol, resolve_token, short_address, token_decimals,
+};
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::util::safe_truncate;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+/// Blocks scanned by the on-chain log scan by default (~1 day on mainnet).
+const SCAN_DEFAULT_BLOCKS: u64 = 7200;
+/// Most blocks one log scan covers (~1 week on mainnet).
+const SCAN_MAX_BLOCKS: u64 = 50_400;
+/// Blocks per eth_getLogs request in a log scan.
+const SCAN_CHUNK_BLOCKS: u64 = 2000;
+/// Transfers listed by a log scan (the most recent are kept).
+const SCAN_MAX_LINES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    In,
+    Out,
+    SelfTransfer,
+}
+
+impl Direction {
+    pub(super) fn of(wallet: &str, from: &str, to: &str) -> Self {
+        match (
+            from.eq_ignore_ascii_case(wallet),
+            to.eq_ignore_ascii_case(wallet),
+        ) {
+            (true, true) => Direction::SelfTransfer,
+            (true, false) => Direction::Out,
+            _ => Direction::In,
+        }
+    }
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Direction::In => "IN",
+            Direction::Out => "OUT",
+            Direction::SelfTransfer => "SELF",
+        }
+    }
+}
+
+/// Explorer list endpoints (`action=` values).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExplorerList {
+    /// `txlist` — normal transactions, native ETH value.
+    Normal,
+    /// `tokentx` — ERC-20 token transfers.
+    Token,
+}
+
+impl ExplorerList {
+    fn action(self) -> &'static str {
+        match self {
+            ExplorerList::Normal => "txlist",
+            ExplorerList::Token => "tokentx",
+        }
+    }
+}
+
+/// One formatted history line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HistoryEntry {
+    pub timestamp: u64,
+    pub hash: String,
+    pub direction: Direction,
+    pub counterparty: String,
+    pub token: String,
+    pub amount: String,
+    pub success: bool,
+}
+
+/// Parse an explorer `txlist` / `tokentx` response for `wallet`.
+/// "No transactions found" is an empty list, not an error.
+pub(crate) fn parse_explorer_response(
+    body: &serde_json::Value,
+    wallet: &str,
+    list: ExplorerList,
+) -> EngineResult<Vec<HistoryEntry>> {
+    let result = &body["result"];
+    if body["status"].as_str() != Some("1") {
+        let message = body["message"].as_str().unwrap_or("unknown error");
+        if message.starts_with("No transactions found") {
+            return Ok(Vec::new());
+        }
+        return Err(EngineError::Other(format!(
+            "Explorer API error: {} ({})",
+            message,
+            result.as_str().unwrap_or("")
+        )));
+    }
+    let rows = result.as_array().ok_or(EngineError::Other(
+        "Explorer API: 'result' is not a list".into(),
+    ))?;
+
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        let field = |k: &str| row[k].as_str().unwrap_or("").to_string();
+        let (from, to) = (field("from"), field("to"));
+        let direction = Direction::of(wallet, &from, &to);
+        let (token, decimals, success) = match list {
+            ExplorerList::Normal => (
+                "ETH".to_string(),
+                18,
+                field("isError") != "1" && field("txreceipt_status") != "0",
+            ),
+            ExplorerList::Token => (
+                field("tokenSymbol"),
+                field("tokenDecimal").parse().unwrap_or(18),
+                true,
+            ),
+        };
+        let amount = parse_u256_decimal(&field("value"))
+            .and_then(|raw| raw_to_amount(&hex_encode(&raw), decimals))
+            .unwrap_or_else(|_| field("value"));
+        entries.push(HistoryEntry {
+            timestamp: field("timeStamp").parse().unwrap_or(0),
+            hash: field("hash"),
+            counterparty: if direction == Direction::In { from } else { to },
+            direction,
+            token,
+            amount,
+            success,
+        });
+    }
+    Ok(entries)
+}
+
+/// Merge normal transactions and token transfers, newest first. Zero-value
+/// contract calls that already show up as token transfers are dropped.
+fn merge_history(
+    normal: Vec<HistoryEntry>,
+    tokens: Vec<HistoryEntry>,
+    limit: usize,
+) -> Vec<HistoryEntry> {
+    let token_hashes: HashSet<String> = tokens.iter().map(|e| e.hash.to_lowercase()).collect();
+    let mut all: Vec<HistoryEntry> = normal
+        .into_iter()
+        .filter(|e| !(e.amount == "0" && token_hashes.contains(&e.hash.to_lowercase())))
+        .chain(tokens)
+        .collect();
+    all.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    all.truncate(limit);
+    all
+}
+
+fn format_entry(e: &HistoryEntry) -> String {
+    let when = chrono::DateTime::from_timestamp(e.timestamp as i64, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "unknown time".into());
+    format!(
+        "  {} | {:<4} | {} {} | {} {} | {} | {}",
+        when,
+        e.direction.label(),
+        e.amount,
+        e.token,
+        if e.direction == Direction::In {
+            "from"
+        } else {
+            "to"
+        },
+        short(&e.counterparty),
+        if e.success { "success" } else { "FAILED" },
+        short(&e.hash)
+    )
+}
+
+/// Display prefix of an address or hash.
+fn short(s: &str) -> &str {
+    safe_truncate(s, 12)
+}
+
+async fn fetch_explorer(
+    api_url: &str,
+    api_key: &str,
+    wallet: &str,
+    list: ExplorerList,
+    limit: u64,
+) -> EngineResult<Vec<HistoryEntry>> {
+    let client = reqwest::Client::new();
+    let limit = limit.to_string();
+    let resp = client
+        .get(api_url)
+        .query(&[
+            ("module", "account"),
+            ("action", list.action()),
+            ("address", wallet),
+            ("page", "1"),
+            ("offset", limit.as_str()),
+            ("sort", "desc"),
+            ("apikey", api_key),
+        ])
+        .timeout(Duration::from_secs(20))
+        .send()
+        .await?;
+    let body: serde_json::Value = resp.json().await?;
+    parse_explorer_response(&body, wallet, list)
+}
+
+/// Show the wallet's recent on-chain transactions and token transfers.
+pub async fn execute_dex_tx_history(
+    args: &serde_json::Value,
+    creds: &HashMap<String, String>,
+) -> EngineResult<String> {
+    let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let wallet = creds
+        .get("DEX_WALLET_ADDRESS")
+        .ok_or("No wallet. Use dex_wallet_create first.")?;
+    let limit = args["limit"]
+        .as_u64()
+        .unwrap_or(DEFAULT_LIMIT)
+        .clamp(1, MAX_LIMIT);
+
+    let chain_id = chain_id_of(creds, rpc_url).await?;
+    let chain = chain_of(creds);
+    // A block window or token filter asks for the on-chain log scan
+    if !args["blocks"].is_null() || !args["token"].is_null() {
+        return log_scan(args, chain, rpc_url, wallet, chain_id, None).await;
+    }
+    let api_key = creds
+        .get(&format!("DEX_EXPLORER_API_KEY_{}", chain_id))
+        .or_else(|| creds.get("DEX_EXPLORER_API_KEY"))
+        .filter(|k| !k.trim().is_empty());
+
+    let (Some(api_url), Some(api_key)) = (explorer_api_url(chain_id), api_key) else {
+        let reason = if explorer_api_url(chain_id).is_none() {
+            format!("No explorer API is known for chain ID {}.", chain_id)
+        } else {
+            format!(
+                "No explorer API key configured. Set DEX_EXPLORER_API_KEY (or \
+                 DEX_EXPLORER_API_KEY_{}) in Skills → DEX Trading for full history.",
+                chain_id
+            )
+        };
+        return log_scan(args, chain, rpc_url, wallet, chain_id, Some(&reason)).await;
+    };
+
+    let normal = fetch_explorer(api_url, api_key, wallet, ExplorerList::Normal, limit).await?;
+    let tokens = fetch_explorer(api_url, api_key, wallet, ExplorerList::Token, limit).await?;
+    let history = merge_history(normal, tokens, limit as usize);
+
+    let mut output = format!(
+        "Transaction history for {} on {} (newest first)\n\n",
+        wallet,
+        chain_name(chain_id)
+    );
+    if history.is_empty() {
+        output.push_str("  No transactions found.\n");
+    }
+    for entry in &history {
+        output.push_str(&format_entry(entry));
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Token transfers to and from the wallet over the last `blocks` blocks,
+/// read straight from ERC-20 Transfer logs (no explorer API key needed),
+/// oldest first, with each token's inbound/outbound flow. `reason`, when
+/// set, explains why the explorer wasn't used.
+async fn log_scan(
+    args: &serde_json::Value,
+    chain: &ChainInfo,
+    rpc_url: &str,
+    wallet_address: &str,
+    chain_id: u64,
+    reason: Option<&str>,
+) -> EngineResult<String> {
+    let blocks = args["blocks"]
+        .as_u64()
+        .unwrap_or(SCAN_DEFAULT_BLOCKS)
+        .clamp(1, SCAN_MAX_BLOCKS);
+    let token_filter = match args["token"].as_str() {
+        Some(token) if token.trim().eq_ignore_ascii_case("ETH") => {
+            return Err(
+                "Native ETH transfers emit no logs — omit 'token' and 'blocks' to list ETH \
+                 transactions from the block explorer."
+                    .into(),
+            )
+        }
+        Some(token) => Some(resolve_token(chain, token)?.0),
+        None => None,
+    };
+
+    let head = eth_block_number(rpc_url).await?;
+    let from_block = head.saturating_sub(blocks - 1);
+    let topic = Some(serde_json::json!(TRANSFER_EVENT_TOPIC));
+    let wallet_topic = Some(serde_json::json!(format!(
+        "0x{:0>64}",
+        wallet_address.trim_start_matches("0x").to_lowercase()
+    )));
+    let mut logs = Vec::new();
+    // Sent (from = wallet), then received (to = wallet)
+    for topics in [
+        vec![topic.clone(), wallet_topic.clone()],
+        vec![topic, None, wallet_topic],
+    ] {
+        logs.extend(
+            chunked_get_logs(
+                rpc_url,
+                token_filter.as_deref(),
+                from_block,
+                head,
+                topics,
+                SCAN_CHUNK_BLOCKS,
+            )
+            .await?,
+        );
+    }
+    // Transfers to self match both queries
+    let mut seen = HashSet::new();
+    logs.retain(|log| {
+        seen.insert((
+            log["transactionHash"].as_str().unwrap_or("").to_string(),
+            log["logIndex"].as_str().unwrap_or("").to_string(),
+        ))
+    });
+    let mut events = parse_transfer_logs(&serde_json::Value::Array(logs));
+    events.sort_by_key(|e| e.block_number);
+
+    let mut output = reason.map(|r| format!("{}\n", r)).unwrap_or_default();
+    output.push_str(&format!(
+        "Token transfers for {} on {} — last {} blocks (#{}–#{}), oldest first\n\n",
+        wallet_address,
+        chain_name(chain_id),
+        blocks,
+        from_block,
+        head
+    ));
+    if events.is_empty() {
+        output.push_str("  No ERC-20 transfers found in this range.\n");
+        return Ok(output);
+    }
+
+    // (symbol, decimals, assumed) per token contract, looked up once
+    let mut tokens: HashMap<String, (String, u8, bool)> = HashMap::new();
+    for event in &events {
+        if tokens.contains_key(&event.token) {
+            continue;
+        }
+        let symbol = match chain
+            .tokens
+            .iter()
+            .find(|(_, addr, _)| addr.eq_ignore_ascii_case(&event.token))
+        {
+            Some((sym, _, _)) => sym.to_string(),
+            None => fetch_token_symbol(rpc_url, &event.token).await,
+        };
+        let (decimals, assumed) = token_decimals(chain, rpc_url, &event.token).await;
+        tokens.insert(event.token.clone(), (symbol, decimals, assumed));
+    }
+
+    let omitted = events.len().saturating_sub(SCAN_MAX_LINES);
+    if omitted > 0 {
+        output.push_str(&format!(
+            "  … {} earlier transfers not listed (net flows below include them)\n",
+            omitted
+        ));
+    }
+    // symbol → (in, out)
+    let mut flows: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    for (i, event) in events.iter().enumerate() {
+        let (symbol, decimals, _) = &tokens[&event.token];
+        let amount =
+            raw_to_amount(&hex_encode(&event.value), *decimals).unwrap_or_else(|_| "?".into());
+        let direction = Direction::of(wallet_address, &event.from, &event.to);
+        let flow = flows.entry(symbol).or_default();
+        let value: f64 = amount.parse().unwrap_or(0.0);
+        match direction {
+            Direction::In => flow.0 += value,
+            Direction::Out => flow.1 += value,
+            Direction::SelfTransfer => {}
+        }
+        if i < omitted {
+            continue;
+        }
+        output.push_str(&history_line(event, direction, &amount, symbol, chain_id));
+    }
+
+    output.push_str("\nNet flow:\n");
+    for (symbol, (inflow, outflow)) in &flows {
+        output.push_str(&format!(
+            "  {}: +{} in, -{} out (net {}{})\n",
+            symbol,
+            flow_amount(*inflow),
+            flow_amount(*outflow),
+            if inflow >= outflow { "+" } else { "-" },
+            flow_amount((inflow - outflow).abs())
+        ));
+    }
+    for (symbol, _, assumed) in tokens.values() {
+        if *assumed {
+            output.push_str(&format!("{}\n", assumed_decimals_note(symbol)));
+        }
+    }
+    output.push_str("\nNative ETH transfers emit no logs and are not included.\n");
+    Ok(output)
+}
+
+/// A summed flow, to 6 decimals without trailing zeros.
+fn flow_amount(amount: f64) -> String {
+    let fixed = format!("{:.6}", amount);
+    fixed
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn history_line(
+    event: &TransferEvent,
+    direction: Direction,
+    amount: &str,
+    symbol: &str,
+    chain_id: u64,
+) -> String {
+    let (preposition, counterparty) = if direction == Direction::In {
+        ("from", &event.from)
+    } else {
+        ("to", &event.to)
+    };
+    format!(
+        "  Block {} | {:<4} | {} {} | {} {} | {}{}\n",
+        event
+            .block_number
+            .map_or("?".to_string(), |b| b.to_string()),
+        direction.label(),
+        amount,
+        symbol,
+        preposition,
+        short_address(counterparty),
+        explorer_tx_url(chain_id),
+        event.tx_hash.as_deref().unwrap_or("?")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET: &str = "0x4bbeEB066eD09B7AEd07bF39EEe0460DFa261520";
+
+    #[test]
+    fn parses_etherscan_txlist_and_tokentx() {
+        let txlist = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [
+                {
+                    "blockNumber": "19000000", "timeStamp": "1705000000",
+                    "hash": "0xaaa1", "from": "0x4bbeeb066ed09b7aed07bf39eee0460dfa261520",
+                    "to": "0x1111111111111111111111111111111111111111",
+                    "value": "250000000000000000", "isError": "0", "txreceipt_status": "1"
+                },
+                {
+                    "blockNumber": "19000100", "timeStamp": "1705001000",
+                    "hash": "0xbbb2", "from": "0x4bbeeb066ed09b7aed07bf39eee0460dfa261520",
+                    "to": "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45",
+                    "value": "0", "isError": "1", "txreceipt_status": "0"
+                }
+            ]
+        });
+        let tokentx = serde_json::json!({
+            "status": "1",
+            "message": "OK",
+            "result": [{
+                "blockNumber": "19000200", "timeStamp": "1705002000",
+                "hash": "0xccc3", "from": "0x2222222222222222222222222222222222222222",
+                "to": "0x4bbeeb066ed09b7aed07bf39eee0460dfa261520",
+                "value": "1500000", "tokenSymbol": "USDC", "tokenDecimal": "6"
+            }]
+        });
+
+        let normal = parse_explorer_response(&txlist, WALLET, ExplorerList::Normal).unwrap();
+        assert_eq!(normal.len(), 2);
+        assert_eq!(normal[0].direction, Direction::Out);
+        assert_eq!(normal[0].amount, "0.25");
+        assert_eq!(normal[0].token, "ETH");
+        assert_eq!(
+            normal[0].counterparty,
+            "0x1111111111111111111111111111111111111111"
+        );
+        assert!(normal[0].success);
+        assert!(!normal[1].success);
+
+        let tokens = parse_explorer_response(&tokentx, WALLET, ExplorerList::Token).unwrap();
+        assert_eq!(tokens[0].direction, Direction::In);
+        assert_eq!(tokens[0].amount, "1.5");
+        assert_eq!(tokens[0].token, "USDC");
+        assert_eq!(
+            tokens[0].counterparty,
+            "0x2222222222222222222222222222222222222222"
+        );
+
+        let merged = merge_history(normal, tokens, 10);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].hash, "0xccc3");
+        assert!(format_entry(&merged[0]).contains("IN   | 1.5 USDC | from 0x2222222222"));
+    }
+
+    #[test]
+    fn empty_and_error_responses() {
+        let empty = serde_json::json!({
+            "status": "0", "message": "No transactions found", "result": []
+        });
+        assert!(
+            parse_explorer_response(&empty, WALLET, ExplorerList::Normal)
+                .unwrap()
+                .is_empty()
+        );
+
+        let bad_key = serde_json::json!({
+            "status": "0", "message": "NOTOK", "result": "Invalid API Key"
+        });
+        let err = parse_explorer_response(&bad_key, WALLET, ExplorerList::Token).unwrap_err();
+        assert!(err.to_string().contains("Invalid API Key"));
+    }
+
+    #[test]
+    fn history_lines_link_the_chain_explorer() {
+        let event = TransferEvent {
+            token: "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913".into(),
+            from: "0x2222222222222222222222222222222222222222".into(),
+            to: "0x1111111111111111111111111111111111111111".into(),
+            value: [0u8; 32],
+            block_number: Some(21_000_000),
+            tx_hash: Some("0xabc".into()),
+        };
+        let line = history_line(&event, Direction::In, "12.5", "USDC", 8453);
+        assert_eq!(
+            line,
+            "  Block 21000000 | IN   | 12.5 USDC | from 0x2222…2222 | https://basescan.org/tx/0xabc\n"
+        );
+        assert_eq!(flow_amount(0.1 + 0.2), "0.3");
+        assert_eq!(flow_amount(1500.0), "1500");
+    }
+}
@@ -0,0 +1,415 @@
+// Paw Agent Engine — DEX Wallet Key Backup (export / import)
+//
+// Lets the user back up any DEX wallet — the default one or a named wallet
+// (see `wallets`) — and restore it on another machine. Only reachable
+// through Tauri commands — never exposed as an agent tool, so the key is
+// never handed to the model.
+//
+// Export is deliberately awkward: it needs a typed confirmation phrase, is
+// rate-limited, and every attempt (allowed or refused) lands in the signed
+// audit log. Import validates the key and refuses to overwrite a different
+// existing wallet (default, or named with the same label) unless explicitly
+// asked to.
+
+use super::primitives::{address_from_pubkey, hex_decode, hex_encode};
+use super::wallets::{check_label, is_default_wallet};
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::audit;
+use crate::engine::sessions::SessionStore;
+use crate::engine::skills::{decrypt_credential, encrypt_credential};
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+/// Phrase the user must type, verbatim, to export the private key.
+pub const EXPORT_CONFIRMATION_PHRASE: &str = "export my private key";
+
+/// At most this many exports per `EXPORT_WINDOW`.
+const MAX_EXPORTS_PER_WINDOW: usize = 3;
+const EXPORT_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+const SKILL_ID: &str = "dex";
+const KEY_CRED: &str = "DEX_PRIVATE_KEY";
+const ADDRESS_CRED: &str = "DEX_WALLET_ADDRESS";
+/// How the default wallet is named in audit entries.
+const DEFAULT_LABEL: &str = super::wallets::DEFAULT_WALLET;
+
+/// Sliding-window limiter for key exports.
+pub struct ExportRateLimiter {
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl ExportRateLimiter {
+    pub fn new() -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record an export if the window allows it.
+    fn try_acquire(&self) -> bool {
+        let mut recent = self.recent.lock();
+        let now = Instant::now();
+        while recent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > EXPORT_WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= MAX_EXPORTS_PER_WINDOW {
+            return false;
+        }
+        recent.push_back(now);
+        true
+    }
+}
+
+impl Default for ExportRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Process-wide limiter used by the export command.
+pub static EXPORT_LIMITER: LazyLock<ExportRateLimiter> = LazyLock::new(ExportRateLimiter::new);
+
+fn audit_event(store: &SessionStore, event: &str, details: serde_json::Value) {
+    audit::log_security_event(store, "", event, "dex_wallet", &details.to_string());
+}
+
+/// Decrypt and return a wallet's private key after the confirmation phrase
+/// and rate limit checks. `wallet` is a named wallet's id or label; `None`
+/// or "default" exports the default wallet. Every attempt is audited.
+pub fn export_private_key(
+    store: &SessionStore,
+    vault_key: &[u8],
+    limiter: &ExportRateLimiter,
+    confirmation_phrase: &str,
+    wallet: Option<&str>,
+) -> EngineResult<Zeroizing<String>> {
+    if confirmation_phrase.trim() != EXPORT_CONFIRMATION_PHRASE {
+        audit_event(
+            store,
+            "dex_key_export_denied",
+            serde_json::json!({ "reason": "confirmation phrase mismatch" }),
+        );
+        return Err(EngineError::Other(format!(
+            "Confirmation phrase does not match. Type \"{}\" to export the key.",
+            EXPORT_CONFIRMATION_PHRASE
+        )));
+    }
+    // Resolve the wallet before taking a rate-limit slot, so a mistyped or
+    // unknown name doesn't use up the export budget.
+    let (label, address, encrypted) = if is_default_wallet(wallet) {
+        let encrypted = store
+            .get_skill_credential(SKILL_ID, KEY_CRED)?
+            .ok_or("No DEX wallet to export. Create or import one first.")?;
+        let address = store
+            .get_skill_credential(SKILL_ID, ADDRESS_CRED)?
+            .and_then(|enc| decrypt_credential(&enc, vault_key).ok())
+            .unwrap_or_default();
+        (DEFAULT_LABEL.to_string(), address, encrypted)
+    } else {
+        let selector = wallet.unwrap_or_default();
+        let (record, encrypted) = store
+            .find_dex_wallet(selector)?
+            .ok_or_else(|| EngineError::Other(format!("Unknown DEX wallet '{}'", selector)))?;
+        (record.label, record.address, encrypted)
+    };
+    if !limiter.try_acquire() {
+        audit_event(
+            store,
+            "dex_key_export_denied",
+            serde_json::json!({ "reason": "rate limited" }),
+        );
+        return Err(EngineError::Other(format!(
+            "Too many key exports — at most {} per hour. Try again later.",
+            MAX_EXPORTS_PER_WINDOW
+        )));
+    }
+    let key = Zeroizing::new(decrypt_credential(&encrypted, vault_key)?);
+
+    warn!(
+        "[dex] Private key exported for wallet '{}' ({})",
+        label, address
+    );
+    audit_event(
+        store,
+        "dex_key_export",
+        serde_json::json!({ "wallet": label, "address": address }),
+    );
+    Ok(key)
+}
+
+/// Validate `private_key_hex`, store it as the default wallet — or as the
+/// named wallet labelled `wallet` — and return its address. An existing,
+/// different wallet is only replaced when `replace_existing` is set.
+pub fn import_private_key(
+    store: &SessionStore,
+    vault_key: &[u8],
+    private_key_hex: &str,
+    replace_existing: bool,
+    wallet: Option<&str>,
+) -> EngineResult<String> {
+    let key_bytes = Zeroizing::new(hex_decode(private_key_hex.trim())?);
+    if key_bytes.len() != 32 {
+        return Err("Private key must be 32 bytes (64 hex characters).".into());
+    }
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&key_bytes)
+        .map_err(|_| EngineError::Other("Invalid secp256k1 private key.".into()))?;
+    let pubkey = signing_key.verifying_key().to_encoded_point(false);
+    let address = address_from_pubkey(pubkey.as_bytes());
+    let normalized = Zeroizing::new(hex_encode(&signing_key.to_bytes()));
+
+    if !is_default_wallet(wallet) {
+        let label = check_label(wallet.unwrap_or_default())?;
+        return import_named(
+            store,
+            vault_key,
+            label,
+            &normalized,
+            &address,
+            replace_existing,
+        );
+    }
+
+    if let Some(existing) = store.get_skill_credential(SKILL_ID, ADDRESS_CRED)? {
+        let existing = decrypt_credential(&existing, vault_key).unwrap_or_default();
+        if !existing.eq_ignore_ascii_case(&address) && !replace_existing {
+            return Err(EngineError::Other(format!(
+                "A different DEX wallet ({}) is already configured. Export it first, then \
+                 import again with replace enabled.",
+                existing
+            )));
+        }
+    }
+
+    store.set_skill_credential(
+        SKILL_ID,
+        KEY_CRED,
+        &encrypt_credential(&normalized, vault_key)?,
+    )?;
+    store.set_skill_credential(
+        SKILL_ID,
+        ADDRESS_CRED,
+        &encrypt_credential(&address, vault_key)?,
+    )?;
+    crate::engine::secret_scrub::register_secrets([normalized.as_str()]);
+
+    info!("[dex] Imported wallet {}", address);
+    audit_event(
+        store,
+        "dex_key_import",
+        serde_json::json!({ "wallet": DEFAULT_LABEL, "address": address, "replaced": replace_existing }),
+    );
+    Ok(address)
+}
+
+/// Store an imported key as the named wallet `label`, keeping the wallet's
+/// id (and so its active status) when it replaces one.
+fn import_named(
+    store: &SessionStore,
+    vault_key: &[u8],
+    label: &str,
+    private_key_hex: &str,
+    address: &str,
+    replace_existing: bool,
+) -> EngineResult<String> {
+    let existing = store.find_dex_wallet(label)?;
+    let id = match existing {
+        Some((record, _)) if record.address.eq_ignore_ascii_case(address) => {
+            return Ok(record.address);
+        }
+        Some((record, _)) if !replace_existing => {
+            return Err(EngineError::Other(format!(
+                "Wallet '{}' already holds a different address ({}). Export it first, then \
+                 import again with replace enabled.",
+                record.label, record.address
+            )));
+        }
+        Some((record, _)) => {
+            store.delete_dex_wallet(&record.id)?;
+            record.id
+        }
+        None => uuid::Uuid::new_v4().to_string(),
+    };
+    store.insert_dex_wallet(
+        &id,
+        label,
+        address,
+        &encrypt_credential(private_key_hex, vault_key)?,
+    )?;
+    crate::engine::secret_scrub::register_secrets([private_key_hex]);
+
+    info!("[dex] Imported wallet '{}': {}", label, address);
+    audit_event(
+        store,
+        "dex_key_import",
+        serde_json::json!({ "wallet": label, "address": address, "replaced": replace_existing }),
+    );
+    Ok(address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    const VAULT_KEY: [u8; 32] = [7u8; 32];
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let store = SessionStore::from_connection(conn);
+        store.init_skill_tables().unwrap();
+        store
+    }
+
+    fn security_actions(store: &SessionStore) -> Vec<String> {
+        audit::query_recent(store, 50, Some("security"), None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.action)
+            .collect()
+    }
+
+    #[test]
+    fn export_requires_confirmation_phrase_and_is_audited() {
+        let store = test_store();
+        let limiter = ExportRateLimiter::new();
+        import_private_key(&store, &VAULT_KEY, PRIVATE_KEY, false, None).unwrap();
+
+        let err = export_private_key(&store, &VAULT_KEY, &limiter, "yes", None).unwrap_err();
+        assert!(err.to_string().contains("Confirmation phrase"));
+        assert!(security_actions(&store).contains(&"dex_key_export_denied".to_string()));
+
+        let key = export_private_key(
+            &store,
+            &VAULT_KEY,
+            &limiter,
+            EXPORT_CONFIRMATION_PHRASE,
+            None,
+        )
+        .unwrap();
+        assert_eq!(key.as_str(), PRIVATE_KEY);
+        assert!(security_actions(&store).contains(&"dex_key_export".to_string()));
+    }
+
+    #[test]
+    fn export_is_rate_limited() {
+        let store = test_store();
+        let limiter = ExportRateLimiter::new();
+        import_private_key(&store, &VAULT_KEY, PRIVATE_KEY, false, None).unwrap();
+        for _ in 0..MAX_EXPORTS_PER_WINDOW {
+            export_private_key(
+                &store,
+                &VAULT_KEY,
+                &limiter,
+                EXPORT_CONFIRMATION_PHRASE,
+                None,
+            )
+            .unwrap();
+        }
+        assert!(export_private_key(
+            &store,
+            &VAULT_KEY,
+            &limiter,
+            EXPORT_CONFIRMATION_PHRASE,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unknown_wallets_do_not_use_up_the_export_budget() {
+        let store = test_store();
+        let limiter = ExportRateLimiter::new();
+        import_private_key(&store, &VAULT_KEY, PRIVATE_KEY, false, None).unwrap();
+        for _ in 0..MAX_EXPORTS_PER_WINDOW {
+            let err = export_private_key(
+                &store,
+                &VAULT_KEY,
+                &limiter,
+                EXPORT_CONFIRMATION_PHRASE,
+                Some("typo"),
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("Unknown DEX wallet"));
+        }
+        assert!(export_private_key(
+            &store,
+            &VAULT_KEY,
+            &limiter,
+            EXPORT_CONFIRMATION_PHRASE,
+            None
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn import_refuses_to_replace_a_different_wallet() {
+        let store = test_store();
+        import_private_key(&store, &VAULT_KEY, PRIVATE_KEY, false, None).unwrap();
+        // Re-importing the same key is fine
+        import_private_key(&store, &VAULT_KEY, PRIVATE_KEY, false, None).unwrap();
+
+        let other = format!("0x{}", "11".repeat(32));
+        assert!(import_private_key(&store, &VAULT_KEY, &other, false, None).is_err());
+        assert!(import_private_key(&store, &VAULT_KEY, &other, true, None).is_ok());
+        assert!(import_private_key(&store, &VAULT_KEY, "0x1234", true, None).is_err());
+    }
+
+    #[test]
+    fn named_wallets_export_and_import() {
+        let store = test_store();
+        let limiter = ExportRateLimiter::new();
+        let bot =
+            super::super::wallets::create_named_wallet(&store, &VAULT_KEY, "bot", false).unwrap();
+
+        // Export a named wallet by label, re-import it on a fresh store
+        let key = export_private_key(
+            &store,
+            &VAULT_KEY,
+            &limiter,
+            EXPORT_CONFIRMATION_PHRASE,
+            Some("bot"),
+        )
+        .unwrap();
+        let restored = test_store();
+        let address = import_private_key(&restored, &VAULT_KEY, &key, false, Some("bot")).unwrap();
+        assert_eq!(address, bot.address);
+        assert_eq!(
+            restored.find_dex_wallet("bot").unwrap().unwrap().0.address,
+            bot.address
+        );
+        // The default wallet is untouched by a named import
+        assert!(restored
+            .get_skill_credential(SKILL_ID, KEY_CRED)
+            .unwrap()
+            .is_none());
+
+        // A different key under the same label needs replace
+        assert!(
+            import_private_key(&restored, &VAULT_KEY, PRIVATE_KEY, false, Some("bot")).is_err()
+        );
+        let id = restored.find_dex_wallet("bot").unwrap().unwrap().0.id;
+        import_private_key(&restored, &VAULT_KEY, PRIVATE_KEY, true, Some("bot")).unwrap();
+        let (record, _) = restored.find_dex_wallet("bot").unwrap().unwrap();
+        assert_eq!(record.id, id);
+        assert_ne!(record.address, bot.address);
+
+        assert!(export_private_key(
+            &store,
+            &VAULT_KEY,
+            &limiter,
+            EXPORT_CONFIRMATION_PHRASE,
+            Some("missing"),
+        )
+        .is_err());
+    }
+}
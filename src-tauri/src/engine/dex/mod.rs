@@ -2,6 +2,7 @@
 //
 // Split from the monolithic dex.rs into focused submodules:
 //   constants      — contract addresses, token list, fee defaults
+//   chains         — per-chain registry (WETH, Uniswap, tokens) + active chain
 //   primitives     — keccak256, hex encode/decode, address utils, amount conversion
 //   abi            — ABI encoding for all contract calls + encode_transfer
 //   rlp            — RLP encoding for EIP-1559 transaction serialisation
@@ -9,35 +10,57 @@
 //   rpc            — JSON-RPC helpers (eth_call, eth_sendRawTransaction, etc.)
 //   tokens         — token symbol / address resolution
 //   wallet         — wallet creation (keygen + vault storage)
+//   wallets        — named wallets, active-wallet selection, dex_wallet_list
+//   key_backup     — user-initiated private key export / import (never agent-facing)
 //   swap           — quote + swap execution
-//   portfolio      — balance / portfolio queries
+//   events         — ERC-20 Transfer log decoding
+//   settlement     — post-confirmation check of what actually arrived
+//   portfolio      — balance / portfolio queries, Transfer-log trade history
+//   history        — wallet tx history via block explorer API (RPC fallback)
 //   transfer       — ETH and ERC-20 outbound transfers
+//   confirm        — wait for a configurable confirmation depth (reorg safety)
+//   eip712         — EIP-712 typed data hashing + signing (dApp sign-in, permits)
+//   permit         — ERC-2612 permit detection + signing for approval-free swaps
 //   token_analysis — token info + honeypot safety check
 //   discovery      — DexScreener search + trending
 //   monitoring     — whale scanner, watch-wallet, top-traders
 
 pub(crate) mod abi;
+pub(crate) mod chains;
+mod confirm;
 pub(crate) mod constants;
 mod discovery;
+mod eip712;
+mod events;
+mod history;
+pub(crate) mod key_backup;
 mod monitoring;
+mod permit;
 mod portfolio;
 pub(crate) mod primitives;
 pub(crate) mod rlp;
 pub(crate) mod rpc;
+mod settlement;
 mod swap;
 mod token_analysis;
 pub(crate) mod tokens;
 mod transfer;
 pub(crate) mod tx;
 mod wallet;
+pub(crate) mod wallets;
 
 // Re-export all public execute functions (called from engine/tools/dex.rs via crate::engine::dex::*)
+pub use confirm::TxReport;
 pub use discovery::{execute_dex_search_token, execute_dex_trending};
+pub use eip712::execute_dex_sign_typed_data;
+pub use history::execute_dex_tx_history;
 pub use monitoring::{
     execute_dex_top_traders, execute_dex_watch_wallet, execute_dex_whale_transfers,
 };
+pub(crate) use portfolio::token_usd_price;
 pub use portfolio::{execute_dex_balance, execute_dex_portfolio};
 pub use swap::{execute_dex_quote, execute_dex_swap};
 pub use token_analysis::{execute_dex_check_token, execute_dex_token_info};
 pub use transfer::execute_dex_transfer;
 pub use wallet::execute_dex_wallet_create;
+pub use wallets::execute_dex_wallet_list;
@@ -1,10 +1,12 @@
 // Paw Agent Engine — DEX Whale / Wallet Monitoring
 
 use super::abi::{decode_abi_string, encode_balance_of, encode_decimals, encode_symbol};
-use super::constants::{chain_name, KNOWN_TOKENS, TRANSFER_EVENT_TOPIC};
+use super::chains::{chain_id_of, chain_of};
+use super::constants::{chain_name, TRANSFER_EVENT_TOPIC};
 use super::primitives::{hex_decode, parse_address, raw_to_amount};
-use super::rpc::{chunked_get_logs, eth_call, eth_chain_id, eth_get_balance, rpc_call};
+use super::rpc::{chunked_get_logs, eth_call, eth_get_balance, rpc_call};
 use crate::atoms::error::EngineResult;
+use crate::engine::util::safe_truncate;
 use std::collections::HashMap;
 
 /// Internal representation of a parsed ERC-20 Transfer event.
@@ -52,7 +54,7 @@ pub async fn execute_dex_watch_wallet(
     let wallet_bytes = parse_address(addr_clean)?;
     output.push_str("\nToken Holdings:\n");
     let mut has_tokens = false;
-    for (symbol, addr, decimals) in KNOWN_TOKENS {
+    for (symbol, addr, decimals) in chain_of(creds).tokens {
         if *symbol == "ETH" {
             continue;
         }
@@ -78,8 +80,8 @@ pub async fn execute_dex_watch_wallet(
                     if let Ok(result) = eth_call(rpc_url, token_addr, &call_data).await {
                         let symbol = match eth_call(rpc_url, token_addr, &encode_symbol()).await {
                             Ok(s) => decode_abi_string(&s)
-                                .unwrap_or_else(|_| token_addr[..10].to_string()),
-                            Err(_) => token_addr[..10].to_string(),
+                                .unwrap_or_else(|_| safe_truncate(token_addr, 10).to_string()),
+                            Err(_) => safe_truncate(token_addr, 10).to_string(),
                         };
                         let decimals = match eth_call(rpc_url, token_addr, &encode_decimals()).await
                         {
@@ -199,7 +201,7 @@ pub async fn execute_dex_watch_wallet(
     }
 
     // Chain info
-    if let Ok(chain_id) = eth_chain_id(rpc_url).await {
+    if let Ok(chain_id) = chain_id_of(creds, rpc_url).await {
         let chain = chain_name(chain_id);
         output.push_str(&format!("\nNetwork: {} (chain ID {})\n", chain, chain_id));
     }
@@ -355,14 +357,10 @@ pub async fn execute_dex_whale_transfers(
             i + 1,
             format_large_number(t.amount),
             symbol,
-            &t.from[..8.min(t.from.len())],
-            &t.to[..8.min(t.to.len())],
+            safe_truncate(&t.from, 8),
+            safe_truncate(&t.to, 8),
             t.block,
-            if t.tx_hash.len() > 14 {
-                &t.tx_hash[..14]
-            } else {
-                &t.tx_hash
-            },
+            safe_truncate(&t.tx_hash, 14),
         ));
     }
 
@@ -408,7 +406,7 @@ pub async fn execute_dex_whale_transfers(
         }
     }
 
-    if let Ok(chain_id) = eth_chain_id(rpc_url).await {
+    if let Ok(chain_id) = chain_id_of(creds, rpc_url).await {
         let chain = chain_name(chain_id);
         output.push_str(&format!("\nNetwork: {} (chain ID {})\n", chain, chain_id));
     }
@@ -734,7 +732,7 @@ pub async fn execute_dex_top_traders(
         );
     }
 
-    if let Ok(chain_id) = eth_chain_id(rpc_url).await {
+    if let Ok(chain_id) = chain_id_of(creds, rpc_url).await {
         let chain = chain_name(chain_id);
         output.push_str(&format!("\nNetwork: {} (chain ID {})\n", chain, chain_id));
     }
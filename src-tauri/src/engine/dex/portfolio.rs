@@ -1,19 +1,39 @@
 // Paw Agent Engine — DEX Portfolio / Balance Queries
 
-use super::abi::encode_balance_of;
-use super::constants::{chain_name, KNOWN_TOKENS};
-use super::primitives::{parse_address, raw_to_amount};
-use super::rpc::{eth_call, eth_chain_id, eth_get_balance};
-use super::tokens::resolve_token;
+use super::abi::{encode_balance_of, encode_quote_exact_input_single};
+use super::chains::{chain_id_of, chain_of, supported_chain, ChainInfo};
+use super::constants::chain_name;
+use super::primitives::{
+    amount_to_raw, hex_encode, parse_address, parse_u256_decimal, raw_to_amount,
+};
+use super::rpc::{eth_call, eth_call_batch, eth_get_balance};
+use super::swap::quoted_amount;
+use super::tokens::{
+    assumed_decimals_note, fetch_decimals, fetch_token_symbol, resolve_for_swap, resolve_token,
+    token_decimals, FALLBACK_DECIMALS,
+};
 use crate::atoms::error::EngineResult;
 use std::collections::HashMap;
 
+/// Fee tiers quoted for USD prices (0.3% and 0.05%); the better quote wins.
+const USD_FEE_TIERS: [u32; 2] = [3000, 500];
+
+/// One balance line in the portfolio view.
+struct Holding {
+    label: String,
+    address: String,
+    decimals: u8,
+    balance: String,
+    note: Option<String>,
+}
+
 /// Check ETH and ERC-20 token balances for a single token or all known tokens.
 pub async fn execute_dex_balance(
     args: &serde_json::Value,
     creds: &HashMap<String, String>,
 ) -> EngineResult<String> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL. Configure your RPC endpoint (Infura/Alchemy) in Skills → DEX Trading.")?;
+    let chain = chain_of(creds);
     let wallet_address = creds
         .get("DEX_WALLET_ADDRESS")
         .ok_or("No wallet found. Use dex_wallet_create first.")?;
@@ -30,97 +50,261 @@ pub async fn execute_dex_balance(
 
     if let Some(token_sym) = token {
         // Check specific token
-        let (token_addr, decimals) = resolve_token(token_sym)?;
+        let (token_addr, _) = resolve_token(chain, token_sym)?;
         if token_addr != "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE" {
+            let (decimals, assumed) = token_decimals(chain, rpc_url, &token_addr).await;
             let wallet_bytes = parse_address(wallet_address)?;
             let calldata = encode_balance_of(&wallet_bytes);
             let result = eth_call(rpc_url, &token_addr, &calldata).await?;
             let balance = raw_to_amount(&result, decimals)?;
-            output.push_str(&format!("{}: {}\n", token_sym.to_uppercase(), balance));
+            // Raw addresses are labelled with the token's on-chain symbol
+            let label = if token_sym.trim().starts_with("0x") {
+                fetch_token_symbol(rpc_url, &token_addr).await
+            } else {
+                token_sym.to_uppercase()
+            };
+            output.push_str(&format!("{}: {}\n", label, balance));
+            if assumed {
+                output.push_str(&format!("{}\n", assumed_decimals_note(&label)));
+            }
         }
     } else {
-        // Check common tokens
+        // Check common tokens (one batched round-trip)
         let wallet_bytes = parse_address(wallet_address)?;
-        for (sym, addr, dec) in KNOWN_TOKENS {
-            if *sym == "ETH" {
-                continue;
-            }
-            let calldata = encode_balance_of(&wallet_bytes);
-            if let Ok(result) = eth_call(rpc_url, addr, &calldata).await {
-                if let Ok(balance) = raw_to_amount(&result, *dec) {
-                    if balance != "0" {
-                        output.push_str(&format!("{}: {}\n", sym, balance));
-                    }
-                }
-            }
+        for (sym, _, _, balance) in known_token_balances(chain, rpc_url, &wallet_bytes).await {
+            output.push_str(&format!("{}: {}\n", sym, balance));
         }
     }
 
     Ok(output)
 }
 
-/// Check multiple token balances at once (full portfolio view).
+/// Check multiple token balances at once (full portfolio view), optionally
+/// valued in USD (`with_usd`).
 pub async fn execute_dex_portfolio(
     args: &serde_json::Value,
     creds: &HashMap<String, String>,
 ) -> EngineResult<String> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = chain_of(creds);
     let wallet_address = creds
         .get("DEX_WALLET_ADDRESS")
         .ok_or("No wallet. Use dex_wallet_create first.")?;
 
+    let with_usd = args
+        .get("with_usd")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
     let wallet_bytes = parse_address(wallet_address)?;
 
     let mut output = format!("📊 Portfolio for {}\n\n", wallet_address);
 
-    // ETH balance
+    // ETH balance (priced as WETH)
     let eth_hex = eth_get_balance(rpc_url, wallet_address).await?;
-    let eth_balance = raw_to_amount(&eth_hex, 18)?;
-    output.push_str(&format!("  ETH: {} ETH\n", eth_balance));
-
-    // Check all known tokens
-    let mut has_tokens = false;
-    for (sym, addr, dec) in KNOWN_TOKENS {
-        if *sym == "ETH" {
-            continue;
-        }
-        let calldata = encode_balance_of(&wallet_bytes);
-        if let Ok(result) = eth_call(rpc_url, addr, &calldata).await {
-            if let Ok(balance) = raw_to_amount(&result, *dec) {
-                if balance != "0" {
-                    output.push_str(&format!("  {}: {}\n", sym, balance));
-                    has_tokens = true;
-                }
+    let mut holdings = vec![Holding {
+        label: "ETH".into(),
+        address: chain.weth.into(),
+        decimals: 18,
+        balance: format!("{} ETH", raw_to_amount(&eth_hex, 18)?),
+        note: None,
+    }];
+
+    // Known tokens, then any custom tokens — each set in one batched request
+    for (sym, addr, decimals, balance) in known_token_balances(chain, rpc_url, &wallet_bytes).await
+    {
+        holdings.push(Holding {
+            label: sym.into(),
+            address: addr.into(),
+            decimals,
+            balance,
+            note: None,
+        });
+    }
+
+    let custom: Vec<&str> = args
+        .get("tokens")
+        .and_then(|v| v.as_array())
+        .map(|tokens| tokens.iter().filter_map(|t| t.as_str()).collect())
+        .unwrap_or_default();
+    let calls: Vec<(&str, Vec<u8>)> = custom
+        .iter()
+        .map(|addr| (*addr, encode_balance_of(&wallet_bytes)))
+        .collect();
+    for (addr, result) in custom.iter().zip(eth_call_batch(rpc_url, &calls).await) {
+        let Ok(raw) = result else { continue };
+        let decimals = fetch_decimals(rpc_url, addr).await;
+        if let Ok(balance) = raw_to_amount(&raw, decimals.unwrap_or(FALLBACK_DECIMALS)) {
+            if balance != "0" {
+                let symbol = fetch_token_symbol(rpc_url, addr).await;
+                holdings.push(Holding {
+                    note: decimals.is_none().then(|| assumed_decimals_note(&symbol)),
+                    label: symbol,
+                    address: addr.to_string(),
+                    decimals: decimals.unwrap_or(FALLBACK_DECIMALS),
+                    balance,
+                });
             }
         }
     }
 
-    // Also check any custom tokens specified
-    if let Some(tokens) = args.get("tokens").and_then(|v| v.as_array()) {
-        for token in tokens {
-            if let Some(addr) = token.as_str() {
-                let calldata = encode_balance_of(&wallet_bytes);
-                if let Ok(result) = eth_call(rpc_url, addr, &calldata).await {
-                    if let Ok(balance) = raw_to_amount(&result, 18) {
-                        if balance != "0" {
-                            output.push_str(&format!("  {}: {}\n", addr, balance));
-                            has_tokens = true;
-                        }
-                    }
-                }
+    let prices = if with_usd {
+        usd_prices(chain, rpc_url, &holdings).await
+    } else {
+        Vec::new()
+    };
+    let mut total_usd = 0.0;
+    let mut unpriced = false;
+    for (i, holding) in holdings.iter().enumerate() {
+        let usd = match prices.get(i) {
+            Some(Some(price)) => {
+                let amount: f64 = holding
+                    .balance
+                    .trim_end_matches(" ETH")
+                    .parse()
+                    .unwrap_or(0.0);
+                total_usd += amount * price;
+                format!(" (~${:.2})", amount * price)
+            }
+            Some(None) => {
+                unpriced = true;
+                " (no price)".to_string()
             }
+            None => String::new(),
+        };
+        output.push_str(&format!(
+            "  {}: {}{}\n",
+            holding.label, holding.balance, usd
+        ));
+        if let Some(note) = &holding.note {
+            output.push_str(&format!("    {}\n", note));
         }
     }
 
-    if !has_tokens {
+    if holdings.len() == 1 {
         output.push_str("\n  No ERC-20 token balances found.\n");
     }
+    if with_usd {
+        output.push_str(&format!(
+            "\nTotal: ~${:.2}{}\n",
+            total_usd,
+            if unpriced {
+                " (tokens with no price not counted)"
+            } else {
+                ""
+            }
+        ));
+    }
 
     // Get chain info
-    if let Ok(id) = eth_chain_id(rpc_url).await {
+    if let Ok(id) = chain_id_of(creds, rpc_url).await {
         let chain = chain_name(id);
         output.push_str(&format!("\nNetwork: {} (chain ID {})\n", chain, id));
     }
 
     Ok(output)
 }
+
+/// USD price of native ETH (quoted as WETH → USDC), if a pool quotes it.
+pub(super) async fn eth_usd_price(chain: &ChainInfo, rpc_url: &str) -> Option<f64> {
+    let eth = Holding {
+        label: "ETH".into(),
+        address: chain.weth.into(),
+        decimals: 18,
+        balance: String::new(),
+        note: None,
+    };
+    usd_prices(chain, rpc_url, &[eth]).await.pop().flatten()
+}
+
+/// USD price of one whole `token` (symbol or address) on the chain a swap
+/// would use, for valuing a trade before it is approved. None when no pool
+/// quotes it — or when the token's decimals couldn't be read, since a quote
+/// on assumed decimals can be off by orders of magnitude.
+pub(crate) async fn token_usd_price(creds: &HashMap<String, String>, token: &str) -> Option<f64> {
+    let rpc_url = creds.get("DEX_RPC_URL")?;
+    let chain = supported_chain(creds, rpc_url).await.ok()?;
+    let (address, _, _) = resolve_for_swap(chain, token).ok()?;
+    let (decimals, assumed) = token_decimals(chain, rpc_url, &address).await;
+    if assumed {
+        return None;
+    }
+    let holding = Holding {
+        label: token.trim().to_uppercase(),
+        address,
+        decimals,
+        balance: String::new(),
+        note: None,
+    };
+    usd_prices(chain, rpc_url, &[holding]).await.pop().flatten()
+}
+
+/// USDC value of one whole token for each holding, from the Uniswap V3
+/// quoter at each of USD_FEE_TIERS (all in one batched request). None when
+/// the token has no USDC pool — or the chain has no USDC.
+async fn usd_prices(chain: &ChainInfo, rpc_url: &str, holdings: &[Holding]) -> Vec<Option<f64>> {
+    let Some((_, usdc, usdc_dec)) = chain.tokens.iter().find(|(sym, _, _)| *sym == "USDC") else {
+        return holdings.iter().map(|_| None).collect();
+    };
+    let Ok(usdc_bytes) = parse_address(usdc) else {
+        return holdings.iter().map(|_| None).collect();
+    };
+    let is_usdc = |h: &Holding| h.address.eq_ignore_ascii_case(usdc);
+
+    let (owners, calls): (Vec<usize>, Vec<(&str, Vec<u8>)>) = holdings
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| !is_usdc(h))
+        .filter_map(|(i, h)| {
+            let token = parse_address(&h.address).ok()?;
+            let one = parse_u256_decimal(&amount_to_raw("1", h.decimals).ok()?).ok()?;
+            Some(USD_FEE_TIERS.map(|tier| {
+                let data = encode_quote_exact_input_single(&token, &usdc_bytes, &one, tier);
+                (i, (chain.quoter, data))
+            }))
+        })
+        .flatten()
+        .unzip();
+
+    let mut prices: Vec<Option<f64>> = holdings.iter().map(|h| is_usdc(h).then_some(1.0)).collect();
+    for (i, result) in owners
+        .into_iter()
+        .zip(eth_call_batch(rpc_url, &calls).await)
+    {
+        let price = result
+            .ok()
+            .and_then(|r| quoted_amount(&r).ok())
+            .and_then(|raw| raw_to_amount(&hex_encode(&raw), *usdc_dec).ok())
+            .and_then(|p| p.parse::<f64>().ok())
+            .filter(|p| *p > 0.0);
+        if let Some(price) = price {
+            prices[i] = Some(prices[i].map_or(price, |best: f64| best.max(price)));
+        }
+    }
+    prices
+}
+
+/// Non-zero balances of the chain's known ERC-20 tokens, via one batched RPC
+/// request, as (symbol, address, decimals, balance).
+async fn known_token_balances(
+    chain: &ChainInfo,
+    rpc_url: &str,
+    wallet_bytes: &[u8; 20],
+) -> Vec<(&'static str, &'static str, u8, String)> {
+    let tokens: Vec<_> = chain
+        .tokens
+        .iter()
+        .filter(|(sym, _, _)| *sym != "ETH")
+        .collect();
+    let calls: Vec<(&str, Vec<u8>)> = tokens
+        .iter()
+        .map(|(_, addr, _)| (*addr, encode_balance_of(wallet_bytes)))
+        .collect();
+    tokens
+        .iter()
+        .zip(eth_call_batch(rpc_url, &calls).await)
+        .filter_map(|((sym, addr, dec), result)| {
+            let balance = raw_to_amount(&result.ok()?, *dec).ok()?;
+            (balance != "0").then_some((*sym, *addr, *dec, balance))
+        })
+        .collect()
+}
@@ -96,9 +96,9 @@ pub(crate) fn parse_address(addr: &str) -> EngineResult<[u8; 20]> {
     Ok(arr)
 }
 
-/// Parse a U256 from decimal string
+/// Parse a U256 from decimal string. Values above 2^256 - 1 are rejected
+/// rather than truncated.
 pub(crate) fn parse_u256_decimal(s: &str) -> EngineResult<[u8; 32]> {
-    // Simple decimal-to-big-endian conversion
     let mut result = [0u8; 32];
 
     // Handle scientific notation
@@ -107,9 +107,11 @@ pub(crate) fn parse_u256_decimal(s: &str) -> EngineResult<[u8; 32]> {
             "Scientific notation not supported, use plain decimal".into(),
         ));
     }
+    if s.is_empty() {
+        return Err(EngineError::Other("Empty decimal amount".into()));
+    }
 
-    // Convert decimal string to bytes
-    let mut digits: Vec<u8> = Vec::new();
+    // result = result * 10 + digit, big-endian, one digit at a time
     for c in s.chars() {
         if !c.is_ascii_digit() {
             return Err(EngineError::Other(format!(
@@ -117,26 +119,15 @@ pub(crate) fn parse_u256_decimal(s: &str) -> EngineResult<[u8; 32]> {
                 c
             )));
         }
-        digits.push(c as u8 - b'0');
-    }
-
-    // Convert to big-endian bytes using repeated division by 256
-    let mut big = digits;
-    let mut byte_pos = 31i32;
-    while (big.len() != 1 || big[0] != 0) && byte_pos >= 0 {
-        let mut remainder = 0u16;
-        let mut quotient = Vec::new();
-        for &d in &big {
-            let val = remainder * 10 + d as u16;
-            let q = val / 256;
-            remainder = val % 256;
-            if !quotient.is_empty() || q > 0 {
-                quotient.push(q as u8);
-            }
+        let mut carry = (c as u8 - b'0') as u16;
+        for byte in result.iter_mut().rev() {
+            let val = *byte as u16 * 10 + carry;
+            *byte = (val & 0xff) as u8;
+            carry = val >> 8;
+        }
+        if carry > 0 {
+            return Err(EngineError::Other("amount too large for uint256".into()));
         }
-        result[byte_pos as usize] = remainder as u8;
-        byte_pos -= 1;
-        big = quotient;
     }
     Ok(result)
 }
@@ -163,10 +154,11 @@ pub(crate) fn amount_to_raw(amount: &str, decimals: u8) -> EngineResult<String>
     // Strip leading zeros but keep at least "0"
     let trimmed = raw.trim_start_matches('0');
     if trimmed.is_empty() {
-        Ok("0".into())
-    } else {
-        Ok(trimmed.into())
+        return Ok("0".into());
     }
+    // Rejects non-digits and anything that would not fit in a uint256
+    parse_u256_decimal(trimmed)?;
+    Ok(trimmed.into())
 }
 
 /// Convert raw units to human-readable amount
@@ -328,6 +320,68 @@ mod tests {
         assert!(amount_to_raw("1.123456789", 6).is_err());
     }
 
+    const U256_MAX: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    #[test]
+    fn parse_u256_decimal_max() {
+        assert_eq!(parse_u256_decimal(U256_MAX).unwrap(), [0xff; 32]);
+        // Leading zeros don't count toward the width
+        assert_eq!(
+            parse_u256_decimal(&format!("000{}", U256_MAX)).unwrap(),
+            [0xff; 32]
+        );
+    }
+
+    #[test]
+    fn parse_u256_decimal_rejects_overflow() {
+        let over = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        let err = parse_u256_decimal(over).unwrap_err().to_string();
+        assert!(err.contains("amount too large for uint256"), "{}", err);
+        assert!(parse_u256_decimal(&format!("{}0", U256_MAX)).is_err());
+        assert!(parse_u256_decimal("").is_err());
+        assert!(parse_u256_decimal("-1").is_err());
+    }
+
+    #[test]
+    fn amount_to_raw_rejects_overflow() {
+        // ~1.16e59 whole tokens is the most an 18-decimal uint256 can hold
+        let max = "115792089237316195423570985008687907853269984665640564039457.584007913129639935";
+        assert_eq!(amount_to_raw(max, 18).unwrap(), U256_MAX);
+        assert!(amount_to_raw(
+            "115792089237316195423570985008687907853269984665640564039458",
+            18
+        )
+        .is_err());
+        assert!(amount_to_raw("1e80", 18).is_err());
+        assert!(amount_to_raw("12abc", 18).is_err());
+    }
+
+    #[test]
+    fn amount_round_trips_through_raw_units() {
+        let cases = [
+            ("0", 18),
+            ("1.5", 18),
+            ("0.000001", 6),
+            ("123456789.123456789", 9),
+            ("42", 0),
+            (
+                "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+                18,
+            ),
+        ];
+        for (amount, decimals) in cases {
+            let raw = parse_u256_decimal(&amount_to_raw(amount, decimals).unwrap()).unwrap();
+            assert_eq!(
+                raw_to_amount(&hex_encode(&raw), decimals).unwrap(),
+                amount,
+                "{} @ {} decimals",
+                amount,
+                decimals
+            );
+        }
+    }
+
     #[test]
     fn raw_to_amount_one_eth() {
         // 1 ETH = 0xDE0B6B3A7640000 in raw units (10^18)
@@ -2,6 +2,7 @@
 
 use super::primitives::hex_encode;
 use crate::atoms::error::{EngineError, EngineResult};
+use log::warn;
 use std::time::Duration;
 
 /// Low-level JSON-RPC call
@@ -18,8 +19,7 @@ pub(crate) async fn rpc_call(
         "id": 1
     });
 
-    let resp = client
-        .post(rpc_url)
+    let resp = crate::engine::net::apply(client.post(rpc_url), rpc_url)
         .json(&body)
         .timeout(Duration::from_secs(30))
         .send()
@@ -37,6 +37,120 @@ pub(crate) async fn rpc_call(
         .ok_or_else(|| EngineError::Other("RPC response missing 'result' field".into()))
 }
 
+/// Several JSON-RPC calls in one HTTP request (a JSON-RPC batch array).
+/// Responses are matched back to `calls` by id, so the result order always
+/// follows `calls`. Providers that reject batches (non-array reply or HTTP
+/// error) are retried one call at a time.
+pub(crate) async fn rpc_batch(
+    rpc_url: &str,
+    calls: &[(&str, serde_json::Value)],
+) -> Vec<EngineResult<serde_json::Value>> {
+    if calls.is_empty() {
+        return Vec::new();
+    }
+    let body: Vec<serde_json::Value> = calls
+        .iter()
+        .enumerate()
+        .map(|(id, (method, params))| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params,
+                "id": id
+            })
+        })
+        .collect();
+
+    let client = reqwest::Client::new();
+    let response = crate::engine::net::apply(client.post(rpc_url), rpc_url)
+        .json(&body)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await;
+    let batched = match response {
+        Ok(resp) if resp.status().is_success() => resp.json::<serde_json::Value>().await.ok(),
+        _ => None,
+    };
+    if let Some(results) = batched.and_then(|v| map_batch_response(calls.len(), v)) {
+        return results;
+    }
+
+    warn!("[dex] RPC provider rejected batch request, falling back to individual calls");
+    let mut results = Vec::with_capacity(calls.len());
+    for (method, params) in calls {
+        results.push(rpc_call(rpc_url, method, params.clone()).await);
+    }
+    results
+}
+
+/// Map a batch reply back to call order by id. `None` when the reply isn't a
+/// batch array (provider doesn't support batching).
+fn map_batch_response(
+    count: usize,
+    response: serde_json::Value,
+) -> Option<Vec<EngineResult<serde_json::Value>>> {
+    let entries = match response {
+        serde_json::Value::Array(entries) => entries,
+        _ => return None,
+    };
+    let mut results: Vec<EngineResult<serde_json::Value>> = (0..count)
+        .map(|id| {
+            Err(EngineError::Other(format!(
+                "RPC batch missing response for id {}",
+                id
+            )))
+        })
+        .collect();
+    for entry in entries {
+        let Some(id) = entry
+            .get("id")
+            .and_then(|v| v.as_u64())
+            .map(|id| id as usize)
+        else {
+            continue;
+        };
+        if id >= count {
+            continue;
+        }
+        results[id] = if let Some(error) = entry.get("error") {
+            Err(EngineError::Other(format!("RPC error: {}", error)))
+        } else {
+            entry
+                .get("result")
+                .cloned()
+                .ok_or_else(|| EngineError::Other("RPC response missing 'result' field".into()))
+        };
+    }
+    Some(results)
+}
+
+/// Batched read-only contract calls: `(to, data)` pairs → hex results, in order.
+pub(crate) async fn eth_call_batch(
+    rpc_url: &str,
+    calls: &[(&str, Vec<u8>)],
+) -> Vec<EngineResult<String>> {
+    let rpc_calls: Vec<(&str, serde_json::Value)> = calls
+        .iter()
+        .map(|(to, data)| {
+            (
+                "eth_call",
+                serde_json::json!([{ "to": to, "data": hex_encode(data) }, "latest"]),
+            )
+        })
+        .collect();
+    rpc_batch(rpc_url, &rpc_calls)
+        .await
+        .into_iter()
+        .map(|r| {
+            r.and_then(|v| {
+                v.as_str()
+                    .map(String::from)
+                    .ok_or(EngineError::Other("Invalid eth_call result".into()))
+            })
+        })
+        .collect()
+}
+
 /// Get ETH balance of an address
 pub(crate) async fn eth_get_balance(rpc_url: &str, address: &str) -> EngineResult<String> {
     let result = rpc_call(
@@ -153,6 +267,52 @@ pub(crate) async fn eth_send_raw_transaction(
         .ok_or(EngineError::Other("Invalid tx hash result".into()))
 }
 
+/// Where a signed transaction was broadcast.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SubmissionRoute {
+    /// Private relay (e.g. Flashbots Protect) — never seen by the public mempool.
+    PrivateRelay(String),
+    /// Public mempool via the regular RPC.
+    Public,
+}
+
+impl SubmissionRoute {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            SubmissionRoute::PrivateRelay(host) => format!("private relay ({})", host),
+            SubmissionRoute::Public => "public mempool".into(),
+        }
+    }
+}
+
+/// Broadcast a signed transaction through `relay_url` when set, otherwise
+/// via the public RPC. A failing relay is an error — silently falling back
+/// to the public mempool would defeat the point of using one.
+pub(crate) async fn submit_raw_transaction(
+    rpc_url: &str,
+    relay_url: Option<&str>,
+    signed_tx: &[u8],
+) -> EngineResult<(String, SubmissionRoute)> {
+    let Some(relay_url) = relay_url else {
+        let hash = eth_send_raw_transaction(rpc_url, signed_tx).await?;
+        return Ok((hash, SubmissionRoute::Public));
+    };
+    // Only the host is reported — relay URLs can carry auth tokens
+    let host = reqwest::Url::parse(relay_url)
+        .ok()
+        .and_then(|u| u.host_str().map(String::from))
+        .unwrap_or_else(|| "configured relay".into());
+    let hash = eth_send_raw_transaction(relay_url, signed_tx)
+        .await
+        .map_err(|e| {
+            EngineError::Other(format!(
+                "Private relay {} rejected the transaction: {}",
+                host, e
+            ))
+        })?;
+    Ok((hash, SubmissionRoute::PrivateRelay(host)))
+}
+
 /// Get chain ID
 pub(crate) async fn eth_chain_id(rpc_url: &str) -> EngineResult<u64> {
     let result = rpc_call(rpc_url, "eth_chainId", serde_json::json!([])).await?;
@@ -163,6 +323,16 @@ pub(crate) async fn eth_chain_id(rpc_url: &str) -> EngineResult<u64> {
         .map_err(|e| EngineError::Other(format!("Parse chain ID: {}", e)))
 }
 
+/// Current block number (chain head)
+pub(crate) async fn eth_block_number(rpc_url: &str) -> EngineResult<u64> {
+    let result = rpc_call(rpc_url, "eth_blockNumber", serde_json::json!([])).await?;
+    let hex = result
+        .as_str()
+        .ok_or(EngineError::Other("Invalid block number".into()))?;
+    u64::from_str_radix(hex.strip_prefix("0x").unwrap_or(hex), 16)
+        .map_err(|e| EngineError::Other(format!("Parse block number: {}", e)))
+}
+
 /// Get transaction receipt (to check if tx was mined)
 pub(crate) async fn eth_get_transaction_receipt(
     rpc_url: &str,
@@ -181,6 +351,56 @@ pub(crate) async fn eth_get_transaction_receipt(
     }
 }
 
+/// First and largest gap between receipt polls.
+const RECEIPT_POLL_START: Duration = Duration::from_secs(1);
+const RECEIPT_POLL_CAP: Duration = Duration::from_secs(5);
+
+/// Outcome of waiting for a transaction to be mined.
+#[derive(Debug)]
+pub(crate) enum ReceiptStatus {
+    /// Mined with status 0x1.
+    Confirmed(serde_json::Value),
+    /// Mined but reverted.
+    Reverted(serde_json::Value),
+    /// Not mined before the deadline.
+    Pending,
+}
+
+/// Next gap between polls: doubles from 1s up to a 5s cap.
+pub(crate) fn next_poll_delay(current: Duration) -> Duration {
+    (current * 2).min(RECEIPT_POLL_CAP)
+}
+
+/// Poll for `tx_hash`'s receipt, backing off from 1s to 5s between polls,
+/// until it is mined or `max_wait` has passed. RPC errors count as "not yet".
+pub(crate) async fn wait_for_receipt(
+    rpc_url: &str,
+    tx_hash: &str,
+    max_wait: Duration,
+) -> ReceiptStatus {
+    let deadline = tokio::time::Instant::now() + max_wait;
+    let mut delay = RECEIPT_POLL_START;
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return ReceiptStatus::Pending;
+        }
+        tokio::time::sleep(delay.min(remaining)).await;
+        if let Ok(Some(receipt)) = eth_get_transaction_receipt(rpc_url, tx_hash).await {
+            let status = receipt
+                .get("status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("0x0");
+            return if status == "0x1" {
+                ReceiptStatus::Confirmed(receipt)
+            } else {
+                ReceiptStatus::Reverted(receipt)
+            };
+        }
+        delay = next_poll_delay(delay);
+    }
+}
+
 /// Chunked eth_getLogs — splits large block ranges into smaller chunks to avoid
 /// RPC provider limits (many free tiers limit to 500-2000 blocks per request).
 /// Returns all matching logs combined from all chunks.
@@ -219,3 +439,111 @@ pub(crate) async fn chunked_get_logs(
 
     Ok(all_logs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{self, MockReply, Requests};
+
+    /// JSON-RPC endpoint answering every call with `body`.
+    async fn mock_rpc(body: &'static str) -> (String, Requests) {
+        test_support::serve(MockReply::json(200, body)).await
+    }
+
+    #[test]
+    fn receipt_polling_backs_off_to_a_cap() {
+        let mut delay = RECEIPT_POLL_START;
+        let mut delays = vec![delay.as_secs()];
+        for _ in 0..4 {
+            delay = next_poll_delay(delay);
+            delays.push(delay.as_secs());
+        }
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+    }
+
+    #[tokio::test]
+    async fn wait_for_receipt_distinguishes_outcomes() {
+        let (url, _) =
+            mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":{"status":"0x0","blockNumber":"0x10"}}"#)
+                .await;
+        assert!(matches!(
+            wait_for_receipt(&url, "0xabc", Duration::from_secs(5)).await,
+            ReceiptStatus::Reverted(_)
+        ));
+
+        let (url, requests) = mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":null}"#).await;
+        let started = std::time::Instant::now();
+        assert!(matches!(
+            wait_for_receipt(&url, "0xabc", Duration::from_millis(2500)).await,
+            ReceiptStatus::Pending
+        ));
+        // Polled at ~1s and ~2.5s (second gap cut to the deadline), then gave up
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(requests.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn batch_maps_out_of_order_responses_by_id() {
+        // Providers may answer in any order and omit or fail entries
+        let (url, _) = mock_rpc(
+            r#"[{"jsonrpc":"2.0","id":2,"result":"0x03"},
+                {"jsonrpc":"2.0","id":0,"result":"0x01"},
+                {"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted"}}]"#,
+        )
+        .await;
+        let calls = vec![
+            ("eth_call", serde_json::json!([{ "to": "0xa" }, "latest"])),
+            ("eth_call", serde_json::json!([{ "to": "0xb" }, "latest"])),
+            ("eth_call", serde_json::json!([{ "to": "0xc" }, "latest"])),
+            ("eth_chainId", serde_json::json!([])),
+        ];
+        let results = rpc_batch(&url, &calls).await;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap(), "0x01");
+        assert!(results[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("execution reverted"));
+        assert_eq!(results[2].as_ref().unwrap(), "0x03");
+        assert!(results[3].is_err());
+    }
+
+    #[tokio::test]
+    async fn non_array_reply_falls_back_to_single_calls() {
+        // A provider without batch support answers with a single object
+        let (url, _) = mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0x2a"}"#).await;
+        let results = eth_call_batch(&url, &[("0xa", vec![1]), ("0xb", vec![2])]).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.as_deref().ok() == Some("0x2a")));
+    }
+
+    #[tokio::test]
+    async fn relay_receives_raw_transaction_when_configured() {
+        let (public_url, public_requests) =
+            mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0xpublic"}"#).await;
+        let (relay_url, relay_requests) =
+            mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"0xrelay"}"#).await;
+        let signed_tx = [0x02u8, 0xf8, 0x6b, 0x01];
+
+        let (hash, route) = submit_raw_transaction(&public_url, Some(&relay_url), &signed_tx)
+            .await
+            .unwrap();
+        assert_eq!(hash, "0xrelay");
+        assert_eq!(route, SubmissionRoute::PrivateRelay("127.0.0.1".into()));
+        assert!(public_requests.lock().is_empty());
+        let sent: serde_json::Value =
+            serde_json::from_str(&test_support::request_body(&relay_requests.lock()[0])).unwrap();
+        assert_eq!(sent["method"], "eth_sendRawTransaction");
+        assert_eq!(sent["params"][0], "0x02f86b01");
+
+        // No relay configured → public mempool
+        let (hash, route) = submit_raw_transaction(&public_url, None, &signed_tx)
+            .await
+            .unwrap();
+        assert_eq!(hash, "0xpublic");
+        assert_eq!(route.describe(), "public mempool");
+    }
+}
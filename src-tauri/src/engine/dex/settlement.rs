@@ -0,0 +1,262 @@
+// Paw Agent Engine — DEX Settlement Verification
+//
+// After a swap or transfer is mined, read what actually moved from the
+// receipt logs instead of trusting the quote: ERC-20 Transfer events to the
+// recipient, or the WETH Withdrawal when the router unwrapped to native ETH.
+// Comparing that to the expected amount catches fee-on-transfer and
+// rebasing tokens, which deliver less than the pool quoted. The receipt also
+// says what the transaction cost in gas.
+
+use super::events::parse_transfer_logs;
+use super::primitives::{hex_decode, raw_to_amount};
+
+/// keccak256("Withdrawal(address,uint256)") — emitted by WETH9 on unwrap.
+pub(crate) const WITHDRAWAL_EVENT_TOPIC: &str =
+    "0x7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65";
+
+/// Shortfall below the expected amount that is reported as a discrepancy
+/// (in basis points) when no minimum was enforced.
+const DISCREPANCY_BPS: u128 = 10;
+
+fn same_address(a: &str, b: &str) -> bool {
+    a.trim_start_matches("0x")
+        .eq_ignore_ascii_case(b.trim_start_matches("0x"))
+}
+
+/// A uint256 log word as u128, saturating (token amounts never get close).
+fn word_to_u128(data: &str) -> Option<u128> {
+    let bytes = hex_decode(data).ok()?;
+    let word = bytes.get(..32)?;
+    if word[..16].iter().any(|b| *b != 0) {
+        return Some(u128::MAX);
+    }
+    Some(u128::from_be_bytes(word[16..].try_into().ok()?))
+}
+
+fn logs(receipt: &serde_json::Value) -> impl Iterator<Item = &serde_json::Value> {
+    receipt["logs"].as_array().into_iter().flatten()
+}
+
+/// Total of `token` Transfer events to `recipient` in a receipt, or None
+/// when the receipt has no such transfer.
+pub(crate) fn received_from_logs(
+    receipt: &serde_json::Value,
+    token: &str,
+    recipient: &str,
+) -> Option<u128> {
+    parse_transfer_logs(receipt)
+        .iter()
+        .filter(|event| same_address(&event.token, token) && same_address(&event.to, recipient))
+        .map(|event| u256_to_u128(&event.value))
+        .reduce(|a, b| a.saturating_add(b))
+}
+
+/// Total WETH unwrapped to native ETH in a receipt (WETH9 Withdrawal events).
+pub(crate) fn unwrapped_from_logs(receipt: &serde_json::Value, weth: &str) -> Option<u128> {
+    logs(receipt)
+        .filter(|log| same_address(log["address"].as_str().unwrap_or(""), weth))
+        .filter_map(|log| {
+            let topic = log["topics"].as_array()?.first()?.as_str()?;
+            topic
+                .eq_ignore_ascii_case(WITHDRAWAL_EVENT_TOPIC)
+                .then(|| word_to_u128(log["data"].as_str()?))
+                .flatten()
+        })
+        .reduce(|a, b| a.saturating_add(b))
+}
+
+/// A 32-byte big-endian amount as u128, saturating.
+pub(crate) fn u256_to_u128(value: &[u8; 32]) -> u128 {
+    if value[..16].iter().any(|b| *b != 0) {
+        u128::MAX
+    } else {
+        u128::from_be_bytes(value[16..].try_into().unwrap_or_default())
+    }
+}
+
+fn display(raw: u128, decimals: u8) -> String {
+    raw_to_amount(&format!("0x{:x}", raw), decimals).unwrap_or_else(|_| raw.to_string())
+}
+
+/// One report line comparing what arrived with what was expected.
+/// `minimum` is the amount the contract enforced (swap min-out), if any.
+pub(crate) fn verification_line(
+    symbol: &str,
+    decimals: u8,
+    expected: u128,
+    minimum: Option<u128>,
+    actual: Option<u128>,
+) -> String {
+    let Some(actual) = actual else {
+        return format!(
+            "Verified: [warn] no {} transfer to the recipient found in the receipt logs",
+            symbol
+        );
+    };
+    let shortfall_bps = if expected > actual {
+        (expected - actual).saturating_mul(10_000) / expected.max(1)
+    } else {
+        0
+    };
+    let below_minimum = minimum.is_some_and(|min| actual < min);
+    let flagged = below_minimum || (minimum.is_none() && shortfall_bps > DISCREPANCY_BPS);
+    if flagged {
+        format!(
+            "Verified: [warn] received {} {} — {:.2}% less than the expected {} {}{}. The token likely charges a fee on transfer.",
+            display(actual, decimals),
+            symbol,
+            shortfall_bps as f64 / 100.0,
+            display(expected, decimals),
+            symbol,
+            if below_minimum { ", below the enforced minimum" } else { "" }
+        )
+    } else {
+        format!(
+            "Verified: received {} {} (expected ~{} {})",
+            display(actual, decimals),
+            symbol,
+            display(expected, decimals),
+            symbol
+        )
+    }
+}
+
+/// Fee a mined transaction paid, in wei: gasUsed × effectiveGasPrice, plus
+/// the L1 data fee OP-stack chains (Base, Optimism) report as `l1Fee`.
+pub(crate) fn gas_cost_wei(receipt: &serde_json::Value) -> Option<u128> {
+    let quantity = |key: &str| -> Option<u128> {
+        u128::from_str_radix(receipt[key].as_str()?.trim_start_matches("0x"), 16).ok()
+    };
+    let execution = quantity("gasUsed")?.checked_mul(quantity("effectiveGasPrice")?)?;
+    Some(execution.saturating_add(quantity("l1Fee").unwrap_or(0)))
+}
+
+/// Report line for the gas paid by one or more transactions (e.g. approval
+/// + swap), in ETH and — when a price is known — USD.
+pub(crate) fn gas_cost_line(costs: &[(&str, u128)], eth_usd: Option<f64>) -> String {
+    let total = costs
+        .iter()
+        .fold(0u128, |sum, (_, wei)| sum.saturating_add(*wei));
+    let usd = eth_usd
+        .and_then(|price| {
+            let eth: f64 = display(total, 18).parse().ok()?;
+            Some(format!(" (~${:.2})", eth * price))
+        })
+        .unwrap_or_default();
+    let breakdown = if costs.len() > 1 {
+        format!(
+            " — {}",
+            costs
+                .iter()
+                .map(|(label, wei)| format!("{} {}", label, display(*wei, 18)))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        )
+    } else {
+        String::new()
+    };
+    format!("Gas cost: {} ETH{}{}", display(total, 18), usd, breakdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::dex::constants::TRANSFER_EVENT_TOPIC;
+    use serde_json::json;
+
+    const TOKEN: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const WALLET: &str = "0x1111111111111111111111111111111111111111";
+
+    fn transfer_log(token: &str, to: &str, amount: u128) -> serde_json::Value {
+        json!({
+            "address": token.to_lowercase(),
+            "topics": [
+                TRANSFER_EVENT_TOPIC,
+                "0x0000000000000000000000002222222222222222222222222222222222222222",
+                format!("0x000000000000000000000000{}", to.trim_start_matches("0x").to_lowercase()),
+            ],
+            "data": format!("0x{:064x}", amount),
+        })
+    }
+
+    #[test]
+    fn parses_received_amount_from_transfer_logs() {
+        let receipt = json!({
+            "status": "0x1",
+            "logs": [
+                // Pool pays the wallet 1,500.25 USDC
+                transfer_log(TOKEN, WALLET, 1_500_250_000),
+                // Unrelated transfers: another recipient, another token
+                transfer_log(TOKEN, "0x3333333333333333333333333333333333333333", 7),
+                transfer_log("0x6B175474E89094C44Da98b954EedeAC495271d0F", WALLET, 9),
+            ],
+        });
+        assert_eq!(
+            received_from_logs(&receipt, TOKEN, WALLET),
+            Some(1_500_250_000)
+        );
+        assert_eq!(
+            received_from_logs(
+                &receipt,
+                TOKEN,
+                "0x4444444444444444444444444444444444444444"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn flags_fee_on_transfer_shortfall() {
+        let ok = verification_line("USDC", 6, 1_000_000, Some(995_000), Some(998_000));
+        assert!(ok.starts_with("Verified: received 0.998"), "{}", ok);
+        // A 5% transfer tax lands below the enforced minimum
+        let taxed = verification_line("TAX", 6, 1_000_000, Some(995_000), Some(950_000));
+        assert!(
+            taxed.contains("[warn]") && taxed.contains("5.00%"),
+            "{}",
+            taxed
+        );
+        let missing = verification_line("TAX", 6, 1_000_000, None, None);
+        assert!(missing.contains("no TAX transfer"), "{}", missing);
+    }
+
+    #[test]
+    fn computes_gas_cost_from_receipt() {
+        // 150,000 gas at 20 gwei
+        let receipt = json!({ "gasUsed": "0x249f0", "effectiveGasPrice": "0x4a817c800" });
+        assert_eq!(gas_cost_wei(&receipt), Some(3_000_000_000_000_000));
+        // OP-stack receipts add the L1 data fee
+        let base = json!({ "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00", "l1Fee": "0x2540be400" });
+        assert_eq!(
+            gas_cost_wei(&base),
+            Some(21_000_000_000_000 + 10_000_000_000)
+        );
+        assert_eq!(gas_cost_wei(&json!({ "gasUsed": "0x5208" })), None);
+
+        assert_eq!(
+            gas_cost_line(&[("swap", 3_000_000_000_000_000)], Some(2500.0)),
+            "Gas cost: 0.003 ETH (~$7.50)"
+        );
+        assert_eq!(
+            gas_cost_line(
+                &[
+                    ("approval", 1_000_000_000_000_000),
+                    ("swap", 3_000_000_000_000_000)
+                ],
+                None
+            ),
+            "Gas cost: 0.004 ETH — approval 0.001 + swap 0.003"
+        );
+    }
+
+    #[test]
+    fn reads_weth_withdrawals() {
+        let weth = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+        let receipt = json!({ "logs": [{
+            "address": weth,
+            "topics": [WITHDRAWAL_EVENT_TOPIC, "0x0000000000000000000000002222222222222222222222222222222222222222"],
+            "data": format!("0x{:064x}", 10u128.pow(17)),
+        }]});
+        assert_eq!(unwrapped_from_logs(&receipt, weth), Some(10u128.pow(17)));
+    }
+}
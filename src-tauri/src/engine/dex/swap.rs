@@ -2,26 +2,174 @@
 
 use super::abi::{
     build_multihop_path, encode_allowance, encode_approve, encode_exact_input,
-    encode_exact_input_single, encode_quote_exact_input, encode_quote_exact_input_single,
-    u256_to_quantity_hex,
-};
-use super::constants::{
-    explorer_tx_url, DEFAULT_FEE_TIER, DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS, UNISWAP_QUOTER_V2,
-    UNISWAP_SWAP_ROUTER_02, WETH_ADDRESS,
+    encode_exact_input_single, encode_multicall, encode_quote_exact_input,
+    encode_quote_exact_input_single, encode_unwrap_weth9, u256_to_quantity_hex,
 };
+use super::chains::{private_relay_url, supported_chain, ChainInfo};
+use super::confirm::{confirm_timeout, required_confirmations, wait_for_confirmation, TxReport};
+use super::constants::{explorer_tx_url, DEFAULT_FEE_TIER, DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS};
+use super::permit::{detect_permit, signed_self_permit, PERMIT_DEADLINE_SECS};
+use super::portfolio::eth_usd_price;
 use super::primitives::{
     amount_to_raw, hex_decode, hex_encode, parse_address, parse_u256_decimal, raw_to_amount,
 };
 use super::rpc::{
-    eth_call, eth_chain_id, eth_estimate_gas, eth_get_transaction_count,
-    eth_get_transaction_receipt, eth_send_raw_transaction, get_gas_fees,
+    eth_call, eth_estimate_gas, eth_get_transaction_count, eth_send_raw_transaction, get_gas_fees,
+    submit_raw_transaction, wait_for_receipt, ReceiptStatus,
+};
+use super::settlement::{
+    gas_cost_line, gas_cost_wei, received_from_logs, u256_to_u128, unwrapped_from_logs,
+    verification_line,
+};
+use super::tokens::{assumed_decimals_note, resolve_for_swap, token_decimals};
+use super::tx::{
+    parse_access_list, sign_eip1559_transaction, sign_eip1559_transaction_with_access_list,
 };
-use super::tokens::resolve_for_swap;
-use super::tx::sign_eip1559_transaction;
 use crate::atoms::error::{EngineError, EngineResult};
 use log::info;
 use std::collections::HashMap;
-use std::time::Duration;
+
+/// Hub tokens tried for two-hop routes when the direct pool can't quote a pair.
+const HUB_TOKENS: &[&str] = &["WETH", "USDC"];
+
+/// A Uniswap V3 route priced by the quoter.
+struct Route {
+    /// Hub token of a two-hop route; None for the direct pool.
+    via: Option<&'static str>,
+    /// Encoded multi-hop path (empty for the direct pool).
+    path: Vec<u8>,
+    amount_out: [u8; 32],
+}
+
+impl Route {
+    fn describe(&self, token_in: &str, token_out: &str) -> String {
+        match self.via {
+            Some(hub) => format!(
+                "Route: {} → {} → {} (multi-hop via {}: two pool fees, so more price impact than a direct pool)",
+                token_in.to_uppercase(),
+                hub,
+                token_out.to_uppercase(),
+                hub
+            ),
+            None => format!(
+                "Route: {} → {} (direct)",
+                token_in.to_uppercase(),
+                token_out.to_uppercase()
+            ),
+        }
+    }
+}
+
+/// Hub tokens on `chain` usable between `token_in` and `token_out`.
+fn hub_tokens(
+    chain: &ChainInfo,
+    token_in: &[u8; 20],
+    token_out: &[u8; 20],
+) -> Vec<(&'static str, [u8; 20])> {
+    HUB_TOKENS
+        .iter()
+        .filter_map(|hub| {
+            let addr = if *hub == "WETH" {
+                chain.weth
+            } else {
+                chain.tokens.iter().find(|(sym, _, _)| sym == hub)?.1
+            };
+            let bytes = parse_address(addr).ok()?;
+            (bytes != *token_in && bytes != *token_out).then_some((*hub, bytes))
+        })
+        .collect()
+}
+
+/// First 32 bytes of a quoter response: amountOut.
+pub(super) fn quoted_amount(result: &str) -> EngineResult<[u8; 32]> {
+    let bytes = hex_decode(result)?;
+    if bytes.len() < 32 {
+        return Err(format!("Unexpected quoter response length: {} bytes", bytes.len()).into());
+    }
+    Ok(bytes[..32]
+        .try_into()
+        .map_err(|_| "Failed to parse 32-byte amount from quoter response")?)
+}
+
+/// Quote the direct pool at `fee_tier`; when it reverts or returns zero, quote
+/// the two-hop routes through each hub token and keep the best output.
+async fn find_route(
+    rpc_url: &str,
+    chain: &ChainInfo,
+    token_in: &[u8; 20],
+    token_out: &[u8; 20],
+    amount_in: &[u8; 32],
+    fee_tier: u32,
+) -> EngineResult<Route> {
+    let single_calldata = encode_quote_exact_input_single(token_in, token_out, amount_in, fee_tier);
+    let direct = match eth_call(rpc_url, chain.quoter, &single_calldata).await {
+        Ok(r) => quoted_amount(&r),
+        Err(e) => Err(e),
+    };
+    let direct_failure = match direct {
+        Ok(amount_out) if amount_out != [0u8; 32] => {
+            return Ok(Route {
+                via: None,
+                path: Vec::new(),
+                amount_out,
+            })
+        }
+        Ok(_) => "direct pool quoted zero output".to_string(),
+        Err(e) => format!("direct pool: {}", e),
+    };
+    info!("[dex] {} — trying two-hop routes", direct_failure);
+
+    let hubs = hub_tokens(chain, token_in, token_out);
+    let mut best: Option<Route> = None;
+    for (hub, hub_bytes) in &hubs {
+        let path = build_multihop_path(&[token_in, hub_bytes, token_out], &[fee_tier, fee_tier]);
+        let quoted = match eth_call(
+            rpc_url,
+            chain.quoter,
+            &encode_quote_exact_input(&path, amount_in),
+        )
+        .await
+        {
+            Ok(r) => quoted_amount(&r),
+            Err(e) => Err(e),
+        };
+        match quoted {
+            Ok(amount_out)
+                if amount_out != [0u8; 32]
+                    && best.as_ref().is_none_or(|b| amount_out > b.amount_out) =>
+            {
+                best = Some(Route {
+                    via: Some(*hub),
+                    path,
+                    amount_out,
+                });
+            }
+            Ok(_) => info!("[dex] Route via {} quoted no better output", hub),
+            Err(e) => info!("[dex] Route via {} failed: {}", hub, e),
+        }
+    }
+
+    best.ok_or_else(|| {
+        let tried = hubs.iter().map(|(h, _)| *h).collect::<Vec<_>>().join(", ");
+        EngineError::Other(format!(
+            "No Uniswap V3 route found at the {}% fee tier ({}; routes via {} returned no output). Try another fee_tier.",
+            fee_tier as f64 / 10000.0,
+            direct_failure,
+            if tried.is_empty() { "no hub tokens" } else { tried.as_str() }
+        ))
+    })
+}
+
+/// Notes (one per line, each "\n"-prefixed) for quoted tokens whose
+/// decimals were assumed because `decimals()` failed. Swaps refuse such
+/// tokens instead.
+fn decimals_notes(tokens: &[(&str, bool)]) -> String {
+    tokens
+        .iter()
+        .filter(|(_, assumed)| *assumed)
+        .map(|(sym, _)| format!("\n{}", assumed_decimals_note(&sym.to_uppercase())))
+        .collect()
+}
 
 /// Get a swap quote from Uniswap V3 Quoter.
 pub async fn execute_dex_quote(
@@ -29,6 +177,7 @@ pub async fn execute_dex_quote(
     creds: &HashMap<String, String>,
 ) -> EngineResult<String> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = supported_chain(creds, rpc_url).await?;
     let token_in_sym = args["token_in"]
         .as_str()
         .ok_or("dex_quote: missing 'token_in'")?;
@@ -39,8 +188,10 @@ pub async fn execute_dex_quote(
         .as_str()
         .ok_or("dex_quote: missing 'amount'")?;
 
-    let (token_in_addr, token_in_dec, _is_eth) = resolve_for_swap(token_in_sym)?;
-    let (token_out_addr, token_out_dec, _) = resolve_for_swap(token_out_sym)?;
+    let (token_in_addr, _, _is_eth) = resolve_for_swap(chain, token_in_sym)?;
+    let (token_out_addr, _, _) = resolve_for_swap(chain, token_out_sym)?;
+    let (token_in_dec, in_assumed) = token_decimals(chain, rpc_url, &token_in_addr).await;
+    let (token_out_dec, out_assumed) = token_decimals(chain, rpc_url, &token_out_addr).await;
 
     let fee_tier = args
         .get("fee_tier")
@@ -53,50 +204,17 @@ pub async fn execute_dex_quote(
 
     let token_in_bytes = parse_address(&token_in_addr)?;
     let token_out_bytes = parse_address(&token_out_addr)?;
-    let weth_bytes = parse_address(WETH_ADDRESS)?;
-
-    // Try single-hop first, then multi-hop through WETH if direct pool doesn't exist
-    let mut used_multihop = false;
-    let result = {
-        let single_calldata = encode_quote_exact_input_single(
-            &token_in_bytes,
-            &token_out_bytes,
-            &amount_u256,
-            fee_tier,
-        );
-        match eth_call(rpc_url, UNISWAP_QUOTER_V2, &single_calldata).await {
-            Ok(r) => Ok(r),
-            Err(_) if token_in_bytes != weth_bytes && token_out_bytes != weth_bytes => {
-                // Try multi-hop: tokenIn → WETH → tokenOut
-                info!("[dex] Single-hop quote failed, trying multi-hop through WETH");
-                used_multihop = true;
-                let path = build_multihop_path(
-                    &[&token_in_bytes, &weth_bytes, &token_out_bytes],
-                    &[fee_tier, fee_tier],
-                );
-                let multi_calldata = encode_quote_exact_input(&path, &amount_u256);
-                eth_call(rpc_url, UNISWAP_QUOTER_V2, &multi_calldata).await
-            }
-            Err(e) => Err(e),
-        }
-    }?;
-
-    // The quoter returns (amountOut, sqrtPriceX96After, initializedTicksCrossed, gasEstimate)
-    // amountOut is the first 32 bytes
-    let result_bytes = hex_decode(&result)?;
-    if result_bytes.len() < 32 {
-        return Err(format!(
-            "Unexpected quoter response length: {} bytes",
-            result_bytes.len()
-        )
-        .into());
-    }
 
-    let amount_out_bytes: [u8; 32] = result_bytes[..32]
-        .try_into()
-        .map_err(|_| "Failed to parse 32-byte amount from quoter response")?;
-    let amount_out_hex = hex_encode(&amount_out_bytes);
-    let amount_out = raw_to_amount(&amount_out_hex, token_out_dec)?;
+    let route = find_route(
+        rpc_url,
+        chain,
+        &token_in_bytes,
+        &token_out_bytes,
+        &amount_u256,
+        fee_tier,
+    )
+    .await?;
+    let amount_out = raw_to_amount(&hex_encode(&route.amount_out), token_out_dec)?;
 
     // Calculate price
     let in_f64: f64 = amount.parse().unwrap_or(0.0);
@@ -110,22 +228,10 @@ pub async fn execute_dex_quote(
 
     let min_out = out_f64 * (10000.0 - slippage_bps as f64) / 10000.0;
 
-    let route_info = if used_multihop {
-        format!(
-            "Route: {} → WETH → {} (multi-hop)",
-            token_in_sym.to_uppercase(),
-            token_out_sym.to_uppercase()
-        )
-    } else {
-        format!(
-            "Route: {} → {} (direct)",
-            token_in_sym.to_uppercase(),
-            token_out_sym.to_uppercase()
-        )
-    };
+    let route_info = route.describe(token_in_sym, token_out_sym);
 
     Ok(format!(
-        "Swap Quote: {} {} → {} {}\n\nInput: {} {}\nExpected Output: {} {}\nMinimum Output ({}% slippage): {:.6} {}\nExchange Rate: 1 {} = {:.6} {}\n{}\nFee Tier: {}%\n\nUse dex_swap to execute this trade.",
+        "Swap Quote: {} {} → {} {}\n\nInput: {} {}\nExpected Output: {} {}\nMinimum Output ({}% slippage): {:.6} {}\nExchange Rate: 1 {} = {:.6} {}\n{}\nFee Tier: {}%{}\n\nUse dex_swap to execute this trade.",
         amount, token_in_sym.to_uppercase(),
         amount_out, token_out_sym.to_uppercase(),
         amount, token_in_sym.to_uppercase(),
@@ -135,15 +241,170 @@ pub async fn execute_dex_quote(
         token_in_sym.to_uppercase(), price, token_out_sym.to_uppercase(),
         route_info,
         fee_tier as f64 / 10000.0,
+        decimals_notes(&[(token_in_sym, in_assumed), (token_out_sym, out_assumed)]),
     ))
 }
 
+/// Opt-in slippage auto-adjustment: when a swap reverts on slippage it is
+/// re-quoted and retried once at a higher tolerance. Enabled by the
+/// DEX_SLIPPAGE_RETRY_MAX_BPS credential (the ceiling, never above
+/// MAX_SLIPPAGE_BPS). A retry above DEX_SLIPPAGE_RETRY_APPROVAL_BPS (default
+/// 100 = 1%) is not made automatically — the user is asked to approve a new
+/// dex_swap at that tolerance instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SlippageRetry {
+    max_bps: u64,
+    approval_bps: u64,
+}
+
+/// Default tolerance above which a retry needs a new, approved dex_swap.
+const SLIPPAGE_RETRY_APPROVAL_BPS: u64 = 100;
+
+impl SlippageRetry {
+    fn from_creds(creds: &HashMap<String, String>) -> Option<Self> {
+        let bps = |key: &str| creds.get(key).and_then(|v| v.trim().parse::<u64>().ok());
+        let max_bps = bps("DEX_SLIPPAGE_RETRY_MAX_BPS")
+            .filter(|b| *b > 0)?
+            .min(MAX_SLIPPAGE_BPS);
+        Some(SlippageRetry {
+            max_bps,
+            approval_bps: bps("DEX_SLIPPAGE_RETRY_APPROVAL_BPS")
+                .unwrap_or(SLIPPAGE_RETRY_APPROVAL_BPS),
+        })
+    }
+
+    /// Tolerance for the retry: double the current one (at least +0.25%),
+    /// capped at the ceiling. None when already at the ceiling.
+    fn next_bps(&self, current: u64) -> Option<u64> {
+        let next = (current * 2).max(current + 25).min(self.max_bps);
+        (next > current).then_some(next)
+    }
+}
+
+/// Revert reasons that mean the output fell below amountOutMinimum. "STF"
+/// (safeTransferFrom failed) is a balance or allowance problem, so a higher
+/// tolerance would not help and it is not retried.
+const SLIPPAGE_REVERT_REASONS: &[&str] = &[
+    "Too little received",
+    "Too much requested",
+    "Price slippage check",
+    "INSUFFICIENT_OUTPUT_AMOUNT",
+];
+
+/// The slippage revert reason in an RPC error, if any.
+fn slippage_revert_reason(error: &str) -> Option<&'static str> {
+    SLIPPAGE_REVERT_REASONS
+        .iter()
+        .copied()
+        .find(|reason| error.contains(reason))
+}
+
+/// One swap attempt at a given slippage tolerance.
+struct SwapAttempt {
+    report: String,
+    /// A swap transaction was broadcast and hasn't reverted.
+    landed: bool,
+    /// Set when the swap reverted (or would revert) on slippage.
+    slippage_revert: Option<&'static str>,
+}
+
+/// Run `attempt` at `slippage_bps`; on a slippage revert, retry exactly once
+/// at the tolerance chosen by `retry` and report the adjustment.
+async fn with_slippage_retry<F, Fut>(
+    slippage_bps: u64,
+    retry: Option<SlippageRetry>,
+    mut attempt: F,
+) -> EngineResult<TxReport>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: std::future::Future<Output = EngineResult<SwapAttempt>>,
+{
+    let first = attempt(slippage_bps).await?;
+    let (Some(reason), Some(retry)) = (first.slippage_revert, retry) else {
+        return Ok(TxReport {
+            text: first.report,
+            landed: first.landed,
+        });
+    };
+    let pct = |bps: u64| bps as f64 / 100.0;
+    let unsent = |text: String| TxReport {
+        text,
+        landed: false,
+    };
+    let Some(next) = retry.next_bps(slippage_bps) else {
+        return Ok(unsent(format!(
+            "{}\n\nSlippage auto-adjust: reverted ({}) at {}%, already the {}% ceiling — not retrying.",
+            first.report,
+            reason,
+            pct(slippage_bps),
+            pct(retry.max_bps)
+        )));
+    };
+    if next > retry.approval_bps {
+        return Ok(unsent(format!(
+            "{}\n\nSlippage auto-adjust: reverted ({}) at {}%. Retrying at {}% is above the {}% auto-retry limit — call dex_swap again with slippage_bps={} to retry with approval.",
+            first.report,
+            reason,
+            pct(slippage_bps),
+            pct(next),
+            pct(retry.approval_bps),
+            next
+        )));
+    }
+    info!(
+        "[dex] Swap reverted on slippage ({}) at {}bps — retrying once at {}bps",
+        reason, slippage_bps, next
+    );
+    let second = attempt(next).await?;
+    let text = format!(
+        "{}\n\nSlippage auto-adjust: reverted ({}) at {}% — re-quoted and retried once at {}% (ceiling {}%).\n\n{}",
+        first.report,
+        reason,
+        pct(slippage_bps),
+        pct(next),
+        pct(retry.max_bps),
+        second.report
+    );
+    Ok(TxReport {
+        text,
+        landed: second.landed,
+    })
+}
+
 /// Execute a token swap on Uniswap V3.
 pub async fn execute_dex_swap(
     args: &serde_json::Value,
     creds: &HashMap<String, String>,
-) -> EngineResult<String> {
+) -> EngineResult<TxReport> {
+    let slippage_bps = args
+        .get("slippage_bps")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_SLIPPAGE_BPS);
+
+    if slippage_bps > MAX_SLIPPAGE_BPS {
+        return Err(format!(
+            "Slippage {}bps exceeds maximum allowed {}bps ({}%)",
+            slippage_bps,
+            MAX_SLIPPAGE_BPS,
+            MAX_SLIPPAGE_BPS as f64 / 100.0
+        )
+        .into());
+    }
+
+    with_slippage_retry(slippage_bps, SlippageRetry::from_creds(creds), |bps| {
+        swap_once(args, creds, bps)
+    })
+    .await
+}
+
+/// Quote, build, sign and broadcast one swap at `slippage_bps`.
+async fn swap_once(
+    args: &serde_json::Value,
+    creds: &HashMap<String, String>,
+    slippage_bps: u64,
+) -> EngineResult<SwapAttempt> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = supported_chain(creds, rpc_url).await?;
     let wallet_address = creds
         .get("DEX_WALLET_ADDRESS")
         .ok_or("No wallet. Use dex_wallet_create first.")?;
@@ -160,28 +421,24 @@ pub async fn execute_dex_swap(
         .ok_or("dex_swap: missing 'amount'")?;
     let _reason = args["reason"].as_str().unwrap_or("swap");
 
-    let slippage_bps = args
-        .get("slippage_bps")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(DEFAULT_SLIPPAGE_BPS);
-
-    if slippage_bps > MAX_SLIPPAGE_BPS {
-        return Err(format!(
-            "Slippage {}bps exceeds maximum allowed {}bps ({}%)",
-            slippage_bps,
-            MAX_SLIPPAGE_BPS,
-            MAX_SLIPPAGE_BPS as f64 / 100.0
-        )
-        .into());
-    }
-
     let fee_tier = args
         .get("fee_tier")
         .and_then(|v| v.as_u64())
         .unwrap_or(DEFAULT_FEE_TIER) as u32;
 
-    let (token_in_addr, token_in_dec, is_eth_in) = resolve_for_swap(token_in_sym)?;
-    let (token_out_addr, token_out_dec, _) = resolve_for_swap(token_out_sym)?;
+    let (token_in_addr, _, is_eth_in) = resolve_for_swap(chain, token_in_sym)?;
+    let (token_out_addr, _, is_eth_out) = resolve_for_swap(chain, token_out_sym)?;
+    let (token_in_dec, in_assumed) = token_decimals(chain, rpc_url, &token_in_addr).await;
+    let (token_out_dec, out_assumed) = token_decimals(chain, rpc_url, &token_out_addr).await;
+    // A wrong guess would sign for an amount off by orders of magnitude
+    for (addr, assumed) in [(&token_in_addr, in_assumed), (&token_out_addr, out_assumed)] {
+        if assumed {
+            return Err(EngineError::Other(format!(
+                "Could not read decimals() from token {}. Refusing to swap an amount that cannot be converted reliably.",
+                addr
+            )));
+        }
+    }
 
     let amount_raw = amount_to_raw(amount, token_in_dec)?;
     let amount_u256 = parse_u256_decimal(&amount_raw)?;
@@ -189,52 +446,24 @@ pub async fn execute_dex_swap(
     let token_in_bytes = parse_address(&token_in_addr)?;
     let token_out_bytes = parse_address(&token_out_addr)?;
     let wallet_bytes = parse_address(wallet_address)?;
+    let router_bytes = parse_address(chain.router)?;
 
     info!(
         "[dex] Swap: {} {} → {} (wallet: {})",
         amount, token_in_sym, token_out_sym, wallet_address
     );
 
-    // Step 1: Get quote for minimum output calculation — try single-hop, fall back to multi-hop via WETH
-    let weth_bytes = parse_address(WETH_ADDRESS)?;
-    let mut use_multihop = false;
-    let expected_out: [u8; 32] = {
-        let single_calldata = encode_quote_exact_input_single(
-            &token_in_bytes,
-            &token_out_bytes,
-            &amount_u256,
-            fee_tier,
-        );
-        match eth_call(rpc_url, UNISWAP_QUOTER_V2, &single_calldata).await {
-            Ok(r) => {
-                let qb = hex_decode(&r)?;
-                if qb.len() < 32 {
-                    return Err("Invalid quoter response".into());
-                }
-                qb[..32]
-                    .try_into()
-                    .map_err(|_| "Quoter response byte conversion failed")?
-            }
-            Err(_) if token_in_bytes != weth_bytes && token_out_bytes != weth_bytes => {
-                info!("[dex] Single-hop quote failed, trying multi-hop through WETH");
-                use_multihop = true;
-                let path = build_multihop_path(
-                    &[&token_in_bytes, &weth_bytes, &token_out_bytes],
-                    &[fee_tier, fee_tier],
-                );
-                let multi_calldata = encode_quote_exact_input(&path, &amount_u256);
-                let r = eth_call(rpc_url, UNISWAP_QUOTER_V2, &multi_calldata).await?;
-                let qb = hex_decode(&r)?;
-                if qb.len() < 32 {
-                    return Err("Invalid quoter response".into());
-                }
-                qb[..32]
-                    .try_into()
-                    .map_err(|_| "Quoter response byte conversion failed")?
-            }
-            Err(e) => return Err(e),
-        }
-    };
+    // Step 1: Quote the best route (direct pool, else two hops via WETH/USDC)
+    let swap_route = find_route(
+        rpc_url,
+        chain,
+        &token_in_bytes,
+        &token_out_bytes,
+        &amount_u256,
+        fee_tier,
+    )
+    .await?;
+    let expected_out = swap_route.amount_out;
 
     // Apply slippage to get minimum output
     let expected_out_hex = hex_encode(&expected_out);
@@ -248,9 +477,52 @@ pub async fn execute_dex_swap(
     )?;
     let min_out_u256 = parse_u256_decimal(&min_out_raw)?;
 
-    // Step 2: If not ETH, check and set token approval
+    // Step 2: Build the swap call for the quoted route. Selling to native ETH
+    // leaves the WETH with the router, which unwraps it to the wallet in the
+    // same multicall; min_out is enforced by the swap and again by the unwrap.
+    let swap_recipient = if is_eth_out {
+        &router_bytes
+    } else {
+        &wallet_bytes
+    };
+    let swap_data = if swap_route.via.is_some() {
+        encode_exact_input(
+            &swap_route.path,
+            swap_recipient,
+            &amount_u256,
+            &min_out_u256,
+        )
+    } else {
+        encode_exact_input_single(
+            &token_in_bytes,
+            &token_out_bytes,
+            fee_tier,
+            swap_recipient,
+            &amount_u256,
+            &min_out_u256,
+        )
+    };
+    let mut calls = vec![swap_data];
+    if is_eth_out {
+        calls.push(encode_unwrap_weth9(&min_out_u256, &wallet_bytes));
+    }
+    let deadline = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        + PERMIT_DEADLINE_SECS;
+    let mut tx_data = if calls.len() == 1 {
+        calls[0].clone()
+    } else {
+        encode_multicall(deadline, &calls)
+    };
+
+    // Step 3: If not ETH, check token approval — an ERC-2612 permit bundled with
+    // the swap when the token supports it, otherwise a separate approve tx
+    let mut approval_note = "";
+    let mut approval_gas = None;
+    let mut pending_approval: Option<(String, u64)> = None;
     if !is_eth_in {
-        let router_bytes = parse_address(UNISWAP_SWAP_ROUTER_02)?;
         let allowance_data = encode_allowance(&wallet_bytes, &router_bytes);
         let allowance_result = eth_call(rpc_url, &token_in_addr, &allowance_data).await?;
         let allowance_bytes = hex_decode(&allowance_result)?;
@@ -264,6 +536,38 @@ pub async fn execute_dex_swap(
             needs_approval = allowance_slice < amount_u256;
         }
 
+        if needs_approval {
+            let chain_id = chain.id;
+            if let Some(support) =
+                detect_permit(rpc_url, chain_id, &token_in_addr, &wallet_bytes).await
+            {
+                let pk_bytes = hex_decode(private_key_hex)?;
+                let signing_key = k256::ecdsa::SigningKey::from_slice(&pk_bytes)
+                    .map_err(|e| EngineError::Other(e.to_string()))?;
+                let permit_call = signed_self_permit(
+                    &support,
+                    chain_id,
+                    &token_in_addr,
+                    wallet_address,
+                    chain.router,
+                    &amount_u256,
+                    deadline,
+                    &signing_key,
+                )?;
+                let bundled = encode_multicall(deadline, &[vec![permit_call], calls].concat());
+                // Tokens with a non-standard permit (e.g. DAI) fail here and use approve
+                if eth_estimate_gas(rpc_url, wallet_address, chain.router, &bundled, "0x0")
+                    .await
+                    .is_ok()
+                {
+                    info!("[dex] Using ERC-2612 permit for {}", token_in_addr);
+                    tx_data = bundled;
+                    approval_note = "\nApproval: signed permit (no approve transaction)";
+                    needs_approval = false;
+                }
+            }
+        }
+
         if needs_approval {
             info!("[dex] Approving token {} for router", token_in_addr);
             let max_approval = [0xffu8; 32]; // type(uint256).max
@@ -273,7 +577,7 @@ pub async fn execute_dex_swap(
             let signing_key = k256::ecdsa::SigningKey::from_slice(&pk_bytes)
                 .map_err(|e| EngineError::Other(e.to_string()))?;
 
-            let chain_id = eth_chain_id(rpc_url).await?;
+            let chain_id = chain.id;
             let nonce = eth_get_transaction_count(rpc_url, wallet_address).await?;
             let (priority_fee, max_fee) = get_gas_fees(rpc_url).await?;
             let gas = eth_estimate_gas(
@@ -302,56 +606,27 @@ pub async fn execute_dex_swap(
 
             let approve_hash = eth_send_raw_transaction(rpc_url, &signed_approve).await?;
             info!("[dex] Approval tx: {}", approve_hash);
-
-            // Wait for approval to be mined (poll for up to 60 seconds)
-            for _ in 0..30 {
-                tokio::time::sleep(Duration::from_secs(2)).await;
-                if let Ok(Some(receipt)) = eth_get_transaction_receipt(rpc_url, &approve_hash).await
-                {
-                    let status = receipt
-                        .get("status")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("0x0");
-                    if status == "0x1" {
-                        info!("[dex] Token approval confirmed");
-                        break;
-                    } else {
-                        return Err(format!(
-                            "Token approval transaction failed (reverted). Tx: {}",
-                            approve_hash
-                        )
-                        .into());
-                    }
-                }
-            }
+            // The swap is queued right behind it (nonce + 1) instead of
+            // waiting for it to be mined
+            pending_approval = Some((approve_hash, nonce));
         }
     }
 
-    // Step 3: Build the swap transaction (single-hop or multi-hop as determined by quote)
-    let swap_data = if use_multihop {
-        let path = build_multihop_path(
-            &[&token_in_bytes, &weth_bytes, &token_out_bytes],
-            &[fee_tier, fee_tier],
-        );
-        encode_exact_input(&path, &wallet_bytes, &amount_u256, &min_out_u256)
-    } else {
-        encode_exact_input_single(
-            &token_in_bytes,
-            &token_out_bytes,
-            fee_tier,
-            &wallet_bytes,
-            &amount_u256,
-            &min_out_u256,
-        )
-    };
-
     let pk_bytes = hex_decode(private_key_hex)?;
     let signing_key = k256::ecdsa::SigningKey::from_slice(&pk_bytes)
         .map_err(|e| EngineError::Other(e.to_string()))?;
 
-    let chain_id = eth_chain_id(rpc_url).await?;
-    let nonce = eth_get_transaction_count(rpc_url, wallet_address).await?;
+    let chain_id = chain.id;
+    let nonce_override = pending_approval.as_ref().map(|(_, nonce)| nonce + 1);
+    let nonce = match nonce_override {
+        Some(nonce) => nonce,
+        None => eth_get_transaction_count(rpc_url, wallet_address).await?,
+    };
     let (priority_fee, max_fee) = get_gas_fees(rpc_url).await?;
+    let access_list = match args.get("access_list") {
+        Some(list) if !list.is_null() => parse_access_list(list)?,
+        _ => Vec::new(),
+    };
 
     // Value is the ETH amount if swapping from ETH, otherwise 0
     let value = if is_eth_in { amount_u256 } else { [0u8; 32] };
@@ -361,18 +636,35 @@ pub async fn execute_dex_swap(
         "0x0".into()
     };
 
-    let router_bytes = parse_address(UNISWAP_SWAP_ROUTER_02)?;
-    let gas = eth_estimate_gas(
-        rpc_url,
-        wallet_address,
-        UNISWAP_SWAP_ROUTER_02,
-        &swap_data,
-        &value_hex,
-    )
-    .await
-    .unwrap_or(300_000); // fallback gas limit for swaps
+    // Until the queued approval is mined the router has no allowance, so
+    // the estimate would revert; use the fallback limit instead
+    let estimate = match pending_approval {
+        Some(_) => Err(EngineError::Other("approval pending".into())),
+        None => eth_estimate_gas(rpc_url, wallet_address, chain.router, &tx_data, &value_hex).await,
+    };
+    let gas = match estimate {
+        Ok(gas) => gas,
+        // Don't broadcast a swap the pre-flight check says will revert on slippage
+        Err(e) => match slippage_revert_reason(&e.to_string()) {
+            Some(reason) => {
+                return Ok(SwapAttempt {
+                    report: format!(
+                        "[failed] Swap not submitted\n\n{} {} → {}: the pre-flight check reverted ({}) at {}% slippage tolerance. Nothing was spent.",
+                        amount,
+                        token_in_sym.to_uppercase(),
+                        token_out_sym.to_uppercase(),
+                        reason,
+                        slippage_bps as f64 / 100.0
+                    ),
+                    landed: false,
+                    slippage_revert: Some(reason),
+                })
+            }
+            None => 300_000, // fallback gas limit for swaps
+        },
+    };
 
-    let signed_tx = sign_eip1559_transaction(
+    let signed_tx = sign_eip1559_transaction_with_access_list(
         chain_id,
         nonce,
         priority_fee,
@@ -380,56 +672,245 @@ pub async fn execute_dex_swap(
         gas,
         &router_bytes,
         &value,
-        &swap_data,
+        &tx_data,
+        &access_list,
         &signing_key,
     )?;
 
-    // Step 4: Broadcast
-    let tx_hash = eth_send_raw_transaction(rpc_url, &signed_tx).await?;
-    info!("[dex] Swap tx broadcast: {}", tx_hash);
-
-    // Step 5: Wait for confirmation (up to 2 minutes)
-    let mut confirmed = false;
-    let mut final_status = "pending";
-    for _ in 0..60 {
-        tokio::time::sleep(Duration::from_secs(2)).await;
-        match eth_get_transaction_receipt(rpc_url, &tx_hash).await {
-            Ok(Some(receipt)) => {
-                let status = receipt
-                    .get("status")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("0x0");
-                if status == "0x1" {
-                    confirmed = true;
-                    final_status = "confirmed";
-                } else {
-                    final_status = "reverted";
-                }
-                break;
+    // Step 4: Broadcast — via the chain's private relay when configured
+    let (tx_hash, route) =
+        submit_raw_transaction(rpc_url, private_relay_url(creds, chain_id), &signed_tx).await?;
+    info!(
+        "[dex] Swap tx broadcast via {}: {}",
+        route.describe(),
+        tx_hash
+    );
+
+    // The queued swap can only succeed if the approval ahead of it does
+    if let Some((approve_hash, _)) = &pending_approval {
+        match wait_for_receipt(rpc_url, approve_hash, confirm_timeout(args, 1)).await {
+            ReceiptStatus::Confirmed(receipt) => {
+                info!("[dex] Token approval confirmed");
+                approval_gas = gas_cost_wei(&receipt);
+            }
+            ReceiptStatus::Reverted(_) => {
+                let network = explorer_tx_url(chain_id);
+                return Ok(SwapAttempt {
+                    report: format!(
+                        "[failed] Swap Failed\n\nThe token approval for {} reverted, so the swap queued behind it will revert too. No tokens were swapped; only gas was spent.\nApproval tx: {}{}\nSwap tx: {}{}",
+                        token_in_sym.to_uppercase(),
+                        network,
+                        approve_hash,
+                        network,
+                        tx_hash
+                    ),
+                    landed: false,
+                    slippage_revert: None,
+                });
+            }
+            ReceiptStatus::Pending => {
+                approval_note =
+                    "\nApproval: still pending — the swap will execute after it is mined";
             }
-            Ok(None) => continue, // Not mined yet
-            Err(_) => continue,
         }
     }
 
+    // Step 5: Wait until the swap is the configured number of blocks deep
+    let required = required_confirmations(creds, chain_id);
+    let confirmation =
+        wait_for_confirmation(rpc_url, &tx_hash, required, confirm_timeout(args, required)).await;
+    let confirmed = confirmation.is_confirmed();
+    let final_status = confirmation.status;
+
+    // A mined revert carries no reason; replaying the call shows whether the
+    // price is still outside the tolerance
+    let slippage_revert = if final_status == "reverted" {
+        eth_estimate_gas(rpc_url, wallet_address, chain.router, &tx_data, &value_hex)
+            .await
+            .err()
+            .and_then(|e| slippage_revert_reason(&e.to_string()))
+    } else {
+        None
+    };
+
+    // Step 6: Check what actually arrived against the quote
+    let verification = match (&confirmation.receipt, confirmed) {
+        (Some(receipt), true) => {
+            let actual = if is_eth_out {
+                unwrapped_from_logs(receipt, chain.weth)
+            } else {
+                received_from_logs(receipt, &token_out_addr, wallet_address)
+            };
+            format!(
+                "\n{}",
+                verification_line(
+                    &token_out_sym.to_uppercase(),
+                    token_out_dec,
+                    u256_to_u128(&expected_out),
+                    Some(u256_to_u128(&min_out_u256)),
+                    actual,
+                )
+            )
+        }
+        _ => String::new(),
+    };
+
+    // Gas paid once the swap is mined (reverted or not), plus any approval
+    let gas_report = match confirmation.receipt.as_ref().and_then(gas_cost_wei) {
+        Some(swap_gas) => {
+            let costs: Vec<(&str, u128)> = approval_gas
+                .map(|wei| ("approval", wei))
+                .into_iter()
+                .chain([("swap", swap_gas)])
+                .collect();
+            format!(
+                "\n{}",
+                gas_cost_line(&costs, eth_usd_price(chain, rpc_url).await)
+            )
+        }
+        None => String::new(),
+    };
+
     let network = explorer_tx_url(chain_id);
 
     let expected_out_display =
         raw_to_amount(&expected_out_hex, token_out_dec).unwrap_or("?".into());
 
-    Ok(format!(
-        "{} Swap {}\n\n{} {} → ~{} {}\nSlippage tolerance: {}%\nTransaction: {}{}\nStatus: {}\n\n{}",
+    let report = format!(
+        "{} Swap {}\n\n{} {} → ~{} {}\n{}\nReceived as: {}\nSlippage tolerance: {}%\nTransaction: {}{}\nSubmitted via: {}{}\nStatus: {}{}{}\n\n{}",
         if confirmed { "[ok]" } else { "[pending]" },
         if confirmed { "Confirmed" } else { "Submitted" },
         amount, token_in_sym.to_uppercase(),
         expected_out_display, token_out_sym.to_uppercase(),
+        swap_route.describe(token_in_sym, token_out_sym),
+        if is_eth_out {
+            "native ETH (router unwrapped the WETH)".to_string()
+        } else {
+            token_out_sym.to_uppercase()
+        },
         slippage_bps as f64 / 100.0,
         network, tx_hash,
-        final_status,
+        route.describe(),
+        approval_note,
+        confirmation.describe(),
+        verification,
+        gas_report,
         if !confirmed && final_status == "pending" {
             "Transaction is still pending. Check the explorer link for status."
         } else if final_status == "reverted" {
             "Transaction reverted! The swap may have failed due to slippage or liquidity issues. Your tokens are safe."
         } else { "" },
-    ))
+    );
+    Ok(SwapAttempt {
+        report,
+        landed: final_status != "reverted",
+        slippage_revert,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::dex::chains::chain_by_id;
+
+    #[test]
+    fn hub_tokens_skip_the_pair_itself() {
+        let mainnet = chain_by_id(1).unwrap();
+        let weth = parse_address(mainnet.weth).unwrap();
+        let link = parse_address("0x514910771AF9Ca656af840dff83E8264EcF986CA").unwrap();
+        let pepe = parse_address("0x6982508145454Ce325dDbE47a25d4ec3d2311933").unwrap();
+
+        let hubs: Vec<&str> = hub_tokens(mainnet, &link, &pepe)
+            .into_iter()
+            .map(|(h, _)| h)
+            .collect();
+        assert_eq!(hubs, vec!["WETH", "USDC"]);
+        let hubs: Vec<&str> = hub_tokens(mainnet, &weth, &pepe)
+            .into_iter()
+            .map(|(h, _)| h)
+            .collect();
+        assert_eq!(hubs, vec!["USDC"]);
+    }
+
+    #[test]
+    fn route_description_names_the_hub() {
+        let route = Route {
+            via: Some("USDC"),
+            path: Vec::new(),
+            amount_out: [0u8; 32],
+        };
+        assert!(route
+            .describe("link", "pepe")
+            .starts_with("Route: LINK → USDC → PEPE (multi-hop via USDC"));
+        let direct = Route { via: None, ..route };
+        assert_eq!(direct.describe("eth", "usdc"), "Route: ETH → USDC (direct)");
+    }
+
+    fn reverting_attempt(bps: u64) -> SwapAttempt {
+        SwapAttempt {
+            report: format!("attempt at {}bps", bps),
+            landed: false,
+            slippage_revert: Some("Too little received"),
+        }
+    }
+
+    async fn attempts_with(retry: Option<SlippageRetry>, start: u64) -> (Vec<u64>, String) {
+        let tried = std::sync::Mutex::new(Vec::new());
+        let report = with_slippage_retry(start, retry, |bps| {
+            tried.lock().unwrap().push(bps);
+            async move { Ok(reverting_attempt(bps)) }
+        })
+        .await
+        .unwrap();
+        assert!(!report.landed, "a reverted swap must not count as landed");
+        (tried.into_inner().unwrap(), report.text)
+    }
+
+    #[tokio::test]
+    async fn slippage_revert_retries_once_up_to_the_ceiling() {
+        let retry = SlippageRetry {
+            max_bps: 80,
+            approval_bps: 100,
+        };
+        // Reverts both times: exactly one retry, doubled but capped at 0.8%
+        let (tried, report) = attempts_with(Some(retry), 50).await;
+        assert_eq!(tried, vec![50, 80]);
+        assert!(report.contains("retried once at 0.8%"), "{}", report);
+
+        // Already at the ceiling: no retry
+        let (tried, report) = attempts_with(Some(retry), 80).await;
+        assert_eq!(tried, vec![80]);
+        assert!(report.contains("not retrying"), "{}", report);
+
+        // Off unless configured
+        let (tried, _) = attempts_with(None, 50).await;
+        assert_eq!(tried, vec![50]);
+    }
+
+    #[tokio::test]
+    async fn large_slippage_bumps_need_a_new_approved_swap() {
+        let retry = SlippageRetry {
+            max_bps: MAX_SLIPPAGE_BPS,
+            approval_bps: 100,
+        };
+        let (tried, report) = attempts_with(Some(retry), 100).await;
+        assert_eq!(tried, vec![100]);
+        assert!(report.contains("slippage_bps=200"), "{}", report);
+    }
+
+    #[test]
+    fn retry_ceiling_never_exceeds_the_hard_maximum() {
+        let creds = HashMap::from([("DEX_SLIPPAGE_RETRY_MAX_BPS".to_string(), "5000".to_string())]);
+        let retry = SlippageRetry::from_creds(&creds).unwrap();
+        assert_eq!(retry.max_bps, MAX_SLIPPAGE_BPS);
+        assert_eq!(retry.next_bps(400), Some(MAX_SLIPPAGE_BPS));
+        assert_eq!(SlippageRetry::from_creds(&HashMap::new()), None);
+        assert_eq!(slippage_revert_reason("execution reverted: STF"), None);
+        assert_eq!(
+            slippage_revert_reason(
+                r#"RPC error: {"message":"execution reverted: Too little received"}"#
+            ),
+            Some("Too little received")
+        );
+    }
 }
@@ -4,12 +4,13 @@ use super::abi::{
     decode_abi_string, encode_decimals, encode_name, encode_owner, encode_quote_exact_input_single,
     encode_symbol, encode_total_supply,
 };
-use super::constants::{chain_name, UNISWAP_QUOTER_V2, WETH_ADDRESS};
+use super::chains::{chain_id_of, chain_of};
+use super::constants::chain_name;
 use super::primitives::{
     amount_to_raw, eip55_checksum, hex_decode, hex_encode, parse_address, parse_u256_decimal,
     raw_to_amount,
 };
-use super::rpc::{eth_call, eth_chain_id, eth_get_balance, rpc_call};
+use super::rpc::{eth_call, eth_get_balance, rpc_call};
 use crate::atoms::error::EngineResult;
 use std::collections::HashMap;
 
@@ -20,6 +21,7 @@ pub async fn execute_dex_token_info(
     creds: &HashMap<String, String>,
 ) -> EngineResult<String> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = chain_of(creds);
     let token_address = args["token_address"]
         .as_str()
         .ok_or("dex_token_info: missing 'token_address'. Provide the ERC-20 contract address.")?;
@@ -144,13 +146,13 @@ pub async fn execute_dex_token_info(
 
     let token_bytes = parse_address(addr_clean)?;
     let tiny_amount = parse_u256_decimal("1000000000000000")?; // 0.001 ETH in wei
-    let weth_addr_bytes = parse_address(WETH_ADDRESS)?;
+    let weth_addr_bytes = parse_address(chain.weth)?;
 
     for fee in &[3000u32, 10000, 500, 100] {
         let quote_data =
             encode_quote_exact_input_single(&weth_addr_bytes, &token_bytes, &tiny_amount, *fee);
 
-        if let Ok(result) = eth_call(rpc_url, UNISWAP_QUOTER_V2, &quote_data).await {
+        if let Ok(result) = eth_call(rpc_url, chain.quoter, &quote_data).await {
             let result_bytes = hex_decode(&result).unwrap_or_default();
             if result_bytes.len() >= 32 {
                 let amount_out: [u8; 32] = result_bytes[..32]
@@ -173,7 +175,7 @@ pub async fn execute_dex_token_info(
                                 &sell_u256,
                                 *fee,
                             );
-                            match eth_call(rpc_url, UNISWAP_QUOTER_V2, &reverse_quote).await {
+                            match eth_call(rpc_url, chain.quoter, &reverse_quote).await {
                                 Ok(rev_result) => {
                                     let rev_bytes = hex_decode(&rev_result).unwrap_or_default();
                                     if rev_bytes.len() >= 32 {
@@ -219,7 +221,7 @@ pub async fn execute_dex_token_info(
     }
 
     // 9. Chain info
-    if let Ok(chain_id) = eth_chain_id(rpc_url).await {
+    if let Ok(chain_id) = chain_id_of(creds, rpc_url).await {
         let chain = chain_name(chain_id);
         output.push_str(&format!("\n  Network: {} (chain ID {})\n", chain, chain_id));
     }
@@ -234,6 +236,7 @@ pub async fn execute_dex_check_token(
     creds: &HashMap<String, String>,
 ) -> EngineResult<String> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = chain_of(creds);
     let token_address = args["token_address"]
         .as_str()
         .ok_or("dex_check_token: missing 'token_address'")?;
@@ -248,7 +251,7 @@ pub async fn execute_dex_check_token(
     let mut flags: Vec<String> = Vec::new();
 
     let token_bytes = parse_address(addr_clean)?;
-    let weth_bytes = parse_address(WETH_ADDRESS)?;
+    let weth_bytes = parse_address(chain.weth)?;
 
     // Check 1: Is it actually a contract?
     let code_result = rpc_call(
@@ -335,7 +338,7 @@ pub async fn execute_dex_check_token(
     for fee in &[3000u32, 10000, 500, 100] {
         let buy_quote =
             encode_quote_exact_input_single(&weth_bytes, &token_bytes, &tiny_amount, *fee);
-        if let Ok(result) = eth_call(rpc_url, UNISWAP_QUOTER_V2, &buy_quote).await {
+        if let Ok(result) = eth_call(rpc_url, chain.quoter, &buy_quote).await {
             let result_bytes = hex_decode(&result).unwrap_or_default();
             if result_bytes.len() >= 32 {
                 let out: [u8; 32] = result_bytes[..32]
@@ -359,7 +362,7 @@ pub async fn execute_dex_check_token(
                                 &sell_u256,
                                 *fee,
                             );
-                            match eth_call(rpc_url, UNISWAP_QUOTER_V2, &sell_quote).await {
+                            match eth_call(rpc_url, chain.quoter, &sell_quote).await {
                                 Ok(rev) => {
                                     let rev_bytes = hex_decode(&rev).unwrap_or_default();
                                     if rev_bytes.len() >= 32 {
@@ -1,14 +1,35 @@
 // Paw Agent Engine — DEX Token Resolution
 
-use super::constants::{KNOWN_TOKENS, WETH_ADDRESS};
+use super::abi::{decode_abi_string, encode_decimals, encode_symbol};
+use super::chains::ChainInfo;
+use super::primitives::hex_decode;
+use super::rpc::eth_call;
 use crate::atoms::error::{EngineError, EngineResult};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
-/// Resolve a token symbol or address to (address, decimals).
-pub(crate) fn resolve_token(symbol_or_address: &str) -> EngineResult<(String, u8)> {
+/// (rpc_url, lowercase token address) → on-chain symbol.
+static TOKEN_SYMBOLS: LazyLock<Mutex<HashMap<(String, String), String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// (rpc_url, lowercase token address) → on-chain decimals.
+static TOKEN_DECIMALS: LazyLock<Mutex<HashMap<(String, String), u8>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Decimals assumed for a token whose `decimals()` call fails.
+pub(crate) const FALLBACK_DECIMALS: u8 = 18;
+
+/// Resolve a token symbol (on `chain`) or address to (address, decimals).
+/// Decimals of an unlisted address are a placeholder — use `token_decimals`.
+pub(crate) fn resolve_token(
+    chain: &ChainInfo,
+    symbol_or_address: &str,
+) -> EngineResult<(String, u8)> {
     let input = symbol_or_address.trim().to_uppercase();
 
     // Check known tokens by symbol
-    for (sym, addr, dec) in KNOWN_TOKENS {
+    for (sym, addr, dec) in chain.tokens {
         if input == *sym {
             return Ok((addr.to_string(), *dec));
         }
@@ -17,14 +38,16 @@ pub(crate) fn resolve_token(symbol_or_address: &str) -> EngineResult<(String, u8
     // Check if it's an address
     let lower = symbol_or_address.trim().to_lowercase();
     if lower.starts_with("0x") && lower.len() == 42 {
-        // Unknown token — assume 18 decimals (caller can override)
-        return Ok((symbol_or_address.trim().to_string(), 18));
+        let dec = listed_token(chain, &lower).map_or(FALLBACK_DECIMALS, |(_, dec)| dec);
+        return Ok((symbol_or_address.trim().to_string(), dec));
     }
 
     Err(EngineError::Other(format!(
-        "Unknown token '{}'. Use a known symbol ({}) or provide the ERC-20 contract address.",
+        "Unknown token '{}' on {}. Use a known symbol ({}) or provide the ERC-20 contract address.",
         symbol_or_address,
-        KNOWN_TOKENS
+        chain.name,
+        chain
+            .tokens
             .iter()
             .map(|(s, _, _)| *s)
             .collect::<Vec<_>>()
@@ -34,13 +57,146 @@ pub(crate) fn resolve_token(symbol_or_address: &str) -> EngineResult<(String, u8
 
 /// For swaps, if token_in is "ETH" we need to use WETH as the Uniswap input.
 /// Returns (address, decimals, is_native_eth).
-pub(crate) fn resolve_for_swap(symbol_or_address: &str) -> EngineResult<(String, u8, bool)> {
+pub(crate) fn resolve_for_swap(
+    chain: &ChainInfo,
+    symbol_or_address: &str,
+) -> EngineResult<(String, u8, bool)> {
     let input = symbol_or_address.trim().to_uppercase();
     if input == "ETH" {
         // Swap uses WETH but sends ETH value
-        Ok((WETH_ADDRESS.to_string(), 18, true))
+        Ok((chain.weth.to_string(), 18, true))
     } else {
-        let (addr, dec) = resolve_token(symbol_or_address)?;
+        let (addr, dec) = resolve_token(chain, symbol_or_address)?;
         Ok((addr, dec, false))
     }
 }
+
+/// The chain's listed (symbol, decimals) for a token address.
+fn listed_token(chain: &ChainInfo, address: &str) -> Option<(&'static str, u8)> {
+    chain
+        .tokens
+        .iter()
+        .find(|(_, addr, _)| addr.eq_ignore_ascii_case(address.trim()))
+        .map(|(sym, _, dec)| (*sym, *dec))
+}
+
+/// Decode a `decimals()` return value — a uint8 in one ABI word.
+pub(crate) fn decode_decimals(hex_data: &str) -> Option<u8> {
+    let bytes = hex_decode(hex_data).ok()?;
+    let word = bytes.get(..32)?;
+    word[..31].iter().all(|&b| b == 0).then_some(word[31])
+}
+
+/// Decode a `symbol()` return value — an ABI string, or a bytes32 on older
+/// tokens (e.g. MKR). None when it isn't printable text.
+pub(crate) fn decode_symbol(hex_data: &str) -> Option<String> {
+    let bytes = hex_decode(hex_data).ok()?;
+    let symbol = if bytes.len() == 32 {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(32);
+        String::from_utf8(bytes[..end].to_vec()).ok()?
+    } else {
+        decode_abi_string(hex_data).ok()?
+    };
+    let symbol = symbol.trim().to_string();
+    (!symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_graphic())).then_some(symbol)
+}
+
+/// `0x1234…abcd`
+pub(crate) fn short_address(address: &str) -> String {
+    match (
+        address.get(..6),
+        address.get(address.len().saturating_sub(4)..),
+    ) {
+        (Some(head), Some(tail)) if address.len() > 12 => format!("{}…{}", head, tail),
+        _ => address.to_string(),
+    }
+}
+
+/// On-chain symbol for a token address, cached per address. Falls back to a
+/// shortened address (uncached) when `symbol()` fails.
+pub(crate) async fn fetch_token_symbol(rpc_url: &str, address: &str) -> String {
+    let key = (rpc_url.to_string(), address.trim().to_lowercase());
+    if let Some(symbol) = TOKEN_SYMBOLS.lock().get(&key) {
+        return symbol.clone();
+    }
+    match eth_call(rpc_url, address, &encode_symbol())
+        .await
+        .ok()
+        .and_then(|r| decode_symbol(&r))
+    {
+        Some(symbol) => {
+            TOKEN_SYMBOLS.lock().insert(key, symbol.clone());
+            symbol
+        }
+        None => short_address(address),
+    }
+}
+
+/// On-chain `decimals()` for a token address, cached per address. None when
+/// the call reverts or returns something else (uncached, so it is retried).
+pub(crate) async fn fetch_decimals(rpc_url: &str, token_addr: &str) -> Option<u8> {
+    let key = (rpc_url.to_string(), token_addr.trim().to_lowercase());
+    if let Some(decimals) = TOKEN_DECIMALS.lock().get(&key) {
+        return Some(*decimals);
+    }
+    let decimals = eth_call(rpc_url, token_addr.trim(), &encode_decimals())
+        .await
+        .ok()
+        .and_then(|r| decode_decimals(&r))?;
+    TOKEN_DECIMALS.lock().insert(key, decimals);
+    Some(decimals)
+}
+
+/// Decimals of a resolved token: the listed value for the chain's known
+/// tokens, otherwise read on-chain. The flag is true when `decimals()` failed
+/// and FALLBACK_DECIMALS was assumed.
+pub(crate) async fn token_decimals(chain: &ChainInfo, rpc_url: &str, address: &str) -> (u8, bool) {
+    if let Some((_, dec)) = listed_token(chain, address) {
+        return (dec, false);
+    }
+    match fetch_decimals(rpc_url, address).await {
+        Some(dec) => (dec, false),
+        None => (FALLBACK_DECIMALS, true),
+    }
+}
+
+/// Report line for a token whose decimals had to be assumed.
+pub(crate) fn assumed_decimals_note(token: &str) -> String {
+    format!(
+        "Note: {} did not answer decimals() — amounts assume {} decimals and may be wrong.",
+        token, FALLBACK_DECIMALS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_string_symbol() {
+        // ABI string "UNI": offset 0x20, length 3, data
+        let hex = format!("0x{:0>64}{:0>64}{:0<64}", "20", "3", "554e49");
+        assert_eq!(decode_symbol(&hex).as_deref(), Some("UNI"));
+    }
+
+    #[test]
+    fn decodes_decimals_word() {
+        assert_eq!(decode_decimals(&format!("0x{:064x}", 6)), Some(6));
+        assert_eq!(decode_decimals(&format!("0x{:064x}", 8)), Some(8));
+        // Not a uint8, or nothing at all (e.g. an EOA)
+        assert_eq!(decode_decimals(&format!("0x{:064x}", 256)), None);
+        assert_eq!(decode_decimals("0x"), None);
+    }
+
+    #[test]
+    fn decodes_bytes32_symbol() {
+        // MKR returns its symbol as bytes32
+        let hex = format!("0x{:0<64}", "4d4b52");
+        assert_eq!(decode_symbol(&hex).as_deref(), Some("MKR"));
+        assert_eq!(decode_symbol(&format!("0x{:0<64}", "")), None);
+        assert_eq!(
+            short_address("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            "0xA0b8…eB48"
+        );
+    }
+}
@@ -1,19 +1,22 @@
 // Paw Agent Engine — DEX ERC-20 / ETH Transfer
 
 use super::abi::{encode_balance_of, encode_transfer};
+use super::chains::{chain_id_of, chain_of};
+use super::confirm::{confirm_timeout, required_confirmations, wait_for_confirmation, TxReport};
 use super::constants::explorer_tx_url;
+use super::portfolio::eth_usd_price;
 use super::primitives::{
     amount_to_raw, hex_decode, parse_address, parse_u256_decimal, raw_to_amount,
 };
 use super::rpc::{
-    eth_call, eth_chain_id, eth_estimate_gas, eth_get_balance, eth_get_transaction_count,
-    eth_get_transaction_receipt, eth_send_raw_transaction, get_gas_fees,
+    eth_call, eth_estimate_gas, eth_get_balance, eth_get_transaction_count,
+    eth_send_raw_transaction, get_gas_fees,
 };
-use super::tokens::resolve_token;
+use super::settlement;
+use super::tokens::{resolve_token, token_decimals};
 use super::tx::sign_eip1559_transaction;
 use crate::atoms::error::{EngineError, EngineResult};
 use std::collections::HashMap;
-use std::time::Duration;
 
 /// Transfer ETH or ERC-20 tokens to an external address.
 /// For ETH: simple value transfer (21000 gas, no calldata).
@@ -21,8 +24,9 @@ use std::time::Duration;
 pub async fn execute_dex_transfer(
     args: &serde_json::Value,
     creds: &HashMap<String, String>,
-) -> EngineResult<String> {
+) -> EngineResult<TxReport> {
     let rpc_url = creds.get("DEX_RPC_URL").ok_or("Missing DEX_RPC_URL")?;
+    let chain = chain_of(creds);
     let wallet_address = creds
         .get("DEX_WALLET_ADDRESS")
         .ok_or("No wallet. Use dex_wallet_create first.")?;
@@ -49,10 +53,12 @@ pub async fn execute_dex_transfer(
     let signing_key = k256::ecdsa::SigningKey::from_slice(&pk_bytes)
         .map_err(|e| EngineError::Other(e.to_string()))?;
 
-    let chain_id = eth_chain_id(rpc_url).await?;
+    let chain_id = chain_id_of(creds, rpc_url).await?;
     let nonce = eth_get_transaction_count(rpc_url, wallet_address).await?;
     let (priority_fee, max_fee) = get_gas_fees(rpc_url).await?;
 
+    // ERC-20 (address, decimals, raw amount), to check the receipt against
+    let mut token_transfer = None;
     let tx_hash = if is_eth {
         // ── Native ETH transfer ──
         let decimals = 18u8;
@@ -91,7 +97,15 @@ pub async fn execute_dex_transfer(
         eth_send_raw_transaction(rpc_url, &signed_tx).await?
     } else {
         // ── ERC-20 transfer ──
-        let (token_addr, decimals) = resolve_token(currency)?;
+        let (token_addr, _) = resolve_token(chain, currency)?;
+        let (decimals, assumed) = token_decimals(chain, rpc_url, &token_addr).await;
+        if assumed {
+            // A wrong guess would send the amount off by orders of magnitude
+            return Err(EngineError::Other(format!(
+                "Could not read decimals() from token {}. Refusing to transfer an amount that cannot be converted reliably.",
+                token_addr
+            )));
+        }
         let amount_raw = amount_to_raw(amount_str, decimals)?;
         let amount_u256 = parse_u256_decimal(&amount_raw)?;
 
@@ -123,6 +137,12 @@ pub async fn execute_dex_transfer(
             return Err("No ETH for gas fees. Deposit ETH to your wallet first.".into());
         }
 
+        token_transfer = Some((
+            token_addr.clone(),
+            decimals,
+            settlement::u256_to_u128(&amount_u256),
+        ));
+
         // Build transfer(to, amount) calldata
         let transfer_data = encode_transfer(&to_bytes, &amount_u256);
 
@@ -149,33 +169,43 @@ pub async fn execute_dex_transfer(
         eth_send_raw_transaction(rpc_url, &signed_tx).await?
     };
 
-    // Wait for confirmation (up to 2 min)
-    let mut confirmed = false;
-    let mut final_status = "pending";
-    for _ in 0..60 {
-        tokio::time::sleep(Duration::from_secs(2)).await;
-        match eth_get_transaction_receipt(rpc_url, &tx_hash).await {
-            Ok(Some(receipt)) => {
-                let status = receipt
-                    .get("status")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("0x0");
-                if status == "0x1" {
-                    confirmed = true;
-                    final_status = "confirmed";
-                } else {
-                    final_status = "reverted";
-                }
-                break;
-            }
-            _ => continue,
-        }
-    }
+    // Wait until the transfer is the configured number of blocks deep
+    let required = required_confirmations(creds, chain_id);
+    let confirmation =
+        wait_for_confirmation(rpc_url, &tx_hash, required, confirm_timeout(args, required)).await;
+    let confirmed = confirmation.is_confirmed();
+
+    // Native ETH moves exactly; a token may take a fee on the way
+    let verification = match (&confirmation.receipt, &token_transfer) {
+        (Some(receipt), Some((token_addr, decimals, expected))) if confirmed => format!(
+            "\n{}",
+            settlement::verification_line(
+                &currency_upper,
+                *decimals,
+                *expected,
+                None,
+                settlement::received_from_logs(receipt, token_addr, to_address),
+            )
+        ),
+        _ => String::new(),
+    };
+
+    let gas_report = match confirmation
+        .receipt
+        .as_ref()
+        .and_then(settlement::gas_cost_wei)
+    {
+        Some(wei) => format!(
+            "\n{}",
+            settlement::gas_cost_line(&[("transfer", wei)], eth_usd_price(chain, rpc_url).await)
+        ),
+        None => String::new(),
+    };
 
     let network = explorer_tx_url(chain_id);
 
-    Ok(format!(
-        "{} Transfer {}\n\n{} {} → {}\nTx: {}{}\nStatus: {}",
+    let text = format!(
+        "{} Transfer {}\n\n{} {} → {}\nTx: {}{}\nStatus: {}{}{}",
         if confirmed { "[ok]" } else { "[pending]" },
         if confirmed { "Confirmed" } else { "Submitted" },
         amount_str,
@@ -183,6 +213,12 @@ pub async fn execute_dex_transfer(
         to_address,
         network,
         tx_hash,
-        final_status,
-    ))
+        confirmation.describe(),
+        verification,
+        gas_report,
+    );
+    Ok(TxReport {
+        text,
+        landed: confirmation.status != "reverted",
+    })
 }
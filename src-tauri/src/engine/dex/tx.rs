@@ -1,10 +1,71 @@
 // Paw Agent Engine — DEX EIP-1559 Transaction Signing
+//
+// The nonce is always the caller's: a fresh eth_getTransactionCount for a
+// lone transaction, or the previous one's + 1 to queue a second transaction
+// (approve, then swap) in the same block without waiting for the first.
 
 use super::abi::strip_leading_zeros;
-use super::primitives::keccak256;
+use super::primitives::{hex_decode, keccak256, parse_address};
 use super::rlp::{rlp_encode_bytes, rlp_encode_list, u256_to_minimal_be, u64_to_minimal_be};
 use crate::atoms::error::{EngineError, EngineResult};
 
+/// One EIP-2930 access list entry: a contract and the storage slots the
+/// transaction touches in it, pre-warmed at a discount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AccessListItem {
+    pub address: [u8; 20],
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// RLP of an access list: [[address, [storage_key, …]], …].
+fn encode_access_list(access_list: &[AccessListItem]) -> Vec<u8> {
+    let entries: Vec<Vec<u8>> = access_list
+        .iter()
+        .map(|item| {
+            let keys: Vec<Vec<u8>> = item
+                .storage_keys
+                .iter()
+                .map(|key| rlp_encode_bytes(key))
+                .collect();
+            rlp_encode_list(&[rlp_encode_bytes(&item.address), rlp_encode_list(&keys)])
+        })
+        .collect();
+    rlp_encode_list(&entries)
+}
+
+/// Parse an access list in JSON-RPC form (as returned by eth_createAccessList):
+/// `[{ "address": "0x…", "storageKeys": ["0x…", …] }, …]`.
+pub(crate) fn parse_access_list(value: &serde_json::Value) -> EngineResult<Vec<AccessListItem>> {
+    let entries = value
+        .as_array()
+        .ok_or("access_list must be an array of { address, storageKeys }")?;
+    entries
+        .iter()
+        .map(|entry| {
+            let address = parse_address(
+                entry["address"]
+                    .as_str()
+                    .ok_or("access_list entry is missing 'address'")?,
+            )?;
+            let storage_keys = entry["storageKeys"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|key| {
+                    let bytes = hex_decode(key.as_str().unwrap_or_default())?;
+                    <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                        EngineError::Other(format!("Storage key must be 32 bytes: {}", key))
+                    })
+                })
+                .collect::<EngineResult<Vec<_>>>()?;
+            Ok(AccessListItem {
+                address,
+                storage_keys,
+            })
+        })
+        .collect()
+}
+
 /// Sign an EIP-1559 transaction and return the raw serialized bytes.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn sign_eip1559_transaction(
@@ -17,6 +78,34 @@ pub(crate) fn sign_eip1559_transaction(
     value: &[u8; 32],
     data: &[u8],
     private_key: &k256::ecdsa::SigningKey,
+) -> EngineResult<Vec<u8>> {
+    sign_eip1559_transaction_with_access_list(
+        chain_id,
+        nonce,
+        max_priority_fee_per_gas,
+        max_fee_per_gas,
+        gas_limit,
+        to,
+        value,
+        data,
+        &[],
+        private_key,
+    )
+}
+
+/// [`sign_eip1559_transaction`] with an EIP-2930 access list.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_eip1559_transaction_with_access_list(
+    chain_id: u64,
+    nonce: u64,
+    max_priority_fee_per_gas: u64,
+    max_fee_per_gas: u64,
+    gas_limit: u64,
+    to: &[u8; 20],
+    value: &[u8; 32],
+    data: &[u8],
+    access_list: &[AccessListItem],
+    private_key: &k256::ecdsa::SigningKey,
 ) -> EngineResult<Vec<u8>> {
     // EIP-1559 unsigned tx: 0x02 || RLP([chain_id, nonce, max_priority_fee, max_fee, gas, to, value, data, access_list])
     let items = vec![
@@ -28,7 +117,7 @@ pub(crate) fn sign_eip1559_transaction(
         rlp_encode_bytes(to),
         rlp_encode_bytes(&u256_to_minimal_be(value)),
         rlp_encode_bytes(data),
-        rlp_encode_list(&[]), // access_list (empty)
+        encode_access_list(access_list),
     ];
 
     let unsigned_rlp = rlp_encode_list(&items);
@@ -60,3 +149,66 @@ pub(crate) fn sign_eip1559_transaction(
     result.extend_from_slice(&signed_rlp);
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ROUTER: &str = "0xE592427A0AEce92De3Edee1F18E0157C05861564";
+
+    fn key() -> k256::ecdsa::SigningKey {
+        k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap()
+    }
+
+    #[test]
+    fn empty_access_list_matches_the_plain_signer() {
+        let to = parse_address(ROUTER).unwrap();
+        let plain = sign_eip1559_transaction(1, 7, 1, 2, 21_000, &to, &[0; 32], &[], &key());
+        let with_list = sign_eip1559_transaction_with_access_list(
+            1,
+            7,
+            1,
+            2,
+            21_000,
+            &to,
+            &[0; 32],
+            &[],
+            &[],
+            &key(),
+        );
+        assert_eq!(plain.unwrap(), with_list.unwrap());
+    }
+
+    #[test]
+    fn encodes_access_list_entries() {
+        let item = AccessListItem {
+            address: [0xaa; 20],
+            storage_keys: vec![[0x01; 32]],
+        };
+        let encoded = encode_access_list(&[item]);
+        // [[0x94 ‖ address, [0xa0 ‖ key]]]: 21 + 34 = 55 payload bytes inside
+        // a 56-byte entry, so the outer list needs the long-form 0xf8 prefix
+        assert_eq!(&encoded[..4], &[0xf8, 0x38, 0xf7, 0x94]);
+        assert_eq!(&encoded[4..24], &[0xaa; 20]);
+        assert_eq!(&encoded[24..26], &[0xe1, 0xa0]);
+        assert_eq!(encoded.len(), 58);
+        assert_eq!(encode_access_list(&[]), vec![0xc0]);
+    }
+
+    #[test]
+    fn parses_json_rpc_access_lists() {
+        let list = parse_access_list(&json!([
+            { "address": ROUTER, "storageKeys": [format!("0x{}", "00".repeat(31) + "05")] },
+            { "address": ROUTER, "storageKeys": [] }
+        ]))
+        .unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].storage_keys[0][31], 5);
+        assert!(list[1].storage_keys.is_empty());
+        assert!(
+            parse_access_list(&json!([{ "address": ROUTER, "storageKeys": ["0x01"] }])).is_err()
+        );
+        assert!(parse_access_list(&json!({ "address": ROUTER })).is_err());
+    }
+}
@@ -1,19 +1,42 @@
 // Paw Agent Engine — DEX Wallet Creation
 
+use super::chains::chain_id_of;
 use super::constants::chain_name;
 use super::primitives::{address_from_pubkey, hex_encode};
-use super::rpc::eth_chain_id;
 use crate::atoms::error::{EngineError, EngineResult};
 use log::info;
 use std::collections::HashMap;
 use zeroize::Zeroizing;
 
 /// Create a new Ethereum wallet and store the private key in the vault.
+/// With a `name` arg a named wallet is added instead (see `wallets`).
 pub async fn execute_dex_wallet_create(
-    _args: &serde_json::Value,
+    args: &serde_json::Value,
     creds: &HashMap<String, String>,
     app_handle: &tauri::AppHandle,
 ) -> EngineResult<String> {
+    if let Some(name) = args["name"].as_str().filter(|n| !n.trim().is_empty()) {
+        use tauri::Manager;
+        let state = app_handle
+            .try_state::<crate::engine::state::EngineState>()
+            .ok_or(EngineError::Other("Engine state not available".into()))?;
+        let vault_key = crate::engine::skills::get_vault_key()?;
+        let wallet = super::wallets::create_named_wallet(
+            &state.store,
+            &vault_key,
+            name,
+            creds.contains_key("DEX_PRIVATE_KEY"),
+        )?;
+        let active = super::wallets::active_wallet(&state.store).as_deref() == Some(&wallet.id);
+        return Ok(format!(
+            "[ok] New wallet '{}' created!\n\nAddress: {}\nActive: {}\n\nUse `wallet: \"{}\"` on dex_balance, dex_swap or dex_transfer to use it. Send ETH to this address to fund it before trading.\n\nSecurity: Private key is encrypted and stored in your OS keychain vault. The AI agent never sees it.",
+            wallet.label,
+            wallet.address,
+            if active { "yes" } else { "no" },
+            wallet.label
+        ));
+    }
+
     if creds.contains_key("DEX_PRIVATE_KEY") && creds.contains_key("DEX_WALLET_ADDRESS") {
         let addr = creds.get("DEX_WALLET_ADDRESS").ok_or(EngineError::Other(
             "DEX_WALLET_ADDRESS not found in credentials".into(),
@@ -55,7 +78,7 @@ pub async fn execute_dex_wallet_create(
     info!("[dex] Created new wallet: {}", address);
 
     let network_name = if let Some(rpc_url) = creds.get("DEX_RPC_URL") {
-        match eth_chain_id(rpc_url).await {
+        match chain_id_of(creds, rpc_url).await {
             Ok(id) => chain_name(id),
             Err(_) => "Unknown",
         }
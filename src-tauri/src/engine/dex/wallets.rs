@@ -0,0 +1,245 @@
+// Paw Agent Engine — Named DEX Wallets
+//
+// Besides the original single wallet (DEX_PRIVATE_KEY / DEX_WALLET_ADDRESS
+// skill credentials, shown as "default"), users can keep any number of named
+// wallets — e.g. one per chain or per strategy. Wallet-using tools take an
+// optional `wallet` arg (id or label); without it the configured active
+// wallet is used, falling back to the default wallet.
+//
+// Selection works by swapping the DEX_PRIVATE_KEY / DEX_WALLET_ADDRESS
+// entries of the credential map, so the execute_* functions are unchanged
+// and keys are still only decrypted here, in Rust.
+
+use super::primitives::{address_from_pubkey, hex_encode};
+use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::sessions::{DexWalletRecord, SessionStore};
+use crate::engine::skills::{decrypt_credential, encrypt_credential};
+use log::info;
+use std::collections::HashMap;
+use zeroize::Zeroizing;
+
+/// engine_config key holding the active wallet id (unset = default wallet).
+pub const ACTIVE_WALLET_KEY: &str = "dex_active_wallet";
+
+/// Selector that always means the original single wallet.
+pub const DEFAULT_WALLET: &str = "default";
+
+/// Tools that sign or read with the wallet and accept a `wallet` arg.
+pub const WALLET_TOOLS: &[&str] = &[
+    "dex_balance",
+    "dex_portfolio",
+    "dex_swap",
+    "dex_transfer",
+    "dex_tx_history",
+    "dex_sign_typed_data",
+];
+
+const MAX_LABEL_LEN: usize = 40;
+
+/// Id of the configured active wallet, if any.
+pub fn active_wallet(store: &SessionStore) -> Option<String> {
+    store
+        .get_config(ACTIVE_WALLET_KEY)
+        .ok()
+        .flatten()
+        .filter(|id| !id.is_empty())
+}
+
+/// Make `selector` (id or label) the active wallet; `None` or "default"
+/// reverts to the default wallet.
+pub fn set_active_wallet(store: &SessionStore, selector: Option<&str>) -> EngineResult<()> {
+    let id = match selector.map(str::trim) {
+        None | Some("") => String::new(),
+        Some(s) if s.eq_ignore_ascii_case(DEFAULT_WALLET) => String::new(),
+        Some(s) => {
+            store
+                .find_dex_wallet(s)?
+                .ok_or_else(|| EngineError::Other(format!("Unknown DEX wallet '{}'", s)))?
+                .0
+                .id
+        }
+    };
+    store.set_config(ACTIVE_WALLET_KEY, &id)
+}
+
+/// Whether `selector` means the original single wallet.
+pub(crate) fn is_default_wallet(selector: Option<&str>) -> bool {
+    selector
+        .map(str::trim)
+        .is_none_or(|s| s.is_empty() || s.eq_ignore_ascii_case(DEFAULT_WALLET))
+}
+
+/// Trimmed `label`, if it is usable as a wallet name.
+pub(crate) fn check_label(label: &str) -> EngineResult<&str> {
+    let label = label.trim();
+    if label.is_empty() || label.len() > MAX_LABEL_LEN {
+        return Err(format!("Wallet name must be 1-{} characters", MAX_LABEL_LEN).into());
+    }
+    if label.eq_ignore_ascii_case(DEFAULT_WALLET) {
+        return Err("'default' is reserved for the original DEX wallet".into());
+    }
+    Ok(label)
+}
+
+/// Generate a new named wallet and store its key encrypted. It becomes the
+/// active wallet only when nothing else could be the default.
+pub fn create_named_wallet(
+    store: &SessionStore,
+    vault_key: &[u8],
+    label: &str,
+    default_exists: bool,
+) -> EngineResult<DexWalletRecord> {
+    let label = check_label(label)?;
+    if store.find_dex_wallet(label)?.is_some() {
+        return Err(format!("A wallet named '{}' already exists", label).into());
+    }
+
+    use k256::ecdsa::SigningKey;
+    let signing_key = SigningKey::random(&mut rand_core::OsRng);
+    let pubkey = signing_key.verifying_key().to_encoded_point(false);
+    let address = address_from_pubkey(pubkey.as_bytes());
+    let private_key_hex = Zeroizing::new(hex_encode(&signing_key.to_bytes()));
+
+    let id = uuid::Uuid::new_v4().to_string();
+    store.insert_dex_wallet(
+        &id,
+        label,
+        &address,
+        &encrypt_credential(&private_key_hex, vault_key)?,
+    )?;
+    if !default_exists && active_wallet(store).is_none() {
+        store.set_config(ACTIVE_WALLET_KEY, &id)?;
+    }
+    info!("[dex] Created wallet '{}': {}", label, address);
+
+    store
+        .find_dex_wallet(&id)?
+        .map(|(record, _)| record)
+        .ok_or_else(|| EngineError::Other("Wallet vanished after insert".into()))
+}
+
+/// Credentials for the wallet chosen by `selector` (or the active wallet).
+/// The default wallet leaves `base` untouched.
+pub fn wallet_creds(
+    store: &SessionStore,
+    vault_key: &[u8],
+    base: &HashMap<String, String>,
+    selector: Option<&str>,
+) -> EngineResult<HashMap<String, String>> {
+    let selector = selector
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .or_else(|| active_wallet(store));
+    let Some(selector) = selector.filter(|s| !s.eq_ignore_ascii_case(DEFAULT_WALLET)) else {
+        return Ok(base.clone());
+    };
+
+    let (record, encrypted_key) = store.find_dex_wallet(&selector)?.ok_or_else(|| {
+        EngineError::Other(format!(
+            "Unknown DEX wallet '{}'. Use dex_wallet_list to see available wallets.",
+            selector
+        ))
+    })?;
+    let private_key = decrypt_credential(&encrypted_key, vault_key)?;
+    crate::engine::secret_scrub::register_secrets([private_key.as_str()]);
+
+    let mut creds = base.clone();
+    creds.insert("DEX_PRIVATE_KEY".into(), private_key);
+    creds.insert("DEX_WALLET_ADDRESS".into(), record.address);
+    Ok(creds)
+}
+
+/// List the default wallet (if configured) and all named wallets.
+pub fn execute_dex_wallet_list(
+    store: &SessionStore,
+    creds: &HashMap<String, String>,
+) -> EngineResult<String> {
+    let active = active_wallet(store);
+    let mut lines = Vec::new();
+    if let Some(addr) = creds.get("DEX_WALLET_ADDRESS") {
+        let marker = if active.is_none() { " (active)" } else { "" };
+        lines.push(format!("- default: {}{}", addr, marker));
+    }
+    for w in store.list_dex_wallets()? {
+        let marker = if active.as_deref() == Some(w.id.as_str()) {
+            " (active)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "- {}: {}{}  [id {}]",
+            w.label, w.address, marker, w.id
+        ));
+    }
+    if lines.is_empty() {
+        return Ok("No DEX wallets yet. Use dex_wallet_create to create one.".into());
+    }
+    Ok(format!(
+        "DEX wallets:\n{}\n\nPass `wallet` (name or id) to dex_balance, dex_portfolio, dex_swap or dex_transfer to pick one.",
+        lines.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    const VAULT_KEY: [u8; 32] = [9u8; 32];
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let store = SessionStore::from_connection(conn);
+        store.init_skill_tables().unwrap();
+        store
+    }
+
+    #[test]
+    fn two_wallets_can_be_created_and_selected() {
+        let store = test_store();
+        let base = HashMap::from([("DEX_RPC_URL".to_string(), "http://rpc".to_string())]);
+
+        let trading = create_named_wallet(&store, &VAULT_KEY, "trading", false).unwrap();
+        let savings = create_named_wallet(&store, &VAULT_KEY, "savings", false).unwrap();
+        assert_ne!(trading.address, savings.address);
+        assert!(create_named_wallet(&store, &VAULT_KEY, "Trading", false).is_err());
+
+        // First wallet became active since there was no default wallet
+        let creds = wallet_creds(&store, &VAULT_KEY, &base, None).unwrap();
+        assert_eq!(creds["DEX_WALLET_ADDRESS"], trading.address);
+        assert_eq!(creds["DEX_RPC_URL"], "http://rpc");
+
+        // Explicit selection by label or id
+        let creds = wallet_creds(&store, &VAULT_KEY, &base, Some("SAVINGS")).unwrap();
+        assert_eq!(creds["DEX_WALLET_ADDRESS"], savings.address);
+        assert!(creds["DEX_PRIVATE_KEY"].starts_with("0x"));
+        let creds = wallet_creds(&store, &VAULT_KEY, &base, Some(&trading.id)).unwrap();
+        assert_eq!(creds["DEX_WALLET_ADDRESS"], trading.address);
+
+        // Switching the active wallet changes the default selection
+        set_active_wallet(&store, Some("savings")).unwrap();
+        let creds = wallet_creds(&store, &VAULT_KEY, &base, None).unwrap();
+        assert_eq!(creds["DEX_WALLET_ADDRESS"], savings.address);
+
+        assert!(wallet_creds(&store, &VAULT_KEY, &base, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn default_wallet_stays_active_when_present() {
+        let store = test_store();
+        let base = HashMap::from([
+            ("DEX_PRIVATE_KEY".to_string(), "0xlegacy".to_string()),
+            ("DEX_WALLET_ADDRESS".to_string(), "0xLegacyAddr".to_string()),
+        ]);
+        create_named_wallet(&store, &VAULT_KEY, "bot", true).unwrap();
+
+        let creds = wallet_creds(&store, &VAULT_KEY, &base, None).unwrap();
+        assert_eq!(creds["DEX_WALLET_ADDRESS"], "0xLegacyAddr");
+        let listing = execute_dex_wallet_list(&store, &base).unwrap();
+        assert!(listing.contains("default: 0xLegacyAddr (active)"));
+        assert!(listing.contains("- bot: "));
+    }
+}
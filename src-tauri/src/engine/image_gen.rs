@@ -0,0 +1,616 @@
+// Paw Agent Engine — Image generation
+//
+// Backs the `image_generate` tool. Two backends:
+//   - any OpenAI-compatible `/images/generations` endpoint: DALL·E,
+//     gpt-image-1, or a self-hosted Stable Diffusion server speaking the same
+//     API (IMAGE_API_URL, optional IMAGE_API_KEY);
+//   - Gemini (GEMINI_API_KEY), the original backend.
+//
+// Every request is checked against the edge-length and per-image cost caps
+// before anything is sent, and the endpoint against the network policy.
+// Images are written to the agent's workspace under `generated-images/`,
+// the only place the chat's image viewer will read from.
+
+use log::info;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Workspace subdirectory generated images are saved in.
+pub const IMAGES_DIR: &str = "generated-images";
+
+/// Size used when the call doesn't ask for one.
+pub const DEFAULT_SIZE: &str = "1024x1024";
+
+/// Longest allowed edge in pixels unless IMAGE_MAX_SIZE says otherwise.
+/// Covers DALL·E 3's 1792×1024 landscape and portrait sizes.
+pub const DEFAULT_MAX_SIDE: u32 = 1792;
+
+/// Most a single image may cost (estimated) unless IMAGE_MAX_COST_USD says
+/// otherwise.
+pub const DEFAULT_MAX_COST_USD: f64 = 0.10;
+
+const DEFAULT_OPENAI_MODEL: &str = "dall-e-3";
+const DEFAULT_GEMINI_MODEL: &str = "gemini-2.0-flash-exp";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Where an image request is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageBackend {
+    OpenAiCompatible {
+        base_url: String,
+        api_key: Option<String>,
+    },
+    Gemini {
+        api_key: String,
+    },
+}
+
+impl ImageBackend {
+    /// URL the request is posted to, for the network policy check.
+    pub fn endpoint(&self, model: &str) -> String {
+        match self {
+            ImageBackend::OpenAiCompatible { base_url, .. } => {
+                format!("{}/images/generations", base_url.trim_end_matches('/'))
+            }
+            ImageBackend::Gemini { .. } => {
+                format!("{}/models/{}:generateContent", GEMINI_BASE_URL, model)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRequest {
+    pub prompt: String,
+    pub model: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageLimits {
+    pub max_side: u32,
+    pub max_cost_usd: f64,
+}
+
+impl ImageLimits {
+    /// Read IMAGE_MAX_SIZE / IMAGE_MAX_COST_USD from the skill credentials,
+    /// falling back to the defaults when unset or unparsable.
+    pub fn from_creds(creds: &HashMap<String, String>) -> Self {
+        let get = |key: &str| creds.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+        ImageLimits {
+            max_side: get("IMAGE_MAX_SIZE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_SIDE),
+            max_cost_usd: get("IMAGE_MAX_COST_USD")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_COST_USD),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub bytes: Vec<u8>,
+    pub mime_type: String,
+    /// Revised prompt or text the model returned alongside the image.
+    pub notes: Option<String>,
+}
+
+/// Parse a "WIDTHxHEIGHT" size.
+pub fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    let parsed = size
+        .trim()
+        .to_lowercase()
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)));
+    match parsed {
+        Some((w, h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(format!(
+            "Invalid image size '{}' — use WIDTHxHEIGHT, e.g. 1024x1024",
+            size
+        )),
+    }
+}
+
+/// Estimated price of one image in USD, from the providers' published
+/// per-image rates at standard quality. Unknown models (typically
+/// self-hosted Stable Diffusion) are assumed free.
+pub fn estimate_cost_usd(model: &str, width: u32, height: u32) -> f64 {
+    let model = model.to_lowercase();
+    let square = width == height;
+    if model.starts_with("dall-e-3") {
+        if square {
+            0.04
+        } else {
+            0.08
+        }
+    } else if model.starts_with("dall-e-2") {
+        match width.max(height) {
+            0..=256 => 0.016,
+            257..=512 => 0.018,
+            _ => 0.02,
+        }
+    } else if model.starts_with("gpt-image") {
+        if square {
+            0.042
+        } else {
+            0.063
+        }
+    } else if model.starts_with("gemini") {
+        0.039
+    } else {
+        0.0
+    }
+}
+
+/// Reject a request over the size or cost cap. Returns the estimated cost.
+pub fn check_limits(request: &ImageRequest, limits: &ImageLimits) -> Result<f64, String> {
+    if request.width.max(request.height) > limits.max_side {
+        return Err(format!(
+            "Image size {}x{} exceeds the {}px limit. Ask for a smaller size or raise IMAGE_MAX_SIZE in the Image Generation skill.",
+            request.width, request.height, limits.max_side
+        ));
+    }
+    let cost = estimate_cost_usd(&request.model, request.width, request.height);
+    if cost > limits.max_cost_usd {
+        return Err(format!(
+            "A {}x{} image from {} costs about ${:.3}, over the ${:.3} per-image limit. Ask for a smaller size or cheaper model, or raise IMAGE_MAX_COST_USD.",
+            request.width, request.height, request.model, cost, limits.max_cost_usd
+        ));
+    }
+    Ok(cost)
+}
+
+/// Pick the backend and model. An explicit Gemini model uses Gemini; otherwise
+/// a configured IMAGE_API_URL wins, then Gemini.
+pub fn resolve_backend(
+    creds: &HashMap<String, String>,
+    model: Option<&str>,
+) -> Result<(ImageBackend, String), String> {
+    let get = |key: &str| {
+        creds
+            .get(key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let model = model
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .or_else(|| get("IMAGE_MODEL"));
+    let gemini = get("GEMINI_API_KEY").map(|api_key| ImageBackend::Gemini { api_key });
+    let wants_gemini = model.as_deref().is_some_and(|m| m.starts_with("gemini"));
+
+    match (get("IMAGE_API_URL"), gemini) {
+        (_, Some(gemini)) if wants_gemini => Ok((gemini, model.unwrap_or_default())),
+        (Some(base_url), _) => Ok((
+            ImageBackend::OpenAiCompatible {
+                base_url,
+                api_key: get("IMAGE_API_KEY"),
+            },
+            model.unwrap_or_else(|| DEFAULT_OPENAI_MODEL.to_string()),
+        )),
+        (None, Some(gemini)) => Ok((
+            gemini,
+            model
+                .filter(|m| m.starts_with("gemini"))
+                .unwrap_or_else(|| DEFAULT_GEMINI_MODEL.to_string()),
+        )),
+        (None, None) => Err(
+            "No image provider configured. Set IMAGE_API_URL (an OpenAI-compatible endpoint) or GEMINI_API_KEY in the Image Generation skill."
+                .into(),
+        ),
+    }
+}
+
+/// Send the request and decode the returned image. `check_url` vets any
+/// URL the image has to be downloaded from.
+pub async fn generate(
+    client: &reqwest::Client,
+    backend: &ImageBackend,
+    request: &ImageRequest,
+    check_url: impl Fn(&str) -> Result<(), String>,
+) -> Result<GeneratedImage, String> {
+    info!(
+        "[image_gen] {} {}x{}: {}",
+        request.model,
+        request.width,
+        request.height,
+        crate::engine::util::safe_truncate(&request.prompt, 80)
+    );
+    match backend {
+        ImageBackend::OpenAiCompatible { api_key, .. } => {
+            generate_openai(client, backend, api_key.as_deref(), request, check_url).await
+        }
+        ImageBackend::Gemini { api_key } => {
+            generate_gemini(client, backend, api_key, request).await
+        }
+    }
+}
+
+async fn generate_openai(
+    client: &reqwest::Client,
+    backend: &ImageBackend,
+    api_key: Option<&str>,
+    request: &ImageRequest,
+    check_url: impl Fn(&str) -> Result<(), String>,
+) -> Result<GeneratedImage, String> {
+    let mut body = serde_json::json!({
+        "model": request.model,
+        "prompt": request.prompt,
+        "n": 1,
+        "size": format!("{}x{}", request.width, request.height),
+    });
+    // gpt-image models always return base64 and reject the parameter
+    if !request.model.starts_with("gpt-image") {
+        body["response_format"] = "b64_json".into();
+    }
+    let mut req = client.post(backend.endpoint(&request.model)).json(&body);
+    if let Some(key) = api_key {
+        req = req.bearer_auth(key);
+    }
+    let resp = req.send().await.map_err(|e| e.to_string())?;
+    let status = resp.status().as_u16();
+    let json = read_json(resp).await?;
+    if status >= 400 {
+        return Err(error_message(status, &json));
+    }
+
+    let image = json["data"]
+        .get(0)
+        .ok_or("Image provider returned no image")?;
+    let notes = image["revised_prompt"].as_str().map(str::to_string);
+    let bytes = if let Some(b64) = image["b64_json"].as_str() {
+        decode_base64(b64)?
+    } else if let Some(url) = image["url"].as_str() {
+        check_url(url)?;
+        let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!(
+                "Downloading the image failed (HTTP {})",
+                resp.status()
+            ));
+        }
+        resp.bytes().await.map_err(|e| e.to_string())?.to_vec()
+    } else {
+        return Err("Image provider returned neither b64_json nor url".into());
+    };
+    Ok(GeneratedImage {
+        mime_type: sniff_mime(&bytes).to_string(),
+        bytes,
+        notes,
+    })
+}
+
+async fn generate_gemini(
+    client: &reqwest::Client,
+    backend: &ImageBackend,
+    api_key: &str,
+    request: &ImageRequest,
+) -> Result<GeneratedImage, String> {
+    let body = serde_json::json!({
+        "contents": [{ "parts": [{ "text": request.prompt }] }],
+        "generationConfig": { "responseModalities": ["TEXT", "IMAGE"] }
+    });
+    let resp = client
+        .post(backend.endpoint(&request.model))
+        .header("x-goog-api-key", api_key)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = resp.status().as_u16();
+    let json = read_json(resp).await?;
+    if status >= 400 {
+        return Err(error_message(status, &json));
+    }
+    if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+        return Err(policy_rejection(reason));
+    }
+
+    let candidate = &json["candidates"][0];
+    let mut image = None;
+    let mut notes = None;
+    for part in candidate["content"]["parts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        if let Some(data) = part["inlineData"]["data"]
+            .as_str()
+            .filter(|d| !d.is_empty())
+        {
+            let mime = part["inlineData"]["mimeType"]
+                .as_str()
+                .unwrap_or("image/png");
+            image = Some((mime.to_string(), data.to_string()));
+        }
+        if let Some(text) = part["text"].as_str() {
+            notes = Some(text.to_string());
+        }
+    }
+    let Some((mime_type, data)) = image else {
+        if let Some(reason) = candidate["finishReason"].as_str().filter(|r| {
+            matches!(
+                *r,
+                "SAFETY" | "IMAGE_SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST"
+            )
+        }) {
+            return Err(policy_rejection(reason));
+        }
+        return Err("Gemini did not return an image. The model may not support image generation for this prompt. Try a more descriptive prompt.".into());
+    };
+    Ok(GeneratedImage {
+        bytes: decode_base64(&data)?,
+        mime_type,
+        notes,
+    })
+}
+
+async fn read_json(resp: reqwest::Response) -> Result<Value, String> {
+    let text = resp.text().await.map_err(|e| e.to_string())?;
+    serde_json::from_str(&text).map_err(|_| {
+        format!(
+            "Image provider returned a non-JSON response: {}",
+            crate::engine::util::safe_truncate(&text, 300)
+        )
+    })
+}
+
+/// Turn an error body into a message, calling out content-policy refusals
+/// so the agent rephrases instead of retrying the same prompt.
+fn error_message(status: u16, body: &Value) -> String {
+    let error = &body["error"];
+    let message = error["message"]
+        .as_str()
+        .or_else(|| error.as_str())
+        .unwrap_or("unknown error");
+    let code = error["code"].as_str().unwrap_or_default();
+    let policy = code == "content_policy_violation"
+        || code == "moderation_blocked"
+        || error["status"].as_str() == Some("SAFETY")
+        || message.to_lowercase().contains("safety system");
+    if policy {
+        policy_rejection(message)
+    } else {
+        format!(
+            "Image provider error (HTTP {}): {}",
+            status,
+            crate::engine::util::safe_truncate(message, 500)
+        )
+    }
+}
+
+fn policy_rejection(detail: &str) -> String {
+    format!(
+        "The image provider declined this prompt under its content policy ({}). Rephrase the prompt rather than retrying it unchanged.",
+        crate::engine::util::safe_truncate(detail, 300)
+    )
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, String> {
+    use base64::Engine as _;
+    base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("Image data is not valid base64: {}", e))
+}
+
+/// Identify the format from the file's magic bytes; PNG when unknown.
+fn sniff_mime(bytes: &[u8]) -> &'static str {
+    match bytes {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        _ => "image/png",
+    }
+}
+
+pub fn extension_for(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/webp" => "webp",
+        "image/gif" => "gif",
+        _ => "png",
+    }
+}
+
+/// File name (without extension) for an image: the caller's, reduced to
+/// safe characters, or a timestamp plus a slug of the prompt.
+pub fn output_name(prompt: &str, filename: Option<&str>) -> String {
+    let slug = |s: &str, max: usize| -> String {
+        s.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(max)
+            .collect::<String>()
+            .trim_matches('_')
+            .to_lowercase()
+    };
+    match filename.map(|f| slug(f, 80)).filter(|f| !f.is_empty()) {
+        Some(name) => name,
+        None => format!(
+            "generated_{}_{}",
+            chrono::Utc::now().format("%Y%m%d_%H%M%S"),
+            slug(prompt, 30)
+        ),
+    }
+}
+
+/// Write the image into `workspace/generated-images/`, never overwriting an
+/// existing file.
+pub fn save_image(
+    workspace: &Path,
+    name: &str,
+    image: &GeneratedImage,
+) -> std::io::Result<PathBuf> {
+    let dir = workspace.join(IMAGES_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let ext = extension_for(&image.mime_type);
+    let mut path = dir.join(format!("{}.{}", name, ext));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("{}_{}.{}", name, n, ext));
+    }
+    std::fs::write(&path, &image.bytes)?;
+    Ok(path)
+}
+
+/// Whether `path` is a generated image inside some agent's workspace — the
+/// chat viewer serves nothing else.
+pub fn is_generated_image(path: &Path) -> bool {
+    crate::engine::tools::is_workspace_output(path, IMAGES_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{self, MockReply};
+
+    // 1×1 transparent PNG
+    const PNG_B64: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
+    /// Answers one request with `status` and a JSON `body`, returning the
+    /// raw request it received.
+    async fn mock_endpoint(
+        status: u16,
+        body: String,
+    ) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+        let (url, handle) = test_support::serve_once(MockReply::json(status, body)).await;
+        (format!("{}/v1", url), handle)
+    }
+
+    fn request(model: &str, width: u32, height: u32) -> ImageRequest {
+        ImageRequest {
+            prompt: "a red fox in the snow".into(),
+            model: model.into(),
+            width,
+            height,
+        }
+    }
+
+    #[tokio::test]
+    async fn base64_image_from_endpoint_is_written_to_the_workspace() {
+        let body = serde_json::json!({
+            "created": 1,
+            "data": [{ "b64_json": PNG_B64, "revised_prompt": "A red fox, snowy forest" }]
+        })
+        .to_string();
+        let (base_url, server) = mock_endpoint(200, body).await;
+        let backend = ImageBackend::OpenAiCompatible {
+            base_url,
+            api_key: Some("sk-test".into()),
+        };
+
+        let image = generate(
+            &reqwest::Client::new(),
+            &backend,
+            &request("dall-e-3", 1024, 1024),
+            |_| Ok(()),
+        )
+        .await
+        .unwrap();
+        let received = String::from_utf8_lossy(&server.await.unwrap()).to_string();
+        assert!(received.starts_with("POST /v1/images/generations"));
+        assert!(received
+            .to_lowercase()
+            .contains("authorization: bearer sk-test"));
+        assert!(received.contains(r#""response_format":"b64_json""#));
+        assert!(received.contains(r#""size":"1024x1024""#));
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(image.notes.as_deref(), Some("A red fox, snowy forest"));
+
+        let workspace =
+            std::env::temp_dir().join(format!("paw-image-test-{}", uuid::Uuid::new_v4()));
+        let name = output_name("a red fox in the snow", Some("../fox"));
+        assert_eq!(name, "fox");
+        let path = save_image(&workspace, &name, &image).unwrap();
+        assert_eq!(path, workspace.join(IMAGES_DIR).join("fox.png"));
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(&written[..4], b"\x89PNG");
+
+        // A second image with the same name doesn't overwrite the first
+        let again = save_image(&workspace, &name, &image).unwrap();
+        assert_eq!(again.file_name().unwrap(), "fox_2.png");
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[tokio::test]
+    async fn content_policy_rejection_reads_as_a_refusal() {
+        let body = serde_json::json!({
+            "error": {
+                "code": "content_policy_violation",
+                "message": "Your request was rejected as a result of our safety system.",
+                "type": "invalid_request_error"
+            }
+        })
+        .to_string();
+        let (base_url, _server) = mock_endpoint(400, body).await;
+        let backend = ImageBackend::OpenAiCompatible {
+            base_url,
+            api_key: None,
+        };
+        let err = generate(
+            &reqwest::Client::new(),
+            &backend,
+            &request("dall-e-3", 1024, 1024),
+            |_| Ok(()),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("content policy"), "{}", err);
+        assert!(err.contains("Rephrase"), "{}", err);
+    }
+
+    #[test]
+    fn size_and_cost_caps_are_enforced() {
+        let limits = ImageLimits {
+            max_side: 1792,
+            max_cost_usd: 0.05,
+        };
+        assert_eq!(
+            check_limits(&request("dall-e-3", 1024, 1024), &limits),
+            Ok(0.04)
+        );
+        let err = check_limits(&request("dall-e-3", 1792, 1024), &limits).unwrap_err();
+        assert!(err.contains("per-image limit"), "{}", err);
+        let err = check_limits(&request("sdxl", 2048, 2048), &limits).unwrap_err();
+        assert!(err.contains("1792px"), "{}", err);
+        // Self-hosted models are free under the cap
+        assert_eq!(check_limits(&request("sdxl", 1024, 1024), &limits), Ok(0.0));
+
+        assert_eq!(parse_size("512X768"), Ok((512, 768)));
+        assert!(parse_size("huge").is_err());
+        assert!(parse_size("0x512").is_err());
+    }
+
+    #[test]
+    fn backend_follows_configured_credentials() {
+        let creds = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let both = creds(&[
+            ("IMAGE_API_URL", "https://api.openai.com/v1"),
+            ("GEMINI_API_KEY", "g"),
+        ]);
+        let (backend, model) = resolve_backend(&both, None).unwrap();
+        assert!(matches!(backend, ImageBackend::OpenAiCompatible { .. }));
+        assert_eq!(model, "dall-e-3");
+        let (backend, _) = resolve_backend(&both, Some("gemini-2.0-flash-exp")).unwrap();
+        assert!(matches!(backend, ImageBackend::Gemini { .. }));
+
+        let (_, model) = resolve_backend(&creds(&[("GEMINI_API_KEY", "g")]), None).unwrap();
+        assert_eq!(model, DEFAULT_GEMINI_MODEL);
+        assert!(resolve_backend(&creds(&[]), None).is_err());
+    }
+}
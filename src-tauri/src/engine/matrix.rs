@@ -18,6 +18,7 @@
 //   - All communication through the homeserver's TLS API
 
 use crate::engine::channels::{self, ChannelStatus, PendingUser};
+use crate::engine::util::safe_truncate;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -388,7 +389,7 @@ async fn handle_room_message(
         sender,
         room_id,
         if content.len() > 50 {
-            format!("{}...", &content[..50])
+            format!("{}...", safe_truncate(&content, 50))
         } else {
             content.clone()
         }
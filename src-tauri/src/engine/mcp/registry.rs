@@ -21,6 +21,9 @@ pub const N8N_MCP_SERVER_ID: &str = "n8n";
 pub struct McpRegistry {
     /// Connected MCP clients, keyed by server config ID.
     clients: HashMap<String, McpClient>,
+    /// Parameter schemas of `all_tool_definitions` by tool name, rebuilt
+    /// whenever a server connects, disconnects or refreshes its tools.
+    schemas: HashMap<String, serde_json::Value>,
 }
 
 impl McpRegistry {
@@ -37,15 +40,18 @@ impl McpRegistry {
             old.shutdown().await;
         }
 
-        let client = McpClient::connect(config).await?;
-        self.clients.insert(id, client);
-        Ok(())
+        let connected = McpClient::connect(config).await.map(|client| {
+            self.clients.insert(id, client);
+        });
+        self.rebuild_schemas();
+        connected
     }
 
     /// Disconnect a specific server.
     pub async fn disconnect(&mut self, id: &str) {
         if let Some(client) = self.clients.remove(id) {
             client.shutdown().await;
+            self.rebuild_schemas();
         }
     }
 
@@ -57,6 +63,7 @@ impl McpRegistry {
                 client.shutdown().await;
             }
         }
+        self.schemas.clear();
     }
 
     /// Get all MCP-provided tools as Paw `ToolDefinition`s.
@@ -71,6 +78,19 @@ impl McpRegistry {
         defs
     }
 
+    /// Declared parameter schema of the MCP tool `name` (prefixed form).
+    pub fn tool_schema(&self, name: &str) -> Option<&serde_json::Value> {
+        self.schemas.get(name)
+    }
+
+    fn rebuild_schemas(&mut self) {
+        self.schemas = self
+            .all_tool_definitions()
+            .into_iter()
+            .map(|tool| (tool.function.name, tool.function.parameters))
+            .collect();
+    }
+
     /// Get tool definitions for specific server IDs only.
     pub fn tool_definitions_for(&self, server_ids: &[String]) -> Vec<ToolDefinition> {
         let mut defs = Vec::new();
@@ -171,7 +191,9 @@ impl McpRegistry {
             .clients
             .get_mut(id)
             .ok_or_else(|| format!("Server '{}' not connected", id))?;
-        client.refresh_tools().await
+        let refreshed = client.refresh_tools().await;
+        self.rebuild_schemas();
+        refreshed
     }
 
     /// Register the embedded n8n engine as an MCP server.
@@ -2,6 +2,7 @@
 // Direct AI API calls, in-process tool execution, and Tauri IPC
 // for zero-network-hop communication.
 
+pub mod agent_bundle;
 pub mod agent_loop;
 pub mod audit;
 pub mod binary_ipc;
@@ -23,6 +24,7 @@ pub mod discord;
 pub mod engram;
 pub mod events;
 pub mod forge;
+pub mod image_gen;
 pub mod injection;
 pub mod irc;
 pub mod key_vault;
@@ -31,22 +33,29 @@ pub mod mattermost;
 pub mod mcp;
 pub mod memory;
 pub mod n8n_engine;
+pub mod net;
 pub mod nextcloud;
 pub mod nostr;
 pub mod oauth;
 pub mod orchestrator;
 pub mod plan;
 pub mod provider_registry;
+pub mod reminders;
+pub mod robots;
 pub mod routing;
 pub mod sandbox;
+pub mod secret_scrub;
 pub mod skills;
 pub mod slack;
 pub mod sol_dex;
 pub mod speculative;
+pub mod speech;
 pub mod swarm;
 pub mod tasks;
 pub mod telegram;
 pub mod telemetry;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod tool_index;
 pub mod tool_registry;
 pub mod twitch;
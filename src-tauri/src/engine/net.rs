@@ -0,0 +1,175 @@
+// Paw Agent Engine — Outbound request identity
+//
+// Some sites 403 unknown user-agents and some APIs want extra headers on
+// every call. The user configures a global User-Agent and per-domain header
+// overrides here; `apply()` adds them to requests made by the fetch tool,
+// service API calls and chain RPC calls.
+//
+// Domain patterns follow the network allowlist rules: `example.com` also
+// covers its subdomains, `*.example.com` covers only subdomains. Header
+// values that look like credentials are redacted in logs and registered with
+// the secret scrubber so they never surface in tool output.
+//
+// Stored as JSON under the `outbound_http` config key and kept in memory
+// so call sites without a store handle (RPC helpers) can apply it.
+
+use crate::atoms::error::EngineResult;
+use crate::commands::browser::{domain_matches_pub, extract_domain_from_url};
+use crate::engine::secret_scrub::{self, is_secret_field, REDACTED};
+use crate::engine::sessions::SessionStore;
+use log::debug;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// engine_config key holding the serialized [`OutboundConfig`].
+pub const OUTBOUND_CONFIG_KEY: &str = "outbound_http";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutboundConfig {
+    /// User-Agent sent on every request. None keeps each client's default.
+    pub user_agent: Option<String>,
+    /// Domain pattern → headers added to requests for that domain.
+    pub domain_headers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// The active settings, installed at startup and on save.
+static ACTIVE: RwLock<Option<OutboundConfig>> = RwLock::new(None);
+
+/// Make `config` the active outbound settings.
+pub fn install(config: OutboundConfig) {
+    secret_scrub::register_secrets(
+        config
+            .domain_headers
+            .values()
+            .flat_map(|headers| headers.iter())
+            .filter(|(name, _)| is_secret_field(name))
+            .map(|(_, value)| value.clone()),
+    );
+    *ACTIVE.write() = Some(config);
+}
+
+/// Headers `config` adds to a request for `url`, User-Agent first.
+pub fn headers_for(config: &OutboundConfig, url: &str) -> Vec<(String, String)> {
+    let mut headers = Vec::new();
+    if let Some(ua) = config
+        .user_agent
+        .as_deref()
+        .filter(|ua| !ua.trim().is_empty())
+    {
+        headers.push(("User-Agent".to_string(), ua.trim().to_string()));
+    }
+    let domain = extract_domain_from_url(url);
+    for (pattern, extra) in &config.domain_headers {
+        if domain_matches_pub(&domain, pattern) {
+            headers.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
+    headers
+}
+
+/// Headers the active settings add to a request for `url`.
+pub fn active_headers(url: &str) -> Vec<(String, String)> {
+    ACTIVE
+        .read()
+        .as_ref()
+        .map(|config| headers_for(config, url))
+        .unwrap_or_default()
+}
+
+/// Add the configured User-Agent and domain headers for `url` to `req`.
+pub fn apply(mut req: reqwest::RequestBuilder, url: &str) -> reqwest::RequestBuilder {
+    for (name, value) in active_headers(url) {
+        debug!("[net] {} {}: {}", url, name, redact_header(&name, &value));
+        req = req.header(name.as_str(), value.as_str());
+    }
+    req
+}
+
+/// A header value safe to log — credentials are replaced with a marker.
+pub fn redact_header(name: &str, value: &str) -> String {
+    if is_secret_field(name) {
+        REDACTED.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Load the settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> OutboundConfig {
+    store
+        .get_config(OUTBOUND_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Persist the settings and make them active.
+pub fn save_config(store: &SessionStore, config: &OutboundConfig) -> EngineResult<()> {
+    store.set_config(OUTBOUND_CONFIG_KEY, &serde_json::to_string(config)?)?;
+    install(config.clone());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OutboundConfig {
+        let mut domain_headers = BTreeMap::new();
+        domain_headers.insert(
+            "api.example.com".to_string(),
+            BTreeMap::from([("X-Api-Key".to_string(), "k-123456789".to_string())]),
+        );
+        OutboundConfig {
+            user_agent: Some("OpenPawz/1.0".into()),
+            domain_headers,
+        }
+    }
+
+    #[test]
+    fn domain_headers_only_attach_to_matching_domain() {
+        let cfg = config();
+        let headers = headers_for(&cfg, "https://api.example.com/v1/items");
+        assert!(headers.contains(&("X-Api-Key".into(), "k-123456789".into())));
+        // Subdomains of the pattern match too
+        let headers = headers_for(&cfg, "https://eu.api.example.com/v1");
+        assert!(headers.iter().any(|(k, _)| k == "X-Api-Key"));
+
+        for other in [
+            "https://example.com/",
+            "https://api.example.com.evil.org/",
+            "https://other.org/?u=api.example.com",
+        ] {
+            let headers = headers_for(&cfg, other);
+            assert!(
+                headers.iter().all(|(k, _)| k != "X-Api-Key"),
+                "leaked to {}",
+                other
+            );
+        }
+    }
+
+    #[test]
+    fn user_agent_applies_everywhere_when_set() {
+        let cfg = config();
+        let headers = headers_for(&cfg, "https://other.org/");
+        assert_eq!(
+            headers,
+            vec![("User-Agent".to_string(), "OpenPawz/1.0".to_string())]
+        );
+        assert!(headers_for(&OutboundConfig::default(), "https://other.org/").is_empty());
+    }
+
+    #[test]
+    fn sensitive_header_values_are_redacted() {
+        assert_eq!(redact_header("Authorization", "Bearer abc"), REDACTED);
+        assert_eq!(redact_header("X-Api-Key", "k-123"), REDACTED);
+        assert_eq!(
+            redact_header("Accept", "application/json"),
+            "application/json"
+        );
+    }
+}
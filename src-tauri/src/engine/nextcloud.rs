@@ -15,6 +15,7 @@
 
 use crate::atoms::error::EngineResult;
 use crate::engine::channels::{self, ChannelStatus, PendingUser};
+use crate::engine::util::safe_truncate;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -335,7 +336,7 @@ async fn run_poll_loop(app_handle: tauri::AppHandle, config: NextcloudConfig) ->
                     actor_name,
                     token,
                     if text.len() > 50 {
-                        format!("{}...", &text[..50])
+                        format!("{}...", safe_truncate(&text, 50))
                     } else {
                         text.clone()
                     }
@@ -29,6 +29,7 @@ mod relay;
 
 use crate::atoms::error::EngineResult;
 use crate::engine::channels::{self, ChannelStatus, PendingUser};
+use crate::engine::util::safe_truncate;
 use crypto::{derive_pubkey, hex_decode, hex_encode};
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
@@ -214,7 +215,9 @@ pub fn get_status(app_handle: &tauri::AppHandle) -> ChannelStatus {
     ChannelStatus {
         running: BRIDGE_RUNNING.load(Ordering::Relaxed),
         connected: BRIDGE_RUNNING.load(Ordering::Relaxed),
-        bot_name: BOT_PUBKEY.get().map(|pk| format!("{}...", &pk[..12])),
+        bot_name: BOT_PUBKEY
+            .get()
+            .map(|pk| format!("{}...", safe_truncate(pk, 12))),
         bot_id: BOT_PUBKEY.get().cloned(),
         message_count: MESSAGE_COUNT.load(Ordering::Relaxed) as u64,
         allowed_users: config.allowed_users,
@@ -44,7 +44,7 @@ pub(crate) async fn run_relay_loop(
 
     // Subscribe to events mentioning our pubkey (NIP-01)
     // kind 1 = text notes, kind 4 = encrypted DMs (NIP-04)
-    let sub_id = format!("paw-{}", &pubkey_hex[..8]);
+    let sub_id = format!("paw-{}", safe_truncate(&pubkey_hex, 8));
     let req = json!(["REQ", &sub_id, {
         "#p": [pubkey_hex],
         "kinds": [1, 4],
@@ -159,8 +159,10 @@ pub(crate) async fn run_relay_loop(
                 debug!(
                     "[nostr] {} from {}...{}: {}",
                     if is_dm { "DM" } else { "Event" },
-                    &sender_pk[..8],
-                    &sender_pk[sender_pk.len() - 4..],
+                    safe_truncate(&sender_pk, 8),
+                    sender_pk
+                        .get(sender_pk.len().saturating_sub(4)..)
+                        .unwrap_or(""),
                     if content.len() > 50 {
                         format!("{}...", &content[..content.floor_char_boundary(50)])
                     } else {
@@ -172,8 +174,8 @@ pub(crate) async fn run_relay_loop(
                 if let Err(_denial_msg) = channels::check_access(
                     &current_config.dm_policy,
                     &sender_pk,
-                    &sender_pk[..12],
-                    &sender_pk[..12],
+                    safe_truncate(&sender_pk, 12),
+                    safe_truncate(&sender_pk, 12),
                     &current_config.allowed_users,
                     &mut current_config.pending_users,
                 ) {
@@ -260,8 +262,10 @@ pub(crate) async fn run_relay_loop(
                     Err(e) => {
                         error!(
                             "[nostr] Agent error for {}...{}: {}",
-                            &sender_pk[..8],
-                            &sender_pk[sender_pk.len() - 4..],
+                            safe_truncate(&sender_pk, 8),
+                            sender_pk
+                                .get(sender_pk.len().saturating_sub(4)..)
+                                .unwrap_or(""),
                             e
                         );
                     }
@@ -11,6 +11,8 @@ use crate::engine::providers::AnyProvider;
 use crate::engine::state::PendingApprovals;
 use crate::engine::types::*;
 use log::{info, warn};
+use openpawz_core::engine::cancel::{run_cancellable, CancelToken};
+use openpawz_core::engine::emergency;
 use tauri::Emitter;
 
 use super::handlers::{execute_boss_tool, execute_worker_tool};
@@ -32,6 +34,11 @@ fn is_orchestrator_safe(name: &str) -> bool {
     openpawz_core::engine::tool_metadata::orchestrator_safe(name)
 }
 
+/// Error for a run cut short by the emergency stop.
+fn halted() -> EngineError {
+    openpawz_core::engine::i18n::t("run.halted", &[]).into()
+}
+
 // ── Unified loop ───────────────────────────────────────────────────────
 
 /// Run a streaming agent loop that intercepts role-specific tools.
@@ -59,10 +66,17 @@ pub(crate) async fn run_orchestrator_loop(
         AgentRole::Worker { agent_id } => format!("Worker {}", agent_id),
     };
 
+    // Emergency stop: no new runs or rounds while halted, and a stop
+    // cancels the model call or tool in flight.
+    emergency::ensure_armed()?;
+    let halt = CancelToken::new();
+    let _halt_watch = emergency::watch(&halt);
+
     let mut round = 0u32;
     let mut final_text = String::new();
 
     loop {
+        emergency::ensure_armed()?;
         round += 1;
         if round > max_rounds {
             warn!(
@@ -78,9 +92,12 @@ pub(crate) async fn run_orchestrator_loop(
         );
 
         // ── Stream from the AI model ───────────────────────────────
-        let chunks = provider
-            .chat_stream(messages, tools, model, None, None)
-            .await?;
+        let chunks = run_cancellable(
+            Some(&halt),
+            provider.chat_stream(messages, tools, model, None, None),
+        )
+        .await
+        .ok_or_else(halted)??;
 
         let mut text_accum = String::new();
         let mut tool_call_map: std::collections::HashMap<
@@ -175,6 +192,7 @@ pub(crate) async fn run_orchestrator_loop(
                         model: confirmed_model.clone(),
                         total_rounds: Some(round),
                         max_rounds: Some(max_rounds),
+                        finish_reason: None,
                     },
                 );
             }
@@ -300,6 +318,7 @@ pub(crate) async fn run_orchestrator_loop(
                         round_number: Some(round + 1),
                         loaded_tools: None,
                         context_tokens: None,
+                        approval_reason: None,
                     },
                 );
                 match tokio::time::timeout(
@@ -328,7 +347,16 @@ pub(crate) async fn run_orchestrator_loop(
                 continue;
             }
 
-            let result = crate::engine::tools::execute_tool(tc, app_handle, agent_id).await;
+            emergency::ensure_armed()?;
+            let result = run_cancellable(
+                Some(&halt),
+                crate::engine::tools::in_session(
+                    session_id,
+                    crate::engine::tools::execute_tool(tc, app_handle, agent_id),
+                ),
+            )
+            .await
+            .ok_or_else(halted)?;
             let _ = app_handle.emit(
                 "engine-event",
                 EngineEvent::ToolResultEvent {
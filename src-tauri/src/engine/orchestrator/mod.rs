@@ -33,6 +33,7 @@ use tools::boss_tools;
 /// The boss agent gets a special system prompt + delegation tools,
 /// and orchestrates sub-agents to achieve the project goal.
 pub async fn run_project(app_handle: &tauri::AppHandle, project_id: &str) -> EngineResult<String> {
+    openpawz_core::engine::emergency::ensure_armed()?;
     let state = app_handle.state::<EngineState>();
     let run_id = uuid::Uuid::new_v4().to_string();
 
@@ -88,6 +89,12 @@ pub async fn run_project(app_handle: &tauri::AppHandle, project_id: &str) -> Eng
     };
     state.store.add_project_message(&init_msg)?;
 
+    // The boss's definition: its model and persona, and its skill list
+    let agent_def = state
+        .store
+        .get_agent_definition(&project.boss_agent)
+        .unwrap_or(None);
+
     // Get provider config — use model routing for boss agent
     let (provider_config, model) = {
         let cfg = state.config.lock();
@@ -107,8 +114,13 @@ pub async fn run_project(app_handle: &tauri::AppHandle, project_id: &str) -> Eng
         {
             agent_model.to_string()
         } else {
-            cfg.model_routing
-                .resolve(&project.boss_agent, "boss", boss_specialty, &default_model)
+            cfg.model_routing.resolve_for_agent(
+                &project.boss_agent,
+                agent_def.as_ref().and_then(|a| a.model.as_deref()),
+                "boss",
+                boss_specialty,
+                &default_model,
+            )
         };
 
         info!(
@@ -126,7 +138,10 @@ pub async fn run_project(app_handle: &tauri::AppHandle, project_id: &str) -> Eng
     let (base_system_prompt, max_rounds, tool_timeout) = {
         let cfg = state.config.lock();
         (
-            cfg.default_system_prompt.clone(),
+            agent_def
+                .as_ref()
+                .and_then(|a| a.persona.clone())
+                .or_else(|| cfg.default_system_prompt.clone()),
             cfg.max_tool_rounds,
             cfg.tool_timeout_secs,
         )
@@ -266,14 +281,14 @@ You are the **Boss Agent** orchestrating project "{}".
 
     // Build tools: builtins + skill tools + orchestrator boss tools
     let mut all_tools = crate::engine::tools::builtin_tools();
-    let enabled_ids: Vec<String> = skills::builtin_skills()
-        .iter()
-        .filter(|s| state.store.is_skill_enabled(&s.id).unwrap_or(false))
-        .map(|s| s.id.clone())
-        .collect();
+    let enabled_ids = skills::enabled_skill_ids(&state.store, &project.boss_agent);
     if !enabled_ids.is_empty() {
         all_tools.extend(crate::engine::tools::skill_tools(&enabled_ids));
     }
+    all_tools.extend(crate::engine::tools::skill_state::agent_definitions(
+        &state.store,
+        &project.boss_agent,
+    ));
     all_tools.extend(boss_tools());
     // Add tools from connected MCP servers
     all_tools.extend(crate::engine::tools::mcp_tools(app_handle));
@@ -308,6 +323,8 @@ You are the **Boss Agent** orchestrating project "{}".
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     state.store.add_message(&user_msg)?;
 
@@ -350,13 +367,15 @@ You are the **Boss Agent** orchestrating project "{}".
                 tool_call_id: None,
                 name: None,
                 created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
             };
             state.store.add_message(&stored).ok();
 
             // §17 Post-capture: store project outcome in Engram memory
             if !text.is_empty() {
                 let summary = if text.len() > 4000 {
-                    &text[..4000]
+                    safe_truncate(text, 4000)
                 } else {
                     text.as_str()
                 };
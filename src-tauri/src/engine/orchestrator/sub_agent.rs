@@ -78,6 +78,8 @@ pub(crate) async fn run_sub_agent(
     context: &str,
 ) -> EngineResult<String> {
     let state = app_handle.state::<EngineState>();
+    // The agent's definition: its model and persona, and its skill list
+    let agent_def = state.store.get_agent_definition(agent_id).unwrap_or(None);
 
     // Get provider — use model routing for worker agents
     let (provider_config, model, agent_capabilities, agent_specialty) = {
@@ -102,7 +104,8 @@ pub(crate) async fn run_sub_agent(
             .map(|a| a.capabilities.clone())
             .unwrap_or_default();
 
-        // Resolve model: per-agent field > model_routing > default
+        // Resolve model: per-agent field > model_routing (with the agent's
+        // own model) > default
         let model = if let Some(agent_model) = agent_entry
             .as_ref()
             .and_then(|a| a.model.as_deref())
@@ -110,8 +113,13 @@ pub(crate) async fn run_sub_agent(
         {
             agent_model.to_string()
         } else {
-            cfg.model_routing
-                .resolve(agent_id, "worker", &specialty, &default_model)
+            cfg.model_routing.resolve_for_agent(
+                agent_id,
+                agent_def.as_ref().and_then(|a| a.model.as_deref()),
+                "worker",
+                &specialty,
+                &default_model,
+            )
         };
 
         info!(
@@ -129,7 +137,10 @@ pub(crate) async fn run_sub_agent(
     let (base_system_prompt, max_rounds, tool_timeout) = {
         let cfg = state.config.lock();
         (
-            cfg.default_system_prompt.clone(),
+            agent_def
+                .as_ref()
+                .and_then(|a| a.persona.clone())
+                .or_else(|| cfg.default_system_prompt.clone()),
             cfg.max_tool_rounds,
             cfg.tool_timeout_secs,
         )
@@ -209,14 +220,14 @@ When told to install a package:
 
     // Build tools: builtins + skills + worker tools
     let mut all_tools = crate::engine::tools::builtin_tools();
-    let enabled_ids: Vec<String> = skills::builtin_skills()
-        .iter()
-        .filter(|s| state.store.is_skill_enabled(&s.id).unwrap_or(false))
-        .map(|s| s.id.clone())
-        .collect();
+    let enabled_ids = skills::enabled_skill_ids(&state.store, agent_id);
     if !enabled_ids.is_empty() {
         all_tools.extend(crate::engine::tools::skill_tools(&enabled_ids));
     }
+    all_tools.extend(crate::engine::tools::skill_state::agent_definitions(
+        &state.store,
+        agent_id,
+    ));
     all_tools.extend(worker_tools());
     // Add tools from connected MCP servers
     all_tools.extend(crate::engine::tools::mcp_tools(app_handle));
@@ -273,6 +284,8 @@ When told to install a package:
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     state.store.add_message(&user_msg)?;
 
@@ -15,10 +15,15 @@ use crate::engine::binary_ipc::ResultAccumulator;
 use crate::engine::tools;
 use crate::engine::types::ToolCall;
 use log::{info, warn};
+use openpawz_core::engine::cancel::{run_cancellable, CancelToken};
+use openpawz_core::engine::emergency;
 use std::collections::HashMap;
 use std::time::Instant;
 use tauri::Emitter;
 
+/// Output of nodes cut short or skipped by the emergency stop.
+const HALTED_OUTPUT: &str = "Cancelled by emergency stop";
+
 /// Execute an action DAG plan with parallel phase execution.
 ///
 /// For each phase (group of independent nodes at the same DAG depth),
@@ -61,13 +66,25 @@ pub async fn execute_plan(
     // binary context injection in future.
     let mut result_accumulator = ResultAccumulator::new();
 
+    // Emergency stop cancels the nodes in flight and skips the rest
+    let halt = CancelToken::new();
+    let _halt_watch = emergency::watch(&halt);
+
     for phase in &phases {
-        // Check overall plan timeout
-        if plan_start.elapsed().as_millis() as u64 > PLAN_TIMEOUT_MS {
+        // Check overall plan timeout and the emergency stop
+        let stop_reason = if plan_start.elapsed().as_millis() as u64 > PLAN_TIMEOUT_MS {
             warn!(
                 "[plan] Plan timeout exceeded ({}ms), cancelling remaining phases",
                 PLAN_TIMEOUT_MS
             );
+            Some("Plan timeout exceeded")
+        } else if halt.is_cancelled() {
+            warn!("[plan] Emergency stop, cancelling remaining phases");
+            Some(HALTED_OUTPUT)
+        } else {
+            None
+        };
+        if let Some(reason) = stop_reason {
             // Mark all remaining nodes as skipped
             for remaining_phase in phases.iter().skip(phase.index) {
                 for node_id in &remaining_phase.node_ids {
@@ -79,7 +96,7 @@ pub async fn execute_plan(
                                 .map(|n| n.tool.clone())
                                 .unwrap_or_default(),
                             status: NodeStatus::Skipped,
-                            output: "Plan timeout exceeded".to_string(),
+                            output: reason.to_string(),
                             retryable: false,
                             retries: 0,
                             duration_ms: 0,
@@ -130,6 +147,7 @@ pub async fn execute_plan(
             let agent_id_owned = agent_id.to_string();
             let session_id_owned = session_id.to_string();
             let run_id_owned = run_id.to_string();
+            let halt_owned = halt.clone();
 
             let task = tokio::spawn(async move {
                 execute_node_with_retry(
@@ -138,6 +156,7 @@ pub async fn execute_plan(
                     &agent_id_owned,
                     &session_id_owned,
                     &run_id_owned,
+                    &halt_owned,
                 )
                 .await
             });
@@ -213,6 +232,7 @@ async fn execute_node_with_retry(
     agent_id: &str,
     session_id: &str,
     run_id: &str,
+    halt: &CancelToken,
 ) -> NodeResult {
     let node_start = Instant::now();
     let timeout = node_timeout_ms(&node.tool);
@@ -243,12 +263,32 @@ async fn execute_node_with_retry(
             thought_parts: vec![],
         };
 
-        // Execute with per-node timeout
-        let execute_result = tokio::time::timeout(
-            std::time::Duration::from_millis(timeout),
-            tools::execute_tool(&tool_call, app_handle, agent_id),
+        // Execute with per-node timeout, unless the engine is halted
+        let execute_result = match run_cancellable(
+            Some(halt),
+            tokio::time::timeout(
+                std::time::Duration::from_millis(timeout),
+                tools::in_session(
+                    session_id,
+                    tools::execute_tool(&tool_call, app_handle, agent_id),
+                ),
+            ),
         )
-        .await;
+        .await
+        {
+            Some(result) => result,
+            None => {
+                return NodeResult {
+                    node_id: node.id.clone(),
+                    tool: node.tool.clone(),
+                    status: NodeStatus::Error,
+                    output: HALTED_OUTPUT.to_string(),
+                    retryable: false,
+                    retries,
+                    duration_ms: node_start.elapsed().as_millis() as u64,
+                };
+            }
+        };
 
         match execute_result {
             Ok(result) => {
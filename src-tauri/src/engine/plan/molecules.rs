@@ -8,6 +8,7 @@
 
 use super::atoms::*;
 use crate::atoms::types::ToolDefinition;
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
 use std::collections::HashSet;
 
@@ -161,7 +162,7 @@ pub fn build_results_context(plan: &ExecutionPlan, results: &[NodeResult]) -> St
         let output = if result.output.len() > 2000 {
             format!(
                 "{}… (truncated, {} chars total)",
-                &result.output[..2000],
+                safe_truncate(&result.output, 2000),
                 result.output.len()
             )
         } else {
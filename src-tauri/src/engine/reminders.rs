@@ -0,0 +1,94 @@
+// engine/reminders.rs — Background delivery of due reminders.
+//
+// Polled from the Tauri setup hook. Each due reminder is marked fired first
+// (so a slow channel can't cause a double delivery), then emitted to the UI
+// as a `reminder-due` event and, when it was set with a channel, sent there
+// too. Reminders that came due while the app was closed fire on the first
+// poll after launch, flagged as missed.
+
+use crate::engine::sessions::Reminder;
+use crate::engine::state::EngineState;
+use crate::engine::whatsapp;
+use log::{info, warn};
+use openpawz_core::engine::reminders::{is_missed, notification_text};
+use tauri::{Emitter, Manager};
+
+/// How often the background loop checks for due reminders.
+pub const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Channels a reminder can also be delivered through.
+pub const DELIVERY_CHANNELS: &[&str] = &["whatsapp"];
+
+/// Fire every reminder that is due now.
+pub async fn run_reminder_tick(app_handle: &tauri::AppHandle) {
+    let Some(state) = app_handle.try_state::<EngineState>() else {
+        return;
+    };
+    let now = chrono::Utc::now();
+    let due = match state.store.due_reminders(&now) {
+        Ok(due) => due,
+        Err(e) => {
+            warn!("[reminders] Failed to query due reminders: {}", e);
+            return;
+        }
+    };
+
+    for reminder in due {
+        match state.store.mark_reminder_fired(reminder.id) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                warn!("[reminders] Failed to mark #{} fired: {}", reminder.id, e);
+                continue;
+            }
+        }
+        let fire_at = reminder.fire_at_utc().unwrap_or(now);
+        let text = notification_text(&reminder.text, &fire_at, now);
+        info!(
+            "[reminders] Firing #{} for agent {}",
+            reminder.id, reminder.agent_id
+        );
+
+        let _ = app_handle.emit(
+            "reminder-due",
+            serde_json::json!({
+                "id": reminder.id,
+                "agent_id": reminder.agent_id,
+                "text": reminder.text,
+                "message": text,
+                "fire_at": reminder.fire_at,
+                "missed": is_missed(&fire_at, now),
+            }),
+        );
+        deliver_to_channel(app_handle, &reminder, &text).await;
+    }
+}
+
+async fn deliver_to_channel(app_handle: &tauri::AppHandle, reminder: &Reminder, text: &str) {
+    let (Some(channel), Some(recipient)) = (&reminder.channel, &reminder.recipient) else {
+        return;
+    };
+    match channel.as_str() {
+        "whatsapp" => {
+            if !whatsapp::get_status(app_handle).running {
+                warn!(
+                    "[reminders] WhatsApp bridge not running — #{} shown in the app only",
+                    reminder.id
+                );
+                return;
+            }
+            if let Err(e) =
+                whatsapp::evolution_api::send_whatsapp_message(app_handle, recipient, text).await
+            {
+                warn!(
+                    "[reminders] WhatsApp delivery of #{} failed: {}",
+                    reminder.id, e
+                );
+            }
+        }
+        other => warn!(
+            "[reminders] Unknown delivery channel '{}' for #{}",
+            other, reminder.id
+        ),
+    }
+}
@@ -0,0 +1,534 @@
+// Paw Agent Engine — robots.txt compliance & per-host rate limiting
+//
+// Good-citizen behaviour for automated web access. Before the fetch and
+// web tools hit a site they:
+//   1. look up the host's robots.txt (fetched once, cached for an hour) and
+//      refuse paths disallowed for the `OpenPawz` user-agent, and
+//   2. wait out a per-host minimum interval — or the site's Crawl-delay,
+//      whichever is longer — so bursts of tool calls don't hammer one server.
+//
+// robots.txt is honored by default for page retrieval through the generic
+// `fetch` / `web_read` tools. API endpoints (api.* hosts, /api/ and /vN/
+// paths, authenticated requests) aren't crawled pages and only get the rate
+// limit unless `respect_robots_for_apis` is set. Interactive browsing (`web_browse`, `web_screenshot`) acts on the
+// user's explicit behalf and only checks it when enabled in settings.
+// Stored as JSON under the `web_politeness` config key.
+
+use crate::atoms::error::EngineResult;
+use crate::engine::sessions::SessionStore;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+/// engine_config key holding the serialized [`PolitenessConfig`].
+pub const POLITENESS_CONFIG_KEY: &str = "web_politeness";
+
+/// Product token matched against robots.txt `User-agent` lines.
+pub const ROBOTS_AGENT: &str = "OpenPawz";
+
+/// How long a fetched robots.txt is trusted.
+const ROBOTS_TTL: Duration = Duration::from_secs(3600);
+/// Unreachable robots.txt is retried sooner.
+const ROBOTS_ERROR_TTL: Duration = Duration::from_secs(300);
+/// Cap on a robots.txt body (RFC 9309 asks parsers to read at least 500 KiB).
+const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+/// Longest Crawl-delay we'll honor — anything above is treated as this.
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolitenessConfig {
+    /// Honor robots.txt for `fetch` and `web_read`.
+    pub respect_robots: bool,
+    /// Honor robots.txt for user-directed browsing (`web_browse`, `web_screenshot`).
+    pub respect_robots_in_browser: bool,
+    /// Also honor it for `fetch` GETs to API endpoints.
+    pub respect_robots_for_apis: bool,
+    /// Minimum gap between requests to the same host. 0 disables throttling.
+    pub min_interval_ms: u64,
+}
+
+impl Default for PolitenessConfig {
+    fn default() -> Self {
+        PolitenessConfig {
+            respect_robots: true,
+            respect_robots_in_browser: false,
+            respect_robots_for_apis: false,
+            min_interval_ms: 500,
+        }
+    }
+}
+
+/// Which path a request comes through — decides whether robots.txt applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// Generic automated retrieval (`fetch`, `web_read`).
+    Fetch,
+    /// Headless-browser navigation the user asked for.
+    Browser,
+    /// Reads from an API endpoint (see [`is_api_url`]) — robots.txt only
+    /// when `respect_robots_for_apis` is set.
+    ApiRead,
+    /// State-changing API calls (non-GET `fetch`) — rate limited only.
+    Api,
+}
+
+// ── robots.txt parsing ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+/// A parsed robots.txt. An empty one allows everything.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsTxt {
+    groups: Vec<Group>,
+}
+
+impl RobotsTxt {
+    pub fn parse(text: &str) -> Self {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut current: Option<Group> = None;
+        // A User-agent line after rules starts a new group
+        let mut in_rules = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+            match key.as_str() {
+                "user-agent" => {
+                    if in_rules || current.is_none() {
+                        if let Some(done) = current.take() {
+                            groups.push(done);
+                        }
+                        current = Some(Group::default());
+                        in_rules = false;
+                    }
+                    if let Some(group) = current.as_mut() {
+                        group.agents.push(value.to_ascii_lowercase());
+                    }
+                }
+                "allow" | "disallow" => {
+                    let Some(group) = current.as_mut() else {
+                        continue;
+                    };
+                    in_rules = true;
+                    // An empty Disallow means "allow everything" — no rule
+                    if !value.is_empty() {
+                        group.rules.push(Rule {
+                            allow: key == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    let Some(group) = current.as_mut() else {
+                        continue;
+                    };
+                    in_rules = true;
+                    if let Ok(secs) = value.parse::<f64>() {
+                        if secs.is_finite() && secs >= 0.0 {
+                            group.crawl_delay =
+                                Some(Duration::from_secs_f64(secs).min(MAX_CRAWL_DELAY));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(done) = current {
+            groups.push(done);
+        }
+        RobotsTxt { groups }
+    }
+
+    /// Groups addressed to `agent`, falling back to the `*` groups.
+    fn groups_for(&self, agent: &str) -> Vec<&Group> {
+        let agent = agent.to_ascii_lowercase();
+        let specific: Vec<&Group> = self
+            .groups
+            .iter()
+            .filter(|g| g.agents.iter().any(|a| a != "*" && agent.starts_with(a)))
+            .collect();
+        if !specific.is_empty() {
+            return specific;
+        }
+        self.groups
+            .iter()
+            .filter(|g| g.agents.iter().any(|a| a == "*"))
+            .collect()
+    }
+
+    /// Whether `agent` may fetch `path` (path plus optional `?query`).
+    /// The longest matching rule wins; Allow wins a tie; no match allows.
+    pub fn is_allowed(&self, agent: &str, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        let mut best: Option<&Rule> = None;
+        for rule in self.groups_for(agent).into_iter().flat_map(|g| &g.rules) {
+            if !pattern_matches(&rule.pattern, path) {
+                continue;
+            }
+            best = match best {
+                Some(b)
+                    if b.pattern.len() > rule.pattern.len()
+                        || (b.pattern.len() == rule.pattern.len() && b.allow) =>
+                {
+                    Some(b)
+                }
+                _ => Some(rule),
+            };
+        }
+        best.is_none_or(|r| r.allow)
+    }
+
+    /// The Crawl-delay addressed to `agent`, if any.
+    pub fn crawl_delay(&self, agent: &str) -> Option<Duration> {
+        self.groups_for(agent)
+            .into_iter()
+            .filter_map(|g| g.crawl_delay)
+            .max()
+    }
+}
+
+/// robots.txt path matching: prefix match with `*` wildcards and an
+/// optional trailing `$` end anchor.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    if !path.starts_with(first) {
+        return false;
+    }
+    let rest: Vec<&str> = parts.collect();
+    let mut pos = first.len();
+    if rest.is_empty() {
+        return !anchored || pos == path.len();
+    }
+    for (i, part) in rest.iter().enumerate() {
+        if anchored && i == rest.len() - 1 {
+            return path.len() - pos >= part.len() && path.ends_with(part);
+        }
+        match path[pos..].find(part) {
+            Some(idx) => pos += idx + part.len(),
+            None => return false,
+        }
+    }
+    true
+}
+
+// ── Per-origin robots.txt cache ────────────────────────────────────────
+
+static ROBOTS_CACHE: OnceLock<Mutex<HashMap<String, (Instant, Arc<RobotsTxt>)>>> = OnceLock::new();
+
+async fn robots_for(origin: &str) -> Arc<RobotsTxt> {
+    let cache = ROBOTS_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some((expires, robots)) = cache.lock().get(origin) {
+        if *expires > Instant::now() {
+            return Arc::clone(robots);
+        }
+    }
+
+    let (robots, ttl) = match download_robots(origin).await {
+        Ok(robots) => (robots, ROBOTS_TTL),
+        Err(e) => {
+            warn!("[robots] {}/robots.txt unavailable: {}", origin, e);
+            (RobotsTxt::default(), ROBOTS_ERROR_TTL)
+        }
+    };
+    let robots = Arc::new(robots);
+    cache.lock().insert(
+        origin.to_string(),
+        (Instant::now() + ttl, Arc::clone(&robots)),
+    );
+    robots
+}
+
+/// Fetch and parse `<origin>/robots.txt`. A 4xx means "no restrictions".
+async fn download_robots(origin: &str) -> Result<RobotsTxt, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .user_agent(ROBOTS_AGENT)
+        .redirect(reqwest::redirect::Policy::limited(5))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let resp = client
+        .get(format!("{}/robots.txt", origin))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = resp.status();
+    if status.is_client_error() {
+        return Ok(RobotsTxt::default());
+    }
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    Ok(RobotsTxt::parse(crate::engine::util::safe_truncate(
+        &body,
+        MAX_ROBOTS_BYTES,
+    )))
+}
+
+// ── Per-host rate limiting ─────────────────────────────────────────────
+
+static LAST_REQUEST: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+/// Claim the next request slot for `host` and return how long to wait for it.
+/// Slots are reserved under the lock, so concurrent callers queue up rather
+/// than all firing once the interval elapses.
+fn reserve_slot(
+    slots: &mut HashMap<String, Instant>,
+    host: &str,
+    interval: Duration,
+    now: Instant,
+) -> Duration {
+    let start = match slots.get(host) {
+        Some(last) => (*last + interval).max(now),
+        None => now,
+    };
+    slots.insert(host.to_string(), start);
+    start - now
+}
+
+async fn throttle(host: &str, interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+    let wait = {
+        let slots = LAST_REQUEST.get_or_init(|| Mutex::new(HashMap::new()));
+        reserve_slot(&mut slots.lock(), host, interval, Instant::now())
+    };
+    if !wait.is_zero() {
+        info!("[robots] Rate limit: waiting {:?} before {}", wait, host);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// ── Entry point ────────────────────────────────────────────────────────
+
+/// URLs that address an API rather than a page: `api.` hosts, and paths
+/// under `/api/`, `/graphql` or a version segment like `/v1/`.
+pub fn is_api_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    let host = parsed.host_str().unwrap_or("");
+    if host.starts_with("api.") || host.contains(".api.") {
+        return true;
+    }
+    let first = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .unwrap_or("");
+    first == "api"
+        || first == "graphql"
+        || (first.len() > 1
+            && first.starts_with('v')
+            && first[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Gate a request to `url`: refuse it when robots.txt disallows the path
+/// (if robots apply to `kind`), otherwise wait for the host's next slot.
+pub async fn before_request(
+    config: &PolitenessConfig,
+    url: &str,
+    kind: AccessKind,
+) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Ok(());
+    }
+    let Some(host) = parsed.host_str() else {
+        return Ok(());
+    };
+
+    let check_robots = match kind {
+        AccessKind::Fetch => config.respect_robots,
+        AccessKind::Browser => config.respect_robots_in_browser,
+        AccessKind::ApiRead => config.respect_robots && config.respect_robots_for_apis,
+        AccessKind::Api => false,
+    };
+    let mut interval = Duration::from_millis(config.min_interval_ms);
+    if check_robots {
+        let origin = parsed.origin().ascii_serialization();
+        let robots = robots_for(&origin).await;
+        let mut path = parsed.path().to_string();
+        if let Some(query) = parsed.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+        if !robots.is_allowed(ROBOTS_AGENT, &path) {
+            warn!("[robots] Disallowed by robots.txt: {}", url);
+            return Err(format!(
+                "robots.txt on {} disallows {} for automated agents. \
+                 Ask the user before retrieving it another way.",
+                host, path
+            ));
+        }
+        if let Some(delay) = robots.crawl_delay(ROBOTS_AGENT) {
+            interval = interval.max(delay);
+        }
+    }
+
+    throttle(host, interval).await;
+    Ok(())
+}
+
+/// Load the settings, falling back to defaults.
+pub fn load_config(store: &SessionStore) -> PolitenessConfig {
+    store
+        .get_config(POLITENESS_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_config(store: &SessionStore, config: &PolitenessConfig) -> EngineResult<()> {
+    store.set_config(POLITENESS_CONFIG_KEY, &serde_json::to_string(config)?)
+}
+
+/// Settings for tool code that only holds an app handle.
+pub fn config_for(app_handle: &tauri::AppHandle) -> PolitenessConfig {
+    app_handle
+        .try_state::<crate::engine::state::EngineState>()
+        .map(|state| load_config(&state.store))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "\
+# Example robots.txt
+User-agent: *
+Disallow: /private/
+Allow: /private/press/
+Disallow: /*.pdf$
+Crawl-delay: 2
+
+User-agent: BadBot
+User-agent: OpenPawz
+Disallow: /search
+Allow: /search/about
+
+User-agent: Other
+Disallow: /
+";
+
+    #[test]
+    fn parses_groups_and_crawl_delay() {
+        let robots = RobotsTxt::parse(ROBOTS);
+        assert_eq!(robots.groups.len(), 3);
+        assert_eq!(robots.groups[1].agents, vec!["badbot", "openpawz"]);
+        assert_eq!(
+            robots.crawl_delay("SomeCrawler"),
+            Some(Duration::from_secs(2))
+        );
+        // Our own group has no Crawl-delay
+        assert_eq!(robots.crawl_delay(ROBOTS_AGENT), None);
+    }
+
+    #[test]
+    fn specific_group_replaces_wildcard_group() {
+        let robots = RobotsTxt::parse(ROBOTS);
+        // The OpenPawz group applies, so `*` rules don't
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/private/secret"));
+        assert!(!robots.is_allowed(ROBOTS_AGENT, "/search?q=otters"));
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/search/about"));
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/"));
+        assert!(!robots.is_allowed("other", "/anything"));
+    }
+
+    #[test]
+    fn longest_match_and_wildcards_for_default_group() {
+        let robots = RobotsTxt::parse(ROBOTS);
+        let agent = "GenericBot";
+        assert!(!robots.is_allowed(agent, "/private/notes.html"));
+        assert!(robots.is_allowed(agent, "/private/press/release.html"));
+        assert!(!robots.is_allowed(agent, "/docs/report.pdf"));
+        assert!(robots.is_allowed(agent, "/docs/report.pdf?download=1"));
+        assert!(robots.is_allowed(agent, "/index.html"));
+        assert!(robots.is_allowed(agent, "/robots.txt"));
+    }
+
+    #[test]
+    fn empty_or_permissive_files_allow_everything() {
+        assert!(RobotsTxt::parse("").is_allowed(ROBOTS_AGENT, "/x"));
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow:\n");
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/anything"));
+        // Rules before any User-agent line are ignored
+        let robots = RobotsTxt::parse("Disallow: /\n");
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/anything"));
+    }
+
+    #[test]
+    fn allow_wins_equal_length_tie() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow: /page\nAllow: /page\n");
+        assert!(robots.is_allowed(ROBOTS_AGENT, "/page"));
+    }
+
+    #[test]
+    fn api_endpoints_are_told_apart_from_pages() {
+        for url in [
+            "https://api.github.com/repos/o/r/issues",
+            "https://example.com/api/items",
+            "https://example.com/v2/users/1",
+            "https://example.com/graphql",
+            "https://eu.api.example.com/things",
+        ] {
+            assert!(is_api_url(url), "{url}");
+        }
+        for url in [
+            "https://github.com/o/r/issues",
+            "https://example.com/blog/api-design",
+            "https://example.com/video/1",
+            "https://example.com/",
+            "not a url",
+        ] {
+            assert!(!is_api_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn slots_are_spaced_per_host() {
+        let mut slots = HashMap::new();
+        let now = Instant::now();
+        let gap = Duration::from_millis(500);
+        assert_eq!(reserve_slot(&mut slots, "a.com", gap, now), Duration::ZERO);
+        assert_eq!(reserve_slot(&mut slots, "a.com", gap, now), gap);
+        assert_eq!(reserve_slot(&mut slots, "a.com", gap, now), gap * 2);
+        // Other hosts are independent
+        assert_eq!(reserve_slot(&mut slots, "b.com", gap, now), Duration::ZERO);
+        // Once the interval has passed there's no wait
+        let later = now + Duration::from_secs(5);
+        assert_eq!(
+            reserve_slot(&mut slots, "a.com", gap, later),
+            Duration::ZERO
+        );
+    }
+}
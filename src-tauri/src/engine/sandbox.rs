@@ -3,6 +3,7 @@
 // Uses bollard (Docker API client) to manage ephemeral containers.
 
 use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::util::safe_truncate;
 use bollard::container::LogOutput;
 use bollard::models::{ContainerCreateBody, HostConfig};
 use bollard::query_parameters::{
@@ -194,7 +195,7 @@ pub async fn run_in_sandbox(command: &str, config: &SandboxConfig) -> EngineResu
 
     info!(
         "[sandbox] Created container {} for command: {}",
-        &container_id[..12],
+        safe_truncate(&container_id, 12),
         &command[..command.floor_char_boundary(100)]
     );
 
@@ -233,7 +234,7 @@ pub async fn run_in_sandbox(command: &str, config: &SandboxConfig) -> EngineResu
         Err(_) => {
             warn!(
                 "[sandbox] Container {} timed out after {}s",
-                &container_id[..12],
+                safe_truncate(&container_id, 12),
                 config.timeout_secs
             );
             // Kill the container
@@ -295,11 +296,14 @@ pub async fn run_in_sandbox(command: &str, config: &SandboxConfig) -> EngineResu
     {
         warn!(
             "[sandbox] Failed to remove container {}: {}",
-            &container_id[..12],
+            safe_truncate(&container_id, 12),
             e
         );
     } else {
-        info!("[sandbox] Removed container {}", &container_id[..12]);
+        info!(
+            "[sandbox] Removed container {}",
+            safe_truncate(&container_id, 12)
+        );
     }
 
     Ok(SandboxResult {
@@ -450,18 +450,28 @@ Risk Management Rules:
             required_credentials: vec![
                 CredentialField { key: "NOTION_API_KEY".into(), label: "Integration Token".into(), description: "Notion internal integration token (secret_...)".into(), required: true, placeholder: "secret_xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx".into() },
             ],
-            tool_names: vec![],
-            required_binaries: vec![], required_env_vars: vec![], install_hint: "Create an integration at notion.so/my-integrations".into(),
-            agent_instructions: r#"You have Notion API access. Use the fetch tool to interact with the Notion API (https://api.notion.com/v1/).
-Key endpoints:
-- POST /pages — create a page
-- PATCH /pages/{id} — update page properties
-- POST /databases/{id}/query — query a database
-- GET /blocks/{id}/children — get block children
-- PATCH /blocks/{id} — update a block
-- POST /search — search across all pages/databases
-Headers: Authorization: Bearer {token}, Notion-Version: 2022-06-28, Content-Type: application/json
-Notion uses rich text blocks. Page content is a list of block objects (paragraph, heading_1, to_do, etc.)."#.into(),
+            tool_names: vec![
+                "notion_search".into(), "notion_list_databases".into(), "notion_query_database".into(),
+                "notion_get_page".into(), "notion_create_page".into(), "notion_update_page".into(),
+                "notion_get_blocks".into(), "notion_append_blocks".into(),
+            ],
+            required_binaries: vec![], required_env_vars: vec![], install_hint: "Create an integration at notion.so/my-integrations, then share each page or database with it (••• → Connections)".into(),
+            agent_instructions: r#"You have Notion access with 8 built-in tools:
+
+**Databases (3)**: notion_search, notion_list_databases, notion_query_database
+**Pages (3)**: notion_get_page, notion_create_page, notion_update_page
+**Blocks (2)**: notion_get_blocks, notion_append_blocks
+
+TOOL SELECTION RULES:
+- FIND a page or database → notion_search (by title)
+- ADD a row to a database → notion_list_databases first to learn the property names and types, then notion_create_page with parent_database_id and properties by name
+- CHANGE a row → notion_update_page with properties by name (null clears a property); archived=true deletes the page
+- READ a page → notion_get_page; long pages continue with notion_get_blocks and next_cursor
+- ADD content → notion_append_blocks with plain text (# headings, - bullets, 1. numbered, [ ] to-dos, > quotes, ``` code)
+
+Property values are plain: text for title/rich_text, the option name for select/status, a list or comma-separated names for multi_select, ISO 8601 for dates (or {"start", "end"} for a range). Relations, people, and files can't be set.
+IDs and Notion URLs are interchangeable. A 404 almost always means the page isn't shared with the integration.
+NEVER use fetch or exec/curl to call the Notion API — use your built-in tools."#.into(),
             default_enabled: false,
         },
 
@@ -631,18 +641,24 @@ Include: file (audio binary), model: "whisper-1", optional: language, response_f
         SkillDefinition {
             id: "image_gen".into(),
             name: "Image Generation".into(),
-            description: "Generate images from text using Gemini (Google AI)".into(),
+            description: "Generate images from text with DALL·E, Stable Diffusion (OpenAI-compatible API) or Gemini".into(),
             icon: "🖼️".into(),
             category: SkillCategory::Media,
             tier: SkillTier::Integration,
             required_credentials: vec![
-                CredentialField { key: "GEMINI_API_KEY".into(), label: "Gemini API Key".into(), description: "Google AI API key for image generation".into(), required: true, placeholder: "AIza...".into() },
+                CredentialField { key: "IMAGE_API_URL".into(), label: "Image API URL".into(), description: "OpenAI-compatible images endpoint base (e.g. https://api.openai.com/v1 or a self-hosted Stable Diffusion server). Leave empty to use Gemini.".into(), required: false, placeholder: "https://api.openai.com/v1".into() },
+                CredentialField { key: "IMAGE_API_KEY".into(), label: "Image API Key".into(), description: "API key for the image endpoint, if it needs one".into(), required: false, placeholder: "sk-...".into() },
+                CredentialField { key: "IMAGE_MODEL".into(), label: "Default Model".into(), description: "Model used when the agent doesn't pick one (default dall-e-3)".into(), required: false, placeholder: "dall-e-3".into() },
+                CredentialField { key: "GEMINI_API_KEY".into(), label: "Gemini API Key".into(), description: "Google AI API key, used when no image endpoint is set or a gemini model is requested".into(), required: false, placeholder: "AIza...".into() },
+                CredentialField { key: "IMAGE_MAX_SIZE".into(), label: "Max Edge (px)".into(), description: "Largest width or height allowed (default 1792)".into(), required: false, placeholder: "1792".into() },
+                CredentialField { key: "IMAGE_MAX_COST_USD".into(), label: "Max Cost per Image (USD)".into(), description: "Requests estimated above this are refused (default 0.10)".into(), required: false, placeholder: "0.10".into() },
             ],
             tool_names: vec!["image_generate".into()],
-            required_binaries: vec![], required_env_vars: vec![], install_hint: "Get API key from aistudio.google.com/apikey".into(),
-            agent_instructions: r#"You have an image_generate tool that creates images from text descriptions using Gemini.
-Call image_generate with a detailed prompt describing the image you want to create.
-The tool returns the file path of the generated image.
+            required_binaries: vec![], required_env_vars: vec![], install_hint: "Set an OpenAI-compatible image endpoint, or get a Gemini key from aistudio.google.com/apikey".into(),
+            agent_instructions: r#"You have an image_generate tool that creates images from text descriptions.
+Call image_generate with a detailed prompt; pass size (e.g. 1024x1024, 1792x1024) or model only when the user asks for them.
+The image is saved in your workspace. Copy the "Image saved: <path>" line from the result into your reply so the chat shows the image.
+If the provider refuses the prompt under its content policy, tell the user and offer a rephrased prompt instead of retrying the same one.
 Tip: Be descriptive — include style, lighting, composition, colors, and mood in your prompts for best results."#.into(),
             default_enabled: false,
         },
@@ -675,14 +691,17 @@ Key commands:
             tier: SkillTier::Integration,
             required_credentials: vec![
                 CredentialField { key: "ELEVENLABS_API_KEY".into(), label: "ElevenLabs API Key".into(), description: "API key from elevenlabs.io".into(), required: true, placeholder: "xi_...".into() },
+                CredentialField { key: "ELEVENLABS_VOICE_ID".into(), label: "Default Voice ID".into(), description: "Voice used when the agent doesn't pick one (default: Rachel)".into(), required: false, placeholder: "21m00Tcm4TlvDq8ikWAM".into() },
+                CredentialField { key: "ELEVENLABS_MODEL".into(), label: "Model".into(), description: "ElevenLabs model (default eleven_multilingual_v2)".into(), required: false, placeholder: "eleven_multilingual_v2".into() },
+                CredentialField { key: "TTS_MAX_CHARS".into(), label: "Max Characters per Call".into(), description: "Longer text is refused (default 2500)".into(), required: false, placeholder: "2500".into() },
             ],
-            tool_names: vec![],
-            required_binaries: vec!["sag".into()],
-            required_env_vars: vec![], install_hint: "brew install sag".into(),
-            agent_instructions: r#"You can speak text aloud using ElevenLabs TTS via the `sag` CLI.
-Usage: sag "text to speak" [--voice <name>] [--model eleven_turbo_v2] [--output file.mp3]
-Or use the ElevenLabs API directly: POST https://api.elevenlabs.io/v1/text-to-speech/{voice_id}
-with {"text":"...", "model_id":"eleven_turbo_v2"}. Returns audio bytes."#.into(),
+            tool_names: vec!["speak".into()],
+            required_binaries: vec![],
+            required_env_vars: vec![], install_hint: "Get an API key from elevenlabs.io".into(),
+            agent_instructions: r#"You have a speak tool that turns text into speech with ElevenLabs.
+Call speak with plain text (no markdown); pass voice only when the user names an ElevenLabs voice ID.
+The MP3 is saved in your workspace. Copy the "Audio saved: <path>" line from the result into your reply so the chat shows a player.
+Text is capped per call — summarize long content first. If the result says the quota is exhausted, tell the user rather than retrying."#.into(),
             default_enabled: false,
         },
 
@@ -966,19 +985,28 @@ Supports RTSP, ONVIF, and HTTP MJPEG streams."#.into(),
             tier: SkillTier::Integration,
             required_credentials: vec![
                 CredentialField { key: "ETHEREUM_PRIVATE_KEY".into(), label: "Ethereum Private Key".into(), description: "Your Ethereum wallet private key (hex, with or without 0x prefix). Used for signing transactions locally — never sent to any server.".into(), required: true, placeholder: "0xabcdef1234567890...".into() },
+                CredentialField { key: "DEX_EXPLORER_API_KEY".into(), label: "Block Explorer API Key".into(), description: "Etherscan-compatible API key (Etherscan, Basescan, Arbiscan, …) for dex_tx_history. Set DEX_EXPLORER_API_KEY_<chain id> to use a different key per chain.".into(), required: false, placeholder: "ABC123...".into() },
+                CredentialField { key: "DEX_PRIVATE_RELAY_URL".into(), label: "Private Relay URL (Ethereum)".into(), description: "Optional private-mempool RPC (e.g. Flashbots Protect) for submitting swaps on Ethereum mainnet, so MEV bots can't sandwich them. Set DEX_PRIVATE_RELAY_URL_<chain id> for other chains that have one.".into(), required: false, placeholder: "https://rpc.flashbots.net/fast".into() },
+                CredentialField { key: "DEX_CONFIRMATIONS".into(), label: "Confirmations".into(), description: "Optional. Blocks a swap or transfer must be buried under before it is reported as confirmed (default 1, max 64). Raise it on reorg-prone chains; set DEX_CONFIRMATIONS_<chain id> to use a different depth per chain.".into(), required: false, placeholder: "1".into() },
+                CredentialField { key: "DEX_SLIPPAGE_RETRY_MAX_BPS".into(), label: "Slippage Auto-Retry Ceiling (bps)".into(), description: "Optional. When a swap reverts on slippage, re-quote and retry once at a higher tolerance, up to this many basis points (max 500). Leave empty to never retry automatically.".into(), required: false, placeholder: "100".into() },
+                CredentialField { key: "DEX_SLIPPAGE_RETRY_APPROVAL_BPS".into(), label: "Slippage Auto-Retry Approval Limit (bps)".into(), description: "Optional. Retries above this tolerance are not made automatically; you are asked to approve a new swap instead. Default 100 (1%).".into(), required: false, placeholder: "100".into() },
             ],
-            tool_names: vec!["dex_wallet_create".into(), "dex_balance".into(), "dex_quote".into(), "dex_swap".into(), "dex_transfer".into(), "dex_portfolio".into(), "dex_token_info".into(), "dex_check_token".into(), "dex_search_token".into(), "dex_watch_wallet".into(), "dex_whale_transfers".into(), "dex_top_traders".into(), "dex_trending".into()],
+            tool_names: vec!["dex_wallet_create".into(), "dex_wallet_list".into(), "dex_switch_chain".into(), "dex_balance".into(), "dex_quote".into(), "dex_swap".into(), "dex_transfer".into(), "dex_portfolio".into(), "dex_tx_history".into(), "dex_sign_typed_data".into(), "dex_token_info".into(), "dex_check_token".into(), "dex_search_token".into(), "dex_watch_wallet".into(), "dex_whale_transfers".into(), "dex_top_traders".into(), "dex_trending".into()],
             required_binaries: vec![], required_env_vars: vec![], install_hint: "Import or create an Ethereum wallet".into(),
             agent_instructions: r#"You have EVM DEX trading tools for self-custody Ethereum trading.
 Credentials are injected automatically. Do NOT read source code or key files.
 
 Available tools:
-- **dex_wallet_create**: Create or import an Ethereum wallet. Requires approval.
+- **dex_wallet_create**: Create or import an Ethereum wallet. Requires approval. Pass `name` to add an extra named wallet.
+- **dex_wallet_list**: List wallets and see which one is active. Pass `wallet` to dex_balance, dex_swap or dex_transfer to choose one.
+- **dex_switch_chain**: Switch the active chain (ethereum, base, arbitrum, optimism, sepolia). Token symbols and contracts follow the active chain.
 - **dex_balance**: Check ETH and token balances.
 - **dex_quote**: Get swap quotes from Uniswap V3 before executing.
-- **dex_swap**: Execute on-chain token swaps. ALWAYS requires approval.
+- **dex_swap**: Execute on-chain token swaps. ALWAYS requires approval. Sent through a private relay when one is configured (the result says which path was used). If the result says a retry at a higher slippage needs approval, tell the user the new tolerance before calling dex_swap again with that slippage_bps.
 - **dex_transfer**: Send ETH or tokens. ALWAYS requires approval.
-- **dex_portfolio**: View full portfolio with USD values.
+- **dex_portfolio**: View full portfolio. Pass `with_usd` for USD values and a total.
+- **dex_tx_history**: Show the wallet's on-chain transaction history (needs an explorer API key for full history). Pass `blocks` or `token` to list token transfers from on-chain logs with net flow per token — use that for "what did I trade this week".
+- **dex_sign_typed_data**: Sign EIP-712 typed data (dApp sign-in, token permits). ALWAYS requires approval. Never broadcasts; explain what a permit allows before signing one.
 - **dex_token_info**: Get token details (price, liquidity, contract info).
 - **dex_check_token**: Audit a token contract for rug-pull risks.
 - **dex_search_token**: Search tokens by name or symbol.
@@ -6,7 +6,7 @@
 //   builtins   — the 400+ built-in skill definitions
 //   vault      — SessionStore impl: credential CRUD, enabled state, custom instructions
 //   crypto     — OS-keychain key, XOR encrypt/decrypt
-//   status     — get_all_skill_status, get_skill_credentials
+//   status     — get_all_skill_status, get_skills_status_summary, get_skill_credentials
 //   prompt     — get_enabled_skill_instructions, inject_credentials_into_instructions
 //   community  — SKILL.md parser, GitHub fetcher, skills.sh search, DB CRUD
 //   toml       — pawz-skill.toml manifest subsystem (types, parser, scanner, installer)
@@ -15,6 +15,7 @@ mod builtins;
 pub mod community;
 pub(crate) mod crypto;
 mod prompt;
+mod sandbox;
 mod status;
 pub mod toml;
 pub(crate) mod types;
@@ -29,15 +30,21 @@ pub use community::{
     search_community_skills, search_pawzhub, CommunitySkill, DiscoveredSkill, PawzHubEntry,
 };
 pub use crypto::{decrypt_credential, encrypt_credential, get_vault_key};
-pub use prompt::get_enabled_skill_instructions;
-pub use status::{get_all_skill_status, get_skill_credentials};
+pub use prompt::{agent_skill_list, enabled_skill_ids, get_enabled_skill_instructions};
+pub use sandbox::{
+    active_skill_policies, check_tool_permitted, get_permitted_tools_override,
+    is_default_permitted, set_permitted_tools_override, SkillToolPolicy,
+};
+pub use status::{
+    get_all_skill_status, get_skill_credentials, get_skills_status_summary, skill_mcp_server_id,
+};
 pub use toml::{
-    install_toml_skill, parse_manifest, scan_toml_skills, uninstall_toml_skill, SkillManifest,
-    TomlSkillEntry,
+    fetch_remote_skill, install_toml_skill, parse_manifest, resolve_manifest_url, scan_toml_skills,
+    uninstall_toml_skill, write_bundled_files, RemoteSkillInstall, SkillManifest, TomlSkillEntry,
 };
 pub use types::{
-    CredentialField, SkillCategory, SkillDefinition, SkillRecord, SkillSource, SkillStatus,
-    SkillTier,
+    CredentialField, CredentialPresence, SkillCategory, SkillDefinition, SkillRecord, SkillSource,
+    SkillStatus, SkillStatusSummary, SkillTier,
 };
 
 // SkillTier is now defined in types.rs and re-exported above.
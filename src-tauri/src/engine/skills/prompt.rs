@@ -11,24 +11,59 @@ use crate::atoms::error::EngineResult;
 use crate::engine::sessions::SessionStore;
 use crate::engine::util::safe_truncate;
 
+/// The skills an agent's definition lists, or None when it lists none and
+/// the globally enabled skills apply.
+pub fn agent_skill_list(store: &SessionStore, agent_id: &str) -> Option<Vec<String>> {
+    store
+        .get_agent_definition(agent_id)
+        .ok()
+        .flatten()
+        .map(|a| a.skills)
+        .filter(|skills| !skills.is_empty())
+}
+
+/// Built-in skills whose tools this agent gets — the same choice as its
+/// prompt: the agent's own skill list, else the enabled skills.
+pub fn enabled_skill_ids(store: &SessionStore, agent_id: &str) -> Vec<String> {
+    let agent_skills = agent_skill_list(store, agent_id);
+    builtin_skills()
+        .into_iter()
+        .filter(|s| match &agent_skills {
+            Some(skills) => skills.contains(&s.id),
+            None => store
+                .get_skill_enabled_state(&s.id)
+                .unwrap_or(None)
+                .unwrap_or(s.default_enabled),
+        })
+        .map(|s| s.id)
+        .collect()
+}
+
 /// Collect agent instructions from all enabled skills.
 /// Returns a combined string to be injected into the system prompt.
 /// - Prefers custom instructions over defaults (if user edited them).
 /// - For skills with credentials, injects actual decrypted values into placeholders.
 /// - `agent_id` filters community skills to only those assigned to this agent.
+/// - An agent whose config lists skills gets exactly those built-in / TOML
+///   skills; otherwise the globally enabled ones.
 pub fn get_enabled_skill_instructions(
     store: &SessionStore,
     agent_id: &str,
 ) -> EngineResult<String> {
     let definitions = builtin_skills();
     let mut sections: Vec<String> = Vec::new();
+    let agent_skills = agent_skill_list(store, agent_id);
 
     // ── Built-in skills ────────────────────────────────────────────────
     for def in &definitions {
-        // Use explicit user choice if set, otherwise fall back to definition default
-        let enabled = store
-            .get_skill_enabled_state(&def.id)?
-            .unwrap_or(def.default_enabled);
+        // Agent's own skill list, else the explicit user choice, else the
+        // definition default
+        let enabled = match &agent_skills {
+            Some(skills) => skills.contains(&def.id),
+            None => store
+                .get_skill_enabled_state(&def.id)?
+                .unwrap_or(def.default_enabled),
+        };
         if !enabled {
             continue;
         }
@@ -74,7 +109,11 @@ pub fn get_enabled_skill_instructions(
         if builtin_ids.contains(def.id.as_str()) {
             continue;
         }
-        if !store.is_skill_enabled(&def.id).unwrap_or(false) {
+        let enabled = match &agent_skills {
+            Some(skills) => skills.contains(&def.id),
+            None => store.is_skill_enabled(&def.id).unwrap_or(false),
+        };
+        if !enabled {
             continue;
         }
 
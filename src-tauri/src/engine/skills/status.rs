@@ -1,11 +1,12 @@
 // Pawz Agent Engine — Skill Status
 // Aggregates builtin skill definitions with stored DB state.
 // Also merges TOML manifest skills from ~/.paw/skills/ (Phase F.1).
+// `get_skills_status_summary` adds community skills and MCP state on top.
 
 use super::builtins::builtin_skills;
 use super::crypto::{decrypt_credential, get_vault_key};
 use super::toml::scan_toml_skills;
-use super::types::{SkillSource, SkillStatus};
+use super::types::{CredentialPresence, SkillSource, SkillStatus, SkillStatusSummary};
 use crate::atoms::error::EngineResult;
 use crate::engine::sessions::SessionStore;
 use log::warn;
@@ -139,6 +140,77 @@ pub fn get_all_skill_status(store: &SessionStore) -> EngineResult<Vec<SkillStatu
     Ok(statuses)
 }
 
+/// MCP server id registered for a TOML skill's bundled `[mcp]` section.
+pub fn skill_mcp_server_id(skill_id: &str) -> String {
+    format!("skill-{}", skill_id)
+}
+
+/// Consolidated status of every skill (built-in, TOML and community).
+/// Credentials are reported as presence flags only — values are never read.
+/// `connected_mcp_ids` is the registry's current set of connected server ids.
+pub fn get_skills_status_summary(
+    store: &SessionStore,
+    connected_mcp_ids: &[String],
+) -> EngineResult<Vec<SkillStatusSummary>> {
+    let mut summaries: Vec<SkillStatusSummary> = get_all_skill_status(store)?
+        .into_iter()
+        .map(|s| {
+            let credentials: Vec<CredentialPresence> = s
+                .required_credentials
+                .iter()
+                .map(|c| CredentialPresence {
+                    key: c.key.clone(),
+                    label: c.label.clone(),
+                    required: c.required,
+                    configured: s.configured_credentials.contains(&c.key),
+                })
+                .collect();
+            let mcp_connected = s
+                .has_mcp
+                .then(|| connected_mcp_ids.contains(&skill_mcp_server_id(&s.id)));
+            SkillStatusSummary {
+                credentials_configured: s.missing_credentials.is_empty(),
+                has_custom_instructions: !s.custom_instructions.is_empty(),
+                id: s.id,
+                name: s.name,
+                icon: s.icon,
+                source: s.source,
+                enabled: s.enabled,
+                credentials,
+                has_mcp: s.has_mcp,
+                mcp_connected,
+                is_ready: s.is_ready,
+            }
+        })
+        .collect();
+
+    // ── Community skills (SKILL.md) — no credentials or MCP ────────────
+    let community = store.list_community_skills().unwrap_or_else(|e| {
+        warn!("[skills] Failed to list community skills: {}", e);
+        Vec::new()
+    });
+    for skill in community {
+        let has_custom_instructions = store
+            .get_skill_custom_instructions(&skill.id)?
+            .is_some_and(|c| !c.is_empty());
+        summaries.push(SkillStatusSummary {
+            id: skill.id,
+            name: skill.name,
+            icon: String::new(),
+            source: SkillSource::Community,
+            enabled: skill.enabled,
+            credentials: Vec::new(),
+            credentials_configured: true,
+            has_custom_instructions,
+            has_mcp: false,
+            mcp_connected: None,
+            is_ready: skill.enabled,
+        });
+    }
+
+    Ok(summaries)
+}
+
 /// Get credential values for a skill (decrypted). Used by tool executor at runtime.
 pub fn get_skill_credentials(
     store: &SessionStore,
@@ -161,5 +233,72 @@ pub fn get_skill_credentials(
         }
     }
 
+    // Remember decrypted secrets so tool output and logs can be scrubbed.
+    crate::engine::secret_scrub::register_credentials(&creds);
+
     Ok(creds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::sessions::schema::run_migrations;
+    use rusqlite::Connection;
+
+    fn test_store() -> SessionStore {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        let store = SessionStore::from_connection(conn);
+        store.init_community_skills_table().unwrap();
+        store
+    }
+
+    #[test]
+    fn configured_credential_is_reported_without_its_value() {
+        let store = test_store();
+        let def = builtin_skills()
+            .into_iter()
+            .find(|d| !d.required_credentials.is_empty())
+            .expect("a built-in skill with credentials");
+        let key = def.required_credentials[0].key.clone();
+        store
+            .set_skill_credential(&def.id, &key, "very-secret-value-123")
+            .unwrap();
+
+        let summaries = get_skills_status_summary(&store, &[]).unwrap();
+        let summary = summaries.iter().find(|s| s.id == def.id).unwrap();
+        let cred = summary.credentials.iter().find(|c| c.key == key).unwrap();
+        assert!(cred.configured);
+        assert_eq!(summary.source, SkillSource::Builtin);
+        assert_eq!(summary.mcp_connected, None);
+
+        let json = serde_json::to_string(&summaries).unwrap();
+        assert!(!json.contains("very-secret-value-123"));
+    }
+
+    #[test]
+    fn community_skills_are_included() {
+        let store = test_store();
+        store
+            .save_community_skill(&crate::engine::skills::CommunitySkill {
+                id: "owner/repo/demo".into(),
+                name: "Demo".into(),
+                description: String::new(),
+                instructions: "Do the demo thing.".into(),
+                source: "owner/repo".into(),
+                enabled: true,
+                agent_ids: vec![],
+                installed_at: String::new(),
+                updated_at: String::new(),
+            })
+            .unwrap();
+
+        let summaries = get_skills_status_summary(&store, &[]).unwrap();
+        let demo = summaries
+            .iter()
+            .find(|s| s.id == "owner/repo/demo")
+            .unwrap();
+        assert_eq!(demo.source, SkillSource::Community);
+        assert!(demo.is_ready);
+    }
+}
@@ -5,6 +5,7 @@
 
 use super::parser::{parse_manifest, validate_manifest};
 use super::scanner::skills_dir;
+use crate::engine::sessions::note_skills_changed;
 use std::path::PathBuf;
 
 /// Install a TOML skill by writing a manifest to `~/.paw/skills/{id}/pawz-skill.toml`.
@@ -46,6 +47,7 @@ pub fn install_toml_skill(skill_id: &str, toml_content: &str) -> Result<PathBuf,
         skill_id,
         manifest_path.display()
     );
+    note_skills_changed();
 
     Ok(manifest_path)
 }
@@ -87,5 +89,6 @@ pub fn uninstall_toml_skill(skill_id: &str) -> Result<(), String> {
         skill_id,
         skill_dir.display()
     );
+    note_skills_changed();
     Ok(())
 }
@@ -7,15 +7,24 @@
 //   parser    — parse_manifest, validate_manifest, manifest_to_definition
 //   scanner   — skills_dir, scan_toml_skills, load_manifest_from_path
 //   installer — install_toml_skill, uninstall_toml_skill
+//   remote    — install from URL / GitHub (fetch, size caps, bundled files)
 
 mod installer;
 mod parser;
+mod remote;
 mod scanner;
 pub(crate) mod types;
 
 // ── Re-exports (keep crate::engine::skills::toml::* API stable) ────────────
 
 pub use installer::{install_toml_skill, uninstall_toml_skill};
-pub use parser::{manifest_to_definition, parse_category, parse_manifest, validate_manifest};
+pub use parser::{
+    is_safe_relative_path, manifest_to_definition, parse_category, parse_manifest,
+    validate_manifest,
+};
+pub use remote::{
+    fetch_remote_skill, inspect_manifest, requires_confirmation, resolve_manifest_url,
+    write_bundled_files, FetchedSkill, RemoteSkillInstall,
+};
 pub use scanner::{load_manifest_from_path, scan_toml_skills, skills_dir};
 pub use types::{SkillManifest, TomlSkillEntry};
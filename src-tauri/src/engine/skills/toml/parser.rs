@@ -148,9 +148,56 @@ pub fn validate_manifest(manifest: &SkillManifest) -> Result<(), String> {
             }
         }
     }
+    if manifest.files.len() > MAX_BUNDLED_FILES {
+        return Err(format!(
+            "Too many bundled files ({}, max {})",
+            manifest.files.len(),
+            MAX_BUNDLED_FILES
+        ));
+    }
+    for file in &manifest.files {
+        if !is_safe_relative_path(file) {
+            return Err(format!(
+                "Bundled file path '{}' must be relative and stay inside the skill directory",
+                file
+            ));
+        }
+    }
+    if let Some(tools) = &manifest.permitted_tools {
+        for tool in tools {
+            let name = tool.strip_suffix('*').unwrap_or(tool);
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            {
+                return Err(format!(
+                    "Invalid permitted tool '{}' (use a tool name or a 'prefix_*' pattern)",
+                    tool
+                ));
+            }
+        }
+    }
     Ok(())
 }
 
+/// Maximum number of extra files a manifest may bundle.
+pub const MAX_BUNDLED_FILES: usize = 20;
+
+/// A bundled file path is safe when it is relative, uses `/` separators and
+/// has no `..`, empty or hidden-root components — i.e. it cannot escape the
+/// skill directory or overwrite the manifest.
+pub fn is_safe_relative_path(path: &str) -> bool {
+    if path.is_empty() || path.starts_with('/') || path.contains('\\') || path.contains(':') {
+        return false;
+    }
+    if path == "pawz-skill.toml" {
+        return false;
+    }
+    path.split('/')
+        .all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -419,6 +466,42 @@ description = "{}"
         assert!(result.unwrap_err().contains("too long"));
     }
 
+    #[test]
+    fn bundled_files_reject_traversal() {
+        for bad in [
+            "../evil.sh",
+            "/etc/passwd",
+            "a/../../b",
+            "C:\\x",
+            "pawz-skill.toml",
+            "",
+        ] {
+            assert!(!is_safe_relative_path(bad), "{bad} should be rejected");
+        }
+        assert!(is_safe_relative_path("scripts/run.sh"));
+
+        let toml_str = format!("files = [\"../escape.sh\"]\n{}", MINIMAL_MANIFEST);
+        let manifest = parse_manifest(&toml_str).unwrap();
+        assert!(validate_manifest(&manifest).is_err());
+    }
+
+    #[test]
+    fn permitted_tools_parse_and_validate() {
+        let ok = format!(
+            "permitted_tools = [\"fetch\", \"skill_store_*\"]\n{}",
+            MINIMAL_MANIFEST
+        );
+        let manifest = parse_manifest(&ok).unwrap();
+        assert!(validate_manifest(&manifest).is_ok());
+        assert_eq!(
+            manifest.permitted_tools,
+            Some(vec!["fetch".to_string(), "skill_store_*".to_string()])
+        );
+
+        let bad = format!("permitted_tools = [\"*\"]\n{}", MINIMAL_MANIFEST);
+        assert!(validate_manifest(&parse_manifest(&bad).unwrap()).is_err());
+    }
+
     #[test]
     fn invalid_toml_syntax() {
         let result = parse_manifest("this is not valid toml {{{}}}");
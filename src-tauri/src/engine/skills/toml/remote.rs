@@ -0,0 +1,389 @@
+// TOML Manifest — Remote install (URL / GitHub)
+//
+// Resolves a user-supplied source to a raw `pawz-skill.toml` URL, downloads
+// the manifest plus any files it bundles (size-capped, HTTPS only) and writes
+// the bundle into `~/.paw/skills/{id}/`. Manifest validation itself lives in
+// parser.rs; the network policy check is done by the caller.
+
+use super::parser::{is_safe_relative_path, parse_manifest, validate_manifest};
+use super::scanner::skills_dir;
+use super::types::SkillManifest;
+use crate::engine::skills::is_default_permitted;
+use serde::{Deserialize, Serialize};
+
+/// Largest manifest we will download.
+pub const MAX_MANIFEST_BYTES: usize = 64 * 1024;
+/// Largest single bundled file.
+pub const MAX_BUNDLED_FILE_BYTES: usize = 1024 * 1024;
+/// Cap on manifest + all bundled files together.
+pub const MAX_BUNDLE_BYTES: usize = 5 * 1024 * 1024;
+
+/// A downloaded, validated skill bundle ready to install.
+#[derive(Debug, Clone)]
+pub struct FetchedSkill {
+    pub manifest_url: String,
+    pub toml_content: String,
+    pub manifest: SkillManifest,
+    /// (relative path, contents) for every entry in `manifest.files`.
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+/// Result of a remote install request. When `requires_confirmation` is set
+/// and the caller did not confirm, nothing is written (`installed = false`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSkillInstall {
+    pub skill_id: String,
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    pub source_url: String,
+    /// Credential keys the skill will ask for.
+    pub credentials: Vec<String>,
+    /// MCP server the skill will launch/connect (command or URL).
+    pub mcp_server: Option<String>,
+    pub bundled_files: Vec<String>,
+    /// Built-in tools the skill asks to use (None = safe default subset).
+    pub permitted_tools: Option<Vec<String>>,
+    pub requires_confirmation: bool,
+    pub installed: bool,
+    pub path: Option<String>,
+}
+
+impl RemoteSkillInstall {
+    pub fn preview(fetched: &FetchedSkill) -> Self {
+        let m = &fetched.manifest;
+        let mcp_server = m.mcp.as_ref().map(|mcp| {
+            if mcp.url.is_empty() {
+                format!("{} {}", mcp.command, mcp.args.join(" "))
+                    .trim()
+                    .to_string()
+            } else {
+                mcp.url.clone()
+            }
+        });
+        RemoteSkillInstall {
+            skill_id: m.skill.id.clone(),
+            name: m.skill.name.clone(),
+            version: m.skill.version.clone(),
+            author: m.skill.author.clone(),
+            source_url: fetched.manifest_url.clone(),
+            credentials: m.credentials.iter().map(|c| c.key.clone()).collect(),
+            mcp_server,
+            bundled_files: m.files.clone(),
+            permitted_tools: m.permitted_tools.clone(),
+            requires_confirmation: requires_confirmation(m),
+            installed: false,
+            path: None,
+        }
+    }
+}
+
+/// Skills that ask for secrets, run an MCP server, or request tools beyond
+/// the sandbox's safe default need explicit consent.
+pub fn requires_confirmation(manifest: &SkillManifest) -> bool {
+    let elevated_tools = manifest.permitted_tools.as_ref().is_some_and(|tools| {
+        tools
+            .iter()
+            .any(|t| t.ends_with('*') || !is_default_permitted(t))
+    });
+    !manifest.credentials.is_empty() || manifest.mcp.is_some() || elevated_tools
+}
+
+// ── Source resolution ──────────────────────────────────────────────────────
+
+/// Turn a user-supplied source into a raw HTTPS manifest URL. Accepts:
+/// - `https://…/pawz-skill.toml` (any host, used as-is)
+/// - `https://github.com/owner/repo[/tree|blob/branch/path]`
+/// - `owner/repo[/path]` shorthand (GitHub, `main` branch)
+pub fn resolve_manifest_url(source: &str) -> Result<String, String> {
+    let source = source.trim().trim_end_matches('/');
+    if source.is_empty() {
+        return Err("Skill source is empty".into());
+    }
+    if source.starts_with("http://") {
+        return Err("Only HTTPS sources are allowed".into());
+    }
+
+    if let Some(rest) = source.strip_prefix("https://github.com/") {
+        return github_raw_url(rest);
+    }
+    if source.starts_with("https://") {
+        if !source.ends_with(".toml") {
+            return Err("URL must point to a pawz-skill.toml manifest".into());
+        }
+        return Ok(source.to_string());
+    }
+    if source.contains("://") {
+        return Err(format!("Unsupported source '{}'", source));
+    }
+    // owner/repo[/path] shorthand
+    let mut parts = source.splitn(3, '/');
+    let (owner, repo) = match (parts.next(), parts.next()) {
+        (Some(o), Some(r)) if !o.is_empty() && !r.is_empty() => (o, r),
+        _ => return Err("Expected a URL or 'owner/repo[/path]'".into()),
+    };
+    let path = parts.next().unwrap_or("");
+    Ok(raw_github(owner, repo, "main", path))
+}
+
+/// `owner/repo[/tree|blob/branch/path]` → raw.githubusercontent.com URL.
+fn github_raw_url(rest: &str) -> Result<String, String> {
+    let segs: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+    if segs.len() < 2 {
+        return Err("GitHub URL must include owner and repo".into());
+    }
+    let (owner, repo) = (segs[0], segs[1].trim_end_matches(".git"));
+    if segs.len() >= 4 && (segs[2] == "tree" || segs[2] == "blob") {
+        let branch = segs[3];
+        let path = segs[4..].join("/");
+        if path.ends_with(".toml") {
+            return Ok(format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                owner, repo, branch, path
+            ));
+        }
+        return Ok(raw_github(owner, repo, branch, &path));
+    }
+    Ok(raw_github(owner, repo, "main", ""))
+}
+
+fn raw_github(owner: &str, repo: &str, branch: &str, dir: &str) -> String {
+    let dir = dir.trim_matches('/');
+    if dir.is_empty() {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/pawz-skill.toml",
+            owner, repo, branch
+        )
+    } else {
+        format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}/pawz-skill.toml",
+            owner, repo, branch, dir
+        )
+    }
+}
+
+// ── Validation ─────────────────────────────────────────────────────────────
+
+/// Check downloaded manifest bytes: size, UTF-8, TOML syntax, and schema.
+pub fn inspect_manifest(bytes: &[u8]) -> Result<(String, SkillManifest), String> {
+    if bytes.len() > MAX_MANIFEST_BYTES {
+        return Err(format!(
+            "Manifest too large ({} bytes, max {})",
+            bytes.len(),
+            MAX_MANIFEST_BYTES
+        ));
+    }
+    let content =
+        String::from_utf8(bytes.to_vec()).map_err(|_| "Manifest is not valid UTF-8".to_string())?;
+    let manifest = parse_manifest(&content)?;
+    validate_manifest(&manifest)?;
+    Ok((content, manifest))
+}
+
+// ── Download ───────────────────────────────────────────────────────────────
+
+/// Download and validate a manifest and its bundled files.
+pub async fn fetch_remote_skill(manifest_url: &str) -> Result<FetchedSkill, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let bytes = fetch_limited(&client, manifest_url, MAX_MANIFEST_BYTES).await?;
+    let (toml_content, manifest) = inspect_manifest(&bytes)?;
+
+    let base = manifest_url
+        .rsplit_once('/')
+        .map(|(b, _)| b)
+        .unwrap_or(manifest_url);
+    let mut total = bytes.len();
+    let mut files = Vec::with_capacity(manifest.files.len());
+    for rel in &manifest.files {
+        let url = format!("{}/{}", base, rel);
+        let data = fetch_limited(&client, &url, MAX_BUNDLED_FILE_BYTES).await?;
+        total += data.len();
+        if total > MAX_BUNDLE_BYTES {
+            return Err(format!(
+                "Skill bundle exceeds {} bytes — aborting",
+                MAX_BUNDLE_BYTES
+            ));
+        }
+        files.push((rel.clone(), data));
+    }
+
+    log::info!(
+        "[toml-loader] Fetched skill '{}' from {} ({} bundled files, {} bytes)",
+        manifest.skill.id,
+        manifest_url,
+        files.len(),
+        total
+    );
+
+    Ok(FetchedSkill {
+        manifest_url: manifest_url.to_string(),
+        toml_content,
+        manifest,
+        files,
+    })
+}
+
+/// GET a URL over HTTPS, aborting as soon as the body exceeds `max_bytes`.
+async fn fetch_limited(
+    client: &reqwest::Client,
+    url: &str,
+    max_bytes: usize,
+) -> Result<Vec<u8>, String> {
+    if !url.starts_with("https://") {
+        return Err(format!("Refusing non-HTTPS download: {}", url));
+    }
+    let mut resp = client
+        .get(url)
+        .header("User-Agent", "Pawz/1.0")
+        .send()
+        .await
+        .map_err(|e| format!("Download failed for {}: {}", url, e))?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "Download of {} returned HTTP {}",
+            url,
+            resp.status()
+        ));
+    }
+    if resp.content_length().unwrap_or(0) as usize > max_bytes {
+        return Err(format!("{} is larger than {} bytes", url, max_bytes));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Download failed for {}: {}", url, e))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() > max_bytes {
+            return Err(format!("{} is larger than {} bytes", url, max_bytes));
+        }
+    }
+    Ok(body)
+}
+
+// ── Install ────────────────────────────────────────────────────────────────
+
+/// Write bundled files into `~/.paw/skills/{id}/`. Paths are re-checked here
+/// and the final location must resolve inside the skill directory.
+pub fn write_bundled_files(skill_id: &str, files: &[(String, Vec<u8>)]) -> Result<(), String> {
+    let dir = skills_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+    let skill_dir = dir.join(skill_id);
+    std::fs::create_dir_all(&skill_dir)
+        .map_err(|e| format!("Failed to create directory {}: {}", skill_dir.display(), e))?;
+    let canonical_base = skill_dir
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+
+    for (rel, data) in files {
+        if !is_safe_relative_path(rel) {
+            return Err(format!("Unsafe bundled file path '{}'", rel));
+        }
+        let target = skill_dir.join(rel);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+            let canonical_parent = parent
+                .canonicalize()
+                .map_err(|e| format!("Failed to resolve path: {}", e))?;
+            if !canonical_parent.starts_with(&canonical_base) {
+                return Err("Path traversal detected — aborting".to_string());
+            }
+        }
+        std::fs::write(&target, data)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    }
+    Ok(())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"
+files = ["scripts/run.sh"]
+
+[skill]
+id = "weather-plus"
+name = "Weather Plus"
+version = "1.2.0"
+author = "someone"
+category = "api"
+description = "Extended forecasts"
+
+[[credentials]]
+key = "WEATHER_KEY"
+label = "API Key"
+required = true
+
+[instructions]
+text = "Use the forecast API."
+"#;
+
+    #[test]
+    fn fetched_manifest_is_validated() {
+        let (content, manifest) = inspect_manifest(VALID.as_bytes()).unwrap();
+        assert_eq!(content, VALID);
+        assert_eq!(manifest.skill.id, "weather-plus");
+        assert_eq!(manifest.files, vec!["scripts/run.sh".to_string()]);
+        assert!(requires_confirmation(&manifest));
+    }
+
+    #[test]
+    fn requesting_exec_requires_confirmation() {
+        let no_creds = VALID.replace(
+            "[[credentials]]\nkey = \"WEATHER_KEY\"\nlabel = \"API Key\"\nrequired = true\n",
+            "",
+        );
+        let (_, safe) = inspect_manifest(no_creds.as_bytes()).unwrap();
+        assert!(!requires_confirmation(&safe));
+
+        let with_exec = format!("permitted_tools = [\"exec\"]\n{}", no_creds);
+        let (_, elevated) = inspect_manifest(with_exec.as_bytes()).unwrap();
+        assert!(requires_confirmation(&elevated));
+    }
+
+    #[test]
+    fn invalid_fetched_manifest_is_rejected() {
+        // Missing required author/description
+        let missing = "[skill]\nid = \"x\"\nname = \"X\"\nversion = \"1\"\ncategory = \"api\"\n";
+        assert!(inspect_manifest(missing.as_bytes()).is_err());
+
+        // Traversal in bundled files
+        let traversal = VALID.replace("scripts/run.sh", "../../.bashrc");
+        assert!(inspect_manifest(traversal.as_bytes()).is_err());
+
+        // Oversized
+        let huge = vec![b'#'; MAX_MANIFEST_BYTES + 1];
+        assert!(inspect_manifest(&huge).unwrap_err().contains("too large"));
+
+        // Not UTF-8
+        assert!(inspect_manifest(&[0xff, 0xfe, 0x00]).is_err());
+    }
+
+    #[test]
+    fn resolves_sources_to_raw_manifest_urls() {
+        assert_eq!(
+            resolve_manifest_url("acme/skills/weather").unwrap(),
+            "https://raw.githubusercontent.com/acme/skills/main/weather/pawz-skill.toml"
+        );
+        assert_eq!(
+            resolve_manifest_url("https://github.com/acme/skills/tree/dev/weather").unwrap(),
+            "https://raw.githubusercontent.com/acme/skills/dev/weather/pawz-skill.toml"
+        );
+        assert_eq!(
+            resolve_manifest_url("https://example.com/s/pawz-skill.toml").unwrap(),
+            "https://example.com/s/pawz-skill.toml"
+        );
+        assert!(resolve_manifest_url("http://example.com/pawz-skill.toml").is_err());
+        assert!(resolve_manifest_url("https://example.com/page.html").is_err());
+        assert!(resolve_manifest_url("justaname").is_err());
+    }
+}
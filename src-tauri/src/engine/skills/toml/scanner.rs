@@ -116,6 +116,7 @@ pub fn load_manifest_from_path(path: &Path) -> Result<TomlSkillEntry, String> {
             .as_ref()
             .map(|v| v.icon.clone())
             .unwrap_or_default(),
+        permitted_tools: manifest.permitted_tools,
     })
 }
 
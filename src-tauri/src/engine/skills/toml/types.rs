@@ -11,6 +11,14 @@ use serde::Deserialize;
 /// Root of a `pawz-skill.toml` manifest file.
 #[derive(Debug, Clone, Deserialize)]
 pub struct SkillManifest {
+    /// Extra files bundled next to the manifest (relative paths), downloaded
+    /// alongside it on remote install. Must come before any `[table]`.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Built-in tools this skill's instructions may drive (e.g. `["fetch",
+    /// "skill_store_*"]`). Absent = the safe default subset; see skills::sandbox.
+    #[serde(default)]
+    pub permitted_tools: Option<Vec<String>>,
     pub skill: SkillMeta,
     #[serde(default)]
     pub credentials: Vec<ManifestCredential>,
@@ -145,4 +153,7 @@ pub struct TomlSkillEntry {
     /// View icon for sidebar tab (if has_view is true).
     #[serde(default)]
     pub view_icon: String,
+    /// Tools declared in `permitted_tools` (None = safe default subset).
+    #[serde(default)]
+    pub permitted_tools: Option<Vec<String>>,
 }
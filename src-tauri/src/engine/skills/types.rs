@@ -97,6 +97,8 @@ pub enum SkillSource {
     Builtin,
     /// Loaded from `~/.paw/skills/*/pawz-skill.toml`.
     Toml,
+    /// Installed SKILL.md from a community repository (skills.sh / GitHub).
+    Community,
 }
 
 /// Skill status for the frontend — combines definition + stored state.
@@ -145,6 +147,35 @@ pub struct SkillStatus {
     pub default_enabled: bool,
 }
 
+/// Whether one declared credential has a stored value — never the value itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialPresence {
+    pub key: String,
+    pub label: String,
+    pub required: bool,
+    pub configured: bool,
+}
+
+/// Consolidated per-skill status for the settings page, across built-in,
+/// TOML and community skills. Credentials are reduced to presence flags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillStatusSummary {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+    pub source: SkillSource,
+    pub enabled: bool,
+    pub credentials: Vec<CredentialPresence>,
+    /// All required credentials are configured.
+    pub credentials_configured: bool,
+    /// The user has overridden the default agent instructions.
+    pub has_custom_instructions: bool,
+    pub has_mcp: bool,
+    /// Live MCP connection state — `None` when the skill bundles no MCP server.
+    pub mcp_connected: Option<bool>,
+    pub is_ready: bool,
+}
+
 fn default_source() -> SkillSource {
     SkillSource::Builtin
 }
@@ -21,8 +21,7 @@ pub(crate) async fn rpc_call(
         "params": params
     });
 
-    let resp = client
-        .post(rpc_url)
+    let resp = crate::engine::net::apply(client.post(rpc_url), rpc_url)
         .json(&body)
         .timeout(Duration::from_secs(30))
         .send()
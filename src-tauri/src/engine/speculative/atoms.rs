@@ -410,6 +410,11 @@ mod tests {
             "skill_output",
             "skill_store_get",
             "skill_store_list",
+            "skill_state_get",
+            "note_read",
+            "note_list",
+            "task_list",
+            "reminder_list",
             "email_read",
             "slack_read",
             "telegram_read",
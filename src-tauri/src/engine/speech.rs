@@ -0,0 +1,351 @@
+// Paw Agent Engine — Speech synthesis for agents
+//
+// Backs the `speak` tool: text goes to ElevenLabs text-to-speech (the
+// endpoint already on the default network allowlist) and the returned MP3 is
+// written to the agent's workspace under `speech/`, where the chat's audio
+// player reads it from. The API key is the ElevenLabs TTS skill credential.
+//
+// One call is one request, so text is capped at `max_chars` instead of being
+// chunked — an agent reading a long document aloud should summarize first.
+//
+// The other direction lives here too: `transcribe` posts recorded audio to an
+// OpenAI Whisper-compatible `/audio/transcriptions` endpoint.
+
+use log::info;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Workspace subdirectory spoken audio is saved in.
+pub const SPEECH_DIR: &str = "speech";
+
+pub const ELEVENLABS_BASE_URL: &str = "https://api.elevenlabs.io/v1";
+
+/// ElevenLabs' "Rachel" premade voice, available on every account.
+pub const DEFAULT_VOICE_ID: &str = "21m00Tcm4TlvDq8ikWAM";
+
+pub const DEFAULT_MODEL: &str = "eleven_multilingual_v2";
+
+/// Longest text one `speak` call accepts unless TTS_MAX_CHARS says
+/// otherwise. ElevenLabs itself stops at 5,000 characters per request.
+pub const DEFAULT_MAX_CHARS: usize = 2500;
+
+/// Settings for one synthesis call, read from the skill credentials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechConfig {
+    pub base_url: String,
+    pub api_key: String,
+    pub voice_id: String,
+    pub model: String,
+    pub max_chars: usize,
+}
+
+impl SpeechConfig {
+    pub fn from_creds(creds: &HashMap<String, String>) -> Result<Self, String> {
+        let get = |key: &str| {
+            creds
+                .get(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Ok(SpeechConfig {
+            base_url: ELEVENLABS_BASE_URL.into(),
+            api_key: get("ELEVENLABS_API_KEY").ok_or("Missing ELEVENLABS_API_KEY credential")?,
+            voice_id: get("ELEVENLABS_VOICE_ID").unwrap_or_else(|| DEFAULT_VOICE_ID.into()),
+            model: get("ELEVENLABS_MODEL").unwrap_or_else(|| DEFAULT_MODEL.into()),
+            max_chars: get("TTS_MAX_CHARS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_CHARS),
+        })
+    }
+
+    /// URL the request for `voice_id` is posted to.
+    pub fn endpoint(&self, voice_id: &str) -> String {
+        format!(
+            "{}/text-to-speech/{}",
+            self.base_url.trim_end_matches('/'),
+            voice_id
+        )
+    }
+}
+
+/// Reject empty text or text over the cap. Returns the character count.
+pub fn check_text(text: &str, max_chars: usize) -> Result<usize, String> {
+    let chars = text.chars().count();
+    if text.trim().is_empty() {
+        return Err("speak: no text to speak".into());
+    }
+    if chars > max_chars {
+        return Err(format!(
+            "speak: text is {} characters, over the {}-character limit. Summarize it or speak it in shorter parts.",
+            chars, max_chars
+        ));
+    }
+    Ok(chars)
+}
+
+/// Synthesize `text` and return the MP3 bytes.
+pub async fn synthesize(
+    client: &reqwest::Client,
+    config: &SpeechConfig,
+    voice_id: &str,
+    text: &str,
+    voice_settings: Option<Value>,
+) -> Result<Vec<u8>, String> {
+    let mut body = serde_json::json!({ "text": text, "model_id": config.model });
+    if let Some(settings) = voice_settings {
+        body["voice_settings"] = settings;
+    }
+    let resp = client
+        .post(config.endpoint(voice_id))
+        .header("xi-api-key", &config.api_key)
+        .header("Accept", "audio/mpeg")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("ElevenLabs TTS request failed: {}", e))?;
+
+    let status = resp.status().as_u16();
+    if status >= 400 {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(error_message(status, &body));
+    }
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| format!("ElevenLabs TTS read error: {}", e))?;
+    if bytes.is_empty() {
+        return Err("ElevenLabs returned no audio".into());
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Explain an ElevenLabs error. Quota and key problems get a message the
+/// user can act on; anything else passes the provider's detail through.
+fn error_message(status: u16, body: &str) -> String {
+    let json: Value = serde_json::from_str(body).unwrap_or_default();
+    let detail = &json["detail"];
+    let code = detail["status"].as_str().unwrap_or_default();
+    let message = detail["message"]
+        .as_str()
+        .or_else(|| detail.as_str())
+        .unwrap_or(body);
+    let message = crate::engine::util::safe_truncate(message, 300);
+    match (status, code) {
+        (_, "quota_exceeded") => format!(
+            "ElevenLabs character quota exceeded — the account has no characters left this period ({}).",
+            message
+        ),
+        (401, _) | (_, "invalid_api_key") => {
+            "ElevenLabs rejected the API key — check ELEVENLABS_API_KEY in the ElevenLabs TTS skill."
+                .into()
+        }
+        (404, _) | (_, "voice_not_found") => format!("ElevenLabs voice not found ({}).", message),
+        (429, _) => format!(
+            "ElevenLabs is rate limiting requests — try again shortly ({}).",
+            message
+        ),
+        _ => format!("ElevenLabs TTS error (HTTP {}): {}", status, message),
+    }
+}
+
+/// An OpenAI Whisper-compatible speech-to-text endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SttEndpoint {
+    pub base_url: String,
+    /// Self-hosted servers often need none.
+    pub api_key: Option<String>,
+    pub model: String,
+}
+
+pub const DEFAULT_STT_MODEL: &str = "whisper-1";
+
+/// File extension Whisper expects for a recorded-audio MIME type (codec
+/// parameters ignored). `None` for formats it doesn't accept.
+pub fn audio_extension(mime_type: &str) -> Option<&'static str> {
+    let base = mime_type.split(';').next().unwrap_or_default().trim();
+    match base.to_ascii_lowercase().as_str() {
+        "audio/webm" => Some("webm"),
+        "audio/ogg" => Some("ogg"),
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => Some("mp4"),
+        "audio/wav" | "audio/wave" | "audio/x-wav" => Some("wav"),
+        "audio/mpeg" | "audio/mp3" => Some("mp3"),
+        _ => None,
+    }
+}
+
+/// Transcribe recorded audio. `language` is an ISO-639-1 hint; `None` lets
+/// the model detect it.
+pub async fn transcribe(
+    client: &reqwest::Client,
+    endpoint: &SttEndpoint,
+    audio: Vec<u8>,
+    mime_type: &str,
+    language: Option<&str>,
+) -> Result<String, String> {
+    let ext = audio_extension(mime_type)
+        .ok_or_else(|| format!("Unsupported audio format: {}", mime_type))?;
+    let file_part = reqwest::multipart::Part::bytes(audio)
+        .file_name(format!("audio.{}", ext))
+        .mime_str(mime_type)
+        .map_err(|e| format!("MIME error: {}", e))?;
+
+    let mut form = reqwest::multipart::Form::new().text("model", endpoint.model.clone());
+    if let Some(lang) = language {
+        form = form.text("language", lang.to_string());
+    }
+    let form = form.part("file", file_part);
+
+    let mut req = client.post(format!(
+        "{}/audio/transcriptions",
+        endpoint.base_url.trim_end_matches('/')
+    ));
+    if let Some(key) = &endpoint.api_key {
+        req = req.header("Authorization", format!("Bearer {}", key));
+    }
+    let resp = req
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| format!("Whisper API request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!(
+            "Whisper API error ({}): {}",
+            status,
+            crate::engine::util::safe_truncate(&body, 300)
+        ));
+    }
+
+    let result: Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Whisper API JSON parse error: {}", e))?;
+
+    result["text"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| "Whisper API: no text in response".into())
+}
+
+/// Write the audio into `workspace/speech/` under a fresh timestamped name.
+pub fn save_audio(workspace: &Path, text: &str, audio: &[u8]) -> std::io::Result<PathBuf> {
+    let dir = workspace.join(SPEECH_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let slug: String = text
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .take(24)
+        .collect::<String>()
+        .trim_matches('_')
+        .to_lowercase();
+    let stamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let mut path = dir.join(format!("speech_{}_{}.mp3", stamp, slug));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("speech_{}_{}_{}.mp3", stamp, slug, n));
+    }
+    std::fs::write(&path, audio)?;
+    info!("[speech] Saved {} bytes to {}", audio.len(), path.display());
+    Ok(path)
+}
+
+/// Whether `path` is spoken audio inside some agent's workspace — the
+/// chat's audio player serves nothing else.
+pub fn is_generated_audio(path: &Path) -> bool {
+    crate::engine::tools::is_workspace_output(path, SPEECH_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{self, MockReply};
+
+    /// Answers one request with `status`, `content_type` and `body`,
+    /// returning the raw request it received.
+    async fn mock_endpoint(
+        status: u16,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+        let (url, handle) =
+            test_support::serve_once(MockReply::new(status, content_type, body)).await;
+        (format!("{}/v1", url), handle)
+    }
+
+    fn config(base_url: String) -> SpeechConfig {
+        SpeechConfig {
+            base_url,
+            api_key: "xi-test".into(),
+            voice_id: DEFAULT_VOICE_ID.into(),
+            model: DEFAULT_MODEL.into(),
+            max_chars: DEFAULT_MAX_CHARS,
+        }
+    }
+
+    #[tokio::test]
+    async fn audio_from_endpoint_is_written_to_the_workspace() {
+        // An MP3 frame header followed by filler
+        let mut audio = vec![0xFF, 0xFB, 0x90, 0x64];
+        audio.extend([0xAA; 400]);
+        let (base_url, server) = mock_endpoint(200, "audio/mpeg", audio.clone()).await;
+        let config = config(base_url);
+
+        let bytes = synthesize(
+            &reqwest::Client::new(),
+            &config,
+            "voice123",
+            "Hello there!",
+            None,
+        )
+        .await
+        .unwrap();
+        let received = String::from_utf8_lossy(&server.await.unwrap()).to_string();
+        assert!(received.starts_with("POST /v1/text-to-speech/voice123"));
+        assert!(received.to_lowercase().contains("xi-api-key: xi-test"));
+        assert!(received.contains(r#""text":"Hello there!""#));
+        assert_eq!(bytes, audio);
+
+        let workspace =
+            std::env::temp_dir().join(format!("paw-speech-test-{}", uuid::Uuid::new_v4()));
+        let path = save_audio(&workspace, "Hello there!", &bytes).unwrap();
+        assert_eq!(path.parent().unwrap(), workspace.join(SPEECH_DIR));
+        assert!(path.to_string_lossy().ends_with("_hello_there.mp3"));
+        assert_eq!(std::fs::read(&path).unwrap(), audio);
+        std::fs::remove_dir_all(&workspace).ok();
+    }
+
+    #[tokio::test]
+    async fn exhausted_quota_is_reported_plainly() {
+        let body = serde_json::json!({
+            "detail": {
+                "status": "quota_exceeded",
+                "message": "This request exceeds your quota. You have 12 credits remaining."
+            }
+        })
+        .to_string();
+        let (base_url, _server) = mock_endpoint(401, "application/json", body.into_bytes()).await;
+        let err = synthesize(
+            &reqwest::Client::new(),
+            &config(base_url),
+            DEFAULT_VOICE_ID,
+            "Hi",
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("quota exceeded"), "{}", err);
+        assert!(err.contains("12 credits"), "{}", err);
+    }
+
+    #[test]
+    fn text_over_the_cap_is_refused() {
+        assert_eq!(check_text("Hello", 10), Ok(5));
+        assert!(check_text("   ", 10).is_err());
+        let err = check_text(&"a".repeat(11), 10).unwrap_err();
+        assert!(err.contains("10-character limit"), "{}", err);
+    }
+}
@@ -4,17 +4,21 @@
 
 use crate::engine::engram::CognitiveState;
 use crate::engine::memory::EmbeddingClient;
-use crate::engine::sessions::SessionStore;
+use crate::engine::sessions::{DailySpend, SessionStore};
 use crate::engine::speculative::{SpeculationConfig, SpeculativeCache};
 use crate::engine::tool_index::ToolIndex;
 use crate::engine::tool_registry::PersistentToolRegistry;
 use crate::engine::types::*;
+use openpawz_core::engine::budget_window::BudgetWindow;
 
 use crate::engine::mcp::McpRegistry;
 
 use crate::atoms::engram_types::EngramConfig;
 use crate::atoms::error::EngineResult;
 use log::{info, warn};
+use openpawz_core::engine::cancel::CancelToken;
+use openpawz_core::engine::run_limiter::RunLimiter;
+use openpawz_core::engine::tool_cache::{self, ToolResultCache};
 use parking_lot::Mutex;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -27,11 +31,14 @@ use std::sync::Arc;
 pub type PendingApprovals = Arc<Mutex<HashMap<String, tokio::sync::oneshot::Sender<bool>>>>;
 
 /// Daily token spend tracker.  Tracks cumulative input & output tokens
-/// for the current UTC date.  Resets automatically on new day.
+/// for the current budget window (a local day in the user's timezone, see
+/// `BudgetWindow`).  Resets automatically when the window ends.
 /// All fields are atomic so the tracker can be shared across tasks cheaply.
 pub struct DailyTokenTracker {
-    /// UTC date string "YYYY-MM-DD" of the current tracking day
-    pub date: Mutex<String>,
+    /// When the window resets (timezone + local hour)
+    pub window: Mutex<BudgetWindow>,
+    /// Start of the current window, RFC 3339 UTC — the key spend is persisted under
+    pub window_start: Mutex<String>,
     /// Cumulative input tokens today
     pub input_tokens: AtomicU64,
     /// Cumulative output tokens today
@@ -46,6 +53,8 @@ pub struct DailyTokenTracker {
     pub last_model: Mutex<String>,
     /// Budget warning thresholds already emitted (50, 75, 90)
     pub warnings_emitted: Mutex<Vec<u8>>,
+    /// Accumulated USD cost today per agent_id (micro-dollars)
+    pub agent_cost_microdollars: Mutex<HashMap<String, u64>>,
 }
 
 impl Default for DailyTokenTracker {
@@ -56,8 +65,13 @@ impl Default for DailyTokenTracker {
 
 impl DailyTokenTracker {
     pub fn new() -> Self {
+        Self::with_window(BudgetWindow::default())
+    }
+
+    pub fn with_window(window: BudgetWindow) -> Self {
         DailyTokenTracker {
-            date: Mutex::new(chrono::Utc::now().format("%Y-%m-%d").to_string()),
+            window_start: Mutex::new(window.key(chrono::Utc::now())),
+            window: Mutex::new(window),
             input_tokens: AtomicU64::new(0),
             output_tokens: AtomicU64::new(0),
             cache_read_tokens: AtomicU64::new(0),
@@ -65,25 +79,76 @@ impl DailyTokenTracker {
             cost_microdollars: AtomicU64::new(0),
             last_model: Mutex::new("unknown".into()),
             warnings_emitted: Mutex::new(Vec::new()),
+            agent_cost_microdollars: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Seed the current window's counters from the persisted per-agent rows
+    /// so budgets survive a restart. Rows from an elapsed window — the app
+    /// was closed across the reset — are ignored.
+    pub fn restore(&self, rows: &[DailySpend]) {
+        let current = self.window_start();
+        let mut agents = self.agent_cost_microdollars.lock();
+        for row in rows.iter().filter(|r| r.window_start == current) {
+            let micro = (row.cost_usd * 1_000_000.0) as u64;
+            self.input_tokens
+                .fetch_add(row.input_tokens, Ordering::Relaxed);
+            self.output_tokens
+                .fetch_add(row.output_tokens, Ordering::Relaxed);
+            self.cache_read_tokens
+                .fetch_add(row.cache_read_tokens, Ordering::Relaxed);
+            self.cache_create_tokens
+                .fetch_add(row.cache_create_tokens, Ordering::Relaxed);
+            self.cost_microdollars.fetch_add(micro, Ordering::Relaxed);
+            *agents.entry(row.agent_id.clone()).or_insert(0) += micro;
         }
     }
 
+    /// Change the reset schedule. Counters are cleared; callers restore the
+    /// new window's persisted spend afterwards.
+    pub fn set_window(&self, window: BudgetWindow) {
+        let key = window.key(chrono::Utc::now());
+        *self.window.lock() = window;
+        *self.window_start.lock() = key;
+        self.clear();
+    }
+
+    /// Start of the current window (RFC 3339 UTC).
+    pub fn window_start(&self) -> String {
+        self.maybe_reset();
+        self.window_start.lock().clone()
+    }
+
     fn maybe_reset(&self) {
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let mut d = self.date.lock();
-        if *d != today {
-            *d = today;
-            self.input_tokens.store(0, Ordering::Relaxed);
-            self.output_tokens.store(0, Ordering::Relaxed);
-            self.cache_read_tokens.store(0, Ordering::Relaxed);
-            self.cache_create_tokens.store(0, Ordering::Relaxed);
-            self.cost_microdollars.store(0, Ordering::Relaxed);
-            self.warnings_emitted.lock().clear();
+        let current = self.window.lock().key(chrono::Utc::now());
+        let mut start = self.window_start.lock();
+        if *start != current {
+            *start = current;
+            self.clear();
         }
     }
 
-    /// Add tokens from a completed round with model-aware pricing.
-    pub fn record(&self, model: &str, input: u64, output: u64, cache_read: u64, cache_create: u64) {
+    fn clear(&self) {
+        self.input_tokens.store(0, Ordering::Relaxed);
+        self.output_tokens.store(0, Ordering::Relaxed);
+        self.cache_read_tokens.store(0, Ordering::Relaxed);
+        self.cache_create_tokens.store(0, Ordering::Relaxed);
+        self.cost_microdollars.store(0, Ordering::Relaxed);
+        self.warnings_emitted.lock().clear();
+        self.agent_cost_microdollars.lock().clear();
+    }
+
+    /// Add tokens from a completed round with model-aware pricing, charged
+    /// to `agent_id` as well as the global total. Returns the round's cost.
+    pub fn record(
+        &self,
+        agent_id: &str,
+        model: &str,
+        input: u64,
+        output: u64,
+        cache_read: u64,
+        cache_create: u64,
+    ) -> f64 {
         self.maybe_reset();
         self.input_tokens.fetch_add(input, Ordering::Relaxed);
         self.output_tokens.fetch_add(output, Ordering::Relaxed);
@@ -96,7 +161,13 @@ impl DailyTokenTracker {
             crate::engine::types::estimate_cost_usd(model, input, output, cache_read, cache_create);
         let micro = (cost * 1_000_000.0) as u64;
         self.cost_microdollars.fetch_add(micro, Ordering::Relaxed);
+        *self
+            .agent_cost_microdollars
+            .lock()
+            .entry(agent_id.to_string())
+            .or_insert(0) += micro;
         *self.last_model.lock() = model.to_string();
+        cost
     }
 
     /// Estimate today's USD spend using accumulated per-model costs.
@@ -119,6 +190,39 @@ impl DailyTokenTracker {
         }
     }
 
+    /// Today's USD spend for one agent.
+    pub fn agent_spend_usd(&self, agent_id: &str) -> f64 {
+        self.maybe_reset();
+        let micro = self
+            .agent_cost_microdollars
+            .lock()
+            .get(agent_id)
+            .copied()
+            .unwrap_or(0);
+        micro as f64 / 1_000_000.0
+    }
+
+    /// Today's USD spend for every agent that has run.
+    pub fn agent_spend(&self) -> HashMap<String, f64> {
+        self.maybe_reset();
+        self.agent_cost_microdollars
+            .lock()
+            .iter()
+            .map(|(id, micro)| (id.clone(), *micro as f64 / 1_000_000.0))
+            .collect()
+    }
+
+    /// Check if an agent's spend today exceeds its own budget.
+    /// Returns Some(spend_usd) if over budget.
+    pub fn check_agent_budget(&self, agent_id: &str, budget_usd: f64) -> Option<f64> {
+        let usd = self.agent_spend_usd(agent_id);
+        if usd >= budget_usd {
+            Some(usd)
+        } else {
+            None
+        }
+    }
+
     /// Check budget warning thresholds (50%, 75%, 90%).
     /// Returns the threshold percentage if a NEW warning should be emitted.
     pub fn check_budget_warning(&self, budget_usd: f64) -> Option<u8> {
@@ -191,6 +295,9 @@ pub type RequestQueue = Arc<Mutex<HashMap<String, Vec<QueuedRequest>>>>;
 /// When a steering request is queued, yield is requested on the active run.
 pub type YieldSignals = Arc<Mutex<HashMap<String, YieldSignal>>>;
 
+/// Per-session cancellation tokens for in-flight agent runs.
+pub type CancelTokens = Arc<Mutex<HashMap<String, CancelToken>>>;
+
 /// Map retired / renamed / shorthand model IDs to their current API names.
 /// This lets old task configs, agent overrides, and user-entered short names keep working.
 pub fn normalize_model_name(model: &str) -> &str {
@@ -320,9 +427,8 @@ pub struct EngineState {
     pub config: Mutex<EngineConfig>,
     pub memory_config: Mutex<MemoryConfig>,
     pub pending_approvals: PendingApprovals,
-    /// Semaphore limiting concurrent agent runs (chat + cron + manual tasks).
-    /// Chat gets a reserved slot; background tasks share the rest.
-    pub run_semaphore: Arc<tokio::sync::Semaphore>,
+    /// Caps concurrent agent runs (chat + cron + manual tasks + swarm).
+    pub run_limiter: Arc<RunLimiter>,
     /// Track task IDs currently being executed to prevent duplicate cron fires.
     pub inflight_tasks: Arc<Mutex<HashSet<String>>>,
     /// Daily token spend tracker — shared across all agent runs.
@@ -340,6 +446,8 @@ pub struct EngineState {
     pub speculation_cache: Arc<Mutex<SpeculativeCache>>,
     /// Speculative execution config.
     pub speculation_config: SpeculationConfig,
+    /// Opt-in result cache for idempotent read tools (see core tool_cache).
+    pub tool_cache: Arc<Mutex<ToolResultCache>>,
     /// Tools loaded via request_tools in the current chat turn.
     /// Cleared at the start of each new chat message.
     pub loaded_tools: Arc<Mutex<std::collections::HashSet<String>>>,
@@ -349,6 +457,10 @@ pub struct EngineState {
     /// Per-session yield signals (VS Code pattern).
     /// When a queued request arrives, the active agent is asked to wrap up.
     pub yield_signals: YieldSignals,
+    /// Per-session cancellation tokens, keyed by session_id.
+    /// engine_cancel_run fires the token; the agent loop stops cooperatively
+    /// so the partial turn is persisted consistently (unlike engine_chat_abort).
+    pub cancel_tokens: CancelTokens,
     /// Per-agent cognitive state (Engram three-tier pipeline).
     /// Keyed by agent_id. Each agent gets its own SensoryBuffer + WorkingMemory.
     /// Uses tokio::sync::Mutex per-agent to allow holding across .await points
@@ -361,6 +473,10 @@ pub struct EngineState {
 
 impl EngineState {
     pub fn new() -> EngineResult<Self> {
+        // Old copies from a data-root migration go before anything opens
+        openpawz_core::engine::storage_migration::finish_pending_cleanup(
+            &openpawz_core::engine::paths::paw_data_dir(),
+        );
         let store = SessionStore::open()?;
 
         // Initialize skill vault tables
@@ -395,6 +511,12 @@ impl EngineState {
             }
         }
 
+        crate::engine::engram::set_capability_overrides(&config.model_capabilities);
+        openpawz_core::engine::i18n::set_locale(config.locale.as_deref());
+        openpawz_core::engine::i18n::load_catalog_dir(
+            &openpawz_core::engine::paths::paw_data_dir().join("locales"),
+        );
+
         // Load memory config from DB or use defaults
         let memory_config = match store.get_config("memory_config") {
             Ok(Some(json)) => serde_json::from_str::<MemoryConfig>(&json).unwrap_or_default(),
@@ -403,6 +525,7 @@ impl EngineState {
 
         // Read max_concurrent_runs from config (default 4)
         let max_concurrent = config.max_concurrent_runs;
+        let run_limit_policy = config.run_limit_policy;
 
         // Load speculation config from DB or use defaults
         let speculation_config = match store.get_config("speculation_config") {
@@ -410,6 +533,9 @@ impl EngineState {
             _ => SpeculationConfig::default(),
         };
 
+        let tool_cache_config = tool_cache::load_config(&store);
+        crate::engine::net::install(crate::engine::net::load_config(&store));
+
         // Build HNSW index from existing episodic memory embeddings
         let hnsw_index = {
             let idx = crate::engine::engram::hnsw::new_shared();
@@ -420,14 +546,21 @@ impl EngineState {
             idx
         };
 
+        // Rebuild the current window's budget counters from the persisted spend
+        let daily_tokens = Arc::new(DailyTokenTracker::with_window(config.budget_window()));
+        match store.list_daily_spend(&daily_tokens.window_start()) {
+            Ok(rows) => daily_tokens.restore(&rows),
+            Err(e) => warn!("[engine] Failed to load daily spend: {}", e),
+        }
+
         Ok(EngineState {
             store,
             config: Mutex::new(config),
             memory_config: Mutex::new(memory_config),
             pending_approvals: Arc::new(Mutex::new(HashMap::new())),
-            run_semaphore: Arc::new(tokio::sync::Semaphore::new(max_concurrent as usize)),
+            run_limiter: RunLimiter::new(max_concurrent, run_limit_policy),
             inflight_tasks: Arc::new(Mutex::new(HashSet::new())),
-            daily_tokens: Arc::new(DailyTokenTracker::new()),
+            daily_tokens,
             active_runs: Arc::new(Mutex::new(HashMap::new())),
             mcp_registry: Arc::new(tokio::sync::Mutex::new(McpRegistry::new())),
             tool_index: Arc::new(tokio::sync::Mutex::new(ToolIndex::new())),
@@ -436,9 +569,11 @@ impl EngineState {
             )),
             speculation_cache: Arc::new(Mutex::new(SpeculativeCache::new(&speculation_config))),
             speculation_config,
+            tool_cache: Arc::new(Mutex::new(ToolResultCache::new(tool_cache_config))),
             loaded_tools: Arc::new(Mutex::new(HashSet::new())),
             request_queue: Arc::new(Mutex::new(HashMap::new())),
             yield_signals: Arc::new(Mutex::new(HashMap::new())),
+            cancel_tokens: Arc::new(Mutex::new(HashMap::new())),
             cognitive_states: Arc::new(Mutex::new(HashMap::new())),
             hnsw_index,
         })
@@ -493,11 +628,13 @@ impl EngineState {
         let cfg = self.memory_config.lock();
 
         // For provider-based modes we don't require base_url/model to be set
-        // because we'll derive them from the chat provider config.
-        let needs_explicit_url = matches!(
-            cfg.embedding_provider,
-            EmbeddingProvider::Auto | EmbeddingProvider::Ollama
-        );
+        // because we'll derive them from the chat provider config — unless
+        // OpenAI has its own key, in which case it's a standalone endpoint.
+        let needs_explicit_url = match cfg.embedding_provider {
+            EmbeddingProvider::Auto | EmbeddingProvider::Ollama => true,
+            EmbeddingProvider::OpenAI => !cfg.embedding_api_key.is_empty(),
+            _ => false,
+        };
         if needs_explicit_url
             && (cfg.embedding_base_url.is_empty() || cfg.embedding_model.is_empty())
         {
@@ -525,12 +662,7 @@ impl EngineState {
                     .clone()
                     .unwrap_or_else(|| "gpt-4o-mini".to_string());
 
-                // Pick an appropriate embedding model per provider kind
-                let embedding_model = match p.kind {
-                    ProviderKind::Google => "text-embedding-004".to_string(),
-                    ProviderKind::Mistral => "mistral-embed".to_string(),
-                    _ => "text-embedding-3-small".to_string(),
-                };
+                let embedding_model = p.kind.default_embedding_model().to_string();
 
                 client =
                     client.with_openai_fallback(crate::engine::memory::embedding::OpenAiFallback {
@@ -538,6 +670,7 @@ impl EngineState {
                         base_url,
                         embedding_model,
                         chat_model,
+                        provider: Some(p.clone()),
                     });
             }
         }
@@ -545,3 +678,50 @@ impl EngineState {
         Some(client)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spend(window_start: &str, agent_id: &str, cost_usd: f64) -> DailySpend {
+        DailySpend {
+            window_start: window_start.into(),
+            agent_id: agent_id.into(),
+            cost_usd,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn per_agent_cap_blocks_only_that_agent() {
+        let tracker = DailyTokenTracker::new();
+        let current = tracker.window_start();
+        tracker.restore(&[
+            spend(&current, "trader", 2.5),
+            spend(&current, "chat", 0.5),
+            spend("1999-01-01T00:00:00Z", "chat", 50.0),
+        ]);
+
+        assert_eq!(tracker.check_agent_budget("trader", 2.0), Some(2.5));
+        assert_eq!(tracker.check_agent_budget("chat", 2.0), None);
+        assert_eq!(tracker.check_agent_budget("new-agent", 2.0), None);
+        // Global total is the sum of the current window's agents only
+        assert!(tracker.check_budget(10.0).is_none());
+        assert_eq!(tracker.check_budget(3.0), Some(3.0));
+        assert_eq!(tracker.agent_spend().len(), 2);
+    }
+
+    #[test]
+    fn counters_reset_when_stored_window_has_elapsed() {
+        let tracker = DailyTokenTracker::with_window(BudgetWindow::new("Asia/Tokyo", 0));
+        let current = tracker.window_start();
+        tracker.restore(&[spend(&current, "chat", 1.0)]);
+        assert_eq!(tracker.agent_spend_usd("chat"), 1.0);
+
+        // Counters loaded before the last local-midnight boundary are stale
+        *tracker.window_start.lock() = "2000-01-01T15:00:00Z".into();
+        assert_eq!(tracker.agent_spend_usd("chat"), 0.0);
+        assert_eq!(tracker.estimated_spend_usd().2, 0.0);
+        assert_eq!(tracker.window_start(), current);
+    }
+}
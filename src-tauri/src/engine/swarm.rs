@@ -422,6 +422,8 @@ async fn run_swarm_turn(
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     state.store.add_message(&user_msg)?;
 
@@ -441,23 +443,20 @@ async fn run_swarm_turn(
         for t in crate::engine::tools::builtin_tools() {
             all_names.insert(t.function.name);
         }
-        let enabled_ids: Vec<String> = crate::engine::skills::builtin_skills()
-            .iter()
-            .filter(|s| {
-                state
-                    .store
-                    .get_skill_enabled_state(&s.id)
-                    .unwrap_or(None)
-                    .unwrap_or(s.default_enabled)
-            })
-            .map(|s| s.id.clone())
-            .collect();
+        let enabled_ids = crate::engine::skills::enabled_skill_ids(&state.store, recipient_id);
         for t in crate::engine::tools::skill_tools(&enabled_ids) {
             all_names.insert(t.function.name);
         }
         all_names
     };
-    let mut tools = chat_org::build_chat_tools(&state.store, true, None, app_handle, &loaded_tools);
+    let mut tools = chat_org::build_chat_tools(
+        &state.store,
+        recipient_id,
+        true,
+        None,
+        app_handle,
+        &loaded_tools,
+    );
 
     let provider = AnyProvider::from_config(&provider_config);
     let run_id = uuid::Uuid::new_v4().to_string();
@@ -474,11 +473,11 @@ async fn run_swarm_turn(
 
     let approvals = state.pending_approvals.clone();
     let daily_tokens = state.daily_tokens.clone();
-    let sem = state.run_semaphore.clone();
+    let run_limiter = state.run_limiter.clone();
     let pre_loop_msg_count = messages.len();
 
     // Acquire semaphore slot
-    let _permit = sem.acquire_owned().await.ok();
+    let _permit = run_limiter.acquire_queued().await;
     info!("[swarm] Agent '{}' acquired run slot", recipient_id);
 
     let result = agent_loop::run_agent_turn(
@@ -500,6 +499,8 @@ async fn run_swarm_turn(
         false, // auto_approve_all — swarm agents respect HIL policies
         &[],   // user_approved_tools
         None,  // yield_signal
+        None,  // cancel
+        None,  // partial_reply
     )
     .await
     .map_err(|e| e.to_string())?;
@@ -523,6 +524,8 @@ async fn run_swarm_turn(
                 tool_call_id: msg.tool_call_id.clone(),
                 name: msg.name.clone(),
                 created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
             };
             let _ = state.store.add_message(&stored);
         }
@@ -30,6 +30,8 @@ pub async fn execute_task(
     state: &EngineState,
     task_id: &str,
 ) -> Result<String, String> {
+    openpawz_core::engine::emergency::ensure_armed()?;
+
     // ── Dedup guard: skip if this task is already running ──
     {
         let mut inflight = state.inflight_tasks.lock();
@@ -105,33 +107,10 @@ pub async fn execute_task(
         )
     };
 
-    let first_agent_id = agent_ids.first().map(|s| s.as_str()).unwrap_or("default");
-    let skill_instructions =
-        skills::get_enabled_skill_instructions(&state.store, first_agent_id).unwrap_or_default();
-
-    let mut all_tools = crate::engine::tools::builtin_tools();
-    let enabled_ids: Vec<String> = skills::builtin_skills()
-        .iter()
-        .filter(|s| state.store.is_skill_enabled(&s.id).unwrap_or(false))
-        .map(|s| s.id.clone())
-        .collect();
-    if !enabled_ids.is_empty() {
-        all_tools.extend(crate::engine::tools::skill_tools(&enabled_ids));
-    }
-    if !enabled_ids.contains(&"telegram".into()) {
-        if let Ok(tg_cfg) = telegram::load_telegram_config(app_handle) {
-            if !tg_cfg.bot_token.is_empty() {
-                all_tools.push(crate::engine::tools::telegram::telegram_send());
-                all_tools.push(crate::engine::tools::telegram::telegram_read());
-            }
-        }
-    }
-
     // Add tools from connected MCP servers
     let mcp_tools = crate::engine::tools::mcp_tools(app_handle);
     if !mcp_tools.is_empty() {
         info!("[engine] Adding {} MCP tools for task", mcp_tools.len());
-        all_tools.extend(mcp_tools);
     }
 
     let pending = state.pending_approvals.clone();
@@ -139,7 +118,7 @@ pub async fn execute_task(
     let task_id_for_spawn = task_id.to_string();
     let agent_count = agent_ids.len();
     let is_recurring = task.cron_schedule.as_ref().is_some_and(|s| !s.is_empty());
-    let sem = state.run_semaphore.clone();
+    let run_limiter = state.run_limiter.clone();
     let inflight = state.inflight_tasks.clone();
 
     let is_persistent = task.persistent;
@@ -176,10 +155,14 @@ pub async fn execute_task(
             }
         }
 
-        let agent_model = if let Some(ref task_model) = task.model {
-            if !task_model.is_empty() {
+        // The agent's definition: its persona replaces the default prompt,
+        // its model ranks below a routing override, its skills pick the tools
+        let agent_def = state.store.get_agent_definition(&agent_id).unwrap_or(None);
+
+        let agent_model = match task.model.as_deref().filter(|m| !m.is_empty()) {
+            Some(task_model) => {
                 let normalized = normalize_model_name(task_model).to_string();
-                if normalized != *task_model {
+                if normalized != task_model {
                     info!(
                         "[engine] Task '{}' model remapped: {} → {}",
                         task.title, task_model, normalized
@@ -190,11 +173,14 @@ pub async fn execute_task(
                     task.title, normalized
                 );
                 normalized
-            } else {
-                model_routing.resolve(&agent_id, "worker", "", &default_model)
             }
-        } else {
-            model_routing.resolve(&agent_id, "worker", "", &default_model)
+            None => model_routing.resolve_for_agent(
+                &agent_id,
+                agent_def.as_ref().and_then(|a| a.model.as_deref()),
+                "worker",
+                "",
+                &default_model,
+            ),
         };
         info!(
             "[engine] Agent '{}' resolved model: {} (task_override: {:?}, default: {})",
@@ -294,6 +280,20 @@ pub async fn execute_task(
             task.title, task.priority, agent_count_note, cron_context
         );
 
+        // The user's template lays out the base prompt and may place the skills itself
+        let mut skill_instructions =
+            skills::get_enabled_skill_instructions(&state.store, &agent_id).unwrap_or_default();
+        let base_system_prompt = chat_org::templated_base_prompt(
+            &state.config.lock(),
+            agent_def
+                .as_ref()
+                .and_then(|a| a.persona.clone())
+                .or_else(|| base_system_prompt.clone()),
+            &agent_id,
+            &mut state.store.get_todays_memories(&agent_id).unwrap_or(None),
+            &mut skill_instructions,
+        );
+
         let full_system_prompt = {
             let cognitive = cognitive_lock.lock().await;
             let mut builder =
@@ -306,7 +306,7 @@ pub async fn execute_task(
                 builder = builder.core_context(ac.clone());
             }
             if !skill_instructions.is_empty() {
-                builder = builder.skill_instructions(skill_instructions.clone());
+                builder = builder.skill_instructions(skill_instructions);
             }
             // Task context at priority 1 (never dropped — it's the reason this agent exists)
             builder = builder.custom_section("task_context", &task_context, 1);
@@ -367,6 +367,8 @@ pub async fn execute_task(
             tool_call_id: None,
             name: None,
             created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
         };
         state.store.add_message(&user_msg)?;
 
@@ -377,15 +379,18 @@ pub async fn execute_task(
             Some(&agent_id),
         )?;
 
-        let provider = AnyProvider::from_config(&provider_config);
+        let provider = {
+            let cfg = state.config.lock();
+            AnyProvider::with_fallback(&provider_config, &cfg.providers)
+        };
         let pending_clone = pending.clone();
         let task_id_clone = task_id.to_string();
         let store_path_clone = store_path.clone();
         let run_id_clone = run_id.clone();
         let app_handle_clone = app_handle.clone();
-        let mut all_tools_clone = all_tools.clone();
+        let mut all_tools_clone = task_tools(app_handle, &state.store, &agent_id, &mcp_tools);
         let model_clone = model.clone();
-        let sem_clone = sem.clone();
+        let run_limiter_clone = run_limiter.clone();
         let task_daily_tokens_clone = task_daily_tokens.clone();
         let task_daily_budget_clone = task_daily_budget;
         let task_prompt_clone = task_prompt.clone();
@@ -397,7 +402,7 @@ pub async fn execute_task(
         };
 
         let handle = tauri::async_runtime::spawn(async move {
-            let _permit = sem_clone.acquire_owned().await.ok();
+            let _permit = run_limiter_clone.acquire_queued().await;
             info!("[engine] Task agent '{}' acquired run slot", agent_id);
 
             let result = agent_loop::run_agent_turn(
@@ -419,6 +424,8 @@ pub async fn execute_task(
                 false, // auto_approve_all — tasks use safe default; opt-in is per-chat
                 &[],   // user_approved_tools
                 None,  // yield_signal
+                None,  // cancel
+                None,  // partial_reply
             )
             .await;
 
@@ -618,6 +625,37 @@ pub async fn execute_task(
     Ok(run_id)
 }
 
+/// One task agent's tools: builtins, the tools of its skills (telegram's
+/// too when the bridge is configured and the agent pins no skill list), and
+/// the connected MCP tools.
+fn task_tools(
+    app_handle: &tauri::AppHandle,
+    store: &sessions::SessionStore,
+    agent_id: &str,
+    mcp_tools: &[ToolDefinition],
+) -> Vec<ToolDefinition> {
+    let mut all_tools = crate::engine::tools::builtin_tools();
+    let enabled_ids = skills::enabled_skill_ids(store, agent_id);
+    if !enabled_ids.is_empty() {
+        all_tools.extend(crate::engine::tools::skill_tools(&enabled_ids));
+    }
+    if !enabled_ids.contains(&"telegram".into())
+        && skills::agent_skill_list(store, agent_id).is_none()
+    {
+        if let Ok(tg_cfg) = telegram::load_telegram_config(app_handle) {
+            if !tg_cfg.bot_token.is_empty() {
+                all_tools.push(crate::engine::tools::telegram::telegram_send());
+                all_tools.push(crate::engine::tools::telegram::telegram_read());
+            }
+        }
+    }
+    all_tools.extend(crate::engine::tools::skill_state::agent_definitions(
+        store, agent_id,
+    ));
+    all_tools.extend(mcp_tools.iter().cloned());
+    all_tools
+}
+
 // ── Position Management ────────────────────────────────────────────────
 
 /// Check all open positions against current prices. Auto-sell on SL/TP.
@@ -830,7 +868,11 @@ async fn execute_position_sell(
 
 /// Background cron heartbeat — called every 60 seconds from the Tauri
 /// setup hook. Checks open positions (SL/TP) and executes due cron tasks.
+/// Paused entirely (trades included) while an emergency stop is active.
 pub async fn run_cron_heartbeat(app_handle: &tauri::AppHandle) {
+    if openpawz_core::engine::emergency::is_halted() {
+        return;
+    }
     let state = app_handle.state::<EngineState>();
 
     check_positions(app_handle).await;
@@ -16,6 +16,7 @@ use crate::atoms::error::EngineResult;
 use crate::engine::channels;
 use crate::engine::state::EngineState;
 use log::{debug, error, info, warn};
+use openpawz_core::engine::i18n;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
@@ -452,7 +453,7 @@ async fn run_polling_loop(
                                         &client,
                                         &token,
                                         chat_id,
-                                        "Pairing request sent to Paw. Waiting for approval...",
+                                        &i18n::t("access.pairing_requested", &[]),
                                         Some(msg.message_id),
                                     )
                                     .await;
@@ -5,34 +5,19 @@
 use super::{RunCollector, TelemetryTurnSummary};
 use crate::engine::sessions::SessionStore;
 use log::info;
-use std::time::Instant;
 
-/// Capture timing around a tool execution and record a child span.
-pub struct ToolTimer {
-    tool_name: String,
-    start: Instant,
-}
-
-impl ToolTimer {
-    /// Start timing a tool execution.
-    pub fn start(tool_name: &str) -> Self {
-        Self {
-            tool_name: tool_name.to_string(),
-            start: Instant::now(),
-        }
-    }
-
-    /// Finish timing and record a span into the collector.
-    pub fn finish(self, collector: &RunCollector, root_id: &str, success: bool) -> u64 {
-        let duration_ms = self.start.elapsed().as_millis() as u64;
-
-        let mut handle = collector.start_span(&self.tool_name, Some(root_id));
-        handle.set_attribute("tool.success", if success { "true" } else { "false" });
-        handle.set_attribute("tool.duration_ms", &duration_ms.to_string());
-        handle.finish_with(success);
-
-        duration_ms
-    }
+/// Record a finished tool execution as a child span of the turn.
+pub fn record_tool_span(
+    collector: &RunCollector,
+    root_id: &str,
+    tool_name: &str,
+    duration_ms: u64,
+    success: bool,
+) {
+    let mut handle = collector.start_span(tool_name, Some(root_id));
+    handle.set_attribute("tool.success", if success { "true" } else { "false" });
+    handle.set_attribute("tool.duration_ms", &duration_ms.to_string());
+    handle.finish_with(success);
 }
 
 /// Record a turn's telemetry summary into the SessionStore.
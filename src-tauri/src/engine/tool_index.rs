@@ -162,6 +162,11 @@ pub fn domain_summaries() -> Vec<(&'static str, &'static str, &'static str)> {
             "view_kanban",
             "Trello project management — boards, lists, cards, labels, checklists",
         ),
+        (
+            "notion",
+            "description",
+            "Notion workspace — search, database queries, pages, block content",
+        ),
         ("github", "code", "GitHub API calls (issues, PRs, repos)"),
         (
             "integrations",
@@ -579,6 +584,7 @@ mod tests {
         assert_eq!(tool_domain("skill_store_get"), "storage");
         assert_eq!(tool_domain("skill_store_list"), "storage");
         assert_eq!(tool_domain("skill_store_delete"), "storage");
+        assert_eq!(tool_domain("note_write"), "storage");
     }
 
     #[test]
@@ -179,6 +179,7 @@ pub fn classify_domain_by_keywords(query: &str) -> Vec<&'static str> {
         (&["discord"], "discord"),
         (&["discourse", "forum"], "discourse"),
         (&["trello", "kanban", "board", "card"], "trello"),
+        (&["notion"], "notion"),
         (
             &["github", "issue", "pull request", "pr ", "repo"],
             "github",
@@ -218,6 +219,10 @@ pub fn classify_domain_by_keywords(query: &str) -> Vec<&'static str> {
             &["image", "generate image", "dall-e", "picture"],
             "integrations",
         ),
+        (
+            &["speak", "text to speech", "read aloud", "voice"],
+            "integrations",
+        ),
     ];
 
     for (keywords, domain) in domain_keywords {
@@ -377,20 +377,7 @@ async fn execute_create_agent(
         })
         .unwrap_or_default();
 
-    let slug: String = name
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-");
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let agent_id = format!("agent-{}-{}", slug, timestamp);
+    let agent_id = crate::engine::sessions::agents::new_agent_id(name);
 
     info!(
         "[engine] create_agent tool: creating '{}' as {}",
@@ -413,6 +400,16 @@ async fn execute_create_agent(
     };
 
     state.store.add_project_agent("_standalone", &agent)?;
+    state.store.upsert_agent_definition(&AgentDefinition {
+        id: agent_id.clone(),
+        name: name.to_string(),
+        persona: Some(system_prompt.to_string()),
+        model: model.map(String::from),
+        skills: vec![],
+        autonomy: Default::default(),
+        created_at: String::new(),
+        updated_at: String::new(),
+    })?;
 
     let memory_content = format!(
         "Created agent '{}' (id: {}, role: {}, specialty: {})",
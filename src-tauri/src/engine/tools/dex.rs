@@ -2,22 +2,46 @@
 
 use crate::atoms::types::*;
 use crate::engine::state::EngineState;
+use openpawz_core::engine::tx_idempotency::{self, Claim};
 use tauri::Manager;
 
 pub fn definitions() -> Vec<ToolDefinition> {
     vec![
         ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
             name: "dex_wallet_create".into(),
-            description: "Create a new self-custody Ethereum wallet. The private key is encrypted and stored in the OS keychain vault — you never see it. Returns the wallet address.".into(),
+            description: "Create a new self-custody Ethereum wallet. The private key is encrypted and stored in the OS keychain vault — you never see it. Pass a name to add an extra named wallet (e.g. per chain or strategy). Returns the wallet address.".into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Optional name for an additional wallet (e.g. 'arbitrum', 'dca-bot'). Omit to create the default wallet." }
+                }
+            }),
+        }},
+        ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
+            name: "dex_wallet_list".into(),
+            description: "List the DEX wallets (default + named) with their addresses and which one is active.".into(),
             parameters: serde_json::json!({"type": "object", "properties": {}}),
         }},
+        ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
+            name: "dex_switch_chain".into(),
+            description: "Switch the EVM chain the DEX tools use (ethereum, base, arbitrum, optimism, sepolia, or a chain ID). The RPC endpoint is checked to really serve that chain. Token symbols, WETH and Uniswap contracts follow the active chain. Use 'auto' to follow the configured DEX_RPC_URL again.".into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "chain": { "type": "string", "description": "Chain name or ID (e.g. 'base', 'arbitrum', '8453'), or 'auto'" },
+                    "rpc_url": { "type": "string", "description": "RPC endpoint for that chain. Optional if one was used before or DEX_RPC_URL already serves it." }
+                },
+                "required": ["chain"]
+            }),
+        }},
         ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
             name: "dex_balance".into(),
             description: "Check ETH and ERC-20 token balances for the DEX wallet. If no token specified, shows ETH and all tokens with non-zero balances.".into(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
-                    "token": { "type": "string", "description": "Specific token to check (e.g. 'USDC', 'WBTC', or a contract address). Omit to check all known tokens." }
+                    "token": { "type": "string", "description": "Specific token to check (e.g. 'USDC', 'WBTC', or a contract address). Omit to check all known tokens." },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." }
                 }
             }),
         }},
@@ -38,7 +62,7 @@ pub fn definitions() -> Vec<ToolDefinition> {
         }},
         ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
             name: "dex_swap".into(),
-            description: "Execute a token swap on Uniswap V3. REQUIRES USER APPROVAL. Gets a quote, handles token approval if needed, builds and signs the transaction, then broadcasts it. The private key never leaves the vault.".into(),
+            description: "Execute a token swap on Uniswap V3. REQUIRES USER APPROVAL. Gets a quote, handles token approval if needed (a signed ERC-2612 permit bundled into the swap when the token supports it), builds and signs the transaction, then broadcasts it. The private key never leaves the vault.".into(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -46,22 +70,64 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "token_out": { "type": "string", "description": "Token to buy (e.g. 'USDC', 'ETH', 'UNI')" },
                     "amount": { "type": "string", "description": "Amount of token_in to swap (e.g. '0.1', '50')" },
                     "reason": { "type": "string", "description": "Reason for this swap (shown in approval modal and trade history)" },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." },
                     "fee_tier": { "type": "integer", "description": "Uniswap V3 fee tier: 100, 500, 3000 (default), or 10000" },
-                    "slippage_bps": { "type": "integer", "description": "Slippage tolerance in basis points. Default: 50 (0.5%). Max: 500 (5%)" }
+                    "slippage_bps": { "type": "integer", "description": "Slippage tolerance in basis points. Default: 50 (0.5%). Max: 500 (5%)" },
+                    "confirm_timeout_secs": { "type": "integer", "description": "Optional. Seconds to wait for the approval and swap to confirm before reporting them pending. Default: 120 (plus 12 per extra required confirmation). Max: 1800" },
+                    "allow_repeat": { "type": "boolean", "description": "Optional. Set true only when the user wants the exact same swap made again — identical swaps within 5 minutes are otherwise answered with the first one's result" },
+                    "access_list": {
+                        "type": "array",
+                        "description": "Optional, advanced. EIP-2930 access list for the swap transaction, in eth_createAccessList form",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "address": { "type": "string" },
+                                "storageKeys": { "type": "array", "items": { "type": "string" } }
+                            },
+                            "required": ["address"]
+                        }
+                    }
                 },
                 "required": ["token_in", "token_out", "amount", "reason"]
             }),
         }},
         ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
             name: "dex_portfolio".into(),
-            description: "Get a complete portfolio view: ETH balance + all known ERC-20 token balances + network info.".into(),
+            description: "Get a complete portfolio view: ETH balance + all known ERC-20 token balances + network info. Set with_usd to value each holding in USD (Uniswap quotes against USDC) with a total.".into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "tokens": { "type": "array", "items": { "type": "string" }, "description": "Additional ERC-20 contract addresses to check beyond the built-in list" },
+                    "with_usd": { "type": "boolean", "description": "Add USD values and a portfolio total (slower: one price quote per token). Default false." },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." }
+                }
+            }),
+        }},
+        ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
+            name: "dex_tx_history".into(),
+            description: "Show the wallet's on-chain transaction history (ETH transactions and ERC-20 token transfers) from the chain's block explorer, newest first, with direction, counterparty, token, amount, and status. Set blocks or token to instead scan ERC-20 Transfer logs over RPC (no explorer API key needed), oldest first with net inbound/outbound flow per token — use that for 'what did I trade today/this week'. Without an explorer API key the log scan is used.".into(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
-                    "tokens": { "type": "array", "items": { "type": "string" }, "description": "Additional ERC-20 contract addresses to check beyond the built-in list" }
+                    "limit": { "type": "integer", "description": "Max entries to show from the explorer (default 20, max 100)" },
+                    "blocks": { "type": "integer", "description": "Scan token transfers over this many blocks back from the head (default 7200 ≈ 1 day on Ethereum, max 50400 ≈ 1 week)" },
+                    "token": { "type": "string", "description": "Only transfers of this token (symbol or contract address); implies the log scan" },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." }
                 }
             }),
         }},
+        ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
+            name: "dex_sign_typed_data".into(),
+            description: "Sign EIP-712 typed data (eth_signTypedData_v4) with the wallet key, e.g. for dApp sign-in or an EIP-2612 permit. REQUIRES USER APPROVAL. Returns the signature only; nothing is broadcast. A signed permit lets the spender move tokens without another on-chain approval.".into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "typed_data": { "type": "object", "description": "The full eth_signTypedData_v4 payload: { types, primaryType, domain, message }" },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." }
+                },
+                "required": ["typed_data"]
+            }),
+        }},
         ToolDefinition { tool_type: "function".into(), function: FunctionDefinition {
             name: "dex_token_info".into(),
             description: "Get comprehensive on-chain info about any ERC-20 token by its contract address. Reads name, symbol, decimals, total supply, owner, contract code size, and tests swap viability on Uniswap V3.".into(),
@@ -156,7 +222,10 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     "currency": { "type": "string", "description": "Token to send: 'ETH' for native Ether, or a token symbol or ERC-20 contract address" },
                     "amount": { "type": "string", "description": "Amount to send in human-readable units (e.g. '0.5' for 0.5 ETH, '100' for 100 USDC)" },
                     "to_address": { "type": "string", "description": "Recipient Ethereum address (0x-prefixed, 42 characters)" },
-                    "reason": { "type": "string", "description": "Brief explanation of why this transfer is being made" }
+                    "reason": { "type": "string", "description": "Brief explanation of why this transfer is being made" },
+                    "wallet": { "type": "string", "description": "Optional wallet name or id (see dex_wallet_list). Defaults to the active wallet." },
+                    "confirm_timeout_secs": { "type": "integer", "description": "Optional. Seconds to wait for the transfer to confirm before reporting it pending. Default: 120 (plus 12 per extra required confirmation). Max: 1800" },
+                    "allow_repeat": { "type": "boolean", "description": "Optional. Set true only when the user wants the exact same transfer sent again — identical transfers within 5 minutes are otherwise answered with the first one's result" }
                 },
                 "required": ["currency", "amount", "to_address", "reason"]
             }),
@@ -179,12 +248,75 @@ pub async fn execute(
         Err(e) => return Some(Err(e.to_string())),
     };
     let state = app_handle.state::<EngineState>();
-    Some(match name {
+    if name == "dex_switch_chain" {
+        let result = match crate::engine::skills::get_vault_key() {
+            Ok(vault_key) => {
+                crate::engine::dex::chains::execute_dex_switch_chain(
+                    &state.store,
+                    &vault_key,
+                    args,
+                    &creds,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        return Some(result.map_err(|e| e.to_string()));
+    }
+    // Active chain context (RPC URL + cached chain id)
+    let creds = match crate::engine::dex::chains::chain_context(&state.store, &creds).await {
+        Ok(c) => c,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    // Named wallet selection (`wallet` arg or the active wallet)
+    let creds = if crate::engine::dex::wallets::WALLET_TOOLS.contains(&name) {
+        let selected = crate::engine::skills::get_vault_key().and_then(|vault_key| {
+            crate::engine::dex::wallets::wallet_creds(
+                &state.store,
+                &vault_key,
+                &creds,
+                args["wallet"].as_str(),
+            )
+        });
+        match selected {
+            Ok(c) => c,
+            Err(e) => return Some(Err(e.to_string())),
+        }
+    } else {
+        creds
+    };
+    // Hard spending allowance — enforced here too so nothing over the cap
+    // reaches signing, whatever path approved it.
+    if let Err(e) = openpawz_core::engine::dex_allowance::enforce(&state.store, name, args) {
+        return Some(Err(e));
+    }
+    // Fund-moving calls are claimed by (tool, args, chain, wallet) so a
+    // replayed call returns the first one's result instead of broadcasting.
+    let chain = creds
+        .get(crate::engine::dex::chains::CHAIN_ID_CRED)
+        .or_else(|| creds.get("DEX_RPC_URL"))
+        .map(String::as_str)
+        .unwrap_or("");
+    let wallet = creds
+        .get("DEX_WALLET_ADDRESS")
+        .map(String::as_str)
+        .unwrap_or("");
+    let guard = match tx_idempotency::claim(name, args, &[chain, wallet]) {
+        Some(Claim::Execute(guard)) => Some(guard),
+        Some(Claim::Duplicate(output)) => return Some(Ok(output)),
+        Some(Claim::InFlight(e)) | Some(Claim::Interrupted(e)) => return Some(Err(e)),
+        None => None,
+    };
+    // Whether a swap/transfer broadcast a transaction that hasn't reverted
+    let mut landed = false;
+    let result = match name {
         "dex_wallet_create" => {
             crate::engine::dex::execute_dex_wallet_create(args, &creds, app_handle)
                 .await
                 .map_err(|e| e.to_string())
         }
+        "dex_wallet_list" => crate::engine::dex::execute_dex_wallet_list(&state.store, &creds)
+            .map_err(|e| e.to_string()),
         "dex_balance" => crate::engine::dex::execute_dex_balance(args, &creds)
             .await
             .map_err(|e| e.to_string()),
@@ -192,7 +324,12 @@ pub async fn execute(
             .await
             .map_err(|e| e.to_string()),
         "dex_swap" => {
-            let result = crate::engine::dex::execute_dex_swap(args, &creds).await;
+            let result = crate::engine::dex::execute_dex_swap(args, &creds)
+                .await
+                .map(|report| {
+                    landed = report.landed;
+                    report.text
+                });
             if result.is_ok() {
                 let token_in = args["token_in"].as_str().unwrap_or("?");
                 let token_out = args["token_out"].as_str().unwrap_or("?");
@@ -223,6 +360,12 @@ pub async fn execute(
         "dex_portfolio" => crate::engine::dex::execute_dex_portfolio(args, &creds)
             .await
             .map_err(|e| e.to_string()),
+        "dex_tx_history" => crate::engine::dex::execute_dex_tx_history(args, &creds)
+            .await
+            .map_err(|e| e.to_string()),
+        "dex_sign_typed_data" => crate::engine::dex::execute_dex_sign_typed_data(args, &creds)
+            .await
+            .map_err(|e| e.to_string()),
         "dex_token_info" => crate::engine::dex::execute_dex_token_info(args, &creds)
             .await
             .map_err(|e| e.to_string()),
@@ -245,7 +388,12 @@ pub async fn execute(
             .await
             .map_err(|e| e.to_string()),
         "dex_transfer" => {
-            let result = crate::engine::dex::execute_dex_transfer(args, &creds).await;
+            let result = crate::engine::dex::execute_dex_transfer(args, &creds)
+                .await
+                .map(|report| {
+                    landed = report.landed;
+                    report.text
+                });
             if result.is_ok() {
                 let currency = args["currency"].as_str().unwrap_or("?");
                 let _ = state.store.insert_trade(
@@ -268,5 +416,9 @@ pub async fn execute(
             result.map_err(|e| e.to_string())
         }
         _ => return None,
-    })
+    };
+    if let Some(guard) = guard {
+        guard.complete(&result, landed);
+    }
+    Some(result)
 }
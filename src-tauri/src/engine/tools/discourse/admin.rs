@@ -19,6 +19,7 @@
 use super::{authorized_client, discourse_request, get_credentials};
 use crate::atoms::error::EngineResult;
 use crate::atoms::types::*;
+use crate::engine::util::safe_truncate;
 use log::info;
 use serde_json::{json, Value};
 
@@ -359,7 +360,7 @@ async fn exec_settings_list(args: &Value, app_handle: &tauri::AppHandle) -> Engi
         let val_str = match value {
             Value::String(s) => {
                 if s.len() > 80 {
-                    format!("{}…", &s[..80])
+                    format!("{}…", safe_truncate(s, 80))
                 } else {
                     s.clone()
                 }
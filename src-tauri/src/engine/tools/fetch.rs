@@ -1,9 +1,15 @@
 // Paw Agent Engine — fetch tool
-// HTTP requests to any URL.
+// HTTP requests to any URL. HTML responses can optionally be reduced to
+// their readable main text; every response is capped at `max_bytes`, and the
+// download itself stops once the cap (or the readable-mode read limit) is hit.
 
 use crate::atoms::error::EngineResult;
 use crate::atoms::types::*;
+use crate::commands::browser::NetworkPolicy;
+use crate::engine::secret_scrub::is_secret_field;
+use crate::engine::util::safe_truncate;
 use log::{info, warn};
+use std::collections::HashMap;
 use std::time::Duration;
 use tauri::Manager;
 
@@ -103,12 +109,252 @@ async fn check_dns_rebinding(url: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Default cap on the returned body, in bytes.
+const DEFAULT_MAX_BYTES: usize = 50_000;
+/// Hard ceiling for a caller-supplied `max_bytes`.
+const MAX_BYTES_LIMIT: usize = 200_000;
+/// HTML downloaded for readable mode before extraction — the page's text is
+/// a fraction of its markup, so this is read past `max_bytes`.
+const READABLE_READ_LIMIT: usize = 2_000_000;
+/// Redirect hops followed before giving up.
+const MAX_REDIRECTS: usize = 5;
+
+/// Check `url` against the user's outbound network policy (blocklist always,
+/// allowlist when enabled).
+fn check_network_policy(policy: Option<&NetworkPolicy>, url: &str) -> Result<(), String> {
+    let Some(policy) = policy else {
+        return Ok(());
+    };
+    let domain = crate::commands::browser::extract_domain_from_url(url);
+    if policy
+        .blocked_domains
+        .iter()
+        .any(|d| crate::commands::browser::domain_matches_pub(&domain, d))
+    {
+        return Err(format!("Network policy: domain '{}' is blocked", domain));
+    }
+    if policy.enabled
+        && !policy
+            .allowed_domains
+            .iter()
+            .any(|d| crate::commands::browser::domain_matches_pub(&domain, d))
+    {
+        return Err(format!(
+            "Network policy: domain '{}' is not in the allowlist",
+            domain
+        ));
+    }
+    Ok(())
+}
+
+/// Check `url` against the network policy saved in the engine config, for
+/// tools other than fetch that call out to user-configured endpoints.
+pub(crate) fn check_url_allowed(app_handle: &tauri::AppHandle, url: &str) -> Result<(), String> {
+    let policy: Option<NetworkPolicy> = app_handle
+        .try_state::<crate::engine::state::EngineState>()
+        .and_then(|state| state.store.get_config("network_policy").ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok());
+    check_network_policy(policy.as_ref(), url)
+}
+
+/// Cap `body` at `max_bytes` (on a char boundary), noting the original size.
+fn truncate_body(body: String, max_bytes: usize) -> String {
+    if body.len() <= max_bytes {
+        return body;
+    }
+    format!(
+        "{}...\n[truncated, {} total bytes]",
+        safe_truncate(&body, max_bytes),
+        body.len()
+    )
+}
+
+/// Headers for one hop of a request first sent to `first_url`: the
+/// configured headers for the hop's own domain (`configured`), then the
+/// auto-injected ones, then the caller's (which override). When a redirect
+/// leaves the first host, injected and caller-set headers that carry
+/// credentials stay behind, as the first domain's settings do.
+fn hop_headers(
+    first_url: &str,
+    hop_url: &str,
+    explicit: Option<&serde_json::Map<String, serde_json::Value>>,
+    injected: &HashMap<String, String>,
+    configured: Vec<(String, String)>,
+) -> Vec<(String, String)> {
+    let host = |u: &str| {
+        url::Url::parse(u)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_ascii_lowercase))
+    };
+    let same_host = host(first_url) == host(hop_url);
+    let keep = |name: &str| same_host || !is_secret_field(name);
+    let is_explicit =
+        |name: &str| explicit.is_some_and(|h| h.keys().any(|k| k.eq_ignore_ascii_case(name)));
+
+    let mut headers: Vec<(String, String)> = configured
+        .into_iter()
+        .filter(|(k, _)| !is_explicit(k) && !injected.contains_key(k))
+        .collect();
+    headers.extend(
+        injected
+            .iter()
+            .filter(|(k, _)| keep(k))
+            .map(|(k, v)| (k.clone(), v.clone())),
+    );
+    for (key, value) in explicit.into_iter().flatten() {
+        if let Some(v) = value.as_str().filter(|_| keep(key)) {
+            headers.push((key.clone(), v.to_string()));
+        }
+    }
+    headers
+}
+
+enum SendError {
+    /// The request itself failed (timeouts and refused connections are retried).
+    Transport(reqwest::Error),
+    /// A redirect was refused.
+    Redirect(String),
+}
+
+/// Send the request, following redirects by hand. Every hop is re-checked
+/// so a permitted URL can't bounce the request to an internal address or a
+/// domain outside the allowlist, and carries the headers for its own host
+/// (see [`hop_headers`]).
+async fn send_following(
+    client: &reqwest::Client,
+    method: &str,
+    url: &str,
+    args: &serde_json::Value,
+    injected: &HashMap<String, String>,
+    policy: Option<&NetworkPolicy>,
+) -> Result<reqwest::Response, SendError> {
+    use reqwest::Method;
+
+    let mut method = match method.to_uppercase().as_str() {
+        "POST" => Method::POST,
+        "PUT" => Method::PUT,
+        "PATCH" => Method::PATCH,
+        "DELETE" => Method::DELETE,
+        "HEAD" => Method::HEAD,
+        _ => Method::GET,
+    };
+    // Accept body as either a JSON string or a JSON object/array.
+    // When the model passes an object (e.g. {"name":"foo","type":0}),
+    // we serialize it to a JSON string. This avoids the double-escaping
+    // problem that causes MALFORMED_FUNCTION_CALL errors in Gemini.
+    let mut body = match &args["body"] {
+        serde_json::Value::String(body) => Some(body.clone()),
+        b if b.is_object() || b.is_array() => Some(serde_json::to_string(b).unwrap_or_default()),
+        _ => None,
+    };
+    let mut current =
+        url::Url::parse(url).map_err(|e| SendError::Redirect(format!("Invalid URL: {}", e)))?;
+
+    for hop in 0..=MAX_REDIRECTS {
+        let mut req = client.request(method.clone(), current.clone());
+        for (key, value) in hop_headers(
+            url,
+            current.as_str(),
+            args["headers"].as_object(),
+            injected,
+            crate::engine::net::active_headers(current.as_str()),
+        ) {
+            req = req.header(key.as_str(), value.as_str());
+        }
+        if let Some(body) = &body {
+            req = req.body(body.clone());
+        }
+        let resp = req.send().await.map_err(SendError::Transport)?;
+
+        let status = resp.status();
+        let location = resp
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok());
+        let Some(location) = location.filter(|_| status.is_redirection()) else {
+            return Ok(resp);
+        };
+        if hop == MAX_REDIRECTS {
+            break;
+        }
+        let next = current
+            .join(location)
+            .map_err(|e| SendError::Redirect(format!("bad redirect location: {}", e)))?;
+        if is_ssrf_target(next.as_str()) {
+            return Err(SendError::Redirect(format!(
+                "redirect to internal address blocked: {}",
+                next
+            )));
+        }
+        check_dns_rebinding(next.as_str())
+            .await
+            .map_err(|e| SendError::Redirect(format!("redirect blocked: {}", e)))?;
+        check_network_policy(policy, next.as_str()).map_err(SendError::Redirect)?;
+        // 303, and 301/302 after a POST, continue as a body-less GET
+        let code = status.as_u16();
+        if code == 303 || (matches!(code, 301 | 302) && method == Method::POST) {
+            if method != Method::HEAD {
+                method = Method::GET;
+            }
+            body = None;
+        }
+        current = next;
+    }
+    Err(SendError::Redirect("too many redirects".into()))
+}
+
+/// Read at most `limit` bytes of the body, chunk by chunk, and stop the
+/// download there. Returns the text and whether the body went on past it.
+async fn read_capped(
+    mut resp: reqwest::Response,
+    limit: usize,
+) -> Result<(String, bool), reqwest::Error> {
+    let mut buf: Vec<u8> = Vec::new();
+    let mut cut_off = false;
+    while let Some(chunk) = resp.chunk().await? {
+        let room = limit - buf.len();
+        if chunk.len() > room {
+            buf.extend_from_slice(&chunk[..room]);
+            cut_off = true;
+            break;
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    // Drop a multi-byte character the cap split in half
+    if cut_off {
+        if let Err(e) = std::str::from_utf8(&buf) {
+            if e.error_len().is_none() {
+                buf.truncate(e.valid_up_to());
+            }
+        }
+    }
+    Ok((String::from_utf8_lossy(&buf).into_owned(), cut_off))
+}
+
+/// Turn a response body into what the model sees. With `readable` set,
+/// HTML is reduced to its main text (scripts, styles and page chrome
+/// dropped); anything else passes through untouched.
+fn render_body(body: String, content_type: &str, readable: bool, max_bytes: usize) -> String {
+    let body = if readable && content_type.contains("html") {
+        let document = scraper::Html::parse_document(&body);
+        let text = crate::engine::web::extract_readable_text(&document);
+        if text.trim().is_empty() {
+            "(no readable text — the page may require JavaScript to render)".to_string()
+        } else {
+            text
+        }
+    } else {
+        body
+    };
+    truncate_body(body, max_bytes)
+}
+
 pub fn definitions() -> Vec<ToolDefinition> {
     vec![ToolDefinition {
         tool_type: "function".into(),
         function: FunctionDefinition {
             name: "fetch".into(),
-            description: "Make an HTTP request to any URL. Returns the response body. Use for API calls, web scraping, downloading content, or any HTTP interaction.".into(),
+            description: "Make an HTTP request to any URL. Returns the status, final URL (after redirects), content type and response body. Use for API calls, web scraping, downloading content, or any HTTP interaction. Set readable=true to get an HTML page's main text instead of raw markup.".into(),
             parameters: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -119,7 +365,9 @@ pub fn definitions() -> Vec<ToolDefinition> {
                         "description": "HTTP method (default: GET)"
                     },
                     "headers": { "type": "object", "description": "HTTP headers as key-value pairs" },
-                    "body": { "description": "Request body for POST/PUT/PATCH. Pass a JSON object directly (preferred) or a JSON string." }
+                    "body": { "description": "Request body for POST/PUT/PATCH. Pass a JSON object directly (preferred) or a JSON string." },
+                    "readable": { "type": "boolean", "description": "For HTML responses, return only the main article text with scripts, styles and navigation stripped (default: false)" },
+                    "max_bytes": { "type": "integer", "description": "Maximum response size returned, in bytes (default: 50000, max: 200000). Longer bodies are truncated." }
                 },
                 "required": ["url"]
             }),
@@ -150,6 +398,11 @@ async fn execute_fetch(
         .as_str()
         .ok_or("fetch: missing 'url' argument")?;
     let method = args["method"].as_str().unwrap_or("GET");
+    let readable = args["readable"].as_bool().unwrap_or(false);
+    let max_bytes = args["max_bytes"]
+        .as_u64()
+        .map(|n| (n as usize).clamp(1, MAX_BYTES_LIMIT))
+        .unwrap_or(DEFAULT_MAX_BYTES);
 
     info!("[engine] fetch: {} {}", method, url);
 
@@ -173,43 +426,18 @@ async fn execute_fetch(
     }
 
     // Network policy enforcement
-    if let Some(state) = app_handle.try_state::<crate::engine::state::EngineState>() {
-        if let Ok(Some(policy_json)) = state.store.get_config("network_policy") {
-            if let Ok(policy) =
-                serde_json::from_str::<crate::commands::browser::NetworkPolicy>(&policy_json)
-            {
-                let domain = crate::commands::browser::extract_domain_from_url(url);
-                if policy
-                    .blocked_domains
-                    .iter()
-                    .any(|d| crate::commands::browser::domain_matches_pub(&domain, d))
-                {
-                    return Err(format!("Network policy: domain '{}' is blocked", domain).into());
-                }
-                if policy.enabled {
-                    let allowed = policy
-                        .allowed_domains
-                        .iter()
-                        .any(|d| crate::commands::browser::domain_matches_pub(&domain, d));
-                    if !allowed {
-                        return Err(format!(
-                            "Network policy: domain '{}' is not in the allowlist",
-                            domain
-                        )
-                        .into());
-                    }
-                }
-            }
-        }
-    }
+    let policy: Option<NetworkPolicy> = app_handle
+        .try_state::<crate::engine::state::EngineState>()
+        .and_then(|state| state.store.get_config("network_policy").ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok());
+    check_network_policy(policy.as_ref(), url)?;
 
     // ── Auto-inject credentials for known API domains ─────────────────
     // If the agent calls a Discord API URL without an Authorization header,
     // automatically inject the bot token from the skill vault. This prevents
     // 401 errors when the LLM forgets to include the header (which happens
     // frequently after context truncation).
-    let mut injected_headers: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
+    let mut injected_headers: HashMap<String, String> = HashMap::new();
 
     let has_auth_header = args["headers"]
         .as_object()
@@ -241,48 +469,51 @@ async fn execute_fetch(
         }
     }
 
+    // robots.txt applies to page retrieval — not to API calls that change
+    // state, nor (unless opted in) to authenticated or API-endpoint reads
+    let politeness = crate::engine::robots::config_for(app_handle);
+    let authenticated = hop_headers(
+        url,
+        url,
+        args["headers"].as_object(),
+        &injected_headers,
+        crate::engine::net::active_headers(url),
+    )
+    .iter()
+    .any(|(k, _)| k.eq_ignore_ascii_case("authorization"));
+    let kind = if !matches!(method.to_uppercase().as_str(), "GET" | "HEAD") {
+        crate::engine::robots::AccessKind::Api
+    } else if authenticated || crate::engine::robots::is_api_url(url) {
+        crate::engine::robots::AccessKind::ApiRead
+    } else {
+        crate::engine::robots::AccessKind::Fetch
+    };
+    crate::engine::robots::before_request(&politeness, url, kind)
+        .await
+        .map_err(|e| format!("fetch: {}", e))?;
+
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
+        .redirect(reqwest::redirect::Policy::none())
         .build()?;
 
     // ── Retry loop for transient errors ──────────────────────────────
     use crate::engine::http::{is_retryable_status, parse_retry_after, retry_delay, MAX_RETRIES};
 
     let mut last_err: Option<String> = None;
-    let mut response_result: Option<(u16, String)> = None;
+    let mut response_result: Option<(u16, String, String, String, bool)> = None;
 
     for attempt in 0..=MAX_RETRIES {
-        // Rebuild the request each attempt (RequestBuilder is not Clone)
-        let mut req = match method.to_uppercase().as_str() {
-            "POST" => client.post(url),
-            "PUT" => client.put(url),
-            "PATCH" => client.patch(url),
-            "DELETE" => client.delete(url),
-            "HEAD" => client.head(url),
-            _ => client.get(url),
-        };
-        // Apply auto-injected credential headers first (so explicit headers override)
-        for (key, value) in &injected_headers {
-            req = req.header(key.as_str(), value.as_str());
-        }
-        if let Some(headers) = args["headers"].as_object() {
-            for (key, value) in headers {
-                if let Some(v) = value.as_str() {
-                    req = req.header(key.as_str(), v);
-                }
-            }
-        }
-        // Accept body as either a JSON string or a JSON object/array.
-        // When the model passes an object (e.g. {"name":"foo","type":0}),
-        // we serialize it to a JSON string. This avoids the double-escaping
-        // problem that causes MALFORMED_FUNCTION_CALL errors in Gemini.
-        if let Some(body_str) = args["body"].as_str() {
-            req = req.body(body_str.to_string());
-        } else if args["body"].is_object() || args["body"].is_array() {
-            req = req.body(serde_json::to_string(&args["body"]).unwrap_or_default());
-        }
-
-        match req.send().await {
+        let sent = send_following(
+            &client,
+            method,
+            url,
+            args,
+            &injected_headers,
+            policy.as_ref(),
+        )
+        .await;
+        match sent {
             Ok(resp) => {
                 let status = resp.status().as_u16();
                 let retry_after = resp
@@ -301,14 +532,29 @@ async fn execute_fetch(
                     continue;
                 }
 
-                let body = resp
-                    .text()
+                let final_url = resp.url().to_string();
+                let content_type = resp
+                    .headers()
+                    .get("content-type")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+                let read_limit = if readable && content_type.contains("html") {
+                    READABLE_READ_LIMIT
+                } else {
+                    max_bytes
+                };
+                let (body, cut_off) = read_capped(resp, read_limit)
                     .await
-                    .unwrap_or_else(|e| format!("(body read error: {})", e));
-                response_result = Some((status, body));
+                    .unwrap_or_else(|e| (format!("(body read error: {})", e), false));
+                response_result = Some((status, final_url, content_type, body, cut_off));
+                break;
+            }
+            Err(SendError::Redirect(msg)) => {
+                last_err = Some(msg);
                 break;
             }
-            Err(e) => {
+            Err(SendError::Transport(e)) => {
                 if attempt < MAX_RETRIES && (e.is_timeout() || e.is_connect()) {
                     log::warn!(
                         "[fetch] Transport error on attempt {}: {} — retrying",
@@ -324,7 +570,7 @@ async fn execute_fetch(
         }
     }
 
-    let (status, body) = match response_result {
+    let (status, final_url, content_type, body, cut_off) = match response_result {
         Some(r) => r,
         None => {
             return Err(format!(
@@ -335,21 +581,227 @@ async fn execute_fetch(
         }
     };
 
-    const MAX_BODY: usize = 50_000;
-    let truncated = if body.len() > MAX_BODY {
-        format!(
-            "{}...\n[truncated, {} total bytes]",
-            &body[..MAX_BODY],
-            body.len()
-        )
-    } else {
-        body
-    };
-
+    let mut rendered = render_body(body, &content_type, readable, max_bytes);
+    if cut_off {
+        rendered.push_str("\n[truncated — download stopped at the size limit]");
+    }
     Ok(format!(
-        "HTTP {} {}\n\n{}",
+        "HTTP {} {}\nURL: {}\nContent-Type: {}\n\n{}",
         status,
         if status < 400 { "OK" } else { "Error" },
-        truncated
+        final_url,
+        if content_type.is_empty() {
+            "unknown"
+        } else {
+            &content_type
+        },
+        rendered
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"<html>
+<head><title>T</title><style>body { color: red; }</style>
+<script>var tracking = "pixel";</script></head>
+<body>
+<nav><a href="/">Home</a></nav>
+<article>
+<h1>Otters hold hands</h1>
+<p>Sea otters hold hands while sleeping so they don't drift apart.</p>
+<script>alert("inline");</script>
+</article>
+<footer>Copyright</footer>
+</body></html>"#;
+
+    #[test]
+    fn readable_mode_reduces_html_to_text() {
+        let out = render_body(ARTICLE.into(), "text/html; charset=utf-8", true, 10_000);
+        assert!(out.contains("Otters hold hands"));
+        assert!(out.contains("so they don't drift apart."));
+        assert!(!out.contains('<'));
+        assert!(!out.contains("tracking"));
+        assert!(!out.contains("alert"));
+        assert!(!out.contains("color: red"));
+        assert!(!out.contains("Copyright"));
+    }
+
+    #[test]
+    fn raw_mode_and_non_html_pass_through() {
+        let raw = render_body(ARTICLE.into(), "text/html", false, 10_000);
+        assert_eq!(raw, ARTICLE);
+        let json = r#"{"ok":true}"#;
+        assert_eq!(
+            render_body(json.into(), "application/json", true, 10_000),
+            json
+        );
+    }
+
+    #[test]
+    fn oversized_bodies_are_truncated() {
+        let out = render_body("a".repeat(1_000), "text/plain", false, 100);
+        assert!(out.starts_with(&"a".repeat(100)));
+        assert!(!out.starts_with(&"a".repeat(101)));
+        assert!(out.ends_with("[truncated, 1000 total bytes]"));
+
+        // Never splits a multi-byte character
+        let out = truncate_body("é".repeat(100), 51);
+        assert!(out.starts_with(&"é".repeat(25)));
+        assert!(out.contains("[truncated, 200 total bytes]"));
+    }
+
+    #[tokio::test]
+    async fn download_stops_at_the_cap() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut req = [0u8; 1024];
+            let _ = sock.read(&mut req).await;
+            // Chunked and far larger than the cap; "é" makes the cap split a character
+            let _ = sock
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await;
+            let chunk = "aé".repeat(4096);
+            for _ in 0..64 {
+                let frame = format!("{:x}\r\n{}\r\n", chunk.len(), chunk);
+                if sock.write_all(frame.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+            let _ = sock.write_all(b"0\r\n\r\n").await;
+        });
+
+        let resp = reqwest::get(format!("http://{}/", addr)).await.unwrap();
+        let (body, cut_off) = read_capped(resp, 101).await.unwrap();
+        assert!(cut_off);
+        assert_eq!(body, "aé".repeat(33) + "a");
+
+        // A body under the cap is read whole
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut req = [0u8; 1024];
+            let _ = sock.read(&mut req).await;
+            let _ = sock
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello")
+                .await;
+        });
+        let resp = reqwest::get(format!("http://{}/", addr)).await.unwrap();
+        assert_eq!(
+            read_capped(resp, 100).await.unwrap(),
+            ("hello".to_string(), false)
+        );
+    }
+
+    #[tokio::test]
+    async fn redirect_to_a_host_resolving_privately_is_blocked() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut req = [0u8; 1024];
+            let _ = sock.read(&mut req).await;
+            // The userinfo hides "localhost" from the string check; only
+            // resolving the host shows it is loopback
+            let resp = format!(
+                "HTTP/1.1 302 Found\r\nLocation: http://probe@localhost:{}/secret\r\n\
+                 Content-Length: 0\r\n\r\n",
+                addr.port()
+            );
+            let _ = sock.write_all(resp.as_bytes()).await;
+        });
+
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let sent = send_following(
+            &client,
+            "GET",
+            &format!("http://{}/start", addr),
+            &serde_json::json!({}),
+            &HashMap::new(),
+            None,
+        )
+        .await;
+        match sent {
+            Err(SendError::Redirect(msg)) => assert!(msg.contains("DNS rebinding"), "{}", msg),
+            Err(SendError::Transport(e)) => panic!("unexpected transport error: {}", e),
+            Ok(resp) => panic!("redirect was followed to {}", resp.url()),
+        }
+    }
+
+    #[test]
+    fn credentials_stay_with_the_first_host() {
+        let explicit = serde_json::json!({ "Accept": "text/html", "Cookie": "sid=1" });
+        let injected: HashMap<String, String> = [
+            ("Authorization".to_string(), "Bot t".to_string()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let names = |headers: Vec<(String, String)>| -> Vec<String> {
+            let mut names: Vec<String> = headers.into_iter().map(|(k, _)| k).collect();
+            names.sort();
+            names
+        };
+
+        let first = "https://discord.com/api/x";
+        let same = hop_headers(
+            first,
+            "https://discord.com/api/y",
+            explicit.as_object(),
+            &injected,
+            vec![("X-Api-Key".into(), "k".into())],
+        );
+        assert_eq!(
+            names(same),
+            [
+                "Accept",
+                "Authorization",
+                "Content-Type",
+                "Cookie",
+                "X-Api-Key"
+            ]
+        );
+
+        // Another host gets its own configured headers, not the first one's secrets
+        let other = hop_headers(
+            first,
+            "https://cdn.example.net/file",
+            explicit.as_object(),
+            &injected,
+            vec![("User-Agent".into(), "Paw".into())],
+        );
+        assert_eq!(names(other), ["Accept", "Content-Type", "User-Agent"]);
+    }
+
+    #[test]
+    fn network_policy_allowlist_and_blocklist() {
+        let policy = NetworkPolicy {
+            enabled: true,
+            allowed_domains: vec!["example.com".into()],
+            blocked_domains: vec!["evil.example.com".into()],
+            log_requests: false,
+            recent_requests: vec![],
+        };
+        assert!(check_network_policy(Some(&policy), "https://example.com/a").is_ok());
+        assert!(check_network_policy(Some(&policy), "https://other.org/")
+            .unwrap_err()
+            .contains("not in the allowlist"));
+        assert!(
+            check_network_policy(Some(&policy), "https://evil.example.com/")
+                .unwrap_err()
+                .contains("blocked")
+        );
+        assert!(check_network_policy(None, "https://other.org/").is_ok());
+    }
+}
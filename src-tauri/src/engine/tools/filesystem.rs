@@ -4,6 +4,7 @@
 use crate::atoms::error::EngineResult;
 use crate::atoms::types::*;
 use log::{info, warn};
+use openpawz_core::engine::disk;
 
 /// Sensitive paths that agents must never read or write.
 /// Checked against the canonicalized path (lowercased on case-insensitive OS).
@@ -363,6 +364,10 @@ async fn execute_write_file(args: &serde_json::Value, agent_id: &str) -> EngineR
         );
     }
 
+    if content.len() as u64 >= disk::LARGE_WRITE_BYTES {
+        disk::check_free_space(&resolved, content.len() as u64)?;
+    }
+
     if let Some(parent) = resolved.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -488,6 +493,10 @@ async fn execute_append_file(args: &serde_json::Value, agent_id: &str) -> Engine
         agent_id
     );
 
+    if content.len() as u64 >= disk::LARGE_WRITE_BYTES {
+        disk::check_free_space(&resolved, content.len() as u64)?;
+    }
+
     use std::io::Write;
     let mut file = std::fs::OpenOptions::new()
         .create(true)
@@ -20,6 +20,7 @@
 //   google_api           — generic Google API call (escape hatch)
 
 use crate::atoms::types::*;
+use crate::engine::util::safe_truncate;
 use log::info;
 use std::time::Duration;
 
@@ -84,7 +85,7 @@ async fn check_response(resp: reqwest::Response, api_name: &str) -> Result<Strin
             api_name,
             status.as_u16(),
             hint,
-            &body[..body.len().min(500)]
+            safe_truncate(&body, 500)
         ))
     }
 }
@@ -807,7 +808,7 @@ async fn drive_read(args: &serde_json::Value) -> Result<String, String> {
             "File: {} ({})\n\n{}",
             meta["name"].as_str().unwrap_or(""),
             mime,
-            &content[..content.len().min(50000)]
+            safe_truncate(&content, 50000)
         ))
     } else {
         // For non-Google files, return metadata (binary download not practical for agent)
@@ -1104,7 +1105,7 @@ async fn generic_api(args: &serde_json::Value) -> Result<String, String> {
     if body.len() > 50000 {
         Ok(format!(
             "{}... (truncated, {} total bytes)",
-            &body[..50000],
+            safe_truncate(&body, 50000),
             body.len()
         ))
     } else {
@@ -1,5 +1,5 @@
 // Paw Agent Engine — Integration tools
-// rest_api_call, webhook_send, image_generate
+// rest_api_call, webhook_send, image_generate, speak
 
 use crate::atoms::error::EngineResult;
 use crate::atoms::types::*;
@@ -45,17 +45,34 @@ pub fn definitions() -> Vec<ToolDefinition> {
             tool_type: "function".into(),
             function: FunctionDefinition {
                 name: "image_generate".into(),
-                description: "Generate an image from a text description using AI. Returns the file path of the saved image. Use detailed, descriptive prompts for best results.".into(),
+                description: "Generate an image from a text description using AI. The image is saved to your workspace; the result gives its path and an 'Image saved:' line to include in your reply so the chat shows it. Use detailed, descriptive prompts for best results.".into(),
                 parameters: serde_json::json!({
                     "type": "object",
                     "properties": {
                         "prompt": { "type": "string", "description": "Detailed text description of the image to generate." },
+                        "size": { "type": "string", "description": "Image size as WIDTHxHEIGHT (default 1024x1024). DALL-E 3 also supports 1792x1024 and 1024x1792." },
+                        "model": { "type": "string", "description": "Optional model override (e.g. 'dall-e-3', 'gpt-image-1', 'gemini-2.0-flash-exp'). Defaults to the configured image model." },
                         "filename": { "type": "string", "description": "Optional filename for the output image (without extension)." }
                     },
                     "required": ["prompt"]
                 }),
             },
         },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "speak".into(),
+                description: "Convert text to speech with ElevenLabs. The MP3 is saved to your workspace; the result gives its path and an 'Audio saved:' line to include in your reply so the chat shows a player. Keep text short — long text must be summarized first.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "text": { "type": "string", "description": "Plain text to speak (no markdown)." },
+                        "voice": { "type": "string", "description": "Optional ElevenLabs voice ID. Defaults to the configured voice." }
+                    },
+                    "required": ["text"]
+                }),
+            },
+        },
     ]
 }
 
@@ -78,7 +95,7 @@ const REST_API_SERVICES: &[&str] = &[
 ];
 
 /// Return definitions only for the given skill_id ("rest_api", "webhook", "image_gen",
-/// or a per-service ID like "linear", "stripe", etc.)
+/// "tts_sag", or a per-service ID like "linear", "stripe", etc.)
 pub fn definitions_for(skill_id: &str) -> Vec<ToolDefinition> {
     definitions()
         .into_iter()
@@ -86,6 +103,7 @@ pub fn definitions_for(skill_id: &str) -> Vec<ToolDefinition> {
             "rest_api" => d.function.name == "rest_api_call",
             "webhook" => d.function.name == "webhook_send",
             "image_gen" => d.function.name == "image_generate",
+            "tts_sag" => d.function.name == "speak",
             s if REST_API_SERVICES.contains(&s) => d.function.name == "rest_api_call",
             _ => false,
         })
@@ -96,6 +114,7 @@ pub async fn execute(
     name: &str,
     args: &serde_json::Value,
     app_handle: &tauri::AppHandle,
+    agent_id: &str,
 ) -> Option<Result<String, String>> {
     let skill_id = match name {
         "rest_api_call" => {
@@ -114,6 +133,7 @@ pub async fn execute(
         }
         "webhook_send" => "webhook".to_string(),
         "image_generate" => "image_gen".to_string(),
+        "speak" => "tts_sag".to_string(),
         _ => return None,
     };
     let creds = match super::get_skill_creds(&skill_id, app_handle) {
@@ -139,9 +159,8 @@ pub async fn execute(
         "webhook_send" => execute_webhook_send(args, &creds)
             .await
             .map_err(|e| e.to_string()),
-        "image_generate" => execute_image_generate(args, &creds)
-            .await
-            .map_err(|e| e.to_string()),
+        "image_generate" => execute_image_generate(args, &creds, app_handle, agent_id).await,
+        "speak" => execute_speak(args, &creds, app_handle, agent_id).await,
         _ => unreachable!(),
     })
 }
@@ -309,141 +328,103 @@ fn simple_hmac_hex(key: &str, data: &str) -> String {
 async fn execute_image_generate(
     args: &serde_json::Value,
     creds: &std::collections::HashMap<String, String>,
-) -> EngineResult<String> {
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    use crate::engine::image_gen::{self, ImageLimits, ImageRequest};
+
     let prompt = args["prompt"]
         .as_str()
+        .filter(|p| !p.trim().is_empty())
         .ok_or("image_generate: missing 'prompt'")?;
-    let filename = args["filename"].as_str().unwrap_or("");
-    let api_key = creds
-        .get("GEMINI_API_KEY")
-        .ok_or("Missing GEMINI_API_KEY credential")?;
-
-    info!(
-        "[skill:image_gen] Generating image for prompt: {}",
-        safe_truncate(prompt, 80)
-    );
+    let (width, height) =
+        image_gen::parse_size(args["size"].as_str().unwrap_or(image_gen::DEFAULT_SIZE))?;
+    let (backend, model) = image_gen::resolve_backend(creds, args["model"].as_str())?;
+    let request = ImageRequest {
+        prompt: prompt.to_string(),
+        model,
+        width,
+        height,
+    };
+    let cost = image_gen::check_limits(&request, &ImageLimits::from_creds(creds))?;
+    super::fetch::check_url_allowed(app_handle, &backend.endpoint(&request.model))?;
 
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(120))
-        .build()?;
-
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash-exp:generateContent?key={}",
-        api_key
-    );
-
-    let body = serde_json::json!({
-        "contents": [{ "parts": [{ "text": prompt }] }],
-        "generationConfig": { "responseModalities": ["TEXT", "IMAGE"] }
-    });
-
-    let resp = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&body)
-        .send()
-        .await?;
-
-    let status = resp.status().as_u16();
-    let resp_text = resp.text().await?;
-
-    if status >= 400 {
-        return Err(format!(
-            "Gemini API error (HTTP {}): {}",
-            status,
-            safe_truncate(&resp_text, 500)
-        )
-        .into());
-    }
-
-    let resp_json: serde_json::Value = serde_json::from_str(&resp_text)?;
-
-    let parts = resp_json
-        .get("candidates")
-        .and_then(|c| c.get(0))
-        .and_then(|c| c.get("content"))
-        .and_then(|c| c.get("parts"))
-        .and_then(|p| p.as_array())
-        .ok_or("Gemini response missing candidates/content/parts")?;
-
-    let mut image_data: Option<(String, String)> = None;
-    let mut text_response: Option<String> = None;
-
-    for part in parts {
-        if let Some(inline) = part.get("inlineData") {
-            let mime = inline["mimeType"].as_str().unwrap_or("image/png");
-            let data = inline["data"].as_str().unwrap_or("");
-            if !data.is_empty() {
-                image_data = Some((mime.to_string(), data.to_string()));
-            }
-        }
-        if let Some(text) = part.get("text").and_then(|t| t.as_str()) {
-            text_response = Some(text.to_string());
-        }
-    }
-
-    let (mime_type, base64_data) = image_data
-        .ok_or("Gemini did not return an image. The model may not support image generation for this prompt. Try a more descriptive prompt.")?;
-
-    let ext = match mime_type.as_str() {
-        "image/png" => "png",
-        "image/jpeg" | "image/jpg" => "jpg",
-        "image/webp" => "webp",
-        "image/gif" => "gif",
-        _ => "png",
-    };
-
-    let output_name = if filename.is_empty() {
-        let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
-        let slug: String = prompt
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == ' ')
-            .take(30)
-            .collect::<String>()
-            .trim()
-            .replace(' ', "_")
-            .to_lowercase();
-        format!("generated_{}_{}", ts, slug)
-    } else {
-        filename.to_string()
-    };
-
-    let output_dir = std::env::var("HOME")
-        .map(|h| std::path::PathBuf::from(h).join("Pictures").join("paw"))
-        .unwrap_or_else(|_| std::env::temp_dir().join("paw_images"));
-
-    std::fs::create_dir_all(&output_dir)?;
-
-    let output_path = output_dir.join(format!("{}.{}", output_name, ext));
-
-    use base64::Engine as _;
-    let bytes = base64::engine::general_purpose::STANDARD
-        .decode(&base64_data)
-        .map_err(|e| crate::atoms::error::EngineError::Other(e.to_string()))?;
-
-    std::fs::write(&output_path, &bytes)?;
+        .build()
+        .map_err(|e| e.to_string())?;
+    let image = image_gen::generate(&client, &backend, &request, |url| {
+        super::fetch::check_url_allowed(app_handle, url)
+    })
+    .await?;
 
-    let path_str = output_path.to_string_lossy().to_string();
-    let size_kb = bytes.len() / 1024;
+    let workspace = super::ensure_workspace(agent_id).map_err(|e| e.to_string())?;
+    let name = image_gen::output_name(prompt, args["filename"].as_str());
+    let path = image_gen::save_image(&workspace, &name, &image)
+        .map_err(|e| format!("Failed to save image: {}", e))?;
+    let path_str = path.to_string_lossy().to_string();
+    let size_kb = image.bytes.len() / 1024;
 
     info!(
-        "[skill:image_gen] Saved {} ({} KB) to {}",
-        mime_type, size_kb, path_str
+        "[skill:image_gen] Saved {} ({} KB, ~${:.3}) to {}",
+        image.mime_type, size_kb, cost, path_str
     );
 
     let mut result = format!(
-        "Image generated and saved to: {}\nSize: {} KB | Format: {}",
+        "Image saved: {}\nSize: {}x{} | {} KB | Format: {} | Model: {} | Est. cost: ${:.3}",
         path_str,
+        width,
+        height,
         size_kb,
-        ext.to_uppercase()
+        image_gen::extension_for(&image.mime_type).to_uppercase(),
+        request.model,
+        cost
     );
-    if let Some(text) = text_response {
-        result.push_str(&format!("\n\nModel notes: {}", text));
+    if let Some(notes) = image.notes {
+        result.push_str(&format!("\n\nModel notes: {}", notes));
     }
-
     Ok(result)
 }
 
+async fn execute_speak(
+    args: &serde_json::Value,
+    creds: &std::collections::HashMap<String, String>,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    use crate::engine::speech::{self, SpeechConfig};
+
+    let text =
+        crate::commands::tts::strip_markdown(args["text"].as_str().ok_or("speak: missing 'text'")?);
+    let config = SpeechConfig::from_creds(creds)?;
+    let chars = speech::check_text(&text, config.max_chars)?;
+    let voice = args["voice"]
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(&config.voice_id)
+        .to_string();
+    super::fetch::check_url_allowed(app_handle, &config.endpoint(&voice))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let audio = speech::synthesize(&client, &config, &voice, &text, None).await?;
+
+    let workspace = super::ensure_workspace(agent_id).map_err(|e| e.to_string())?;
+    let path = speech::save_audio(&workspace, &text, &audio)
+        .map_err(|e| format!("Failed to save audio: {}", e))?;
+
+    Ok(format!(
+        "Audio saved: {}\nVoice: {} | {} characters | {} KB | Format: MP3",
+        path.to_string_lossy(),
+        voice,
+        chars,
+        audio.len() / 1024
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,6 +469,13 @@ mod tests {
         assert_eq!(defs[0].function.name, "image_generate");
     }
 
+    #[test]
+    fn definitions_for_tts() {
+        let defs = definitions_for("tts_sag");
+        assert_eq!(defs.len(), 1);
+        assert_eq!(defs[0].function.name, "speak");
+    }
+
     #[test]
     fn definitions_for_unknown_returns_empty() {
         let defs = definitions_for("nonexistent_skill");
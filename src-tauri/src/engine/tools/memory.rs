@@ -6,6 +6,7 @@ use crate::atoms::types::*;
 use crate::engine::engram;
 use crate::engine::memory;
 use crate::engine::state::EngineState;
+use crate::engine::types::truncate_utf8;
 use crate::engine::util::safe_truncate;
 use log::{info, warn};
 use tauri::Manager;
@@ -259,7 +260,7 @@ async fn execute_memory_store(
     match result {
         Some(id) => Ok(format!(
             "Memory stored (id: {}). Use memory_search to recall it in future sessions.",
-            &id[..8.min(id.len())]
+            truncate_utf8(&id, 8)
         )),
         None => Ok("Memory deduplicated — a similar memory already exists.".into()),
     }
@@ -22,6 +22,7 @@
 //   microsoft_api           — generic Microsoft Graph API call (escape hatch)
 
 use crate::atoms::types::*;
+use crate::engine::util::safe_truncate;
 use log::info;
 use std::time::Duration;
 
@@ -81,7 +82,7 @@ async fn check_response(resp: reqwest::Response, api_name: &str) -> Result<Strin
             api_name,
             status.as_u16(),
             hint,
-            &body[..body.len().min(500)]
+            safe_truncate(&body, 500)
         ))
     }
 }
@@ -532,7 +533,7 @@ async fn mail_send(args: &serde_json::Value) -> Result<String, String> {
         Err(format!(
             "Outlook sendMail failed (HTTP {}): {}",
             status.as_u16(),
-            &body[..body.len().min(500)]
+            safe_truncate(&body, 500)
         ))
     }
 }
@@ -7,10 +7,14 @@
 
 use crate::atoms::error::EngineResult;
 use crate::atoms::types::*;
+use crate::engine::secret_scrub;
 use crate::engine::skills;
 use crate::engine::state::EngineState;
 use crate::engine::util::safe_truncate;
 use log::{debug, info};
+use openpawz_core::engine::tool_args;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 use tauri::Manager;
 
 pub mod agent_comms;
@@ -30,9 +34,13 @@ pub mod integrations;
 pub mod memory;
 pub mod microsoft;
 pub mod n8n;
+pub mod notes;
+pub mod notion;
+pub mod reminders;
 pub mod request_tools;
 pub mod service_api;
 pub mod skill_output;
+pub mod skill_state;
 pub mod skill_storage;
 pub mod skills_tools;
 pub mod solana;
@@ -40,6 +48,7 @@ pub mod soul;
 pub mod squads;
 pub mod tasks;
 pub mod telegram;
+pub mod todos;
 pub mod web;
 pub mod worker_delegate;
 
@@ -115,6 +124,9 @@ pub fn builtin_tools() -> Vec<ToolDefinition> {
     tools.extend(skills_tools::definitions());
     tools.extend(skill_output::definitions());
     tools.extend(skill_storage::definitions());
+    tools.extend(notes::definitions());
+    tools.extend(todos::definitions());
+    tools.extend(reminders::definitions());
     tools.extend(canvas::definitions());
     tools.extend(canvas_dashboards::definitions());
     tools.extend(canvas_templates::definitions());
@@ -141,31 +153,94 @@ pub fn mcp_tools(app_handle: &tauri::AppHandle) -> Vec<ToolDefinition> {
     }
 }
 
+/// Skills that contribute tools, with the definitions each one adds.
+const SKILL_TOOLS: &[(&str, fn() -> Vec<ToolDefinition>)] = &[
+    ("telegram", telegram::definitions),
+    ("rest_api", || integrations::definitions_for("rest_api")),
+    ("webhook", || integrations::definitions_for("webhook")),
+    ("image_gen", || integrations::definitions_for("image_gen")),
+    ("tts_sag", || integrations::definitions_for("tts_sag")),
+    ("discord", discord::definitions),
+    ("discourse", discourse::definitions),
+    ("notion", notion::definitions),
+    ("coinbase", coinbase::definitions),
+    ("solana_dex", solana::definitions),
+    ("dex", dex::definitions),
+    ("google_workspace", google::definitions),
+    ("microsoft_365", microsoft::definitions),
+    ("connected_services", service_api::definitions),
+];
+
 /// Return tools for enabled skills.
 pub fn skill_tools(enabled_skill_ids: &[String]) -> Vec<ToolDefinition> {
-    let mut tools = Vec::new();
-    for id in enabled_skill_ids {
-        match id.as_str() {
-            "telegram" => tools.extend(telegram::definitions()),
-            "rest_api" => tools.extend(integrations::definitions_for("rest_api")),
-            "webhook" => tools.extend(integrations::definitions_for("webhook")),
-            "image_gen" => tools.extend(integrations::definitions_for("image_gen")),
-            "discord" => tools.extend(discord::definitions()),
-            "discourse" => tools.extend(discourse::definitions()),
-            "coinbase" => tools.extend(coinbase::definitions()),
-            "solana_dex" => tools.extend(solana::definitions()),
-            "dex" => tools.extend(dex::definitions()),
-            "google_workspace" => tools.extend(google::definitions()),
-            "microsoft_365" => tools.extend(microsoft::definitions()),
-            "connected_services" => tools.extend(service_api::definitions()),
-            _ => {}
-        }
+    enabled_skill_ids
+        .iter()
+        .filter_map(|id| SKILL_TOOLS.iter().find(|(skill, _)| skill == id))
+        .flat_map(|(_, definitions)| definitions())
+        .collect()
+}
+
+/// Parameter schemas of the built-in and skill tools by name (first
+/// declaration wins). Both sets are fixed at compile time, so the map is
+/// built once; per-skill state tools are keyed by family, and MCP schemas
+/// live in the registry, which rebuilds them whenever a server changes.
+static DECLARED_SCHEMAS: LazyLock<HashMap<String, serde_json::Value>> = LazyLock::new(|| {
+    let skill_ids: Vec<String> = SKILL_TOOLS.iter().map(|(id, _)| id.to_string()).collect();
+    let state_families = skill_state::definitions_for("skill")
+        .into_iter()
+        .filter_map(|mut tool| {
+            let family = skill_state::split_tool(&tool.function.name)?.0.to_string();
+            tool.function.name = family;
+            Some(tool)
+        });
+    let mut schemas = HashMap::new();
+    for tool in builtin_tools()
+        .into_iter()
+        .chain(skill_tools(&skill_ids))
+        .chain(state_families)
+    {
+        schemas
+            .entry(tool.function.name)
+            .or_insert(tool.function.parameters);
     }
-    tools
+    schemas
+});
+
+/// The `parameters` schema `name` was declared with — built-in, skill or
+/// MCP — or None for tools nothing declares (validation is then skipped).
+fn declared_schema(name: &str, app_handle: &tauri::AppHandle) -> Option<serde_json::Value> {
+    let key = skill_state::split_tool(name).map_or(name, |(family, _)| family);
+    if let Some(schema) = DECLARED_SCHEMAS.get(key) {
+        return Some(schema.clone());
+    }
+    let state = app_handle.try_state::<EngineState>()?;
+    // Same as mcp_tools: if the registry is busy, skip rather than block
+    let registry = state.mcp_registry.try_lock().ok()?;
+    registry.tool_schema(name).cloned()
 }
 
 // ── Main executor ──────────────────────────────────────────────────────────
 
+tokio::task_local! {
+    /// Session the current run belongs to, for session-scoped tools.
+    static RUN_SESSION: String;
+}
+
+/// Run `fut` (a tool execution) on behalf of `session_id`, so tools that
+/// keep per-conversation state can find it via [`current_session`]
+/// instead of asking the model for an id it doesn't have.
+pub async fn in_session<F: std::future::Future>(session_id: &str, fut: F) -> F::Output {
+    RUN_SESSION.scope(session_id.to_string(), fut).await
+}
+
+/// The session of the run executing the current tool, if any.
+pub fn current_session() -> Option<String> {
+    RUN_SESSION
+        .try_with(|s| s.clone())
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
 /// Execute a single tool call and return the result.
 pub async fn execute_tool(
     tool_call: &crate::engine::types::ToolCall,
@@ -187,10 +262,10 @@ pub async fn execute_tool(
         args_str
     };
 
-    let args: serde_json::Value = match serde_json::from_str(args_str) {
+    let mut args: serde_json::Value = match serde_json::from_str(args_str) {
         Ok(v) => v,
         Err(parse_err) => {
-            let truncated = safe_truncate(args_str, 300);
+            let truncated = secret_scrub::scrub(safe_truncate(args_str, 300));
             log::warn!(
                 "[engine] Malformed tool args for '{}' — JSON parse failed: {}. Args: {}",
                 name,
@@ -212,6 +287,65 @@ pub async fn execute_tool(
         }
     };
 
+    // §Security: Skill sandbox — while a third-party skill is active for this
+    // agent, only the built-in tools it permits may run.
+    if let Some(state) = app_handle.try_state::<EngineState>() {
+        let policies = skills::active_skill_policies(&state.store, agent_id);
+        if let Err(reason) = skills::check_tool_permitted(&policies, name) {
+            log::warn!(
+                "[engine] Skill sandbox blocked '{}' for agent={}",
+                name,
+                agent_id
+            );
+            return ToolResult {
+                tool_call_id: tool_call.id.clone(),
+                output: format!("Error: {}", reason),
+                success: false,
+            };
+        }
+    }
+
+    // Declared schemas are authoritative: fix the usual stringly-typed
+    // quirks, then reject args that still don't match so the model can
+    // correct the call before any tool code runs.
+    if let Some(schema) = declared_schema(name, app_handle) {
+        let coerced = tool_args::coerce(&schema, &mut args);
+        if !coerced.is_empty() {
+            debug!(
+                "[engine] Coerced args for '{}': {}",
+                name,
+                coerced.join("; ")
+            );
+        }
+        if let Err(errors) = tool_args::validate(&schema, &args) {
+            log::warn!(
+                "[engine] Rejected args for '{}': {}",
+                name,
+                errors.join("; ")
+            );
+            return ToolResult {
+                tool_call_id: tool_call.id.clone(),
+                output: tool_args::rejection_message(name, &errors),
+                success: false,
+            };
+        }
+    }
+
+    // Idempotent reads may be answered from the (opt-in) result cache.
+    let tool_cache = app_handle
+        .try_state::<EngineState>()
+        .map(|state| state.tool_cache.clone());
+    if let Some(cache) = &tool_cache {
+        if let Some(output) = cache.lock().get(name, &args, agent_id) {
+            info!("[engine] Tool cache hit: {} agent={}", name, agent_id);
+            return ToolResult {
+                tool_call_id: tool_call.id.clone(),
+                output,
+                success: true,
+            };
+        }
+    }
+
     // fetch & exec: When a worker model is configured, delegate these to the
     // worker (Foreman) so the main model doesn't spend API tokens on
     // data-fetching rounds. The worker is typically a cheaper model.
@@ -220,6 +354,11 @@ pub async fn execute_tool(
         if let Some(worker_result) =
             worker_delegate::delegate_to_worker(tool_call, app_handle, agent_id).await
         {
+            // The worker may have run any tool — drop every cached read,
+            // and don't cache its summary
+            if let Some(cache) = &tool_cache {
+                cache.lock().flush();
+            }
             return worker_result;
         }
         // Worker delegation failed — fall through to direct execution
@@ -242,6 +381,10 @@ pub async fn execute_tool(
         .or(skills_tools::execute(name, &args, app_handle, agent_id).await)
         .or(skill_output::execute(name, &args, app_handle, agent_id).await)
         .or(skill_storage::execute(name, &args, app_handle, agent_id).await)
+        .or(skill_state::execute(name, &args, app_handle, agent_id).await)
+        .or(notes::execute(name, &args, app_handle, agent_id).await)
+        .or(todos::execute(name, &args, app_handle, agent_id).await)
+        .or(reminders::execute(name, &args, app_handle, agent_id).await)
         .or(canvas::execute(name, &args, app_handle, agent_id).await)
         .or(canvas_dashboards::execute(name, &args, app_handle, agent_id).await)
         .or(canvas_templates::execute(name, &args, app_handle, agent_id).await)
@@ -249,13 +392,14 @@ pub async fn execute_tool(
         .or(squads::execute(name, &args, app_handle, agent_id).await)
         .or(request_tools::execute(name, &args, app_handle, agent_id).await)
         .or(telegram::execute(name, &args, app_handle).await)
-        .or(integrations::execute(name, &args, app_handle).await)
+        .or(integrations::execute(name, &args, app_handle, agent_id).await)
         .or(n8n::execute(name, &args, app_handle).await)
         .or(coinbase::execute(name, &args, app_handle).await)
         .or(solana::execute(name, &args, app_handle).await)
         .or(dex::execute(name, &args, app_handle).await)
         .or(discord::execute(name, &args, app_handle).await)
         .or(discourse::execute(name, &args, app_handle).await)
+        .or(notion::execute(name, &args, app_handle).await)
         .or(google::execute(name, &args, app_handle).await)
         .or(microsoft::execute(name, &args, app_handle).await)
         .or(service_api::execute(name, &args, app_handle).await);
@@ -292,15 +436,27 @@ pub async fn execute_tool(
         None => Err(format!("Unknown tool: {}", name)),
     };
 
+    if let Some(cache) = &tool_cache {
+        cache.lock().note_executed(name);
+    }
+
+    // §Security: Redact any known credential values before the output is
+    // logged, stored in the audit trail, shown in the UI, or fed to the model.
     match result {
-        Ok(output) => ToolResult {
-            tool_call_id: tool_call.id.clone(),
-            output,
-            success: true,
-        },
+        Ok(output) => {
+            let output = secret_scrub::scrub(&output);
+            if let Some(cache) = &tool_cache {
+                cache.lock().put(name, &args, agent_id, &output);
+            }
+            ToolResult {
+                tool_call_id: tool_call.id.clone(),
+                output,
+                success: true,
+            }
+        }
         Err(err) => ToolResult {
             tool_call_id: tool_call.id.clone(),
-            output: format!("Error: {}", err),
+            output: secret_scrub::scrub(&format!("Error: {}", err)),
             success: false,
         },
     }
@@ -322,6 +478,22 @@ pub fn ensure_workspace(agent_id: &str) -> EngineResult<std::path::PathBuf> {
     Ok(ws)
 }
 
+/// Whether `path` is a file directly inside `{workspace}/{subdir}/` of some
+/// agent — used to serve tool output (images, audio) to the UI without
+/// exposing anything else on disk.
+pub fn is_workspace_output(path: &std::path::Path, subdir: &str) -> bool {
+    let workspaces = crate::engine::paths::paw_data_dir().join("workspaces");
+    let (Ok(path), Ok(root)) = (path.canonicalize(), workspaces.canonicalize()) else {
+        return false;
+    };
+    let Ok(rel) = path.strip_prefix(&root) else {
+        return false;
+    };
+    // {agent}/{subdir}/{file}
+    let parts: Vec<_> = rel.components().collect();
+    parts.len() == 3 && parts[1].as_os_str() == subdir && path.is_file()
+}
+
 // ── Shared credential helper (used by skill modules) ──────────────────────
 
 /// Check that a skill is enabled and return its decrypted credentials.
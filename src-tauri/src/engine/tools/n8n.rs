@@ -8,6 +8,7 @@
 use crate::atoms::types::*;
 use crate::engine::channels;
 use crate::engine::state::EngineState;
+use crate::engine::util::safe_truncate;
 use log::info;
 use std::time::Duration;
 use tauri::Manager;
@@ -746,7 +747,7 @@ async fn execute_search_ncnodes(args: &serde_json::Value) -> Result<String, Stri
             if pkg.last_updated.is_empty() {
                 "unknown".to_string()
             } else {
-                pkg.last_updated[..10.min(pkg.last_updated.len())].to_string()
+                safe_truncate(&pkg.last_updated, 10).to_string()
             },
         ));
         if let Some(repo) = &pkg.repository_url {
@@ -0,0 +1,199 @@
+// tools/notes.rs — Agent scratchpad notes.
+// Exact-key notes the agent stashes for itself (plans, progress, ids it
+// needs later). Scoped to the calling agent; optionally to the session
+// the agent is running in.
+
+use crate::atoms::types::*;
+use crate::engine::state::EngineState;
+use log::info;
+use tauri::Manager;
+
+// ── Tool definitions ───────────────────────────────────────────────────
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    let session_param = serde_json::json!({
+        "type": "boolean",
+        "description": "Keep the note private to this conversation (default: shared across your sessions)"
+    });
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "note_write".into(),
+                description: "Write a scratchpad note under an exact key, replacing any \
+                    previous value. Use it to stash intermediate state (plans, progress, ids) \
+                    you'll need in later turns. Lighter than memory: no search, exact keys only."
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Note key (e.g. 'plan', 'last_page')"
+                        },
+                        "value": {
+                            "type": "string",
+                            "description": "Note content (plain text or JSON string)"
+                        },
+                        "this_session": session_param
+                    },
+                    "required": ["key", "value"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "note_read".into(),
+                description: "Read a scratchpad note by its exact key.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Note key to read"
+                        },
+                        "this_session": session_param
+                    },
+                    "required": ["key"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "note_list".into(),
+                description: "List all of your scratchpad notes.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "this_session": session_param
+                    }
+                }),
+            },
+        },
+    ]
+}
+
+// ── Execute dispatcher ─────────────────────────────────────────────────
+
+pub async fn execute(
+    name: &str,
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Option<Result<String, String>> {
+    Some(match name {
+        "note_write" => execute_write(args, app_handle, agent_id),
+        "note_read" => execute_read(args, app_handle, agent_id),
+        "note_list" => execute_list(args, app_handle, agent_id),
+        _ => return None,
+    })
+}
+
+// ── Private handlers ───────────────────────────────────────────────────
+
+/// The run's own session when the model asked for session scope.
+/// The id comes from the run context, never from the model.
+fn session_arg(args: &serde_json::Value) -> Result<Option<String>, String> {
+    if !args["this_session"].as_bool().unwrap_or(false) {
+        return Ok(None);
+    }
+    super::current_session()
+        .map(Some)
+        .ok_or_else(|| "No active session — omit this_session to use an agent-wide note".into())
+}
+
+fn execute_write(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let key = args["key"]
+        .as_str()
+        .ok_or("Missing required parameter: key")?;
+    let value = args["value"]
+        .as_str()
+        .ok_or("Missing required parameter: value")?;
+
+    let session = session_arg(args)?;
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    state
+        .store
+        .note_write(agent_id, session.as_deref(), key, value)?;
+
+    info!("[engine] Note write: {}:{}", agent_id, key);
+    Ok(format!("Saved note '{key}'."))
+}
+
+fn execute_read(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let key = args["key"]
+        .as_str()
+        .ok_or("Missing required parameter: key")?;
+
+    let session = session_arg(args)?;
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    match state.store.note_read(agent_id, session.as_deref(), key)? {
+        Some(note) => Ok(note.value),
+        None => Ok(format!("No note found for key '{key}'.")),
+    }
+}
+
+fn execute_list(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let session = session_arg(args)?;
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let notes = state.store.note_list(agent_id, session.as_deref())?;
+
+    if notes.is_empty() {
+        return Ok("No notes yet.".into());
+    }
+
+    let json = serde_json::to_string_pretty(&notes).unwrap_or_default();
+    Ok(json)
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_valid() {
+        let defs = definitions();
+        let names: Vec<_> = defs.iter().map(|d| d.function.name.as_str()).collect();
+        assert_eq!(names, ["note_write", "note_read", "note_list"]);
+        for d in &defs {
+            assert_eq!(d.tool_type, "function");
+            assert!(d.function.parameters["properties"]["this_session"].is_object());
+            assert!(d.function.parameters["properties"]["session_id"].is_null());
+        }
+    }
+
+    #[tokio::test]
+    async fn session_scope_comes_from_the_run() {
+        let args = serde_json::json!({ "this_session": true });
+        assert!(session_arg(&args).is_err());
+        let scoped = super::super::in_session("s-42", async { session_arg(&args) }).await;
+        assert_eq!(scoped.unwrap().as_deref(), Some("s-42"));
+        let shared = super::super::in_session("s-42", async {
+            session_arg(&serde_json::json!({ "session_id": "other" }))
+        })
+        .await;
+        assert_eq!(shared.unwrap(), None);
+    }
+}
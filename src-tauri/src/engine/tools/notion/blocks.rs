@@ -0,0 +1,377 @@
+// notion/blocks.rs — Page content
+//
+// Tools:
+//   notion_get_blocks     — read the content (child blocks) of a page or block
+//   notion_append_blocks  — append text content to a page or block
+//
+// Content goes in as plain text with light Markdown-style line prefixes and
+// comes out the same way, so the model never builds block JSON by hand.
+
+use super::properties::rich_text;
+use super::{page_size, resolve_id, NotionClient};
+use crate::atoms::error::EngineResult;
+use crate::atoms::types::*;
+use log::info;
+use reqwest::Method;
+use serde_json::{json, Value};
+
+pub(crate) const TOOLS: &[&str] = &["notion_get_blocks", "notion_append_blocks"];
+
+/// Notion accepts at most 100 children per append or create request.
+pub(crate) const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_get_blocks".into(),
+                description: "Read the content of a Notion page (or the children of a block) as text, one line per block with its block ID.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "block_id": { "type": "string", "description": "Page or block ID or URL." },
+                        "page_size": { "type": "integer", "description": "Max blocks (1-100, default 100)." },
+                        "start_cursor": { "type": "string", "description": "Cursor from a previous call's 'next_cursor' to get the next page." }
+                    },
+                    "required": ["block_id"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_append_blocks".into(),
+                description: "Append content to the end of a Notion page (or inside a block). Write plain text: lines starting with '# ', '## ', '### ' become headings, '- ' bullets, '1. ' numbered items, '[ ] '/'[x] ' to-dos, '> ' quotes, '---' a divider, and ``` fences code blocks. Other lines become paragraphs.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "block_id": { "type": "string", "description": "Page or block ID or URL to append to." },
+                        "content": { "type": "string", "description": "Text to append." }
+                    },
+                    "required": ["block_id", "content"]
+                }),
+            },
+        },
+    ]
+}
+
+pub async fn execute(name: &str, args: &Value, client: &NotionClient) -> EngineResult<String> {
+    match name {
+        "notion_get_blocks" => exec_get(args, client).await,
+        "notion_append_blocks" => exec_append(args, client).await,
+        _ => Err(format!("Unknown Notion block tool: {}", name).into()),
+    }
+}
+
+// ── Text → blocks ──────────────────────────────────────────────────────
+
+fn text_block(kind: &str, text: &str) -> Value {
+    json!({ "object": "block", "type": kind, kind: { "rich_text": rich_text(text) } })
+}
+
+/// Blocks for `text`, one per non-empty line (a ``` fence becomes one code
+/// block).
+pub(crate) fn text_to_blocks(text: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        if let Some((language, lines)) = code.as_mut() {
+            if line.trim_start().starts_with("```") {
+                blocks.push(json!({
+                    "object": "block",
+                    "type": "code",
+                    "code": { "rich_text": rich_text(&lines.join("\n")), "language": language }
+                }));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            let lang = lang.trim();
+            let language = if lang.is_empty() { "plain text" } else { lang };
+            code = Some((language.to_lowercase(), Vec::new()));
+            continue;
+        }
+
+        let block = if let Some(t) = trimmed.strip_prefix("### ") {
+            text_block("heading_3", t)
+        } else if let Some(t) = trimmed.strip_prefix("## ") {
+            text_block("heading_2", t)
+        } else if let Some(t) = trimmed.strip_prefix("# ") {
+            text_block("heading_1", t)
+        } else if trimmed == "---" {
+            json!({ "object": "block", "type": "divider", "divider": {} })
+        } else if let Some((checked, t)) = to_do(trimmed) {
+            json!({
+                "object": "block",
+                "type": "to_do",
+                "to_do": { "rich_text": rich_text(t), "checked": checked }
+            })
+        } else if let Some(t) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            text_block("bulleted_list_item", t)
+        } else if let Some(t) = numbered(trimmed) {
+            text_block("numbered_list_item", t)
+        } else if let Some(t) = trimmed.strip_prefix("> ") {
+            text_block("quote", t)
+        } else {
+            text_block("paragraph", trimmed)
+        };
+        blocks.push(block);
+    }
+
+    // An unclosed fence still keeps its lines
+    if let Some((language, lines)) = code {
+        blocks.push(json!({
+            "object": "block",
+            "type": "code",
+            "code": { "rich_text": rich_text(&lines.join("\n")), "language": language }
+        }));
+    }
+    blocks
+}
+
+/// "[ ] task", "[x] task", optionally after a "- " bullet.
+fn to_do(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix("- ").unwrap_or(line);
+    if let Some(t) = rest.strip_prefix("[ ] ") {
+        Some((false, t))
+    } else {
+        rest.strip_prefix("[x] ")
+            .or_else(|| rest.strip_prefix("[X] "))
+            .map(|t| (true, t))
+    }
+}
+
+/// "1. item" → "item".
+fn numbered(line: &str) -> Option<&str> {
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ")
+}
+
+// ── Blocks → text ──────────────────────────────────────────────────────
+
+/// One block as a line of text, with the prefix `text_to_blocks` reads.
+pub(crate) fn block_text(block: &Value) -> String {
+    let kind = block["type"].as_str().unwrap_or("");
+    let text: String = block[kind]["rich_text"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t["plain_text"].as_str().or(t["text"]["content"].as_str()))
+        .collect();
+    match kind {
+        "heading_1" => format!("# {}", text),
+        "heading_2" => format!("## {}", text),
+        "heading_3" => format!("### {}", text),
+        "bulleted_list_item" => format!("- {}", text),
+        "numbered_list_item" => format!("1. {}", text),
+        "to_do" => {
+            let mark = if block["to_do"]["checked"].as_bool().unwrap_or(false) {
+                "x"
+            } else {
+                " "
+            };
+            format!("[{}] {}", mark, text)
+        }
+        "quote" => format!("> {}", text),
+        "code" => format!(
+            "```{}\n{}\n```",
+            block["code"]["language"].as_str().unwrap_or(""),
+            text
+        ),
+        "divider" => "---".into(),
+        "child_page" => format!(
+            "[page] {}",
+            block["child_page"]["title"].as_str().unwrap_or("")
+        ),
+        "child_database" => format!(
+            "[database] {}",
+            block["child_database"]["title"].as_str().unwrap_or("")
+        ),
+        "paragraph" | "callout" | "toggle" => text,
+        other => format!("[{}]", other),
+    }
+}
+
+/// The first page of a block's children as text (empty when it has none).
+pub(crate) async fn read_children(client: &NotionClient, block_id: &str) -> EngineResult<String> {
+    let resp = client
+        .request(
+            Method::GET,
+            &format!(
+                "/blocks/{}/children?page_size={}",
+                block_id, MAX_BLOCKS_PER_REQUEST
+            ),
+            None,
+        )
+        .await?;
+    let mut lines: Vec<String> = resp["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(block_text)
+        .collect();
+    if resp["has_more"].as_bool().unwrap_or(false) {
+        lines.push("… (more content — use notion_get_blocks with next_cursor)".into());
+        if let Some(cursor) = resp["next_cursor"].as_str() {
+            lines.push(format!("next_cursor: {}", cursor));
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Append `blocks` under `block_id`, 100 per request. Returns how many were
+/// appended.
+pub(crate) async fn append_children(
+    client: &NotionClient,
+    block_id: &str,
+    blocks: &[Value],
+) -> EngineResult<usize> {
+    for chunk in blocks.chunks(MAX_BLOCKS_PER_REQUEST) {
+        client
+            .request(
+                Method::PATCH,
+                &format!("/blocks/{}/children", block_id),
+                Some(&json!({ "children": chunk })),
+            )
+            .await?;
+    }
+    Ok(blocks.len())
+}
+
+async fn exec_get(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let block_id = resolve_id(args, "block_id")?;
+    let mut path = format!(
+        "/blocks/{}/children?page_size={}",
+        block_id,
+        page_size(args, MAX_BLOCKS_PER_REQUEST as u64)
+    );
+    if let Some(cursor) = args["start_cursor"].as_str() {
+        path.push_str(&format!("&start_cursor={}", urlencoding::encode(cursor)));
+    }
+    let resp = client.request(Method::GET, &path, None).await?;
+    let blocks = resp["results"].as_array().cloned().unwrap_or_default();
+    if blocks.is_empty() {
+        return Ok("No content.".into());
+    }
+
+    let mut out = String::new();
+    for block in &blocks {
+        let children = if block["has_children"].as_bool().unwrap_or(false) {
+            " (has children)"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "{}\n  ↳ id: {}{}\n",
+            block_text(block),
+            block["id"].as_str().unwrap_or("?"),
+            children
+        ));
+    }
+    if let Some(cursor) = resp["next_cursor"]
+        .as_str()
+        .filter(|_| resp["has_more"].as_bool().unwrap_or(false))
+    {
+        out.push_str(&format!("\nMore blocks — next_cursor: {}\n", cursor));
+    }
+    Ok(out)
+}
+
+async fn exec_append(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let block_id = resolve_id(args, "block_id")?;
+    let content = args["content"].as_str().ok_or("Missing 'content'")?;
+    let blocks = text_to_blocks(content);
+    if blocks.is_empty() {
+        return Err("Nothing to append — 'content' is empty".into());
+    }
+    let count = append_children(client, &block_id, &blocks).await?;
+    info!("[notion] Appended {} block(s) to {}", count, block_id);
+    Ok(format!("Appended {} block(s) to {}", count, block_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(blocks: &[Value]) -> Vec<&str> {
+        blocks.iter().map(|b| b["type"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn maps_line_prefixes_to_block_types() {
+        let text = "# Plan\n\nIntro line\n## Tasks\n- [ ] draft\n[x] review\n- ship it\n* tell people\n1. first\n12. twelfth\n> quoted\n---\n### Notes\n1.5 is not a list";
+        let blocks = text_to_blocks(text);
+        assert_eq!(
+            kinds(&blocks),
+            vec![
+                "heading_1",
+                "paragraph",
+                "heading_2",
+                "to_do",
+                "to_do",
+                "bulleted_list_item",
+                "bulleted_list_item",
+                "numbered_list_item",
+                "numbered_list_item",
+                "quote",
+                "divider",
+                "heading_3",
+                "paragraph",
+            ]
+        );
+        assert_eq!(blocks[3]["to_do"]["checked"], json!(false));
+        assert_eq!(blocks[4]["to_do"]["checked"], json!(true));
+        assert_eq!(
+            blocks[8]["numbered_list_item"]["rich_text"][0]["text"]["content"],
+            json!("twelfth")
+        );
+        assert_eq!(
+            blocks[9]["quote"]["rich_text"][0]["text"]["content"],
+            json!("quoted")
+        );
+    }
+
+    #[test]
+    fn fences_become_one_code_block() {
+        let blocks = text_to_blocks("Run:\n```bash\ncargo test\n\ncargo build\n```\nDone");
+        assert_eq!(kinds(&blocks), vec!["paragraph", "code", "paragraph"]);
+        assert_eq!(blocks[1]["code"]["language"], json!("bash"));
+        assert_eq!(
+            blocks[1]["code"]["rich_text"][0]["text"]["content"],
+            json!("cargo test\n\ncargo build")
+        );
+    }
+
+    #[test]
+    fn blocks_render_back_to_the_same_text() {
+        let text = "# Plan\n- [ ] draft\n- ship it\n1. first\n> quoted\n---";
+        let rendered: Vec<String> = text_to_blocks(text).iter().map(block_text).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "# Plan",
+                "[ ] draft",
+                "- ship it",
+                "1. first",
+                "> quoted",
+                "---"
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+// notion/databases.rs — Search and database queries
+//
+// Tools:
+//   notion_search          — search pages and databases shared with the integration
+//   notion_list_databases  — list databases with their property schema
+//   notion_query_database  — query rows of a database (filter, sort, paginate)
+
+use super::properties::{page_title, property_text};
+use super::{page_size, resolve_id, NotionClient};
+use crate::atoms::error::EngineResult;
+use crate::atoms::types::*;
+use reqwest::Method;
+use serde_json::{json, Map, Value};
+
+pub(crate) const TOOLS: &[&str] = &[
+    "notion_search",
+    "notion_list_databases",
+    "notion_query_database",
+];
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_search".into(),
+                description: "Search the Notion workspace by title. Only pages and databases shared with the integration are visible. Returns titles, IDs, and URLs.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string", "description": "Text to match against titles. Omit to list recently edited items." },
+                        "object": { "type": "string", "enum": ["page", "database"], "description": "Only return pages or only databases. Omit for both." },
+                        "page_size": { "type": "integer", "description": "Max results (1-100, default 20)." },
+                        "start_cursor": { "type": "string", "description": "Cursor from a previous call's 'next_cursor' to get the next page." }
+                    }
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_list_databases".into(),
+                description: "List Notion databases shared with the integration, with each database's properties and their types. Call this before creating pages so you know the property names.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string", "description": "Only databases whose title matches this text." },
+                        "page_size": { "type": "integer", "description": "Max databases (1-100, default 20)." }
+                    }
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_query_database".into(),
+                description: "Query the rows (pages) of a Notion database. Supports Notion's filter and sorts objects, e.g. filter {\"property\": \"Status\", \"select\": {\"equals\": \"Done\"}}. Returns each row's title, ID, and property values.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "database_id": { "type": "string", "description": "Database ID or URL." },
+                        "filter": { "type": "object", "description": "Notion filter object (see the Notion API docs for database queries)." },
+                        "sorts": { "type": "array", "items": { "type": "object" }, "description": "Notion sort objects, e.g. [{\"property\": \"Due\", \"direction\": \"ascending\"}]." },
+                        "page_size": { "type": "integer", "description": "Max rows (1-100, default 25)." },
+                        "start_cursor": { "type": "string", "description": "Cursor from a previous call's 'next_cursor' to get the next page." }
+                    },
+                    "required": ["database_id"]
+                }),
+            },
+        },
+    ]
+}
+
+pub async fn execute(name: &str, args: &Value, client: &NotionClient) -> EngineResult<String> {
+    match name {
+        "notion_search" => exec_search(args, client).await,
+        "notion_list_databases" => exec_list_databases(args, client).await,
+        "notion_query_database" => exec_query(args, client).await,
+        _ => Err(format!("Unknown Notion database tool: {}", name).into()),
+    }
+}
+
+/// Plain text of a database's title.
+fn database_title(db: &Value) -> String {
+    let title: String = db["title"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t["plain_text"].as_str())
+        .collect();
+    if title.is_empty() {
+        "(untitled database)".into()
+    } else {
+        title
+    }
+}
+
+/// Appended when a list has more results behind a cursor.
+fn next_cursor_line(resp: &Value) -> String {
+    match resp["next_cursor"].as_str() {
+        Some(cursor) if resp["has_more"].as_bool().unwrap_or(false) => {
+            format!("\nMore results — next_cursor: {}\n", cursor)
+        }
+        _ => String::new(),
+    }
+}
+
+fn search_body(args: &Value, object: Option<&str>, default_size: u64) -> Value {
+    let mut body = Map::new();
+    if let Some(q) = args["query"].as_str().filter(|q| !q.trim().is_empty()) {
+        body.insert("query".into(), json!(q));
+    }
+    if let Some(object) = object {
+        body.insert(
+            "filter".into(),
+            json!({ "property": "object", "value": object }),
+        );
+    }
+    if let Some(cursor) = args["start_cursor"].as_str() {
+        body.insert("start_cursor".into(), json!(cursor));
+    }
+    body.insert("page_size".into(), json!(page_size(args, default_size)));
+    Value::Object(body)
+}
+
+async fn exec_search(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let body = search_body(args, args["object"].as_str(), 20);
+    let resp = client.request(Method::POST, "/search", Some(&body)).await?;
+    let results = resp["results"].as_array().cloned().unwrap_or_default();
+    if results.is_empty() {
+        return Ok("No matching pages or databases. Items must be shared with the integration (••• → Connections) to be found.".into());
+    }
+
+    let mut out = format!("**{} result(s)**\n\n", results.len());
+    for item in &results {
+        let (kind, title) = if item["object"] == "database" {
+            ("database", database_title(item))
+        } else {
+            ("page", page_title(item))
+        };
+        out.push_str(&format!(
+            "• [{}] **{}** (id: {})\n  {}\n",
+            kind,
+            title,
+            item["id"].as_str().unwrap_or("?"),
+            item["url"].as_str().unwrap_or("")
+        ));
+    }
+    out.push_str(&next_cursor_line(&resp));
+    Ok(out)
+}
+
+async fn exec_list_databases(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let body = search_body(args, Some("database"), 20);
+    let resp = client.request(Method::POST, "/search", Some(&body)).await?;
+    let databases = resp["results"].as_array().cloned().unwrap_or_default();
+    if databases.is_empty() {
+        return Ok("No databases are shared with the integration. Share one via ••• → Connections in Notion.".into());
+    }
+
+    let mut out = format!("**{} database(s)**\n\n", databases.len());
+    for db in &databases {
+        out.push_str(&format!(
+            "• **{}** (id: {})\n",
+            database_title(db),
+            db["id"].as_str().unwrap_or("?")
+        ));
+        if let Some(props) = db["properties"].as_object() {
+            let mut schema: Vec<String> = props
+                .iter()
+                .map(|(name, p)| format!("{} ({})", name, p["type"].as_str().unwrap_or("?")))
+                .collect();
+            schema.sort();
+            out.push_str(&format!("  Properties: {}\n", schema.join(", ")));
+        }
+    }
+    out.push_str(&next_cursor_line(&resp));
+    Ok(out)
+}
+
+async fn exec_query(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let database_id = resolve_id(args, "database_id")?;
+    let mut body = Map::new();
+    for key in ["filter", "sorts", "start_cursor"] {
+        if !args[key].is_null() {
+            body.insert(key.into(), args[key].clone());
+        }
+    }
+    body.insert("page_size".into(), json!(page_size(args, 25)));
+
+    let resp = client
+        .request(
+            Method::POST,
+            &format!("/databases/{}/query", database_id),
+            Some(&Value::Object(body)),
+        )
+        .await?;
+    let rows = resp["results"].as_array().cloned().unwrap_or_default();
+    if rows.is_empty() {
+        return Ok("No rows match.".into());
+    }
+
+    let mut out = format!("**{} row(s)**\n\n", rows.len());
+    for row in &rows {
+        out.push_str(&format!(
+            "• **{}** (id: {})\n",
+            page_title(row),
+            row["id"].as_str().unwrap_or("?")
+        ));
+        if let Some(props) = row["properties"].as_object() {
+            let mut fields: Vec<String> = props
+                .iter()
+                .filter(|(_, p)| p["type"] != "title")
+                .filter_map(|(name, p)| {
+                    let text = property_text(p);
+                    (!text.is_empty()).then(|| format!("{}: {}", name, text))
+                })
+                .collect();
+            fields.sort();
+            if !fields.is_empty() {
+                out.push_str(&format!("  {}\n", fields.join(" | ")));
+            }
+        }
+    }
+    out.push_str(&next_cursor_line(&resp));
+    Ok(out)
+}
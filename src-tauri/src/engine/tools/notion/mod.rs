@@ -0,0 +1,258 @@
+// Paw Agent Engine — Notion Tools (Atomic Module)
+//
+// Notion workspace access via the public REST API.
+// Each sub-module handles one domain:
+//
+//   databases  — search, list databases, query a database
+//   pages      — get, create (in a database or under a page), update
+//   blocks     — read page content, append blocks
+//   properties — typed property payloads (title, rich_text, select, date…)
+//
+// Shared helpers (token resolution, API client, rate-limit retry) live here.
+
+pub mod blocks;
+pub mod databases;
+pub mod pages;
+pub mod properties;
+
+use crate::atoms::error::EngineResult;
+use crate::atoms::types::*;
+use crate::engine::http::{parse_retry_after, retry_delay};
+use crate::engine::state::EngineState;
+use crate::engine::util::safe_truncate;
+use log::{info, warn};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::Manager;
+
+pub(crate) const NOTION_API: &str = "https://api.notion.com/v1";
+/// API version sent as the Notion-Version header.
+const NOTION_VERSION: &str = "2022-06-28";
+
+// ── Public API (called by tools/mod.rs) ────────────────────────────────
+
+/// All Notion tool definitions across sub-modules.
+pub fn definitions() -> Vec<ToolDefinition> {
+    let mut defs = Vec::new();
+    defs.extend(databases::definitions());
+    defs.extend(pages::definitions());
+    defs.extend(blocks::definitions());
+    defs
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Databases,
+    Pages,
+    Blocks,
+}
+
+/// The sub-module that owns `name`, or None for anything else.
+fn route(name: &str) -> Option<Route> {
+    if databases::TOOLS.contains(&name) {
+        Some(Route::Databases)
+    } else if pages::TOOLS.contains(&name) {
+        Some(Route::Pages)
+    } else if blocks::TOOLS.contains(&name) {
+        Some(Route::Blocks)
+    } else {
+        None
+    }
+}
+
+/// Route a tool call to the correct sub-module executor.
+pub async fn execute(
+    name: &str,
+    args: &Value,
+    app_handle: &tauri::AppHandle,
+) -> Option<Result<String, String>> {
+    let route = route(name)?;
+    let client = match get_token(app_handle) {
+        Ok(token) => NotionClient::new(&token),
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    let result = match route {
+        Route::Databases => databases::execute(name, args, &client).await,
+        Route::Pages => pages::execute(name, args, &client).await,
+        Route::Blocks => blocks::execute(name, args, &client).await,
+    };
+    Some(result.map_err(|e| e.to_string()))
+}
+
+// ── Shared helpers ─────────────────────────────────────────────────────
+
+/// Resolve the Notion integration token from the skill vault.
+pub(crate) fn get_token(app_handle: &tauri::AppHandle) -> EngineResult<String> {
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let creds = crate::engine::skills::get_skill_credentials(&state.store, "notion")
+        .map_err(|e| format!("Failed to get Notion credentials: {}", e))?;
+    let token = creds
+        .get("NOTION_API_KEY")
+        .cloned()
+        .ok_or("NOTION_API_KEY not found in skill vault. Enable the Notion skill and add your integration secret in Settings → Skills → Notion.")?;
+    if token.is_empty() {
+        return Err("Notion integration secret is empty".into());
+    }
+    Ok(token)
+}
+
+/// HTTP client carrying the bearer token and API version.
+pub(crate) struct NotionClient {
+    client: reqwest::Client,
+    auth: String,
+}
+
+impl NotionClient {
+    pub(crate) fn new(token: &str) -> Self {
+        NotionClient {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            auth: format!("Bearer {}", token),
+        }
+    }
+
+    fn build(
+        &self,
+        method: &reqwest::Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> reqwest::RequestBuilder {
+        let mut req = self
+            .client
+            .request(method.clone(), url)
+            .header("Authorization", &self.auth)
+            .header("Notion-Version", NOTION_VERSION)
+            .header("Content-Type", "application/json");
+        if let Some(b) = body {
+            req = req.json(b);
+        }
+        req
+    }
+
+    /// Make a Notion API request (`path` relative to /v1) with automatic
+    /// rate-limit retry (once, after Retry-After).
+    pub(crate) async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> EngineResult<Value> {
+        let url = format!("{}{}", NOTION_API, path);
+        info!("[notion] {} {}", method, path);
+
+        let resp = self
+            .build(&method, &url, body)
+            .send()
+            .await
+            .map_err(|e| format!("HTTP error: {}", e))?;
+        let resp = if resp.status().as_u16() == 429 {
+            // Rate limited (~3 requests/s per integration) — wait and retry
+            // once; retry_delay caps whatever Retry-After the server sent
+            let retry_after = resp
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            let waited = retry_delay(0, retry_after).await;
+            warn!("[notion] Rate limited, waited {:.1}s", waited.as_secs_f64());
+            self.build(&method, &url, body)
+                .send()
+                .await
+                .map_err(|e| format!("Retry HTTP error: {}", e))?
+        } else {
+            resp
+        };
+
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            // Notion errors are { object: "error", code, message }
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|v| v["message"].as_str().map(String::from))
+                .unwrap_or_else(|| safe_truncate(&text, 300).to_string());
+            let hint = if status.as_u16() == 404 {
+                " — make sure the page or database is shared with your integration (••• → Connections)"
+            } else {
+                ""
+            };
+            return Err(format!("Notion API {}: {}{}", status, message, hint).into());
+        }
+        serde_json::from_str(&text).or_else(|_| Ok(json!({ "raw": text })))
+    }
+}
+
+/// A Notion id from args, accepting bare ids, dashed UUIDs, or page URLs
+/// (the id is the trailing 32 hex characters).
+pub(crate) fn resolve_id(args: &Value, key: &str) -> EngineResult<String> {
+    let raw = args[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Missing '{}'", key))?;
+    let path = raw.split(['?', '#']).next().unwrap_or(raw);
+    let hex: String = path
+        .chars()
+        .rev()
+        .filter(|c| *c != '-')
+        .take_while(|c| c.is_ascii_hexdigit())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    if hex.len() < 32 {
+        return Err(format!("'{}' is not a Notion id or URL: {}", key, raw).into());
+    }
+    Ok(hex[hex.len() - 32..].to_lowercase())
+}
+
+/// Page size argument clamped to Notion's 1..=100.
+pub(crate) fn page_size(args: &Value, default: u64) -> u64 {
+    args["page_size"].as_u64().unwrap_or(default).clamp(1, 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_definition_routes_to_its_module() {
+        let names: Vec<String> = definitions().into_iter().map(|d| d.function.name).collect();
+        let unique: HashSet<&String> = names.iter().collect();
+        assert_eq!(unique.len(), names.len(), "duplicate tool names");
+        for name in &names {
+            assert!(route(name).is_some(), "{} is not routed", name);
+        }
+        let routed = databases::TOOLS.len() + pages::TOOLS.len() + blocks::TOOLS.len();
+        assert_eq!(routed, names.len(), "a routed tool has no definition");
+
+        assert_eq!(route("notion_query_database"), Some(Route::Databases));
+        assert_eq!(route("notion_create_page"), Some(Route::Pages));
+        assert_eq!(route("notion_append_blocks"), Some(Route::Blocks));
+        assert_eq!(route("notion_unknown"), None);
+        assert_eq!(route("trello_create_card"), None);
+    }
+
+    #[test]
+    fn resolves_ids_from_urls_and_uuids() {
+        let id = "1429989fe8ac4effbc8f57f56486db54";
+        for raw in [
+            id.to_string(),
+            "1429989f-e8ac-4eff-bc8f-57f56486db54".to_string(),
+            format!("https://www.notion.so/acme/Roadmap-{}", id),
+            format!("https://www.notion.so/{}?v=abc123", id),
+        ] {
+            assert_eq!(
+                resolve_id(&json!({ "page_id": raw }), "page_id").unwrap(),
+                id
+            );
+        }
+        assert!(resolve_id(&json!({ "page_id": "Roadmap" }), "page_id").is_err());
+        assert!(resolve_id(&json!({}), "page_id").is_err());
+    }
+}
@@ -0,0 +1,241 @@
+// notion/pages.rs — Page management
+//
+// Tools:
+//   notion_get_page     — read a page's properties and content
+//   notion_create_page  — create a page in a database or under another page
+//   notion_update_page  — set properties, rename, archive or restore a page
+
+use super::blocks::{append_children, read_children, text_to_blocks, MAX_BLOCKS_PER_REQUEST};
+use super::properties::{
+    build_properties, page_title, property_text, property_types, rich_text, title_property,
+};
+use super::{resolve_id, NotionClient};
+use crate::atoms::error::EngineResult;
+use crate::atoms::types::*;
+use log::info;
+use reqwest::Method;
+use serde_json::{json, Map, Value};
+
+pub(crate) const TOOLS: &[&str] = &[
+    "notion_get_page",
+    "notion_create_page",
+    "notion_update_page",
+];
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_get_page".into(),
+                description: "Read a Notion page: its title, property values, and (by default) its content as text.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "page_id": { "type": "string", "description": "Page ID or URL." },
+                        "include_content": { "type": "boolean", "description": "Also return the page body. Default: true." }
+                    },
+                    "required": ["page_id"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_create_page".into(),
+                description: "Create a Notion page, either as a row in a database (set parent_database_id and properties by name) or as a sub-page (set parent_page_id). Property values are plain: text, a select option name, an ISO date, a number. Content is plain text; lines starting with #, -, 1., [ ] or > become headings, bullets, numbered items, to-dos and quotes.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "parent_database_id": { "type": "string", "description": "Database ID or URL to add the page to." },
+                        "parent_page_id": { "type": "string", "description": "Page ID or URL to create the page under. Use instead of parent_database_id." },
+                        "title": { "type": "string", "description": "Page title." },
+                        "properties": { "type": "object", "description": "Database property values by name, e.g. {\"Status\": \"In progress\", \"Due\": \"2026-10-20\", \"Tags\": [\"web\"]}. Only for database parents." },
+                        "content": { "type": "string", "description": "Page body text." }
+                    },
+                    "required": ["title"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "notion_update_page".into(),
+                description: "Update a Notion page: set property values by name, rename it, or archive/restore it. Set a property to null to clear it. Use notion_append_blocks to add content.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "page_id": { "type": "string", "description": "Page ID or URL." },
+                        "title": { "type": "string", "description": "New page title." },
+                        "properties": { "type": "object", "description": "Property values by name, e.g. {\"Status\": \"Done\"}." },
+                        "archived": { "type": "boolean", "description": "true to archive (delete) the page, false to restore it." }
+                    },
+                    "required": ["page_id"]
+                }),
+            },
+        },
+    ]
+}
+
+pub async fn execute(name: &str, args: &Value, client: &NotionClient) -> EngineResult<String> {
+    match name {
+        "notion_get_page" => exec_get(args, client).await,
+        "notion_create_page" => exec_create(args, client).await,
+        "notion_update_page" => exec_update(args, client).await,
+        _ => Err(format!("Unknown Notion page tool: {}", name).into()),
+    }
+}
+
+async fn exec_get(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let page_id = resolve_id(args, "page_id")?;
+    let page = client
+        .request(Method::GET, &format!("/pages/{}", page_id), None)
+        .await?;
+
+    let mut out = format!(
+        "# {}\nid: {}\n{}\n",
+        page_title(&page),
+        page["id"].as_str().unwrap_or(&page_id),
+        page["url"].as_str().unwrap_or("")
+    );
+    if page["archived"].as_bool().unwrap_or(false) {
+        out.push_str("(archived)\n");
+    }
+    if let Some(props) = page["properties"].as_object() {
+        let mut fields: Vec<String> = props
+            .iter()
+            .filter(|(_, p)| p["type"] != "title")
+            .map(|(name, p)| format!("- {}: {}", name, property_text(p)))
+            .collect();
+        fields.sort();
+        if !fields.is_empty() {
+            out.push_str(&format!("\n{}\n", fields.join("\n")));
+        }
+    }
+    if args["include_content"].as_bool().unwrap_or(true) {
+        let content = read_children(client, &page_id).await?;
+        if !content.is_empty() {
+            out.push_str(&format!("\n---\n{}", content));
+        }
+    }
+    Ok(out)
+}
+
+/// Insert the title under the database's title property (or "title" for
+/// sub-pages), unless properties already set it.
+fn set_title(props: &mut Map<String, Value>, title_prop: &str, title: Option<&str>) {
+    if let Some(title) = title {
+        props
+            .entry(title_prop.to_string())
+            .or_insert_with(|| json!({ "title": rich_text(title) }));
+    }
+}
+
+async fn exec_create(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let title = args["title"].as_str();
+    let (parent, props) = if !args["parent_database_id"].is_null() {
+        let database_id = resolve_id(args, "parent_database_id")?;
+        let database = client
+            .request(Method::GET, &format!("/databases/{}", database_id), None)
+            .await?;
+        let types = property_types(&database);
+        let mut props = match args["properties"].as_object() {
+            Some(values) => build_properties(&types, values)?,
+            None => json!({}),
+        };
+        let title_prop = title_property(&types).unwrap_or("title").to_string();
+        if let Some(map) = props.as_object_mut() {
+            set_title(map, &title_prop, title);
+        }
+        (json!({ "database_id": database_id }), props)
+    } else if !args["parent_page_id"].is_null() {
+        if args["properties"]
+            .as_object()
+            .is_some_and(|p| !p.is_empty())
+        {
+            return Err(
+                "Sub-pages only have a title — 'properties' needs a parent_database_id".into(),
+            );
+        }
+        let page_id = resolve_id(args, "parent_page_id")?;
+        let mut props = json!({});
+        if let Some(map) = props.as_object_mut() {
+            set_title(map, "title", title);
+        }
+        (json!({ "page_id": page_id }), props)
+    } else {
+        return Err("Set parent_database_id or parent_page_id".into());
+    };
+    if props.as_object().is_some_and(|p| p.is_empty()) {
+        return Err("A page needs a title".into());
+    }
+
+    let blocks = args["content"]
+        .as_str()
+        .map(text_to_blocks)
+        .unwrap_or_default();
+    let (first, rest) = blocks.split_at(blocks.len().min(MAX_BLOCKS_PER_REQUEST));
+    let body = json!({ "parent": parent, "properties": props, "children": first });
+    let page = client.request(Method::POST, "/pages", Some(&body)).await?;
+    let page_id = page["id"].as_str().unwrap_or("").to_string();
+    if !rest.is_empty() {
+        append_children(client, &page_id, rest).await?;
+    }
+
+    info!("[notion] Created page {}", page_id);
+    Ok(format!(
+        "Created page **{}** (id: {})\n{}",
+        page_title(&page),
+        page_id,
+        page["url"].as_str().unwrap_or("")
+    ))
+}
+
+async fn exec_update(args: &Value, client: &NotionClient) -> EngineResult<String> {
+    let page_id = resolve_id(args, "page_id")?;
+    let mut body = Map::new();
+
+    let values = args["properties"].as_object().filter(|p| !p.is_empty());
+    let title = args["title"].as_str();
+    if values.is_some() || title.is_some() {
+        // Property types come from the page itself (its database's schema)
+        let page = client
+            .request(Method::GET, &format!("/pages/{}", page_id), None)
+            .await?;
+        let types = property_types(&page);
+        let mut props = match values {
+            Some(values) => build_properties(&types, values)?,
+            None => json!({}),
+        };
+        let title_prop = title_property(&types).unwrap_or("title").to_string();
+        if let Some(map) = props.as_object_mut() {
+            set_title(map, &title_prop, title);
+        }
+        body.insert("properties".into(), props);
+    }
+    if let Some(archived) = args["archived"].as_bool() {
+        body.insert("archived".into(), json!(archived));
+    }
+    if body.is_empty() {
+        return Err("Nothing to update — set properties, title, or archived".into());
+    }
+
+    let page = client
+        .request(
+            Method::PATCH,
+            &format!("/pages/{}", page_id),
+            Some(&Value::Object(body)),
+        )
+        .await?;
+    let state = if page["archived"].as_bool().unwrap_or(false) {
+        " (archived)"
+    } else {
+        ""
+    };
+    Ok(format!(
+        "Updated page **{}**{} (id: {})",
+        page_title(&page),
+        state,
+        page_id
+    ))
+}
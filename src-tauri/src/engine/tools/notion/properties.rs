@@ -0,0 +1,378 @@
+// notion/properties.rs — Property payloads
+//
+// Notion wants every property value wrapped in its type: a title is
+// { "title": [rich text…] }, a select { "select": { "name": … } }, a date
+// { "date": { "start": … } }. Tools take plain values ("Done", "2026-10-20")
+// and look the types up from the database or page, so the model never has
+// to spell out the wrapping. Also renders property values back to text.
+
+use crate::atoms::error::EngineResult;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Notion caps the content of a single rich-text object at 2000 characters.
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// Property types this module can write.
+const WRITABLE_TYPES: &[&str] = &[
+    "title",
+    "rich_text",
+    "select",
+    "status",
+    "multi_select",
+    "date",
+    "number",
+    "checkbox",
+    "url",
+    "email",
+    "phone_number",
+];
+
+/// A rich-text array for `text`, split into 2000-character objects.
+pub(crate) fn rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    Value::Array(
+        chars
+            .chunks(MAX_TEXT_CHARS)
+            .map(|chunk| {
+                json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } })
+            })
+            .collect(),
+    )
+}
+
+/// A scalar as text: strings as-is, numbers and booleans printed.
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// YYYY-MM-DD, optionally followed by a time (ISO 8601).
+fn is_iso_date(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() >= 10
+        && b[..4].iter().all(u8::is_ascii_digit)
+        && b[4] == b'-'
+        && b[5..7].iter().all(u8::is_ascii_digit)
+        && b[7] == b'-'
+        && b[8..10].iter().all(u8::is_ascii_digit)
+        && (b.len() == 10 || b[10] == b'T')
+}
+
+fn date_value(value: &Value) -> EngineResult<Value> {
+    let (start, end) = match value {
+        Value::String(s) => (s.trim().to_string(), None),
+        Value::Object(o) => (
+            o.get("start").map(as_text).unwrap_or_default(),
+            o.get("end").filter(|e| !e.is_null()).map(as_text),
+        ),
+        _ => (String::new(), None),
+    };
+    for date in std::iter::once(&start).chain(end.iter()) {
+        if !is_iso_date(date) {
+            return Err(format!(
+                "Dates must be ISO 8601 (e.g. 2026-10-20 or 2026-10-20T09:00:00Z), got '{}'",
+                date
+            )
+            .into());
+        }
+    }
+    Ok(match end {
+        Some(end) => json!({ "start": start, "end": end }),
+        None => json!({ "start": start }),
+    })
+}
+
+/// The payload for one property of type `kind` set to `value`. A null
+/// clears the property.
+pub(crate) fn property_value(kind: &str, value: &Value) -> EngineResult<Value> {
+    if value.is_null() {
+        let cleared = match kind {
+            "title" | "rich_text" | "multi_select" => json!([]),
+            "checkbox" => json!(false),
+            _ if WRITABLE_TYPES.contains(&kind) => Value::Null,
+            _ => return Err(unsupported(kind)),
+        };
+        return Ok(json!({ kind: cleared }));
+    }
+    let payload = match kind {
+        "title" | "rich_text" => rich_text(&as_text(value)),
+        "select" | "status" => json!({ "name": as_text(value) }),
+        "multi_select" => {
+            let names: Vec<String> = match value {
+                Value::Array(items) => items.iter().map(as_text).collect(),
+                other => as_text(other)
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            };
+            Value::Array(names.into_iter().map(|n| json!({ "name": n })).collect())
+        }
+        "date" => date_value(value)?,
+        "number" => match value {
+            Value::Number(_) => value.clone(),
+            other => as_text(other)
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|n| serde_json::Number::from_f64(n).map(Value::Number))
+                .ok_or_else(|| format!("'{}' is not a number", as_text(other)))?,
+        },
+        "checkbox" => match value {
+            Value::Bool(b) => json!(b),
+            other => match as_text(other).trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" => json!(true),
+                "false" | "no" => json!(false),
+                _ => return Err(format!("'{}' is not true or false", as_text(other)).into()),
+            },
+        },
+        "url" | "email" | "phone_number" => json!(as_text(value)),
+        _ => return Err(unsupported(kind)),
+    };
+    Ok(json!({ kind: payload }))
+}
+
+fn unsupported(kind: &str) -> crate::atoms::error::EngineError {
+    format!(
+        "Property type '{}' can't be set by this tool (supported: {})",
+        kind,
+        WRITABLE_TYPES.join(", ")
+    )
+    .into()
+}
+
+/// Property name → type from a database or page object's `properties`.
+pub(crate) fn property_types(object: &Value) -> HashMap<String, String> {
+    object["properties"]
+        .as_object()
+        .map(|props| {
+            props
+                .iter()
+                .filter_map(|(name, p)| Some((name.clone(), p["type"].as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Name of the title property (every database has exactly one).
+pub(crate) fn title_property(types: &HashMap<String, String>) -> Option<&str> {
+    types
+        .iter()
+        .find(|(_, kind)| kind.as_str() == "title")
+        .map(|(name, _)| name.as_str())
+}
+
+/// Typed payloads for plain `values`, keyed by property name (matched
+/// case-insensitively when there is no exact match).
+pub(crate) fn build_properties(
+    types: &HashMap<String, String>,
+    values: &Map<String, Value>,
+) -> EngineResult<Value> {
+    let mut out = Map::new();
+    for (name, value) in values {
+        let (prop_name, kind) = types
+            .get_key_value(name)
+            .or_else(|| types.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)))
+            .ok_or_else(|| {
+                let mut known: Vec<&str> = types.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                format!(
+                    "Unknown property '{}'. Available: {}",
+                    name,
+                    known.join(", ")
+                )
+            })?;
+        let payload = property_value(kind, value)
+            .map_err(|e| format!("Property '{}' ({}): {}", prop_name, kind, e))?;
+        out.insert(prop_name.clone(), payload);
+    }
+    Ok(Value::Object(out))
+}
+
+fn plain_text(rich: &Value) -> String {
+    rich.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t["plain_text"].as_str().or(t["text"]["content"].as_str()))
+        .collect()
+}
+
+/// A property value as display text (empty when unset).
+pub(crate) fn property_text(prop: &Value) -> String {
+    let kind = prop["type"].as_str().unwrap_or("");
+    let v = &prop[kind];
+    match kind {
+        "title" | "rich_text" => plain_text(v),
+        "select" | "status" => v["name"].as_str().unwrap_or("").to_string(),
+        "multi_select" => v
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|o| o["name"].as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        "date" => match (v["start"].as_str(), v["end"].as_str()) {
+            (Some(start), Some(end)) => format!("{} → {}", start, end),
+            (Some(start), None) => start.to_string(),
+            _ => String::new(),
+        },
+        "people" => v
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p["name"].as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        "relation" => match v.as_array().map(Vec::len) {
+            Some(n) if n > 0 => format!("{} linked", n),
+            _ => String::new(),
+        },
+        "formula" => {
+            let result_kind = v["type"].as_str().unwrap_or("");
+            match &v[result_kind] {
+                Value::Null => String::new(),
+                other => as_text(other),
+            }
+        }
+        _ => match v {
+            Value::Null | Value::Object(_) | Value::Array(_) => String::new(),
+            other => as_text(other),
+        },
+    }
+}
+
+/// A page's title (from whichever property is the title), or "(untitled)".
+pub(crate) fn page_title(page: &Value) -> String {
+    let title = page["properties"]
+        .as_object()
+        .into_iter()
+        .flat_map(|props| props.values())
+        .find(|p| p["type"] == "title")
+        .map(property_text)
+        .unwrap_or_default();
+    if title.is_empty() {
+        "(untitled)".into()
+    } else {
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types() -> HashMap<String, String> {
+        [
+            ("Name", "title"),
+            ("Notes", "rich_text"),
+            ("Status", "select"),
+            ("Due", "date"),
+            ("Tags", "multi_select"),
+            ("Points", "number"),
+            ("Done", "checkbox"),
+            ("Owner", "people"),
+        ]
+        .into_iter()
+        .map(|(n, t)| (n.to_string(), t.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn builds_typed_payloads_from_plain_values() {
+        let values = json!({
+            "Name": "Launch",
+            "notes": "Ship the beta",
+            "Status": "In progress",
+            "Due": "2026-10-20",
+            "Tags": "web, mobile",
+            "Points": "3",
+            "Done": false
+        });
+        let props = build_properties(&types(), values.as_object().unwrap()).unwrap();
+        assert_eq!(
+            props,
+            json!({
+                "Name": { "title": [{ "type": "text", "text": { "content": "Launch" } }] },
+                "Notes": { "rich_text": [{ "type": "text", "text": { "content": "Ship the beta" } }] },
+                "Status": { "select": { "name": "In progress" } },
+                "Due": { "date": { "start": "2026-10-20" } },
+                "Tags": { "multi_select": [{ "name": "web" }, { "name": "mobile" }] },
+                "Points": { "number": 3.0 },
+                "Done": { "checkbox": false }
+            })
+        );
+    }
+
+    #[test]
+    fn dates_ranges_and_clearing() {
+        assert_eq!(
+            property_value(
+                "date",
+                &json!({ "start": "2026-10-20T09:00:00Z", "end": "2026-10-21" })
+            )
+            .unwrap(),
+            json!({ "date": { "start": "2026-10-20T09:00:00Z", "end": "2026-10-21" } })
+        );
+        assert!(property_value("date", &json!("next Tuesday")).is_err());
+        assert!(property_value("date", &json!("20/10/2026")).is_err());
+        assert_eq!(
+            property_value("date", &Value::Null).unwrap(),
+            json!({ "date": null })
+        );
+        assert_eq!(
+            property_value("select", &Value::Null).unwrap(),
+            json!({ "select": null })
+        );
+        assert_eq!(
+            property_value("title", &Value::Null).unwrap(),
+            json!({ "title": [] })
+        );
+    }
+
+    #[test]
+    fn long_text_is_split_into_rich_text_chunks() {
+        let text = "a".repeat(4500);
+        let chunks = rich_text(&text);
+        let lens: Vec<usize> = chunks
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["text"]["content"].as_str().unwrap().len())
+            .collect();
+        assert_eq!(lens, vec![2000, 2000, 500]);
+    }
+
+    #[test]
+    fn rejects_unknown_and_unsupported_properties() {
+        let values = json!({ "Priority": "High" });
+        let err = build_properties(&types(), values.as_object().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown property 'Priority'") && err.contains("Status"));
+        let values = json!({ "Owner": "Sam" });
+        let err = build_properties(&types(), values.as_object().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'people' can't be set"), "{}", err);
+        assert!(property_value("number", &json!("lots")).is_err());
+    }
+
+    #[test]
+    fn renders_properties_back_to_text() {
+        let page = json!({ "properties": {
+            "Name": { "type": "title", "title": [{ "plain_text": "Launch" }, { "plain_text": " plan" }] },
+            "Due": { "type": "date", "date": { "start": "2026-10-20", "end": null } },
+            "Tags": { "type": "multi_select", "multi_select": [{ "name": "web" }, { "name": "mobile" }] },
+            "Points": { "type": "number", "number": 3 }
+        }});
+        assert_eq!(page_title(&page), "Launch plan");
+        assert_eq!(property_text(&page["properties"]["Due"]), "2026-10-20");
+        assert_eq!(property_text(&page["properties"]["Tags"]), "web, mobile");
+        assert_eq!(property_text(&page["properties"]["Points"]), "3");
+        assert_eq!(page_title(&json!({ "properties": {} })), "(untitled)");
+        assert_eq!(title_property(&types()), Some("Name"));
+    }
+}
@@ -0,0 +1,222 @@
+// tools/reminders.rs — Reminder tools.
+// "Remind me to…": the agent schedules a reminder that the background loop
+// in engine/reminders.rs fires at the right time, even across restarts.
+
+use crate::atoms::types::*;
+use crate::engine::reminders::DELIVERY_CHANNELS;
+use crate::engine::state::EngineState;
+use log::info;
+use openpawz_core::engine::reminders::parse_when;
+use tauri::Manager;
+
+// ── Tool definitions ───────────────────────────────────────────────────
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "reminder_set".into(),
+                description: "Set a reminder for the user. It fires as an app notification at \
+                    the given time, even if the app is restarted in between. Optionally also \
+                    deliver it through a messaging channel."
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "when": {
+                            "type": "string",
+                            "description": "Relative ('in 2 hours', 'in 1 hour 30 minutes', '45m') or an ISO 8601 timestamp ('2025-06-01T09:00:00Z'; without an offset it's local time)"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "What to remind the user about"
+                        },
+                        "channel": {
+                            "type": "string",
+                            "enum": ["whatsapp"],
+                            "description": "Also deliver through this channel"
+                        },
+                        "recipient": {
+                            "type": "string",
+                            "description": "Channel address to deliver to (WhatsApp: the chat JID). Required with channel."
+                        }
+                    },
+                    "required": ["when", "text"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "reminder_list".into(),
+                description: "List reminders you've set, soonest first.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "include_done": {
+                            "type": "boolean",
+                            "description": "Also list fired and cancelled reminders (default: false)"
+                        }
+                    }
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "reminder_cancel".into(),
+                description: "Cancel a pending reminder by its id.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "integer",
+                            "description": "Reminder id from reminder_set or reminder_list"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+            },
+        },
+    ]
+}
+
+// ── Execute dispatcher ─────────────────────────────────────────────────
+
+pub async fn execute(
+    name: &str,
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Option<Result<String, String>> {
+    Some(match name {
+        "reminder_set" => execute_set(args, app_handle, agent_id),
+        "reminder_list" => execute_list(args, app_handle, agent_id),
+        "reminder_cancel" => execute_cancel(args, app_handle, agent_id),
+        _ => return None,
+    })
+}
+
+// ── Private handlers ───────────────────────────────────────────────────
+
+fn execute_set(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let when = args["when"]
+        .as_str()
+        .ok_or("Missing required parameter: when")?;
+    let text = args["text"]
+        .as_str()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or("Missing required parameter: text")?;
+    let channel = args["channel"].as_str().filter(|c| !c.is_empty());
+    let recipient = args["recipient"].as_str().filter(|r| !r.is_empty());
+    if let Some(channel) = channel {
+        if !DELIVERY_CHANNELS.contains(&channel) {
+            return Err(format!(
+                "Reminders can't be delivered through '{}'. Supported channels: {}",
+                channel,
+                DELIVERY_CHANNELS.join(", ")
+            ));
+        }
+        if recipient.is_none() {
+            return Err(format!("Delivering through {} needs a recipient", channel));
+        }
+    }
+
+    let fire_at = parse_when(when, chrono::Utc::now())?;
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let reminder = state.store.reminder_add(
+        agent_id,
+        text,
+        &fire_at,
+        channel,
+        recipient.filter(|_| channel.is_some()),
+    )?;
+
+    info!(
+        "[engine] Reminder #{} set for {} (agent={})",
+        reminder.id, reminder.fire_at, agent_id
+    );
+    Ok(format!(
+        "Reminder #{} set for {}: {}",
+        reminder.id,
+        fire_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M %Z"),
+        reminder.text
+    ))
+}
+
+fn execute_list(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let include_done = args["include_done"].as_bool().unwrap_or(false);
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let reminders = state.store.reminder_list(agent_id, include_done)?;
+
+    if reminders.is_empty() {
+        return Ok("No reminders.".into());
+    }
+    let json = serde_json::to_string_pretty(&reminders).unwrap_or_default();
+    Ok(json)
+}
+
+fn execute_cancel(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    // Models sometimes send ids as strings
+    let id = args["id"]
+        .as_i64()
+        .or_else(|| {
+            args["id"]
+                .as_str()
+                .and_then(|s| s.trim_start_matches('#').parse().ok())
+        })
+        .ok_or("Missing required parameter: id")?;
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let reminder = state.store.reminder_cancel(agent_id, id)?;
+
+    info!("[engine] Reminder #{} cancelled (agent={})", id, agent_id);
+    Ok(format!(
+        "Cancelled reminder #{}: {}",
+        reminder.id, reminder.text
+    ))
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_valid() {
+        let names: Vec<_> = definitions().into_iter().map(|d| d.function.name).collect();
+        assert_eq!(names, ["reminder_set", "reminder_list", "reminder_cancel"]);
+    }
+
+    #[test]
+    fn channel_enum_matches_delivery_channels() {
+        let defs = definitions();
+        let listed = &defs[0].function.parameters["properties"]["channel"]["enum"];
+        assert_eq!(listed, &serde_json::json!(DELIVERY_CHANNELS));
+    }
+}
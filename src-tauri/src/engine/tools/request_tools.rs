@@ -37,7 +37,7 @@ pub fn definitions() -> Vec<ToolDefinition> {
                     },
                     "domain": {
                         "type": "string",
-                        "description": "Optional: request all tools from a specific domain directly. One of: system, filesystem, web, identity, memory, agents, communication, squads, tasks, skills, canvas, dashboard, storage, email, messaging, discord, discourse, trello, github, google, integrations, coinbase, dex, solana"
+                        "description": "Optional: request all tools from a specific domain directly. One of: system, filesystem, web, identity, memory, agents, communication, squads, tasks, skills, canvas, dashboard, storage, email, messaging, discord, discourse, trello, notion, github, google, integrations, coinbase, dex, solana"
                     }
                 },
                 "required": ["query"]
@@ -270,6 +270,7 @@ fn build_all_tools_for_index(
         "rest_api".to_string(),
         "webhook".to_string(),
         "image_gen".to_string(),
+        "tts_sag".to_string(),
         "coinbase".to_string(),
         "dex".to_string(),
         "solana_dex".to_string(),
@@ -277,6 +278,7 @@ fn build_all_tools_for_index(
         "discord".to_string(),
         "discourse".to_string(),
         "trello".to_string(),
+        "notion".to_string(),
     ];
     tools.extend(crate::engine::tools::skill_tools(&all_skill_ids));
 
@@ -191,6 +191,8 @@ async fn execute_service_api(args: &serde_json::Value) -> Result<String, String>
         }
     }
 
+    request = crate::engine::net::apply(request, &url);
+
     // Add query parameters
     if let Some(query) = args.get("query").and_then(|v| v.as_object()) {
         let pairs: Vec<(String, String)> = query
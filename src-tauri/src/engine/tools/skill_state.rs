@@ -0,0 +1,250 @@
+// tools/skill_state.rs — Per-skill persistent state for third-party skills.
+// TOML and community skills only get credentials; these tools let their
+// instructions keep counters, cursors and last-run timestamps. Each active
+// skill gets its own `skill_state_*__<skill>` tools, so the namespace a call
+// reaches comes from the tool itself, never from a model-chosen argument.
+
+use crate::atoms::types::*;
+use crate::engine::sessions::SessionStore;
+use crate::engine::skills::active_skill_policies;
+use crate::engine::state::EngineState;
+use log::info;
+use tauri::Manager;
+
+/// Tool families; each active skill gets one tool per family.
+const FAMILIES: [&str; 3] = ["skill_state_get", "skill_state_set", "skill_state_delete"];
+
+/// Tool names are capped at 64 characters by the providers; slugs are cut
+/// to what fits after the longest family name.
+const MAX_TOOL_NAME: usize = 64;
+const MAX_SLUG: usize = MAX_TOOL_NAME - "skill_state_delete__".len();
+
+// ── Tool definitions ───────────────────────────────────────────────────
+
+/// Name-safe form of a skill id (`acme/counter` → `acme_counter`).
+fn skill_slug(skill_id: &str) -> String {
+    skill_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_SLUG)
+        .collect()
+}
+
+/// The tool of `family` bound to `skill_id`.
+pub fn tool_name(family: &str, skill_id: &str) -> String {
+    format!("{}__{}", family, skill_slug(skill_id))
+}
+
+/// Split a bound tool name into its family and skill slug.
+pub(crate) fn split_tool(name: &str) -> Option<(&str, &str)> {
+    let (family, slug) = name.split_once("__")?;
+    (FAMILIES.contains(&family) && !slug.is_empty()).then_some((family, slug))
+}
+
+/// State tools for one skill.
+pub fn definitions_for(skill_id: &str) -> Vec<ToolDefinition> {
+    let key_param = serde_json::json!({
+        "type": "string",
+        "description": "State key (e.g. 'cursor', 'last_run')"
+    });
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: tool_name("skill_state_get", skill_id),
+                description: format!(
+                    "Read a value from the persistent state of skill '{}'.",
+                    skill_id
+                ),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": { "key": key_param },
+                    "required": ["key"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: tool_name("skill_state_set", skill_id),
+                description: format!(
+                    "Save a value in the persistent state of skill '{}' (counters, \
+                     cursors, last-run timestamps). Survives restarts.",
+                    skill_id
+                ),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "key": key_param,
+                        "value": {
+                            "type": "string",
+                            "description": "Value to store (plain text or JSON string)"
+                        }
+                    },
+                    "required": ["key", "value"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: tool_name("skill_state_delete", skill_id),
+                description: format!(
+                    "Delete a key from the persistent state of skill '{}'.",
+                    skill_id
+                ),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": { "key": key_param },
+                    "required": ["key"]
+                }),
+            },
+        },
+    ]
+}
+
+/// State tools for every third-party skill active for `agent_id`. Skills
+/// whose ids collapse to the same tool name are listed once (and refused
+/// when called, see `resolve_skill`).
+pub fn agent_definitions(store: &SessionStore, agent_id: &str) -> Vec<ToolDefinition> {
+    let mut seen = std::collections::HashSet::new();
+    active_skill_policies(store, agent_id)
+        .iter()
+        .flat_map(|p| definitions_for(&p.skill_id))
+        .filter(|d| seen.insert(d.function.name.clone()))
+        .collect()
+}
+
+// ── Execute dispatcher ─────────────────────────────────────────────────
+
+pub async fn execute(
+    name: &str,
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Option<Result<String, String>> {
+    let (family, slug) = split_tool(name)?;
+    Some(execute_state(family, slug, args, app_handle, agent_id))
+}
+
+// ── Private handlers ───────────────────────────────────────────────────
+
+fn execute_state(
+    family: &str,
+    slug: &str,
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let key = args["key"]
+        .as_str()
+        .filter(|k| !k.is_empty())
+        .ok_or("Missing required parameter: key")?;
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let active: Vec<String> = active_skill_policies(&state.store, agent_id)
+        .into_iter()
+        .map(|p| p.skill_id)
+        .collect();
+    let skill_id = resolve_skill(&active, slug)?;
+
+    match family {
+        "skill_state_get" => match state.store.skill_kv_get(&skill_id, key)? {
+            Some(value) => Ok(value),
+            None => Ok(format!("No value for '{key}' in skill '{skill_id}'.")),
+        },
+        "skill_state_set" => {
+            let value = args["value"]
+                .as_str()
+                .ok_or("Missing required parameter: value")?;
+            state.store.skill_kv_set(&skill_id, key, value)?;
+            info!("[engine] Skill state set: {}:{}", skill_id, key);
+            Ok(format!("Saved '{key}' for skill '{skill_id}'."))
+        }
+        _ => {
+            let existed = state.store.skill_kv_delete(&skill_id, key)?;
+            info!("[engine] Skill state delete: {}:{}", skill_id, key);
+            Ok(if existed {
+                format!("Deleted '{key}' from skill '{skill_id}'.")
+            } else {
+                format!("No value for '{key}' in skill '{skill_id}' — nothing to delete.")
+            })
+        }
+    }
+}
+
+/// The active skill a bound tool acts for. A tool whose skill is no
+/// longer active for the agent (or whose slug two skills share) is refused.
+fn resolve_skill(active: &[String], slug: &str) -> Result<String, String> {
+    let matches: Vec<&String> = active.iter().filter(|id| skill_slug(id) == slug).collect();
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => Err(format!(
+            "No active skill matches '{}' — a skill can only access its own state",
+            slug
+        )),
+        _ => Err(format!(
+            "Skills {} share a state tool name; rename one to use skill state",
+            matches
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_valid() {
+        let names: Vec<_> = definitions_for("acme/counter")
+            .into_iter()
+            .map(|d| d.function.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "skill_state_get__acme_counter",
+                "skill_state_set__acme_counter",
+                "skill_state_delete__acme_counter"
+            ]
+        );
+        for name in &names {
+            assert!(split_tool(name).is_some());
+        }
+        assert!(split_tool("skill_state_get").is_none());
+        assert!(split_tool("skill_store_get__acme").is_none());
+        assert!(tool_name("skill_state_delete", &"x".repeat(80)).len() <= MAX_TOOL_NAME);
+    }
+
+    #[test]
+    fn skill_comes_from_the_tool() {
+        let one = vec!["acme/counter".to_string()];
+        let (_, slug) = split_tool("skill_state_set__acme_counter").unwrap();
+        assert_eq!(resolve_skill(&one, slug).unwrap(), "acme/counter");
+        // A tool for a skill that isn't active for this agent is refused
+        assert!(resolve_skill(&one, "other_skill").is_err());
+        assert!(resolve_skill(&[], slug).is_err());
+
+        let two = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(resolve_skill(&two, "b").unwrap(), "b");
+        // Ids that collapse to the same tool name are ambiguous
+        let clash = vec!["a/b".to_string(), "a.b".to_string()];
+        assert!(resolve_skill(&clash, "a_b")
+            .unwrap_err()
+            .contains("a/b, a.b"));
+    }
+}
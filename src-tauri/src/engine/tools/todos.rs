@@ -0,0 +1,239 @@
+// tools/todos.rs — Agent todo list tools.
+// Lets an agent break long-running work into subtasks, check them off and
+// report progress. Optionally mirrored to a Today dashboard widget.
+
+use crate::atoms::types::*;
+use crate::engine::sessions::{AgentTodo, TODO_DONE, TODO_PENDING};
+use crate::engine::state::EngineState;
+use log::info;
+use tauri::Manager;
+
+/// skill_id of the dashboard widget that mirrors an agent's todo list.
+const TODO_WIDGET_SKILL: &str = "agent-todos";
+
+// ── Tool definitions ───────────────────────────────────────────────────
+
+pub fn definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "task_add".into(),
+                description: "Add a subtask to your own todo list. Use it to plan multi-step \
+                    work, then check items off with task_complete. This is your working \
+                    checklist, not the user's task board."
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "description": {
+                            "type": "string",
+                            "description": "What needs doing"
+                        }
+                    },
+                    "required": ["description"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "task_complete".into(),
+                description: "Mark one of your todo items done by its id.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "integer",
+                            "description": "Todo id returned by task_add or task_list"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".into(),
+            function: FunctionDefinition {
+                name: "task_list".into(),
+                description: "List your todo items and their status.".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "status": {
+                            "type": "string",
+                            "enum": ["pending", "done", "all"],
+                            "description": "Filter by status (default: all)"
+                        },
+                        "show_on_dashboard": {
+                            "type": "boolean",
+                            "description": "Also publish the list as a widget on the user's Today dashboard"
+                        }
+                    }
+                }),
+            },
+        },
+    ]
+}
+
+// ── Execute dispatcher ─────────────────────────────────────────────────
+
+pub async fn execute(
+    name: &str,
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Option<Result<String, String>> {
+    Some(match name {
+        "task_add" => execute_add(args, app_handle, agent_id),
+        "task_complete" => execute_complete(args, app_handle, agent_id),
+        "task_list" => execute_list(args, app_handle, agent_id),
+        _ => return None,
+    })
+}
+
+// ── Private handlers ───────────────────────────────────────────────────
+
+fn execute_add(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let description = args["description"]
+        .as_str()
+        .ok_or("Missing required parameter: description")?;
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let todo = state.store.todo_add(agent_id, description)?;
+
+    info!("[engine] Todo added: agent={} id={}", agent_id, todo.id);
+    Ok(format!("Added todo #{}: {}", todo.id, todo.description))
+}
+
+fn execute_complete(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    // Models sometimes send ids as strings
+    let id = args["id"]
+        .as_i64()
+        .or_else(|| {
+            args["id"]
+                .as_str()
+                .and_then(|s| s.trim_start_matches('#').parse().ok())
+        })
+        .ok_or("Missing required parameter: id")?;
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let todo = state.store.todo_complete(agent_id, id)?;
+    let remaining = state.store.todo_list(agent_id, Some(TODO_PENDING))?.len();
+
+    info!("[engine] Todo completed: agent={} id={}", agent_id, id);
+    Ok(format!(
+        "Completed todo #{}: {} ({} pending)",
+        todo.id, todo.description, remaining
+    ))
+}
+
+fn execute_list(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+    agent_id: &str,
+) -> Result<String, String> {
+    let status = match args["status"].as_str().unwrap_or("all") {
+        "all" => None,
+        s @ (TODO_PENDING | TODO_DONE) => Some(s),
+        other => {
+            return Err(format!(
+                "Invalid status '{}'. Must be one of: pending, done, all",
+                other
+            ))
+        }
+    };
+
+    let state = app_handle
+        .try_state::<EngineState>()
+        .ok_or("Engine state not available")?;
+    let todos = state.store.todo_list(agent_id, status)?;
+
+    if args["show_on_dashboard"].as_bool().unwrap_or(false) {
+        let all = match status {
+            None => todos.clone(),
+            Some(_) => state.store.todo_list(agent_id, None)?,
+        };
+        let data = serde_json::to_string(&widget_data(&all)).unwrap_or_default();
+        state.store.upsert_skill_output(
+            &format!("so-{}-{}", TODO_WIDGET_SKILL, agent_id),
+            TODO_WIDGET_SKILL,
+            agent_id,
+            "table",
+            "Agent tasks",
+            &data,
+        )?;
+    }
+
+    if todos.is_empty() {
+        return Ok("No todos.".into());
+    }
+    Ok(format_list(&todos))
+}
+
+/// One line per todo: `[x] #3 description`.
+fn format_list(todos: &[AgentTodo]) -> String {
+    let done = todos.iter().filter(|t| t.status == TODO_DONE).count();
+    let mut out = format!("{}/{} done\n", done, todos.len());
+    for todo in todos {
+        let mark = if todo.status == TODO_DONE { "x" } else { " " };
+        out.push_str(&format!("[{}] #{} {}\n", mark, todo.id, todo.description));
+    }
+    out
+}
+
+/// `table` widget data for the dashboard.
+fn widget_data(todos: &[AgentTodo]) -> serde_json::Value {
+    let rows: Vec<_> = todos
+        .iter()
+        .map(|t| serde_json::json!([t.id, t.description, t.status]))
+        .collect();
+    serde_json::json!({ "columns": ["#", "Task", "Status"], "rows": rows })
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: i64, status: &str) -> AgentTodo {
+        AgentTodo {
+            id,
+            agent_id: "a1".into(),
+            description: format!("step {}", id),
+            status: status.into(),
+            created_at: String::new(),
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_definitions_valid() {
+        let names: Vec<_> = definitions().into_iter().map(|d| d.function.name).collect();
+        assert_eq!(names, ["task_add", "task_complete", "task_list"]);
+    }
+
+    #[test]
+    fn list_and_widget_show_status() {
+        let todos = [todo(1, TODO_DONE), todo(2, TODO_PENDING)];
+        assert_eq!(
+            format_list(&todos),
+            "1/2 done\n[x] #1 step 1\n[ ] #2 step 2\n"
+        );
+        let data = widget_data(&todos);
+        assert_eq!(data["rows"][1], serde_json::json!([2, "step 2", "pending"]));
+    }
+}
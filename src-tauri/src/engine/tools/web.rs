@@ -90,7 +90,7 @@ pub async fn execute(
                 .map_err(|e| e.to_string()),
         ),
         "web_read" => Some(
-            crate::engine::web::execute_web_read(args)
+            crate::engine::web::execute_web_read(args, app_handle)
                 .await
                 .map_err(|e| e.to_string()),
         ),
@@ -388,8 +388,13 @@ async fn execute_worker_tool(
     // These must go through the main agent loop where the user can approve/deny.
     // Uses the centralized tool_metadata registry which handles both direct tools
     // and MCP naming convention blocklists.
+    // Calls whose arguments raise the tool's tier (a reminder sent to a
+    // channel) need the same approval.
     let name_lower = name.to_lowercase();
-    if !openpawz_core::engine::tool_metadata::worker_allowed(&name_lower) {
+    let escalated =
+        openpawz_core::engine::tool_metadata::call_tier(name, &tool_call.function.arguments)
+            != openpawz_core::engine::tool_metadata::tier(name);
+    if !openpawz_core::engine::tool_metadata::worker_allowed(&name_lower) || escalated {
         warn!(
             "[worker-delegate] Blocked tool '{}' from worker execution (not worker-allowed)",
             name
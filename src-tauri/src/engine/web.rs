@@ -10,6 +10,8 @@
 // when the agent actually calls web_screenshot or web_browse.
 
 use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::robots::{self, AccessKind};
+use crate::engine::util::safe_truncate;
 use headless_chrome::{Browser, LaunchOptions, Tab};
 use log::{info, warn};
 use parking_lot::Mutex;
@@ -153,7 +155,10 @@ pub async fn execute_web_search(args: &serde_json::Value) -> EngineResult<String
 
 // ── web_read: Fetch URL → readable text ────────────────────────────────
 
-pub async fn execute_web_read(args: &serde_json::Value) -> EngineResult<String> {
+pub async fn execute_web_read(
+    args: &serde_json::Value,
+    app_handle: &tauri::AppHandle,
+) -> EngineResult<String> {
     let url = args["url"]
         .as_str()
         .ok_or("web_read: missing 'url' argument")?;
@@ -161,6 +166,11 @@ pub async fn execute_web_read(args: &serde_json::Value) -> EngineResult<String>
 
     info!("[web] read: {} selector={:?}", url, selector);
 
+    let politeness = robots::config_for(app_handle);
+    robots::before_request(&politeness, url, AccessKind::Fetch)
+        .await
+        .map_err(|e| format!("web_read: {}", e))?;
+
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
         .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
@@ -247,11 +257,25 @@ pub async fn execute_web_read(args: &serde_json::Value) -> EngineResult<String>
     ))
 }
 
+/// Elements whose text content is never readable page content.
+const NON_CONTENT_TAGS: &[&str] = &["script", "style", "noscript", "template", "svg"];
+
 /// Extract readable text from an HTML element, skipping scripts/styles.
 fn extract_text_from_element(element: &scraper::ElementRef) -> String {
     let mut text = String::new();
-    for node in element.text() {
-        let trimmed = node.trim();
+    for node in element.descendants() {
+        let Some(fragment) = node.value().as_text() else {
+            continue;
+        };
+        let hidden = node.ancestors().any(|a| {
+            a.value()
+                .as_element()
+                .is_some_and(|e| NON_CONTENT_TAGS.contains(&e.name()))
+        });
+        if hidden {
+            continue;
+        }
+        let trimmed = fragment.trim();
         if !trimmed.is_empty() {
             if !text.is_empty() {
                 text.push(' ');
@@ -264,7 +288,7 @@ fn extract_text_from_element(element: &scraper::ElementRef) -> String {
 
 /// Extract readable content from a full HTML document.
 /// Tries <article>, <main>, then falls back to <body>, skipping nav/footer/script/style.
-fn extract_readable_text(document: &Html) -> String {
+pub(crate) fn extract_readable_text(document: &Html) -> String {
     // Try content-rich selectors first
     for sel_str in &[
         "article",
@@ -367,6 +391,11 @@ pub async fn execute_web_screenshot(
         url, width, height, full_page
     );
 
+    let politeness = robots::config_for(app_handle);
+    robots::before_request(&politeness, url, AccessKind::Browser)
+        .await
+        .map_err(|e| format!("web_screenshot: {}", e))?;
+
     let url_owned = url.to_string();
     let profile_dir = resolve_profile_dir(app_handle);
 
@@ -419,7 +448,7 @@ pub async fn execute_web_screenshot(
             .map(|html| {
                 let doc = Html::parse_document(&html);
                 let text = extract_readable_text(&doc);
-                if text.len() > 5000 { format!("{}...", &text[..5000]) } else { text }
+                if text.len() > 5000 { format!("{}...", safe_truncate(&text, 5000)) } else { text }
             })
             .unwrap_or_default();
 
@@ -454,6 +483,13 @@ pub async fn execute_web_browse(
         action, url, selector
     );
 
+    if let (Some(target), "navigate" | "goto") = (url.as_deref(), action) {
+        let politeness = robots::config_for(app_handle);
+        robots::before_request(&politeness, target, AccessKind::Browser)
+            .await
+            .map_err(|e| format!("web_browse: {}", e))?;
+    }
+
     let action_owned = action.to_string();
     let profile_dir = resolve_profile_dir(app_handle);
 
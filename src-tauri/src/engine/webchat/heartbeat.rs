@@ -0,0 +1,129 @@
+// Web Chat — WebSocket heartbeat
+//
+// Idle connections can be dropped by NAT/proxies without either side
+// noticing. The server pings every PING_INTERVAL and closes the socket
+// once MAX_MISSED_PONGS pings in a row go unanswered. Browsers answer
+// pings automatically.
+
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use log::warn;
+use std::time::Duration;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message as WsMessage};
+
+pub(super) const PING_INTERVAL: Duration = Duration::from_secs(30);
+pub(super) const MAX_MISSED_PONGS: u32 = 3;
+
+pub(super) struct Heartbeat {
+    ticker: Interval,
+    missed: u32,
+    max_missed: u32,
+}
+
+impl Heartbeat {
+    pub(super) fn new(interval: Duration, max_missed: u32) -> Self {
+        let mut ticker = tokio::time::interval_at(Instant::now() + interval, interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Heartbeat {
+            ticker,
+            missed: 0,
+            max_missed,
+        }
+    }
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Heartbeat::new(PING_INTERVAL, MAX_MISSED_PONGS)
+    }
+}
+
+/// Next frame from the client, pinging while idle. Pongs are consumed here.
+/// Returns None when the stream ends or the client stops answering pings.
+pub(super) async fn next_frame<R, W>(
+    receiver: &mut R,
+    sender: &mut W,
+    heartbeat: &mut Heartbeat,
+) -> Option<Result<WsMessage, WsError>>
+where
+    R: Stream<Item = Result<WsMessage, WsError>> + Unpin,
+    W: Sink<WsMessage> + Unpin,
+{
+    loop {
+        tokio::select! {
+            frame = receiver.next() => match frame {
+                Some(Ok(WsMessage::Pong(_))) => heartbeat.missed = 0,
+                other => return other,
+            },
+            _ = heartbeat.ticker.tick() => {
+                if heartbeat.missed >= heartbeat.max_missed {
+                    warn!(
+                        "[webchat] No pong after {} pings — closing connection",
+                        heartbeat.missed
+                    );
+                    let _ = sender.send(WsMessage::Close(None)).await;
+                    return None;
+                }
+                heartbeat.missed += 1;
+                if sender.send(WsMessage::Ping(Default::default())).await.is_err() {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn connect() -> (
+        tokio_tungstenite::WebSocketStream<tokio::io::DuplexStream>,
+        tokio_tungstenite::WebSocketStream<tokio::io::DuplexStream>,
+    ) {
+        let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+        let (server, client) = tokio::join!(
+            tokio_tungstenite::accept_async(server_io),
+            tokio_tungstenite::client_async("ws://localhost/ws", client_io),
+        );
+        (server.unwrap(), client.unwrap().0)
+    }
+
+    #[tokio::test]
+    async fn closes_connection_after_missed_pongs() {
+        let (server, _client) = connect().await;
+        let (mut tx, mut rx) = server.split();
+
+        // The client never reads, so it never answers a ping
+        let mut heartbeat = Heartbeat::new(Duration::from_millis(10), 2);
+        let started = Instant::now();
+        let frame = tokio::time::timeout(
+            Duration::from_secs(2),
+            next_frame(&mut rx, &mut tx, &mut heartbeat),
+        )
+        .await
+        .expect("heartbeat should close the connection");
+
+        assert!(frame.is_none());
+        // Two unanswered pings, closed on the third tick
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn responsive_client_stays_connected() {
+        let (server, mut client) = connect().await;
+        let (mut tx, mut rx) = server.split();
+        // Reading lets tungstenite answer pings automatically
+        tokio::spawn(async move { while client.next().await.is_some() {} });
+
+        let mut heartbeat = Heartbeat::new(Duration::from_millis(10), 2);
+        let idle = tokio::time::timeout(
+            Duration::from_millis(150),
+            next_frame(&mut rx, &mut tx, &mut heartbeat),
+        )
+        .await;
+
+        assert!(idle.is_err(), "connection should still be open");
+        assert!(heartbeat.missed <= 1);
+    }
+}
@@ -2,8 +2,10 @@
 //
 // Self-contained HTML/CSS/JS chat page served by the webchat bridge.
 
-/// Build the complete HTML page for the chat interface.
-pub fn build_chat_html(title: &str) -> String {
+/// Build the complete HTML page for the chat interface. `voice_max_seconds`
+/// shows the mic button and caps recordings; `None` hides it.
+pub fn build_chat_html(title: &str, voice_max_seconds: Option<u32>) -> String {
+    let voice_max_ms = voice_max_seconds.map_or(0, |s| u64::from(s) * 1000);
     format!(
         r##"<!DOCTYPE html>
 <html lang="en">
@@ -36,6 +38,9 @@ body{{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;backgro
 .input-bar textarea:focus{{border-color:#ff00ff}}
 .input-bar button{{padding:10px 20px;background:#ff00ff;color:#fff;border:none;border-radius:8px;font-weight:600;cursor:pointer;white-space:nowrap}}
 .input-bar button:disabled{{opacity:.4;cursor:not-allowed}}
+.input-bar button.mic{{background:#313131;border:1px solid #3c3c3c;color:#cccccc}}
+.input-bar button.mic.recording{{background:#f44;border-color:#f44;color:#fff}}
+.msg.pending{{opacity:.6;font-style:italic}}
 </style>
 </head>
 <body>
@@ -51,6 +56,7 @@ body{{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;backgro
 <div class="messages" id="messages"></div>
 <div class="input-bar" id="inputBar" style="display:none">
   <textarea id="chatInput" placeholder="Type a message..." rows="1"></textarea>
+  <button id="micBtn" class="mic" onclick="toggleMic()" style="display:none" title="Record a voice message">Mic</button>
   <button id="sendBtn" onclick="send()">Send</button>
 </div>
 <script>
@@ -58,6 +64,10 @@ let ws,name="";
 const msgs=document.getElementById("messages");
 const inp=document.getElementById("chatInput");
 const dot=document.getElementById("dot");
+const mic=document.getElementById("micBtn");
+const VOICE_MAX_MS={voice_max_ms};
+let rec=null,recStart=0,recTimer=null;
+if(VOICE_MAX_MS>0&&window.MediaRecorder&&navigator.mediaDevices){{mic.style.display=""}}
 
 async function connect(){{
   name=document.getElementById("nameInput").value.trim();
@@ -83,6 +93,13 @@ async function connect(){{
       const d=JSON.parse(e.data);
       removeTyping();
       if(d.type==="typing"){{addTyping();return}}
+      const pending=msgs.querySelector(".msg.pending");
+      if(d.type==="transcript"){{
+        if(pending){{pending.textContent=d.text;pending.classList.remove("pending")}}
+        else addMsg("user",d.text);
+        return;
+      }}
+      if(pending&&d.type==="error")pending.classList.remove("pending");
       addMsg(d.type||"assistant",d.text||"");
     }}catch(err){{addMsg("assistant",e.data)}}
   }};
@@ -97,6 +114,40 @@ function send(){{
   inp.style.height="auto";
 }}
 
+async function toggleMic(){{
+  if(rec){{rec.stop();return}}
+  if(!ws||ws.readyState!==1)return;
+  let stream;
+  try{{stream=await navigator.mediaDevices.getUserMedia({{audio:true}})}}
+  catch(e){{addMsg("error","Microphone unavailable: "+e.message);return}}
+  const chunks=[];
+  rec=new MediaRecorder(stream);
+  rec.ondataavailable=(e)=>{{if(e.data.size)chunks.push(e.data)}};
+  rec.onstop=()=>{{
+    clearTimeout(recTimer);
+    stream.getTracks().forEach(t=>t.stop());
+    const duration=Math.min(Date.now()-recStart,VOICE_MAX_MS);
+    const type=rec.mimeType||"audio/webm";
+    rec=null;
+    mic.classList.remove("recording");
+    mic.textContent="Mic";
+    const reader=new FileReader();
+    reader.onload=()=>{{
+      const data=String(reader.result).split(",")[1]||"";
+      if(!data||!ws||ws.readyState!==1)return;
+      addMsg("user pending","Voice message…");
+      ws.send(JSON.stringify({{type:"audio",mime_type:type,data,duration_ms:duration}}));
+    }};
+    reader.readAsDataURL(new Blob(chunks,{{type}}));
+  }};
+  rec.start();
+  recStart=Date.now();
+  mic.classList.add("recording");
+  mic.textContent="Stop";
+  // Stop at the server's limit rather than have the recording refused
+  recTimer=setTimeout(()=>{{if(rec)rec.stop()}},VOICE_MAX_MS);
+}}
+
 function addMsg(type,text){{
   const d=document.createElement("div");
   d.className="msg "+type;
@@ -130,9 +181,17 @@ inp.addEventListener("input",()=>{{
 document.getElementById("tokenInput").addEventListener("keydown",(e)=>{{
   if(e.key==="Enter"){{e.preventDefault();connect()}}
 }});
+// Shared links carry the token in the fragment — prefill it, then drop it
+// from the address bar
+const shared=new URLSearchParams(location.hash.slice(1)).get("token");
+if(shared){{
+  document.getElementById("tokenInput").value=shared;
+  history.replaceState(null,"",location.pathname+location.search);
+}}
 </script>
 </body>
 </html>"##,
-        title = title
+        title = title,
+        voice_max_ms = voice_max_ms
     )
 }
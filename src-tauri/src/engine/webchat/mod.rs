@@ -8,7 +8,10 @@
 //   - GET /         → serves a self-contained HTML chat page (no secrets embedded)
 //   - POST /auth    → validates access token, returns a session cookie
 //   - GET /ws       → upgrades to WebSocket (session cookie required)
+//   - WebSocket heartbeat: server pings idle clients, closes ones that stop ponging
 //   - Optional TLS via rustls for HTTPS/WSS when cert+key paths are set
+//   - Optional public tunnel (cloudflared / ngrok) started and stopped with the bridge
+//   - Optional voice input: recorded audio frames are transcribed and answered like text
 //
 // Security:
 //   - Access token required (auto-generated or user-set)
@@ -19,15 +22,22 @@ use crate::engine::util::safe_truncate;
 //   - Binds to 127.0.0.1 (localhost) by default; set bind_address to "0.0.0.0" for LAN
 //   - Optional TLS for HTTPS/WSS (recommended when binding to 0.0.0.0)
 
+mod heartbeat;
 mod html;
+mod network;
 mod server;
 mod session;
+mod tunnel;
+mod voice;
+
+pub use tunnel::TunnelProvider;
 
 use crate::atoms::error::{EngineError, EngineResult};
 use crate::engine::channels::{self, ChannelStatus, PendingUser};
 use futures::stream::StreamExt;
 use futures::SinkExt;
 use log::{debug, error, info, warn};
+use openpawz_core::engine::i18n;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
@@ -64,6 +74,27 @@ pub struct WebChatConfig {
     /// Phase C: allow dangerous/side-effect tools for messages from this channel
     #[serde(default)]
     pub allow_dangerous_tools: bool,
+    /// Tunnel client to expose the chat publicly — "none", "cloudflared" or "ngrok"
+    #[serde(default)]
+    pub tunnel: TunnelProvider,
+    /// Show a mic button and transcribe recorded voice messages
+    #[serde(default)]
+    pub voice_input: bool,
+    /// Longest voice message accepted, in seconds
+    #[serde(default = "default_max_voice_seconds")]
+    pub max_voice_seconds: u32,
+    /// Whisper-compatible endpoint for voice messages; the OpenAI provider when unset
+    #[serde(default)]
+    pub stt_base_url: Option<String>,
+    #[serde(default)]
+    pub stt_api_key: Option<String>,
+    /// Transcription model — "whisper-1" when unset
+    #[serde(default)]
+    pub stt_model: Option<String>,
+}
+
+fn default_max_voice_seconds() -> u32 {
+    60
 }
 
 impl Default for WebChatConfig {
@@ -85,6 +116,12 @@ impl Default for WebChatConfig {
             tls_cert_path: None,
             tls_key_path: None,
             allow_dangerous_tools: false,
+            tunnel: TunnelProvider::None,
+            voice_input: false,
+            max_voice_seconds: default_max_voice_seconds(),
+            stt_base_url: None,
+            stt_api_key: None,
+            stt_model: None,
         }
     }
 }
@@ -138,6 +175,22 @@ pub fn start_bridge(app_handle: tauri::AppHandle) -> EngineResult<()> {
         return Err("Web Chat bridge is disabled.".into());
     }
 
+    if !network::is_loopback_bind(&config.bind_address) && !network::tls_configured(&config) {
+        let message = format!(
+            "Web Chat is reachable from your network on {} without TLS — the access token \
+             and chat travel in plaintext. Use 127.0.0.1 or configure a TLS certificate.",
+            config.bind_address
+        );
+        warn!("[webchat] {}", message);
+        let _ = app_handle.emit(
+            "webchat-status",
+            json!({ "kind": "exposure_warning", "message": message }),
+        );
+    }
+
+    // Fail up front if the tunnel client isn't installed
+    let tunnel_binary = tunnel::resolve_binary(&config)?;
+
     let stop = get_stop_signal();
     stop.store(false, Ordering::Relaxed);
     BRIDGE_RUNNING.store(true, Ordering::Relaxed);
@@ -147,11 +200,16 @@ pub fn start_bridge(app_handle: tauri::AppHandle) -> EngineResult<()> {
         config.bind_address, config.port
     );
 
+    if let Some(binary) = tunnel_binary {
+        tunnel::start(app_handle.clone(), &config, binary, stop.clone());
+    }
+
     tauri::async_runtime::spawn(async move {
         if let Err(e) = server::run_server(app_handle, config).await {
             error!("[webchat] Server crashed: {}", e);
         }
         BRIDGE_RUNNING.store(false, Ordering::Relaxed);
+        tunnel::stop();
         info!("[webchat] Server stopped");
     });
 
@@ -162,6 +220,7 @@ pub fn stop_bridge() {
     let stop = get_stop_signal();
     stop.store(true, Ordering::Relaxed);
     BRIDGE_RUNNING.store(false, Ordering::Relaxed);
+    tunnel::stop();
     info!("[webchat] Stop signal sent");
 }
 
@@ -171,7 +230,11 @@ pub fn get_status(app_handle: &tauri::AppHandle) -> ChannelStatus {
         running: BRIDGE_RUNNING.load(Ordering::Relaxed),
         connected: BRIDGE_RUNNING.load(Ordering::Relaxed),
         bot_name: Some(config.page_title.clone()),
-        bot_id: Some(format!("{}:{}", config.bind_address, config.port)),
+        bot_id: Some(
+            tunnel::public_host()
+                .map(|host| format!("https://{}", host))
+                .unwrap_or_else(|| network::share_url(&config)),
+        ),
         message_count: MESSAGE_COUNT.load(Ordering::Relaxed) as u64,
         allowed_users: config.allowed_users,
         pending_users: config.pending_users,
@@ -232,7 +295,7 @@ async fn handle_websocket<S: AsyncRead + AsyncWrite + Unpin>(
     // Send welcome
     let welcome = json!({
         "type": "system",
-        "text": format!("Connected to {}. Send a message to start chatting!", config.page_title)
+        "text": i18n::t("webchat.connected", &[("title", &config.page_title)])
     });
     let _ = ws_sender
         .send(WsMessage::Text(welcome.to_string().into()))
@@ -245,8 +308,13 @@ async fn handle_websocket<S: AsyncRead + AsyncWrite + Unpin>(
         username, peer
     );
 
-    // Message loop
-    while let Some(msg) = ws_receiver.next().await {
+    let stt_client = reqwest::Client::new();
+
+    // Message loop — pings the client while idle, reaps it when pongs stop
+    let mut heartbeat = heartbeat::Heartbeat::default();
+    while let Some(msg) =
+        heartbeat::next_frame(&mut ws_receiver, &mut ws_sender, &mut heartbeat).await
+    {
         let msg = match msg {
             Ok(m) => m,
             Err(e) => {
@@ -257,15 +325,51 @@ async fn handle_websocket<S: AsyncRead + AsyncWrite + Unpin>(
 
         match msg {
             WsMessage::Text(text) => {
-                let text = text.to_string();
-                // Parse incoming JSON: { "type": "message", "text": "hello" }
-                let incoming: serde_json::Value =
-                    serde_json::from_str(&text).unwrap_or(json!({"text": text}));
-                let user_text = incoming["text"].as_str().unwrap_or("").trim().to_string();
-
-                if user_text.is_empty() {
-                    continue;
-                }
+                let user_text = match voice::parse_frame(&text, &config) {
+                    Ok(Some(voice::Incoming::Text(text))) => text,
+                    Ok(Some(voice::Incoming::Audio(clip))) => {
+                        let typing = json!({ "type": "typing" });
+                        let _ = ws_sender
+                            .send(WsMessage::Text(typing.to_string().into()))
+                            .await;
+                        let endpoint = voice::stt_endpoint(&app_handle, &config);
+                        match voice::transcribe(&stt_client, endpoint.as_ref(), clip).await {
+                            Ok(transcript) if !transcript.is_empty() => {
+                                // Lets the page replace its placeholder bubble
+                                let echo = json!({ "type": "transcript", "text": &transcript });
+                                let _ = ws_sender
+                                    .send(WsMessage::Text(echo.to_string().into()))
+                                    .await;
+                                transcript
+                            }
+                            Ok(_) => {
+                                let msg = json!({
+                                    "type": "error",
+                                    "text": i18n::t("webchat.no_speech", &[])
+                                });
+                                let _ = ws_sender
+                                    .send(WsMessage::Text(msg.to_string().into()))
+                                    .await;
+                                continue;
+                            }
+                            Err(e) => {
+                                let msg = json!({ "type": "error", "text": e });
+                                let _ = ws_sender
+                                    .send(WsMessage::Text(msg.to_string().into()))
+                                    .await;
+                                continue;
+                            }
+                        }
+                    }
+                    Ok(None) => continue,
+                    Err(e) => {
+                        let msg = json!({ "type": "error", "text": e });
+                        let _ = ws_sender
+                            .send(WsMessage::Text(msg.to_string().into()))
+                            .await;
+                        continue;
+                    }
+                };
 
                 MESSAGE_COUNT.fetch_add(1, Ordering::Relaxed);
                 debug!(
@@ -294,7 +398,10 @@ async fn handle_websocket<S: AsyncRead + AsyncWrite + Unpin>(
 
                 let response = match reply {
                     Ok(text) => json!({ "type": "message", "text": text }),
-                    Err(e) => json!({ "type": "error", "text": format!("Error: {}", e) }),
+                    Err(e) => json!({
+                        "type": "error",
+                        "text": i18n::t("webchat.error", &[("error", &e)])
+                    }),
                 };
 
                 if ws_sender
@@ -0,0 +1,104 @@
+// Web Chat — bind address checks and the shareable URL
+//
+// Loopback binds stay on this machine. Anything else is reachable from the
+// network, which should come with TLS since the access token and session
+// cookie would otherwise travel in plaintext.
+
+use super::WebChatConfig;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+
+/// True when `bind` only accepts connections from this machine.
+pub(crate) fn is_loopback_bind(bind: &str) -> bool {
+    let bind = bind.trim().trim_start_matches('[').trim_end_matches(']');
+    if bind.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    bind.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// True for 0.0.0.0 / :: — every interface, including the LAN.
+pub(crate) fn is_wildcard_bind(bind: &str) -> bool {
+    let bind = bind.trim().trim_start_matches('[').trim_end_matches(']');
+    bind.parse::<IpAddr>().is_ok_and(|ip| ip.is_unspecified())
+}
+
+pub(crate) fn tls_configured(config: &WebChatConfig) -> bool {
+    let set = |path: &Option<String>| path.as_deref().is_some_and(|p| !p.trim().is_empty());
+    set(&config.tls_cert_path) && set(&config.tls_key_path)
+}
+
+/// The machine's LAN address — the source IP the OS would route outbound
+/// traffic from. Connecting a UDP socket sends no packets.
+pub(crate) fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(192, 168, 0, 1), 9)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+/// The URL to share for `config`. Wildcard binds resolve to the LAN address
+/// (falling back to localhost when it can't be detected).
+pub(crate) fn share_url(config: &WebChatConfig) -> String {
+    let scheme = if tls_configured(config) {
+        "https"
+    } else {
+        "http"
+    };
+    let host = if is_wildcard_bind(&config.bind_address) {
+        lan_ip().map_or("127.0.0.1".to_string(), |ip| ip.to_string())
+    } else {
+        config.bind_address.trim().to_string()
+    };
+    let host = match host.parse::<IpAddr>() {
+        Ok(IpAddr::V6(_)) if !host.starts_with('[') => format!("[{}]", host),
+        _ => host,
+    };
+    format!("{}://{}:{}", scheme, host, config.port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_vs_lan_binds() {
+        for bind in [
+            "127.0.0.1",
+            "127.0.0.53",
+            "localhost",
+            "LOCALHOST",
+            "::1",
+            "[::1]",
+        ] {
+            assert!(is_loopback_bind(bind), "{bind} should be loopback");
+        }
+        for bind in [
+            "0.0.0.0",
+            "::",
+            "192.168.1.20",
+            "10.0.0.5",
+            "example.com",
+            "",
+        ] {
+            assert!(!is_loopback_bind(bind), "{bind} should not be loopback");
+        }
+        assert!(is_wildcard_bind("0.0.0.0"));
+        assert!(is_wildcard_bind("[::]"));
+        assert!(!is_wildcard_bind("192.168.1.20"));
+    }
+
+    #[test]
+    fn share_url_uses_bind_address_and_scheme() {
+        let mut config = WebChatConfig {
+            bind_address: "192.168.1.20".into(),
+            port: 3939,
+            ..Default::default()
+        };
+        assert_eq!(share_url(&config), "http://192.168.1.20:3939");
+
+        config.bind_address = "::1".into();
+        config.tls_cert_path = Some("/tmp/cert.pem".into());
+        config.tls_key_path = Some("/tmp/key.pem".into());
+        assert_eq!(share_url(&config), "https://[::1]:3939");
+    }
+}
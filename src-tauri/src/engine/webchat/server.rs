@@ -3,10 +3,12 @@
 // TCP/TLS listener, HTTP routing, auth endpoint, and stream utilities.
 
 use super::html::build_chat_html;
+use super::network::share_url;
 use super::session::{create_session, extract_cookie, validate_session};
 use super::{get_stop_signal, handle_websocket, WebChatConfig};
 
 use crate::atoms::error::EngineResult;
+use crate::engine::channels::{read_request, reject_request, HttpLimits, RequestError};
 use log::{info, warn};
 use serde_json::json;
 use std::io::BufReader as StdBufReader;
@@ -15,7 +17,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use tauri::Emitter;
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::net::TcpListener;
 
 // ── Prefixed Stream (replays buffered bytes then delegates) ────────────
@@ -119,16 +121,6 @@ pub(crate) async fn run_server(
     // Build optional TLS acceptor
     let tls_acceptor = build_tls_acceptor(&config)?;
 
-    if config.bind_address != "127.0.0.1"
-        && config.bind_address != "localhost"
-        && tls_acceptor.is_none()
-    {
-        warn!(
-            "[webchat] Binding to {} without TLS — credentials sent in plaintext over the network",
-            config.bind_address
-        );
-    }
-
     let scheme = if tls_acceptor.is_some() {
         "https"
     } else {
@@ -141,6 +133,7 @@ pub(crate) async fn run_server(
         json!({
             "kind": "connected",
             "address": &addr,
+            "url": share_url(&config),
             "title": &config.page_title,
             "tls": tls_acceptor.is_some(),
         }),
@@ -195,6 +188,13 @@ pub(crate) async fn run_server(
 
 // ── Connection Handler ─────────────────────────────────────────────────
 
+/// Auth bodies are tiny JSON objects; the WS upgrade has no body at all.
+const REQUEST_LIMITS: HttpLimits = HttpLimits {
+    max_header_bytes: 16 * 1024,
+    max_body_bytes: 4 * 1024,
+    timeout: std::time::Duration::from_secs(10),
+};
+
 async fn handle_connection(
     mut stream: Box<dyn ChatStream>,
     peer: std::net::SocketAddr,
@@ -203,15 +203,14 @@ async fn handle_connection(
     _stop: Arc<AtomicBool>,
 ) -> EngineResult<()> {
     // Read the HTTP request (consumed — PrefixedStream replays it for WS)
-    let mut buf = vec![0u8; 8192];
-    let n = stream
-        .read(&mut buf)
-        .await
-        .map_err(|e| format!("Read: {e}"))?;
-    if n == 0 {
-        return Ok(());
-    }
-    buf.truncate(n);
+    let buf = match read_request(&mut stream, &REQUEST_LIMITS).await {
+        Ok(buf) => buf,
+        Err(RequestError::Closed) => return Ok(()),
+        Err(e) => {
+            reject_request(&mut stream, &e).await;
+            return Err(format!("Request from {peer}: {e}").into());
+        }
+    };
 
     let request_str = String::from_utf8_lossy(&buf);
     let first_line = request_str.lines().next().unwrap_or("");
@@ -304,7 +303,10 @@ async fn handle_auth(
 // ── HTML Chat Page ─────────────────────────────────────────────────────
 
 async fn serve_html(mut stream: Box<dyn ChatStream>, config: &WebChatConfig) -> EngineResult<()> {
-    let html = build_chat_html(&config.page_title);
+    let html = build_chat_html(
+        &config.page_title,
+        config.voice_input.then_some(config.max_voice_seconds),
+    );
     let response = format!(
         "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
         html.len(), html
@@ -0,0 +1,340 @@
+// Web Chat — public tunnel
+//
+// Shares the chat beyond the LAN without port-forwarding by running a
+// tunnel client next to the bridge: `cloudflared` (quick tunnel, no account
+// needed) or `ngrok` (uses the authtoken from its own config). The client
+// forwards a public HTTPS hostname to the local port; we read the hostname
+// from its output and emit the share link as a `webchat-status` event.
+//
+// The access token rides in the URL fragment (`#token=…`). Browsers never
+// send fragments, so neither the tunnel service nor the server sees it in a
+// request line — the chat page reads it to prefill the login form.
+
+use super::network::{is_loopback_bind, is_wildcard_bind, tls_configured};
+use super::WebChatConfig;
+use crate::atoms::error::EngineResult;
+use log::{info, warn};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelProvider {
+    /// No tunnel — reachable on the bind address only.
+    #[default]
+    None,
+    Cloudflared,
+    Ngrok,
+}
+
+impl TunnelProvider {
+    fn binary(self) -> Option<&'static str> {
+        match self {
+            TunnelProvider::None => None,
+            TunnelProvider::Cloudflared => Some("cloudflared"),
+            TunnelProvider::Ngrok => Some("ngrok"),
+        }
+    }
+
+    fn install_hint(self) -> &'static str {
+        match self {
+            TunnelProvider::Cloudflared => {
+                "https://developers.cloudflare.com/cloudflare-one/connections/connect-networks/downloads/"
+            }
+            _ => "https://ngrok.com/download",
+        }
+    }
+}
+
+/// The running tunnel client, killed on stop.
+static TUNNEL: Mutex<Option<Child>> = Mutex::new(None);
+/// Public hostname once the tunnel reports it.
+static PUBLIC_HOST: RwLock<Option<String>> = RwLock::new(None);
+/// Bumped per start so a finished client can't clear its successor's state.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Public hostname of the running tunnel, if it's up.
+pub(crate) fn public_host() -> Option<String> {
+    PUBLIC_HOST.read().clone()
+}
+
+/// The link to share: the tunnel's HTTPS hostname with the access token in
+/// the fragment.
+pub(crate) fn public_url(host: &str, token: &str) -> String {
+    let host = host
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    format!("https://{}/#token={}", host, urlencoding::encode(token))
+}
+
+/// Find `name` on PATH or in the usual install locations.
+fn find_binary(name: &str) -> Option<PathBuf> {
+    let file = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let mut search: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    search.extend(
+        ["/usr/local/bin", "/opt/homebrew/bin", "/usr/bin"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    if let Some(home) = dirs::home_dir() {
+        search.push(home.join(".local/bin"));
+    }
+    search
+        .into_iter()
+        .map(|dir| dir.join(&file))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Resolve the configured tunnel client. `Ok(None)` when no tunnel is
+/// configured; an error naming the missing binary when it isn't installed.
+pub(crate) fn resolve_binary(config: &WebChatConfig) -> EngineResult<Option<PathBuf>> {
+    let Some(name) = config.tunnel.binary() else {
+        return Ok(None);
+    };
+    find_binary(name).map(Some).ok_or_else(|| {
+        format!(
+            "Public sharing needs `{}`, which isn't installed or on PATH. Install it from {} \
+             or turn the tunnel off.",
+            name,
+            config.tunnel.install_hint()
+        )
+        .into()
+    })
+}
+
+/// The local URL the tunnel forwards to.
+fn local_url(config: &WebChatConfig) -> String {
+    let scheme = if tls_configured(config) {
+        "https"
+    } else {
+        "http"
+    };
+    let host = if is_wildcard_bind(&config.bind_address) || is_loopback_bind(&config.bind_address) {
+        "127.0.0.1".to_string()
+    } else {
+        let bind = config.bind_address.trim();
+        match bind.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => format!("[{}]", bind),
+            _ => bind.to_string(),
+        }
+    };
+    format!("{}://{}:{}", scheme, host, config.port)
+}
+
+fn tunnel_args(provider: TunnelProvider, local_url: &str) -> Vec<String> {
+    let mut args: Vec<String> = match provider {
+        TunnelProvider::Cloudflared => vec!["tunnel", "--no-autoupdate", "--url", local_url],
+        TunnelProvider::Ngrok => vec!["http", local_url, "--log", "stdout", "--log-format", "json"],
+        TunnelProvider::None => vec![],
+    }
+    .into_iter()
+    .map(str::to_string)
+    .collect();
+    // A local TLS listener usually has a self-signed certificate
+    if provider == TunnelProvider::Cloudflared && local_url.starts_with("https://") {
+        args.push("--no-tls-verify".into());
+    }
+    args
+}
+
+/// The public hostname in a line of tunnel client output, if it has one.
+fn parse_public_host(provider: TunnelProvider, line: &str) -> Option<String> {
+    let url = match provider {
+        // "|  https://quiet-sun-1234.trycloudflare.com  |"
+        TunnelProvider::Cloudflared => line
+            .split(|c: char| c.is_whitespace() || c == '|')
+            .find(|word| word.starts_with("https://") && word.contains(".trycloudflare.com"))?
+            .to_string(),
+        // {"msg":"started tunnel","url":"https://ab12.ngrok-free.app",...}
+        TunnelProvider::Ngrok => serde_json::from_str::<serde_json::Value>(line)
+            .ok()?
+            .get("url")?
+            .as_str()
+            .filter(|url| url.starts_with("https://"))?
+            .to_string(),
+        TunnelProvider::None => return None,
+    };
+    let host = url.trim_start_matches("https://").trim_end_matches('/');
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+fn forward_lines<R: AsyncRead + Unpin + Send + 'static>(reader: R, tx: mpsc::Sender<String>) {
+    tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Start the tunnel client for `config` and emit the public URL once it's
+/// known. Runs until `stop()` or the bridge's stop signal.
+pub(crate) fn start(
+    app_handle: tauri::AppHandle,
+    config: &WebChatConfig,
+    binary: PathBuf,
+    stop_signal: Arc<AtomicBool>,
+) {
+    let provider = config.tunnel;
+    let token = config.access_token.clone();
+    let args = tunnel_args(provider, &local_url(config));
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let current = move || GENERATION.load(Ordering::SeqCst) == generation;
+
+    tauri::async_runtime::spawn(async move {
+        let mut child = match Command::new(&binary)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                let message = format!("Couldn't start {}: {}", binary.display(), e);
+                warn!("[webchat] {}", message);
+                let _ = app_handle.emit(
+                    "webchat-status",
+                    json!({ "kind": "tunnel_error", "message": message }),
+                );
+                return;
+            }
+        };
+        info!(
+            "[webchat] Tunnel started: {} {}",
+            binary.display(),
+            args.join(" ")
+        );
+
+        let (tx, mut rx) = mpsc::channel::<String>(64);
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, tx);
+        }
+        // The bridge may have been stopped (or restarted) while the client
+        // was starting
+        if stop_signal.load(Ordering::Relaxed) || !current() {
+            let _ = child.start_kill();
+            return;
+        }
+        *TUNNEL.lock() = Some(child);
+
+        // Both pipes close when the client exits
+        while let Some(line) = rx.recv().await {
+            if !current() || PUBLIC_HOST.read().is_some() {
+                continue;
+            }
+            if let Some(host) = parse_public_host(provider, &line) {
+                info!("[webchat] Public URL: https://{}", host);
+                *PUBLIC_HOST.write() = Some(host.clone());
+                let _ = app_handle.emit(
+                    "webchat-status",
+                    json!({ "kind": "tunnel_url", "url": public_url(&host, &token) }),
+                );
+            }
+        }
+
+        if current() {
+            *PUBLIC_HOST.write() = None;
+            TUNNEL.lock().take();
+        }
+        info!("[webchat] Tunnel stopped");
+        let _ = app_handle.emit("webchat-status", json!({ "kind": "tunnel_stopped" }));
+    });
+}
+
+/// Kill the tunnel client, if one is running.
+pub(crate) fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Some(mut child) = TUNNEL.lock().take() {
+        let _ = child.start_kill();
+    }
+    *PUBLIC_HOST.write() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_url_puts_token_in_fragment() {
+        assert_eq!(
+            public_url("quiet-sun-1234.trycloudflare.com", "abc123"),
+            "https://quiet-sun-1234.trycloudflare.com/#token=abc123"
+        );
+        // Scheme and trailing slash on the host are tolerated, token is encoded
+        assert_eq!(
+            public_url("https://ab12.ngrok-free.app/", "a b&c"),
+            "https://ab12.ngrok-free.app/#token=a%20b%26c"
+        );
+    }
+
+    #[test]
+    fn parses_hostname_from_tunnel_output() {
+        let cloudflared = "2025-01-01T00:00:00Z INF |  https://quiet-sun-1234.trycloudflare.com                                |";
+        assert_eq!(
+            parse_public_host(TunnelProvider::Cloudflared, cloudflared).as_deref(),
+            Some("quiet-sun-1234.trycloudflare.com")
+        );
+        assert!(parse_public_host(
+            TunnelProvider::Cloudflared,
+            "INF Requesting new quick Tunnel on trycloudflare.com..."
+        )
+        .is_none());
+
+        let ngrok = r#"{"lvl":"info","msg":"started tunnel","name":"command_line","url":"https://ab12.ngrok-free.app"}"#;
+        assert_eq!(
+            parse_public_host(TunnelProvider::Ngrok, ngrok).as_deref(),
+            Some("ab12.ngrok-free.app")
+        );
+        assert!(parse_public_host(TunnelProvider::Ngrok, "not json").is_none());
+    }
+
+    #[test]
+    fn tunnel_targets_the_local_listener() {
+        let mut config = WebChatConfig {
+            bind_address: "0.0.0.0".into(),
+            port: 3939,
+            ..Default::default()
+        };
+        assert_eq!(local_url(&config), "http://127.0.0.1:3939");
+        assert_eq!(
+            tunnel_args(TunnelProvider::Cloudflared, &local_url(&config)),
+            vec![
+                "tunnel",
+                "--no-autoupdate",
+                "--url",
+                "http://127.0.0.1:3939"
+            ]
+        );
+
+        config.tls_cert_path = Some("/tmp/cert.pem".into());
+        config.tls_key_path = Some("/tmp/key.pem".into());
+        let args = tunnel_args(TunnelProvider::Cloudflared, &local_url(&config));
+        assert_eq!(args[3], "https://127.0.0.1:3939");
+        assert!(args.contains(&"--no-tls-verify".to_string()));
+    }
+}
@@ -0,0 +1,226 @@
+// Paw Agent Engine — Web Chat voice input
+//
+// The chat page records a voice message with MediaRecorder and sends it as
+//   { "type": "audio", "mime_type": "audio/webm", "data": "<base64>", "duration_ms": 4200 }
+// The bridge checks format, size and duration, transcribes it with a
+// Whisper-compatible endpoint, echoes the transcript back to the page and
+// hands it to the agent exactly as if it had been typed.
+//
+// The endpoint is `stt_base_url` from the Web Chat config when set (e.g. a
+// self-hosted faster-whisper server), otherwise the OpenAI provider.
+
+use super::WebChatConfig;
+use crate::engine::speech::{self, SttEndpoint};
+use crate::engine::state::EngineState;
+use crate::engine::types::ProviderKind;
+use openpawz_core::engine::i18n;
+use serde_json::Value;
+use tauri::Manager;
+
+/// Largest decoded recording accepted, well under Whisper's 25 MB cap.
+pub(super) const MAX_AUDIO_BYTES: usize = 10 * 1024 * 1024;
+
+/// A frame from the chat page, after validation.
+#[derive(Debug, PartialEq)]
+pub(super) enum Incoming {
+    Text(String),
+    Audio(AudioClip),
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct AudioClip {
+    pub bytes: Vec<u8>,
+    pub mime_type: String,
+}
+
+/// Parse one WebSocket text frame. `Ok(None)` for frames with nothing to
+/// answer; `Err` carries a message to show the user.
+pub(super) fn parse_frame(frame: &str, config: &WebChatConfig) -> Result<Option<Incoming>, String> {
+    // Plain text (not JSON) is treated as a message
+    let incoming: Value =
+        serde_json::from_str(frame).unwrap_or_else(|_| serde_json::json!({ "text": frame }));
+
+    if incoming["type"].as_str() != Some("audio") {
+        let text = incoming["text"].as_str().unwrap_or("").trim().to_string();
+        return Ok((!text.is_empty()).then_some(Incoming::Text(text)));
+    }
+
+    if !config.voice_input {
+        return Err(i18n::t("webchat.voice_disabled", &[]));
+    }
+    let mime_type = incoming["mime_type"].as_str().unwrap_or("audio/webm");
+    if speech::audio_extension(mime_type).is_none() {
+        return Err(i18n::t(
+            "webchat.voice_unsupported",
+            &[("format", &mime_type)],
+        ));
+    }
+    let max_ms = u64::from(config.max_voice_seconds) * 1000;
+    if incoming["duration_ms"].as_u64().unwrap_or(0) > max_ms {
+        return Err(i18n::t(
+            "webchat.voice_too_long",
+            &[("seconds", &config.max_voice_seconds)],
+        ));
+    }
+    let data = incoming["data"].as_str().unwrap_or("");
+    // Reject before decoding: base64 is 4/3 the size of the audio
+    if data.len() / 4 * 3 > MAX_AUDIO_BYTES {
+        return Err(i18n::t(
+            "webchat.voice_too_large",
+            &[("mb", &(MAX_AUDIO_BYTES / (1024 * 1024)))],
+        ));
+    }
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data)
+        .map_err(|_| i18n::t("webchat.voice_undecodable", &[]))?;
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Incoming::Audio(AudioClip {
+        bytes,
+        mime_type: mime_type.to_string(),
+    })))
+}
+
+/// The configured speech-to-text endpoint, if any.
+pub(super) fn stt_endpoint(
+    app_handle: &tauri::AppHandle,
+    config: &WebChatConfig,
+) -> Option<SttEndpoint> {
+    let model = config
+        .stt_model
+        .clone()
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| speech::DEFAULT_STT_MODEL.into());
+    if let Some(base_url) = config.stt_base_url.clone().filter(|u| !u.trim().is_empty()) {
+        return Some(SttEndpoint {
+            base_url,
+            api_key: config.stt_api_key.clone().filter(|k| !k.is_empty()),
+            model,
+        });
+    }
+    let state = app_handle.try_state::<EngineState>()?;
+    let cfg = state.config.lock();
+    cfg.providers
+        .iter()
+        .find(|p| p.kind == ProviderKind::OpenAI)
+        .map(|p| SttEndpoint {
+            base_url: p
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://api.openai.com/v1".into()),
+            api_key: Some(p.api_key.clone()),
+            model,
+        })
+}
+
+/// Transcribe a clip. Errors are worded for the person in the chat.
+pub(super) async fn transcribe(
+    client: &reqwest::Client,
+    endpoint: Option<&SttEndpoint>,
+    clip: AudioClip,
+) -> Result<String, String> {
+    let endpoint = endpoint.ok_or_else(|| i18n::t("webchat.voice_not_configured", &[]))?;
+    speech::transcribe(client, endpoint, clip.bytes, &clip.mime_type, None)
+        .await
+        .map_err(|e| {
+            log::warn!("[webchat] Transcription failed: {}", e);
+            i18n::t("webchat.transcription_failed", &[])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_support::{self, MockReply};
+
+    fn voice_config() -> WebChatConfig {
+        WebChatConfig {
+            voice_input: true,
+            ..Default::default()
+        }
+    }
+
+    fn audio_frame(bytes: &[u8], mime_type: &str, duration_ms: u64) -> String {
+        serde_json::json!({
+            "type": "audio",
+            "mime_type": mime_type,
+            "data": base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes),
+            "duration_ms": duration_ms,
+        })
+        .to_string()
+    }
+
+    /// Answers one request with a Whisper-style JSON body, returning the
+    /// raw request it received.
+    async fn mock_whisper(transcript: &'static str) -> (String, tokio::task::JoinHandle<Vec<u8>>) {
+        let body = serde_json::json!({ "text": transcript }).to_string();
+        let (url, handle) = test_support::serve_once(MockReply::json(200, body)).await;
+        (format!("{}/v1", url), handle)
+    }
+
+    #[tokio::test]
+    async fn audio_frame_is_decoded_and_transcribed() {
+        let recording = b"\x1aE\xdf\xa3 fake webm opus recording".to_vec();
+        let frame = audio_frame(&recording, "audio/webm;codecs=opus", 3_000);
+
+        let Some(Incoming::Audio(clip)) = parse_frame(&frame, &voice_config()).unwrap() else {
+            panic!("audio frame should route to speech-to-text");
+        };
+        assert_eq!(clip.bytes, recording);
+
+        let (base_url, server) = mock_whisper(" What's the weather tomorrow? ").await;
+        let endpoint = SttEndpoint {
+            base_url,
+            api_key: None,
+            model: "whisper-small".into(),
+        };
+        let text = transcribe(&reqwest::Client::new(), Some(&endpoint), clip)
+            .await
+            .unwrap();
+        assert_eq!(text, "What's the weather tomorrow?");
+
+        let received = server.await.unwrap();
+        let head = String::from_utf8_lossy(&received);
+        assert!(head.starts_with("POST /v1/audio/transcriptions"));
+        assert!(head.contains("whisper-small"));
+        assert!(head.contains(r#"filename="audio.webm""#));
+        assert!(received
+            .windows(recording.len())
+            .any(|w| w == recording.as_slice()));
+    }
+
+    #[test]
+    fn text_frames_bypass_transcription() {
+        let config = voice_config();
+        assert_eq!(
+            parse_frame(r#"{"type":"message","text":" hi "}"#, &config),
+            Ok(Some(Incoming::Text("hi".into())))
+        );
+        assert_eq!(
+            parse_frame("plain", &config),
+            Ok(Some(Incoming::Text("plain".into())))
+        );
+        assert_eq!(
+            parse_frame(r#"{"type":"message","text":""}"#, &config),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn voice_limits_are_enforced() {
+        let config = voice_config();
+        let err = parse_frame(&audio_frame(b"abc", "video/quicktime", 1_000), &config).unwrap_err();
+        assert!(err.contains("Unsupported audio format"), "{}", err);
+
+        let too_long = u64::from(config.max_voice_seconds) * 1000 + 1;
+        let err = parse_frame(&audio_frame(b"abc", "audio/ogg", too_long), &config).unwrap_err();
+        assert!(err.contains("too long"), "{}", err);
+
+        let huge = vec![0u8; MAX_AUDIO_BYTES + 3];
+        let err = parse_frame(&audio_frame(&huge, "audio/webm", 1_000), &config).unwrap_err();
+        assert!(err.contains("too large"), "{}", err);
+
+        let off = WebChatConfig::default();
+        assert!(parse_frame(&audio_frame(b"abc", "audio/webm", 1_000), &off).is_err());
+    }
+}
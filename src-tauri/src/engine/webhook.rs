@@ -15,6 +15,7 @@
 
 use crate::atoms::error::{EngineError, EngineResult};
 use crate::engine::channels;
+use crate::engine::util::safe_truncate;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -23,7 +24,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::Emitter;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 use tokio::net::TcpListener;
 
 // ── Webhook Config ─────────────────────────────────────────────────────
@@ -282,16 +283,16 @@ async fn handle_request(
     config: Arc<WebhookConfig>,
     rate_limiter: Arc<RateLimiter>,
 ) -> EngineResult<()> {
-    // Read the full HTTP request (up to 64KB)
-    let mut buf = vec![0u8; 65536];
-    let n = stream
-        .read(&mut buf)
-        .await
-        .map_err(|e| format!("Read error: {}", e))?;
-    if n == 0 {
-        return Ok(());
-    }
-    let raw = String::from_utf8_lossy(&buf[..n]).to_string();
+    // Read the full HTTP request (64KB body cap, 10s deadline)
+    let buf = match channels::read_request(&mut stream, &channels::HttpLimits::default()).await {
+        Ok(buf) => buf,
+        Err(channels::RequestError::Closed) => return Ok(()),
+        Err(e) => {
+            channels::reject_request(&mut stream, &e).await;
+            return Err(format!("Request rejected: {}", e).into());
+        }
+    };
+    let raw = String::from_utf8_lossy(&buf).to_string();
 
     // Parse first line: "METHOD /path HTTP/1.x"
     let first_line = raw.lines().next().unwrap_or("");
@@ -458,7 +459,7 @@ async fn handle_request(
                 "agent_id": agent_id,
                 "user_id": webhook_req.user_id,
                 "message_preview": if webhook_req.message.len() > 80 {
-                    format!("{}…", &webhook_req.message[..80])
+                    format!("{}…", safe_truncate(&webhook_req.message, 80))
                 } else {
                     webhook_req.message.clone()
                 },
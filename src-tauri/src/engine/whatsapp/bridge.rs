@@ -1,10 +1,11 @@
 // WhatsApp Bridge — Core Bridge Lifecycle
-// statics, get_stop_signal, start_bridge, stop_bridge, get_status, run_whatsapp_bridge
+// statics, get_stop_signal, start_bridge, stop_bridge, get_status, rotate_webhook_secret,
+// run_whatsapp_bridge
 
 use super::config::{WhatsAppConfig, CONFIG_KEY};
 use super::docker::ensure_evolution_container;
-use super::evolution_api::create_evolution_instance;
-use super::webhook::run_webhook_listener;
+use super::evolution_api::{create_evolution_instance, set_evolution_webhook};
+use super::webhook::{run_webhook_listener, set_webhook_auth};
 use crate::atoms::error::EngineResult;
 use crate::engine::channels::{self, ChannelStatus};
 use log::{error, info, warn};
@@ -123,6 +124,27 @@ pub fn get_status(app_handle: &tauri::AppHandle) -> ChannelStatus {
     }
 }
 
+/// Replace the webhook secret. A running bridge starts rejecting the old one
+/// immediately, the instance webhook is re-pointed at the new URL, and the
+/// bridge restarts so the container's global webhook picks it up too.
+pub async fn rotate_webhook_secret(app_handle: &tauri::AppHandle) -> EngineResult<()> {
+    let mut config: WhatsAppConfig = channels::load_channel_config(app_handle, CONFIG_KEY)?;
+    config.rotate_webhook_secret();
+    channels::save_channel_config(app_handle, CONFIG_KEY, &config)?;
+    info!("[whatsapp] Webhook secret rotated");
+
+    if BRIDGE_RUNNING.load(Ordering::Relaxed) {
+        set_webhook_auth(&config);
+        set_evolution_webhook(&config).await?;
+        // The container's global webhook URL carries the secret too; a
+        // restart recreates the container with the new one.
+        stop_bridge();
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        start_bridge(app_handle.clone())?;
+    }
+    Ok(())
+}
+
 // ── Main Bridge Loop ───────────────────────────────────────────────────
 
 /// The main bridge loop:
@@ -177,6 +199,7 @@ pub(crate) async fn run_whatsapp_bridge(
 
     // Step 3: Start webhook listener
     let webhook_port = config.webhook_port;
+    set_webhook_auth(&config);
     let app_for_webhook = app_handle.clone();
     let stop_for_webhook = stop.clone();
 
@@ -25,6 +25,12 @@ pub struct WhatsAppConfig {
     pub api_port: u16,
     /// Port for the local webhook listener (default: 8086)
     pub webhook_port: u16,
+    /// Path the webhook listener accepts callbacks on (randomized by default)
+    pub webhook_path: String,
+    /// Shared secret authenticating Evolution callbacks (auto-generated).
+    /// Sent as the path segment after `webhook_path`, as a bearer token, or
+    /// used as the HMAC key for an `X-Paw-Signature: sha256=<hex>` header.
+    pub webhook_secret: String,
     /// "open" | "allowlist" | "pairing"
     pub dm_policy: String,
     /// Allowed phone numbers or WhatsApp JIDs (e.g. "1234567890" or "1234567890@s.whatsapp.net")
@@ -64,6 +70,8 @@ impl Default for WhatsAppConfig {
             api_key,
             api_port: 8085,
             webhook_port: 8086,
+            webhook_path: format!("/webhook/wa-{}", random_hex(6)),
+            webhook_secret: random_hex(24),
             dm_policy: "pairing".into(),
             allowed_users: vec![],
             pending_users: vec![],
@@ -77,6 +85,40 @@ impl Default for WhatsAppConfig {
     }
 }
 
+fn random_hex(bytes: usize) -> String {
+    let mut buf = vec![0u8; bytes];
+    getrandom::getrandom(&mut buf).expect("OS CSPRNG failed");
+    buf.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl WhatsAppConfig {
+    /// `webhook_path` with a leading slash and no trailing one.
+    pub(crate) fn webhook_path(&self) -> String {
+        let path = self.webhook_path.trim().trim_matches('/');
+        if path.is_empty() {
+            "/webhook/whatsapp".into()
+        } else {
+            format!("/{}", path)
+        }
+    }
+
+    /// Replace the webhook secret; the old one stops working immediately.
+    pub(crate) fn rotate_webhook_secret(&mut self) {
+        self.webhook_secret = random_hex(24);
+    }
+}
+
+/// Callback URL Evolution (inside Docker) posts events to. The secret path
+/// segment authenticates the caller; by-event webhooks append `/<event>`.
+pub(crate) fn webhook_url(config: &WhatsAppConfig) -> String {
+    format!(
+        "http://host.docker.internal:{}{}/{}",
+        config.webhook_port,
+        config.webhook_path(),
+        config.webhook_secret
+    )
+}
+
 // ── Config Persistence ─────────────────────────────────────────────────
 
 pub fn load_config(app_handle: &tauri::AppHandle) -> EngineResult<WhatsAppConfig> {
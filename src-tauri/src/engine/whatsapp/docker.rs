@@ -3,8 +3,9 @@
 // ensure_docker_ready, ensure_evolution_container
 
 use super::bridge::get_stop_signal;
-use super::config::WhatsAppConfig;
+use super::config::{webhook_url, WhatsAppConfig};
 use crate::atoms::error::{EngineError, EngineResult};
+use crate::engine::util::safe_truncate;
 use log::{error, info, warn};
 use serde_json::json;
 use std::sync::atomic::Ordering;
@@ -311,6 +312,49 @@ async fn force_remove_container(docker: &bollard::Docker, container_id: &str) {
 
 // ── Evolution Container ────────────────────────────────────────────────
 
+/// Environment the Evolution container is created with. The global webhook
+/// URL carries the webhook secret, so a rotated secret means a new container.
+fn evolution_env(config: &WhatsAppConfig) -> Vec<String> {
+    vec![
+        // v1.x defaults to JWT auth; explicitly switch to API key auth
+        "AUTHENTICATION_TYPE=apikey".to_string(),
+        format!("AUTHENTICATION_API_KEY={}", config.api_key),
+        "SERVER_PORT=8080".to_string(),
+        // Webhook: point back to Paw's webhook listener
+        format!("WEBHOOK_GLOBAL_URL={}", webhook_url(config)),
+        "WEBHOOK_GLOBAL_ENABLED=true".to_string(),
+        "WEBHOOK_GLOBAL_WEBHOOK_BY_EVENTS=true".to_string(),
+        // Events we care about
+        "WEBHOOK_EVENTS_MESSAGES_UPSERT=true".to_string(),
+        "WEBHOOK_EVENTS_QRCODE_UPDATED=true".to_string(),
+        "WEBHOOK_EVENTS_CONNECTION_UPDATE=true".to_string(),
+        // Disable features we don't need
+        "WEBHOOK_EVENTS_MESSAGES_UPDATE=false".to_string(),
+        "WEBHOOK_EVENTS_SEND_MESSAGE=false".to_string(),
+        // Database: SQLite inside the container (persisted via volume)
+        "DATABASE_PROVIDER=sqlite".to_string(),
+        "DATABASE_CONNECTION_URI=file:./data/evolution.db".to_string(),
+    ]
+}
+
+/// Which part of an existing container's env no longer matches the config,
+/// if any.
+fn stale_env(env: &[String], config: &WhatsAppConfig) -> Option<&'static str> {
+    let var = |name: &str| {
+        let prefix = format!("{}=", name);
+        env.iter().find_map(|e| e.strip_prefix(&prefix))
+    };
+    if var("AUTHENTICATION_API_KEY").is_some_and(|key| key != config.api_key) {
+        Some("API key")
+    } else if var("WEBHOOK_GLOBAL_URL").is_some_and(|url| url != webhook_url(config)) {
+        // Older containers post to the unauthenticated webhook URL, and a
+        // rotated secret leaves the old one in place
+        Some("webhook URL")
+    } else {
+        None
+    }
+}
+
 /// Ensure the Evolution API Docker container is running.
 /// Pulls the image if needed, creates and starts the container.
 pub(crate) async fn ensure_evolution_container(
@@ -357,26 +401,18 @@ pub(crate) async fn ensure_evolution_container(
             );
             true
         } else {
-            // Inspect env vars to check API key matches
+            // Inspect env vars to check API key and webhook URL match
             let inspect = docker.inspect_container(&container_id, None).await.ok();
-            let env_key = inspect
-                .as_ref()
-                .and_then(|i| i.config.as_ref())
-                .and_then(|c| c.env.as_ref())
-                .and_then(|envs| {
-                    envs.iter()
-                        .find(|e| e.starts_with("AUTHENTICATION_API_KEY="))
-                        .map(|e| e.trim_start_matches("AUTHENTICATION_API_KEY=").to_string())
-                });
-            if let Some(ref existing_key) = env_key {
-                if existing_key != &config.api_key {
-                    info!("[whatsapp] Container has stale API key, recreating...");
+            let env = inspect
+                .and_then(|i| i.config)
+                .and_then(|c| c.env)
+                .unwrap_or_default();
+            match stale_env(&env, config) {
+                Some(what) => {
+                    info!("[whatsapp] Container has stale {}, recreating...", what);
                     true
-                } else {
-                    false
                 }
-            } else {
-                false
+                None => false,
             }
         };
 
@@ -399,7 +435,7 @@ pub(crate) async fn ensure_evolution_container(
                 Ok(resp) if resp.status().is_success() || resp.status().as_u16() == 401 => {
                     info!(
                         "[whatsapp] Evolution API container already running and healthy: {}",
-                        &container_id[..12]
+                        safe_truncate(&container_id, 12)
                     );
                     return Ok(container_id);
                 }
@@ -484,29 +520,7 @@ pub(crate) async fn ensure_evolution_container(
 
     let container_config = ContainerCreateBody {
         image: Some(EVOLUTION_IMAGE.to_string()),
-        env: Some(vec![
-            // v1.x defaults to JWT auth; explicitly switch to API key auth
-            "AUTHENTICATION_TYPE=apikey".to_string(),
-            format!("AUTHENTICATION_API_KEY={}", config.api_key),
-            "SERVER_PORT=8080".to_string(),
-            // Webhook: point back to Paw's webhook listener
-            format!(
-                "WEBHOOK_GLOBAL_URL=http://host.docker.internal:{}/webhook/whatsapp",
-                config.webhook_port
-            ),
-            "WEBHOOK_GLOBAL_ENABLED=true".to_string(),
-            "WEBHOOK_GLOBAL_WEBHOOK_BY_EVENTS=true".to_string(),
-            // Events we care about
-            "WEBHOOK_EVENTS_MESSAGES_UPSERT=true".to_string(),
-            "WEBHOOK_EVENTS_QRCODE_UPDATED=true".to_string(),
-            "WEBHOOK_EVENTS_CONNECTION_UPDATE=true".to_string(),
-            // Disable features we don't need
-            "WEBHOOK_EVENTS_MESSAGES_UPDATE=false".to_string(),
-            "WEBHOOK_EVENTS_SEND_MESSAGE=false".to_string(),
-            // Database: SQLite inside the container (persisted via volume)
-            "DATABASE_PROVIDER=sqlite".to_string(),
-            "DATABASE_CONNECTION_URI=file:./data/evolution.db".to_string(),
-        ]),
+        env: Some(evolution_env(config)),
         host_config: Some(host_config),
         exposed_ports: Some(vec!["8080/tcp".to_string()]),
         ..Default::default()
@@ -525,7 +539,7 @@ pub(crate) async fn ensure_evolution_container(
     let container_id = container.id.clone();
     info!(
         "[whatsapp] Created Evolution API container: {}",
-        &container_id[..12]
+        safe_truncate(&container_id, 12)
     );
 
     // Start it
@@ -566,3 +580,54 @@ pub(crate) async fn ensure_evolution_container(
     }
     Err("WhatsApp service didn't start. It may need more time — try again in a moment.".into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::whatsapp::webhook::{check_webhook_request, WebhookAuth};
+
+    fn callback(config: &WhatsAppConfig, secret: &str) -> Vec<u8> {
+        let body = r#"{"event":"messages.upsert","data":{}}"#;
+        format!(
+            "POST {}/{}/messages-upsert HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            config.webhook_path(),
+            secret,
+            body.len(),
+            body
+        )
+        .into_bytes()
+    }
+
+    fn auth(config: &WhatsAppConfig) -> WebhookAuth {
+        WebhookAuth {
+            path: config.webhook_path(),
+            secret: config.webhook_secret.clone(),
+        }
+    }
+
+    #[test]
+    fn rotation_replaces_the_secret_everywhere() {
+        let mut config = WhatsAppConfig::default();
+        let old_secret = config.webhook_secret.clone();
+        let old_env = evolution_env(&config);
+        assert_eq!(stale_env(&old_env, &config), None);
+
+        config.rotate_webhook_secret();
+        assert_ne!(config.webhook_secret, old_secret);
+
+        // The running container's global webhook is flagged for recreation,
+        // and its replacement posts with the new secret
+        assert_eq!(stale_env(&old_env, &config), Some("webhook URL"));
+        let new_env = evolution_env(&config);
+        assert_eq!(stale_env(&new_env, &config), None);
+        let global_url = format!("WEBHOOK_GLOBAL_URL={}", webhook_url(&config));
+        assert!(new_env.contains(&global_url));
+        assert!(!global_url.contains(&old_secret));
+
+        // The listener accepts the new secret and rejects the old one
+        let auth = auth(&config);
+        assert!(check_webhook_request(&callback(&config, &config.webhook_secret), &auth).is_ok());
+        let stale = check_webhook_request(&callback(&config, &old_secret), &auth).unwrap_err();
+        assert_eq!(stale.status, "401 Unauthorized");
+    }
+}
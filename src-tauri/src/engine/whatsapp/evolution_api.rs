@@ -1,8 +1,8 @@
 // WhatsApp Bridge — Evolution API Helpers
 // create_evolution_instance, extract_qr_from_response, delete_evolution_instance,
-// connect_evolution_instance, send_whatsapp_message
+// connect_evolution_instance, set_evolution_webhook, send_whatsapp_message
 
-use super::config::{WhatsAppConfig, CONFIG_KEY};
+use super::config::{webhook_url, WhatsAppConfig, CONFIG_KEY};
 use crate::atoms::error::EngineResult;
 use crate::engine::channels;
 use crate::engine::util::safe_truncate;
@@ -27,7 +27,7 @@ pub(crate) async fn create_evolution_instance(config: &WhatsAppConfig) -> Engine
         "instanceName": config.instance_name,
         "token": instance_token,
         "qrcode": true,
-        "webhook": format!("http://host.docker.internal:{}/webhook/whatsapp", config.webhook_port),
+        "webhook": webhook_url(config),
     });
 
     info!(
@@ -70,7 +70,7 @@ pub(crate) async fn create_evolution_instance(config: &WhatsAppConfig) -> Engine
                 "instanceName": config.instance_name,
                 "token": retry_token,
                 "qrcode": true,
-                "webhook": format!("http://host.docker.internal:{}/webhook/whatsapp", config.webhook_port),
+                "webhook": webhook_url(config),
             });
 
             // Retry create after delete
@@ -210,6 +210,42 @@ pub(crate) async fn connect_evolution_instance(config: &WhatsAppConfig) -> Engin
     Ok(extract_qr_from_response(&resp_json))
 }
 
+/// Point the instance's webhook at the current `webhook_url` (after a
+/// secret rotation, so Evolution stops calling the old URL).
+pub(crate) async fn set_evolution_webhook(config: &WhatsAppConfig) -> EngineResult<()> {
+    let client = reqwest::Client::new();
+    let url = format!("{}/webhook/set/{}", config.api_url, config.instance_name);
+    let body = json!({
+        "url": webhook_url(config),
+        "enabled": true,
+        "webhook_by_events": true,
+        "events": ["MESSAGES_UPSERT", "QRCODE_UPDATED", "CONNECTION_UPDATE"],
+    });
+
+    let resp = client
+        .post(&url)
+        .header("apikey", &config.api_key)
+        .json(&body)
+        .send()
+        .await?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!(
+            "Failed to update webhook [{}]: {}",
+            status,
+            safe_truncate(&text, 200)
+        )
+        .into());
+    }
+    info!(
+        "[whatsapp] Webhook URL updated for '{}'",
+        config.instance_name
+    );
+    Ok(())
+}
+
 // ── Message Sending ────────────────────────────────────────────────────
 
 /// Send a text message via Evolution API.
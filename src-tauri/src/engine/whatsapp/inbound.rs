@@ -0,0 +1,280 @@
+// WhatsApp Bridge — Inbound Payload Parsing
+// InboundMessage, InboundMedia, parse_inbound
+//
+// Evolution's `messages.upsert` payload carries a Baileys message whose
+// shape depends on the message type (and shifts between Evolution versions).
+// Everything known is normalized into InboundMessage here; payloads matching
+// none of the shapes are logged and skipped.
+
+use log::{debug, warn};
+use serde::Deserialize;
+
+/// A normalized inbound message, ready for access control and routing.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InboundMessage {
+    /// Chat to reply to — the contact, or the group for group messages
+    pub jid: String,
+    /// Sender phone number / ID without the `@s.whatsapp.net` suffix
+    pub sender_id: String,
+    /// WhatsApp display name, falling back to `sender_id`
+    pub sender_name: String,
+    /// Message text, or the caption for media (may be empty)
+    pub text: String,
+    pub media: Option<InboundMedia>,
+    pub is_group: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InboundMedia {
+    /// "image"
+    pub kind: &'static str,
+    pub mimetype: Option<String>,
+}
+
+// ── Evolution / Baileys wire shapes ────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMessage {
+    key: RawKey,
+    push_name: Option<String>,
+    message: Option<RawContent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawKey {
+    remote_jid: String,
+    #[serde(default)]
+    from_me: bool,
+    participant: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawContent {
+    conversation: Option<String>,
+    extended_text_message: Option<RawExtendedText>,
+    image_message: Option<RawImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawExtendedText {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawImage {
+    caption: Option<String>,
+    mimetype: Option<String>,
+}
+
+/// Normalize the `data` of a `messages.upsert` payload — one message or an
+/// array of them. Our own outgoing messages are dropped.
+pub(crate) fn parse_inbound(payload: &serde_json::Value) -> Vec<InboundMessage> {
+    let data = &payload["data"];
+    let items = match data.as_array() {
+        Some(arr) => arr.iter().collect(),
+        None => vec![data],
+    };
+    items.into_iter().filter_map(parse_one).collect()
+}
+
+fn parse_one(value: &serde_json::Value) -> Option<InboundMessage> {
+    let raw: RawMessage = match serde_json::from_value(value.clone()) {
+        Ok(raw) => raw,
+        Err(e) => {
+            warn!("[whatsapp] Skipping unrecognized message payload: {}", e);
+            return None;
+        }
+    };
+    if raw.key.from_me {
+        return None;
+    }
+
+    let content = raw.message.as_ref();
+    let (text, media) = if let Some(text) = content.and_then(|c| c.conversation.as_ref()) {
+        (text.clone(), None)
+    } else if let Some(ext) = content.and_then(|c| c.extended_text_message.as_ref()) {
+        (ext.text.clone(), None)
+    } else if let Some(image) = content.and_then(|c| c.image_message.as_ref()) {
+        let media = InboundMedia {
+            kind: "image",
+            mimetype: image.mimetype.clone(),
+        };
+        (image.caption.clone().unwrap_or_default(), Some(media))
+    } else {
+        // Reactions, stickers, protocol messages, ...
+        debug!(
+            "[whatsapp] Skipping unsupported message type from {}: {}",
+            raw.key.remote_jid,
+            value["messageType"].as_str().unwrap_or("unknown")
+        );
+        return None;
+    };
+
+    let is_group = raw.key.remote_jid.ends_with("@g.us");
+    let participant = raw
+        .key
+        .participant
+        .as_deref()
+        .filter(|p| !p.is_empty())
+        .unwrap_or(&raw.key.remote_jid);
+    let sender_id = participant
+        .split('@')
+        .next()
+        .unwrap_or(participant)
+        .to_string();
+    let sender_name = raw
+        .push_name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| sender_id.clone());
+
+    Some(InboundMessage {
+        jid: raw.key.remote_jid,
+        sender_id,
+        sender_name,
+        text,
+        media,
+        is_group,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shaped like Evolution API v1.8 `messages.upsert` webhooks
+    const DIRECT_TEXT: &str = r#"{
+        "event": "messages.upsert",
+        "instance": "paw",
+        "data": {
+            "key": {
+                "remoteJid": "5511987654321@s.whatsapp.net",
+                "fromMe": false,
+                "id": "3EB0A1C2D3E4F5A6B7C8"
+            },
+            "pushName": "Maria",
+            "message": {
+                "conversation": "Oi! Can you check my calendar?",
+                "messageContextInfo": { "deviceListMetadataVersion": 2 }
+            },
+            "messageType": "conversation",
+            "messageTimestamp": 1717171717,
+            "owner": "paw",
+            "source": "android"
+        },
+        "destination": "http://host.docker.internal:8086/webhook/wa-1a2b3c",
+        "date_time": "2024-05-31T12:08:37.000Z",
+        "sender": "5511912345678@s.whatsapp.net",
+        "server_url": "http://localhost:8080",
+        "apikey": "paw-wa-0123456789abcdef"
+    }"#;
+
+    const GROUP_BATCH: &str = r#"{
+        "event": "messages.upsert",
+        "instance": "paw",
+        "data": [
+            {
+                "key": {
+                    "remoteJid": "120363025246125486@g.us",
+                    "fromMe": false,
+                    "id": "BAE5F2D1C0B9A8F7",
+                    "participant": "447700900123@s.whatsapp.net"
+                },
+                "pushName": "Tom",
+                "message": {
+                    "extendedTextMessage": {
+                        "text": "see https://example.com",
+                        "matchedText": "https://example.com",
+                        "previewType": 0
+                    }
+                },
+                "messageType": "extendedTextMessage",
+                "messageTimestamp": 1717171800
+            },
+            {
+                "key": {
+                    "remoteJid": "447700900456@s.whatsapp.net",
+                    "fromMe": false,
+                    "id": "3EB0F00DBEEF"
+                },
+                "message": {
+                    "imageMessage": {
+                        "url": "https://mmg.whatsapp.net/v/t62.7118-24/abc.enc",
+                        "mimetype": "image/jpeg",
+                        "caption": "What plant is this?",
+                        "fileLength": "48213",
+                        "height": 1280,
+                        "width": 960
+                    }
+                },
+                "messageType": "imageMessage",
+                "messageTimestamp": 1717171900
+            },
+            {
+                "key": {
+                    "remoteJid": "447700900456@s.whatsapp.net",
+                    "fromMe": true,
+                    "id": "3EB0OUTGOING"
+                },
+                "message": { "conversation": "our own reply" },
+                "messageType": "conversation"
+            },
+            {
+                "key": {
+                    "remoteJid": "447700900456@s.whatsapp.net",
+                    "fromMe": false,
+                    "id": "3EB0REACTION"
+                },
+                "message": {
+                    "reactionMessage": {
+                        "key": { "id": "3EB0F00DBEEF" },
+                        "text": "👍"
+                    }
+                },
+                "messageType": "reactionMessage"
+            },
+            { "status": "DELIVERY_ACK" }
+        ]
+    }"#;
+
+    #[test]
+    fn parses_direct_text_message() {
+        let payload = serde_json::from_str(DIRECT_TEXT).unwrap();
+        assert_eq!(
+            parse_inbound(&payload),
+            vec![InboundMessage {
+                jid: "5511987654321@s.whatsapp.net".into(),
+                sender_id: "5511987654321".into(),
+                sender_name: "Maria".into(),
+                text: "Oi! Can you check my calendar?".into(),
+                media: None,
+                is_group: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_group_and_image_messages_and_skips_the_rest() {
+        let payload = serde_json::from_str(GROUP_BATCH).unwrap();
+        let messages = parse_inbound(&payload);
+        assert_eq!(messages.len(), 2);
+
+        let group = &messages[0];
+        assert!(group.is_group);
+        assert_eq!(group.jid, "120363025246125486@g.us");
+        assert_eq!(group.sender_id, "447700900123");
+        assert_eq!(group.sender_name, "Tom");
+        assert_eq!(group.text, "see https://example.com");
+
+        let image = &messages[1];
+        assert!(!image.is_group);
+        // No pushName — falls back to the number
+        assert_eq!(image.sender_name, "447700900456");
+        assert_eq!(image.text, "What plant is this?");
+        let media = image.media.as_ref().unwrap();
+        assert_eq!(media.kind, "image");
+        assert_eq!(media.mimetype.as_deref(), Some("image/jpeg"));
+    }
+}
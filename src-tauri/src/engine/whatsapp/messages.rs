@@ -4,54 +4,35 @@
 use super::bridge::MESSAGE_COUNT;
 use super::config::{WhatsAppConfig, CONFIG_KEY};
 use super::evolution_api::send_whatsapp_message;
+use super::inbound::{parse_inbound, InboundMessage};
 use crate::engine::channels;
 use log::{debug, error};
 use serde_json::json;
 use std::sync::atomic::Ordering;
 use tauri::Emitter;
 
-/// Process an inbound WhatsApp message from the Evolution API webhook.
+/// Process the messages in a `messages.upsert` webhook from Evolution API.
 pub(crate) async fn handle_inbound_message(
     app_handle: tauri::AppHandle,
     payload: serde_json::Value,
 ) {
-    let data = &payload["data"];
-
-    // Extract message details
-    let messages = match data.as_array() {
-        Some(arr) => arr.clone(),
-        None => vec![data.clone()],
-    };
-
-    for msg in messages {
-        // Skip status messages, reactions, etc.
-        let key = &msg["key"];
-        let from_me = key["fromMe"].as_bool().unwrap_or(false);
-        if from_me {
-            continue;
-        }
-
-        // Extract text content
-        let text = msg["message"]["conversation"]
-            .as_str()
-            .or_else(|| msg["message"]["extendedTextMessage"]["text"].as_str())
-            .unwrap_or("");
-        if text.is_empty() {
+    for msg in parse_inbound(&payload) {
+        if msg.text.trim().is_empty() {
+            debug!(
+                "[whatsapp] Skipping message without text from {}",
+                msg.sender_id
+            );
             continue;
         }
-
-        // Extract sender info
-        let remote_jid = key["remoteJid"].as_str().unwrap_or("");
-        let participant = key["participant"].as_str().unwrap_or(remote_jid);
-        let is_group = remote_jid.contains("@g.us");
-
-        // Normalize sender ID (strip @s.whatsapp.net)
-        let sender_id = participant
-            .split('@')
-            .next()
-            .unwrap_or(participant)
-            .to_string();
-        let push_name = msg["pushName"].as_str().unwrap_or(&sender_id).to_string();
+        let InboundMessage {
+            jid: remote_jid,
+            sender_id,
+            sender_name: push_name,
+            text,
+            media,
+            is_group,
+        } = msg;
+        let remote_jid = remote_jid.as_str();
 
         debug!(
             "[whatsapp] Message from {} ({}): {}",
@@ -60,7 +41,7 @@ pub(crate) async fn handle_inbound_message(
             if text.len() > 50 {
                 format!("{}...", &text[..text.floor_char_boundary(50)])
             } else {
-                text.to_string()
+                text.clone()
             }
         );
 
@@ -111,11 +92,22 @@ pub(crate) async fn handle_inbound_message(
                    Use WhatsApp formatting: *bold*, _italic_, ~strikethrough~, ```code```. \
                    Avoid very long responses — WhatsApp truncates long messages.";
 
+        // The media itself isn't fetched — tell the agent it was there
+        let text = match &media {
+            Some(media) => format!(
+                "[{} attached: {}]\n{}",
+                media.kind,
+                media.mimetype.as_deref().unwrap_or("unknown type"),
+                text
+            ),
+            None => text,
+        };
+
         let response = channels::run_channel_agent(
             &app_handle,
             "whatsapp",
             ctx,
-            text,
+            &text,
             &sender_id,
             agent_id,
             config.allow_dangerous_tools,
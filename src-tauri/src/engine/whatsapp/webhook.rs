@@ -1,13 +1,44 @@
 // WhatsApp Bridge — Webhook HTTP Listener
-// run_webhook_listener
+// run_webhook_listener, set_webhook_auth, check_webhook_request
+//
+// Callbacks are only accepted on the configured webhook path, and must prove
+// they know the webhook secret: as the path segment Evolution is configured
+// with (webhook_url), an `Authorization: Bearer` token, or an
+// `X-Paw-Signature: sha256=<hex>` HMAC of the body. Anything else gets a
+// 404/401 and never reaches handle_inbound_message.
 
+use super::config::WhatsAppConfig;
 use super::messages::handle_inbound_message;
 use crate::atoms::error::EngineResult;
+use crate::engine::channels::{read_request, reject_request, HttpLimits, RequestError};
+use hmac::{Hmac, Mac};
 use log::{info, warn};
+use parking_lot::RwLock;
 use serde_json::json;
+use sha2::Sha256;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
+use subtle::ConstantTimeEq;
 use tauri::Emitter;
+use tokio::io::AsyncWriteExt;
+
+/// The path + secret inbound callbacks are checked against.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WebhookAuth {
+    pub path: String,
+    pub secret: String,
+}
+
+/// Live auth for the running listener — swapped in place on rotation.
+static WEBHOOK_AUTH: LazyLock<RwLock<WebhookAuth>> =
+    LazyLock::new(|| RwLock::new(WebhookAuth::default()));
+
+pub(crate) fn set_webhook_auth(config: &WhatsAppConfig) {
+    *WEBHOOK_AUTH.write() = WebhookAuth {
+        path: config.webhook_path(),
+        secret: config.webhook_secret.clone(),
+    };
+}
 
 /// Minimal HTTP listener that receives webhooks from Evolution API.
 /// Runs on `webhook_port` (default 8086), bound to 127.0.0.1.
@@ -16,7 +47,6 @@ pub(crate) async fn run_webhook_listener(
     port: u16,
     stop: Arc<AtomicBool>,
 ) -> EngineResult<()> {
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::TcpListener;
 
     let addr = format!("127.0.0.1:{}", port);
@@ -34,7 +64,7 @@ pub(crate) async fn run_webhook_listener(
         let accept_result =
             tokio::time::timeout(std::time::Duration::from_secs(2), listener.accept()).await;
 
-        let (mut stream, _peer) = match accept_result {
+        let (stream, _peer) = match accept_result {
             Ok(Ok(conn)) => conn,
             Ok(Err(e)) => {
                 warn!("[whatsapp] Accept error: {}", e);
@@ -43,77 +73,326 @@ pub(crate) async fn run_webhook_listener(
             Err(_) => continue, // Timeout — check stop signal
         };
 
-        // Read the full HTTP request
-        let mut buf = vec![0u8; 65536];
-        let n = match stream.read(&mut buf).await {
-            Ok(n) => n,
-            Err(_) => continue,
-        };
-        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        // One task per connection so a stalled client can't block the listener
+        let app = app_handle.clone();
+        tokio::spawn(async move {
+            if let Some(payload) = read_webhook(stream).await {
+                handle_webhook_event(&app, payload);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Evolution webhooks can carry media previews, so allow bodies up to 1MB.
+const WEBHOOK_LIMITS: HttpLimits = HttpLimits {
+    max_header_bytes: 16 * 1024,
+    max_body_bytes: 1024 * 1024,
+    timeout: std::time::Duration::from_secs(10),
+};
+
+/// Read one webhook request, authenticate it, and answer with its status.
+/// Returns the JSON payload only for accepted requests.
+async fn read_webhook(mut stream: tokio::net::TcpStream) -> Option<serde_json::Value> {
+    let buf = match read_request(&mut stream, &WEBHOOK_LIMITS).await {
+        Ok(buf) => buf,
+        Err(RequestError::Closed) => return None,
+        Err(e) => {
+            warn!("[whatsapp] Webhook request rejected: {}", e);
+            reject_request(&mut stream, &e).await;
+            return None;
+        }
+    };
+
+    let auth = WEBHOOK_AUTH.read().clone();
+    let checked = check_webhook_request(&buf, &auth);
+    // Answer immediately (Evolution expects a quick response)
+    let response = match &checked {
+        Ok(_) => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nOK".to_string(),
+        Err(rejection) => {
+            warn!("[whatsapp] Webhook request rejected: {}", rejection.reason);
+            format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                rejection.status
+            )
+        }
+    };
+    let _ = stream.write_all(response.as_bytes()).await;
+    drop(stream);
+
+    checked.ok()
+}
 
-        // Send 200 OK immediately (Evolution expects quick response)
-        let response = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK";
-        let _ = stream.write_all(response.as_bytes()).await;
-        drop(stream);
+type HmacSha256 = Hmac<Sha256>;
 
-        // Parse the JSON body (after the blank line)
-        let body = if let Some(idx) = request.find("\r\n\r\n") {
-            &request[idx + 4..]
-        } else {
+#[derive(Debug)]
+pub(crate) struct WebhookRejection {
+    pub status: &'static str,
+    pub reason: &'static str,
+}
+
+fn reject(status: &'static str, reason: &'static str) -> WebhookRejection {
+    WebhookRejection { status, reason }
+}
+
+/// Authenticate a raw webhook request and return its JSON payload.
+///
+/// Checks, in order: a complete body matching `Content-Length`, the webhook
+/// path, the secret (signature, bearer token, or path segment), and a JSON
+/// object with an `event`.
+pub(crate) fn check_webhook_request(
+    raw: &[u8],
+    auth: &WebhookAuth,
+) -> Result<serde_json::Value, WebhookRejection> {
+    let header_end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| reject("400 Bad Request", "incomplete headers"))?;
+    let head = String::from_utf8_lossy(&raw[..header_end]);
+    let body = &raw[header_end + 4..];
+
+    let mut lines = head.lines();
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or_else(|| reject("400 Bad Request", "malformed request line"))?;
+    let mut content_length = None;
+    let mut signature = None;
+    let mut bearer = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
             continue;
         };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse::<usize>().ok();
+        } else if name.eq_ignore_ascii_case("x-paw-signature") {
+            signature = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("authorization") {
+            bearer = value.trim().strip_prefix("Bearer ").map(str::to_string);
+        }
+    }
+    if content_length != Some(body.len()) {
+        return Err(reject(
+            "400 Bad Request",
+            "missing or mismatched Content-Length",
+        ));
+    }
 
-        let payload: serde_json::Value = match serde_json::from_str(body) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
+    let path = path.split('?').next().unwrap_or("");
+    let Some(rest) = path
+        .strip_prefix(auth.path.as_str())
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    else {
+        return Err(reject("404 Not Found", "unknown webhook path"));
+    };
 
-        // Handle different webhook events
-        let event = payload["event"].as_str().unwrap_or("");
-
-        match event {
-            "qrcode.updated" => {
-                let qr = payload["data"]["qrcode"]["base64"]
-                    .as_str()
-                    .or_else(|| payload["data"]["qrcode"].as_str())
-                    .unwrap_or("");
-                if !qr.is_empty() {
-                    let _ = app_handle.emit(
-                        "whatsapp-status",
-                        json!({
-                            "kind": "qr_code",
-                            "qr": qr,
-                            "message": "Scan this QR code with WhatsApp",
-                        }),
-                    );
-                }
-            }
-            "connection.update" => {
-                let state = payload["data"]["state"].as_str().unwrap_or("");
-                if state == "open" || state == "connected" {
-                    let _ = app_handle.emit(
-                        "whatsapp-status",
-                        json!({
-                            "kind": "connected",
-                            "message": "WhatsApp connected",
-                        }),
-                    );
-                    info!("[whatsapp] Connection confirmed via webhook");
-                }
-            }
-            "messages.upsert" => {
-                // Process inbound message
-                let app = app_handle.clone();
-                let msg_payload = payload.clone();
-                tauri::async_runtime::spawn(async move {
-                    handle_inbound_message(app, msg_payload).await;
-                });
+    let secret = auth.secret.as_str();
+    if secret.is_empty() {
+        return Err(reject("401 Unauthorized", "no webhook secret configured"));
+    }
+    let authorized = if let Some(signature) = signature {
+        verify_signature(&signature, body, secret)
+    } else if let Some(token) = bearer {
+        bool::from(token.as_bytes().ct_eq(secret.as_bytes()))
+    } else {
+        // `<path>/<secret>` or `<path>/<secret>/<event>`
+        let token = rest.trim_start_matches('/').split('/').next().unwrap_or("");
+        bool::from(token.as_bytes().ct_eq(secret.as_bytes()))
+    };
+    if !authorized {
+        return Err(reject(
+            "401 Unauthorized",
+            "bad or missing webhook signature",
+        ));
+    }
+
+    let payload: serde_json::Value =
+        serde_json::from_slice(body).map_err(|_| reject("400 Bad Request", "body is not JSON"))?;
+    if !payload["event"].is_string() {
+        return Err(reject("400 Bad Request", "payload has no event"));
+    }
+    Ok(payload)
+}
+
+/// `sha256=<hex HMAC-SHA256(body, secret)>`
+fn verify_signature(signature: &str, body: &[u8], secret: &str) -> bool {
+    let hex = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Some(expected) = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn handle_webhook_event(app_handle: &tauri::AppHandle, payload: serde_json::Value) {
+    // Handle different webhook events
+    let event = payload["event"].as_str().unwrap_or("");
+
+    match event {
+        "qrcode.updated" => {
+            let qr = payload["data"]["qrcode"]["base64"]
+                .as_str()
+                .or_else(|| payload["data"]["qrcode"].as_str())
+                .unwrap_or("");
+            if !qr.is_empty() {
+                let _ = app_handle.emit(
+                    "whatsapp-status",
+                    json!({
+                        "kind": "qr_code",
+                        "qr": qr,
+                        "message": "Scan this QR code with WhatsApp",
+                    }),
+                );
             }
-            _ => {
-                // Ignore other events
+        }
+        "connection.update" => {
+            let state = payload["data"]["state"].as_str().unwrap_or("");
+            if state == "open" || state == "connected" {
+                let _ = app_handle.emit(
+                    "whatsapp-status",
+                    json!({
+                        "kind": "connected",
+                        "message": "WhatsApp connected",
+                    }),
+                );
+                info!("[whatsapp] Connection confirmed via webhook");
             }
         }
+        "messages.upsert" => {
+            // Process inbound message
+            let app = app_handle.clone();
+            let msg_payload = payload.clone();
+            tauri::async_runtime::spawn(async move {
+                handle_inbound_message(app, msg_payload).await;
+            });
+        }
+        _ => {
+            // Ignore other events
+        }
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "0123456789abcdef";
+    const BODY: &str = r#"{"event":"messages.upsert","data":{}}"#;
+
+    fn auth() -> WebhookAuth {
+        WebhookAuth {
+            path: "/webhook/wa-1a2b3c".into(),
+            secret: SECRET.into(),
+        }
+    }
+
+    fn request(path: &str, extra_header: &str, body: &str) -> Vec<u8> {
+        format!(
+            "POST {} HTTP/1.1\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\n\r\n{}",
+            path,
+            extra_header,
+            body.len(),
+            body
+        )
+        .into_bytes()
+    }
+
+    fn sign(body: &str, secret: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        let hex: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("X-Paw-Signature: sha256={}\r\n", hex)
+    }
+
+    fn status(raw: &[u8]) -> &'static str {
+        check_webhook_request(raw, &auth()).unwrap_err().status
+    }
+
+    #[test]
+    fn authenticated_requests_are_accepted() {
+        let signed = request("/webhook/wa-1a2b3c", &sign(BODY, SECRET), BODY);
+        let payload = check_webhook_request(&signed, &auth()).unwrap();
+        assert_eq!(payload["event"], "messages.upsert");
+
+        let bearer = request(
+            "/webhook/wa-1a2b3c",
+            "Authorization: Bearer 0123456789abcdef\r\n",
+            BODY,
+        );
+        assert!(check_webhook_request(&bearer, &auth()).is_ok());
+
+        let by_event = request(
+            "/webhook/wa-1a2b3c/0123456789abcdef/messages-upsert",
+            "",
+            BODY,
+        );
+        assert!(check_webhook_request(&by_event, &auth()).is_ok());
+    }
+
+    #[test]
+    fn bad_signature_is_rejected() {
+        let forged = request("/webhook/wa-1a2b3c", &sign(BODY, "wrong-secret"), BODY);
+        assert_eq!(status(&forged), "401 Unauthorized");
+
+        // A valid signature over a different body doesn't carry over
+        let tampered = request(
+            "/webhook/wa-1a2b3c",
+            &sign(BODY, SECRET),
+            r#"{"event":"messages.upsert","data":{"x":1}}"#,
+        );
+        assert_eq!(status(&tampered), "401 Unauthorized");
+    }
+
+    #[test]
+    fn wrong_path_or_token_is_rejected() {
+        let old_path = request("/webhook/whatsapp/0123456789abcdef", "", BODY);
+        assert_eq!(status(&old_path), "404 Not Found");
+
+        let lookalike = request("/webhook/wa-1a2b3cd/0123456789abcdef", "", BODY);
+        assert_eq!(status(&lookalike), "404 Not Found");
+
+        let wrong_token = request("/webhook/wa-1a2b3c/fedcba9876543210", "", BODY);
+        assert_eq!(status(&wrong_token), "401 Unauthorized");
+
+        let wrong_bearer = request(
+            "/webhook/wa-1a2b3c",
+            "Authorization: Bearer fedcba9876543210\r\n",
+            BODY,
+        );
+        assert_eq!(status(&wrong_bearer), "401 Unauthorized");
+
+        let no_token = request("/webhook/wa-1a2b3c/messages-upsert", "", BODY);
+        assert_eq!(status(&no_token), "401 Unauthorized");
+    }
+
+    #[test]
+    fn malformed_requests_are_rejected() {
+        let path = "/webhook/wa-1a2b3c/0123456789abcdef";
+        let mut truncated = request(path, "", BODY);
+        truncated.truncate(truncated.len() - 5);
+        assert_eq!(status(&truncated), "400 Bad Request");
+
+        assert_eq!(status(&request(path, "", "hello")), "400 Bad Request");
+        assert_eq!(
+            status(&request(path, "", r#"{"data":{}}"#)),
+            "400 Bad Request"
+        );
+    }
 }
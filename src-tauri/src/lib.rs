@@ -124,8 +124,17 @@ pub fn run() {
 
     tauri::Builder::default()
         .manage(engine_state)
-        .plugin(
-            tauri_plugin_log::Builder::new()
+        .plugin(tauri_plugin_sql::Builder::default().build())
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            // ── Logging ───────────────────────────────────────────────────
+            // The log plugin drives stdout / the app log dir; its logger is
+            // teed into the scrubbed, rotating file under the data root.
+            let (log_plugin, log_level, console_logger) = tauri_plugin_log::Builder::new()
                 .target(tauri_plugin_log::Target::new(
                     tauri_plugin_log::TargetKind::LogDir {
                         file_name: Some("openpawz".into()),
@@ -136,15 +145,14 @@ pub fn run() {
                 .level(log::LevelFilter::Info)
                 // App crate: keep Debug for engine diagnostics
                 .level_for("paw_temp", log::LevelFilter::Debug)
-                .build(),
-        )
-        .plugin(tauri_plugin_sql::Builder::default().build())
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_updater::Builder::new().build())
-        .setup(|app| {
+                .split(app.handle())?;
+            app.handle().plugin(log_plugin)?;
+            let file_log_config = app
+                .try_state::<commands::state::EngineState>()
+                .map(|state| openpawz_core::engine::file_log::load_config(&state.store))
+                .unwrap_or_default();
+            openpawz_core::engine::file_log::install(console_logger, log_level, &file_log_config)?;
+
             // ── Startup DB housekeeping (runs once, non-blocking) ─────────
             {
                 let app_handle = app.handle().clone();
@@ -195,6 +203,63 @@ pub fn run() {
                 }
             });
 
+            // ── Reminders (30s poll; overdue ones fire right after launch) ──
+            let app_handle_reminders = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                loop {
+                    engine::reminders::run_reminder_tick(&app_handle_reminders).await;
+                    tokio::time::sleep(engine::reminders::POLL_INTERVAL).await;
+                }
+            });
+
+            // ── Session archiving (hourly check; runs every interval_hours) ──
+            let app_handle_archive = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                let mut last_run: Option<std::time::Instant> = None;
+                loop {
+                    if let Some(state) = app_handle_archive.try_state::<crate::commands::state::EngineState>() {
+                        let config = openpawz_core::engine::session_archive::load_config(&state.store);
+                        let interval = std::time::Duration::from_secs(config.interval_hours.max(1) as u64 * 3600);
+                        if config.enabled && last_run.is_none_or(|t| t.elapsed() >= interval) {
+                            last_run = Some(std::time::Instant::now());
+                            if let Err(e) = openpawz_core::engine::session_archive::archive_old_sessions(
+                                &state.store,
+                                config.older_than_days,
+                                config.summarize,
+                            ) {
+                                log::warn!("[engine] Session archiving failed: {}", e);
+                            }
+                        }
+                    }
+                    tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+                }
+            });
+
+            // ── Memory merge (opt-in; hourly check; runs every interval_hours) ──
+            let app_handle_merge = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+                let mut last_run: Option<std::time::Instant> = None;
+                loop {
+                    if let Some(state) = app_handle_merge.try_state::<crate::commands::state::EngineState>() {
+                        let config = engine::engram::memory_merge::load_config(&state.store);
+                        let interval = std::time::Duration::from_secs(config.interval_hours.max(1) as u64 * 3600);
+                        if config.enabled
+                            && !openpawz_core::engine::emergency::is_halted()
+                            && last_run.is_none_or(|t| t.elapsed() >= interval)
+                        {
+                            last_run = Some(std::time::Instant::now());
+                            if let Err(e) = crate::commands::memory::run_memory_merge(&state, &config).await {
+                                log::warn!("[engram] Memory merge failed: {}", e);
+                            }
+                        }
+                    }
+                    tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+                }
+            });
+
             // ── Engram memory maintenance (consolidation + decay + GC) ─────
             // Runs every 5 minutes in the background. Consolidates episodic
             // memories into semantic triples, applies Ebbinghaus decay, and
@@ -296,10 +361,20 @@ pub fn run() {
             commands::chat::engine_chat_send,
             commands::chat::engine_chat_history,
             commands::chat::engine_chat_abort,
+            commands::chat::engine_cancel_run,
+            commands::emergency::engine_emergency_stop,
+            commands::emergency::engine_resume,
+            commands::emergency::engine_emergency_status,
             commands::chat::engine_sessions_list,
             commands::chat::engine_session_rename,
             commands::chat::engine_session_delete,
+            commands::chat::engine_session_secure_delete,
+            commands::chat::engine_session_fork,
+            commands::chat::engine_session_forks,
             commands::chat::engine_session_clear,
+            commands::chat::engine_session_set_archived,
+            commands::chat::engine_sessions_archive_old,
+            commands::chat::engine_sessions_search_semantic,
             commands::chat::engine_session_cleanup,
             commands::chat::engine_session_compact,
             commands::chat::engine_approve_tool,
@@ -307,17 +382,32 @@ pub fn run() {
             commands::config::engine_sandbox_check,
             commands::config::engine_sandbox_get_config,
             commands::config::engine_sandbox_set_config,
+            commands::config::engine_approval_rules_get,
+            commands::config::engine_approval_rules_set,
+            commands::config::engine_tool_cache_get_config,
+            commands::config::engine_tool_cache_set_config,
+            commands::config::engine_tool_cache_stats,
+            commands::config::engine_tool_cache_flush,
+            commands::config::engine_tool_result_policy_get,
+            commands::config::engine_tool_result_policy_set,
+            commands::config::engine_session_archive_get_config,
+            commands::config::engine_session_archive_set_config,
+            commands::config::engine_file_log_get_config,
+            commands::config::engine_file_log_set_config,
             commands::config::engine_get_config,
+            commands::config::engine_available_locales,
             commands::config::engine_get_daily_spend,
             commands::config::engine_set_config,
             commands::config::engine_upsert_provider,
             commands::config::engine_remove_provider,
             commands::config::engine_list_provider_models,
+            commands::config::engine_test_provider,
             commands::config::engine_status,
             commands::config::engine_auto_setup,
             // ── Storage Paths ──
             commands::config::engine_storage_get_paths,
             commands::config::engine_storage_set_data_root,
+            commands::config::engine_storage_migrate_data,
             // ── Agent Files (Soul / Persona) ──
             commands::agent::engine_agent_file_list,
             commands::agent::engine_agent_file_get,
@@ -326,6 +416,14 @@ pub fn run() {
             commands::agent::engine_list_all_agents,
             commands::agent::engine_create_agent,
             commands::agent::engine_delete_agent,
+            // ── Agent Config (persona / model / skills) ──
+            commands::agent::engine_agent_config_list,
+            commands::agent::engine_agent_config_get,
+            commands::agent::engine_agent_config_save,
+            commands::agent::engine_agent_config_delete,
+            commands::agent::engine_clone_agent,
+            commands::agent::engine_export_agent,
+            commands::agent::engine_import_agent,
             // ── Memory (Long-term Semantic) ──
             commands::memory::engine_memory_store,
             commands::memory::engine_memory_search,
@@ -338,11 +436,19 @@ pub fn run() {
             commands::memory::engine_memory_edges,
             commands::memory::engine_get_memory_config,
             commands::memory::engine_set_memory_config,
+            commands::memory::engine_memory_get_capture_policy,
+            commands::memory::engine_memory_set_capture_policy,
+            commands::memory::engine_memory_merge_get_config,
+            commands::memory::engine_memory_merge_set_config,
+            commands::memory::engine_memory_merge_run,
             commands::memory::engine_test_embedding,
             commands::memory::engine_embedding_status,
             commands::memory::engine_embedding_pull_model,
             commands::memory::engine_ensure_embedding_ready,
             commands::memory::engine_memory_backfill,
+            commands::memory::engine_memory_prune_stale,
+            commands::memory::engine_memory_export,
+            commands::memory::engine_memory_import,
             commands::memory::engine_working_memory_save,
             commands::memory::engine_working_memory_restore,
             commands::memory::engine_memory_purge_user,
@@ -350,6 +456,7 @@ pub fn run() {
             commands::memory::engine_message_feedback,
             // ── Skill Vault ──
             commands::skills::engine_skills_list,
+            commands::skills::engine_skills_status,
             commands::skills::engine_skill_set_enabled,
             commands::skills::engine_skill_bulk_enable,
             commands::skills::engine_skill_set_credential,
@@ -358,9 +465,13 @@ pub fn run() {
             commands::skills::engine_skill_revoke_all,
             commands::skills::engine_skill_get_instructions,
             commands::skills::engine_skill_set_instructions,
+            commands::skills::engine_skill_get_permitted_tools,
+            commands::skills::engine_skill_set_permitted_tools,
             // ── Onboarding (Phase 4) ──
             commands::skills::engine_is_onboarding_complete,
             commands::skills::engine_set_onboarding_complete,
+            commands::onboarding::engine_onboarding_status,
+            commands::onboarding::engine_onboarding_step,
             // ── Community Skills (skills.sh) ──
             commands::skills::engine_community_skills_list,
             commands::skills::engine_community_skills_browse,
@@ -373,6 +484,7 @@ pub fn run() {
             commands::skills::engine_toml_skills_scan,
             commands::skills::engine_toml_skill_install,
             commands::skills::engine_toml_skill_uninstall,
+            commands::skills::engine_install_community_skill,
             // ── PawzHub Registry (Phase F.4) ──
             commands::skills::engine_pawzhub_search,
             commands::skills::engine_pawzhub_browse,
@@ -421,6 +533,9 @@ pub fn run() {
             commands::telemetry::engine_get_model_breakdown,
             commands::telemetry::engine_list_session_metrics,
             commands::telemetry::engine_purge_old_metrics,
+            commands::telemetry::engine_get_run_trace,
+            commands::telemetry::engine_list_run_traces,
+            commands::telemetry::engine_provider_rotation_events,
             // ── Skill Wizard (Phase F.5) ──
             commands::skill_wizard::engine_wizard_generate_toml,
             commands::skill_wizard::engine_wizard_publish_url,
@@ -431,6 +546,12 @@ pub fn run() {
             commands::trade::engine_trading_summary,
             commands::trade::engine_trading_policy_get,
             commands::trade::engine_trading_policy_set,
+            commands::trade::engine_dex_allowance_get,
+            commands::trade::engine_dex_allowance_set,
+            commands::trade::engine_dex_export_private_key,
+            commands::trade::engine_dex_import_private_key,
+            commands::trade::engine_dex_wallets_list,
+            commands::trade::engine_dex_set_active_wallet,
             // ── Positions (Stop-Loss / Take-Profit) ──
             commands::trade::engine_positions_list,
             commands::trade::engine_position_close,
@@ -562,6 +683,7 @@ pub fn run() {
             commands::channels::engine_whatsapp_approve_user,
             commands::channels::engine_whatsapp_deny_user,
             commands::channels::engine_whatsapp_remove_user,
+            commands::channels::engine_whatsapp_rotate_webhook_secret,
             // ── Orchestrator: Projects ──
             commands::project::engine_projects_list,
             commands::project::engine_project_create,
@@ -578,6 +700,8 @@ pub fn run() {
             // ── Screenshots ──
             commands::browser::engine_screenshots_list,
             commands::browser::engine_screenshot_get,
+            commands::browser::engine_generated_image_get,
+            commands::browser::engine_generated_audio_get,
             commands::browser::engine_screenshot_delete,
             // ── Per-Agent Workspaces ──
             commands::browser::engine_workspaces_list,
@@ -587,6 +711,10 @@ pub fn run() {
             commands::browser::engine_network_get_policy,
             commands::browser::engine_network_set_policy,
             commands::browser::engine_network_check_url,
+            commands::browser::engine_outbound_http_get,
+            commands::browser::engine_outbound_http_set,
+            commands::browser::engine_web_politeness_get,
+            commands::browser::engine_web_politeness_set,
             // ── Tailscale (Remote Access) ──
             commands::tailscale::engine_tailscale_status,
             commands::tailscale::engine_tailscale_get_config,
@@ -661,6 +789,7 @@ pub fn run() {
             commands::guardrails::engine_guardrails_log_action,
             commands::guardrails::engine_guardrails_get_audit_log,
             commands::guardrails::engine_guardrails_clear_audit,
+            commands::guardrails::engine_guardrails_export_csv,
             commands::guardrails::engine_guardrails_check_token_expiry,
             commands::guardrails::engine_guardrails_update_token_info,
             // ── Integration Action Log (Phase 4) ──
@@ -672,6 +801,7 @@ pub fn run() {
             commands::audit::engine_audit_query,
             commands::audit::engine_audit_stats,
             commands::audit::engine_audit_verify_chain,
+            commands::audit::engine_list_approvals,
             // ── Compliance Export ──
             commands::export::engine_compliance_export,
             commands::export::engine_compliance_export_to_file,
@@ -219,3 +219,130 @@ fn update_memory_embedding() {
     let without = store.list_memories_without_embeddings(10).unwrap();
     assert!(without.is_empty());
 }
+
+#[test]
+fn recording_retrievals_increments_count() {
+    let store = test_store();
+    store
+        .store_memory("m1", "Recalled fact", "general", 5, None, None)
+        .unwrap();
+    store
+        .store_memory("m2", "Ignored fact", "general", 5, None, None)
+        .unwrap();
+
+    let ids = vec!["m1".to_string()];
+    assert_eq!(store.record_memory_retrievals(&ids).unwrap(), 1);
+    store.record_memory_retrievals(&ids).unwrap();
+
+    let counts = store
+        .get_memory_retrieval_counts(&["m1".to_string(), "m2".to_string()])
+        .unwrap();
+    assert_eq!(counts.get("m1"), Some(&2));
+    assert_eq!(counts.get("m2"), Some(&0));
+}
+
+#[test]
+fn prune_removes_stale_unretrieved_memories() {
+    let store = test_store();
+    store
+        .store_memory("old-unused", "Old unused fact", "general", 5, None, None)
+        .unwrap();
+    store
+        .store_memory("old-used", "Old recalled fact", "general", 5, None, None)
+        .unwrap();
+    store
+        .store_memory("fresh", "Fresh fact", "general", 5, None, None)
+        .unwrap();
+
+    // Backdate two memories well past the pruning window
+    {
+        let conn = store.conn.lock();
+        conn.execute(
+            "UPDATE memories SET created_at = datetime('now', '-200 days')
+             WHERE id IN ('old-unused', 'old-used')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE memories SET retrieval_count = 3 WHERE id = 'old-used'",
+            [],
+        )
+        .unwrap();
+    }
+
+    let removed = store.prune_stale_memories(90 * 86_400, 1).unwrap();
+    assert_eq!(removed, 1);
+
+    let ids: Vec<String> = store
+        .list_memories(10)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    assert!(!ids.contains(&"old-unused".to_string()));
+    assert!(ids.contains(&"old-used".to_string()));
+    assert!(ids.contains(&"fresh".to_string()));
+}
+
+#[test]
+fn fact_buried_in_long_memory_is_found_through_its_chunk() {
+    use openpawz_core::engine::memory::chunk_content;
+    use openpawz_core::engine::sessions::f32_vec_to_bytes;
+
+    let store = test_store();
+    let filler = "The quarterly report covers revenue, hiring and office moves. ".repeat(60);
+    let content = format!(
+        "{}The staging database password rotates every Tuesday. {}",
+        filler, filler
+    );
+    let chunks = chunk_content(&content, 1200, 200);
+    assert!(chunks.len() > 3);
+
+    // Stand-in embeddings: the whole doc (and most chunks) read as a
+    // business report; only the chunk holding the fact is about credentials.
+    let report = [1.0f32, 0.1, 0.0];
+    let credentials = [0.0f32, 0.1, 1.0];
+    store
+        .store_memory(
+            "doc",
+            &content,
+            "reference",
+            5,
+            Some(&f32_vec_to_bytes(&report)),
+            None,
+        )
+        .unwrap();
+    let rows: Vec<(String, Option<Vec<u8>>)> = chunks
+        .iter()
+        .map(|c| {
+            let vec = if c.contains("password rotates") {
+                credentials
+            } else {
+                report
+            };
+            (c.clone(), Some(f32_vec_to_bytes(&vec)))
+        })
+        .collect();
+    store.store_memory_chunks("doc", &rows).unwrap();
+    store
+        .store_memory(
+            "other",
+            "Lunch is at noon",
+            "general",
+            5,
+            Some(&f32_vec_to_bytes(&[0.5, 1.0, 0.0])),
+            None,
+        )
+        .unwrap();
+
+    let hits = store
+        .search_memories_by_embedding(&[0.0, 0.0, 1.0], 5, 0.5, None)
+        .unwrap();
+    // The parent is returned once, however many of its chunks match
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, "doc");
+    assert!(hits[0].score.unwrap() > 0.9);
+
+    store.delete_memory("doc").unwrap();
+    assert_eq!(store.count_memory_chunks("doc").unwrap(), 0);
+}
@@ -3,6 +3,9 @@
 
 use super::test_store;
 
+const TOOL_CALL_JSON: &str =
+    r#"[{"id":"call_1","type":"function","function":{"name":"fetch","arguments":"{}"}}]"#;
+
 #[test]
 fn create_session_returns_valid_session() {
     let store = test_store();
@@ -68,6 +71,8 @@ fn add_message_increments_count() {
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     store.add_message(&msg).unwrap();
 
@@ -94,6 +99,8 @@ fn get_messages_returns_added_messages() {
             tool_call_id: None,
             name: None,
             created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
         };
         store.add_message(&msg).unwrap();
     }
@@ -118,6 +125,8 @@ fn delete_session_cascades_messages() {
         tool_call_id: None,
         name: None,
         created_at: chrono::Utc::now().to_rfc3339(),
+        incomplete: false,
+        reasoning: None,
     };
     store.add_message(&msg).unwrap();
     store.delete_session("s1").unwrap();
@@ -125,3 +134,721 @@ fn delete_session_cascades_messages() {
     let messages = store.get_messages("s1", 100).unwrap();
     assert!(messages.is_empty());
 }
+
+#[test]
+fn edit_and_resend_truncates_downstream_history() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+
+    let roles = [
+        "user",
+        "assistant",
+        "user",
+        "assistant",
+        "tool",
+        "assistant",
+    ];
+    for (i, role) in roles.iter().enumerate() {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: format!("m{}", i + 1),
+            session_id: "s1".into(),
+            role: role.to_string(),
+            content: format!("Message {}", i + 1),
+            tool_calls_json: (i == 3).then(|| TOOL_CALL_JSON.to_string()),
+            tool_call_id: (i == 4).then(|| "call_1".to_string()),
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    // Only user messages can be edited
+    assert!(store.edit_and_resend("s1", "m2", "nope").is_err());
+
+    let edited = store
+        .edit_and_resend("s1", "m3", "Message 3, edited")
+        .unwrap();
+    assert_eq!(edited.content, "Message 3, edited");
+
+    let messages = store.get_messages("s1", 100).unwrap();
+    let ids: Vec<&str> = messages.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, vec!["m1", "m2", "m3"]);
+    assert_eq!(messages[2].content, "Message 3, edited");
+
+    let session = store.get_session("s1").unwrap().unwrap();
+    assert_eq!(session.message_count, 3);
+}
+
+#[test]
+fn truncate_session_after_keeps_the_target() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+    for i in 0..4 {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: format!("m{}", i),
+            session_id: "s1".into(),
+            role: "user".into(),
+            content: format!("Message {}", i),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    assert_eq!(store.truncate_session_after("m1").unwrap(), 2);
+    assert_eq!(store.get_messages("s1", 100).unwrap().len(), 2);
+    assert_eq!(store.get_session("s1").unwrap().unwrap().message_count, 2);
+    assert!(store.truncate_session_after("missing").is_err());
+}
+
+#[test]
+fn fork_session_copies_history_up_to_the_fork_point() {
+    let store = test_store();
+    store
+        .create_session("s1", "gpt-4", Some("You are helpful"), None)
+        .unwrap();
+    for i in 0..5 {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: format!("m{}", i),
+            session_id: "s1".into(),
+            role: if i % 2 == 0 {
+                "user".into()
+            } else {
+                "assistant".into()
+            },
+            content: format!("Message {}", i),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    let fork = store.fork_session("s1", "m2").unwrap();
+    assert_ne!(fork.id, "s1");
+    assert_eq!(fork.parent_session_id.as_deref(), Some("s1"));
+    assert_eq!(fork.forked_from_message_id.as_deref(), Some("m2"));
+    assert_eq!(fork.system_prompt.as_deref(), Some("You are helpful"));
+    assert_eq!(fork.message_count, 3);
+
+    let copied: Vec<String> = store
+        .get_messages(&fork.id, 100)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(copied, vec!["Message 0", "Message 1", "Message 2"]);
+
+    // The parent is untouched and lists the fork as a child
+    assert_eq!(store.get_messages("s1", 100).unwrap().len(), 5);
+    let forks = store.list_session_forks("s1").unwrap();
+    assert_eq!(forks.len(), 1);
+    assert_eq!(forks[0].id, fork.id);
+
+    assert!(store.fork_session("s1", "missing").is_err());
+}
+
+#[test]
+fn regenerate_last_drops_only_the_trailing_reply() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+
+    let turns = [
+        ("m1", "user", None, None),
+        ("m2", "assistant", None, None),
+        ("m3", "user", None, None),
+        ("m4", "assistant", Some(TOOL_CALL_JSON), None),
+        ("m5", "tool", None, Some("call_1")),
+        ("m6", "assistant", None, None),
+    ];
+    for (id, role, tool_calls, tool_call_id) in turns {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: id.into(),
+            session_id: "s1".into(),
+            role: role.into(),
+            content: format!("{} content", id),
+            tool_calls_json: tool_calls.map(str::to_string),
+            tool_call_id: tool_call_id.map(str::to_string),
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    let kept = store.regenerate_last("s1").unwrap();
+    assert_eq!(kept.id, "m3");
+    assert_eq!(kept.content, "m3 content");
+
+    // The assistant tool call and its result go together
+    let messages = store.get_messages("s1", 100).unwrap();
+    let ids: Vec<&str> = messages.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, vec!["m1", "m2", "m3"]);
+    assert_eq!(store.get_session("s1").unwrap().unwrap().message_count, 3);
+
+    store.create_session("empty", "gpt-4", None, None).unwrap();
+    assert!(store.regenerate_last("empty").is_err());
+}
+
+#[test]
+fn old_tool_results_are_elided_from_context_but_kept_in_storage() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+
+    let big_output = "line of output\n".repeat(400);
+    let turns = [
+        ("m1", "user", "Fetch the page".to_string(), None, None),
+        ("m2", "assistant", String::new(), Some(TOOL_CALL_JSON), None),
+        ("m3", "tool", big_output.clone(), None, Some("call_1")),
+        ("m4", "assistant", "Here it is".to_string(), None, None),
+        ("m5", "user", "Thanks".to_string(), None, None),
+        ("m6", "assistant", "Sure".to_string(), None, None),
+        ("m7", "user", "Another question".to_string(), None, None),
+        ("m8", "assistant", "Answer".to_string(), None, None),
+        ("m9", "user", "And one more".to_string(), None, None),
+    ];
+    for (id, role, content, tool_calls, tool_call_id) in turns {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: id.into(),
+            session_id: "s1".into(),
+            role: role.into(),
+            content,
+            tool_calls_json: tool_calls.map(str::to_string),
+            tool_call_id: tool_call_id.map(str::to_string),
+            name: (role == "tool").then(|| "fetch".to_string()),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    // Default policy: results older than 3 user turns are summarized
+    let context = store
+        .load_conversation("s1", None, Some(1_000_000), None)
+        .unwrap();
+    let tool_result = context
+        .iter()
+        .find(|m| m.tool_call_id.as_deref() == Some("call_1"))
+        .expect("tool result stays paired with its call");
+    let text = tool_result.content.as_text();
+    assert!(text.starts_with("[Earlier `fetch` result"));
+    assert!(text.len() < 500);
+
+    // Storage still has the full output
+    let stored = store.get_messages("s1", 100).unwrap();
+    assert_eq!(stored[2].content, big_output);
+
+    // Turning the policy off sends the full result again
+    use openpawz_core::engine::tool_result_policy::{self, ToolResultMode, ToolResultPolicy};
+    let keep = ToolResultPolicy {
+        mode: ToolResultMode::Keep,
+        ..Default::default()
+    };
+    tool_result_policy::save_config(&store, &keep).unwrap();
+    let context = store
+        .load_conversation("s1", None, Some(1_000_000), None)
+        .unwrap();
+    assert!(context.iter().any(|m| m.content.as_text() == big_output));
+}
+
+#[test]
+fn reasoning_is_stored_but_not_sent_back_as_history() {
+    let store = test_store();
+    store.create_session("s1", "o3-mini", None, None).unwrap();
+    for (id, role, content, reasoning) in [
+        ("m1", "user", "What's the next prime after 17?", None),
+        (
+            "m2",
+            "assistant",
+            "19.",
+            Some("18 is even, 19 has no divisors."),
+        ),
+    ] {
+        let msg = paw_temp_lib::atoms::types::StoredMessage {
+            id: id.into(),
+            session_id: "s1".into(),
+            role: role.into(),
+            content: content.into(),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: reasoning.map(str::to_string),
+        };
+        store.add_message(&msg).unwrap();
+    }
+
+    let stored = store.get_messages("s1", 100).unwrap();
+    assert_eq!(stored[1].content, "19.");
+    assert_eq!(
+        stored[1].reasoning.as_deref(),
+        Some("18 is even, 19 has no divisors.")
+    );
+    assert_eq!(stored[0].reasoning, None);
+
+    let context = store
+        .load_conversation("s1", None, Some(1_000_000), None)
+        .unwrap();
+    assert!(context
+        .iter()
+        .all(|m| !m.content.as_text().contains("divisors")));
+}
+
+#[test]
+fn tool_calls_get_stable_ids_and_results_stay_linked() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+    let add = |id: &str, role: &str, tool_calls: Option<&str>, tool_call_id: Option<&str>, name| {
+        store.add_message(&paw_temp_lib::atoms::types::StoredMessage {
+            id: id.into(),
+            session_id: "s1".into(),
+            role: role.into(),
+            content: format!("{} content", id),
+            tool_calls_json: tool_calls.map(str::to_string),
+            tool_call_id: tool_call_id.map(str::to_string),
+            name: name.map(|n: &str| n.to_string()),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        })
+    };
+
+    add("m1", "user", None, None, None).unwrap();
+    // The provider sent two calls without ids
+    let calls = r#"[
+        {"id":"","type":"function","function":{"name":"read_file","arguments":"{}"}},
+        {"id":"","type":"function","function":{"name":"fetch","arguments":"{}"}}
+    ]"#;
+    add("m2", "assistant", Some(calls), None, None).unwrap();
+    // Results arrive out of order and without a usable id
+    add("m3", "tool", None, None, Some("fetch")).unwrap();
+    add("m4", "tool", None, Some("bogus"), Some("read_file")).unwrap();
+    // Every call is answered — a further result is an orphan
+    assert!(add("m5", "tool", None, Some("call_x"), Some("fetch")).is_err());
+
+    let stored = store.get_messages("s1", 100).unwrap();
+    assert_eq!(stored.len(), 4);
+    let calls: Vec<paw_temp_lib::atoms::types::ToolCall> =
+        serde_json::from_str(stored[1].tool_calls_json.as_deref().unwrap()).unwrap();
+    assert_eq!(calls[0].id, "call_m2_0");
+    assert_eq!(calls[1].id, "call_m2_1");
+    assert_eq!(stored[2].tool_call_id.as_deref(), Some("call_m2_1"));
+    assert_eq!(stored[3].tool_call_id.as_deref(), Some("call_m2_0"));
+
+    // Loading puts results back in call order, right after the call
+    let context = store
+        .load_conversation("s1", None, Some(1_000_000), None)
+        .unwrap();
+    let linked: Vec<(String, Option<String>)> = context
+        .iter()
+        .map(|m| (m.content.as_text(), m.tool_call_id.clone()))
+        .collect();
+    assert_eq!(linked[1].0, "m2 content");
+    assert_eq!(linked[2], ("m4 content".into(), Some("call_m2_0".into())));
+    assert_eq!(linked[3], ("m3 content".into(), Some("call_m2_1".into())));
+}
+
+#[test]
+fn orphaned_tool_result_in_storage_is_dropped_on_load() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+    store
+        .add_message(&paw_temp_lib::atoms::types::StoredMessage {
+            id: "m1".into(),
+            session_id: "s1".into(),
+            role: "user".into(),
+            content: "What's the weather like today?".into(),
+            tool_calls_json: None,
+            tool_call_id: None,
+            name: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            incomplete: false,
+            reasoning: None,
+        })
+        .unwrap();
+    // Written by an older build, bypassing validation
+    store
+        .conn
+        .lock()
+        .execute(
+            "INSERT INTO messages (id, session_id, role, content, tool_call_id)
+             VALUES ('m2', 's1', 'tool', 'stray output', 'call_gone')",
+            [],
+        )
+        .unwrap();
+
+    let context = store
+        .load_conversation("s1", None, Some(1_000_000), None)
+        .unwrap();
+    assert!(context
+        .iter()
+        .all(|m| m.content.as_text() != "stray output"));
+    assert_eq!(store.get_messages("s1", 100).unwrap().len(), 2);
+}
+
+#[test]
+fn agent_notes_write_read_list() {
+    let store = test_store();
+    store.note_write("a1", None, "plan", "step 1").unwrap();
+    store.note_write("a1", None, "plan", "step 2").unwrap();
+    store.note_write("a1", None, "cursor", "page=3").unwrap();
+
+    let note = store.note_read("a1", None, "plan").unwrap().unwrap();
+    assert_eq!(note.value, "step 2");
+    assert!(note.session_id.is_none());
+    assert!(store.note_read("a1", None, "missing").unwrap().is_none());
+
+    let keys: Vec<_> = store
+        .note_list("a1", None)
+        .unwrap()
+        .into_iter()
+        .map(|n| n.key)
+        .collect();
+    assert_eq!(keys, ["cursor", "plan"]);
+
+    assert!(store.note_delete("a1", None, "cursor").unwrap());
+    assert!(!store.note_delete("a1", None, "cursor").unwrap());
+    assert_eq!(store.note_list("a1", None).unwrap().len(), 1);
+}
+
+#[test]
+fn agent_notes_are_scoped_by_agent_and_session() {
+    let store = test_store();
+    store.create_session("s1", "gpt-4", None, None).unwrap();
+    store.note_write("a1", None, "plan", "shared").unwrap();
+    store
+        .note_write("a1", Some("s1"), "plan", "s1 only")
+        .unwrap();
+    store.note_write("a2", None, "secret", "a2's").unwrap();
+
+    // Other agents never see each other's notes
+    assert!(store.note_read("a1", None, "secret").unwrap().is_none());
+    assert!(store
+        .note_list("a2", None)
+        .unwrap()
+        .iter()
+        .all(|n| n.agent_id == "a2"));
+
+    // The session note shadows the agent-wide one inside that session only
+    assert_eq!(
+        store
+            .note_read("a1", Some("s1"), "plan")
+            .unwrap()
+            .unwrap()
+            .value,
+        "s1 only"
+    );
+    assert_eq!(
+        store
+            .note_read("a1", Some("s2"), "plan")
+            .unwrap()
+            .unwrap()
+            .value,
+        "shared"
+    );
+    assert_eq!(
+        store.note_read("a1", None, "plan").unwrap().unwrap().value,
+        "shared"
+    );
+    assert_eq!(store.note_list("a1", None).unwrap().len(), 1);
+    assert_eq!(store.note_list("a1", Some("s1")).unwrap().len(), 2);
+
+    // Session notes go with their session
+    store.delete_session("s1").unwrap();
+    assert_eq!(store.note_list("a1", Some("s1")).unwrap().len(), 1);
+}
+
+#[test]
+fn agent_todos_move_from_pending_to_done() {
+    let store = test_store();
+    let first = store.todo_add("a1", "Fetch the report").unwrap();
+    let second = store.todo_add("a1", "Summarize it").unwrap();
+    store.todo_add("a2", "Someone else's work").unwrap();
+    assert_eq!(first.status, "pending");
+    assert!(first.completed_at.is_none());
+    assert!(store.todo_add("a1", "   ").is_err());
+
+    let done = store.todo_complete("a1", first.id).unwrap();
+    assert_eq!(done.status, "done");
+    assert!(done.completed_at.is_some());
+    // Done is final, and agents can't complete each other's todos
+    assert!(store.todo_complete("a1", first.id).is_err());
+    assert!(store.todo_complete("a2", second.id).is_err());
+    assert!(store.todo_complete("a1", 9999).is_err());
+
+    let all = store.todo_list("a1", None).unwrap();
+    assert_eq!(
+        all.iter().map(|t| t.id).collect::<Vec<_>>(),
+        [first.id, second.id]
+    );
+    let pending = store.todo_list("a1", Some("pending")).unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].description, "Summarize it");
+    assert_eq!(store.todo_list("a1", Some("done")).unwrap().len(), 1);
+    assert_eq!(store.todo_list("a2", None).unwrap().len(), 1);
+}
+
+#[test]
+fn due_reminders_include_overdue_and_fire_once() {
+    use chrono::{Duration, Utc};
+    let store = test_store();
+    let now = Utc::now();
+    // Came due while the app was closed
+    let missed = store
+        .reminder_add("a1", "call mom", &(now - Duration::hours(3)), None, None)
+        .unwrap();
+    let due = store
+        .reminder_add("a1", "stretch", &(now - Duration::seconds(5)), None, None)
+        .unwrap();
+    let future = store
+        .reminder_add(
+            "a1",
+            "standup",
+            &(now + Duration::hours(1)),
+            Some("whatsapp"),
+            Some("123@s.whatsapp.net"),
+        )
+        .unwrap();
+    let cancelled = store
+        .reminder_add(
+            "a1",
+            "never mind",
+            &(now - Duration::minutes(1)),
+            None,
+            None,
+        )
+        .unwrap();
+    store.reminder_cancel("a1", cancelled.id).unwrap();
+    assert!(store.reminder_cancel("a1", cancelled.id).is_err());
+    assert!(store.reminder_cancel("a2", future.id).is_err());
+
+    let ids: Vec<_> = store
+        .due_reminders(&now)
+        .unwrap()
+        .iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(ids, [missed.id, due.id]);
+
+    assert!(store.mark_reminder_fired(missed.id).unwrap());
+    assert!(!store.mark_reminder_fired(missed.id).unwrap());
+    assert_eq!(store.due_reminders(&now).unwrap().len(), 1);
+
+    // The future one comes due later; pending list excludes fired/cancelled
+    let later = now + Duration::hours(2);
+    assert_eq!(store.due_reminders(&later).unwrap().len(), 2);
+    assert_eq!(store.reminder_list("a1", false).unwrap().len(), 2);
+    assert_eq!(store.reminder_list("a1", true).unwrap().len(), 4);
+    let stored = store.reminder_list("a1", false).unwrap().pop().unwrap();
+    assert_eq!(stored.channel.as_deref(), Some("whatsapp"));
+    assert_eq!(
+        stored.fire_at_utc().map(|t| t.timestamp()),
+        Some((now + Duration::hours(1)).timestamp())
+    );
+}
+
+#[test]
+fn archive_old_sessions_only_archives_stale_ones() {
+    use openpawz_core::engine::session_archive::archive_old_sessions;
+
+    let store = test_store();
+    for id in ["old", "recent"] {
+        store.create_session(id, "gpt-4", None, None).unwrap();
+        store
+            .add_message(&paw_temp_lib::atoms::types::StoredMessage {
+                id: format!("{}-m1", id),
+                session_id: id.into(),
+                role: "user".into(),
+                content: format!("Plan the {} project", id),
+                tool_calls_json: None,
+                tool_call_id: None,
+                name: None,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
+            })
+            .unwrap();
+    }
+    store
+        .conn
+        .lock()
+        .execute(
+            "UPDATE sessions SET updated_at = datetime('now', '-45 days') WHERE id = 'old'",
+            [],
+        )
+        .unwrap();
+
+    let report = archive_old_sessions(&store, 30, true).unwrap();
+    assert_eq!(report.session_ids, ["old"]);
+    assert_eq!(report.summarized, 1);
+
+    let old = store.get_session("old").unwrap().unwrap();
+    assert!(old.archived);
+    assert_eq!(
+        old.summary.as_deref(),
+        Some("Plan the old project (1 messages)")
+    );
+    assert!(!store.get_session("recent").unwrap().unwrap().archived);
+
+    // Archived sessions leave the default list but stay reachable
+    let visible = store.list_sessions_visible(10, None, false).unwrap();
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].id, "recent");
+    assert_eq!(
+        store.list_sessions_visible(10, None, true).unwrap().len(),
+        2
+    );
+
+    // Already archived: a second run is a no-op
+    assert_eq!(archive_old_sessions(&store, 30, true).unwrap().archived, 0);
+}
+
+#[test]
+fn semantic_session_search_ranks_by_meaning_over_shared_words() {
+    use openpawz_core::engine::session_search::search_sessions;
+
+    let store = test_store();
+    for (id, content) in [
+        ("k8s", "Our pods keep getting OOM-killed after the deploy"),
+        (
+            "reading",
+            "Add a book on container orchestration to my reading list",
+        ),
+    ] {
+        store.create_session(id, "gpt-4", None, None).unwrap();
+        store
+            .add_message(&paw_temp_lib::atoms::types::StoredMessage {
+                id: format!("{}-m1", id),
+                session_id: id.into(),
+                role: "user".into(),
+                content: content.into(),
+                tool_calls_json: None,
+                tool_call_id: None,
+                name: None,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
+            })
+            .unwrap();
+    }
+    assert_eq!(store.sessions_needing_chunks(10).unwrap().len(), 2);
+
+    // Stand-in embeddings: the incident chat sits close to the query in
+    // meaning even though it shares none of its words.
+    store
+        .replace_session_chunks(
+            "k8s",
+            1,
+            &[(
+                "User: Our pods keep getting OOM-killed after the deploy".into(),
+                Some(vec![0.9, 0.1, 0.0]),
+            )],
+        )
+        .unwrap();
+    store
+        .replace_session_chunks(
+            "reading",
+            1,
+            &[(
+                "User: Add a book on container orchestration to my reading list".into(),
+                Some(vec![0.0, 0.2, 0.98]),
+            )],
+        )
+        .unwrap();
+    assert!(store.sessions_needing_chunks(10).unwrap().is_empty());
+
+    let query = "container orchestration outage";
+    let hits = search_sessions(&store, query, Some(&[1.0, 0.0, 0.0]), 5).unwrap();
+    assert_eq!(hits[0].session_id, "k8s");
+    assert!(hits[0].semantic);
+    assert!(hits[0].snippet.contains("OOM-killed"));
+
+    // No query vector: keyword fallback only finds the lexical match
+    let hits = search_sessions(&store, query, None, 5).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].session_id, "reading");
+    assert!(!hits[0].semantic);
+
+    store.delete_session("reading").unwrap();
+    assert!(search_sessions(&store, query, None, 5).unwrap().is_empty());
+}
+
+#[test]
+fn secure_delete_erases_session_and_vacuums() {
+    let store = test_store();
+    store.create_session("secret", "gpt-4", None, None).unwrap();
+    store.create_session("keep", "gpt-4", None, None).unwrap();
+    for (session, id) in [("secret", "m1"), ("secret", "m2"), ("keep", "m3")] {
+        store
+            .add_message(&paw_temp_lib::atoms::types::StoredMessage {
+                id: id.into(),
+                session_id: session.into(),
+                role: "user".into(),
+                content: format!("my bank PIN is 4921 ({})", id),
+                tool_calls_json: None,
+                tool_call_id: None,
+                name: None,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                incomplete: false,
+                reasoning: None,
+            })
+            .unwrap();
+    }
+    store
+        .replace_session_chunks("secret", 2, &[("User: my bank PIN is 4921".into(), None)])
+        .unwrap();
+
+    let secure_delete_before: i64 = store
+        .conn
+        .lock()
+        .query_row("PRAGMA secure_delete", [], |row| row.get(0))
+        .unwrap();
+    let report = store.secure_delete_session("secret", true).unwrap();
+    assert_eq!(report.messages_erased, 2);
+    assert!(report.vacuumed);
+    assert!(store
+        .search_session_chunks_fts("PIN 4921", 10)
+        .unwrap()
+        .is_empty());
+
+    assert!(store.get_session("secret").unwrap().is_none());
+    assert!(store.get_messages("secret", 100).unwrap().is_empty());
+    let conn = store.conn.lock();
+    let leftover: i64 = conn
+        .query_row(
+            "SELECT (SELECT COUNT(*) FROM messages WHERE session_id = 'secret')
+                  + (SELECT COUNT(*) FROM session_chunks WHERE session_id = 'secret')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(leftover, 0);
+    let fts_rows: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM session_chunks_fts WHERE session_chunks_fts MATCH '4921'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(fts_rows, 0);
+    // The connection's own secure_delete setting is restored
+    let secure_delete: i64 = conn
+        .query_row("PRAGMA secure_delete", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(secure_delete, secure_delete_before);
+    drop(conn);
+    // Other sessions are untouched
+    assert_eq!(store.get_messages("keep", 100).unwrap().len(), 1);
+}
@@ -302,8 +302,8 @@ const APPROVAL_CATEGORIES: ApprovalCategory[] = [
     id: 'media',
     label: 'Media',
     icon: 'image',
-    description: 'Image generation',
-    tools: ['image_generate'],
+    description: 'Image generation and speech',
+    tools: ['image_generate', 'speak'],
   },
 ];
 
@@ -62,6 +62,7 @@ const KIND_LABELS: Record<string, string> = {
   anthropic: 'Anthropic',
   google: 'Google',
   azurefoundry: 'Azure AI Foundry',
+  azureopenai: 'Azure OpenAI',
   openrouter: 'OpenRouter',
   custom: 'Custom',
   deepseek: 'DeepSeek',
@@ -77,6 +78,7 @@ export const PROVIDER_ICONS: Record<string, string> = {
   anthropic: 'psychology',
   google: 'auto_awesome',
   azurefoundry: 'cloud',
+  azureopenai: 'cloud',
   openrouter: 'language',
   custom: 'build',
   deepseek: 'explore',
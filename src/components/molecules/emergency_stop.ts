@@ -0,0 +1,65 @@
+// src/components/molecules/emergency_stop.ts
+// Global emergency stop ("panic button").
+// The sidebar button halts every agent run, channel bridge and scheduled
+// task at once; a banner stays up until the user resumes the engine.
+// Call initEmergencyStop() once at app startup.
+
+import { pawEngine, type EngineHaltStatus, type EngineEmergencyStatus } from '../../engine';
+import { showToast } from '../toast';
+
+function renderHalted(status: EngineHaltStatus): void {
+  const banner = document.getElementById('emergency-stop-banner');
+  if (banner) banner.style.display = status.halted ? '' : 'none';
+  const btn = document.getElementById('sidebar-emergency-stop');
+  btn?.classList.toggle('halted', status.halted);
+  if (btn) {
+    btn.title = status.halted
+      ? 'Emergency stop active'
+      : 'Emergency stop — halt all agents, channels and scheduled tasks';
+  }
+}
+
+async function stopEverything(): Promise<void> {
+  try {
+    const status = await pawEngine.emergencyStop();
+    renderHalted(status);
+    const bridges = status.stopped_bridges.length
+      ? `, ${status.stopped_bridges.length} channel(s) stopped`
+      : '';
+    showToast(`Emergency stop: ${status.cancelled_runs} run(s) cancelled${bridges}`, 'warning');
+  } catch (e) {
+    showToast(`Emergency stop failed: ${e}`, 'error');
+  }
+}
+
+async function resumeEngine(): Promise<void> {
+  try {
+    renderHalted(await pawEngine.resume());
+    showToast('Engine resumed — restart any channels you need', 'success');
+  } catch (e) {
+    showToast(`Resume failed: ${e}`, 'error');
+  }
+}
+
+export function initEmergencyStop(): void {
+  document.getElementById('sidebar-emergency-stop')?.addEventListener('click', () => {
+    stopEverything();
+  });
+  document.getElementById('emergency-resume')?.addEventListener('click', () => {
+    resumeEngine();
+  });
+
+  pawEngine
+    .emergencyStatus()
+    .then(renderHalted)
+    .catch((e) => console.warn('[emergency] Failed to read status:', e));
+
+  // Keep every window in sync (e.g. a stop triggered from a pop-out)
+  import('@tauri-apps/api/event')
+    .then(({ listen }) =>
+      listen<EngineEmergencyStatus>('engine-emergency', (event) => renderHalted(event.payload)),
+    )
+    .catch(() => {
+      /* not running under Tauri */
+    });
+}
@@ -130,6 +130,7 @@ interface BubbleOptions {
     allTargetsLocal: boolean;
   };
   requireTypeToConfirm: boolean;
+  ruleReason?: string;
   onAllow: () => void;
   onDeny: () => void;
   onAlwaysAllow: () => void;
@@ -142,6 +143,7 @@ function injectChatBubble(opts: BubbleOptions): HTMLElement | null {
   if (!chatMessages) return null;
 
   const { toolCallId, toolName, args, tier, risk, pattern, netAudit, requireTypeToConfirm } = opts;
+  const { ruleReason } = opts;
 
   const bubble = document.createElement('div');
   bubble.className = `chat-approval-bubble${tier === 'external' ? ' bubble-external' : tier === 'dangerous' ? ' bubble-dangerous' : ''}`;
@@ -171,6 +173,11 @@ function injectChatBubble(opts: BubbleOptions): HTMLElement | null {
     </div>`;
   }
 
+  // Approval-rule banner (prompt forced by a configured rule)
+  const ruleBannerHtml = ruleReason
+    ? `<div class="chat-approval-net-banner"><span class="ms" style="font-size:14px">rule</span> ${escHtml(ruleReason)}</div>`
+    : '';
+
   // Network audit banner
   let netBannerHtml = '';
   if (netAudit.isNetworkRequest) {
@@ -231,6 +238,7 @@ function injectChatBubble(opts: BubbleOptions): HTMLElement | null {
     </div>
     <div class="chat-approval-subtitle"><code>${escHtml(toolName)}</code>${args && 'command' in args ? ` — <span class="approval-cmd-preview">${escHtml(String(args.command).slice(0, 120))}</span>` : ''}</div>
     ${riskBannerHtml}
+    ${ruleBannerHtml}
     ${netBannerHtml}
     ${
       argsJson
@@ -387,10 +395,12 @@ export function initHILModal(): void {
     const secSettings = loadSecuritySettings();
     const risk: RiskClassification | null = classifyCommandRisk(toolName, args);
     const cmdStr = extractCommandString(toolName, args);
+    // Prompts forced by an approval rule bypass the user's shortcuts
+    const ruleReason = event.approval_reason;
 
     // ── "Always Allow" check: auto-approve if user previously set it ──
     const alwaysAllowed = getAlwaysAllowedTools();
-    if (alwaysAllowed.includes(toolName) && toolTier !== 'dangerous') {
+    if (!ruleReason && alwaysAllowed.includes(toolName) && toolTier !== 'dangerous') {
       resolveEngineToolApproval(toolCallId, true);
       logCredentialActivity({
         action: 'approved',
@@ -427,7 +437,7 @@ export function initHILModal(): void {
 
     // Session override: auto-approve
     const overrideRemaining = getSessionOverrideRemaining();
-    if (overrideRemaining > 0) {
+    if (overrideRemaining > 0 && !ruleReason) {
       if (!(secSettings.autoDenyPrivilegeEscalation && isPrivilegeEscalation(toolName, args))) {
         resolveEngineToolApproval(toolCallId, true);
         const minsLeft = Math.ceil(overrideRemaining / 60000);
@@ -599,6 +609,7 @@ export function initHILModal(): void {
       pattern,
       netAudit,
       requireTypeToConfirm: !!(secSettings.requireTypeToCritical && risk?.level === 'critical'),
+      ruleReason,
       onAllow: doAllow,
       onDeny: doDeny,
       onAlwaysAllow: doAlwaysAllow,
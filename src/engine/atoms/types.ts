@@ -16,10 +16,15 @@ export interface EngineProviderConfig {
     | 'deepseek'
     | 'grok'
     | 'mistral'
-    | 'moonshot';
+    | 'moonshot'
+    | 'azureopenai';
   api_key: string;
   base_url?: string;
   default_model?: string;
+  /** Azure OpenAI `api-version` query parameter. */
+  api_version?: string;
+  /** Azure OpenAI model → deployment name; unmapped models use their own name. */
+  deployments?: Record<string, string>;
 }
 
 export interface EngineConfig {
@@ -28,16 +33,32 @@ export interface EngineConfig {
   default_model?: string;
   default_system_prompt?: string;
   max_tool_rounds: number;
+  /** Total tool calls allowed per run before the agent is told to conclude. 0 = no limit. Default: 100 */
+  max_tool_iterations?: number;
   tool_timeout_secs: number;
   model_routing?: ModelRouting;
-  /** Max simultaneous agent runs (chat + cron + tasks). Chat always gets priority. Default: 4 */
+  /** Max simultaneous agent runs (chat + cron + tasks + swarm). Default: 4 */
   max_concurrent_runs?: number;
+  /** A chat run past the limit waits for a slot ('queue') or fails ('reject'). Default: 'queue' */
+  run_limit_policy?: 'queue' | 'reject';
   /** Daily budget in USD. When estimated spend exceeds this, new API calls are blocked. 0 = disabled. Default: 10 */
   daily_budget_usd?: number;
+  /** Per-agent daily budgets in USD keyed by agent id, enforced alongside daily_budget_usd (whichever is hit first). */
+  agent_budgets_usd?: Record<string, number>;
+  /** IANA timezone the daily budgets reset in. Default: the user's timezone */
+  budget_timezone?: string;
+  /** Local hour (0-23) the daily budgets reset at. Default: 0 */
+  budget_reset_hour?: number;
   /** Context window size in tokens. Controls how much conversation history the agent sees. Default: 32000 */
   context_window_tokens?: number;
   /** Weather location for Today dashboard (e.g. "New York"). Auto-detected via IP if empty. */
   weather_location?: string;
+  /** Layout for the base system prompt. Variables: {base_prompt} {agent_name} {date} {memory_summary} {skills} {workspace} */
+  system_prompt_template?: string;
+  /** Stream and show thinking-model reasoning in chat. Default: true */
+  show_reasoning?: boolean;
+  /** Language for engine messages shown to people (e.g. "es"). Default: English */
+  locale?: string;
 }
 
 /** Model routing for multi-agent orchestration.
@@ -78,6 +99,10 @@ export interface EngineChatRequest {
   auto_approve_all?: boolean;
   /** Tool names the user has approved via the sidebar Approvals panel. */
   user_approved_tools?: string[];
+  /** Edit-and-resend: replace this earlier user message and drop the turns after it. */
+  edit_message_id?: string;
+  /** Regenerate: answer the last user message again, replacing the previous reply. */
+  regenerate?: boolean;
 }
 
 export interface EngineChatResponse {
@@ -96,6 +121,14 @@ export interface EngineSession {
   updated_at: string;
   message_count: number;
   agent_id?: string;
+  /** Session this one was forked from. */
+  parent_session_id?: string;
+  /** Message in the parent the fork was taken at. */
+  forked_from_message_id?: string;
+  /** Hidden from the default session list. */
+  archived?: boolean;
+  /** One-line summary stored when the session was archived. */
+  summary?: string;
 }
 
 export interface EngineStoredMessage {
@@ -109,6 +142,10 @@ export interface EngineStoredMessage {
   created_at: string;
   /** Agent that produced this message (populated by backend for squad sessions). */
   agent_id?: string;
+  /** Reply saved mid-stream whose run never finished — the text may be cut short. */
+  incomplete?: boolean;
+  /** Reasoning a thinking model produced before the reply. Never re-sent as history. */
+  reasoning?: string;
 }
 
 // ── Events ───────────────────────────────────────────────────────────
@@ -118,10 +155,13 @@ export interface EngineEvent {
     | 'delta'
     | 'tool_request'
     | 'tool_result'
+    | 'tool_start'
+    | 'tool_end'
     | 'complete'
     | 'error'
     | 'thinking_delta'
     | 'tool_auto_approved'
+    | 'run_queued'
     | 'canvas_push'
     | 'canvas_update';
   session_id: string;
@@ -132,18 +172,28 @@ export interface EngineEvent {
   tool_call?: { id: string; type: string; function: { name: string; arguments: string } };
   /** Tool tier: "safe" | "reversible" | "external" | "dangerous" | "unknown" */
   tool_tier?: string;
+  /** Set when a configured approval rule forced this prompt. */
+  approval_reason?: string;
   // tool_result
   tool_call_id?: string;
   output?: string;
   success?: boolean;
+  // tool_start / tool_end — redacted, size-capped summaries for display
+  args_summary?: string;
+  result_summary?: string;
   // complete
   tool_calls_count?: number;
   usage?: { input_tokens: number; output_tokens: number; total_tokens: number };
   model?: string;
+  /** Why the model stopped on the final round */
+  finish_reason?: 'stop' | 'length' | 'tool_calls' | 'content_filter' | 'other';
   // error
   message?: string;
-  // tool_auto_approved
+  // tool_auto_approved, tool_start, tool_end
   tool_name?: string;
+  // run_queued
+  active_runs?: number;
+  max_runs?: number;
   // multi-agent: which agent produced this event
   agent_id?: string;
   // canvas_push
@@ -158,7 +208,7 @@ export interface EngineEvent {
   loaded_tools?: string[];
   /** Estimated context token count (on tool_request) */
   context_tokens?: number;
-  /** Tool execution duration in ms (on tool_result) */
+  /** Tool execution duration in ms (on tool_result and tool_end) */
   duration_ms?: number;
   /** Total rounds executed in this turn (on complete) */
   total_rounds?: number;
@@ -166,12 +216,27 @@ export interface EngineEvent {
   max_rounds?: number;
 }
 
+/** Whether an emergency stop is blocking all agent runs. */
+export interface EngineHaltStatus {
+  halted: boolean;
+  halted_at?: string | null;
+}
+
+/** Result of engine_emergency_stop / engine_resume, also sent as `engine-emergency`. */
+export interface EngineEmergencyStatus extends EngineHaltStatus {
+  cancelled_runs: number;
+  stopped_bridges: string[];
+}
+
 export interface EngineStatus {
   ready: boolean;
   providers: number;
   has_api_key: boolean;
   default_model?: string;
   default_provider?: string;
+  active_runs?: number;
+  queued_runs?: number;
+  max_concurrent_runs?: number;
 }
 
 // ── Agent Files (Soul / Persona) ─────────────────────────────────────
@@ -201,11 +266,34 @@ export interface EngineMemoryConfig {
   embedding_provider: EmbeddingProvider;
   embedding_base_url: string;
   embedding_model: string;
+  /** Key for a standalone OpenAI-compatible embedding endpoint (empty = use chat provider). */
+  embedding_api_key?: string;
   embedding_dims: number;
   auto_recall: boolean;
   auto_capture: boolean;
   recall_limit: number;
   recall_threshold: number;
+  /** Memories longer than this (chars) are also embedded in chunks. */
+  chunk_chars?: number;
+  /** Characters shared by consecutive chunks. */
+  chunk_overlap?: number;
+}
+
+export interface AutoCapturePolicy {
+  enabled: boolean;
+  /** Fact categories to keep — empty = all. */
+  categories: string[];
+  /** Minimum importance (0–10) for a captured fact. */
+  min_importance: number;
+}
+
+export interface MemoryImportReport {
+  imported: number;
+  /** Ids that already existed (left untouched). */
+  skipped: number;
+  reembedded: number;
+  /** Stored without a vector — pending backfill. */
+  without_embedding: number;
 }
 
 export interface EngineMemoryStats {
@@ -272,7 +360,32 @@ export interface EngineSkillStatus {
   default_enabled?: boolean;
 }
 
-export type SkillSource = 'builtin' | 'toml';
+export type SkillSource = 'builtin' | 'toml' | 'community';
+
+/** Whether one declared credential is stored — the value is never sent. */
+export interface SkillCredentialPresence {
+  key: string;
+  label: string;
+  required: boolean;
+  configured: boolean;
+}
+
+/** Consolidated per-skill status (built-in, TOML and community). */
+export interface SkillStatusSummary {
+  id: string;
+  name: string;
+  icon: string;
+  source: SkillSource;
+  enabled: boolean;
+  credentials: SkillCredentialPresence[];
+  /** All required credentials are configured */
+  credentials_configured: boolean;
+  has_custom_instructions: boolean;
+  has_mcp: boolean;
+  /** Live MCP connection state — null when the skill bundles no MCP server */
+  mcp_connected: boolean | null;
+  is_ready: boolean;
+}
 
 // ── TOML Manifest Skills (Phase F.1) ─────────────────────────────────
 
@@ -286,6 +399,24 @@ export interface TomlSkillEntry {
   has_view: boolean;
   view_label: string;
   view_icon: string;
+  permitted_tools: string[] | null;
+}
+
+/** Result of installing a skill from a URL / GitHub source. */
+export interface RemoteSkillInstall {
+  skill_id: string;
+  name: string;
+  version: string;
+  author: string;
+  source_url: string;
+  credentials: string[];
+  mcp_server: string | null;
+  bundled_files: string[];
+  /** Built-in tools requested by the manifest (null = safe default subset). */
+  permitted_tools: string[] | null;
+  requires_confirmation: boolean;
+  installed: boolean;
+  path: string | null;
 }
 
 // ── FORGE (Certification & Skill Trees) ─────────────────────────────
@@ -483,6 +614,76 @@ export interface TelemetryMetricRow {
   created_at: string;
 }
 
+/** One ordered step (LLM call, tool call or outcome) of an agent run trace. */
+export interface RunTraceSpan {
+  seq: number;
+  kind: 'llm' | 'tool' | 'outcome';
+  /** Model name, tool name, or outcome status. */
+  name: string;
+  round: number;
+  start_ms: number;
+  duration_ms: number;
+  success: boolean;
+  /** Redacted, size-capped details (tokens, args summary, result_bytes, error). */
+  attributes: Record<string, string>;
+}
+
+/** Per-tool HIL approval rule (first matching rule wins). */
+export interface ApprovalRule {
+  /** Tool name or `prefix*` pattern. */
+  tool: string;
+  action: 'require' | 'auto' | 'threshold';
+  /** USD amount at which a threshold rule requires approval. */
+  threshold_usd?: number;
+  /** Argument holding the token amount, valued at a USD quote (default "amount"). */
+  amount_field?: string;
+}
+
+export interface ApprovalRules {
+  rules: ApprovalRule[];
+}
+
+/** Opt-in cache for idempotent read tools. */
+export interface ToolCacheConfig {
+  enabled: boolean;
+  default_ttl_secs: number;
+  /** Per-tool TTL in seconds; 0 = never cache that tool. */
+  ttl_secs: Record<string, number>;
+  max_entries: number;
+}
+
+export interface ToolCacheStats {
+  entries: number;
+  hits: number;
+  misses: number;
+}
+
+/** One HIL approval decision and the outcome of the approved call. */
+export interface ApprovalRecord {
+  id: number;
+  session_id: string;
+  tool_call_id: string;
+  tool_name: string;
+  /** Redacted `key=value, …` summary of the arguments. */
+  args_summary: string;
+  decision: 'approved' | 'denied';
+  /** "user", "rule", "autonomy", "agent_policy", "trading_policy", "timeout", … */
+  decider: string;
+  created_at: string;
+  success: boolean | null;
+  outcome: string | null;
+  tx_hash: string | null;
+}
+
+/** A switch to a fallback provider after billing/auth errors or sustained 529 overload. */
+export interface ProviderRotationEvent {
+  timestamp: string;
+  from_provider: string;
+  to_provider: string;
+  to_model: string;
+  reason: string;
+}
+
 /** Aggregated metrics for a single day. */
 export interface TelemetryDailySummary {
   date: string;
@@ -668,6 +869,27 @@ export interface TradingPolicy {
   max_transfer_usd: number;
 }
 
+/** Hard DEX spending caps, enforced before approval and signing (0 = no cap). */
+export interface DexAllowance {
+  max_tx_eth: number;
+  max_tx_usd: number;
+  max_daily_eth: number;
+  max_daily_usd: number;
+}
+
+export interface DexWallet {
+  id: string;
+  label: string;
+  address: string;
+  created_at: string;
+}
+
+export interface DexWalletList {
+  /** Active wallet id; null means the default wallet. */
+  active: string | null;
+  wallets: DexWallet[];
+}
+
 export interface Position {
   id: string;
   mint: string;
@@ -788,6 +1010,64 @@ export interface BackendAgent {
   capabilities?: string[];
 }
 
+/** Stored agent config (agents table): persona, default model, skills. */
+export interface EngineAgentDefinition {
+  id: string;
+  name: string;
+  /** Persona prompt; unset = the engine's default system prompt. */
+  persona?: string | null;
+  /** Default model; unset = the engine's default model. */
+  model?: string | null;
+  /** Enabled skill ids; empty = the globally enabled skills. */
+  skills: string[];
+  /** How much the agent may do without asking. */
+  autonomy?: EngineAgentAutonomy;
+  created_at?: string;
+  updated_at?: string;
+}
+
+/** manual = ask before any change, assisted = tier defaults, autonomous = only big trades ask. */
+export type EngineAutonomyLevel = 'manual' | 'assisted' | 'autonomous';
+
+/** An agent's autonomy level plus overrides of what it derives. */
+export interface EngineAgentAutonomy {
+  level: EngineAutonomyLevel;
+  /** Per-tool rules checked before the level's defaults. */
+  rules?: ApprovalRule[];
+  /** Replaces the level's tool-call cap per run. */
+  max_tool_iterations?: number | null;
+  /** USD amount at which an autonomous agent's financial tools ask first. Default: 50 */
+  financial_threshold_usd?: number | null;
+}
+
+/** Portable agent bundle (engine_export_agent / engine_import_agent). */
+export interface EngineAgentBundle {
+  version: number;
+  exported_at: string;
+  name: string;
+  persona?: string | null;
+  model?: string | null;
+  skills: string[];
+  files: { file_name: string; content: string }[];
+  toml_skills: { id: string; manifest: string }[];
+  /** Credentials the agent's skills need — names only, never values. */
+  credentials: EngineRequiredCredential[];
+}
+
+export interface EngineRequiredCredential {
+  skill_id: string;
+  key: string;
+  label: string;
+  required: boolean;
+}
+
+export interface EngineAgentImportReport {
+  agent: EngineAgentDefinition;
+  installed_skills: string[];
+  skills_needing_confirmation: string[];
+  missing_credentials: EngineRequiredCredential[];
+}
+
 // ── Channel Types ─────────────────────────────────────────────────────
 
 export interface TelegramConfig {
@@ -936,6 +1216,8 @@ export interface WhatsAppConfig {
   api_key: string;
   api_port: number;
   webhook_port: number;
+  webhook_path: string;
+  webhook_secret: string;
   dm_policy: string;
   allowed_users: string[];
   pending_users: ChannelPendingUser[];
@@ -988,6 +1270,13 @@ export interface ScreenshotEntry {
   base64_png?: string;
 }
 
+/** A file saved by the image_generate or speak tool, loaded for display in chat. */
+export interface GeneratedMediaEntry {
+  path: string;
+  mime_type: string;
+  base64: string;
+}
+
 // ── Per-Agent Workspaces ──────────────────────────────────────────────
 
 export interface WorkspaceInfo {
@@ -1024,6 +1313,18 @@ export interface NetworkRequest {
   tool_name: string;
 }
 
+export interface OutboundHttpConfig {
+  user_agent: string | null;
+  domain_headers: Record<string, Record<string, string>>;
+}
+
+export interface WebPolitenessConfig {
+  respect_robots: boolean;
+  respect_robots_in_browser: boolean;
+  respect_robots_for_apis: boolean;
+  min_interval_ms: number;
+}
+
 // ── Tailscale (Remote Access) ─────────────────────────────────────────
 
 export interface TailscaleStatus {
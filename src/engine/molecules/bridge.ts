@@ -255,24 +255,44 @@ function translateEngineEvent(event: EngineEvent): Record<string, unknown> | nul
         agentId: event.agent_id,
       };
 
-    case 'tool_request':
+    // Approval prompts go to the approval handlers; the tool's progress
+    // shows once tool_start confirms it is actually running.
+    case 'tool_start':
       return {
         stream: 'tool',
         data: {
           phase: 'start',
-          name: event.tool_call?.function?.name ?? 'tool',
-          tool: event.tool_call?.function?.name,
+          name: event.tool_name ?? 'tool',
+          tool: event.tool_name,
+          tool_call_id: event.tool_call_id,
+          args: event.args_summary,
         },
         runId: event.run_id,
         sessionKey: event.session_id,
         agentId: event.agent_id,
       };
 
-    case 'tool_result':
+    case 'tool_end':
       return {
         stream: 'tool',
         data: {
           phase: 'end',
+          name: event.tool_name ?? 'tool',
+          tool: event.tool_name,
+          tool_call_id: event.tool_call_id,
+          summary: event.result_summary,
+          success: event.success,
+          duration_ms: event.duration_ms,
+        },
+        runId: event.run_id,
+        sessionKey: event.session_id,
+      };
+
+    case 'tool_result':
+      return {
+        stream: 'tool',
+        data: {
+          phase: 'output',
           tool_call_id: event.tool_call_id,
           output: event.output,
           success: event.success,
@@ -350,7 +370,7 @@ function kineticPulse(kind: string): void {
 
   const classMap: Record<string, string> = {
     delta: 'kinetic-pulse-stream',
-    tool_request: 'kinetic-pulse-tool',
+    tool_start: 'kinetic-pulse-tool',
     tool_result: 'kinetic-pulse-tool-end',
     error: 'kinetic-pulse-error',
     complete: 'kinetic-pulse-complete',
@@ -20,7 +20,11 @@ import {
   renderMessages,
   renderAttachmentStrip,
   renderScreenshotCard,
+  renderGeneratedImageCard,
+  renderGeneratedAudioCard,
   showStreamingMessage,
+  showToolStep,
+  showToolOutcome,
   appendStreamingDelta,
   appendThinkingDelta,
   scrollToBottom,
@@ -277,6 +281,29 @@ describe('showStreamingMessage', () => {
   });
 });
 
+// ── showToolStep / showToolOutcome ───────────────────────────────────────
+
+describe('showToolOutcome', () => {
+  it('replaces the running step with the result', () => {
+    const container = document.createElement('div');
+    showStreamingMessage(container, 'Aria');
+    showToolStep(container, 'trello_create_card', 'name="Ship it"');
+    const step = container.querySelector('.agent-step-indicator')!;
+    expect(step.textContent).toContain('Using trello_create_card');
+    expect(step.textContent).toContain('name="Ship it"');
+
+    showToolOutcome(container, 'trello_create_card', {
+      success: false,
+      summary: 'Error: board not found',
+      durationMs: 1250,
+    });
+    expect(container.querySelectorAll('.agent-step-indicator').length).toBe(1);
+    expect(step.classList.contains('step-failed')).toBe(true);
+    expect(step.textContent).toContain('1.3s');
+    expect(step.textContent).toContain('board not found');
+  });
+});
+
 // ── appendStreamingDelta ─────────────────────────────────────────────────
 
 describe('appendStreamingDelta', () => {
@@ -364,6 +391,38 @@ describe('renderScreenshotCard', () => {
   });
 });
 
+// ── renderGeneratedImageCard ─────────────────────────────────────────────
+
+describe('renderGeneratedImageCard', () => {
+  it('returns null outside a generated-images directory', () => {
+    expect(renderGeneratedImageCard('Image saved: /etc/passwd.png')).toBeNull();
+  });
+
+  it('returns an element for a saved image path', () => {
+    const el = renderGeneratedImageCard(
+      'Here it is.\nImage saved: /home/u/.paw/workspaces/default/generated-images/fox.png',
+    );
+    expect(el).not.toBeNull();
+    expect(el!.className).toBe('message-generated-image-card');
+  });
+});
+
+// ── renderGeneratedAudioCard ─────────────────────────────────────────────
+
+describe('renderGeneratedAudioCard', () => {
+  it('returns null outside a speech directory', () => {
+    expect(renderGeneratedAudioCard('Audio saved: /tmp/other.mp3')).toBeNull();
+  });
+
+  it('returns an element for saved speech', () => {
+    const el = renderGeneratedAudioCard(
+      'Audio saved: /home/u/.paw/workspaces/default/speech/speech_20260101_000000_hi.mp3',
+    );
+    expect(el).not.toBeNull();
+    expect(el!.className).toBe('message-generated-audio-card');
+  });
+});
+
 // ── renderSingleMessage — edge cases ─────────────────────────────────────
 
 describe('renderSingleMessage — edge cases', () => {
@@ -61,18 +61,13 @@ function generateFollowUpSuggestions(content: string): string[] {
  * Show or update an agent tool-step indicator in the streaming message.
  * Displays as a subtle status line: "⟳ Running: tool_name"
  */
-export function showToolStep(container: HTMLElement, toolName: string): void {
-  const streamingMsg = container.querySelector('#streaming-message');
-  if (!streamingMsg) return;
-  let stepEl = streamingMsg.querySelector('.agent-step-indicator') as HTMLElement | null;
-  if (!stepEl) {
-    stepEl = document.createElement('div');
-    stepEl.className = 'agent-step-indicator';
-    // Insert before message-content
-    const contentEl = streamingMsg.querySelector('.message-content');
-    if (contentEl) streamingMsg.insertBefore(stepEl, contentEl);
-    else streamingMsg.appendChild(stepEl);
-  }
+export function showToolStep(
+  container: HTMLElement,
+  toolName: string,
+  argsSummary?: string,
+): void {
+  const stepEl = toolStepElement(container);
+  if (!stepEl) return;
   const humanLabels: Record<string, string> = {
     exec: 'Running command',
     run_command: 'Running command',
@@ -86,7 +81,42 @@ export function showToolStep(container: HTMLElement, toolName: string): void {
     grep: 'Searching code',
   };
   const label = humanLabels[toolName] ?? `Using ${toolName}`;
-  stepEl.innerHTML = `<span class="ms step-spin" style="font-size:14px">progress_activity</span> ${escHtml(label)}`;
+  stepEl.classList.remove('step-ok', 'step-failed');
+  stepEl.innerHTML = `<span class="ms step-spin" style="font-size:14px">progress_activity</span> ${escHtml(label)}…${stepDetail(argsSummary)}`;
+}
+
+/** Show how the last tool call went in place of the spinner. */
+export function showToolOutcome(
+  container: HTMLElement,
+  toolName: string,
+  outcome: { success: boolean; summary?: string; durationMs?: number },
+): void {
+  const stepEl = toolStepElement(container);
+  if (!stepEl) return;
+  const ms = outcome.durationMs;
+  const took = ms === undefined ? '' : ` · ${ms < 1000 ? `${ms}ms` : `${(ms / 1000).toFixed(1)}s`}`;
+  stepEl.classList.toggle('step-ok', outcome.success);
+  stepEl.classList.toggle('step-failed', !outcome.success);
+  stepEl.innerHTML = `<span class="ms" style="font-size:14px">${outcome.success ? 'check_circle' : 'error'}</span> ${escHtml(toolName)}${took}${stepDetail(outcome.summary)}`;
+}
+
+function toolStepElement(container: HTMLElement): HTMLElement | null {
+  const streamingMsg = container.querySelector('#streaming-message');
+  if (!streamingMsg) return null;
+  let stepEl = streamingMsg.querySelector('.agent-step-indicator') as HTMLElement | null;
+  if (!stepEl) {
+    stepEl = document.createElement('div');
+    stepEl.className = 'agent-step-indicator';
+    // Insert before message-content
+    const contentEl = streamingMsg.querySelector('.message-content');
+    if (contentEl) streamingMsg.insertBefore(stepEl, contentEl);
+    else streamingMsg.appendChild(stepEl);
+  }
+  return stepEl;
+}
+
+function stepDetail(text?: string): string {
+  return text ? ` <span class="step-detail">${escHtml(text)}</span>` : '';
 }
 
 /** Remove the tool step indicator from the streaming message. */
@@ -160,6 +190,74 @@ export function renderScreenshotCard(msgContent: string): HTMLElement | null {
   return ssCard;
 }
 
+// ── Generated image card ─────────────────────────────────────────────────
+
+const GENERATED_IMAGE_RE =
+  /Image saved:\s*([^\n]+[\\/]generated-images[\\/][^\n\\/]+\.(?:png|jpg|webp|gif))/;
+
+/** Render an inline card for an image saved by the image_generate tool. */
+export function renderGeneratedImageCard(msgContent: string): HTMLElement | null {
+  const match = msgContent.match(GENERATED_IMAGE_RE);
+  if (!match) return null;
+  const imagePath = match[1].trim();
+
+  const card = document.createElement('div');
+  card.className = 'message-generated-image-card';
+  card.style.cssText =
+    'margin:8px 0;border-radius:8px;overflow:hidden;border:1px solid var(--border-color);max-width:400px';
+  card.innerHTML =
+    '<div style="padding:8px;text-align:center;color:var(--text-muted);font-size:12px">Loading image…</div>';
+  (async () => {
+    try {
+      const { pawEngine: eng } = await import('./ipc_client');
+      const image = await eng.generatedImageGet(imagePath);
+      card.innerHTML = '';
+      const img = document.createElement('img');
+      img.src = `data:${image.mime_type};base64,${image.base64}`;
+      img.style.cssText = 'width:100%;display:block';
+      img.alt = imagePath.split('/').pop() || 'Generated image';
+      card.appendChild(img);
+    } catch {
+      card.innerHTML =
+        '<div style="padding:8px;color:var(--text-muted);font-size:12px">Image unavailable</div>';
+    }
+  })();
+  return card;
+}
+
+// ── Generated audio card ─────────────────────────────────────────────────
+
+const GENERATED_AUDIO_RE = /Audio saved:\s*([^\n]+[\\/]speech[\\/][^\n\\/]+\.mp3)/;
+
+/** Render an inline player for audio saved by the speak tool. */
+export function renderGeneratedAudioCard(msgContent: string): HTMLElement | null {
+  const match = msgContent.match(GENERATED_AUDIO_RE);
+  if (!match) return null;
+  const audioPath = match[1].trim();
+
+  const card = document.createElement('div');
+  card.className = 'message-generated-audio-card';
+  card.style.cssText = 'margin:8px 0;max-width:400px';
+  card.innerHTML =
+    '<div style="padding:8px;color:var(--text-muted);font-size:12px">Loading audio…</div>';
+  (async () => {
+    try {
+      const { pawEngine: eng } = await import('./ipc_client');
+      const audio = await eng.generatedAudioGet(audioPath);
+      card.innerHTML = '';
+      const player = document.createElement('audio');
+      player.controls = true;
+      player.src = `data:${audio.mime_type};base64,${audio.base64}`;
+      player.style.cssText = 'width:100%';
+      card.appendChild(player);
+    } catch {
+      card.innerHTML =
+        '<div style="padding:8px;color:var(--text-muted);font-size:12px">Audio unavailable</div>';
+    }
+  })();
+  return card;
+}
+
 // ── Attachment strip ─────────────────────────────────────────────────────
 
 /** Render attachment strip (images + file chips) for a message. */
@@ -279,6 +377,18 @@ export function renderSingleMessage(
     if (ssCard) div.appendChild(ssCard);
   }
 
+  // Inline generated image
+  if (msg.role === 'assistant' && msg.content.includes('Image saved:')) {
+    const imageCard = renderGeneratedImageCard(msg.content);
+    if (imageCard) div.appendChild(imageCard);
+  }
+
+  // Inline spoken audio
+  if (msg.role === 'assistant' && msg.content.includes('Audio saved:')) {
+    const audioCard = renderGeneratedAudioCard(msg.content);
+    if (audioCard) div.appendChild(audioCard);
+  }
+
   // Image/file attachments
   if (msg.attachments?.length) {
     div.appendChild(renderAttachmentStrip(msg.attachments));
@@ -297,6 +297,7 @@ export function createSessionManager(deps: SessionManagerDeps): SessionManager {
           role: m.role as 'user' | 'assistant' | 'system',
           content: m.content,
           timestamp: parseDate(m.created_at),
+          thinkingContent: m.reasoning,
         }));
       deps.renderMessages();
     } catch (e) {
@@ -20,16 +20,24 @@ export interface StreamHandlers {
   onStreamEnd?: (content: string) => void;
   /** Called when the stream errors. */
   onStreamError?: (error: string) => void;
-  /** Called when a tool call starts. */
-  onToolStart?: (toolName: string) => void;
+  /** Called when a tool call starts, with a short summary of its arguments. */
+  onToolStart?: (toolName: string, argsSummary?: string) => void;
   /** Called when a tool call ends. */
-  onToolEnd?: (toolName: string) => void;
+  onToolEnd?: (toolName: string, outcome?: ToolOutcome) => void;
   /** Called on delta with agent identity (for squad/multi-agent sessions). */
   onAgentDelta?: (agentId: string, text: string) => void;
   /** Called on lifecycle:start with agent identity (for squad sessions). */
   onAgentStart?: (agentId: string) => void;
 }
 
+/** How a tool call went, as shown while the turn is still streaming. */
+export interface ToolOutcome {
+  success: boolean;
+  /** Redacted, size-capped excerpt of the result. */
+  summary?: string;
+  durationMs?: number;
+}
+
 export interface ResearchRouter {
   isStreaming: () => boolean;
   getRunId: () => string | null;
@@ -342,13 +350,18 @@ function routeToHandlers(
         appState.sessionToolCallCount++;
       }
       if (stream_s?.el || isBackground) handlers.onDelta(`\n\n▶ ${tool}...`);
-      handlers.onToolStart?.(tool);
+      handlers.onToolStart?.(tool, data.args as string | undefined);
     } else if (phase === 'end') {
+      handlers.onToolEnd?.(tool ?? '', {
+        success: data.success !== false,
+        summary: data.summary as string | undefined,
+        durationMs: data.duration_ms as number | undefined,
+      });
+    } else if (phase === 'output') {
       if (!isBackground && data.output) {
         const outputLen = String(data.output).length;
         appState.sessionToolResultTokens += Math.ceil(outputLen / 4) + 4;
       }
-      handlers.onToolEnd?.(tool ?? '');
     }
   } else if (stream === 'error') {
     const error = (data.message ?? data.error ?? '') as string;
@@ -16,17 +16,23 @@ import type {
   EngineMemory,
   EngineMemoryConfig,
   EngineMemoryStats,
+  AutoCapturePolicy,
+  MemoryImportReport,
   OllamaReadyStatus,
   EngineSkillStatus,
+  SkillStatusSummary,
   CommunitySkill,
   DiscoveredSkill,
   TomlSkillEntry,
+  RemoteSkillInstall,
   PawzHubEntry,
   WizardFormData,
   SkillStorageItem,
   TradeRecord,
   TradingSummary,
   TradingPolicy,
+  DexAllowance,
+  DexWalletList,
   Position,
   TtsConfig,
   EngineTask,
@@ -36,6 +42,9 @@ import type {
   EngineProjectAgent,
   EngineProjectMessage,
   BackendAgent,
+  EngineAgentDefinition,
+  EngineAgentBundle,
+  EngineAgentImportReport,
   TelegramConfig,
   TelegramStatus,
   ChannelStatus,
@@ -52,9 +61,12 @@ import type {
   BrowserConfig,
   BrowserProfile,
   ScreenshotEntry,
+  GeneratedMediaEntry,
   WorkspaceInfo,
   WorkspaceFile,
   NetworkPolicy,
+  OutboundHttpConfig,
+  WebPolitenessConfig,
   TailscaleStatus,
   TailscaleConfig,
   WebhookConfig,
@@ -73,6 +85,12 @@ import type {
   DashboardTabRow,
   DashboardWindowRow,
   TelemetryMetricRow,
+  RunTraceSpan,
+  ProviderRotationEvent,
+  ApprovalRules,
+  ToolCacheConfig,
+  ToolCacheStats,
+  ApprovalRecord,
   TelemetryDailySummary,
   TelemetryModelBreakdown,
   EngineSquad,
@@ -91,6 +109,8 @@ import type {
   ForgeDomainSummary,
   MemoryEdge,
   EmbeddingProjection,
+  EngineHaltStatus,
+  EngineEmergencyStatus,
 } from '../atoms/types';
 
 export class PawEngineClient {
@@ -155,20 +175,52 @@ export class PawEngineClient {
     return invoke<EngineChatResponse>('engine_chat_send', { request });
   }
 
+  /** Re-run the last assistant turn for the session's last user message. */
+  async chatRegenerate(
+    sessionId: string,
+    options?: Omit<EngineChatRequest, 'session_id' | 'message' | 'regenerate'>,
+  ): Promise<EngineChatResponse> {
+    return this.chatSend({ ...options, session_id: sessionId, message: '', regenerate: true });
+  }
+
+  /** Stop every run, bridge and scheduled task, and block new runs until resumed. */
+  async emergencyStop(): Promise<EngineEmergencyStatus> {
+    return invoke<EngineEmergencyStatus>('engine_emergency_stop');
+  }
+
+  /** Re-arm the engine after an emergency stop. */
+  async resume(): Promise<EngineEmergencyStatus> {
+    return invoke<EngineEmergencyStatus>('engine_resume');
+  }
+
+  async emergencyStatus(): Promise<EngineHaltStatus> {
+    return invoke<EngineHaltStatus>('engine_emergency_status');
+  }
+
   async chatAbort(sessionId: string): Promise<void> {
     return invoke<void>('engine_chat_abort', { sessionId });
   }
 
+  /** Cooperatively cancel the session's run. Resolves false if none was active. */
+  async cancelRun(sessionId: string): Promise<boolean> {
+    return invoke<boolean>('engine_cancel_run', { sessionId });
+  }
+
   async chatHistory(sessionId: string, limit?: number): Promise<EngineStoredMessage[]> {
     return invoke<EngineStoredMessage[]>('engine_chat_history', { sessionId, limit: limit ?? 200 });
   }
 
   // ── Sessions ─────────────────────────────────────────────────────────
 
-  async sessionsList(limit?: number, agentId?: string): Promise<EngineSession[]> {
+  async sessionsList(
+    limit?: number,
+    agentId?: string,
+    includeArchived = false,
+  ): Promise<EngineSession[]> {
     return invoke<EngineSession[]>('engine_sessions_list', {
       limit: limit ?? 50,
       agentId: agentId ?? null,
+      includeArchived,
     });
   }
 
@@ -180,6 +232,51 @@ export class PawEngineClient {
     return invoke('engine_session_delete', { sessionId });
   }
 
+  /** Erase a session so it can't be recovered from the database file. */
+  async sessionSecureDelete(sessionId: string, scrubMemories?: boolean): Promise<SecureDeleteReport> {
+    return invoke<SecureDeleteReport>('engine_session_secure_delete', {
+      sessionId,
+      scrubMemories: scrubMemories ?? null,
+    });
+  }
+
+  /** Fork a session at a message into a new session linked to it. */
+  async sessionFork(sessionId: string, messageId: string): Promise<EngineSession> {
+    return invoke<EngineSession>('engine_session_fork', { sessionId, messageId });
+  }
+
+  async sessionForks(sessionId: string): Promise<EngineSession[]> {
+    return invoke<EngineSession[]>('engine_session_forks', { sessionId });
+  }
+
+  async sessionSetArchived(sessionId: string, archived: boolean): Promise<void> {
+    return invoke('engine_session_set_archived', { sessionId, archived });
+  }
+
+  /** Archive sessions untouched for `olderThanDays` (defaults from the archive settings). */
+  async sessionsArchiveOld(olderThanDays?: number, summarize?: boolean): Promise<ArchiveReport> {
+    return invoke<ArchiveReport>('engine_sessions_archive_old', {
+      olderThanDays: olderThanDays ?? null,
+      summarize: summarize ?? null,
+    });
+  }
+
+  /** Find past sessions by meaning; falls back to keyword search without embeddings. */
+  async sessionsSearchSemantic(query: string, limit?: number): Promise<SessionSearchHit[]> {
+    return invoke<SessionSearchHit[]>('engine_sessions_search_semantic', {
+      query,
+      limit: limit ?? null,
+    });
+  }
+
+  async sessionArchiveGetConfig(): Promise<SessionArchiveConfig> {
+    return invoke<SessionArchiveConfig>('engine_session_archive_get_config');
+  }
+
+  async sessionArchiveSetConfig(config: SessionArchiveConfig): Promise<void> {
+    return invoke('engine_session_archive_set_config', { config });
+  }
+
   async sessionClear(sessionId: string): Promise<void> {
     return invoke('engine_session_clear', { sessionId });
   }
@@ -212,6 +309,38 @@ export class PawEngineClient {
     return invoke('engine_set_config', { config });
   }
 
+  async availableLocales(): Promise<string[]> {
+    return invoke<string[]>('engine_available_locales');
+  }
+
+  async approvalRulesGet(): Promise<ApprovalRules> {
+    return invoke<ApprovalRules>('engine_approval_rules_get');
+  }
+
+  async approvalRulesSet(rules: ApprovalRules): Promise<void> {
+    return invoke('engine_approval_rules_set', { rules });
+  }
+
+  async toolCacheGetConfig(): Promise<ToolCacheConfig> {
+    return invoke<ToolCacheConfig>('engine_tool_cache_get_config');
+  }
+
+  async toolCacheSetConfig(config: ToolCacheConfig): Promise<void> {
+    return invoke('engine_tool_cache_set_config', { config });
+  }
+
+  async toolCacheStats(): Promise<ToolCacheStats> {
+    return invoke<ToolCacheStats>('engine_tool_cache_stats');
+  }
+
+  async toolCacheFlush(): Promise<number> {
+    return invoke<number>('engine_tool_cache_flush');
+  }
+
+  async listApprovals(sessionId?: string, limit?: number): Promise<ApprovalRecord[]> {
+    return invoke<ApprovalRecord[]>('engine_list_approvals', { sessionId, limit });
+  }
+
   async upsertProvider(provider: EngineProviderConfig): Promise<void> {
     return invoke('engine_upsert_provider', { provider });
   }
@@ -226,6 +355,11 @@ export class PawEngineClient {
     return invoke('engine_list_provider_models', { providerId });
   }
 
+  /** Check a provider's key and endpoint without sending a real prompt. */
+  async testProvider(provider: EngineProviderConfig): Promise<ProviderTestResult> {
+    return invoke<ProviderTestResult>('engine_test_provider', { provider });
+  }
+
   async status(): Promise<EngineStatus> {
     return invoke<EngineStatus>('engine_status');
   }
@@ -320,6 +454,26 @@ export class PawEngineClient {
     return invoke('engine_set_memory_config', { config });
   }
 
+  async memoryGetCapturePolicy(agentId: string): Promise<AutoCapturePolicy> {
+    return invoke<AutoCapturePolicy>('engine_memory_get_capture_policy', { agentId });
+  }
+
+  async memorySetCapturePolicy(agentId: string, policy: AutoCapturePolicy): Promise<void> {
+    return invoke('engine_memory_set_capture_policy', { agentId, policy });
+  }
+
+  async memoryMergeGetConfig(): Promise<MemoryMergeConfig> {
+    return invoke<MemoryMergeConfig>('engine_memory_merge_get_config');
+  }
+
+  async memoryMergeSetConfig(config: MemoryMergeConfig): Promise<void> {
+    return invoke('engine_memory_merge_set_config', { config });
+  }
+
+  async memoryMergeRun(): Promise<MemoryMergeReport> {
+    return invoke<MemoryMergeReport>('engine_memory_merge_run');
+  }
+
   async testEmbedding(): Promise<number> {
     return invoke<number>('engine_test_embedding');
   }
@@ -345,6 +499,19 @@ export class PawEngineClient {
     return invoke('engine_memory_backfill');
   }
 
+  async memoryPruneStale(maxAgeDays?: number, minRetrievals?: number): Promise<number> {
+    return invoke<number>('engine_memory_prune_stale', { maxAgeDays, minRetrievals });
+  }
+
+  /** Returns the written path when `path` is given, otherwise the JSON export. */
+  async memoryExport(includeEmbeddings?: boolean, path?: string): Promise<string> {
+    return invoke<string>('engine_memory_export', { includeEmbeddings, path });
+  }
+
+  async memoryImport(path: string, reembed?: boolean): Promise<MemoryImportReport> {
+    return invoke<MemoryImportReport>('engine_memory_import', { path, reembed });
+  }
+
   async memoryEmbeddingProjection(limit?: number): Promise<EmbeddingProjection> {
     return invoke<EmbeddingProjection>('engine_memory_embedding_projection', { limit });
   }
@@ -408,6 +575,10 @@ export class PawEngineClient {
     return invoke<EngineSkillStatus[]>('engine_skills_list');
   }
 
+  async skillsStatus(): Promise<SkillStatusSummary[]> {
+    return invoke<SkillStatusSummary[]>('engine_skills_status');
+  }
+
   async skillSetEnabled(skillId: string, enabled: boolean): Promise<void> {
     return invoke('engine_skill_set_enabled', { skillId, enabled });
   }
@@ -424,6 +595,14 @@ export class PawEngineClient {
     return invoke('engine_set_onboarding_complete');
   }
 
+  async onboardingStatus(): Promise<OnboardingStatus> {
+    return invoke<OnboardingStatus>('engine_onboarding_status');
+  }
+
+  async onboardingStep(step: OnboardingStep): Promise<OnboardingStepStatus> {
+    return invoke<OnboardingStepStatus>('engine_onboarding_step', { step });
+  }
+
   async skillSetCredential(skillId: string, key: string, value: string): Promise<void> {
     return invoke('engine_skill_set_credential', { skillId, key, value });
   }
@@ -448,6 +627,15 @@ export class PawEngineClient {
     return invoke('engine_skill_set_instructions', { skillId, instructions });
   }
 
+  /** User override of the built-in tools a third-party skill may use (null = none). */
+  async skillGetPermittedTools(skillId: string): Promise<string[] | null> {
+    return invoke<string[] | null>('engine_skill_get_permitted_tools', { skillId });
+  }
+
+  async skillSetPermittedTools(skillId: string, tools: string[] | null): Promise<void> {
+    return invoke('engine_skill_set_permitted_tools', { skillId, tools });
+  }
+
   // ── Community Skills (skills.sh) ──────────────────────────────────
 
   async communitySkillsList(): Promise<CommunitySkill[]> {
@@ -492,6 +680,12 @@ export class PawEngineClient {
     return invoke('engine_toml_skill_uninstall', { skillId });
   }
 
+  /** Install from a URL or `owner/repo[/dir]`. Returns a preview (installed=false)
+   *  when the skill declares credentials or MCP and `confirmed` is not set. */
+  async installCommunitySkill(source: string, confirmed?: boolean): Promise<RemoteSkillInstall> {
+    return invoke<RemoteSkillInstall>('engine_install_community_skill', { source, confirmed });
+  }
+
   // ── FORGE (Certification & Skill Trees) ───────────────────────────
 
   async forgeCertSummary(agentId: string): Promise<ForgeCertSummary> {
@@ -702,6 +896,18 @@ export class PawEngineClient {
     return invoke<number>('engine_purge_old_metrics', { cutoffDate });
   }
 
+  async getRunTrace(sessionId: string, runId: string): Promise<RunTraceSpan[]> {
+    return invoke<RunTraceSpan[]>('engine_get_run_trace', { sessionId, runId });
+  }
+
+  async listRunTraces(sessionId: string): Promise<string[]> {
+    return invoke<string[]>('engine_list_run_traces', { sessionId });
+  }
+
+  async providerRotationEvents(limit?: number): Promise<ProviderRotationEvent[]> {
+    return invoke<ProviderRotationEvent[]>('engine_provider_rotation_events', { limit });
+  }
+
   // ── PawzHub Registry (Phase F.4) ─────────────────────────────────────
 
   async pawzhubSearch(query: string): Promise<PawzHubEntry[]> {
@@ -750,6 +956,47 @@ export class PawEngineClient {
     return invoke('engine_trading_policy_set', { policy });
   }
 
+  async dexAllowanceGet(): Promise<DexAllowance> {
+    return invoke<DexAllowance>('engine_dex_allowance_get');
+  }
+
+  async dexAllowanceSet(allowance: DexAllowance): Promise<void> {
+    return invoke('engine_dex_allowance_set', { allowance });
+  }
+
+  /**
+   * DANGER: returns a DEX wallet's raw private key (`wallet` = named wallet
+   * id or label; omit for the default wallet). Requires the exact
+   * confirmation phrase; rate-limited and audited. Never show it to an agent.
+   */
+  async dexExportPrivateKey(confirmationPhrase: string, wallet?: string): Promise<string> {
+    return invoke<string>('engine_dex_export_private_key', { confirmationPhrase, wallet });
+  }
+
+  /**
+   * Restore a DEX wallet from a private key — the default wallet, or the
+   * named wallet `wallet`. Returns the wallet address.
+   */
+  async dexImportPrivateKey(
+    privateKey: string,
+    replaceExisting = false,
+    wallet?: string,
+  ): Promise<string> {
+    return invoke<string>('engine_dex_import_private_key', {
+      privateKey,
+      replaceExisting,
+      wallet,
+    });
+  }
+
+  async dexWalletsList(): Promise<DexWalletList> {
+    return invoke<DexWalletList>('engine_dex_wallets_list');
+  }
+
+  async dexSetActiveWallet(wallet: string | null): Promise<void> {
+    return invoke('engine_dex_set_active_wallet', { wallet });
+  }
+
   async positionsList(status?: string): Promise<Position[]> {
     return invoke<Position[]>('engine_positions_list', { status: status ?? null });
   }
@@ -1135,6 +1382,9 @@ export class PawEngineClient {
   async whatsappRemoveUser(userId: string): Promise<void> {
     return invoke('engine_whatsapp_remove_user', { userId });
   }
+  async whatsappRotateWebhookSecret(): Promise<void> {
+    return invoke('engine_whatsapp_rotate_webhook_secret');
+  }
 
   // ── Discourse ────────────────────────────────────────────────────────
 
@@ -1211,6 +1461,50 @@ export class PawEngineClient {
     return invoke('engine_delete_agent', { agentId });
   }
 
+  async agentConfigList(): Promise<EngineAgentDefinition[]> {
+    return invoke<EngineAgentDefinition[]>('engine_agent_config_list');
+  }
+
+  async agentConfigGet(agentId?: string): Promise<EngineAgentDefinition | null> {
+    return invoke<EngineAgentDefinition | null>('engine_agent_config_get', {
+      agentId: agentId ?? 'default',
+    });
+  }
+
+  async agentConfigSave(agent: EngineAgentDefinition): Promise<void> {
+    return invoke('engine_agent_config_save', { agent });
+  }
+
+  async agentConfigDelete(agentId: string): Promise<void> {
+    return invoke('engine_agent_config_delete', { agentId });
+  }
+
+  async cloneAgent(
+    sourceId: string,
+    newName: string,
+    includeMemories = false,
+  ): Promise<EngineAgentDefinition> {
+    return invoke<EngineAgentDefinition>('engine_clone_agent', {
+      sourceId,
+      newName,
+      includeMemories,
+    });
+  }
+
+  async exportAgent(agentId: string, includeTomlSkills = true): Promise<EngineAgentBundle> {
+    return invoke<EngineAgentBundle>('engine_export_agent', { agentId, includeTomlSkills });
+  }
+
+  async importAgent(
+    bundle: EngineAgentBundle,
+    confirmElevatedSkills = false,
+  ): Promise<EngineAgentImportReport> {
+    return invoke<EngineAgentImportReport>('engine_import_agent', {
+      bundle,
+      confirmElevatedSkills,
+    });
+  }
+
   async projectMessages(projectId: string, limit?: number): Promise<EngineProjectMessage[]> {
     return invoke<EngineProjectMessage[]>('engine_project_messages', { projectId, limit });
   }
@@ -1247,6 +1541,14 @@ export class PawEngineClient {
     return invoke<ScreenshotEntry>('engine_screenshot_get', { filename });
   }
 
+  async generatedImageGet(path: string): Promise<GeneratedMediaEntry> {
+    return invoke<GeneratedMediaEntry>('engine_generated_image_get', { path });
+  }
+
+  async generatedAudioGet(path: string): Promise<GeneratedMediaEntry> {
+    return invoke<GeneratedMediaEntry>('engine_generated_audio_get', { path });
+  }
+
   async screenshotDelete(filename: string): Promise<void> {
     return invoke('engine_screenshot_delete', { filename });
   }
@@ -1279,6 +1581,22 @@ export class PawEngineClient {
     return invoke<[boolean, string]>('engine_network_check_url', { url });
   }
 
+  async outboundHttpGet(): Promise<OutboundHttpConfig> {
+    return invoke<OutboundHttpConfig>('engine_outbound_http_get');
+  }
+
+  async outboundHttpSet(config: OutboundHttpConfig): Promise<void> {
+    return invoke('engine_outbound_http_set', { config });
+  }
+
+  async webPolitenessGet(): Promise<WebPolitenessConfig> {
+    return invoke<WebPolitenessConfig>('engine_web_politeness_get');
+  }
+
+  async webPolitenessSet(config: WebPolitenessConfig): Promise<void> {
+    return invoke('engine_web_politeness_set', { config });
+  }
+
   // ── Tailscale (Remote Access) ──────────────────────────────────────
 
   async tailscaleStatus(): Promise<TailscaleStatus> {
@@ -1529,15 +1847,47 @@ export class PawEngineClient {
     return invoke('engine_squad_remove_member', { squadId, agentId });
   }
 
+  // ── Tool-result context policy ─────────────────────────────────────
+
+  async toolResultPolicyGet(): Promise<ToolResultPolicy> {
+    return invoke<ToolResultPolicy>('engine_tool_result_policy_get');
+  }
+
+  async toolResultPolicySet(policy: ToolResultPolicy): Promise<void> {
+    return invoke('engine_tool_result_policy_set', { policy });
+  }
+
   // ── Storage Paths ──────────────────────────────────────────────────
 
   async storageGetPaths(): Promise<StoragePaths> {
     return invoke<StoragePaths>('engine_storage_get_paths');
   }
 
+  async fileLogGetConfig(): Promise<FileLogConfig> {
+    return invoke<FileLogConfig>('engine_file_log_get_config');
+  }
+
+  async fileLogSetConfig(config: FileLogConfig): Promise<void> {
+    return invoke('engine_file_log_set_config', { config });
+  }
+
   async storageSetDataRoot(path: string | null): Promise<void> {
     return invoke('engine_storage_set_data_root', { path });
   }
+
+  /** Copy all data to `newRoot` and switch to it. Progress arrives as
+   *  `storage-migration-progress` events. A completed migration restarts
+   *  the app; only dry runs resolve with a report. */
+  async storageMigrateData(
+    newRoot: string,
+    options: { removeOld?: boolean; dryRun?: boolean } = {},
+  ): Promise<StorageMigrationReport> {
+    return invoke<StorageMigrationReport>('engine_storage_migrate_data', {
+      newRoot,
+      removeOld: options.removeOld ?? false,
+      dryRun: options.dryRun ?? false,
+    });
+  }
 }
 
 /** Storage paths returned by the engine. */
@@ -1553,7 +1903,101 @@ export interface StoragePaths {
   skills_size: number;
   browser_dir: string;
   browser_size: number;
+  /** Rotating, secret-scrubbed log file to attach to bug reports. */
+  log_file: string;
+  log_size: number;
   workspace_path: string | null;
+  /** Free space on the data root's filesystem; null when unknown. */
+  available_bytes: number | null;
+}
+
+/** How old tool outputs are sent back to the model (storage is unchanged). */
+export interface ToolResultPolicy {
+  mode: 'keep' | 'summarize' | 'drop';
+  /** User turns after which a result counts as old. */
+  after_turns: number;
+  /** Results at or below this size are always kept. */
+  min_chars: number;
+  excerpt_chars: number;
+}
+
+export interface SessionArchiveConfig {
+  /** Archive stale sessions in the background. */
+  enabled: boolean;
+  older_than_days: number;
+  /** Store a one-line summary with each archived session. */
+  summarize: boolean;
+  interval_hours: number;
+}
+
+export interface MemoryMergeConfig {
+  /** Merge related memories in the background. */
+  enabled: boolean;
+  /** Cosine similarity (0.5–1) a memory needs to join a cluster. */
+  similarity_threshold: number;
+  max_cluster_size: number;
+  /** Clusters merged per run; each costs one LLM call. */
+  max_clusters_per_run: number;
+  interval_hours: number;
+  /** Model for merge calls; null uses the cheap model, else the default. */
+  model: string | null;
+}
+
+export interface MemoryMergeReport {
+  clusters_found: number;
+  clusters_merged: number;
+  memories_replaced: number;
+  clusters_skipped: number;
+  merged_ids: string[];
+}
+
+export interface ArchiveReport {
+  archived: number;
+  summarized: number;
+  session_ids: string[];
+}
+
+export interface SecureDeleteReport {
+  messages_erased: number;
+  memories_erased: number;
+  workspace_items_erased: number;
+  /** false when VACUUM couldn't run; the rows are still erased. */
+  vacuumed: boolean;
+}
+
+export interface SessionSearchHit {
+  session_id: string;
+  label?: string;
+  score: number;
+  /** Best-matching part of the transcript. */
+  snippet: string;
+  updated_at: string;
+  /** false when the hit came from the keyword fallback. */
+  semantic: boolean;
+}
+
+export interface FileLogConfig {
+  enabled: boolean;
+  level: 'error' | 'warn' | 'info' | 'debug' | 'trace';
+  max_file_bytes: number;
+  max_files: number;
+}
+
+export interface StorageMigrationProgress {
+  stage: 'plan' | 'database' | 'files' | 'verify' | 'switch' | 'cleanup' | 'done';
+  item: string;
+  copied_bytes: number;
+  total_bytes: number;
+}
+
+export interface StorageMigrationReport {
+  old_root: string;
+  new_root: string;
+  dry_run: boolean;
+  total_bytes: number;
+  files_copied: number;
+  available_bytes: number | null;
+  cleanup_scheduled: boolean;
 }
 
 /** A single Gmail message returned by engine_gmail_inbox. */
@@ -1566,6 +2010,34 @@ export interface GmailMessage {
   read: boolean;
 }
 
+export type ProviderFailure = 'invalid_key' | 'connectivity' | 'not_found' | 'upstream';
+
+export interface ProviderTestResult {
+  ok: boolean;
+  reason: ProviderFailure | null;
+  message: string;
+  /** Models the provider listed, when it supports listing. */
+  models: number | null;
+}
+
+export type OnboardingStep = 'provider' | 'model' | 'embeddings' | 'skills' | 'finish';
+
+export interface OnboardingStepStatus {
+  step: OnboardingStep;
+  complete: boolean;
+  /** Recommended but not required to finish setup. */
+  optional: boolean;
+  detail: string;
+}
+
+export interface OnboardingStatus {
+  steps: OnboardingStepStatus[];
+  next_step: OnboardingStep | null;
+  next_action: string;
+  /** All required steps are complete. */
+  ready: boolean;
+}
+
 /** Create a new engine client instance — useful for testing or custom wiring. */
 export function createPawEngine(): PawEngineClient {
   return new PawEngineClient();
@@ -43,6 +43,7 @@ import {
   appendThinkingDelta as rendererAppendThinking,
   scrollToBottom as rendererScrollToBottom,
   showToolStep,
+  showToolOutcome,
   clearToolStep,
   type RenderOpts,
 } from '../molecules/chat_renderer';
@@ -53,6 +54,7 @@ import {
 } from '../molecules/token_meter';
 import { speakMessage, autoSpeakIfEnabled, type TtsState } from '../molecules/tts';
 import { createSessionManager, type SessionManager } from '../molecules/chat_sessions';
+import type { ToolOutcome } from '../molecules/event_bus';
 import {
   renderAttachmentPreview,
   clearPendingAttachments,
@@ -165,7 +167,11 @@ function teardownStream(sessionKey: string, reason: string): void {
   console.debug(
     `[chat] Tearing down stream for ${sessionKey.slice(0, 12) || '(empty)'}: ${reason}`,
   );
-  pawEngine.chatAbort(sessionKey).catch(() => {});
+  // Prefer cooperative cancel (keeps the partial turn in history); hard-abort as fallback
+  pawEngine
+    .cancelRun(sessionKey)
+    .then((cancelled) => (cancelled ? undefined : pawEngine.chatAbort(sessionKey)))
+    .catch(() => pawEngine.chatAbort(sessionKey).catch(() => {}));
   if (stream.resolve) {
     stream.resolve(stream.content || `(${reason})`);
     stream.resolve = null;
@@ -324,15 +330,17 @@ export function appendThinkingDelta(text: string): void {
 }
 
 /** Show a tool-step indicator in the streaming message. */
-export function handleToolStart(toolName: string): void {
+export function handleToolStart(toolName: string, argsSummary?: string): void {
   const chatMessages = $('chat-messages');
-  if (chatMessages) showToolStep(chatMessages, toolName);
+  if (chatMessages) showToolStep(chatMessages, toolName, argsSummary);
 }
 
-/** Clear the tool-step indicator from the streaming message. */
-export function handleToolEnd(_toolName: string): void {
+/** Replace the tool-step indicator with the outcome, or clear it. */
+export function handleToolEnd(toolName: string, outcome?: ToolOutcome): void {
   const chatMessages = $('chat-messages');
-  if (chatMessages) clearToolStep(chatMessages);
+  if (!chatMessages) return;
+  if (outcome) showToolOutcome(chatMessages, toolName, outcome);
+  else clearToolStep(chatMessages);
 }
 
 export function finalizeStreaming(
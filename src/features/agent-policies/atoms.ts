@@ -90,6 +90,7 @@ export const ALL_TOOLS = [
   'rest_api_call',
   'webhook_send',
   'image_generate',
+  'speak',
   // Trading: Coinbase
   'coinbase_prices',
   'coinbase_balance',
@@ -106,10 +107,14 @@ export const ALL_TOOLS = [
   'sol_transfer',
   // Trading: EVM DEX (Uniswap)
   'dex_wallet_create',
+  'dex_wallet_list',
+  'dex_switch_chain',
   'dex_balance',
   'dex_quote',
   'dex_swap',
   'dex_portfolio',
+  'dex_tx_history',
+  'dex_sign_typed_data',
   'dex_token_info',
   'dex_check_token',
   'dex_search_token',
@@ -135,6 +140,18 @@ export const ALL_TOOLS = [
   'skill_store_get',
   'skill_store_list',
   'skill_store_delete',
+  'skill_state_get',
+  'skill_state_set',
+  'skill_state_delete',
+  'note_write',
+  'note_read',
+  'note_list',
+  'task_add',
+  'task_complete',
+  'task_list',
+  'reminder_set',
+  'reminder_list',
+  'reminder_cancel',
   // Canvas (internal UI)
   'canvas_push',
   'canvas_update',
@@ -165,8 +182,10 @@ export const SAFE_TOOLS: readonly string[] = [
   'sol_balance',
   'sol_portfolio',
   'sol_token_info',
+  'dex_wallet_list',
   'dex_balance',
   'dex_portfolio',
+  'dex_tx_history',
   'dex_token_info',
   'dex_check_token',
   'dex_search_token',
@@ -174,6 +193,10 @@ export const SAFE_TOOLS: readonly string[] = [
   'telegram_read',
   'request_tools',
   'list_tasks',
+  'task_list',
+  'reminder_list',
+  'note_read',
+  'note_list',
   'agent_read_messages',
   'list_squads',
   'skill_search',
@@ -206,8 +229,10 @@ export const HIGH_RISK_TOOLS: readonly string[] = [
   'sol_wallet_create',
   'dex_swap',
   'dex_transfer',
+  'dex_sign_typed_data',
   'dex_wallet_create',
   'image_generate',
+  'speak',
   'soul_write',
   'update_profile',
   'create_agent',
@@ -403,6 +403,49 @@ describe('checkRateLimit — edge cases', () => {
   });
 });
 
+describe('checkRateLimit — window types', () => {
+  const MINUTE = 60_000;
+  // Two actions just before a window boundary, two just after
+  const burst = [MINUTE - 1_000, MINUTE - 500, MINUTE, MINUTE + 500];
+
+  it('fixed windows allow a burst across the boundary', () => {
+    resetRateLimit('fixed-svc');
+    const cfg: RateLimitConfig = {
+      service: 'fixed-svc',
+      maxActions: 2,
+      windowMinutes: 1,
+      windowType: 'fixed',
+    };
+    const allowed = burst.map((t) => checkRateLimit('fixed-svc', cfg, t).allowed);
+    expect(allowed).toEqual([true, true, true, true]);
+    resetRateLimit('fixed-svc');
+  });
+
+  it('sliding windows block the same burst', () => {
+    resetRateLimit('sliding-svc');
+    const cfg: RateLimitConfig = {
+      service: 'sliding-svc',
+      maxActions: 2,
+      windowMinutes: 1,
+      windowType: 'sliding',
+    };
+    const allowed = burst.map((t) => checkRateLimit('sliding-svc', cfg, t).allowed);
+    expect(allowed).toEqual([true, true, false, false]);
+    // The first action expires a full window after it happened
+    expect(checkRateLimit('sliding-svc', cfg, 2 * MINUTE - 1_000).allowed).toBe(true);
+    expect(checkRateLimit('sliding-svc', cfg, 2 * MINUTE - 900).allowed).toBe(false);
+    resetRateLimit('sliding-svc');
+  });
+
+  it('defaults to sliding', () => {
+    resetRateLimit('default-svc');
+    const cfg: RateLimitConfig = { service: 'default-svc', maxActions: 2, windowMinutes: 1 };
+    const allowed = burst.map((t) => checkRateLimit('default-svc', cfg, t).allowed);
+    expect(allowed).toEqual([true, true, false, false]);
+    resetRateLimit('default-svc');
+  });
+});
+
 describe('bumpRateLimit — edge cases', () => {
   it('no-ops on non-existent service window', () => {
     // Should not throw
@@ -94,10 +94,19 @@ export function riskMeta(level: IntegrationRiskLevel): {
 
 // ── Rate limits ────────────────────────────────────────────────────────
 
+/**
+ * How a rate-limit window moves. `sliding` counts actions in the last
+ * `windowMinutes`, continuously; `fixed` counts per clock-aligned window
+ * and resets at each boundary, so a burst can straddle two windows.
+ */
+export type RateLimitWindowType = 'sliding' | 'fixed';
+
 export interface RateLimitConfig {
   service: string;
   maxActions: number;
   windowMinutes: number;
+  /** Defaults to `sliding`. */
+  windowType?: RateLimitWindowType;
 }
 
 export const DEFAULT_RATE_LIMITS: RateLimitConfig[] = [
@@ -139,31 +148,56 @@ export interface RateLimitWindow {
   service: string;
   count: number;
   windowStart: number; // epoch ms
+  /** Times of counted actions, oldest first (sliding windows only). */
+  timestamps: number[];
 }
 
 const _windows: Map<string, RateLimitWindow> = new Map();
 
-/** Record an action and check if rate limited. Returns remaining quota. */
+/**
+ * Record an action and check if rate limited. Returns remaining quota.
+ * Denied actions are not counted against the window.
+ */
 export function checkRateLimit(
   service: string,
   config?: RateLimitConfig,
+  now: number = Date.now(),
 ): { allowed: boolean; remaining: number; limit: number } {
   const limit = config ?? getRateLimit(service);
-  const now = Date.now();
   const windowMs = limit.windowMinutes * 60_000;
+  const sliding = (limit.windowType ?? 'sliding') === 'sliding';
 
   let window = _windows.get(service);
-  if (!window || now - window.windowStart > windowMs) {
-    window = { service, count: 0, windowStart: now };
+  if (!window) {
+    window = { service, count: 0, windowStart: now, timestamps: [] };
     _windows.set(service, window);
   }
 
-  window.count += 1;
-  const remaining = Math.max(0, limit.maxActions - window.count);
+  if (sliding) {
+    // Expire actions older than the window
+    while (window.timestamps.length > 0 && window.timestamps[0] <= now - windowMs) {
+      window.timestamps.shift();
+    }
+    window.count = window.timestamps.length;
+    window.windowStart = window.timestamps[0] ?? now;
+  } else {
+    const boundary = Math.floor(now / windowMs) * windowMs;
+    if (window.windowStart !== boundary) {
+      window.windowStart = boundary;
+      window.count = 0;
+    }
+    window.timestamps = [];
+  }
+
+  const allowed = window.count < limit.maxActions;
+  if (allowed) {
+    window.count += 1;
+    if (sliding) window.timestamps.push(now);
+  }
 
   return {
-    allowed: window.count <= limit.maxActions,
-    remaining,
+    allowed,
+    remaining: Math.max(0, limit.maxActions - window.count),
     limit: limit.maxActions,
   };
 }
@@ -178,6 +212,8 @@ export function bumpRateLimit(service: string, extra: number): void {
   const window = _windows.get(service);
   if (window) {
     window.count = Math.max(0, window.count - extra);
+    // Sliding windows forget their oldest actions
+    window.timestamps.splice(0, window.timestamps.length - window.count);
   }
 }
 
@@ -7,6 +7,7 @@ export {
   type IntegrationRiskLevel,
   type IntegrationAction,
   type RateLimitConfig,
+  type RateLimitWindowType,
   type RateLimitWindow,
   type AgentServicePermission,
   type AccessLevel,
@@ -349,6 +349,20 @@ export function wireGuardrailEvents(container: HTMLElement): void {
   });
 }
 
+/** Which audit log to export as CSV. */
+export type GuardrailExportKind = 'credentials' | 'approvals' | 'actions';
+
+/**
+ * Export an audit log as CSV text. Dates are YYYY-MM-DD or RFC 3339
+ * (inclusive); `service` is a service id, or a tool name/domain for approvals.
+ */
+export function exportGuardrailsCsv(
+  kind: GuardrailExportKind,
+  filters: { from?: string; to?: string; service?: string } = {},
+): Promise<string> {
+  return invoke<string>('engine_guardrails_export_csv', { kind, ...filters });
+}
+
 // ── Internal helpers ───────────────────────────────────────────────────
 
 function _esc(s: string): string {
@@ -11,6 +11,7 @@ import { escHtml, populateModelSelect, promptModal, icon } from './components/he
 import { showToast } from './components/toast';
 import { initTheme, getTheme, setTheme } from './components/molecules/theme';
 import { initHILModal } from './components/molecules/hil_modal';
+import { initEmergencyStop } from './components/molecules/emergency_stop';
 import {
   initChatListeners,
   switchToAgent,
@@ -89,6 +90,7 @@ interface TauriWindow {
 const tauriWindow = window as unknown as TauriWindow;
 const listen = tauriWindow.__TAURI__?.event?.listen;
 let unlistenTaskUpdated: (() => void) | null = null;
+let unlistenReminderDue: (() => void) | null = null;
 
 // ── Global error handlers ──────────────────────────────────────────────────────
 function crashLog(msg: string) {
@@ -170,6 +172,15 @@ async function connectEngine(): Promise<boolean> {
       }).then((fn) => {
         unlistenTaskUpdated = fn;
       });
+      if (unlistenReminderDue) {
+        unlistenReminderDue();
+        unlistenReminderDue = null;
+      }
+      listen<{ id: number; message: string; missed: boolean }>('reminder-due', (event) => {
+        showToast(event.payload.message, event.payload.missed ? 'warning' : 'info', 15000);
+      }).then((fn) => {
+        unlistenReminderDue = fn;
+      });
     }
 
     pawEngine
@@ -503,6 +514,7 @@ document.addEventListener('DOMContentLoaded', async () => {
     initChatListeners();
     // mountInbox deferred — must run after connectEngine sets wsConnected
     initHILModal();
+    initEmergencyStop();
     initCommandPalette({
       getAgents: AgentsModule.getAgents,
       switchView,
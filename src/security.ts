@@ -912,6 +912,7 @@ const ALLOWED_CONFIG_KEYS = new Set([
   'default_model',
   'daily_budget_usd',
   'max_tool_rounds',
+  'max_tool_iterations',
   'max_tokens',
   'temperature',
   'system_prompt',
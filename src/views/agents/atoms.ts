@@ -100,6 +100,7 @@ export const TOOL_GROUPS: {
       { id: 'rest_api_call', name: 'REST API', desc: 'Call REST APIs' },
       { id: 'webhook_send', name: 'Webhook', desc: 'Send webhooks' },
       { id: 'image_generate', name: 'Image Generate', desc: 'Generate images' },
+      { id: 'speak', name: 'Speak', desc: 'Text to speech (ElevenLabs)' },
     ],
   },
 ];
@@ -1,7 +1,7 @@
 // editor.ts — Agent editor modal + community skills management
 // Depends on: atoms, creator (EditorCallbacks), agent-policies, engine, toast
 
-import { pawEngine, type CommunitySkill } from '../../engine';
+import { pawEngine, type CommunitySkill, type EngineAutonomyLevel } from '../../engine';
 import {
   POLICY_PRESETS,
   type ToolPolicy,
@@ -166,6 +166,16 @@ function buildEditorHtml(agent: Agent, availableModels: { id: string; name: stri
 
         <!-- Advanced Tab -->
         <div class="agent-tab-content" id="tab-advanced">
+          <div class="form-group">
+            <label class="form-label">Autonomy Level</label>
+            <select class="form-input" id="agent-edit-autonomy">
+              <option value="manual">Manual — ask before anything that makes changes</option>
+              <option value="assisted" selected>Assisted — local changes run, external actions ask</option>
+              <option value="autonomous">Autonomous — only large financial actions ask</option>
+            </select>
+            <div class="form-hint">Sets approval prompts and how many tool calls one run may make. Approval rules in Settings still apply.</div>
+          </div>
+
           <!-- Phase A: Auto-Approve All Tools -->
           <div class="form-group">
             <label class="form-label" style="display:flex;align-items:center;gap:8px;cursor:pointer">
@@ -351,6 +361,8 @@ function saveAgentEdits(
   agent.systemPrompt = systemPrompt;
   agent.autoApproveAll =
     (modal.querySelector('#agent-edit-autoapprove') as HTMLInputElement)?.checked ?? false;
+  const autonomyLevel = (modal.querySelector('#agent-edit-autonomy') as HTMLSelectElement)
+    ?.value as EngineAutonomyLevel | undefined;
 
   setAgentPolicy(agent.id, newPolicy);
   cbs.onUpdated();
@@ -364,12 +376,33 @@ function saveAgentEdits(
       system_prompt: agent.systemPrompt,
       capabilities: isAllSelected ? [] : selectedTools,
     })
+    // After createAgent, which rewrites the stored definition
+    .then(() => (autonomyLevel ? saveAgentAutonomy(agent.id, autonomyLevel) : undefined))
     .catch((e) => console.warn('[agents] Backend update failed:', e));
 
   close();
   showToast('Changes saved', 'success');
 }
 
+/** Show the stored autonomy level in the editor. */
+async function loadAgentAutonomy(modal: HTMLElement, agentId: string): Promise<void> {
+  const select = modal.querySelector('#agent-edit-autonomy') as HTMLSelectElement | null;
+  if (!select) return;
+  try {
+    const def = await pawEngine.agentConfigGet(agentId);
+    select.value = def?.autonomy?.level ?? 'assisted';
+  } catch (e) {
+    console.warn('[agents] Failed to load autonomy level:', e);
+  }
+}
+
+/** Persist the autonomy level, keeping any per-tool overrides. */
+async function saveAgentAutonomy(agentId: string, level: EngineAutonomyLevel): Promise<void> {
+  const def = await pawEngine.agentConfigGet(agentId);
+  if (!def || (def.autonomy?.level ?? 'assisted') === level) return;
+  await pawEngine.agentConfigSave({ ...def, autonomy: { ...def.autonomy, level } });
+}
+
 /** Wire boundary add/edit/remove UI and perform initial render */
 function wireEditorBoundaries(modal: HTMLElement, boundaries: string[]): void {
   const renderBoundaries = () => {
@@ -433,6 +466,8 @@ export function openAgentEditor(agentId: string, cbs: EditorCallbacks) {
   // Load FORGE certification data for this agent
   loadAgentForgeData(modal, agentId);
 
+  loadAgentAutonomy(modal, agentId);
+
   wireToolPolicyUI(modal, allToolIds, agent.id);
 
   // Tab switching
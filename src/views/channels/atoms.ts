@@ -582,10 +582,11 @@ export const CHANNEL_SETUPS: ChannelSetupDef[] = [
         label: 'Bind Address',
         type: 'select',
         options: [
-          { value: '0.0.0.0', label: '0.0.0.0 (LAN accessible)' },
           { value: '127.0.0.1', label: '127.0.0.1 (localhost only)' },
+          { value: '0.0.0.0', label: '0.0.0.0 (LAN accessible)' },
         ],
-        defaultValue: '0.0.0.0',
+        defaultValue: '127.0.0.1',
+        hint: 'LAN access exposes the chat to your network. Set up TLS before enabling it.',
       },
       {
         key: 'accessToken',
@@ -594,6 +595,18 @@ export const CHANNEL_SETUPS: ChannelSetupDef[] = [
         placeholder: 'Auto-generated if empty',
         hint: 'Share this token with friends so they can connect',
       },
+      {
+        key: 'tunnel',
+        label: 'Public Link',
+        type: 'select',
+        options: [
+          { value: 'none', label: 'Off (this machine / LAN only)' },
+          { value: 'cloudflared', label: 'Cloudflare quick tunnel (cloudflared)' },
+          { value: 'ngrok', label: 'ngrok' },
+        ],
+        defaultValue: 'none',
+        hint: 'Share beyond your network without port-forwarding. Needs the tunnel tool installed.',
+      },
       {
         key: 'pageTitle',
         label: 'Page Title',
@@ -612,15 +625,48 @@ export const CHANNEL_SETUPS: ChannelSetupDef[] = [
         ],
         defaultValue: 'open',
       },
+      {
+        key: 'voiceInput',
+        label: 'Voice messages',
+        type: 'toggle',
+        defaultValue: false,
+        hint: 'Adds a mic button. Browsers only allow the mic on localhost or HTTPS.',
+      },
+      {
+        key: 'maxVoiceSeconds',
+        label: 'Max Voice Message Length (seconds)',
+        type: 'text',
+        placeholder: '60',
+        defaultValue: '60',
+      },
+      {
+        key: 'sttBaseUrl',
+        label: 'Speech-to-Text URL (optional)',
+        type: 'text',
+        placeholder: 'http://localhost:8000/v1',
+        hint: 'Whisper-compatible endpoint. Leave blank to use your OpenAI provider.',
+      },
+      {
+        key: 'sttApiKey',
+        label: 'Speech-to-Text API Key (optional)',
+        type: 'password',
+        placeholder: '',
+        sensitive: true,
+      },
       { key: 'agentId', label: 'Agent ID (optional)', type: 'text', placeholder: '' },
     ],
     buildConfig: (v) => ({
       port: parseInt(v.port as string) || 3939,
-      bind_address: (v.bindAddress as string) || '0.0.0.0',
+      bind_address: (v.bindAddress as string) || '127.0.0.1',
       access_token: (v.accessToken as string) || '',
       page_title: (v.pageTitle as string) || 'Paw Chat',
       enabled: true,
       dm_policy: (v.dmPolicy as string) || 'open',
+      tunnel: (v.tunnel as string) || 'none',
+      voice_input: !!v.voiceInput,
+      max_voice_seconds: parseInt(v.maxVoiceSeconds as string) || 60,
+      stt_base_url: (v.sttBaseUrl as string) || undefined,
+      stt_api_key: (v.sttApiKey as string) || undefined,
     }),
   },
   {
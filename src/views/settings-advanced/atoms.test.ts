@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { PROVIDER_KINDS, DEFAULT_BASE_URLS, POPULAR_MODELS } from './atoms';
+import { PROVIDER_KINDS, DEFAULT_BASE_URLS, POPULAR_MODELS, localeLabel } from './atoms';
 
 // ── PROVIDER_KINDS ─────────────────────────────────────────────────────────
 
@@ -73,3 +73,12 @@ describe('POPULAR_MODELS', () => {
     expect(POPULAR_MODELS.custom).toEqual([]);
   });
 });
+
+// ── localeLabel ────────────────────────────────────────────────────────────
+
+describe('localeLabel', () => {
+  it('names known languages and passes through invalid codes', () => {
+    expect(localeLabel('es')).not.toBe('');
+    expect(localeLabel('not a locale!')).toBe('not a locale!');
+  });
+});
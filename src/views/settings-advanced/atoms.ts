@@ -40,3 +40,12 @@ export const POPULAR_MODELS: Record<string, string[]> = {
   openrouter: ['meta-llama/llama-3.1-405b-instruct', 'anthropic/claude-sonnet-4-6'],
   custom: [],
 };
+
+/** Display name for a locale code ("es" → "Spanish"), or the code itself. */
+export function localeLabel(code: string): string {
+  try {
+    return new Intl.DisplayNames(undefined, { type: 'language' }).of(code) ?? code;
+  } catch {
+    return code;
+  }
+}
@@ -9,10 +9,11 @@ import {
   selectInput,
   textInput,
   numberInput,
+  toggleSwitch,
   saveReloadButtons,
 } from '../settings-config';
 import { $ } from '../../components/helpers';
-import { PROVIDER_KINDS, DEFAULT_BASE_URLS, POPULAR_MODELS } from './atoms';
+import { PROVIDER_KINDS, DEFAULT_BASE_URLS, POPULAR_MODELS, localeLabel } from './atoms';
 
 // ── Render ──────────────────────────────────────────────────────────────────
 
@@ -418,6 +419,19 @@ export async function loadAdvancedSettings() {
     roundsRow.appendChild(roundsInp);
     engSection.appendChild(roundsRow);
 
+    const iterationsRow = formRow(
+      'Max Tool Calls per Run',
+      'Total tool calls one run may make before the agent is told to wrap up (0 = no limit)',
+    );
+    const iterationsInp = numberInput(config.max_tool_iterations ?? 100, {
+      min: 0,
+      max: 1000,
+      placeholder: '100',
+    });
+    iterationsInp.style.maxWidth = '120px';
+    iterationsRow.appendChild(iterationsInp);
+    engSection.appendChild(iterationsRow);
+
     const timeoutRow = formRow('Tool Timeout (seconds)', 'Max seconds for a single tool execution');
     const timeoutInp = numberInput(config.tool_timeout_secs, {
       min: 5,
@@ -430,7 +444,7 @@ export async function loadAdvancedSettings() {
 
     const concurrencyRow = formRow(
       'Max Concurrent Runs',
-      'How many agent runs (chat + cron + tasks) can execute in parallel. Chat always gets priority. Increase if you have multiple providers or a high rate limit.',
+      'How many agent runs (chat + cron + tasks) can execute in parallel. Increase if you have multiple providers or a high rate limit.',
     );
     const concurrencyInp = numberInput(config.max_concurrent_runs ?? 4, {
       min: 1,
@@ -441,6 +455,21 @@ export async function loadAdvancedSettings() {
     concurrencyRow.appendChild(concurrencyInp);
     engSection.appendChild(concurrencyRow);
 
+    const limitPolicyRow = formRow(
+      'When All Slots Are Busy',
+      'A new chat either waits for a running agent to finish, or fails straight away. Background tasks always wait.',
+    );
+    const limitPolicySel = selectInput(
+      [
+        { value: 'queue', label: 'Queue the chat' },
+        { value: 'reject', label: 'Reject the chat' },
+      ],
+      config.run_limit_policy ?? 'queue',
+    );
+    limitPolicySel.style.maxWidth = '220px';
+    limitPolicyRow.appendChild(limitPolicySel);
+    engSection.appendChild(limitPolicyRow);
+
     const budgetRow = formRow(
       'Daily Budget (USD)',
       'Estimated daily spend limit. Agent stops when exceeded. Set to 0 to disable.',
@@ -454,6 +483,20 @@ export async function loadAdvancedSettings() {
     budgetRow.appendChild(budgetInp);
     engSection.appendChild(budgetRow);
 
+    const resetRow = formRow(
+      'Budget Reset Hour',
+      'Local hour (0-23) when the daily budget resets, in your timezone. Default: 0 (midnight).',
+    );
+    const resetInp = numberInput(config.budget_reset_hour ?? 0, {
+      min: 0,
+      max: 23,
+      step: 1,
+      placeholder: '0',
+    });
+    resetInp.style.maxWidth = '120px';
+    resetRow.appendChild(resetInp);
+    engSection.appendChild(resetRow);
+
     const contextRow = formRow(
       'Context Window (tokens)',
       'How much conversation history the agent sees. Higher = better topic tracking but more cost per turn. Models support 128K-1M, so this is conservative. Default: 32,000.',
@@ -468,6 +511,25 @@ export async function loadAdvancedSettings() {
     contextRow.appendChild(contextInp);
     engSection.appendChild(contextRow);
 
+    const { container: reasoningToggle, checkbox: reasoningCb } = toggleSwitch(
+      config.show_reasoning ?? true,
+      'Show model reasoning (thinking models) in chat',
+    );
+    engSection.appendChild(reasoningToggle);
+
+    const localeRow = formRow(
+      'Message Language',
+      'Language for errors and status messages, including what channel users see. Agents still work in English behind the scenes.',
+    );
+    const locales = await pawEngine.availableLocales().catch(() => ['en']);
+    const localeSel = selectInput(
+      locales.map((code) => ({ value: code, label: localeLabel(code) })),
+      config.locale ?? 'en',
+    );
+    localeSel.style.maxWidth = '220px';
+    localeRow.appendChild(localeSel);
+    engSection.appendChild(localeRow);
+
     container.appendChild(engSection);
 
     // ── System Prompt ────────────────────────────────────────────────────
@@ -494,10 +556,16 @@ export async function loadAdvancedSettings() {
             cfg.default_model = modelInp.value.trim() || undefined;
             cfg.default_provider = providerSel.value || undefined;
             cfg.max_tool_rounds = parseInt(roundsInp.value) || 20;
+            const iterations = parseInt(iterationsInp.value);
+            cfg.max_tool_iterations = Number.isNaN(iterations) ? 100 : iterations;
             cfg.tool_timeout_secs = parseInt(timeoutInp.value) || 120;
             cfg.max_concurrent_runs = parseInt(concurrencyInp.value) || 4;
+            cfg.run_limit_policy = limitPolicySel.value === 'reject' ? 'reject' : 'queue';
             cfg.daily_budget_usd = parseFloat(budgetInp.value) || 0;
+            cfg.budget_reset_hour = Math.min(Math.max(parseInt(resetInp.value) || 0, 0), 23);
             cfg.context_window_tokens = parseInt(contextInp.value) || 32000;
+            cfg.show_reasoning = reasoningCb.checked;
+            cfg.locale = localeSel.value === 'en' ? undefined : localeSel.value;
             cfg.default_system_prompt = promptArea.value.trim() || undefined;
             await pawEngine.setConfig(cfg);
             showToast('Engine settings saved', 'success');
@@ -3,7 +3,7 @@
 import { pawEngine } from '../../engine';
 import { showToast } from '../../components/toast';
 import { isConnected } from '../../state/connection';
-import type { EmbeddingProvider } from '../../engine/atoms/types';
+import type { EmbeddingProvider, EngineMemoryConfig } from '../../engine/atoms/types';
 import {
   esc,
   formRow,
@@ -228,10 +228,7 @@ export async function loadAgentDefaultsSettings() {
       try {
         // Save current form values first
         const mc = await pawEngine.getMemoryConfig();
-        mc.embedding_provider = embProviderSel.value as EmbeddingProvider;
-        mc.embedding_base_url = embUrlInp.value.trim() || 'http://localhost:11434';
-        mc.embedding_model = embModelInp.value.trim() || 'nomic-embed-text';
-        mc.embedding_dims = parseInt(embDimsInp.value) || 768;
+        applyEmbeddingForm(mc);
         await pawEngine.setMemoryConfig(mc);
 
         const result = await pawEngine.ensureEmbeddingReady();
@@ -262,8 +259,9 @@ export async function loadAgentDefaultsSettings() {
       'Ollama URL',
       'Where Ollama is running (default: http://localhost:11434)',
     );
+    const isOpenAiConfig = memConfig.embedding_provider === 'openai';
     const embUrlInp = textInput(
-      memConfig.embedding_base_url || 'http://localhost:11434',
+      (!isOpenAiConfig && memConfig.embedding_base_url) || 'http://localhost:11434',
       'http://localhost:11434',
     );
     embUrlInp.style.maxWidth = '320px';
@@ -272,7 +270,7 @@ export async function loadAgentDefaultsSettings() {
 
     const embModelRow = formRow('Embedding Model', 'Model for generating embeddings');
     const embModelInp = textInput(
-      memConfig.embedding_model || 'nomic-embed-text',
+      (!isOpenAiConfig && memConfig.embedding_model) || 'nomic-embed-text',
       'nomic-embed-text',
     );
     embModelInp.style.maxWidth = '220px';
@@ -300,7 +298,39 @@ export async function loadAgentDefaultsSettings() {
     ollamaSection.appendChild(modelChipsRow);
     embSection.appendChild(ollamaSection);
 
-    // ── Cloud provider info section (shown for openai/google/provider) ────
+    // ── OpenAI-compatible endpoint section (independent of chat provider) ─
+    const openaiSection = document.createElement('div');
+    openaiSection.dataset.embProvider = 'openai';
+
+    const oaUrlRow = formRow(
+      'Embedding Endpoint',
+      'OpenAI-compatible base URL — requests go to /v1/embeddings',
+    );
+    const oaUrlInp = textInput(
+      (isOpenAiConfig && memConfig.embedding_base_url) || 'https://api.openai.com',
+      'https://api.openai.com',
+    );
+    oaUrlInp.style.maxWidth = '320px';
+    oaUrlRow.appendChild(oaUrlInp);
+    openaiSection.appendChild(oaUrlRow);
+
+    const oaModelRow = formRow('Embedding Model', 'e.g. text-embedding-3-small');
+    const oaModelInp = textInput(
+      (isOpenAiConfig && memConfig.embedding_model) || 'text-embedding-3-small',
+      'text-embedding-3-small',
+    );
+    oaModelInp.style.maxWidth = '220px';
+    oaModelRow.appendChild(oaModelInp);
+    openaiSection.appendChild(oaModelRow);
+
+    const oaKeyRow = formRow('Embedding API Key', 'Leave empty to reuse your chat provider key');
+    const oaKeyInp = textInput(memConfig.embedding_api_key ?? '', 'sk-...', 'password');
+    oaKeyInp.style.maxWidth = '320px';
+    oaKeyRow.appendChild(oaKeyInp);
+    openaiSection.appendChild(oaKeyRow);
+    embSection.appendChild(openaiSection);
+
+    // ── Cloud provider info section (shown for google/provider) ───────────
     const cloudSection = document.createElement('div');
     cloudSection.dataset.embProvider = 'cloud';
     const cloudInfo = document.createElement('div');
@@ -333,6 +363,21 @@ export async function loadAgentDefaultsSettings() {
     embDimsRow.appendChild(embDimsInp);
     embSection.appendChild(embDimsRow);
 
+    /** Copy the embedding form into a memory config (fields depend on provider). */
+    const applyEmbeddingForm = (mc: EngineMemoryConfig) => {
+      const prov = embProviderSel.value as EmbeddingProvider;
+      mc.embedding_provider = prov;
+      if (prov === 'openai') {
+        mc.embedding_base_url = oaUrlInp.value.trim() || 'https://api.openai.com';
+        mc.embedding_model = oaModelInp.value.trim() || 'text-embedding-3-small';
+        mc.embedding_api_key = oaKeyInp.value.trim();
+      } else {
+        mc.embedding_base_url = embUrlInp.value.trim() || 'http://localhost:11434';
+        mc.embedding_model = embModelInp.value.trim() || 'nomic-embed-text';
+      }
+      mc.embedding_dims = parseInt(embDimsInp.value) || 768;
+    };
+
     // Status / test button
     const embStatusRow = document.createElement('div');
     embStatusRow.style.cssText = 'display:flex;align-items:center;gap:8px;margin:10px 0';
@@ -357,10 +402,7 @@ export async function loadAgentDefaultsSettings() {
       try {
         // Save current values first so the test uses them
         const mc = await pawEngine.getMemoryConfig();
-        mc.embedding_provider = embProviderSel.value as EmbeddingProvider;
-        mc.embedding_base_url = embUrlInp.value.trim() || 'http://localhost:11434';
-        mc.embedding_model = embModelInp.value.trim() || 'nomic-embed-text';
-        mc.embedding_dims = parseInt(embDimsInp.value) || 768;
+        applyEmbeddingForm(mc);
         await pawEngine.setMemoryConfig(mc);
 
         const dims = await pawEngine.testEmbedding();
@@ -397,8 +439,10 @@ export async function loadAgentDefaultsSettings() {
     const updateEmbProviderUI = () => {
       const prov = embProviderSel.value;
       const isOllama = prov === 'auto' || prov === 'ollama';
+      const isOpenAi = prov === 'openai';
       ollamaSection.style.display = isOllama ? '' : 'none';
-      cloudSection.style.display = isOllama ? 'none' : '';
+      openaiSection.style.display = isOpenAi ? '' : 'none';
+      cloudSection.style.display = isOllama || isOpenAi ? 'none' : '';
     };
     embProviderSel.addEventListener('change', updateEmbProviderUI);
     updateEmbProviderUI(); // initial state
@@ -458,10 +502,7 @@ export async function loadAgentDefaultsSettings() {
             mc.auto_recall = recallCb.checked;
             mc.auto_capture = captureCb.checked;
             mc.recall_limit = parseInt(recallLimitInp.value) || 5;
-            mc.embedding_provider = embProviderSel.value as EmbeddingProvider;
-            mc.embedding_base_url = embUrlInp.value.trim() || 'http://localhost:11434';
-            mc.embedding_model = embModelInp.value.trim() || 'nomic-embed-text';
-            mc.embedding_dims = parseInt(embDimsInp.value) || 768;
+            applyEmbeddingForm(mc);
             await pawEngine.setMemoryConfig(mc);
 
             showToast('Agent defaults saved', 'success');
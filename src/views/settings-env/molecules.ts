@@ -89,8 +89,8 @@ export async function loadEnvSettings() {
       '<p class="form-hint" style="margin:0 0 8px;font-size:12px;color:var(--text-muted)">Credentials for enabled skills (email, Slack, GitHub, etc.) are managed in Skills settings. Stored encrypted in the local vault.</p>';
 
     try {
-      const skills = await pawEngine.skillsList();
-      const configured = skills.filter((s) => s.configured_credentials.length > 0);
+      const skills = await pawEngine.skillsStatus();
+      const configured = skills.filter((s) => s.credentials.some((c) => c.configured));
 
       if (configured.length === 0) {
         const hint = document.createElement('p');
@@ -100,14 +100,23 @@ export async function loadEnvSettings() {
         skillSection.appendChild(hint);
       } else {
         for (const skill of configured) {
+          const stored = skill.credentials.filter((c) => c.configured).length;
+          const missing = skill.credentials
+            .filter((c) => c.required && !c.configured)
+            .map((c) => c.key);
+          const mcp =
+            skill.mcp_connected === null
+              ? ''
+              : `<span style="color:var(--text-muted);font-size:11px">MCP ${skill.mcp_connected ? 'connected' : 'disconnected'}</span>`;
           const row = document.createElement('div');
           row.style.cssText =
             'display:flex;gap:8px;align-items:center;margin-bottom:4px;padding:6px 0;border-bottom:1px solid var(--border-light, rgba(255,255,255,0.06))';
           row.innerHTML = `
             <span style="font-size:16px">${esc(skill.icon)}</span>
             <span style="font-weight:600;font-size:13px;min-width:80px">${esc(skill.name)}</span>
-            <span style="color:var(--text-muted);font-size:12px">${skill.configured_credentials.length} credential${skill.configured_credentials.length !== 1 ? 's' : ''} stored</span>
-            ${skill.missing_credentials.length > 0 ? `<span style="color:var(--warning);font-size:11px">Missing: ${skill.missing_credentials.join(', ')}</span>` : '<span style="color:var(--success);font-size:11px">Ready</span>'}
+            <span style="color:var(--text-muted);font-size:12px">${stored} credential${stored !== 1 ? 's' : ''} stored</span>
+            ${missing.length > 0 ? `<span style="color:var(--warning);font-size:11px">Missing: ${missing.join(', ')}</span>` : '<span style="color:var(--success);font-size:11px">Ready</span>'}
+            ${mcp}
           `;
           skillSection.appendChild(row);
         }
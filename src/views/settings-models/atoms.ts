@@ -10,6 +10,7 @@ export const PROVIDER_KINDS: Array<{ value: string; label: string }> = [
   { value: 'anthropic', label: 'Anthropic' },
   { value: 'google', label: 'Google' },
   { value: 'azurefoundry', label: 'Azure AI Foundry' },
+  { value: 'azureopenai', label: 'Azure OpenAI' },
   { value: 'deepseek', label: 'DeepSeek' },
   { value: 'grok', label: 'xAI (Grok)' },
   { value: 'mistral', label: 'Mistral' },
@@ -24,6 +25,7 @@ export const DEFAULT_BASE_URLS: Record<string, string> = {
   anthropic: 'https://api.anthropic.com',
   google: 'https://generativelanguage.googleapis.com/v1beta',
   azurefoundry: '',
+  azureopenai: '',
   deepseek: 'https://api.deepseek.com/v1',
   grok: 'https://api.x.ai/v1',
   mistral: 'https://api.mistral.ai/v1',
@@ -127,6 +129,7 @@ export const POPULAR_MODELS: Record<string, string[]> = {
     'Cohere-command-r-plus',
     'AI21-Jamba-1.5-Large',
   ],
+  azureopenai: ['gpt-4o', 'gpt-4o-mini', 'gpt-4.1', 'o4-mini'],
   custom: ['deepseek-chat', 'deepseek-reasoner'],
 };
 
@@ -136,6 +139,7 @@ export const KIND_ICONS: Record<string, string> = {
   anthropic: 'psychology',
   google: 'auto_awesome',
   azurefoundry: 'cloud',
+  azureopenai: 'cloud',
   deepseek: 'explore',
   grok: 'bolt',
   mistral: 'air',
@@ -515,6 +515,12 @@ function buildAddProviderForm(config: EngineConfig): HTMLDivElement {
   modelRow.appendChild(modelInp);
   form.appendChild(modelRow);
 
+  const versionRow = formRow('API Version', 'Azure OpenAI api-version (e.g. 2024-10-21)');
+  const versionInp = textInput('', '2024-10-21');
+  versionInp.style.maxWidth = '200px';
+  versionRow.appendChild(versionInp);
+  form.appendChild(versionRow);
+
   kindSel.addEventListener('change', () => {
     const kind = kindSel.value;
     if (!urlInp.value || Object.values(DEFAULT_BASE_URLS).includes(urlInp.value)) {
@@ -529,11 +535,22 @@ function buildAddProviderForm(config: EngineConfig): HTMLDivElement {
       idInp.placeholder = 'grok-4-1-fast-reasoning';
       const idSub = idRow.querySelector('small');
       if (idSub) idSub.textContent = 'Use the model name as the ID (e.g. grok-4-1-fast-reasoning)';
+    } else if (kind === 'azureopenai') {
+      urlInp.placeholder = 'https://<resource>.openai.azure.com';
+      const sub = urlRow.querySelector('small');
+      if (sub) sub.textContent = 'Your Azure OpenAI resource endpoint';
+      const modelSub = modelRow.querySelector('small');
+      if (modelSub) modelSub.textContent = 'Deployment name — requests for this model go to it';
     } else {
       urlInp.placeholder = DEFAULT_BASE_URLS[kind] ?? '';
       const sub = urlRow.querySelector('small');
       if (sub) sub.textContent = 'Leave blank for default';
     }
+    if (kind !== 'azureopenai') {
+      const modelSub = modelRow.querySelector('small');
+      if (modelSub) modelSub.textContent = 'Optional default model for this provider';
+    }
+    versionRow.style.display = kind === 'azureopenai' ? '' : 'none';
     if (!idInp.value) {
       idInp.value = kind === 'azurefoundry' ? '' : kind;
     }
@@ -577,6 +594,8 @@ function buildAddProviderForm(config: EngineConfig): HTMLDivElement {
       api_key: keyInp.value.trim(),
       base_url: urlInp.value.trim() || undefined,
       default_model: modelInp.value.trim() || undefined,
+      api_version:
+        kindSel.value === 'azureopenai' ? versionInp.value.trim() || undefined : undefined,
     };
     try {
       createBtn.disabled = true;
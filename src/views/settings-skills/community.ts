@@ -225,6 +225,16 @@ export function renderPawzHubSection(): string {
       ${categoryButtons}
     </div>
 
+    <div style="display:flex;gap:6px;align-items:center;margin-top:8px;padding-top:8px;border-top:1px solid var(--border-subtle)">
+      <span style="font-size:11px;color:var(--text-muted);display:flex;align-items:center;gap:4px">${msIcon('link')} Install from URL:</span>
+      <input type="text" class="form-input" id="pawzhub-url-input"
+        placeholder="https://…/pawz-skill.toml or owner/repo/dir"
+        style="flex:1;font-size:12px;padding:4px 10px;border-radius:8px" />
+      <button class="btn btn-ghost btn-sm" id="pawzhub-url-install" style="border-radius:8px;font-size:12px">
+        ${msIcon('download')} Install
+      </button>
+    </div>
+
     <div id="pawzhub-results" style="display:none;margin-top:16px"></div>
   </div>`;
 }
@@ -363,6 +373,36 @@ function wirePawzHubInstallButtons(container: HTMLElement): void {
   });
 }
 
+// ── Install from URL ───────────────────────────────────────────────────────
+
+async function installFromUrl(source: string): Promise<void> {
+  const btn = $('pawzhub-url-install') as HTMLButtonElement | null;
+  if (btn) btn.disabled = true;
+  try {
+    let result = await pawEngine.installCommunitySkill(source);
+    if (!result.installed && result.requires_confirmation) {
+      const needs = [
+        result.credentials.length > 0 ? `credentials (${result.credentials.join(', ')})` : '',
+        result.mcp_server ? `an MCP server (${result.mcp_server})` : '',
+        result.permitted_tools?.length ? `tools (${result.permitted_tools.join(', ')})` : '',
+      ]
+        .filter(Boolean)
+        .join(' and ');
+      const ok = await confirmModal(
+        `"${result.name}" by ${result.author} requests ${needs}. Only install skills from sources you trust. Install?`,
+      );
+      if (!ok) return;
+      result = await pawEngine.installCommunitySkill(source, true);
+    }
+    showToast(`${result.name} v${result.version} installed`, 'success');
+    if (_reloadFn) await _reloadFn();
+  } catch (err) {
+    showToast(`Install failed: ${err}`, 'error');
+  } finally {
+    if (btn) btn.disabled = false;
+  }
+}
+
 // ── PawzHub event binding ──────────────────────────────────────────────────
 
 export function bindPawzHubEvents(): void {
@@ -378,6 +418,14 @@ export function bindPawzHubEvents(): void {
     }
   });
 
+  const urlInput = $('pawzhub-url-input') as HTMLInputElement | null;
+  $('pawzhub-url-install')?.addEventListener('click', () => {
+    if (urlInput?.value.trim()) installFromUrl(urlInput.value.trim());
+  });
+  urlInput?.addEventListener('keydown', (e) => {
+    if (e.key === 'Enter' && urlInput.value.trim()) installFromUrl(urlInput.value.trim());
+  });
+
   // Category buttons
   document.querySelectorAll('.pawzhub-category-btn').forEach((el) => {
     el.addEventListener('click', () => {